@@ -0,0 +1,192 @@
+/*
+    Benchmarks for the parse and generate hot paths.
+
+    Run with: cargo bench
+    Each benchmark reports its throughput in packets per second.
+*/
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use ascii::AsciiString;
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use gtp::MTU;
+
+use gtp::gtp_v1;
+use gtp::gtp_v1::packet::messages::{echo_request as v1_echo_request, g_pdu};
+
+use gtp::gtp_v2;
+use gtp::gtp_v2::packet::messages::{echo_request, create_session_request};
+use gtp::gtp_v2::packet::messages::information_elements::{
+    apn,
+    bearer_context,
+    bearer_qos,
+    ebi,
+    f_teid,
+    imsi,
+    msisdn,
+    pdn_address_allocation,
+    pdn_type,
+    rat_type,
+    recovery,
+    user_location_information,
+};
+use gtp::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+fn create_session_request() -> gtp_v2::packet::Packet {
+    let bearer_context_to_be_created = bearer_context::InformationElement::new(
+        ebi::InformationElement::new(5, 0).unwrap(),
+        bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 10_000_000, 10_000_000, 0, 0, 0).unwrap(),
+        0
+    ).unwrap();
+
+    let mut m = create_session_request::Message::new(
+        rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+        f_teid::InformationElement::new(
+            f_teid::InterfaceType::S11MmeGtpC,
+            0x12345678,
+            Some(Ipv4Addr::new(10,0,0,1)),
+            None,
+            0
+        ).unwrap(),
+        bearer_context_to_be_created,
+        apn::InformationElement::new(AsciiString::from_ascii("awesome.apn.mnc099.mcc505.gprs").unwrap(), 0).unwrap()
+    );
+
+    m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+    m.msisdn = Some(msisdn::InformationElement::new("61123456789", 0).unwrap());
+    m.pdn_type = Some(pdn_type::InformationElement::new(pdn_type::PDNType::IPv4v6, 0).unwrap());
+    m.pdn_address_allocation = Some(
+        pdn_address_allocation::InformationElement::new(
+            pdn_type::PDNType::IPv4v6,
+            Some(Ipv4Addr::new(10,0,0,1)),
+            Some((Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0, 0, 0, 0), 64)),
+            0
+        ).unwrap()
+    );
+    m.uli = Some(
+        user_location_information::InformationElement::for_eutran(PLMN::new(505, 99, true).unwrap(), 0x1234, 0x1234567, 0).unwrap()
+    );
+
+    gtp_v2::packet::Packet::new(gtp_v2::packet::messages::Message::CreateSessionRequest(m))
+}
+
+fn echo_request() -> gtp_v2::packet::Packet {
+    let m = echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap());
+
+    gtp_v2::packet::Packet::new(gtp_v2::packet::messages::Message::EchoRequest(m))
+}
+
+fn bench_gtp_v2(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gtp_v2");
+    group.throughput(Throughput::Elements(1));
+
+    let mut buffer = [0; MTU];
+
+    let mut p = create_session_request();
+    let create_session_request_bytes = p.to_vec().unwrap();
+
+    group.bench_function("generate create session request", |b| {
+        b.iter(|| black_box(p.generate(black_box(&mut buffer))))
+    });
+
+    group.bench_function("parse create session request", |b| {
+        b.iter(|| black_box(gtp_v2::packet::Packet::parse(black_box(&create_session_request_bytes)).is_ok()))
+    });
+
+    let mut t = gtp_v2::packet::template::PacketTemplate::new(create_session_request()).unwrap();
+    let mut sequence_number = 0;
+
+    group.bench_function("generate create session request from template", |b| {
+        b.iter(|| {
+            sequence_number = (sequence_number + 1) & 0x7FFFFF;
+            t.set_sequence_number(sequence_number).unwrap();
+            t.set_f_teid(0, sequence_number).unwrap();
+            black_box(t.generate(black_box(&mut buffer)))
+        })
+    });
+
+    let mut p = echo_request();
+    let echo_request_bytes = p.to_vec().unwrap();
+
+    group.bench_function("generate echo request", |b| {
+        b.iter(|| black_box(p.generate(black_box(&mut buffer))))
+    });
+
+    group.bench_function("parse echo request", |b| {
+        b.iter(|| black_box(gtp_v2::packet::Packet::parse(black_box(&echo_request_bytes)).is_ok()))
+    });
+
+    group.finish();
+}
+
+fn bench_gtp_v1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gtp_v1");
+    group.throughput(Throughput::Elements(1));
+
+    let mut buffer = [0; MTU];
+
+    let mut p = gtp_v1::packet::Packet::new(
+        gtp_v1::packet::messages::Message::GPDU(g_pdu::Message::new(&[0x45; 1400]).unwrap())
+    );
+    p.header.set_teid(0x12345678);
+    let g_pdu_bytes = p.to_vec();
+
+    group.bench_function("generate g-pdu", |b| {
+        b.iter(|| black_box(p.generate(black_box(&mut buffer))))
+    });
+
+    group.bench_function("parse g-pdu", |b| {
+        b.iter(|| black_box(gtp_v1::packet::Packet::parse(black_box(&g_pdu_bytes)).is_some()))
+    });
+
+    let mut p = gtp_v1::packet::Packet::new(
+        gtp_v1::packet::messages::Message::EchoRequest(v1_echo_request::Message::new())
+    );
+    let echo_request_bytes = p.to_vec();
+
+    group.bench_function("generate echo request", |b| {
+        b.iter(|| black_box(p.generate(black_box(&mut buffer))))
+    });
+
+    group.bench_function("parse echo request", |b| {
+        b.iter(|| black_box(gtp_v1::packet::Packet::parse(black_box(&echo_request_bytes)).is_some()))
+    });
+
+    group.finish();
+}
+
+fn bench_gtp_v1_batch(c: &mut Criterion) {
+    // A capture of G-PDUs over many tunnels
+    const BATCH_SIZE: usize = 1024;
+
+    let mut group = c.benchmark_group("gtp_v1 batch");
+    group.throughput(Throughput::Elements(BATCH_SIZE as u64));
+
+    let captures: Vec<Vec<u8>> = (0..BATCH_SIZE as u32).map(|teid| {
+        let mut p = gtp_v1::packet::Packet::new(
+            gtp_v1::packet::messages::Message::GPDU(g_pdu::Message::new(&[0x45; 1400]).unwrap())
+        );
+        p.header.set_teid(teid);
+        p.to_vec()
+    }).collect();
+
+    let buffers: Vec<&[u8]> = captures.iter().map(|c| &c[..]).collect();
+
+    group.bench_function("parse g-pdus one at a time", |b| {
+        b.iter(|| {
+            let packets: Vec<_> = black_box(&buffers).iter().map(|b| gtp_v1::packet::Packet::parse(b)).collect();
+            black_box(packets)
+        })
+    });
+
+    group.bench_function("parse g-pdus as a batch", |b| {
+        b.iter(|| black_box(gtp_v1::packet::Packet::parse_batch(black_box(&buffers))))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gtp_v2, bench_gtp_v1, bench_gtp_v1_batch);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+#![no_main]
+
+use arbitrary::{Arbitrary, Unstructured};
+use libfuzzer_sys::fuzz_target;
+
+use gtp::gtp_v1::packet::Packet;
+use gtp::MTU;
+
+fuzz_target!(|data: &[u8]| {
+    // Larger inputs can generate messages whose length doesn't fit in the 16 bit length field
+    if data.len() > MTU {
+        return;
+    }
+
+    let mut u = Unstructured::new(data);
+
+    if let Ok(mut p) = Packet::arbitrary(&mut u) {
+        let _ = p.dissect();
+
+        // Generated packets are parsed to exercise the parser with packets that are mostly well formed
+        let buffer = p.to_vec();
+
+        let _ = Packet::parse(&buffer);
+    }
+});
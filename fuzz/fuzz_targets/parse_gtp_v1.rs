@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use gtp::gtp_v1::packet::Packet;
+
+// The largest packet that can be generated from a parsed packet. The length field is 16 bits and doesn't
+// include the first 8 octets of the header.
+const MAX_PACKET_LENGTH: usize = 0xFFFF + 8;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((mut p, _pos)) = Packet::parse(data) {
+        let _ = p.dissect();
+
+        // A parsed packet must generate a packet that parses to the same packet
+        let mut buffer = vec![0; MAX_PACKET_LENGTH];
+        let pos = p.generate(&mut buffer);
+
+        let (mut p, _pos) = Packet::parse(&buffer[..pos]).expect("Generated packet failed to parse");
+
+        let mut regenerated = vec![0; MAX_PACKET_LENGTH];
+        let regenerated_pos = p.generate(&mut regenerated);
+
+        assert_eq!(buffer[..pos], regenerated[..regenerated_pos]);
+    }
+});
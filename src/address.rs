@@ -0,0 +1,151 @@
+/* Encoding of the IPv4 and IPv6 addresses carried by IEs
+
+Most IEs that carry an address tell IPv4 from IPv6 by the length of the address (e.g. GSN Address in GTPv1, IP Address
+and the Trace Collection Entity in GTPv2). Some can carry both, as an IPv4 address followed by an IPv6 address, and
+give the length as 4, 16 or 20 (e.g. End User Address). Others have flags for which addresses follow (e.g. F-TEID) and
+use generate_ipv4/parse_ipv4 and generate_ipv6/parse_ipv6 directly.
+*/
+
+use core::convert::TryInto;
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+pub const IPV4_LENGTH: usize = 4;
+pub const IPV6_LENGTH: usize = 16;
+
+pub fn length(address: IpAddr) -> u16 {
+    match address {
+        IpAddr::V4(_) => IPV4_LENGTH as u16,
+        IpAddr::V6(_) => IPV6_LENGTH as u16,
+    }
+}
+
+pub fn generate(address: IpAddr, buffer: &mut[u8]) -> usize {
+    match address {
+        IpAddr::V4(address) => generate_ipv4(address, buffer),
+        IpAddr::V6(address) => generate_ipv6(address, buffer),
+    }
+}
+
+pub fn parse(buffer: &[u8]) -> Option<IpAddr> {
+    // buffer is the whole address so its length gives the address family
+    match buffer.len() {
+        IPV4_LENGTH => parse_ipv4(buffer).map(IpAddr::V4),
+        IPV6_LENGTH => parse_ipv6(buffer).map(IpAddr::V6),
+        _ => None,
+    }
+}
+
+pub fn generate_ipv4(address: Ipv4Addr, buffer: &mut[u8]) -> usize {
+    buffer[..IPV4_LENGTH].copy_from_slice(&address.octets());
+    IPV4_LENGTH
+}
+
+pub fn parse_ipv4(buffer: &[u8]) -> Option<Ipv4Addr> {
+    // Reads the first 4 octets of buffer
+    let octets: [u8; IPV4_LENGTH] = buffer.get(..IPV4_LENGTH)?.try_into().ok()?;
+    Some(Ipv4Addr::from(octets))
+}
+
+pub fn generate_ipv6(address: Ipv6Addr, buffer: &mut[u8]) -> usize {
+    buffer[..IPV6_LENGTH].copy_from_slice(&address.octets());
+    IPV6_LENGTH
+}
+
+pub fn parse_ipv6(buffer: &[u8]) -> Option<Ipv6Addr> {
+    // Reads the first 16 octets of buffer
+    let octets: [u8; IPV6_LENGTH] = buffer.get(..IPV6_LENGTH)?.try_into().ok()?;
+    Some(Ipv6Addr::from(octets))
+}
+
+pub fn dual_stack_length(ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) -> u16 {
+    let mut length = 0;
+
+    if ipv4_address.is_some() {
+        length = length + IPV4_LENGTH as u16;
+    }
+
+    if ipv6_address.is_some() {
+        length = length + IPV6_LENGTH as u16;
+    }
+
+    length
+}
+
+pub fn generate_dual_stack(ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>, buffer: &mut[u8]) -> usize {
+    // The IPv4 address comes first when there are both
+    let mut pos = 0;
+
+    if let Some(address) = ipv4_address {
+        pos = pos + generate_ipv4(address, &mut buffer[pos..]);
+    }
+
+    if let Some(address) = ipv6_address {
+        pos = pos + generate_ipv6(address, &mut buffer[pos..]);
+    }
+
+    pos
+}
+
+pub fn parse_dual_stack(buffer: &[u8]) -> Option<(Option<Ipv4Addr>, Option<Ipv6Addr>)> {
+    // buffer is 0, 4, 16 or 20 octets long for no address, an IPv4 address, an IPv6 address or both
+    match buffer.len() {
+        0 => Some((None, None)),
+        IPV4_LENGTH => Some((parse_ipv4(buffer), None)),
+        IPV6_LENGTH => Some((None, parse_ipv6(buffer))),
+        20 => Some((parse_ipv4(buffer), parse_ipv6(&buffer[IPV4_LENGTH..]))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_parse() {
+        let mut buffer = [0; 20];
+
+        let ipv4 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let ipv6 = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+
+        let pos = generate(ipv4, &mut buffer);
+        assert_eq!(buffer[..pos], [10, 0, 0, 1]);
+        assert_eq!(length(ipv4), 4);
+        assert_eq!(parse(&buffer[..pos]), Some(ipv4));
+
+        let pos = generate(ipv6, &mut buffer);
+        assert_eq!(buffer[..pos], [0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(length(ipv6), 16);
+        assert_eq!(parse(&buffer[..pos]), Some(ipv6));
+
+        assert_eq!(parse(&buffer[..5]), None);
+        assert_eq!(parse(&[]), None);
+
+        assert_eq!(parse_ipv4(&[10, 0, 0]), None);
+        assert_eq!(parse_ipv6(&buffer[..15]), None);
+    }
+
+    #[test]
+    fn test_dual_stack() {
+        let mut buffer = [0; 20];
+
+        let ipv4 = Some(Ipv4Addr::new(10, 0, 0, 1));
+        let ipv6 = Some(Ipv6Addr::LOCALHOST);
+
+        let pos = generate_dual_stack(ipv4, ipv6, &mut buffer);
+        assert_eq!(pos, 20);
+        assert_eq!(dual_stack_length(ipv4, ipv6), 20);
+        assert_eq!(buffer[..pos], [10, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+        assert_eq!(parse_dual_stack(&buffer[..pos]), Some((ipv4, ipv6)));
+
+        assert_eq!(generate_dual_stack(ipv4, None, &mut buffer), 4);
+        assert_eq!(parse_dual_stack(&buffer[..4]), Some((ipv4, None)));
+
+        assert_eq!(generate_dual_stack(None, ipv6, &mut buffer), 16);
+        assert_eq!(parse_dual_stack(&buffer[..16]), Some((None, ipv6)));
+
+        assert_eq!(dual_stack_length(None, None), 0);
+        assert_eq!(parse_dual_stack(&[]), Some((None, None)));
+        assert_eq!(parse_dual_stack(&buffer[..8]), None);
+    }
+}
@@ -0,0 +1,407 @@
+/* Sends a Create Session Request for a default bearer to an SGW or PGW and prints the Create Session Response.
+
+    gtp-csr --imsi imsi --apn apn [--msisdn msisdn] [--plmn mcc-mnc [--tac tac --eci eci]] [--pdn-type type]
+        [--rat rat] [--teid teid] [--ebi ebi] [--qci qci] [-b local address] [-W timeout] [-r retries] peer[:port]
+
+The PDN Type is one of ipv4 (the default), ipv6 or ipv4v6 and the RAT Type is one of eutran (the default), utran,
+geran, wlan, nb-iot, lte-m, nr or a number. The PAA asks the PGW to allocate the addresses. The request is sent again
+with the same Sequence Number (TS 29.274 7.6) every timeout milliseconds until a response arrives or the retries run
+out. The exit status is 0 if the request was accepted, 1 if it was rejected and 2 for anything else. */
+
+use std::env;
+use std::process;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
+
+use std::time::{Duration, Instant};
+
+use ascii::AsciiString;
+
+use gtp::MTU;
+use gtp::gtp_v2::packet::Packet;
+use gtp::gtp_v2::packet::messages::{Message, create_session_request};
+use gtp::gtp_v2::packet::messages::information_elements::{
+    apn,
+    bearer_context,
+    bearer_qos,
+    ebi,
+    f_teid,
+    msisdn,
+    pdn_address_allocation,
+    pdn_type,
+    rat_type,
+    selection_mode,
+    serving_network,
+    user_location_information,
+};
+use gtp::gtp_v2::sequence::SequenceGenerator;
+use gtp::socket::{GtpSocket, GTP_C_PORT};
+
+const USAGE: &str = "usage: gtp-csr --imsi imsi --apn apn [--msisdn msisdn] [--plmn mcc-mnc [--tac tac --eci eci]] \
+    [--pdn-type ipv4|ipv6|ipv4v6] [--rat rat] [--teid teid] [--ebi ebi] [--qci qci] [-b local address] [-W timeout] \
+    [-r retries] peer[:port]";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Options {
+    imsi: String,
+    apn: String,
+    msisdn: Option<String>,
+    plmn: Option<user_location_information::PLMN>,
+    tac: Option<u16>,
+    eci: Option<u32>,
+    pdn_type: pdn_type::PDNType,
+    rat_type: rat_type::RATType,
+    teid: u32,
+    ebi: u8,
+    qci: u8,
+    local_address: IpAddr,
+    timeout: Duration,
+    retries: u32,
+    peer: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut imsi = None;
+    let mut apn = None;
+    let mut peer = None;
+
+    let mut options = Options {
+        imsi: String::new(),
+        apn: String::new(),
+        msisdn: None,
+        plmn: None,
+        tac: None,
+        eci: None,
+        pdn_type: pdn_type::PDNType::IPv4,
+        rat_type: rat_type::RATType::EUTRAN,
+        teid: 1,
+        ebi: 5,
+        qci: 9,
+        local_address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        timeout: Duration::from_millis(3000),
+        retries: 2,
+        peer: String::new(),
+    };
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--imsi" => imsi = Some(value::<String>(arg, args.next())?),
+            "--apn" => apn = Some(value::<String>(arg, args.next())?),
+            "--msisdn" => options.msisdn = Some(value(arg, args.next())?),
+            "--plmn" => options.plmn = Some(value(arg, args.next())?),
+            "--tac" => options.tac = Some(value(arg, args.next())?),
+            "--eci" => options.eci = Some(value(arg, args.next())?),
+            "--pdn-type" => options.pdn_type = parse_pdn_type(&value::<String>(arg, args.next())?)?,
+            "--rat" => options.rat_type = parse_rat_type(&value::<String>(arg, args.next())?)?,
+            "--teid" => options.teid = value(arg, args.next())?,
+            "--ebi" => options.ebi = value(arg, args.next())?,
+            "--qci" => options.qci = value(arg, args.next())?,
+            "-b" => options.local_address = value(arg, args.next())?,
+            "-W" => options.timeout = Duration::from_millis(value(arg, args.next())?),
+            "-r" => options.retries = value(arg, args.next())?,
+            a if a.starts_with('-') => return Err(format!("Unknown option {}", a)),
+            a => peer = Some(a.to_string()),
+        }
+    }
+
+    if options.tac.is_some() != options.eci.is_some() || (options.tac.is_some() && options.plmn.is_none()) {
+        return Err(String::from("The ULI needs --plmn, --tac and --eci"));
+    }
+
+    match (imsi, apn, peer) {
+        (Some(imsi), Some(apn), Some(peer)) => {
+            options.imsi = imsi;
+            options.apn = apn;
+            options.peer = peer;
+            Ok(options)
+        },
+        (None, _, _) => Err(String::from("No IMSI was given")),
+        (_, None, _) => Err(String::from("No APN was given")),
+        (_, _, None) => Err(String::from("No peer was given")),
+    }
+}
+
+fn value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    match value {
+        Some(v) => v.parse().map_err(|_| format!("Invalid value for {} {}", option, v)),
+        None => Err(format!("No value for {}", option)),
+    }
+}
+
+fn parse_pdn_type(s: &str) -> Result<pdn_type::PDNType, String> {
+    match s.to_lowercase().as_str() {
+        "ipv4" => Ok(pdn_type::PDNType::IPv4),
+        "ipv6" => Ok(pdn_type::PDNType::IPv6),
+        "ipv4v6" => Ok(pdn_type::PDNType::IPv4v6),
+        _ => Err(format!("Unknown PDN Type {}", s)),
+    }
+}
+
+fn parse_rat_type(s: &str) -> Result<rat_type::RATType, String> {
+    match s.to_lowercase().as_str() {
+        "eutran" => Ok(rat_type::RATType::EUTRAN),
+        "utran" => Ok(rat_type::RATType::UTRAN),
+        "geran" => Ok(rat_type::RATType::GERAN),
+        "wlan" => Ok(rat_type::RATType::WLAN),
+        "nb-iot" => Ok(rat_type::RATType::EutranNbIot),
+        "lte-m" => Ok(rat_type::RATType::LteM),
+        "nr" => Ok(rat_type::RATType::NR),
+        s => s.parse::<u8>().map(rat_type::RATType::from).map_err(|_| format!("Unknown RAT Type {}", s)),
+    }
+}
+
+fn resolve(peer: &str) -> Result<SocketAddr, String> {
+    // The peer can be given with or without a port
+    let addrs = match peer.to_socket_addrs() {
+        Ok(addrs) => addrs,
+        Err(_) => (peer, GTP_C_PORT).to_socket_addrs().map_err(|e| format!("Couldn't resolve {} {}", peer, e))?,
+    };
+
+    addrs.into_iter().next().ok_or(format!("Couldn't resolve {}", peer))
+}
+
+fn create_session_request(options: &Options, address: Ipv4Addr) -> Result<create_session_request::Message, String> {
+    // address is sent in the Sender F-TEID for Control Plane
+    let apn = match AsciiString::from_ascii(options.apn.as_str()) {
+        Ok(apn) => apn::InformationElement::new(apn, 0)?,
+        Err(_) => return Err(format!("APN isn't ASCII {}", options.apn)),
+    };
+
+    let bearer_context = bearer_context::InformationElement::new(
+        ebi::InformationElement::new(options.ebi, 0)?,
+        bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true)?, bearer_qos::Qci::from(options.qci), 0, 0, 0, 0, 0)?,
+        0
+    )?;
+
+    let mut m = create_session_request::Message::new(
+        rat_type::InformationElement::new(options.rat_type, 0)?,
+        f_teid::InformationElement::new(f_teid::InterfaceType::S11MmeGtpC, options.teid, Some(address), None, 0)?,
+        bearer_context,
+        apn,
+    );
+
+    m.set_imsi(&options.imsi)?;
+    m.selection_mode = Some(selection_mode::InformationElement::new(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified, 0)?);
+    m.pdn_type = Some(pdn_type::InformationElement::new(options.pdn_type, 0)?);
+
+    // Unspecified addresses ask the PGW to allocate them
+    let (ipv4, ipv6) = match options.pdn_type {
+        pdn_type::PDNType::IPv4 => (Some(Ipv4Addr::UNSPECIFIED), None),
+        pdn_type::PDNType::IPv6 => (None, Some((Ipv6Addr::UNSPECIFIED, 64))),
+        _ => (Some(Ipv4Addr::UNSPECIFIED), Some((Ipv6Addr::UNSPECIFIED, 64))),
+    };
+
+    m.pdn_address_allocation = Some(pdn_address_allocation::InformationElement::new(options.pdn_type, ipv4, ipv6, 0)?);
+
+    if let Some(ref msisdn) = options.msisdn {
+        m.msisdn = Some(msisdn::InformationElement::new(msisdn, 0)?);
+    }
+
+    if let Some(plmn) = options.plmn {
+        m.serving_network = Some(serving_network::InformationElement::new(plmn, 0)?);
+
+        if let (Some(tac), Some(eci)) = (options.tac, options.eci) {
+            m.uli = Some(user_location_information::InformationElement::for_eutran(plmn, tac, eci, 0)?);
+        }
+    }
+
+    Ok(m)
+}
+
+fn run(options: &Options) -> Result<Packet, String> {
+    let peer = resolve(&options.peer)?;
+
+    // Any free port. The response is sent to the port the request came from
+    let socket = GtpSocket::builder(options.local_address, 0).bind().map_err(|e| format!("Couldn't bind {}", e))?;
+
+    // Connecting picks the local address that the peer is reached from, which goes in the Sender F-TEID. It also
+    // filters out packets from anyone else
+    socket.socket().connect(peer).map_err(|e| format!("Couldn't connect to {} {}", peer, e))?;
+
+    let address = match socket.local_addr().map_err(|e| e.to_string())?.ip() {
+        IpAddr::V4(address) => address,
+        IpAddr::V6(_) => return Err(String::from("Only IPv4 peers are supported")),
+    };
+
+    let m = create_session_request(options, address)?;
+
+    let mut request = Packet::new_request(Message::CreateSessionRequest(m), &mut SequenceGenerator::new());
+
+    // The SGW's TEID isn't known yet so the request is sent to TEID 0
+    request.header.set_teid(0);
+
+    println!("{}", request.dissect());
+
+    let buffer = request.to_vec()?;
+
+    for _attempt in 0..=options.retries {
+        socket.send_to(&buffer, peer).map_err(|e| format!("Couldn't send to {} {}", peer, e))?;
+
+        if let Some(response) = wait_for_response(&socket, request.header.sequence_number(), options.timeout) {
+            return Ok(response)
+        }
+    }
+
+    Err(format!("No Create Session Response from {}", peer))
+}
+
+fn wait_for_response(socket: &GtpSocket, sequence_number: u32, timeout: Duration) -> Option<Packet> {
+    let mut buffer = [0; MTU];
+    let sent_at = Instant::now();
+
+    loop {
+        let remaining = timeout.checked_sub(sent_at.elapsed())?;
+
+        socket.set_poll_timeout(remaining).ok()?;
+
+        match socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, _src)) => {
+                // Anything that isn't the response to this request is skipped
+                if let Ok((p, _pos)) = Packet::parse(&buffer[..number_of_bytes]) {
+                    if let Message::CreateSessionResponse(_) = p.message {
+                        if p.header.sequence_number() == sequence_number {
+                            return Some(p)
+                        }
+                    }
+                }
+            },
+            // Timed out or the socket failed
+            Err(_) => return None,
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(response) => {
+            println!("{}", response.dissect());
+
+            match response.message.cause() {
+                Some(cause) if cause.cause_code.is_acceptance() => (),
+                _ => process::exit(1),
+            }
+        },
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::UdpSocket;
+    use std::thread;
+
+    use gtp::gtp_v2::packet::messages::information_elements::cause;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    fn new_options(peer: &str) -> Options {
+        parse_args(&args(&format!("--imsi 505013485090404 --apn internet -b 127.0.0.1 -W 1000 {}", peer))).unwrap()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let a = args("--imsi 505013485090404 --apn internet --msisdn 61412345678 --plmn 505-01 --tac 1 --eci 2 \
+            --pdn-type ipv4v6 --rat nr --teid 7 --ebi 6 --qci 8 -W 100 -r 0 10.0.0.1:2123");
+
+        if let Ok(o) = parse_args(&a) {
+            assert_eq!(o.imsi, "505013485090404");
+            assert_eq!(o.apn, "internet");
+            assert_eq!(o.msisdn, Some(String::from("61412345678")));
+            assert_eq!(o.plmn, Some("505-01".parse().unwrap()));
+            assert_eq!((o.tac, o.eci), (Some(1), Some(2)));
+            assert_eq!(o.pdn_type, pdn_type::PDNType::IPv4v6);
+            assert_eq!(o.rat_type, rat_type::RATType::NR);
+            assert_eq!((o.teid, o.ebi, o.qci), (7, 6, 8));
+            assert_eq!(o.timeout, Duration::from_millis(100));
+            assert_eq!(o.retries, 0);
+            assert_eq!(o.peer, "10.0.0.1:2123");
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(parse_args(&args("--apn internet 10.0.0.1")).is_err());
+        assert!(parse_args(&args("--imsi 505013485090404 10.0.0.1")).is_err());
+        assert!(parse_args(&args("--imsi 505013485090404 --apn internet")).is_err());
+        assert!(parse_args(&args("--imsi 505013485090404 --apn internet --tac 1 10.0.0.1")).is_err());
+        assert!(parse_args(&args("--imsi 505013485090404 --apn internet --pdn-type x 10.0.0.1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_rat_type() {
+        assert_eq!(parse_rat_type("EUTRAN"), Ok(rat_type::RATType::EUTRAN));
+        assert_eq!(parse_rat_type("1"), Ok(rat_type::RATType::UTRAN));
+        assert!(parse_rat_type("x").is_err());
+    }
+
+    #[test]
+    fn test_create_session_request() {
+        let mut o = new_options("10.0.0.1");
+        o.plmn = Some("505-01".parse().unwrap());
+        o.tac = Some(1);
+        o.eci = Some(2);
+
+        if let Ok(m) = create_session_request(&o, Ipv4Addr::new(10, 0, 0, 2)) {
+            assert_eq!(m.sender_f_teid_for_control_plane.ipv4_address, Some(Ipv4Addr::new(10, 0, 0, 2)));
+            assert_eq!(m.bearer_contexts_to_be_created[0].eps_bearer_id.eps_bearer_id, 5);
+            assert!(m.serving_network.is_some());
+            assert!(m.uli.is_some());
+            assert!(m.msisdn.is_none());
+        }
+        else {
+            assert!(false);
+        }
+
+        o.imsi = String::from("x");
+        assert!(create_session_request(&o, Ipv4Addr::new(10, 0, 0, 2)).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        // An SGW that drops the first copy of the request and rejects the retransmission
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = peer.local_addr().unwrap();
+
+        let sgw = thread::spawn(move || {
+            let mut buffer = [0; MTU];
+
+            let _ = peer.recv_from(&mut buffer).unwrap();
+            let (n, src) = peer.recv_from(&mut buffer).unwrap();
+
+            let (request, _pos) = Packet::parse(&buffer[..n]).unwrap();
+
+            Packet::reject_with(&request, cause::CauseCode::UserAuthenticationFailed).unwrap().send_to(&peer, src).unwrap();
+        });
+
+        let mut o = new_options(&addr.to_string());
+        o.timeout = Duration::from_millis(200);
+
+        if let Ok(p) = run(&o) {
+            assert_eq!(p.message.cause().unwrap().cause_code, cause::CauseCode::UserAuthenticationFailed);
+        }
+        else {
+            assert!(false);
+        }
+
+        sgw.join().unwrap();
+    }
+}
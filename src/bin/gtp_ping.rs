@@ -0,0 +1,313 @@
+/* Sends Echo Requests to a GTP peer and reports the round trip time of each Echo Response, like ping.
+
+    gtp-ping [-1 | -2] [-c count] [-i interval] [-W timeout] [-b local address] peer[:port]
+
+-1 sends GTPv1 Echo Requests (to port 2152 unless one is given) and -2, the default, sends GTPv2 Echo Requests (to
+port 2123). The interval and timeout are in milliseconds. With a count of 0 it runs until it is killed. */
+
+use std::env;
+use std::process;
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, ToSocketAddrs};
+
+use std::time::{Duration, Instant};
+
+use std::thread;
+
+use gtp::MTU;
+use gtp::gtp_v1;
+use gtp::gtp_v2;
+use gtp::gtp_v2::sequence::SequenceGenerator;
+use gtp::socket::{GtpSocket, GTP_C_PORT, GTP_U_PORT};
+
+const USAGE: &str = "usage: gtp-ping [-1 | -2] [-c count] [-i interval] [-W timeout] [-b local address] peer[:port]";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Options {
+    version: u8,
+    count: u32,
+    interval: Duration,
+    timeout: Duration,
+    local_address: IpAddr,
+    peer: String,
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        version: 2,
+        count: 0,
+        interval: Duration::from_millis(1000),
+        timeout: Duration::from_millis(1000),
+        local_address: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        peer: String::new(),
+    };
+
+    let mut peer = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-1" => options.version = 1,
+            "-2" => options.version = 2,
+            "-c" => options.count = value(arg, args.next())?,
+            "-i" => options.interval = Duration::from_millis(value(arg, args.next())?),
+            "-W" => options.timeout = Duration::from_millis(value(arg, args.next())?),
+            "-b" => options.local_address = value(arg, args.next())?,
+            a if a.starts_with('-') => return Err(format!("Unknown option {}", a)),
+            a => peer = Some(a.to_string()),
+        }
+    }
+
+    match peer {
+        Some(peer) => {
+            options.peer = peer;
+            Ok(options)
+        },
+        None => Err(String::from("No peer was given")),
+    }
+}
+
+fn value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    match value {
+        Some(v) => v.parse().map_err(|_| format!("Invalid value for {} {}", option, v)),
+        None => Err(format!("No value for {}", option)),
+    }
+}
+
+fn resolve(peer: &str, default_port: u16) -> Result<SocketAddr, String> {
+    // The peer can be given with or without a port
+    let addrs = match peer.to_socket_addrs() {
+        Ok(addrs) => addrs,
+        Err(_) => (peer, default_port).to_socket_addrs().map_err(|e| format!("Couldn't resolve {} {}", peer, e))?,
+    };
+
+    addrs.into_iter().next().ok_or(format!("Couldn't resolve {}", peer))
+}
+
+fn echo_request(version: u8, v1_sequence_number: u16, sequence: &mut SequenceGenerator) -> (Vec<u8>, u32) {
+    // The Echo Request and the sequence number that its Echo Response must carry
+    match version {
+        1 => {
+            let mut p = gtp_v1::packet::Packet::new(gtp_v1::packet::messages::Message::EchoRequest(gtp_v1::packet::messages::echo_request::Message::new()));
+            p.header.enable_sequence_number();
+            p.header.set_sequence_number(v1_sequence_number);
+            (p.to_vec(), v1_sequence_number as u32)
+        },
+        _ => {
+            let recovery = gtp_v2::packet::messages::information_elements::recovery::InformationElement::new(0, 0).unwrap();
+            let m = gtp_v2::packet::messages::Message::EchoRequest(gtp_v2::packet::messages::echo_request::Message::new(recovery));
+
+            // An Echo Request always fits in a packet
+            let mut p = gtp_v2::packet::Packet::new_request(m, sequence);
+            (p.to_vec().unwrap(), p.header.sequence_number())
+        }
+    }
+}
+
+fn echo_response_sequence_number(version: u8, buffer: &[u8]) -> Option<u32> {
+    // The sequence number of an Echo Response. Anything else is ignored
+    match version {
+        1 => match gtp_v1::packet::Packet::parse(buffer) {
+            Some((p, _pos)) => match p.message {
+                gtp_v1::packet::messages::Message::EchoResponse(_) => Some(p.header.sequence_number() as u32),
+                _ => None,
+            },
+            None => None,
+        },
+        _ => match gtp_v2::packet::Packet::parse(buffer) {
+            Ok((p, _pos)) => match p.message {
+                gtp_v2::packet::messages::Message::EchoResponse(_) => Some(p.header.sequence_number()),
+                _ => None,
+            },
+            Err(_) => None,
+        },
+    }
+}
+
+fn run(options: &Options) -> Result<bool, String> {
+    // Returns true if any Echo Response was received
+    let default_port = if options.version == 1 { GTP_U_PORT } else { GTP_C_PORT };
+    let peer = resolve(&options.peer, default_port)?;
+
+    // Any free port. A peer answers to the port the request came from
+    let socket = GtpSocket::builder(options.local_address, 0).bind().map_err(|e| format!("Couldn't bind {}", e))?;
+
+    let mut sequence = SequenceGenerator::new();
+    let mut sequence_number: u16 = 0;
+
+    let mut sent = 0;
+    let mut rtts: Vec<Duration> = Vec::new();
+
+    println!("GTPv{} Echo Request to {}", options.version, peer);
+
+    while options.count == 0 || sent < options.count {
+        sequence_number = sequence_number.wrapping_add(1);
+
+        let (buffer, expected) = echo_request(options.version, sequence_number, &mut sequence);
+
+        let sent_at = Instant::now();
+        socket.send_to(&buffer, peer).map_err(|e| format!("Couldn't send to {} {}", peer, e))?;
+        sent = sent + 1;
+
+        match wait_for_response(&socket, options, peer, expected, sent_at) {
+            Some(rtt) => {
+                println!("Echo Response from {}: seq={} time={:.3} ms", peer, expected, rtt.as_secs_f64() * 1000.0);
+                rtts.push(rtt);
+            },
+            None => println!("No Echo Response for seq={}", expected),
+        }
+
+        if options.count == 0 || sent < options.count {
+            thread::sleep(options.interval.saturating_sub(sent_at.elapsed()));
+        }
+    }
+
+    println!("{}", summary(sent, &rtts));
+
+    Ok(!rtts.is_empty())
+}
+
+fn wait_for_response(socket: &GtpSocket, options: &Options, peer: SocketAddr, expected: u32, sent_at: Instant) -> Option<Duration> {
+    let mut buffer = [0; MTU];
+
+    loop {
+        let remaining = options.timeout.checked_sub(sent_at.elapsed())?;
+
+        socket.set_poll_timeout(remaining).ok()?;
+
+        match socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, src)) => {
+                // Late responses to earlier requests and anything from other peers are skipped
+                if src == peer && echo_response_sequence_number(options.version, &buffer[..number_of_bytes]) == Some(expected) {
+                    return Some(sent_at.elapsed())
+                }
+            },
+            // Timed out or the socket failed
+            Err(_) => return None,
+        }
+    }
+}
+
+fn summary(sent: u32, rtts: &[Duration]) -> String {
+    let received = rtts.len() as u32;
+    let loss = if sent == 0 { 0.0 } else { (sent - received) as f64 * 100.0 / sent as f64 };
+
+    let mut s = format!("{} sent, {} received, {:.1}% loss", sent, received, loss);
+
+    if let (Some(min), Some(max)) = (rtts.iter().min(), rtts.iter().max()) {
+        let avg = rtts.iter().sum::<Duration>() / received;
+
+        s = s + &format!(", rtt min/avg/max = {:.3}/{:.3}/{:.3} ms",
+            min.as_secs_f64() * 1000.0, avg.as_secs_f64() * 1000.0, max.as_secs_f64() * 1000.0);
+    }
+
+    s
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+
+    match run(&options) {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::UdpSocket;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        if let Ok(o) = parse_args(&args("-1 -c 3 -i 200 -W 50 -b 127.0.0.1 10.0.0.1")) {
+            assert_eq!(o.version, 1);
+            assert_eq!(o.count, 3);
+            assert_eq!(o.interval, Duration::from_millis(200));
+            assert_eq!(o.timeout, Duration::from_millis(50));
+            assert_eq!(o.local_address, IpAddr::V4(Ipv4Addr::LOCALHOST));
+            assert_eq!(o.peer, "10.0.0.1");
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(parse_args(&args("-c")).is_err());
+        assert!(parse_args(&args("-c x 10.0.0.1")).is_err());
+        assert!(parse_args(&args("-x 10.0.0.1")).is_err());
+        assert!(parse_args(&args("-2")).is_err());
+    }
+
+    #[test]
+    fn test_resolve() {
+        assert_eq!(resolve("127.0.0.1", GTP_C_PORT), Ok("127.0.0.1:2123".parse().unwrap()));
+        assert_eq!(resolve("127.0.0.1:3386", GTP_C_PORT), Ok("127.0.0.1:3386".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(2, &[]), "2 sent, 0 received, 100.0% loss");
+        assert_eq!(summary(2, &[Duration::from_millis(1), Duration::from_millis(3)]),
+            "2 sent, 2 received, 0.0% loss, rtt min/avg/max = 1.000/2.000/3.000 ms");
+    }
+
+    #[test]
+    fn test_run() {
+        for version in [1, 2] {
+            // A peer that answers every Echo Request
+            let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+            let addr = peer.local_addr().unwrap();
+
+            let responder = thread::spawn(move || {
+                let mut buffer = [0; MTU];
+                let (n, src) = peer.recv_from(&mut buffer).unwrap();
+
+                let response = match version {
+                    1 => {
+                        let (request, _pos) = gtp_v1::packet::Packet::parse(&buffer[..n]).unwrap();
+                        let recovery = gtp_v1::packet::messages::information_elements::recovery::InformationElement::new(0);
+                        request.echo_response(recovery).unwrap().to_vec()
+                    },
+                    _ => {
+                        let (request, _pos) = gtp_v2::packet::Packet::parse(&buffer[..n]).unwrap();
+                        let recovery = gtp_v2::packet::messages::information_elements::recovery::InformationElement::new(0, 0).unwrap();
+                        let m = gtp_v2::packet::messages::Message::EchoResponse(gtp_v2::packet::messages::echo_response::Message::new(recovery));
+                        gtp_v2::packet::Packet::new_response(&request, m).to_vec().unwrap()
+                    }
+                };
+
+                peer.send_to(&response, src).unwrap();
+            });
+
+            let options = Options {
+                version,
+                count: 1,
+                interval: Duration::from_millis(0),
+                timeout: Duration::from_millis(1000),
+                local_address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                peer: addr.to_string(),
+            };
+
+            assert_eq!(run(&options), Ok(true));
+
+            responder.join().unwrap();
+        }
+    }
+}
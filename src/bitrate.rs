@@ -0,0 +1,86 @@
+/*
+    Bitrates as used by the AMBR and Bearer QoS IEs.
+
+    GTP encodes bitrates in kbps (1 kbps = 1000 bps). When a value in bps doesn't convert to a whole number of kbps
+    it is rounded up (TS 29.274 8.7 and 8.15).
+*/
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bitrate(u64); // Bits per second
+
+impl Bitrate {
+    pub const fn from_bps(bps: u64) -> Self {
+        Bitrate(bps)
+    }
+
+    pub const fn from_kbps(kbps: u64) -> Self {
+        Bitrate(kbps.saturating_mul(1_000))
+    }
+
+    pub const fn from_mbps(mbps: u64) -> Self {
+        Bitrate(mbps.saturating_mul(1_000_000))
+    }
+
+    pub const fn from_gbps(gbps: u64) -> Self {
+        Bitrate(gbps.saturating_mul(1_000_000_000))
+    }
+
+    pub fn bps(&self) -> u64 {
+        self.0
+    }
+
+    pub fn kbps(&self) -> u64 {
+        // Rounded up
+        self.0.div_ceil(1_000)
+    }
+}
+
+impl fmt::Display for Bitrate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Use the largest unit that the bitrate is a whole number of
+        if self.0 != 0 && self.0.is_multiple_of(1_000_000_000) {
+            write!(f, "{} Gbps", self.0 / 1_000_000_000)
+        }
+        else if self.0 != 0 && self.0.is_multiple_of(1_000_000) {
+            write!(f, "{} Mbps", self.0 / 1_000_000)
+        }
+        else if self.0 != 0 && self.0.is_multiple_of(1_000) {
+            write!(f, "{} kbps", self.0 / 1_000)
+        }
+        else {
+            write!(f, "{} bps", self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_units() {
+        assert_eq!(Bitrate::from_kbps(1).bps(), 1_000);
+        assert_eq!(Bitrate::from_mbps(1).bps(), 1_000_000);
+        assert_eq!(Bitrate::from_gbps(10), Bitrate::from_kbps(10_000_000));
+        assert_eq!(Bitrate::from_kbps(u64::MAX).bps(), u64::MAX);
+    }
+
+    #[test]
+    fn test_kbps_rounding() {
+        assert_eq!(Bitrate::from_bps(0).kbps(), 0);
+        assert_eq!(Bitrate::from_bps(1).kbps(), 1);
+        assert_eq!(Bitrate::from_bps(1_000).kbps(), 1);
+        assert_eq!(Bitrate::from_bps(1_001).kbps(), 2);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(format!("{}", Bitrate::from_bps(0)), "0 bps");
+        assert_eq!(format!("{}", Bitrate::from_bps(1_500)), "1500 bps");
+        assert_eq!(format!("{}", Bitrate::from_kbps(1_500)), "1500 kbps");
+        assert_eq!(format!("{}", Bitrate::from_mbps(100)), "100 Mbps");
+        assert_eq!(format!("{}", Bitrate::from_gbps(10)), "10 Gbps");
+    }
+}
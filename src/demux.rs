@@ -0,0 +1,355 @@
+// Routes the packets received on a socket to a handler for each tunnel. Only the header is read, with
+// gtp_v1::packet::header::peek or gtp_v2::packet::header::peek, so a handler parses as much of the packet as it
+// needs. Packets are routed by (GTP version, TEID) e.g. every session of a node can register its own TEIDs. Packets
+// with no handler for their TEID, and GTPv2 packets without a TEID (e.g. Echo Request), go to the default handler.
+
+use std::collections::HashMap;
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use std::time::Duration;
+
+use crate::MTU;
+use crate::socket;
+use crate::gtp_v1;
+use crate::gtp_v2;
+use crate::gtp_v2::server::{SHUTDOWN_POLL_INTERVAL, ShutdownHandle};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Route {
+    pub version: u8, // 1 for GTPv1-C and GTP-U, 2 for GTPv2-C
+    pub teid: u32,
+}
+
+impl Route {
+    pub fn new(version: u8, teid: u32) -> Self {
+        Route {
+            version,
+            teid,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Received<'a> {
+    pub buffer: &'a [u8], // Everything that was received, including any piggybacked message
+    pub src: SocketAddr,
+    pub version: u8,
+    pub message_type: u8,
+    pub teid: Option<u32>, // Only None for GTPv2 packets without the T flag
+    pub payload_offset: usize, // Offset of the message (the T-PDU for a G-PDU) from the start of buffer
+    pub total_length: usize, // Length of this packet from the start of buffer
+}
+
+impl<'a> Received<'a> {
+    pub fn route(&self) -> Option<Route> {
+        self.teid.map(|teid| Route::new(self.version, teid))
+    }
+
+    pub fn payload(&self) -> &'a [u8] {
+        &self.buffer[self.payload_offset..self.total_length]
+    }
+}
+
+// A handler can return a packet to send back to the source of the packet it was given
+pub type Handler = Box<dyn FnMut(&Received) -> Option<Vec<u8>> + Send>;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DemuxStatistics {
+    pub rx: usize,
+    pub rx_routed: usize, // Sent to the handler registered for their TEID
+    pub rx_default: usize, // Sent to the default handler
+    pub rx_dropped: usize, // No handler for their TEID and no default handler
+    pub rx_invalid: usize, // Not a GTPv1 or GTPv2 header, or the length is past the end of the packet
+}
+
+pub struct Demux {
+    socket: UdpSocket,
+    handlers: HashMap<Route, Handler>,
+    default: Option<Handler>,
+    statistics: DemuxStatistics,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Demux {
+    pub fn new(socket: UdpSocket) -> Self {
+        Demux {
+            socket,
+            handlers: HashMap::new(),
+            default: None,
+            statistics: DemuxStatistics::default(),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> std::io::Result<Self> {
+        Ok(Demux::new(UdpSocket::bind(addr)?))
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        // For sending packets to peers from the same port
+        &self.socket
+    }
+
+    pub fn register<F>(&mut self, route: Route, handler: F)
+        where F: FnMut(&Received) -> Option<Vec<u8>> + Send + 'static {
+        // Replaces any handler already registered for route
+        self.handlers.insert(route, Box::new(handler));
+    }
+
+    pub fn unregister(&mut self, route: Route) -> bool {
+        self.handlers.remove(&route).is_some()
+    }
+
+    pub fn is_registered(&self, route: Route) -> bool {
+        self.handlers.contains_key(&route)
+    }
+
+    pub fn set_default<F>(&mut self, handler: F)
+        where F: FnMut(&Received) -> Option<Vec<u8>> + Send + 'static {
+        self.default = Some(Box::new(handler));
+    }
+
+    pub fn statistics(&self) -> DemuxStatistics {
+        self.statistics
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle::new(self.shutdown.clone())
+    }
+
+    pub fn peek(buffer: &[u8], src: SocketAddr) -> Option<Received<'_>> {
+        // Reads the header of a GTPv1 or GTPv2 packet. GTP' isn't routed as it has no TEID
+        let version = (buffer.first()? >> 5) & 0b111;
+
+        let (message_type, teid, payload_offset, total_length) = match version {
+            1 => {
+                let h = gtp_v1::packet::header::peek(buffer)?;
+                (h.message_type, Some(h.teid), h.payload_offset, h.total_length)
+            },
+            2 => {
+                let h = gtp_v2::packet::header::peek(buffer)?;
+                (h.message_type, h.teid, h.payload_offset, h.total_length)
+            },
+            _ => return None,
+        };
+
+        Some(
+            Received {
+                buffer,
+                src,
+                version,
+                message_type,
+                teid,
+                payload_offset,
+                total_length,
+            }
+        )
+    }
+
+    pub fn handle(&mut self, buffer: &[u8], src: SocketAddr) -> Option<Vec<u8>> {
+        // Routes a single received packet. Returns the packet that should be sent back to src, if any
+        self.statistics.rx = self.statistics.rx + 1;
+
+        let received = match Self::peek(buffer, src) {
+            Some(received) => received,
+            None => {
+                self.statistics.rx_invalid = self.statistics.rx_invalid + 1;
+                return None
+            }
+        };
+
+        let handler = match received.route() {
+            Some(route) => self.handlers.get_mut(&route),
+            None => None,
+        };
+
+        if let Some(handler) = handler {
+            self.statistics.rx_routed = self.statistics.rx_routed + 1;
+            return handler(&received);
+        }
+
+        match self.default.as_mut() {
+            Some(handler) => {
+                self.statistics.rx_default = self.statistics.rx_default + 1;
+                handler(&received)
+            },
+            None => {
+                debug!("Dropping packet for unknown TEID {:?} from {}", received.route(), src);
+                self.statistics.rx_dropped = self.statistics.rx_dropped + 1;
+                None
+            }
+        }
+    }
+
+    pub fn poll(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        // Waits up to timeout for a single packet and routes it. Returns true if a packet was received
+        let mut buffer = [0; MTU];
+
+        socket::set_poll_timeout(&self.socket, timeout)?;
+
+        match self.socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, src_addr)) => {
+                if let Some(response) = self.handle(&buffer[..number_of_bytes], src_addr) {
+                    self.socket.send_to(&response, src_addr)?;
+                }
+                Ok(true)
+            },
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn run(&mut self) -> std::io::Result<()> {
+        // Routes packets until shutdown() is called on a ShutdownHandle or the socket fails
+        while !self.shutdown.load(Ordering::Relaxed) {
+            self.poll(SHUTDOWN_POLL_INTERVAL)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use std::sync::Mutex;
+
+    use crate::gtp_v2::packet::Packet;
+    use crate::gtp_v2::packet::messages::{Message, echo_request};
+    use crate::gtp_v2::packet::messages::information_elements::recovery;
+
+    fn new_demux() -> Demux {
+        Demux::bind("127.0.0.1:0").expect("couldn't bind to address")
+    }
+
+    fn peer() -> SocketAddr {
+        "127.0.0.1:2152".parse().unwrap()
+    }
+
+    fn g_pdu(teid: u32) -> Vec<u8> {
+        let mut buffer = vec![
+            /* Flags */ 0b0011_0000,
+            /* Message Type */ 255,
+            /* Length */ 0x00, 0x02,
+            /* TEID */ 0, 0, 0, 0,
+            /* T-PDU */ 0xAB, 0xCD,
+        ];
+        buffer[4..8].copy_from_slice(&teid.to_be_bytes());
+        buffer
+    }
+
+    fn echo_request() -> Vec<u8> {
+        Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap()))).to_vec().unwrap()
+    }
+
+    #[test]
+    fn test_peek() {
+        let buffer = g_pdu(0x12345678);
+
+        if let Some(r) = Demux::peek(&buffer, peer()) {
+            assert_eq!(r.version, 1);
+            assert_eq!(r.message_type, 255);
+            assert_eq!(r.route(), Some(Route::new(1, 0x12345678)));
+            assert_eq!(r.payload(), [0xAB, 0xCD]);
+        }
+        else {
+            assert!(false);
+        }
+
+        let buffer = echo_request();
+
+        if let Some(r) = Demux::peek(&buffer, peer()) {
+            assert_eq!(r.version, 2);
+            assert_eq!(r.route(), None);
+        }
+        else {
+            assert!(false);
+        }
+
+        // GTP'
+        assert!(Demux::peek(&[0b0000_1110, 1, 0, 0, 0, 0], peer()).is_none());
+        assert!(Demux::peek(&[], peer()).is_none());
+    }
+
+    #[test]
+    fn test_handle() {
+        let mut d = new_demux();
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let r = received.clone();
+        d.register(Route::new(1, 1), move |p| {
+            r.lock().unwrap().push(p.payload().to_vec());
+            Some(vec![1])
+        });
+
+        // Nothing is sent back for an unknown TEID without a default handler
+        assert_eq!(d.handle(&g_pdu(2), peer()), None);
+        assert_eq!(d.statistics().rx_dropped, 1);
+
+        assert_eq!(d.handle(&g_pdu(1), peer()), Some(vec![1]));
+        assert_eq!(*received.lock().unwrap(), [vec![0xAB, 0xCD]]);
+
+        // The same TEID for a different version isn't the same tunnel
+        d.set_default(|_p| Some(vec![0]));
+        assert_eq!(d.handle(&echo_request(), peer()), Some(vec![0]));
+        assert_eq!(d.handle(&g_pdu(2), peer()), Some(vec![0]));
+
+        assert!(d.unregister(Route::new(1, 1)));
+        assert!(!d.is_registered(Route::new(1, 1)));
+        assert_eq!(d.handle(&g_pdu(1), peer()), Some(vec![0]));
+
+        assert_eq!(d.handle(&[0xFF; 4], peer()), None);
+
+        assert_eq!(d.statistics(), DemuxStatistics {
+            rx: 6,
+            rx_routed: 1,
+            rx_default: 3,
+            rx_dropped: 1,
+            rx_invalid: 1,
+        });
+    }
+
+    #[test]
+    fn test_run() {
+        let mut d = new_demux();
+
+        d.register(Route::new(1, 7), |p| Some(p.payload().to_vec()));
+
+        let addr = d.local_addr().unwrap();
+        let shutdown = d.shutdown_handle();
+
+        let demux = thread::spawn(move || {
+            d.run().unwrap();
+            d
+        });
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        peer.send_to(&g_pdu(7), addr).unwrap();
+
+        let mut buffer = [0; MTU];
+        let (n, _src) = peer.recv_from(&mut buffer).unwrap();
+
+        assert_eq!(buffer[..n], [0xAB, 0xCD]);
+
+        shutdown.shutdown();
+
+        let d = demux.join().unwrap();
+
+        assert_eq!(d.statistics().rx_routed, 1);
+    }
+}
@@ -0,0 +1,231 @@
+/* Wireshark style text dissection of packets
+
+Headers, messages and IEs implement Dissect to write their fields to a Dissector. Nested items (e.g. the
+IEs in a message or a grouped IE) are written as a tree with each level indented by 4 spaces e.g.
+
+    GTPv2 Header
+        Version: 2
+        Message Type: EchoRequest (1)
+        ...
+    Echo Request
+        Recovery (3), Length: 1, Instance: 0
+            Restart Counter: 5
+
+diff compares two items by their dissections, e.g. a packet and the same packet after it has been generated and
+parsed (see verify_round_trip on each Packet).
+*/
+
+use core::fmt::{Debug, Display};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+const INDENT: &str = "    ";
+
+pub trait Dissect {
+    fn dissect(&self, d: &mut Dissector);
+}
+
+pub struct Dissector {
+    output: String,
+    depth: usize,
+}
+
+impl Dissector {
+    pub fn new() -> Self {
+        Dissector {
+            output: String::new(),
+            depth: 0,
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.output.push_str(INDENT);
+        }
+
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    pub fn field<T: Display>(&mut self, name: &str, value: T) {
+        self.line(&format!("{}: {}", name, value));
+    }
+
+    pub fn flag(&mut self, name: &str, value: bool) {
+        self.field(name, if value { "True" } else { "False" });
+    }
+
+    pub fn hex(&mut self, name: &str, data: &[u8]) {
+        self.line(&format!("{} ({} octets): {}", name, data.len(), hex_string(data)));
+    }
+
+    pub fn tree<F: FnOnce(&mut Self)>(&mut self, title: &str, f: F) {
+        // Writes title and then the output of f indented one level below it
+        self.line(title);
+
+        self.depth = self.depth + 1;
+        f(self);
+        self.depth = self.depth - 1;
+    }
+
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+
+    pub fn into_string(self) -> String {
+        self.output
+    }
+}
+
+pub fn hex_string(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect::<Vec<String>>().join(" ")
+}
+
+pub fn digits_string(digits: &[u8]) -> String {
+    // Decimal digits (e.g. an IMSI or MCC) as a string. Filler digits (0xF) are skipped
+    digits.iter().filter(|d| **d <= 9).map(|d| d.to_string()).collect()
+}
+
+pub fn dissect<T: Dissect>(item: &T) -> String {
+    let mut d = Dissector::new();
+
+    item.dissect(&mut d);
+
+    d.into_string()
+}
+
+pub fn diff<T: Dissect + Debug + PartialEq>(expected: &T, actual: &T) -> Vec<String> {
+    /* The lines of the dissections that differ. Lines only in expected start with "- " and lines only in actual start
+    with "+ ". Fields that aren't dissected (e.g. spare bits) can still differ so if the dissections are the same the
+    Debug output is compared instead. Empty if expected == actual */
+    if expected == actual {
+        return Vec::new()
+    }
+
+    let differences = diff_lines(&dissect(expected), &dissect(actual));
+
+    if !differences.is_empty() {
+        return differences
+    }
+
+    diff_lines(&format!("{:#?}", expected), &format!("{:#?}", actual))
+}
+
+fn diff_lines(expected: &str, actual: &str) -> Vec<String> {
+    // A line diff from the longest common subsequence of the lines. Dissections are short so O(n * m) is fine
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            }
+            else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut differences = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            i = i + 1;
+            j = j + 1;
+        }
+        else if i < expected.len() && (j == actual.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            differences.push(format!("- {}", expected[i]));
+            i = i + 1;
+        }
+        else {
+            differences.push(format!("+ {}", actual[j]));
+            j = j + 1;
+        }
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Item {
+        value: u8,
+        data: Vec<u8>,
+    }
+
+    impl Dissect for Item {
+        fn dissect(&self, d: &mut Dissector) {
+            d.tree("Item", |d| {
+                d.field("Value", self.value);
+                d.flag("Flag", self.value > 0);
+                d.tree("Nested", |d| {
+                    d.hex("Data", &self.data);
+                });
+            });
+        }
+    }
+
+    #[test]
+    fn test_dissect() {
+        let item = Item {
+            value: 5,
+            data: vec![0x01, 0xAB, 0xFF],
+        };
+
+        assert_eq!(dissect(&item), concat!(
+            "Item\n",
+            "    Value: 5\n",
+            "    Flag: True\n",
+            "    Nested\n",
+            "        Data (3 octets): 01 ab ff\n",
+        ));
+    }
+
+    #[test]
+    fn test_diff() {
+        let item = Item {
+            value: 5,
+            data: vec![0x01, 0xAB, 0xFF],
+        };
+
+        assert!(diff(&item, &item).is_empty());
+
+        let other = Item {
+            value: 6,
+            data: vec![0x01, 0xAB],
+        };
+
+        assert_eq!(diff(&item, &other), vec![
+            "-     Value: 5",
+            "+     Value: 6",
+            "-         Data (3 octets): 01 ab ff",
+            "+         Data (2 octets): 01 ab",
+        ]);
+
+        assert_eq!(diff_lines("a\nb\nc\n", "a\nc\nd\n"), vec!["- b", "+ d"]);
+        assert_eq!(diff_lines("", "a\n"), vec!["+ a"]);
+    }
+
+    #[test]
+    fn test_hex_string() {
+        assert_eq!(hex_string(&[]), "");
+        assert_eq!(hex_string(&[0x00, 0x1F]), "00 1f");
+    }
+
+    #[test]
+    fn test_digits_string() {
+        assert_eq!(digits_string(&[5, 0, 5]), "505");
+        assert_eq!(digits_string(&[0, 1, 0xF]), "01");
+    }
+}
@@ -0,0 +1,29 @@
+/* Support for fuzzing the parsers
+
+With the fuzzing feature enabled the packets, headers, messages and IEs implement arbitrary::Arbitrary so
+structured packets can be generated from fuzzer input. The fuzz targets are in the fuzz directory and are
+run with cargo-fuzz e.g.
+
+    cargo +nightly fuzz run parse_gtp_v2
+
+Values generated this way only have the ranges of their field types (e.g. an instance may be > 0xF) so they
+exercise generate and parse with values that new() would reject.
+*/
+
+use arbitrary::{Result, Unstructured};
+use ascii::{AsciiString, ToAsciiChar};
+
+pub fn arbitrary_ascii_string(u: &mut Unstructured) -> Result<AsciiString> {
+    // AsciiString doesn't implement Arbitrary so build one from the ASCII bytes of an arbitrary byte string
+    let bytes: Vec<u8> = u.arbitrary()?;
+
+    let mut s = AsciiString::new();
+
+    for b in bytes {
+        if let Ok(ch) = b.to_ascii_char() {
+            s.push(ch);
+        }
+    }
+
+    Ok(s)
+}
@@ -0,0 +1,269 @@
+pub mod header;
+pub mod messages;
+
+use core::fmt;
+#[cfg(feature = "std")]
+use std::net::ToSocketAddrs;
+use messages::{
+    Message,
+    MessageTraits,
+};
+
+use crate::dissect::{self, Dissect, Dissector};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// GTP' is used by CDF / CGF nodes (e.g. a GGSN, PGW or SGW) to transfer CDRs to a Charging Gateway as per TS 32.295
+pub const GTP_PRIME_PORT: u16 = 3386;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Packet {
+    pub header: header::Header,
+    pub message: messages::Message
+}
+
+impl Packet {
+    pub fn new(message: Message) -> Self {
+        Packet {
+            header: header::Header::new(message.message_type()),
+            message
+        }
+    }
+
+    pub fn new_response(request: &Packet, message: Message) -> Self {
+        // A response carries the Sequence Number, version and header length of the request it is for
+        let mut p = Packet::new(message);
+
+        p.header.set_sequence_number(request.header.sequence_number());
+
+        // The version of a parsed or generated request is always supported
+        let _ = p.header.set_version(request.header.version());
+
+        if request.header.short_header() {
+            p.header.enable_short_header();
+        }
+        else {
+            p.header.disable_short_header();
+        }
+
+        p
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let (mut h, h_pos) = header::Header::parse(buffer)?;
+
+        let (m, m_pos) = messages::Message::parse(h.message_type(), &buffer[h_pos..])?;
+
+        h.set_payload_length(m.length());
+
+        Some(
+            (
+                Packet {
+                    header: h,
+                    message: m
+                },
+                h_pos + m_pos
+            )
+        )
+    }
+
+    pub fn generate(&mut self, buffer: &mut[u8]) -> usize {
+        let pos = self.header.generate(buffer);
+        let message_length = self.message.generate(&mut buffer[pos..]);
+
+        // The length of the message is only known once it has been generated.
+        // Writing the header length afterwards avoids walking the IEs a second time to calculate it.
+        self.header.set_payload_length(message_length as u16);
+        self.header.generate_length(buffer);
+
+        pos + message_length
+    }
+
+    pub fn generate_into(&mut self, buffer: &mut Vec<u8>) -> usize {
+        // Appends the packet to the end of buffer, growing it to fit. Returns the number of octets appended.
+        let length = self.header.header_length() + self.message.length() as usize;
+
+        let start = buffer.len();
+        buffer.resize(start + length, 0);
+
+        let pos = self.generate(&mut buffer[start..]);
+        buffer.truncate(start + pos);
+
+        pos
+    }
+
+    pub fn to_vec(&mut self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        self.generate_into(&mut buffer);
+
+        buffer
+    }
+
+    #[cfg(feature = "std")]
+    pub fn send_to<A: ToSocketAddrs>(&mut self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {
+        socket.send_to(&self.to_vec(), addr)
+    }
+
+    pub fn dissect(&self) -> String {
+        dissect::dissect(self)
+    }
+
+    #[cfg(any(test, debug_assertions, feature = "strategies"))]
+    pub fn verify_round_trip(&self) -> Result<(), Vec<String>> {
+        /* Generates the packet, parses the bytes back and compares the result with the packet. Returns the
+        differences if they aren't the same, see dissect::diff. Only in debug builds or with the strategies feature */
+        let mut generated = self.clone();
+        let bytes = generated.to_vec();
+
+        match Packet::parse(&bytes) {
+            Some((parsed, pos)) if pos == bytes.len() => {
+                let differences = dissect::diff(&generated, &parsed);
+
+                if differences.is_empty() { Ok(()) } else { Err(differences) }
+            },
+            Some((_parsed, pos)) => Err(alloc::vec![alloc::format!("Only {} of {} octets were parsed", pos, bytes.len())]),
+            None => Err(alloc::vec![alloc::format!("Failed to parse {}", dissect::hex_string(&bytes))]),
+        }
+    }
+}
+
+impl Dissect for Packet {
+    fn dissect(&self, d: &mut Dissector) {
+        self.header.dissect_with_payload_length(self.message.length(), d);
+        self.message.dissect(d);
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", dissect::dissect(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MTU;
+
+    use messages::{MessageType, data_record_transfer_request, data_record_transfer_response};
+    use messages::information_elements::{InformationElementType, cause, data_record_packet, packet_transfer_command::PacketTransferCommand};
+
+    fn new_request() -> Packet {
+        let mut records = data_record_packet::InformationElement::new(data_record_packet::BASIC_ENCODING_RULES, 0x1234);
+        records.push_data_record(&[0xB5, 0x02, 0x80, 0x00]).unwrap();
+
+        let mut p = Packet::new(Message::DataRecordTransferRequest(data_record_transfer_request::Message::send(records, false)));
+        p.header.set_sequence_number(0x0102);
+
+        p
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut p = new_request();
+
+        let pos = p.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0100_1111,
+            /* Message Type */ MessageType::DataRecordTransferRequest as u8,
+            /* Length */ 0x00, 0x0F,
+            /* Sequence Number */ 0x01, 0x02,
+            InformationElementType::PacketTransferCommand as u8, PacketTransferCommand::SendDataRecordPacket as u8,
+            InformationElementType::DataRecordPacket as u8,
+            0, 10, // Length
+            1, // Number of Data Records
+            data_record_packet::BASIC_ENCODING_RULES,
+            0x12, 0x34, // Data Record Format Version
+            0, 4, 0xB5, 0x02, 0x80, 0x00,
+        ]);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut p = new_request();
+        p.header.disable_short_header();
+
+        let buffer = p.to_vec();
+
+        assert_eq!(buffer.len(), 20 + 15);
+        assert_eq!(buffer[..6], [
+            /* Flags */ 0b0100_1110,
+            /* Message Type */ MessageType::DataRecordTransferRequest as u8,
+            /* Length */ 0x00, 0x0F,
+            /* Sequence Number */ 0x01, 0x02,
+        ]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let buffer = new_request().to_vec();
+
+        if let Some((p, pos)) = Packet::parse(&buffer) {
+            assert_eq!(pos, buffer.len());
+            assert_eq!(p.header.sequence_number(), 0x0102);
+            assert_eq!(p.header.length(), 15);
+
+            if let Message::DataRecordTransferRequest(m) = p.message {
+                assert_eq!(m.packet_transfer_command.command, PacketTransferCommand::SendDataRecordPacket);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // A GTPv1 Echo Request isn't GTP'
+        assert!(Packet::parse(&[0b0011_0010, 1, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00]).is_none());
+    }
+
+    #[test]
+    fn test_new_response() {
+        let mut request = new_request();
+        request.header.disable_short_header();
+        request.header.set_version(1).unwrap();
+
+        let mut p = Packet::new_response(
+            &request,
+            Message::DataRecordTransferResponse(
+                data_record_transfer_response::Message::new(cause::REQUEST_ACCEPTED, vec![request.header.sequence_number()]).unwrap()
+            )
+        );
+
+        let buffer = p.to_vec();
+
+        assert_eq!(buffer.len(), 20 + 7);
+        assert_eq!(buffer[..6], [
+            /* Flags */ 0b0010_1110,
+            /* Message Type */ MessageType::DataRecordTransferResponse as u8,
+            /* Length */ 0x00, 0x07,
+            /* Sequence Number */ 0x01, 0x02,
+        ]);
+        assert_eq!(buffer[20..], [
+            InformationElementType::Cause as u8, cause::REQUEST_ACCEPTED,
+            InformationElementType::RequestsResponded as u8,
+            0, 2, // Length
+            0x01, 0x02,
+        ]);
+    }
+
+    #[test]
+    fn test_dissect() {
+        let p = new_request();
+
+        let output = p.dissect();
+
+        assert!(output.starts_with("GTP' Header\n"));
+        assert!(output.contains("    Sequence Number: 0x0102\n"));
+        assert!(output.contains("DataRecordTransferRequest (240), Length: 15\n"));
+        assert!(output.contains("        Data Record (4 octets): b5 02 80 00\n"));
+    }
+}
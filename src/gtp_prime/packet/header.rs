@@ -0,0 +1,308 @@
+use crate::field::*;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::messages::MessageType;
+
+use core::convert::TryFrom;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+/*
+                                        Bits
+            |---------------------------------------------------------------|
+    Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+    (index) |---------------------------------------------------------------|
+    1 (0)   | Version               | PT    | Spare (1 1 1)         | HL    |
+    2 (1)   | Message Type                                                  |
+    3 (2)   | Length (1st Octet)                                            |
+    4 (3)   | Length (2nd Octet)                                            |
+    5 (4)   | Sequence Number (1st Octet)                                   |
+    6 (5)   | Sequence Number (2nd Octet)                                   |
+    7 (6)   | Spare (only present in the 20 octet header)                   |
+    ...     |                                                               |
+    20 (19) |                                                               |
+            |---------------------------------------------------------------|
+
+    HL is the Header Length flag. 0 = 20 octet header, 1 = 6 octet header.
+    The 20 octet header keeps GTP' compatible with the GTPv0 header. Its spare octets are written as all ones
+    and are ignored when parsing.
+*/
+
+pub const LENGTH: Field = 2..4;
+pub const SEQUENCE_NUMBER: Field = 4..6;
+
+pub const SHORT_HEADER_LENGTH: usize = 6;
+pub const LONG_HEADER_LENGTH: usize = 20;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Header {
+    version: u8, // GTP' version 0, 1 or 2
+    message_type: MessageType,
+    pt: u8, // Protocol Type: 0 = GTP', 1 = GTP
+    hl: u8, // Header Length Flag: 0 = 20 octets, 1 = 6 octets
+    payload_length: u16, /* This is the length of any payload associated with the packet that
+        this header is attached to */
+    sequence_number: u16, /* Identifies the Data Record Transfer Request that a Data Record Transfer
+        Response is for. It is also the sequence number that is cancelled or released in later requests */
+}
+
+impl Header {
+    pub fn new(message_type: MessageType) -> Self {
+        Header {
+            version: 2,
+            message_type,
+            pt: 0,
+            hl: 1,
+            payload_length: 0,
+            sequence_number: 0,
+        }
+    }
+
+    pub fn message_type(&self) -> MessageType {
+        self.message_type
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: u8) -> Result<u8, String> {
+        if version > 2 {
+            Err(format!("Unsupported GTP' version ({})", version))
+        }
+        else {
+            self.version = version;
+            Ok(self.version)
+        }
+    }
+
+    pub fn header_length(&self) -> usize {
+        // The number of octets in the header
+        if self.hl == 1 {
+            SHORT_HEADER_LENGTH
+        }
+        else {
+            LONG_HEADER_LENGTH
+        }
+    }
+
+    pub fn enable_short_header(&mut self) {
+        self.hl = 1;
+    }
+
+    pub fn disable_short_header(&mut self) {
+        self.hl = 0;
+    }
+
+    pub fn short_header(&self) -> bool {
+        self.hl == 1
+    }
+
+    pub fn length(&self) -> u16 {
+        // Length of the payload in octets. Unlike GTPv1 the header isn't included at all
+        self.payload_length
+    }
+
+    pub fn set_payload_length(&mut self, payload_length: u16) {
+        self.payload_length = payload_length;
+    }
+
+    pub fn set_sequence_number(&mut self, sequence_number: u16) {
+        self.sequence_number = sequence_number;
+    }
+
+    pub fn sequence_number(&self) -> u16 {
+        self.sequence_number
+    }
+
+    pub fn generate_length(&self, buffer: &mut[u8]) {
+        // Rewrites only the length field of a header that has already been generated into buffer.
+        // This lets the length be filled in after the payload has been generated.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length());
+    }
+
+    fn generate_flags(&self) -> u8 {
+        (self.version << 5) | (self.pt << 4) | (0b111 << 1) | self.hl
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        buffer[0] = self.generate_flags();
+
+        buffer[1] = self.message_type as u8;
+
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length());
+
+        NetworkEndian::write_u16(&mut buffer[SEQUENCE_NUMBER], self.sequence_number);
+
+        let mut pos: usize = SHORT_HEADER_LENGTH;
+
+        while pos < self.header_length() {
+            buffer[pos] = 0xFF;
+            pos = pos + 1;
+        }
+
+        pos
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < SHORT_HEADER_LENGTH {
+            return None
+        }
+
+        let version = (buffer[0] >> 5) & 0b111;
+        let pt = (buffer[0] >> 4) & 0b1;
+        let hl = buffer[0] & 0b1;
+
+        if version > 2 {
+            // The packet isn't a GTP' packet
+            return None;
+        }
+
+        if pt != 0 {
+            // GTP packets are parsed by gtp_v1
+            return None;
+        }
+
+        let message_type = match MessageType::try_from(buffer[1]) {
+            Ok(message_type) => message_type,
+            Err(_) => return None,
+        };
+
+        let mut h = Self::new(message_type);
+
+        h.version = version;
+        h.hl = hl;
+
+        if buffer.len() < h.header_length() {
+            return None
+        }
+
+        let _length = NetworkEndian::read_u16(&buffer[LENGTH]);
+
+        h.set_sequence_number(NetworkEndian::read_u16(&buffer[SEQUENCE_NUMBER]));
+
+        let pos = h.header_length();
+
+        Some((h, pos))
+    }
+
+    pub fn dissect_with_payload_length(&self, payload_length: u16, d: &mut Dissector) {
+        // The payload length is only updated when the packet is generated or parsed so the caller supplies it
+        d.tree("GTP' Header", |d| {
+            d.field("Version", self.version);
+            d.field("Protocol Type (PT)", self.pt);
+            d.field("Header Length", self.header_length());
+            d.field("Message Type", format!("{:?} ({})", self.message_type, self.message_type as u8));
+            d.field("Length", payload_length);
+            d.field("Sequence Number", format!("{:#06x}", self.sequence_number));
+        });
+    }
+}
+
+impl Dissect for Header {
+    fn dissect(&self, d: &mut Dissector) {
+        self.dissect_with_payload_length(self.payload_length, d);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::MTU;
+
+    use crate::gtp_prime::packet::messages::MessageType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut h = Header::new(MessageType::DataRecordTransferRequest);
+
+        h.set_sequence_number(0x1234);
+        h.set_payload_length(10);
+
+        let pos = h.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0100_1111,
+            /* Message Type */ MessageType::DataRecordTransferRequest as u8,
+            /* Length */ 0x00, 0x0A,
+            /* Sequence Number */ 0x12, 0x34
+            ]);
+
+        h.disable_short_header();
+        h.set_version(0).unwrap();
+
+        let pos = h.generate(&mut buffer);
+
+        assert_eq!(pos, 20);
+        assert_eq!(buffer[..6], [
+            /* Flags */ 0b0000_1110,
+            /* Message Type */ MessageType::DataRecordTransferRequest as u8,
+            /* Length */ 0x00, 0x0A,
+            /* Sequence Number */ 0x12, 0x34
+            ]);
+        assert_eq!(buffer[6..20], [0xFF; 14]);
+    }
+
+    #[test]
+    fn test_set_version() {
+        let mut h = Header::new(MessageType::DataRecordTransferRequest);
+
+        assert_eq!(h.set_version(1), Ok(1));
+        assert!(h.set_version(3).is_err());
+        assert_eq!(h.version(), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        let header_bytes = [
+            /* Flags */ 0b0100_1111,
+            /* Message Type */ MessageType::DataRecordTransferResponse as u8,
+            /* Length */ 0x00, 0x0A,
+            /* Sequence Number */ 0x12, 0x34
+        ];
+
+        if let Some((h, pos)) = Header::parse(&header_bytes) {
+            assert_eq!(pos, 6);
+            assert_eq!(h.version(), 2);
+            assert!(h.short_header());
+            assert_eq!(h.message_type() as u8, MessageType::DataRecordTransferResponse as u8);
+            assert_eq!(h.sequence_number(), 0x1234);
+        }
+        else {
+            assert!(false);
+        }
+
+        // The 20 octet header needs all 20 octets
+        let mut header_bytes = [0xFF; 20];
+        header_bytes[..6].copy_from_slice(&[0b0010_1110, MessageType::DataRecordTransferRequest as u8, 0x00, 0x00, 0x00, 0x01]);
+
+        if let Some((h, pos)) = Header::parse(&header_bytes) {
+            assert_eq!(pos, 20);
+            assert_eq!(h.version(), 1);
+            assert!(!h.short_header());
+            assert_eq!(h.sequence_number(), 1);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Header::parse(&header_bytes[..19]).is_none());
+    }
+
+    #[test]
+    fn test_parse_not_gtp_prime() {
+        // GTPv1 (PT = 1)
+        assert!(Header::parse(&[0b0011_0000, MessageType::DataRecordTransferRequest as u8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).is_none());
+
+        // Unsupported version
+        assert!(Header::parse(&[0b0110_1111, MessageType::DataRecordTransferRequest as u8, 0x00, 0x00, 0x00, 0x00]).is_none());
+    }
+}
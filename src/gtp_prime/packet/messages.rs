@@ -0,0 +1,109 @@
+pub mod data_record_transfer_request;
+pub mod data_record_transfer_response;
+pub mod information_elements;
+
+use core::convert::TryFrom;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum MessageType
+{
+    DataRecordTransferRequest = 240,
+    DataRecordTransferResponse = 241,
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = String;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        match v {
+            240 => Ok(MessageType::DataRecordTransferRequest),
+            241 => Ok(MessageType::DataRecordTransferResponse),
+            _ => Err(format!("Unsupported Message Type ({})", v))
+        }
+    }
+}
+
+pub trait MessageTraits {
+    fn message_type(&self) -> MessageType;
+    fn length(&self) -> u16;
+    fn generate(&self, buffer: &mut[u8]) -> usize;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Message {
+    DataRecordTransferRequest(data_record_transfer_request::Message),
+    DataRecordTransferResponse(data_record_transfer_response::Message),
+}
+
+impl Message {
+    pub fn parse(message_type: MessageType, buffer: &[u8]) -> Option<(Self, usize)> {
+        match message_type {
+            MessageType::DataRecordTransferRequest => {
+                let (m, pos) = data_record_transfer_request::Message::parse(buffer)?;
+                Some((Message::DataRecordTransferRequest(m), pos))
+            },
+            MessageType::DataRecordTransferResponse => {
+                let (m, pos) = data_record_transfer_response::Message::parse(buffer)?;
+                Some((Message::DataRecordTransferResponse(m), pos))
+            },
+        }
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        match self {
+            Message::DataRecordTransferRequest(m) => m.message_type(),
+            Message::DataRecordTransferResponse(m) => m.message_type(),
+        }
+    }
+
+    fn length(&self) -> u16 {
+        match self {
+            Message::DataRecordTransferRequest(m) => m.length(),
+            Message::DataRecordTransferResponse(m) => m.length(),
+        }
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        match self {
+            Message::DataRecordTransferRequest(m) => m.generate(buffer),
+            Message::DataRecordTransferResponse(m) => m.generate(buffer),
+        }
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            Message::DataRecordTransferRequest(m) => m.dissect(d),
+            Message::DataRecordTransferResponse(m) => m.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_message<T: MessageTraits, F: FnOnce(&mut Dissector)>(m: &T, d: &mut Dissector, f: F) {
+    // Writes the message type followed by the IEs of the message written by f
+    let message_type = m.message_type();
+
+    d.tree(&format!("{:?} ({}), Length: {}", message_type, message_type as u8, m.length()), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(MessageType::try_from(240), Ok(MessageType::DataRecordTransferRequest));
+        assert_eq!(MessageType::try_from(241), Ok(MessageType::DataRecordTransferResponse));
+        assert!(MessageType::try_from(1).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+use super::{MessageTraits, MessageType, dissect_message};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    packet_transfer_command::{self, PacketTransferCommand},
+    data_record_packet,
+    sequence_numbers,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Packet Transfer Command                     |    Mandatory              |
+        Data Record Packet                          |    Conditional            |
+        Sequence Numbers of Released Packets        |    Conditional            |
+        Sequence Numbers of Cancelled Packets       |    Conditional            |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+
+        The Data Record Packet is present when sending (possibly duplicated) records. The Sequence Numbers of
+        Released / Cancelled Packets are present when releasing / cancelling packets sent earlier as possibly
+        duplicated.
+    */
+    pub packet_transfer_command: packet_transfer_command::InformationElement,
+    pub sequence_numbers_of_released_packets: Option<sequence_numbers::InformationElement>,
+    pub sequence_numbers_of_cancelled_packets: Option<sequence_numbers::InformationElement>,
+    pub data_record_packet: Option<data_record_packet::InformationElement>,
+}
+
+impl Message {
+    pub fn new(command: PacketTransferCommand) -> Self {
+        Message {
+            packet_transfer_command: packet_transfer_command::InformationElement::new(command),
+            sequence_numbers_of_released_packets: None,
+            sequence_numbers_of_cancelled_packets: None,
+            data_record_packet: None,
+        }
+    }
+
+    pub fn send(data_record_packet: data_record_packet::InformationElement, possibly_duplicated: bool) -> Self {
+        let command = if possibly_duplicated {
+            PacketTransferCommand::SendPossiblyDuplicatedDataRecordPacket
+        }
+        else {
+            PacketTransferCommand::SendDataRecordPacket
+        };
+
+        let mut m = Message::new(command);
+        m.data_record_packet = Some(data_record_packet);
+        m
+    }
+
+    pub fn release(sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        let mut m = Message::new(PacketTransferCommand::ReleaseDataRecordPacket);
+        m.sequence_numbers_of_released_packets = Some(sequence_numbers::InformationElement::released(sequence_numbers)?);
+        Ok(m)
+    }
+
+    pub fn cancel(sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        let mut m = Message::new(PacketTransferCommand::CancelDataRecordPacket);
+        m.sequence_numbers_of_cancelled_packets = Some(sequence_numbers::InformationElement::cancelled(sequence_numbers)?);
+        Ok(m)
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut packet_transfer_command: Option<packet_transfer_command::InformationElement> = None;
+        let mut sequence_numbers_of_released_packets: Option<sequence_numbers::InformationElement> = None;
+        let mut sequence_numbers_of_cancelled_packets: Option<sequence_numbers::InformationElement> = None;
+        let mut data_record_packet: Option<data_record_packet::InformationElement> = None;
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                match ie {
+                    InformationElement::PacketTransferCommand(ie) => packet_transfer_command = Some(ie),
+                    InformationElement::SequenceNumbers(ie) => {
+                        match ie.information_element_type() {
+                            InformationElementType::SequenceNumbersOfReleasedPackets => sequence_numbers_of_released_packets = Some(ie),
+                            InformationElementType::SequenceNumbersOfCancelledPackets => sequence_numbers_of_cancelled_packets = Some(ie),
+                            _ => { /* Its an IE that we didn't expect. Just ignore it */ }
+                        }
+                    },
+                    InformationElement::DataRecordPacket(ie) => data_record_packet = Some(ie),
+                    _ => { /* Its an IE that we didn't expect. Just ignore it */ }
+                }
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support e.g. Private Extension
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    packet_transfer_command: packet_transfer_command?,
+                    sequence_numbers_of_released_packets,
+                    sequence_numbers_of_cancelled_packets,
+                    data_record_packet,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DataRecordTransferRequest
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.packet_transfer_command.length();
+
+        if let Some(ref ie) = self.sequence_numbers_of_released_packets {
+            length = length + ie.length();
+        }
+
+        if let Some(ref ie) = self.sequence_numbers_of_cancelled_packets {
+            length = length + ie.length();
+        }
+
+        if let Some(ref ie) = self.data_record_packet {
+            length = length + ie.length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        // IEs are written in order of IE Type
+        let mut pos = 0;
+
+        pos = pos + self.packet_transfer_command.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.sequence_numbers_of_released_packets {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sequence_numbers_of_cancelled_packets {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.data_record_packet {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.packet_transfer_command.dissect(d);
+
+            if let Some(ref ie) = self.sequence_numbers_of_released_packets {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sequence_numbers_of_cancelled_packets {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.data_record_packet {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    fn new_data_record_packet() -> data_record_packet::InformationElement {
+        let mut ie = data_record_packet::InformationElement::new(data_record_packet::BASIC_ENCODING_RULES, 0x1234);
+        ie.push_data_record(&[0xB5, 0x02, 0x80, 0x00]).unwrap();
+        ie
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::send(new_data_record_packet(), false);
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::PacketTransferCommand as u8, PacketTransferCommand::SendDataRecordPacket as u8,
+            InformationElementType::DataRecordPacket as u8,
+            0, 10, // Length
+            1, // Number of Data Records
+            data_record_packet::BASIC_ENCODING_RULES,
+            0x12, 0x34, // Data Record Format Version
+            0, 4, 0xB5, 0x02, 0x80, 0x00,
+        ]);
+
+        let m = Message::release(vec![0x0102, 0x0304]).unwrap();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::PacketTransferCommand as u8, PacketTransferCommand::ReleaseDataRecordPacket as u8,
+            InformationElementType::SequenceNumbersOfReleasedPackets as u8,
+            0, 4, // Length
+            0x01, 0x02, 0x03, 0x04,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = Message::send(new_data_record_packet(), true);
+        assert_eq!(m.length(), 2 + 13);
+
+        let m = Message::cancel(vec![1]).unwrap();
+        assert_eq!(m.length(), 2 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new(PacketTransferCommand::SendDataRecordPacket);
+        assert_eq!(m.message_type() as u8, MessageType::DataRecordTransferRequest as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let message_bytes = [
+            InformationElementType::PacketTransferCommand as u8, PacketTransferCommand::SendPossiblyDuplicatedDataRecordPacket as u8,
+            0xFF, 0, 3, 0x00, 0x01, 0xAA, // Private Extension
+            InformationElementType::DataRecordPacket as u8,
+            0, 10, // Length
+            1, // Number of Data Records
+            data_record_packet::BASIC_ENCODING_RULES,
+            0x12, 0x34, // Data Record Format Version
+            0, 4, 0xB5, 0x02, 0x80, 0x00,
+        ];
+
+        if let Some((m, pos)) = Message::parse(&message_bytes) {
+            assert_eq!(pos, message_bytes.len());
+            assert_eq!(m.packet_transfer_command.command, PacketTransferCommand::SendPossiblyDuplicatedDataRecordPacket);
+
+            if let Some(ie) = m.data_record_packet {
+                assert_eq!(ie.data_records(), &[vec![0xB5, 0x02, 0x80, 0x00]]);
+            }
+            else {
+                assert!(false);
+            }
+
+            assert!(m.sequence_numbers_of_released_packets.is_none());
+        }
+        else {
+            assert!(false);
+        }
+
+        let message_bytes = [
+            InformationElementType::PacketTransferCommand as u8, PacketTransferCommand::CancelDataRecordPacket as u8,
+            InformationElementType::SequenceNumbersOfCancelledPackets as u8,
+            0, 2, // Length
+            0x12, 0x34,
+        ];
+
+        if let Some((m, _pos)) = Message::parse(&message_bytes) {
+            assert_eq!(m.packet_transfer_command.command, PacketTransferCommand::CancelDataRecordPacket);
+            assert_eq!(m.sequence_numbers_of_cancelled_packets.map(|ie| ie.sequence_numbers), Some(vec![0x1234]));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::SequenceNumbersOfCancelledPackets as u8,
+            0, 2, // Length
+            0x12, 0x34,
+        ];
+
+        assert!(Message::parse(&message_bytes).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use super::{MessageTraits, MessageType, dissect_message};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    sequence_numbers,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |
+        Requests Responded                          |    Mandatory              |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+
+        Requests Responded lists the Sequence Numbers of the Data Record Transfer Requests this response is for.
+    */
+    pub cause: cause::InformationElement,
+    pub requests_responded: sequence_numbers::InformationElement,
+}
+
+impl Message {
+    pub fn new(cause: u8, requests_responded: Vec<u16>) -> Result<Self, String> {
+        Ok(
+            Message {
+                cause: cause::InformationElement::new(cause),
+                requests_responded: sequence_numbers::InformationElement::requests_responded(requests_responded)?,
+            }
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut requests_responded: Option<sequence_numbers::InformationElement> = None;
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::SequenceNumbers(ie) if ie.information_element_type() == InformationElementType::RequestsResponded => requests_responded = Some(ie),
+                    _ => { /* Its an IE that we didn't expect. Just ignore it */ }
+                }
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support e.g. Private Extension
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    cause: cause?,
+                    requests_responded: requests_responded?,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DataRecordTransferResponse
+    }
+
+    fn length(&self) -> u16 {
+        self.cause.length() + self.requests_responded.length()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.cause.generate(&mut buffer[pos..]);
+
+        pos = pos + self.requests_responded.generate(&mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+            self.requests_responded.dissect(d);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::new(cause::REQUEST_ACCEPTED, vec![0x1234]).unwrap();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8, cause::REQUEST_ACCEPTED,
+            InformationElementType::RequestsResponded as u8,
+            0, 2, // Length
+            0x12, 0x34,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = Message::new(cause::REQUEST_ACCEPTED, vec![1, 2]).unwrap();
+        assert_eq!(m.length(), 2 + 7);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new(cause::REQUEST_ACCEPTED, vec![1]).unwrap();
+        assert_eq!(m.message_type() as u8, MessageType::DataRecordTransferResponse as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let message_bytes = [
+            InformationElementType::Cause as u8, cause::REQUEST_ALREADY_FULFILLED,
+            InformationElementType::RequestsResponded as u8,
+            0, 4, // Length
+            0x12, 0x34,
+            0x12, 0x35,
+        ];
+
+        if let Some((m, pos)) = Message::parse(&message_bytes) {
+            assert_eq!(pos, message_bytes.len());
+            assert_eq!(m.cause.cause(), cause::REQUEST_ALREADY_FULFILLED);
+            assert_eq!(m.requests_responded.sequence_numbers, vec![0x1234, 0x1235]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        assert!(Message::parse(&[InformationElementType::Cause as u8, cause::REQUEST_ACCEPTED]).is_none());
+    }
+}
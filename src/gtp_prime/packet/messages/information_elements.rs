@@ -0,0 +1,220 @@
+pub mod cause;
+pub mod packet_transfer_command;
+pub mod data_record_packet;
+pub mod sequence_numbers;
+
+use core::convert::{TryFrom, TryInto};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum InformationElementType
+{
+    Cause = 1,
+    PacketTransferCommand = 126,
+    SequenceNumbersOfReleasedPackets = 249,
+    SequenceNumbersOfCancelledPackets = 250,
+    DataRecordPacket = 252,
+    RequestsResponded = 253,
+}
+
+impl TryFrom<u8> for InformationElementType
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(InformationElementType::Cause),
+            126 => Ok(InformationElementType::PacketTransferCommand),
+            249 => Ok(InformationElementType::SequenceNumbersOfReleasedPackets),
+            250 => Ok(InformationElementType::SequenceNumbersOfCancelledPackets),
+            252 => Ok(InformationElementType::DataRecordPacket),
+            253 => Ok(InformationElementType::RequestsResponded),
+            _ => Err(format!("Unsupported IE type ({})", value))
+        }
+    }
+}
+
+// Length of the TV format IEs (including the type octet) from TS 32.295.
+// IE types >= 128 are TLV format and carry their own length.
+fn tv_length(ie_type: u8) -> Option<usize> {
+    match ie_type {
+        1 => Some(2),   // Cause
+        14 => Some(2),  // Recovery
+        126 => Some(2), // Packet Transfer Command
+        _ => None
+    }
+}
+
+pub trait InformationElementTraits {
+    fn length(&self) -> u16;
+    fn generate(&self, buffer: &mut[u8]) -> usize;
+    fn information_element_type(&self) -> InformationElementType;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum InformationElement
+{
+    Cause(cause::InformationElement),
+    PacketTransferCommand(packet_transfer_command::InformationElement),
+    SequenceNumbers(sequence_numbers::InformationElement),
+    DataRecordPacket(data_record_packet::InformationElement),
+}
+
+impl InformationElement {
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        // First we parse the first byte to determine the type of the IE.
+        // Then we pass off the parsing to the specific IE implementation
+
+        if buffer.is_empty() {
+            return None
+        }
+
+        let ie_type = buffer[0];
+
+        if let Ok(ie_type) = ie_type.try_into() {
+            match ie_type {
+                InformationElementType::Cause => {
+                    let (ie, pos) = cause::InformationElement::parse(buffer)?;
+                    Some((InformationElement::Cause(ie), pos))
+                },
+                InformationElementType::PacketTransferCommand => {
+                    let (ie, pos) = packet_transfer_command::InformationElement::parse(buffer)?;
+                    Some((InformationElement::PacketTransferCommand(ie), pos))
+                },
+                InformationElementType::SequenceNumbersOfReleasedPackets |
+                InformationElementType::SequenceNumbersOfCancelledPackets |
+                InformationElementType::RequestsResponded => {
+                    let (ie, pos) = sequence_numbers::InformationElement::parse(buffer)?;
+                    Some((InformationElement::SequenceNumbers(ie), pos))
+                },
+                InformationElementType::DataRecordPacket => {
+                    let (ie, pos) = data_record_packet::InformationElement::parse(buffer)?;
+                    Some((InformationElement::DataRecordPacket(ie), pos))
+                },
+            }
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn skip_parsing(buffer: &[u8]) -> Option<usize> {
+        // This function will return the number of bytes to advance the buffer to skip over parsing this IE.
+        // As in GTPv1 only TLV format IEs carry a length so we can only skip the TV format IEs we know about.
+
+        if buffer.is_empty() {
+            return None
+        }
+
+        let ie_type = buffer[0];
+
+        if ie_type >= 128 {
+            if buffer.len() < 3 {
+                return None
+            }
+
+            // Read length of IE and add 3 for the IE header
+            let length = NetworkEndian::read_u16(&buffer[1..3]) as usize + 3;
+
+            if buffer.len() < length {
+                return None
+            }
+
+            Some(length)
+        }
+        else {
+            match tv_length(ie_type) {
+                Some(length) if buffer.len() >= length => Some(length),
+                _ => None
+            }
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement
+{
+    fn length(&self) -> u16 {
+        match self {
+            InformationElement::Cause(ie) => ie.length(),
+            InformationElement::PacketTransferCommand(ie) => ie.length(),
+            InformationElement::SequenceNumbers(ie) => ie.length(),
+            InformationElement::DataRecordPacket(ie) => ie.length(),
+        }
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        match self {
+            InformationElement::Cause(ie) => ie.generate(buffer),
+            InformationElement::PacketTransferCommand(ie) => ie.generate(buffer),
+            InformationElement::SequenceNumbers(ie) => ie.generate(buffer),
+            InformationElement::DataRecordPacket(ie) => ie.generate(buffer),
+        }
+    }
+
+    fn information_element_type(&self) -> InformationElementType {
+        match self {
+            InformationElement::Cause(ie) => ie.information_element_type(),
+            InformationElement::PacketTransferCommand(ie) => ie.information_element_type(),
+            InformationElement::SequenceNumbers(ie) => ie.information_element_type(),
+            InformationElement::DataRecordPacket(ie) => ie.information_element_type(),
+        }
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            InformationElement::Cause(ie) => ie.dissect(d),
+            InformationElement::PacketTransferCommand(ie) => ie.dissect(d),
+            InformationElement::SequenceNumbers(ie) => ie.dissect(d),
+            InformationElement::DataRecordPacket(ie) => ie.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_ie<T: InformationElementTraits, F: FnOnce(&mut Dissector)>(ie: &T, d: &mut Dissector, f: F) {
+    // Writes the IE type followed by the fields of the IE written by f
+    let ie_type = ie.information_element_type();
+
+    d.tree(&format!("{:?} ({})", ie_type, ie_type as u8), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        if let Some((InformationElement::SequenceNumbers(ie), pos)) = InformationElement::parse(&[InformationElementType::RequestsResponded as u8, 0, 2, 0x12, 0x34]) {
+            assert_eq!(ie.information_element_type(), InformationElementType::RequestsResponded);
+            assert_eq!(ie.sequence_numbers, vec![0x1234]);
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[0x7F, 0]).is_none());
+    }
+
+    #[test]
+    fn test_skip_parsing() {
+        // Private Extension
+        assert_eq!(InformationElement::skip_parsing(&[255, 0, 3, 0x00, 0x01, 0xAA]), Some(6));
+        assert_eq!(InformationElement::skip_parsing(&[255, 0, 3, 0x00, 0x01]), None);
+
+        // Recovery
+        assert_eq!(InformationElement::skip_parsing(&[14, 5]), Some(2));
+
+        // Unknown TV format IE
+        assert_eq!(InformationElement::skip_parsing(&[0x7F, 0]), None);
+    }
+}
@@ -0,0 +1,143 @@
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+// Cause values from TS 32.295 that are used in a Data Record Transfer Response
+pub const REQUEST_ACCEPTED: u8 = 128;
+pub const CDR_DECODING_ERROR: u8 = 177;
+pub const INVALID_MESSAGE_FORMAT: u8 = 193;
+pub const NO_RESOURCES_AVAILABLE: u8 = 199;
+pub const SERVICE_NOT_SUPPORTED: u8 = 200;
+pub const MANDATORY_IE_INCORRECT: u8 = 201;
+pub const MANDATORY_IE_MISSING: u8 = 202;
+pub const OPTIONAL_IE_INCORRECT: u8 = 203;
+pub const SYSTEM_FAILURE: u8 = 204;
+pub const REQUEST_RELATED_TO_POSSIBLY_DUPLICATED_PACKETS_ALREADY_FULFILLED: u8 = 252;
+pub const REQUEST_ALREADY_FULFILLED: u8 = 253;
+pub const SEQUENCE_NUMBERS_OF_RELEASED_CANCELLED_PACKETS_IE_INCORRECT: u8 = 254;
+pub const REQUEST_NOT_FULFILLED: u8 = 255;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (1)                                                   |
+        2       | Cause value                                                   |
+                |---------------------------------------------------------------|
+    */
+    cause: u8
+}
+
+impl InformationElement {
+    pub fn new(cause: u8) -> Self {
+        InformationElement {
+            cause
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    cause: buffer[1]
+                },
+                2
+            )
+        )
+    }
+
+    pub fn cause(&self) -> u8 {
+        self.cause
+    }
+
+    pub fn set_cause(&mut self, cause: u8) {
+        self.cause = cause;
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.cause == REQUEST_ACCEPTED
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Cause
+    }
+
+    fn length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        buffer[pos] = self.cause;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Cause", self.cause);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(REQUEST_ACCEPTED);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Cause as u8, 128]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(REQUEST_ACCEPTED);
+
+        assert_eq!(ie.length(), 2)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(REQUEST_ACCEPTED);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::Cause as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Cause as u8, REQUEST_ALREADY_FULFILLED]) {
+            assert_eq!(ie.cause(), 253);
+            assert!(!ie.is_accepted());
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::Cause as u8]).is_none());
+    }
+}
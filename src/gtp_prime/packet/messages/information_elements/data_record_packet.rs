@@ -0,0 +1,276 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Data Record Format values. The Data Record Format Version identifies the application (e.g. the charging
+// domain) and the release of the CDR definitions that the records are encoded with.
+pub const BASIC_ENCODING_RULES: u8 = 1;
+pub const UNALIGNED_PACKED_ENCODING_RULES: u8 = 2;
+pub const ALIGNED_PACKED_ENCODING_RULES: u8 = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (252)                                                 |
+        2       | Length (1st Octet)                                            |
+        3       | Length (2nd Octet)                                            |
+        4       | Number of Data Records                                        |
+        5       | Data Record Format                                            |
+        6       | Data Record Format Version (1st Octet)                        |
+        7       | Data Record Format Version (2nd Octet)                        |
+        8       | Length of Data Record 1 (1st Octet)                           |
+        9       | Length of Data Record 1 (2nd Octet)                           |
+        10 -> m | Data Record 1                                                 |
+        ...     | Length of Data Record / Data Record 2 to N                    |
+                |---------------------------------------------------------------|
+    */
+    pub data_record_format: u8,
+    pub data_record_format_version: u16,
+    data_records: Vec<Vec<u8>>,
+}
+
+impl InformationElement {
+    pub fn new(data_record_format: u8, data_record_format_version: u16) -> Self {
+        InformationElement {
+            data_record_format,
+            data_record_format_version,
+            data_records: Vec::new(),
+        }
+    }
+
+    pub fn push_data_record(&mut self, data_record: &[u8]) -> Result<usize, String> {
+        // Returns the number of data records in the packet
+        if self.data_records.len() >= u8::MAX as usize {
+            return Err(format!("Data Record Packet is full ({} records)", self.data_records.len()));
+        }
+
+        if self.length() as usize + 2 + data_record.len() > u16::MAX as usize {
+            return Err(format!("Data Record is too long ({} octets)", data_record.len()));
+        }
+
+        self.data_records.push(data_record.to_vec());
+
+        Ok(self.data_records.len())
+    }
+
+    pub fn data_records(&self) -> &[Vec<u8>] {
+        &self.data_records
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[pos..pos+2]) as usize;
+        pos = pos + 2;
+
+        if length < 4 || buffer.len() < length + 3 {
+            return None
+        }
+
+        let end = length + 3;
+
+        let number_of_data_records = buffer[pos];
+        pos = pos + 1;
+
+        let mut ie = InformationElement::new(buffer[pos], NetworkEndian::read_u16(&buffer[pos+1..pos+3]));
+        pos = pos + 3;
+
+        for _ in 0..number_of_data_records {
+            if pos + 2 > end {
+                return None
+            }
+
+            let data_record_length = NetworkEndian::read_u16(&buffer[pos..pos+2]) as usize;
+            pos = pos + 2;
+
+            if pos + data_record_length > end {
+                return None
+            }
+
+            ie.data_records.push(buffer[pos..pos+data_record_length].to_vec());
+            pos = pos + data_record_length;
+        }
+
+        if pos != end {
+            // The length doesn't match the Data Records
+            return None
+        }
+
+        Some((ie, pos))
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::DataRecordPacket
+    }
+
+    fn length(&self) -> u16 {
+        // This is the length of the IE INCLUDING the type and length fields
+        let mut length = 3 + 4;
+
+        for data_record in &self.data_records {
+            length = length + 2 + data_record.len() as u16;
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.length() - 3);
+        pos = pos + 2;
+
+        buffer[pos] = self.data_records.len() as u8;
+        pos = pos + 1;
+
+        buffer[pos] = self.data_record_format;
+        pos = pos + 1;
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.data_record_format_version);
+        pos = pos + 2;
+
+        for data_record in &self.data_records {
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], data_record.len() as u16);
+            pos = pos + 2;
+
+            buffer[pos..pos+data_record.len()].copy_from_slice(data_record);
+            pos = pos + data_record.len();
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Number of Data Records", self.data_records.len());
+            d.field("Data Record Format", self.data_record_format);
+            d.field("Data Record Format Version", format!("{:#06x}", self.data_record_format_version));
+
+            for data_record in &self.data_records {
+                d.hex("Data Record", data_record);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    fn new_data_record_packet() -> InformationElement {
+        let mut ie = InformationElement::new(BASIC_ENCODING_RULES, 0x1234);
+
+        ie.push_data_record(&[0xB5, 0x02, 0x80, 0x00]).unwrap();
+        ie.push_data_record(&[0xBF, 0x4F]).unwrap();
+
+        ie
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = new_data_record_packet();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::DataRecordPacket as u8,
+            0, 14, // Length
+            2, // Number of Data Records
+            BASIC_ENCODING_RULES,
+            0x12, 0x34, // Data Record Format Version
+            0, 4, 0xB5, 0x02, 0x80, 0x00,
+            0, 2, 0xBF, 0x4F,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = new_data_record_packet();
+
+        assert_eq!(ie.length(), 17)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = new_data_record_packet();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::DataRecordPacket as u8)
+    }
+
+    #[test]
+    fn test_push_data_record() {
+        let mut ie = InformationElement::new(BASIC_ENCODING_RULES, 0x1234);
+
+        for i in 0..255 {
+            assert_eq!(ie.push_data_record(&[i]), Ok(i as usize + 1));
+        }
+
+        // The Number of Data Records is 1 octet
+        assert!(ie.push_data_record(&[0]).is_err());
+
+        // The IE length is 2 octets
+        let mut ie = InformationElement::new(BASIC_ENCODING_RULES, 0x1234);
+        assert!(ie.push_data_record(&[0; 0xFFFF]).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::DataRecordPacket as u8,
+            0, 14, // Length
+            2, // Number of Data Records
+            BASIC_ENCODING_RULES,
+            0x12, 0x34, // Data Record Format Version
+            0, 4, 0xB5, 0x02, 0x80, 0x00,
+            0, 2, 0xBF, 0x4F,
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 17);
+            assert_eq!(ie.data_record_format, BASIC_ENCODING_RULES);
+            assert_eq!(ie.data_record_format_version, 0x1234);
+            assert_eq!(ie.data_records(), &[vec![0xB5, 0x02, 0x80, 0x00], vec![0xBF, 0x4F]]);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..16]).is_none());
+
+        // More Data Records than fit in the IE
+        let mut ie_bytes = ie_bytes;
+        ie_bytes[3] = 3;
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use core::convert::TryFrom;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum PacketTransferCommand {
+    SendDataRecordPacket = 1,
+    SendPossiblyDuplicatedDataRecordPacket = 2,
+    CancelDataRecordPacket = 3,
+    ReleaseDataRecordPacket = 4,
+}
+
+impl TryFrom<u8> for PacketTransferCommand
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(PacketTransferCommand::SendDataRecordPacket),
+            2 => Ok(PacketTransferCommand::SendPossiblyDuplicatedDataRecordPacket),
+            3 => Ok(PacketTransferCommand::CancelDataRecordPacket),
+            4 => Ok(PacketTransferCommand::ReleaseDataRecordPacket),
+            _ => Err(format!("Unsupported Packet Transfer Command ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (126)                                                 |
+        2       | Packet Transfer Command value                                 |
+                |---------------------------------------------------------------|
+    */
+    pub command: PacketTransferCommand,
+}
+
+impl InformationElement {
+    pub fn new(command: PacketTransferCommand) -> Self {
+        InformationElement {
+            command
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        let command = PacketTransferCommand::try_from(buffer[1]).ok()?;
+
+        Some(
+            (
+                InformationElement {
+                    command
+                },
+                2
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::PacketTransferCommand
+    }
+
+    fn length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        buffer[pos] = self.command as u8;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Packet Transfer Command", format!("{:?} ({})", self.command, self.command as u8));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PacketTransferCommand::CancelDataRecordPacket);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PacketTransferCommand as u8, 3]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(PacketTransferCommand::SendDataRecordPacket);
+
+        assert_eq!(ie.length(), 2)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(PacketTransferCommand::SendDataRecordPacket);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::PacketTransferCommand as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::PacketTransferCommand as u8, 2]) {
+            assert_eq!(ie.command, PacketTransferCommand::SendPossiblyDuplicatedDataRecordPacket);
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::PacketTransferCommand as u8, 5]).is_none());
+        assert!(InformationElement::parse(&[InformationElementType::PacketTransferCommand as u8]).is_none());
+    }
+}
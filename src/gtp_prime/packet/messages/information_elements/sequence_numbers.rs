@@ -0,0 +1,216 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryFrom;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (249, 250 or 253)                                     |
+        2       | Length (1st Octet)                                            |
+        3       | Length (2nd Octet)                                            |
+        4       | Sequence Number 1 (1st Octet)                                 |
+        5       | Sequence Number 1 (2nd Octet)                                 |
+        ...     |                                                               |
+        n       | Sequence Number N (2nd Octet)                                 |
+                |---------------------------------------------------------------|
+
+        The Sequence Numbers of Released Packets (249), Sequence Numbers of Cancelled Packets (250) and
+        Requests Responded (253) IEs all carry a list of GTP' header Sequence Numbers.
+    */
+    ie_type: InformationElementType,
+    pub sequence_numbers: Vec<u16>,
+}
+
+impl InformationElement {
+    pub fn new(ie_type: InformationElementType, sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        if !Self::is_sequence_numbers(ie_type) {
+            Err(format!("IE type is not a list of Sequence Numbers {:?}", ie_type))
+        }
+        else if sequence_numbers.len() * 2 > u16::MAX as usize - 3 {
+            Err(format!("Too many Sequence Numbers ({})", sequence_numbers.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    ie_type,
+                    sequence_numbers,
+                }
+            )
+        }
+    }
+
+    pub fn released(sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        Self::new(InformationElementType::SequenceNumbersOfReleasedPackets, sequence_numbers)
+    }
+
+    pub fn cancelled(sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        Self::new(InformationElementType::SequenceNumbersOfCancelledPackets, sequence_numbers)
+    }
+
+    pub fn requests_responded(sequence_numbers: Vec<u16>) -> Result<Self, String> {
+        Self::new(InformationElementType::RequestsResponded, sequence_numbers)
+    }
+
+    pub fn is_sequence_numbers(ie_type: InformationElementType) -> bool {
+        matches!(
+            ie_type,
+            InformationElementType::SequenceNumbersOfReleasedPackets |
+            InformationElementType::SequenceNumbersOfCancelledPackets |
+            InformationElementType::RequestsResponded
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let ie_type = match InformationElementType::try_from(buffer[pos]) {
+            Ok(ie_type) if Self::is_sequence_numbers(ie_type) => ie_type,
+            _ => return None,
+        };
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[pos..pos+2]) as usize;
+        pos = pos + 2;
+
+        if length & 1 == 1 || buffer.len() < length + 3 {
+            return None
+        }
+
+        let mut sequence_numbers = Vec::new();
+
+        while pos < length + 3 {
+            sequence_numbers.push(NetworkEndian::read_u16(&buffer[pos..pos+2]));
+            pos = pos + 2;
+        }
+
+        Some(
+            (
+                InformationElement {
+                    ie_type,
+                    sequence_numbers,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        self.ie_type
+    }
+
+    fn length(&self) -> u16 {
+        // This is the length of the IE INCLUDING the type and length fields
+        3 + (self.sequence_numbers.len() * 2) as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.length() - 3);
+        pos = pos + 2;
+
+        for sequence_number in &self.sequence_numbers {
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], *sequence_number);
+            pos = pos + 2;
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            for sequence_number in &self.sequence_numbers {
+                d.field("Sequence Number", format!("{:#06x}", sequence_number));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::released(vec![0x1234, 0x5678]).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::SequenceNumbersOfReleasedPackets as u8,
+            0, 4, // Length
+            0x12, 0x34,
+            0x56, 0x78,
+        ]);
+
+        assert!(InformationElement::new(InformationElementType::Cause, vec![1]).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::requests_responded(vec![1, 2, 3]).unwrap();
+
+        assert_eq!(ie.length(), 9)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::cancelled(vec![1]).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::SequenceNumbersOfCancelledPackets as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::SequenceNumbersOfCancelledPackets as u8,
+            0, 4, // Length
+            0x00, 0x01,
+            0xFF, 0xFE,
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.information_element_type(), InformationElementType::SequenceNumbersOfCancelledPackets);
+            assert_eq!(ie.sequence_numbers, vec![0x0001, 0xFFFE]);
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..6]).is_none());
+
+        // Odd length
+        assert!(InformationElement::parse(&[InformationElementType::RequestsResponded as u8, 0, 1, 0x00]).is_none());
+    }
+}
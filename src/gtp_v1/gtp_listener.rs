@@ -7,7 +7,7 @@ extern crate pnet;
 use std::sync::Mutex;
 use std::sync::Arc;
 
-use std::net::{IpAddr, UdpSocket};
+use std::net::{IpAddr, ToSocketAddrs, UdpSocket};
 
 use pnet::packet::ipv4::Ipv4Packet;
 
@@ -15,9 +15,11 @@ use pnet::transport;
 // use arp_cache::ArpCache;
 
 use super::listener_statistics::Statistics;
+use super::reorder::{ReorderBuffer, ReorderStatistics, SequenceNumberPolicy};
 
 use super::packet::Packet as GtpPacket;
-use super::packet::messages::{Message, echo_response};
+use super::packet::messages::Message;
+use super::packet::messages::information_elements::recovery;
 
 use crate::MTU;
 
@@ -27,6 +29,7 @@ pub struct GtpListener
     o_teid: u32,
     stats: Arc<Mutex<Statistics>>,
     socket: UdpSocket,
+    reorder: Option<Mutex<ReorderBuffer<Vec<u8>>>>, // T-PDUs are forwarded as they arrive if there's no reorder buffer
     // o_interface: datalink::NetworkInterface,
     // arp_cache: ArpCache,
 }
@@ -46,6 +49,7 @@ impl GtpListener {
                     o_teid,
                     stats: statistics,
                     socket: UdpSocket::bind("0.0.0.0:2152").expect("couldn't bind to address"),
+                    reorder: None,
                     // o_interface,
                     // arp_cache
                 }
@@ -57,6 +61,29 @@ impl GtpListener {
        
     }
 
+    pub fn with_reorder(mut self, policy: SequenceNumberPolicy) -> Result<Self, String> {
+        // Reorders the received T-PDUs by their Sequence Number before forwarding them
+        self.reorder = Some(Mutex::new(ReorderBuffer::new(policy)?));
+        Ok(self)
+    }
+
+    pub fn reorder_statistics(&self) -> Option<ReorderStatistics> {
+        self.reorder.as_ref().map(|reorder| reorder.lock().unwrap().statistics())
+    }
+
+    pub fn send_end_marker<A: ToSocketAddrs>(&self, addr: A) -> std::io::Result<usize> {
+        // Tells the peer that no more G-PDUs will be sent on this tunnel e.g. when the path is switched at handover
+        let mut end_marker = GtpPacket::end_marker(self.o_teid);
+
+        let n = end_marker.send_to(&self.socket, addr)?;
+
+        let mut s = self.stats.lock().unwrap();
+        (*s).tx_gtp_add(1);
+        drop(s);
+
+        Ok(n)
+    }
+
     pub fn listen(&self) {
         let mut buffer = [0; MTU];
 
@@ -72,54 +99,38 @@ impl GtpListener {
                     drop(s);
                     // It is for us
                     match p.message {
-                        Message::EchoRequest(_m) => {
+                        Message::EchoRequest(_) => {
                             // Send an EchoResponse
                             let mut s = self.stats.lock().unwrap();
                             (*s).rx_gtp_echo_request_add(1);
                             drop(s);
                             
-                            let mut echo_response = GtpPacket::new(Message::EchoResponse(echo_response::Message::new()));
-                            echo_response.header.set_teid(self.o_teid);
-                            if let Ok(_n) = echo_response.send_to(&self.socket, src_addr){
-                                let mut s = self.stats.lock().unwrap();
-                                (*s).tx_gtp_echo_response_add(1);
-                                drop(s);
-                            }
-                            else {}
-                        },
-                        Message::GPDU(m) => {
-                            // Process the GPDU
-                            // Now we need to parse the packet inside the GTP packet
-                            
-                            // Here we are assuming IPv4 for the inner packet
-                            let inner_ip_packet = Ipv4Packet::new(&m.t_pdu);
-
-                            if let Some(inner_ip_packet) = inner_ip_packet {
-                                
-
-                                let (mut sender, _) = match transport::transport_channel(
-                                    4096, 
-                                    transport::TransportChannelType::Layer3(inner_ip_packet.get_next_level_protocol())
-                                ) {
-                                    Ok((tx, rx)) => (tx, rx),
-                                    Err(e) => panic!(
-                                        "An error occurred when creating the transport channel: {}",
-                                        e
-                                    ),
-                                };
-
-                                let dest_ip_address = IpAddr::V4(inner_ip_packet.get_destination());
-                                
-                                if let Ok(_n) = sender.send_to(inner_ip_packet, dest_ip_address) {
+                            if let Some(mut echo_response) = p.echo_response(recovery::InformationElement::new(0)) {
+                                echo_response.header.set_teid(self.o_teid);
+                                if let Ok(_n) = echo_response.send_to(&self.socket, src_addr){
                                     let mut s = self.stats.lock().unwrap();
-                                    (*s).tx_ip_add(1);
+                                    (*s).tx_gtp_echo_response_add(1);
                                     drop(s);
                                 }
                                 else {}
                             }
+                        },
+                        Message::GPDU(m) => {
+                            // Process the GPDU
+                            let sequence_number = if p.header.sequence_number_enabled() {
+                                Some(p.header.sequence_number())
+                            }
                             else {
-                                // If the inner packet isn't IPv4 we bail out
-                                // TODO: Support IPv6 inner packets
+                                None
+                            };
+
+                            let t_pdus = match self.reorder {
+                                Some(ref reorder) => reorder.lock().unwrap().push(sequence_number, m.t_pdu),
+                                None => vec![m.t_pdu],
+                            };
+
+                            for t_pdu in t_pdus {
+                                self.forward(&t_pdu);
                             }
 
                             // let e_fields = Ethernet {
@@ -155,4 +166,38 @@ impl GtpListener {
             buffer = [0; MTU];
         }            
     }
+
+    fn forward(&self, t_pdu: &[u8]) {
+        // Now we need to parse the packet inside the GTP packet
+
+        // Here we are assuming IPv4 for the inner packet
+        let inner_ip_packet = Ipv4Packet::new(t_pdu);
+
+        if let Some(inner_ip_packet) = inner_ip_packet {
+
+            let (mut sender, _) = match transport::transport_channel(
+                4096, 
+                transport::TransportChannelType::Layer3(inner_ip_packet.get_next_level_protocol())
+            ) {
+                Ok((tx, rx)) => (tx, rx),
+                Err(e) => panic!(
+                    "An error occurred when creating the transport channel: {}",
+                    e
+                ),
+            };
+
+            let dest_ip_address = IpAddr::V4(inner_ip_packet.get_destination());
+
+            if let Ok(_n) = sender.send_to(inner_ip_packet, dest_ip_address) {
+                let mut s = self.stats.lock().unwrap();
+                (*s).tx_ip_add(1);
+                drop(s);
+            }
+            else {}
+        }
+        else {
+            // If the inner packet isn't IPv4 we bail out
+            // TODO: Support IPv6 inner packets
+        }
+    }
 }
@@ -0,0 +1,474 @@
+/* Helpers for the IP packets carried as the T-PDU of a G-PDU.
+
+encapsulate() wraps an IPv4 or IPv6 packet in G-PDUs that fit in the MTU of the path to the peer. The overhead is the
+outer IP header, the UDP header and a GTP-U header without optional fields or extension headers. Adding a Sequence
+Number or extension headers to the G-PDUs afterwards makes them longer than the budget.
+
+When the inner packet is too long it is fragmented at the inner IP layer, as a tunnel endpoint would do before
+encapsulation (TS 29.281 Annex C). Only IPv4 packets without the Don't Fragment flag can be fragmented as an IPv6
+packet can only be fragmented by its source.
+
+parse() validates the header of a received T-PDU: the version, the header length, the IPv4 header checksum and that
+the length in the header matches the T-PDU. */
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::packet::Packet;
+use super::packet::messages::{Message, g_pdu};
+
+use crate::address;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub const IPV4_HEADER_LENGTH: usize = 20; // Without options
+pub const IPV6_HEADER_LENGTH: usize = 40;
+pub const UDP_HEADER_LENGTH: usize = 8;
+pub const GTP_U_HEADER_LENGTH: usize = 8; // Without optional fields or extension headers
+
+const DONT_FRAGMENT: u16 = 0x4000;
+const MORE_FRAGMENTS: u16 = 0x2000;
+const FRAGMENT_OFFSET: u16 = 0x1FFF;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ipv4Header {
+    pub header_length: usize, // In octets, including options
+    pub total_length: u16,
+    pub identification: u16,
+    pub dont_fragment: bool,
+    pub more_fragments: bool,
+    pub fragment_offset: u16, // In octets
+    pub ttl: u8,
+    pub protocol: u8,
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+}
+
+impl Ipv4Header {
+    pub fn is_fragment(&self) -> bool {
+        self.more_fragments || self.fragment_offset > 0
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ipv6Header {
+    pub payload_length: u16,
+    pub next_header: u8,
+    pub hop_limit: u8,
+    pub source: Ipv6Addr,
+    pub destination: Ipv6Addr,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InnerHeader {
+    V4(Ipv4Header),
+    V6(Ipv6Header),
+}
+
+impl InnerHeader {
+    pub fn source(&self) -> IpAddr {
+        match self {
+            InnerHeader::V4(h) => IpAddr::V4(h.source),
+            InnerHeader::V6(h) => IpAddr::V6(h.source),
+        }
+    }
+
+    pub fn destination(&self) -> IpAddr {
+        match self {
+            InnerHeader::V4(h) => IpAddr::V4(h.destination),
+            InnerHeader::V6(h) => IpAddr::V6(h.destination),
+        }
+    }
+
+    pub fn protocol(&self) -> u8 {
+        // The Protocol (IPv4) or Next Header (IPv6)
+        match self {
+            InnerHeader::V4(h) => h.protocol,
+            InnerHeader::V6(h) => h.next_header,
+        }
+    }
+}
+
+pub fn checksum(buffers: &[&[u8]]) -> u16 {
+    // Internet checksum (RFC 1071) over the concatenation of buffers. Each buffer must be an even length except the last
+    let mut sum: u32 = 0;
+
+    for buffer in buffers {
+        for chunk in buffer.chunks(2) {
+            if chunk.len() == 2 {
+                sum = sum + NetworkEndian::read_u16(chunk) as u32;
+            }
+            else {
+                sum = sum + ((chunk[0] as u32) << 8);
+            }
+        }
+    }
+
+    while sum >> 16 != 0 {
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+
+    !(sum as u16)
+}
+
+pub fn overhead(peer: IpAddr) -> usize {
+    // The octets added to each T-PDU sent to peer
+    let ip_header_length = match peer {
+        IpAddr::V4(_) => IPV4_HEADER_LENGTH,
+        IpAddr::V6(_) => IPV6_HEADER_LENGTH,
+    };
+
+    ip_header_length + UDP_HEADER_LENGTH + GTP_U_HEADER_LENGTH
+}
+
+pub fn parse(t_pdu: &[u8]) -> Result<InnerHeader, String> {
+    if t_pdu.is_empty() {
+        return Err(String::from("T-PDU is empty"));
+    }
+
+    match t_pdu[0] >> 4 {
+        4 => parse_ipv4(t_pdu).map(InnerHeader::V4),
+        6 => parse_ipv6(t_pdu).map(InnerHeader::V6),
+        version => Err(format!("T-PDU isn't an IP packet. Version = {}", version)),
+    }
+}
+
+fn parse_ipv4(t_pdu: &[u8]) -> Result<Ipv4Header, String> {
+    if t_pdu.len() < IPV4_HEADER_LENGTH {
+        return Err(format!("IPv4 header truncated. Length = {}", t_pdu.len()));
+    }
+
+    let header_length = ((t_pdu[0] & 0xF) as usize) * 4;
+
+    if header_length < IPV4_HEADER_LENGTH || header_length > t_pdu.len() {
+        return Err(format!("Invalid IPv4 header length ({})", header_length));
+    }
+
+    if checksum(&[&t_pdu[..header_length]]) != 0 {
+        return Err(format!("Invalid IPv4 header checksum ({:#06x})", NetworkEndian::read_u16(&t_pdu[10..12])));
+    }
+
+    let total_length = NetworkEndian::read_u16(&t_pdu[2..4]);
+
+    if total_length as usize != t_pdu.len() {
+        return Err(format!("IPv4 total length ({}) doesn't match the T-PDU length ({})", total_length, t_pdu.len()));
+    }
+
+    let flags = NetworkEndian::read_u16(&t_pdu[6..8]);
+
+    Ok(Ipv4Header {
+        header_length,
+        total_length,
+        identification: NetworkEndian::read_u16(&t_pdu[4..6]),
+        dont_fragment: flags & DONT_FRAGMENT != 0,
+        more_fragments: flags & MORE_FRAGMENTS != 0,
+        fragment_offset: (flags & FRAGMENT_OFFSET) * 8,
+        ttl: t_pdu[8],
+        protocol: t_pdu[9],
+        source: address::parse_ipv4(&t_pdu[12..]).unwrap(),
+        destination: address::parse_ipv4(&t_pdu[16..]).unwrap(),
+    })
+}
+
+fn parse_ipv6(t_pdu: &[u8]) -> Result<Ipv6Header, String> {
+    if t_pdu.len() < IPV6_HEADER_LENGTH {
+        return Err(format!("IPv6 header truncated. Length = {}", t_pdu.len()));
+    }
+
+    let payload_length = NetworkEndian::read_u16(&t_pdu[4..6]);
+
+    if payload_length as usize + IPV6_HEADER_LENGTH != t_pdu.len() {
+        return Err(format!("IPv6 payload length ({}) doesn't match the T-PDU length ({})", payload_length, t_pdu.len()));
+    }
+
+    Ok(Ipv6Header {
+        payload_length,
+        next_header: t_pdu[6],
+        hop_limit: t_pdu[7],
+        source: address::parse_ipv6(&t_pdu[8..]).unwrap(),
+        destination: address::parse_ipv6(&t_pdu[24..]).unwrap(),
+    })
+}
+
+pub fn extract(packet: &Packet) -> Result<(InnerHeader, &[u8]), String> {
+    // The validated header and the whole inner packet of a G-PDU
+    match packet.message {
+        Message::GPDU(ref m) => Ok((parse(&m.t_pdu)?, &m.t_pdu)),
+        _ => Err(format!("Not a G-PDU ({:?})", packet.header.message_type())),
+    }
+}
+
+pub fn fragment(inner: &[u8], max_length: usize) -> Result<Vec<Vec<u8>>, String> {
+    /* Splits inner into IPv4 fragments of at most max_length octets. The first fragment keeps the options of inner and
+    the others have none (RFC 791 3.2). A packet that fits is returned as it is. */
+    let header = parse(inner)?;
+
+    if inner.len() <= max_length {
+        return Ok(vec![inner.to_vec()]);
+    }
+
+    let header = match header {
+        InnerHeader::V4(header) => header,
+        InnerHeader::V6(_) => return Err(format!("IPv6 packet ({} octets) can't be fragmented", inner.len())),
+    };
+
+    if header.dont_fragment {
+        return Err(format!("IPv4 packet ({} octets) has the Don't Fragment flag set", inner.len()));
+    }
+
+    let payload = &inner[header.header_length..];
+
+    let mut fragments = Vec::new();
+    let mut pos = 0;
+
+    while pos < payload.len() {
+        let header_length = if pos == 0 { header.header_length } else { IPV4_HEADER_LENGTH };
+
+        // Every fragment but the last carries a multiple of 8 octets
+        if max_length < header_length + 8 {
+            return Err(format!("MTU ({}) is too small to fragment into", max_length));
+        }
+        let max_payload = (max_length - header_length) & !0x7;
+
+        let length = core::cmp::min(max_payload, payload.len() - pos);
+        let last = pos + length == payload.len();
+
+        let mut f = Vec::with_capacity(header_length + length);
+        f.extend_from_slice(&inner[..header_length]);
+        f.extend_from_slice(&payload[pos..pos+length]);
+
+        // The fragments of a fragment are still followed by the rest of the original packet
+        let more_fragments = !last || header.more_fragments;
+        let offset = (header.fragment_offset as usize + pos) / 8;
+
+        f[0] = 0x40 | (header_length / 4) as u8;
+        NetworkEndian::write_u16(&mut f[2..4], (header_length + length) as u16);
+        NetworkEndian::write_u16(&mut f[6..8], if more_fragments { MORE_FRAGMENTS } else { 0 } | offset as u16);
+        NetworkEndian::write_u16(&mut f[10..12], 0);
+        let c = checksum(&[&f[..header_length]]);
+        NetworkEndian::write_u16(&mut f[10..12], c);
+
+        fragments.push(f);
+        pos = pos + length;
+    }
+
+    Ok(fragments)
+}
+
+pub fn encapsulate(teid: u32, inner: &[u8], mtu: usize, peer: IpAddr) -> Result<Vec<Packet>, String> {
+    // G-PDUs to peer that carry inner, fragmenting it if it doesn't fit in mtu once encapsulated
+    if mtu <= overhead(peer) {
+        return Err(format!("MTU ({}) is too small for the encapsulation overhead ({})", mtu, overhead(peer)));
+    }
+
+    let mut packets = Vec::new();
+
+    for f in fragment(inner, mtu - overhead(peer))? {
+        let mut p = Packet::new(Message::GPDU(g_pdu::Message::new(&f)?));
+        p.header.set_teid(teid);
+        packets.push(p);
+    }
+
+    Ok(packets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ipv4_packet(payload_length: usize, flags: u16, options: &[u8]) -> Vec<u8> {
+        let header_length = IPV4_HEADER_LENGTH + options.len();
+
+        let mut p = vec![0; header_length + payload_length];
+
+        p[0] = 0x40 | (header_length / 4) as u8;
+        NetworkEndian::write_u16(&mut p[2..4], (header_length + payload_length) as u16);
+        NetworkEndian::write_u16(&mut p[4..6], 0x1234); // Identification
+        NetworkEndian::write_u16(&mut p[6..8], flags);
+        p[8] = 64; // TTL
+        p[9] = 17; // UDP
+        p[12..16].copy_from_slice(&[10, 0, 0, 1]);
+        p[16..20].copy_from_slice(&[10, 0, 0, 2]);
+        p[IPV4_HEADER_LENGTH..header_length].copy_from_slice(options);
+
+        let c = checksum(&[&p[..header_length]]);
+        NetworkEndian::write_u16(&mut p[10..12], c);
+
+        for i in 0..payload_length {
+            p[header_length + i] = i as u8;
+        }
+
+        p
+    }
+
+    fn ipv6_packet(payload_length: usize) -> Vec<u8> {
+        let mut p = vec![0; IPV6_HEADER_LENGTH + payload_length];
+
+        p[0] = 0x60;
+        NetworkEndian::write_u16(&mut p[4..6], payload_length as u16);
+        p[6] = 58; // ICMPv6
+        p[7] = 255;
+        p[8..24].copy_from_slice(&Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).octets());
+        p[24..40].copy_from_slice(&Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2).octets());
+
+        p
+    }
+
+    #[test]
+    fn test_parse_ipv4() {
+        let p = ipv4_packet(8, DONT_FRAGMENT, &[]);
+
+        if let Ok(InnerHeader::V4(h)) = parse(&p) {
+            assert_eq!(h.header_length, 20);
+            assert_eq!(h.total_length, 28);
+            assert_eq!(h.identification, 0x1234);
+            assert!(h.dont_fragment);
+            assert!(!h.is_fragment());
+            assert_eq!(h.ttl, 64);
+            assert_eq!(h.protocol, 17);
+            assert_eq!(h.source, Ipv4Addr::new(10, 0, 0, 1));
+            assert_eq!(h.destination, Ipv4Addr::new(10, 0, 0, 2));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_ipv6() {
+        let p = ipv6_packet(8);
+
+        if let Ok(h) = parse(&p) {
+            assert_eq!(h.source(), IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+            assert_eq!(h.destination(), IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)));
+            assert_eq!(h.protocol(), 58);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse(&[]).is_err());
+
+        // Not IP
+        assert!(parse(&[0x50; 20]).is_err());
+
+        // Truncated
+        assert!(parse(&ipv4_packet(8, 0, &[])[..19]).is_err());
+        assert!(parse(&ipv6_packet(8)[..39]).is_err());
+
+        // Bad checksum
+        let mut p = ipv4_packet(8, 0, &[]);
+        p[8] = 63;
+        assert!(parse(&p).is_err());
+
+        // Header length shorter than the minimum
+        let mut p = ipv4_packet(8, 0, &[]);
+        p[0] = 0x44;
+        assert!(parse(&p).is_err());
+
+        // Trailing octets
+        let mut p = ipv4_packet(8, 0, &[]);
+        p.push(0);
+        assert!(parse(&p).is_err());
+
+        let mut p = ipv6_packet(8);
+        p.push(0);
+        assert!(parse(&p).is_err());
+    }
+
+    #[test]
+    fn test_fragment() {
+        let p = ipv4_packet(100, 0, &[]);
+
+        // Fits
+        assert_eq!(fragment(&p, 120).unwrap(), [p.to_vec()]);
+
+        // 100 octets of payload in at most 56 octets per fragment are 32 + 32 + 32 + 4
+        let fragments = fragment(&p, 56).unwrap();
+        assert_eq!(fragments.len(), 4);
+
+        let mut payload = Vec::new();
+
+        for (i, f) in fragments.iter().enumerate() {
+            if let Ok(InnerHeader::V4(h)) = parse(f) {
+                assert!(f.len() <= 56);
+                assert_eq!(h.identification, 0x1234);
+                assert_eq!(h.fragment_offset as usize, i * 32);
+                assert_eq!(h.more_fragments, i < 3);
+                payload.extend_from_slice(&f[h.header_length..]);
+            }
+            else {
+                assert!(false);
+            }
+        }
+
+        assert_eq!(payload, p[20..]);
+    }
+
+    #[test]
+    fn test_fragment_options() {
+        // Only the first fragment has the options
+        let p = ipv4_packet(40, 0, &[1, 1, 1, 0]);
+
+        let fragments = fragment(&p, 44).unwrap();
+
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(parse(&fragments[0]).map(|h| if let InnerHeader::V4(h) = h { h.header_length } else { 0 }), Ok(24));
+        assert_eq!(parse(&fragments[1]).map(|h| if let InnerHeader::V4(h) = h { h.header_length } else { 0 }), Ok(20));
+        assert_eq!(fragments[0].len(), 24 + 16);
+        assert_eq!(fragments[1].len(), 20 + 24);
+    }
+
+    #[test]
+    fn test_fragment_not_allowed() {
+        assert!(fragment(&ipv4_packet(100, DONT_FRAGMENT, &[]), 56).is_err());
+        assert!(fragment(&ipv6_packet(100), 56).is_err());
+        assert!(fragment(&ipv4_packet(100, 0, &[]), 27).is_err());
+
+        // They can still be sent if they fit
+        assert!(fragment(&ipv6_packet(100), 140).is_ok());
+    }
+
+    #[test]
+    fn test_encapsulate() {
+        let p = ipv4_packet(1400, 0, &[]);
+        let peer = IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1));
+
+        let packets = encapsulate(0x12345678, &p, 1500, peer).unwrap();
+        assert_eq!(packets.len(), 1);
+
+        let packets = encapsulate(0x12345678, &p, 1000, peer).unwrap();
+        assert_eq!(packets.len(), 2);
+
+        for mut packet in packets {
+            assert_eq!(packet.header.teid(), 0x12345678);
+            assert!(packet.to_vec().len() + IPV4_HEADER_LENGTH + UDP_HEADER_LENGTH <= 1000);
+
+            if let Ok((header, inner)) = extract(&packet) {
+                assert_eq!(header.destination(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+                assert!(inner.len() <= 1000 - overhead(peer));
+            }
+            else {
+                assert!(false);
+            }
+        }
+
+        // An IPv6 outer header leaves less room
+        assert_eq!(overhead(IpAddr::V6(Ipv6Addr::LOCALHOST)), 56);
+        assert!(encapsulate(1, &ipv6_packet(1400), 1500, IpAddr::V6(Ipv6Addr::LOCALHOST)).is_ok());
+        assert!(encapsulate(1, &ipv6_packet(1420), 1500, IpAddr::V6(Ipv6Addr::LOCALHOST)).is_err());
+        assert!(encapsulate(1, &p, 36, peer).is_err());
+    }
+
+    #[test]
+    fn test_extract_not_g_pdu() {
+        assert!(extract(&Packet::end_marker(1)).is_err());
+    }
+}
@@ -17,6 +17,11 @@ use pnet::packet::ipv6::Ipv6Packet;
 
 use super::listener_statistics::Statistics;
 
+#[cfg(unix)]
+use crate::socket;
+#[cfg(unix)]
+use crate::gtp_v2::packet::messages::information_elements::bearer_qos;
+
 use super::packet::Packet as GtpPacket;
 use super::packet::messages::{
     Message,
@@ -66,6 +71,22 @@ impl IpListener {
         })
     }
 
+    #[cfg(unix)]
+    pub fn set_dscp(&self, dscp: u8) -> std::io::Result<()> {
+        // Marks the G-PDUs sent to the peer from now on
+        socket::set_dscp(&self.socket, dscp)
+    }
+
+    #[cfg(unix)]
+    pub fn set_qci(&self, qci: u8) -> std::io::Result<()> {
+        self.set_dscp(bearer_qos::dscp_for_qci(qci))
+    }
+
+    #[cfg(unix)]
+    pub fn dscp(&self) -> std::io::Result<u8> {
+        socket::dscp(&self.socket)
+    }
+
     pub fn listen(&self) {
         // Create a channel to receive on
         let (_, mut rx) = match datalink::channel(&self.interface, Default::default()) {
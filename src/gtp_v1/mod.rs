@@ -1,4 +1,13 @@
 pub mod packet;
+pub mod inner_ip;
+pub mod listener_statistics;
+pub mod reorder;
+
+#[cfg(feature = "std")]
 pub mod gtp_listener;
+#[cfg(feature = "std")]
 pub mod ip_listener;
-pub mod listener_statistics;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod path_manager;
+#[cfg(feature = "std")]
+pub mod relay;
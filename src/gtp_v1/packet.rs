@@ -1,14 +1,29 @@
+pub mod batch;
 pub mod header;
 pub mod messages;
+pub mod view;
 
+use core::fmt;
+use core::net::IpAddr;
+#[cfg(feature = "std")]
 use std::net::ToSocketAddrs;
 use messages::{
     Message, 
     MessageTraits,
+    echo_response,
+    end_marker,
+    error_indication,
 };
+use messages::information_elements::recovery;
+use header::extension_headers::{ExtensionHeader, udp_port};
 
-use crate::MTU;
+use crate::dissect::{self, Dissect, Dissector};
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Packet {
     pub header: header::Header,
     pub message: messages::Message
@@ -22,6 +37,49 @@ impl Packet {
         }
     }
 
+    pub fn end_marker(teid: u32) -> Self {
+        // An End Marker for the tunnel identified by teid. Extension headers can be added to the header afterwards.
+        let mut p = Packet::new(Message::EndMarker(end_marker::Message::new()));
+        p.header.set_teid(teid);
+        p
+    }
+
+    pub fn echo_response(&self, recovery: recovery::InformationElement) -> Option<Self> {
+        // The Echo Response that answers this packet if it is an Echo Request. The sequence number is mirrored so the
+        // peer can match it to its request (TS 29.060 7.2.2). GTP-U peers expect a Restart Counter of 0.
+        match self.message {
+            Message::EchoRequest(_) => {
+                let mut p = Packet::new(Message::EchoResponse(echo_response::Message::new(recovery)));
+                p.header.enable_sequence_number();
+                p.header.set_sequence_number(self.header.sequence_number());
+                Some(p)
+            },
+            _ => None
+        }
+    }
+
+    pub fn error_indication(&self, local_address: IpAddr, src_port: u16) -> Option<Self> {
+        /* The Error Indication that rejects this packet if it is a G-PDU, e.g. one for a TEID that doesn't exist.
+        local_address is the address the G-PDU was received on and src_port is the UDP port it was sent from, which is
+        returned in a UDP Port extension header (TS 29.281 5.2.2.1). The Error Indication is sent to the GTP-U port
+        (2152) of the sender rather than src_port (TS 29.281 4.4.2.3). */
+        match self.message {
+            Message::GPDU(_) => {
+                let mut p = Packet::new(Message::ErrorIndication(error_indication::Message::for_g_pdu(&self.header, local_address)));
+
+                // The TEID is 0 and the Sequence Number is present (TS 29.281 5.1)
+                p.header.enable_sequence_number();
+
+                let mut udp_port = udp_port::ExtensionHeader::new();
+                udp_port.set_udp_port_number(src_port);
+                p.header.push_extension_header(ExtensionHeader::UDPPort(udp_port));
+
+                Some(p)
+            },
+            _ => None
+        }
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
         let h = header::Header::parse(&buffer);
 
@@ -41,33 +99,102 @@ impl Packet {
                 )
             }
             else {
+                debug!("Failed to parse {:?} message", h.message_type());
                 None
             }
             
         }
         else {
+            debug!("Failed to parse GTPv1 header of {} byte packet", buffer.len());
             None
         }
     }
 
+    pub fn parse_batch(buffers: &[&[u8]]) -> Vec<Option<(Self, usize)>> {
+        // Parses each buffer as if by parse. Plain G-PDUs take a faster path, see batch.
+        batch::parse_batch(buffers)
+    }
+
     pub fn generate(&mut self, buffer: &mut[u8]) -> usize {
+        let pos = self.header.generate(buffer);
+        let message_length = self.message.generate(&mut buffer[pos..]);
+
+        // The length of the message is only known once it has been generated.
+        // Writing the header length afterwards avoids walking the IEs a second time to calculate it.
+        self.header.set_payload_length(message_length as u16);
+        self.header.generate_length(buffer);
+
+        pos + message_length
+    }
+    pub fn generate_into(&mut self, buffer: &mut Vec<u8>) -> usize {
+        // Appends the packet to the end of buffer, growing it to fit. Returns the number of octets appended.
+        // Unlike generate this isn't limited to the size of a fixed buffer.
         self.header.set_payload_length(self.message.length());
 
-        let pos = self.header.generate(buffer);
-        let pos = pos + self.message.generate(&mut buffer[pos..]);
+        // The header length doesn't include the first 8 octets of the header
+        let length = self.header.length() as usize + 8;
+
+        let start = buffer.len();
+        buffer.resize(start + length, 0);
+
+        let pos = self.generate(&mut buffer[start..]);
+        buffer.truncate(start + pos);
 
         pos
     }
-    pub fn send_to<A: ToSocketAddrs>(&mut self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {        
-        let mut buffer = [0; MTU];
 
-        let pos = self.generate(&mut buffer);
+    pub fn to_vec(&mut self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+
+        self.generate_into(&mut buffer);
+
+        buffer
+    }
+
+    #[cfg(feature = "std")]
+    pub fn send_to<A: ToSocketAddrs>(&mut self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {
+        socket.send_to(&self.to_vec(), addr)
+    }
+
+    pub fn dissect(&self) -> String {
+        dissect::dissect(self)
+    }
+
+    #[cfg(any(test, debug_assertions, feature = "strategies"))]
+    pub fn verify_round_trip(&self) -> Result<(), Vec<String>> {
+        /* Generates the packet, parses the bytes back and compares the result with the packet. Returns the
+        differences if they aren't the same, see dissect::diff. Only in debug builds or with the strategies feature */
+        let mut generated = self.clone();
+        let bytes = generated.to_vec();
+
+        match Packet::parse(&bytes) {
+            Some((parsed, pos)) if pos == bytes.len() => {
+                let differences = dissect::diff(&generated, &parsed);
+
+                if differences.is_empty() { Ok(()) } else { Err(differences) }
+            },
+            Some((_parsed, pos)) => Err(alloc::vec![alloc::format!("Only {} of {} octets were parsed", pos, bytes.len())]),
+            None => Err(alloc::vec![alloc::format!("Failed to parse {}", dissect::hex_string(&bytes))]),
+        }
+    }
+}
+
+impl Dissect for Packet {
+    fn dissect(&self, d: &mut Dissector) {
+        self.header.dissect_with_payload_length(self.message.length(), d);
+        self.message.dissect(d);
+    }
+}
 
-        socket.send_to(&buffer[..pos], addr)
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", dissect::dissect(self))
     }
 }
 
 #[cfg(test)]
+// Most of the imports are only used by test_send which needs a socket
+#[cfg_attr(not(feature = "std"), allow(unused_imports))]
 mod tests {
     use super::*;
 
@@ -78,7 +205,7 @@ mod tests {
     use messages::{MessageType, Message, echo_request, echo_response, create_pdp_context_request, g_pdu};
     use messages::information_elements::{self, InformationElement};
 
-    use header::extension_headers::{ExtensionHeader, mbms_support_indication, pdcp_pdu_number, suspend_request};
+    use header::extension_headers::{mbms_support_indication, pdcp_pdu_number, suspend_request};
 
     #[test]
     fn test_generate() {
@@ -99,7 +226,7 @@ mod tests {
         
         buffer = [0; MTU];
 
-        let mut p = Packet::new(Message::EchoResponse(echo_response::Message::new()));
+        let mut p = Packet::new(Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0))));
 
         assert_eq!(p.header.message_type() as u8, MessageType::EchoResponse as u8);
 
@@ -108,12 +235,82 @@ mod tests {
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0000, 
             /* Message Type */ MessageType::EchoResponse as u8,
-            /* Length */ 0x00, 0x00,
-            /* TEID */ 0x00, 0x00, 0x00, 0x00
+            /* Length */ 0x00, 0x02,
+            /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Recovery */ 14, 0x00
+            ]);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        // A G-PDU larger than MTU
+        let payload = [0x45; MTU * 2];
+
+        let mut p = Packet::new(Message::GPDU(g_pdu::Message::new(&payload).unwrap()));
+
+        p.header.set_teid(0x12345678);
+
+        let buffer = p.to_vec();
+
+        assert_eq!(buffer.len(), 8 + MTU * 2);
+        assert_eq!(buffer[..8], [
+            /* Flags */ 0b0011_0000, 
+            /* Message Type */ MessageType::GPDU as u8,
+            /* Length */ ((MTU * 2) >> 8) as u8, (MTU * 2) as u8,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78
+            ]);
+        assert_eq!(buffer[8..], payload[..]);
+    }
+
+    #[test]
+    fn test_echo_response() {
+        let mut request = Packet::new(Message::EchoRequest(echo_request::Message::new()));
+        request.header.enable_sequence_number();
+        request.header.set_sequence_number(0x1234);
+
+        if let Some(mut p) = request.echo_response(recovery::InformationElement::new(0)) {
+            assert_eq!(p.header.sequence_number(), 0x1234);
+            assert_eq!(p.message, Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0))));
+
+            // It can be parsed by the peer
+            let bytes = p.to_vec();
+            assert_eq!(Packet::parse(&bytes).map(|(parsed, _pos)| parsed), Some(p));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Only Echo Requests are answered
+        let response = Packet::new(Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0))));
+        assert!(response.echo_response(recovery::InformationElement::new(0)).is_none());
+    }
+
+    #[test]
+    fn test_generate_into() {
+        let mut buffer = vec![0xFF, 0xFF];
+
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new()));
+
+        p.header.set_sequence_number(0x1234);
+        p.header.enable_sequence_number();
+
+        let pos = p.generate_into(&mut buffer);
+
+        assert_eq!(pos, 12);
+        assert_eq!(buffer, [
+            0xFF, 0xFF, // Existing contents are kept
+            /* Flags */ 0b0011_0010, 
+            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Length */ 0x00, 0x04,
+            /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x12, 0x34,
+            /* N-PDU Number */ 0x00,
+            /* Next Extension Header Type */ 0x00
             ]);
     }
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_send() {
         let socket = UdpSocket::bind("0.0.0.0:0").expect("couldn't bind to address");
 
@@ -121,7 +318,7 @@ mod tests {
 
         p.send_to(&socket, "192.168.1.1:2123").expect("Couldn't send data.");
 
-        let mut p = Packet::new(Message::EchoResponse(echo_response::Message::new()));
+        let mut p = Packet::new(Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0))));
 
         p.header.set_teid(0x12345678);
 
@@ -250,4 +447,124 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dissect() {
+        let mut p = Packet::new(Message::GPDU(g_pdu::Message::new(&[0x45, 0x00, 0x00, 0x14]).unwrap()));
+
+        p.header.set_teid(0x12345678);
+        p.header.enable_sequence_number();
+        p.header.set_sequence_number(0x1234);
+        p.header.push_extension_header(ExtensionHeader::PdcpPduNum(pdcp_pdu_number::ExtensionHeader::new()));
+
+        assert_eq!(p.dissect(), concat!(
+            "GTPv1 Header\n",
+            "    Version: 1\n",
+            "    Protocol Type (PT): 1\n",
+            "    Extension Header Flag (E): True\n",
+            "    Sequence Number Flag (S): True\n",
+            "    N-PDU Number Flag (PN): False\n",
+            "    Message Type: GPDU (255)\n",
+            "    Length: 12\n",
+            "    TEID: 0x12345678\n",
+            "    Sequence Number: 0x1234\n",
+            "    PdcpPduNum (0xc0), Length: 4\n",
+            "        PDCP PDU Number: 0\n",
+            "GPDU (255), Length: 4\n",
+            "    T-PDU (4 octets): 45 00 00 14\n",
+        ));
+    }
+
+    #[test]
+    fn test_end_marker() {
+        let mut p = Packet::end_marker(0x12345678);
+
+        assert_eq!(p.to_vec(), [
+            /* Flags */ 0x30,
+            /* Message Type */ MessageType::EndMarker as u8,
+            /* Length */ 0x00, 0x00,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+        ]);
+
+        // Extension headers are allowed on an End Marker
+        p.header.push_extension_header(ExtensionHeader::PdcpPduNum(pdcp_pdu_number::ExtensionHeader::new()));
+
+        let bytes = p.to_vec();
+
+        if let Some((p, pos)) = Packet::parse(&bytes) {
+            assert_eq!(p.message.message_type() as u8, MessageType::EndMarker as u8);
+            assert_eq!(p.header.teid(), 0x12345678);
+            assert_eq!(pos, bytes.len());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_error_indication() {
+        let mut g_pdu = Packet::new(Message::GPDU(g_pdu::Message::new(&[0x45, 0x00]).unwrap()));
+        g_pdu.header.set_teid(0x12345678);
+
+        if let Some(mut p) = g_pdu.error_indication(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 40000) {
+            assert_eq!(p.to_vec(), [
+                /* Flags */ 0x36,
+                /* Message Type */ MessageType::ErrorIndication as u8,
+                /* Length */ 0x00, 0x14,
+                /* TEID */ 0x00, 0x00, 0x00, 0x00,
+                /* Sequence Number */ 0x00, 0x00,
+                /* N-PDU Number */ 0x00,
+                /* Next Extension Header Type */ 0x40,
+                /* UDP Port */ 0x01, 0x9C, 0x40, 0x00,
+                /* TEID Data I */ 0x10, 0x12, 0x34, 0x56, 0x78,
+                /* GTP-U Peer Address */ 0x85, 0x00, 0x04, 10, 0, 0, 2,
+            ]);
+
+            let bytes = p.to_vec();
+
+            if let Some((parsed, _pos)) = Packet::parse(&bytes) {
+                if let Some(ExtensionHeader::UDPPort(eh)) = parsed.header.extension_headers.first() {
+                    assert_eq!(eh.udp_port_number(), 40000);
+                }
+                else {
+                    assert!(false);
+                }
+
+                if let Message::ErrorIndication(m) = parsed.message {
+                    assert_eq!(m.teid(), Some(0x12345678));
+                    assert_eq!(m.peer_address(), Some(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))));
+                }
+                else {
+                    assert!(false);
+                }
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // Only G-PDUs are rejected
+        assert!(Packet::end_marker(0x12345678).error_indication(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 40000).is_none());
+    }
+
+    #[test]
+    fn test_verify_round_trip() {
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new()));
+        p.header.enable_sequence_number();
+
+        assert_eq!(p.verify_round_trip(), Ok(()));
+
+        // The N-PDU Number isn't sent as the PN flag isn't set. It isn't dissected either so the Debug output is compared
+        p.header.set_n_pdu_number(0x12);
+
+        if let Err(differences) = p.verify_round_trip() {
+            assert_eq!(differences, vec!["-         n_pdu_number: 18,", "+         n_pdu_number: 0,"]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
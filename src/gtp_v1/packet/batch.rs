@@ -0,0 +1,151 @@
+/* Parsing of many GTP-U packets at once.
+
+Packet captures are mostly G-PDUs with no optional header fields. Those are recognised from the first two octets
+and parsed without going through the generic header and message dispatch. Everything else falls back to
+Packet::parse so the results are always the same as parsing each buffer on its own. */
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::Packet;
+use super::header::{self, TEID};
+use super::messages::{Message, MessageType, g_pdu};
+
+use alloc::vec::Vec;
+
+// Version 1, GTP and no sequence number, N-PDU number or extension headers
+const PLAIN_FLAGS: u8 = 0b0011_0000;
+
+pub struct ParseBatch<I> {
+    buffers: I
+}
+
+impl<I> ParseBatch<I> {
+    pub fn new(buffers: I) -> Self {
+        ParseBatch { buffers }
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a [u8]>> Iterator for ParseBatch<I> {
+    type Item = Option<(Packet, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.buffers.next().map(parse)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buffers.size_hint()
+    }
+}
+
+pub fn parse(buffer: &[u8]) -> Option<(Packet, usize)> {
+    if buffer.len() >= 8 && buffer[0] == PLAIN_FLAGS && buffer[1] == MessageType::GPDU as u8 {
+        parse_g_pdu(buffer)
+    }
+    else {
+        Packet::parse(buffer)
+    }
+}
+
+fn parse_g_pdu(buffer: &[u8]) -> Option<(Packet, usize)> {
+    // As in Packet::parse the T-PDU is the rest of the buffer
+    let t_pdu = &buffer[8..];
+
+    if t_pdu.len() > 0xFFFF {
+        return None
+    }
+
+    let mut h = header::Header::new(MessageType::GPDU);
+    h.set_teid(NetworkEndian::read_u32(&buffer[TEID]));
+    h.set_payload_length(t_pdu.len() as u16);
+
+    Some(
+        (
+            Packet {
+                header: h,
+                message: Message::GPDU(g_pdu::Message { t_pdu: t_pdu.to_vec() })
+            },
+            buffer.len()
+        )
+    )
+}
+
+pub fn parse_batch(buffers: &[&[u8]]) -> Vec<Option<(Packet, usize)>> {
+    let mut packets = Vec::with_capacity(buffers.len());
+
+    packets.extend(ParseBatch::new(buffers.iter().copied()));
+
+    packets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v1::packet::messages::echo_request;
+
+    use alloc::vec;
+
+    fn g_pdu_bytes(teid: u32, t_pdu: &[u8]) -> Vec<u8> {
+        let mut p = Packet::new(Message::GPDU(g_pdu::Message::new(t_pdu).unwrap()));
+        p.header.set_teid(teid);
+        p.to_vec()
+    }
+
+    #[test]
+    fn test_parse_g_pdu() {
+        let bytes = g_pdu_bytes(0x12345678, &[0x45; 100]);
+
+        assert_eq!(parse(&bytes), Packet::parse(&bytes));
+
+        if let Some((p, pos)) = parse(&bytes) {
+            assert_eq!(pos, 108);
+            assert_eq!(p.header.teid(), 0x12345678);
+        }
+        else {
+            assert!(false);
+        }
+
+        // An empty T-PDU
+        let bytes = g_pdu_bytes(1, &[]);
+        assert_eq!(parse(&bytes), Packet::parse(&bytes));
+    }
+
+    #[test]
+    fn test_parse_fallback() {
+        // A G-PDU with a sequence number doesn't take the fast path
+        let mut p = Packet::new(Message::GPDU(g_pdu::Message::new(&[0x45; 20]).unwrap()));
+        p.header.enable_sequence_number();
+        p.header.set_sequence_number(1000);
+        let bytes = p.to_vec();
+
+        assert_eq!(parse(&bytes), Packet::parse(&bytes));
+        assert!(parse(&bytes).is_some());
+
+        let bytes = Packet::new(Message::EchoRequest(echo_request::Message::new())).to_vec();
+        assert_eq!(parse(&bytes), Packet::parse(&bytes));
+        assert!(parse(&bytes).is_some());
+
+        // Too short and not GTPv1
+        assert!(parse(&[0x30, 0xFF, 0, 0]).is_none());
+        assert!(parse(&[0x48, 0x01, 0, 0, 0, 0, 0, 0]).is_none());
+    }
+
+    #[test]
+    fn test_parse_batch() {
+        let g_pdu = g_pdu_bytes(0x1000, &[0x45; 64]);
+        let echo_request = Packet::new(Message::EchoRequest(echo_request::Message::new())).to_vec();
+        let malformed = vec![0x30, 0xFF, 0, 0];
+
+        let buffers: [&[u8]; 3] = [&g_pdu, &malformed, &echo_request];
+
+        let packets = parse_batch(&buffers);
+
+        assert_eq!(packets.len(), 3);
+        assert_eq!(packets[0], Packet::parse(&g_pdu));
+        assert!(packets[1].is_none());
+        assert_eq!(packets[2], Packet::parse(&echo_request));
+
+        // The iterator gives the same results without collecting them
+        assert!(ParseBatch::new(buffers.iter().copied()).eq(packets.into_iter()));
+    }
+}
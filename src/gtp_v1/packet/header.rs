@@ -4,10 +4,17 @@ use byteorder::{ByteOrder, NetworkEndian};
 
 use super::messages::MessageType;
 
+use core::convert::TryFrom;
+
 pub mod extension_headers;
 
 use extension_headers::{ExtensionHeader, ExtensionHeaderTraits};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::vec::Vec;
+
 /*                                  
                                         Bits
             |---------------------------------------------------------------| 
@@ -31,6 +38,8 @@ use extension_headers::{ExtensionHeader, ExtensionHeaderTraits};
 pub const LENGTH: Field = 2..4;
 pub const TEID: Field = 4..8;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Header {
     // MANDATORY FIELDS
     version: u8, // Always present, always set to 1
@@ -88,17 +97,9 @@ impl Header {
 
         // This returns the length of the optional parts of the header
         let mut length = self.payload_length;
-        
-        if self.s == 1 {
-            length = length + 2; // 2 octets for sequence number
-        }
 
-        if self.pn == 1 {
-            length = length + 1; // 1 octet for N_PDU number
-        }
-
-        if self.e == 1 {
-            length = length + 1; // 1 octet for next extension header
+        if self.optional_fields_present() {
+            length = length + 4; // Sequence Number, N-PDU Number and Next Extension Header Type
         }
 
         for e in self.extension_headers.iter() {
@@ -125,11 +126,15 @@ impl Header {
         self.s = 0;
     }
 
+    pub fn sequence_number_enabled(&self) -> bool {
+        self.s == 1
+    }
+
     pub fn set_sequence_number(&mut self, sequence_number: u16) {
         self.sequence_number = sequence_number;
     }
 
-    pub fn sequence_number(&mut self) -> u16 {
+    pub fn sequence_number(&self) -> u16 {
         self.sequence_number
     }
 
@@ -186,6 +191,18 @@ impl Header {
         self.payload_length = payload_length;
     }
 
+    pub fn generate_length(&self, buffer: &mut[u8]) {
+        // Rewrites only the length field of a header that has already been generated into buffer.
+        // This lets the length be filled in after the payload has been generated.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length());
+    }
+
+    fn optional_fields_present(&self) -> bool {
+        // The Sequence Number, N-PDU Number and Next Extension Header Type are all present if any of E, S or PN are set.
+        // The fields whose flag isn't set are 0 (TS 29.281 5.1)
+        self.e == 1 || self.s == 1 || self.pn == 1
+    }
+
     fn generate_flags(&self) -> u8 {
         (self.version << 5) | (self.pt << 4) | (0 << 3) | (self.e << 2) | (self.s << 1) | self.pn
     }
@@ -202,28 +219,26 @@ impl Header {
         // Optional fields start at index 8
         let mut pos: usize = 8;
 
-        if self.s == 1 {
-            NetworkEndian::write_u16(&mut buffer[pos..],self.sequence_number);
-            pos = pos + 2;
+        if !self.optional_fields_present() {
+            return pos
         }
 
-        if self.pn == 1 {
-            buffer[pos] = self.n_pdu_number;
-            pos = pos + 1;
-        }
+        let sequence_number = if self.s == 1 { self.sequence_number } else { 0 };
+        NetworkEndian::write_u16(&mut buffer[pos..], sequence_number);
+        pos = pos + 2;
+
+        buffer[pos] = if self.pn == 1 { self.n_pdu_number } else { 0 };
+        pos = pos + 1;
+
+        // The type of the first extension header is written in the next_extension_header_type field here. If e is set
+        // without anything in the extension headers vector ExtensionHeaderType::NoMore is written
+        buffer[pos] = match self.extension_headers.first() {
+            Some(e) if self.e == 1 => e.extension_header_type() as u8,
+            _ => extension_headers::ExtensionHeaderType::NoMore as u8,
+        };
+        pos = pos + 1;
 
         if self.e == 1 {
-            if self.extension_headers.len() > 0 {
-                // We write the type of the first extension header in the next_extension_header_type field here
-                buffer[pos] = self.extension_headers[0].extension_header_type() as u8;
-                pos = pos + 1;
-            }
-            else {
-                // This should never happen as we shouldnt have e set without anything in the extenstion headers vector
-                // however, when/if this happens we should write ExtensionHeaderType::NoMore as u8 in the next_extension_header_type field
-                buffer[pos] = extension_headers::ExtensionHeaderType::NoMore as u8;
-                pos = pos + 1;
-            }
             for e in self.extension_headers.iter() {
                 let extenstion_header_size = e.generate(&mut buffer[pos..]);
                 pos = pos + extenstion_header_size;
@@ -244,6 +259,10 @@ impl Header {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 8 {
+            return None
+        }
+
         let (version, pt, e, s, pn) = Self::parse_flags(buffer[0]);
 
         if version != 1 {
@@ -252,11 +271,14 @@ impl Header {
         }
 
         if pt != 1 {
-            // We dont support GTP'
+            // GTP' packets are parsed by gtp_prime
             return None;
         }
 
-        let message_type:MessageType = buffer[1].into();
+        let message_type = match MessageType::try_from(buffer[1]) {
+            Ok(message_type) => message_type,
+            Err(_) => return None,
+        };
 
         let mut h = Self::new(message_type);
 
@@ -267,27 +289,39 @@ impl Header {
 
         let mut pos: usize = 8;
 
+        if e == 0 && s == 0 && pn == 0 {
+            return Some((h, pos))
+        }
+
+        if buffer.len() < pos + 4 {
+            // The header is too short to contain the optional fields
+            return None
+        }
+
+        // The fields whose flag isn't set are ignored
         if s == 1 {
-            let sequence_number = NetworkEndian::read_u16(&buffer[pos..]);
-            pos = pos + 2;
-            h.set_sequence_number(sequence_number);
+            h.set_sequence_number(NetworkEndian::read_u16(&buffer[pos..]));
             h.enable_sequence_number();
         }
+        pos = pos + 2;
 
         if pn == 1 {
-            let n_pdu_number = buffer[pos];
-            pos = pos + 1;
-
-            h.set_n_pdu_number(n_pdu_number);
+            h.set_n_pdu_number(buffer[pos]);
             h.enable_n_pdu_number();
         }
+        pos = pos + 1;
 
-        if e == 1 {
-            let mut next_extension_header_type = buffer[pos];
-            pos = pos + 1;
+        let mut next_extension_header_type = buffer[pos];
+        pos = pos + 1;
 
+        if e == 1 {
             loop {
-                match next_extension_header_type.into() {
+                let extension_header_type = match extension_headers::ExtensionHeaderType::try_from(next_extension_header_type) {
+                    Ok(extension_header_type) => extension_header_type,
+                    Err(_) => return None,
+                };
+
+                match extension_header_type {
                     extension_headers::ExtensionHeaderType::NoMore => break,
                     extension_headers::ExtensionHeaderType::MbmsSi => {
                         let eh = extension_headers::mbms_support_indication::ExtensionHeader::parse(&buffer[pos..]);
@@ -296,6 +330,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::MbmsSi(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::LongPdcpPduNumber => {
                         let eh = extension_headers::mbms_support_indication::ExtensionHeader::parse(&buffer[pos..]);
@@ -304,6 +341,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::MbmsSi(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::MsInfoChange => {
                         let eh = extension_headers::ms_info_change_reporting_support_indication::ExtensionHeader::parse(&buffer[pos..]);
@@ -312,6 +352,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::MsInfoChange(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::PdcpPduNum => {
                         let eh = extension_headers::pdcp_pdu_number::ExtensionHeader::parse(&buffer[pos..]);
@@ -320,6 +363,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::PdcpPduNum(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::SuspendReq => {
                         let eh = extension_headers::suspend_request::ExtensionHeader::parse(&buffer[pos..]);
@@ -328,6 +374,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::SuspendReq(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::SuspendRes => {
                         let eh = extension_headers::suspend_response::ExtensionHeader::parse(&buffer[pos..]);
@@ -336,6 +385,20 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::SuspendRes(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
+                    },
+                    extension_headers::ExtensionHeaderType::PDUSessionContainer => {
+                        let eh = extension_headers::pdu_session_container::ExtensionHeader::parse(&buffer[pos..]);
+                        if let Some((eh, eh_pos)) = eh {
+                            next_extension_header_type = eh.next_extension_header_type() as u8;
+                            h.push_extension_header(ExtensionHeader::PDUSessionContainer(eh));
+                            pos = pos + eh_pos;
+                        }
+                        else {
+                            return None
+                        }
                     },
                     extension_headers::ExtensionHeaderType::UDPPort => {
                         let eh = extension_headers::udp_port::ExtensionHeader::parse(&buffer[pos..]);
@@ -344,6 +407,9 @@ impl Header {
                             h.push_extension_header(ExtensionHeader::UDPPort(eh));
                             pos = pos + eh_pos;
                         }
+                        else {
+                            return None
+                        }
                     },
                 }
             }
@@ -351,6 +417,109 @@ impl Header {
 
         Some((h, pos))
     }
+
+    pub fn dissect_with_payload_length(&self, payload_length: u16, d: &mut Dissector) {
+        // The payload length is only updated when the packet is generated or parsed so the caller supplies it
+        let length = self.length() - self.payload_length + payload_length;
+
+        d.tree("GTPv1 Header", |d| {
+            d.field("Version", self.version);
+            d.field("Protocol Type (PT)", self.pt);
+            d.flag("Extension Header Flag (E)", self.e == 1);
+            d.flag("Sequence Number Flag (S)", self.s == 1);
+            d.flag("N-PDU Number Flag (PN)", self.pn == 1);
+            d.field("Message Type", format!("{:?} ({})", self.message_type, self.message_type as u8));
+            d.field("Length", length);
+            d.field("TEID", format!("{:#010x}", self.teid));
+
+            if self.s == 1 {
+                d.field("Sequence Number", format!("{:#06x}", self.sequence_number));
+            }
+
+            if self.pn == 1 {
+                d.field("N-PDU Number", self.n_pdu_number);
+            }
+
+            for eh in &self.extension_headers {
+                eh.dissect(d);
+            }
+        });
+    }
+}
+
+impl Dissect for Header {
+    fn dissect(&self, d: &mut Dissector) {
+        self.dissect_with_payload_length(self.payload_length, d);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeaderSummary {
+    pub version: u8,
+    pub message_type: u8, // Not checked against MessageType so unknown messages can still be forwarded
+    pub teid: u32,
+    pub payload_offset: usize, // Offset of the message (the T-PDU for a G-PDU) from the start of the buffer
+    pub total_length: usize, // Length of the whole message including the first 8 octets of the header
+}
+
+pub fn peek(buffer: &[u8]) -> Option<HeaderSummary> {
+    /* Reads only what a forwarder needs from the header. The extension headers are walked to find the payload
+    offset but aren't parsed. As in TS 29.281 the Sequence Number, N-PDU Number and Next Extension Header Type
+    fields are all present if any of E, S or PN are set. */
+
+    if buffer.len() < 8 {
+        return None
+    }
+
+    let version = (buffer[0] >> 5) & 0b111;
+
+    if version != 1 || (buffer[0] >> 4) & 0b1 != 1 {
+        // Not GTPv1 or GTP'
+        return None
+    }
+
+    let total_length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize + 8;
+
+    if buffer.len() < total_length {
+        return None
+    }
+
+    let mut payload_offset = 8;
+
+    if buffer[0] & 0b111 != 0 {
+        payload_offset = 12;
+
+        if total_length < payload_offset {
+            return None
+        }
+
+        let mut next_extension_header_type = if (buffer[0] >> 2) & 0b1 == 1 { buffer[11] } else { 0 };
+
+        while next_extension_header_type != 0 {
+            // The length of an extension header is in multiples of 4 octets
+            if payload_offset >= total_length || buffer[payload_offset] == 0 {
+                return None
+            }
+
+            payload_offset = payload_offset + buffer[payload_offset] as usize * 4;
+
+            if payload_offset > total_length {
+                return None
+            }
+
+            next_extension_header_type = buffer[payload_offset - 1];
+        }
+    }
+
+    Some(
+        HeaderSummary {
+            version,
+            message_type: buffer[1],
+            teid: NetworkEndian::read_u32(&buffer[TEID]),
+            payload_offset,
+            total_length,
+        }
+    )
 }
 
 #[cfg(test)]
@@ -432,18 +601,25 @@ mod tests {
             /* TEID */ 0x00, 0x00, 0x00, 0x00
             ]);
 
+        assert!(!h.sequence_number_enabled());
+
         h.enable_sequence_number();
 
-        assert_eq!(h.length(), 2);
+        assert!(h.sequence_number_enabled());
+
+        // The N-PDU Number and Next Extension Header Type are present but 0
+        assert_eq!(h.length(), 4);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0010, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x02,
+            /* Length */ 0x00, 0x04,
             /* TEID */0x00, 0x00, 0x00, 0x00,
-            /* Sequence Number */ 0x12, 0x34
+            /* Sequence Number */ 0x12, 0x34,
+            /* N_PDU Number */ 0x00,
+            /* Next Extension Header Type */ 0x00
             ]);
 
         h.disable_sequence_number();
@@ -481,16 +657,18 @@ mod tests {
 
         h.enable_n_pdu_number();
 
-        assert_eq!(h.length(), 1);
+        assert_eq!(h.length(), 4);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0001, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x01,
+            /* Length */ 0x00, 0x04,
             /* TEID */0x00, 0x00, 0x00, 0x00,
-            /* N_PDU Number */ 0x12
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x12,
+            /* Next Extension Header Type */ 0x00
             ]);
 
         h.disable_n_pdu_number();
@@ -521,8 +699,10 @@ mod tests {
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0100, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x05,
+            /* Length */ 0x00, 0x08,
             /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
             /* Next Extension Header Type */ ExtensionHeaderType::MbmsSi as u8,
             /* MBMS SI Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::NoMore as u8
             ]);
@@ -536,8 +716,10 @@ mod tests {
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0100, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x09,
+            /* Length */ 0x00, 0x0c,
             /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
             /* Next Extension Header Type */ ExtensionHeaderType::MbmsSi as u8,
             /* MBMS SI Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::SuspendReq as u8,
             /* Suspend Request Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::NoMore as u8
@@ -554,8 +736,10 @@ mod tests {
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0100, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x0d,
+            /* Length */ 0x00, 0x10,
             /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
             /* Next Extension Header Type */ ExtensionHeaderType::MbmsSi as u8,
             /* MBMS SI Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::SuspendReq as u8,
             /* Suspend Request Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::PdcpPduNum as u8,
@@ -569,14 +753,35 @@ mod tests {
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0100, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x09,
+            /* Length */ 0x00, 0x0c,
             /* TEID */ 0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
             /* Next Extension Header Type */ ExtensionHeaderType::MbmsSi as u8,
             /* MBMS SI Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::SuspendReq as u8,
             /* Suspend Request Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::NoMore as u8
             ]);
     }
 
+    #[test]
+    fn test_generate_length() {
+        let mut buffer = [0; MTU];
+
+        let mut h = Header::new(MessageType::EchoRequest);
+
+        let pos = h.generate(&mut buffer);
+
+        h.set_payload_length(0x1234);
+        h.generate_length(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0011_0000, 
+            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Length */ 0x12, 0x34,
+            /* TEID */ 0x00, 0x00, 0x00, 0x00
+            ]);
+    }
+
     #[test]
     fn test_length() {
         let mut buffer = [0; MTU];
@@ -597,32 +802,35 @@ mod tests {
         h.set_sequence_number(0x4567);
         h.enable_sequence_number();
 
-        assert_eq!(h.length(), 0x1234+2);
+        assert_eq!(h.length(), 0x1234+4);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0010, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x12, 0x36,
+            /* Length */ 0x12, 0x38,
             /* TEID */0x00, 0x00, 0x00, 0x00,
-            /* Sequence Number */ 0x45, 0x67
+            /* Sequence Number */ 0x45, 0x67,
+            /* N_PDU Number */ 0x00,
+            /* Next Extension Header Type */ 0x00
             ]);
 
         h.set_n_pdu_number(0x12);
         h.enable_n_pdu_number();
 
-        assert_eq!(h.length(), 0x1234+2+1);
+        // The optional fields are only counted once
+        assert_eq!(h.length(), 0x1234+4);
         let pos = h.generate(&mut buffer);
 
-        // We haven't enabled SN so it shouldn't be output
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0011, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x12, 0x37,
+            /* Length */ 0x12, 0x38,
             /* TEID */ 0x00, 0x00, 0x00, 0x00,
             /* Sequence Number */ 0x45, 0x67,
-            /* N_PDU Number */ 0x12
+            /* N_PDU Number */ 0x12,
+            /* Next Extension Header Type */ 0x00
             ]);
     }
 
@@ -638,18 +846,40 @@ mod tests {
         h.enable_sequence_number();
         h.enable_n_pdu_number();
 
-        assert_eq!(h.length(), 3);
+        assert_eq!(h.length(), 4);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0011_0011, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x03,
+            /* Length */ 0x00, 0x04,
+            /* TEID */0x00, 0x00, 0x00, 0x00,
+            /* Sequence Number */ 0x12, 0x34,
+            /* N_PDU Number */ 0x12,
+            /* Next Extension Header Type */ 0x00
+            ]);
+
+        let mut udp_port = extension_headers::udp_port::ExtensionHeader::new();
+        udp_port.set_udp_port_number(0x1234);
+        h.push_extension_header(ExtensionHeader::UDPPort(udp_port));
+
+        let pos = h.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0011_0111, 
+            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Length */ 0x00, 0x08,
             /* TEID */0x00, 0x00, 0x00, 0x00,
             /* Sequence Number */ 0x12, 0x34,
-            /* N_PDU Number */ 0x12
+            /* N_PDU Number */ 0x12,
+            /* Next Extension Header Type */ ExtensionHeaderType::UDPPort as u8,
+            /* UDP Port Ext Header */ 0x01, 0x12, 0x34, ExtensionHeaderType::NoMore as u8
             ]);
+
+        // The header the peer reads is the same
+        assert_eq!(Header::parse(&buffer[..pos]), Some((h, pos)));
+        assert_eq!(peek(&buffer[..pos]).map(|summary| summary.payload_offset), Some(pos));
     }
 
     #[test]
@@ -658,8 +888,10 @@ mod tests {
         let header_bytes =  [
             /* Flags */ 0b0011_0100, 
             /* Message Type */ MessageType::EchoRequest as u8,
-            /* Length */ 0x00, 0x0d,
+            /* Length */ 0x00, 0x10,
             /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
             /* Next Extension Header Type */ ExtensionHeaderType::MbmsSi as u8,
             /* MBMS SI Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::SuspendReq as u8,
             /* Suspend Request Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::PdcpPduNum as u8,
@@ -670,7 +902,7 @@ mod tests {
 
         if let Some((h, pos)) = h {
             assert_eq!(h.message_type as u8, MessageType::EchoRequest as u8);
-            assert_eq!(h.length(), 0x0d);
+            assert_eq!(h.length(), 0x10);
             assert_eq!(h.teid(), 0x12345678);
 
             assert_eq!(h.extension_headers.len(), 3);
@@ -681,11 +913,160 @@ mod tests {
 
             assert_eq!(h.extension_headers[2].extension_header_type() as u8, ExtensionHeaderType::PdcpPduNum as u8);
 
-            assert_eq!(pos, 21);
+            assert_eq!(pos, 24);
         }
         else {
             // Failed to parse. This shouldnt happen with a valid header
             assert!(false)
         }
     }
+    #[test]
+    fn test_message_parse_pdu_session_container() {
+
+        let header_bytes =  [
+            /* Flags */ 0b0011_0100,
+            /* Message Type */ MessageType::GPDU as u8,
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
+            /* Next Extension Header Type */ ExtensionHeaderType::PDUSessionContainer as u8,
+            /* PDU Session Container Ext Header */ 0x01, 0x00, 0b0100_1001, ExtensionHeaderType::NoMore as u8
+            ];
+
+        let h = Header::parse(&header_bytes);
+
+        if let Some((h, pos)) = h {
+            assert_eq!(h.message_type as u8, MessageType::GPDU as u8);
+
+            assert_eq!(h.extension_headers.len(), 1);
+
+            assert_eq!(h.extension_headers[0].extension_header_type() as u8, ExtensionHeaderType::PDUSessionContainer as u8);
+
+            if let ExtensionHeader::PDUSessionContainer(eh) = &h.extension_headers[0] {
+                assert_eq!(eh.qfi(), 9);
+                assert_eq!(eh.rqi(), true);
+            }
+            else {
+                assert!(false)
+            }
+
+            assert_eq!(pos, 16);
+        }
+        else {
+            // Failed to parse. This shouldnt happen with a valid header
+            assert!(false)
+        }
+    }
+
+    #[test]
+    fn test_message_parse_truncated() {
+        let header_bytes =  [
+            /* Flags */ 0b0011_0110,
+            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x12, 0x34,
+            /* N_PDU Number */ 0x00,
+            /* Next Extension Header Type */ ExtensionHeaderType::PdcpPduNum as u8,
+            /* PDCP PDU Number Ext Header */ 0x01, 0x12, 0x34, ExtensionHeaderType::NoMore as u8
+            ];
+
+        assert!(Header::parse(&header_bytes).is_some());
+
+        for length in 0..header_bytes.len() {
+            assert!(Header::parse(&header_bytes[..length]).is_none());
+        }
+    }
+
+    #[test]
+    fn test_message_parse_unsupported_types() {
+        let header_bytes =  [
+            /* Flags */ 0b0011_0000,
+            /* Message Type */ 0x00,
+            /* Length */ 0x00, 0x00,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            ];
+
+        assert!(Header::parse(&header_bytes).is_none());
+
+        let header_bytes =  [
+            /* Flags */ 0b0011_0100,
+            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x00, 0x00,
+            /* N_PDU Number */ 0x00,
+            /* Next Extension Header Type */ 0b0010_0000,
+            /* Unknown Ext Header */ 0x01, 0xFF, 0xFF, ExtensionHeaderType::NoMore as u8
+            ];
+
+        assert!(Header::parse(&header_bytes).is_none());
+    }
+
+    #[test]
+    fn test_peek() {
+        let g_pdu = [
+            /* Flags */ 0b0011_0000,
+            /* Message Type */ MessageType::GPDU as u8,
+            /* Length */ 0x00, 0x04,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* T-PDU */ 0xDE, 0xAD, 0xBE, 0xEF,
+        ];
+
+        assert_eq!(peek(&g_pdu), Some(HeaderSummary {
+            version: 1,
+            message_type: MessageType::GPDU as u8,
+            teid: 0x12345678,
+            payload_offset: 8,
+            total_length: 12,
+        }));
+
+        let g_pdu_extension_headers = [
+            /* Flags */ 0b0011_0110,
+            /* Message Type */ MessageType::GPDU as u8,
+            /* Length */ 0x00, 0x12,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0xAB, 0xCD,
+            /* N-PDU Number */ 0x00,
+            /* Next Extension Header Type */ ExtensionHeaderType::PDUSessionContainer as u8,
+            1, 0x00, 0x01, ExtensionHeaderType::UDPPort as u8,
+            1, 0x12, 0x34, ExtensionHeaderType::NoMore as u8,
+            /* T-PDU */ 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE,
+        ];
+
+        if let Some(summary) = peek(&g_pdu_extension_headers) {
+            assert_eq!(summary.payload_offset, 20);
+            assert_eq!(summary.total_length, 26);
+            assert_eq!(&g_pdu_extension_headers[summary.payload_offset..summary.total_length], &[0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE]);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Only the sequence number flag is set
+        let mut buffer = g_pdu_extension_headers;
+        buffer[0] = 0b0011_0010;
+        assert_eq!(peek(&buffer).map(|s| s.payload_offset), Some(12));
+    }
+
+    #[test]
+    fn test_peek_invalid() {
+        let g_pdu = [0b0011_0000, MessageType::GPDU as u8, 0x00, 0x04, 0x12, 0x34, 0x56, 0x78, 0xDE, 0xAD, 0xBE, 0xEF];
+
+        assert!(peek(&g_pdu[..7]).is_none());
+        assert!(peek(&g_pdu[..11]).is_none());
+
+        // GTPv2 and GTP'
+        let mut buffer = g_pdu;
+        buffer[0] = 0b0100_1000;
+        assert!(peek(&buffer).is_none());
+        buffer[0] = 0b0010_0000;
+        assert!(peek(&buffer).is_none());
+
+        // Extension header runs past the end of the message
+        let buffer = [0b0011_0100, MessageType::GPDU as u8, 0x00, 0x08, 0, 0, 0, 1, 0, 0, 0,
+            ExtensionHeaderType::UDPPort as u8, 2, 0x12, 0x34, 0x00];
+        assert!(peek(&buffer).is_none());
+    }
 }
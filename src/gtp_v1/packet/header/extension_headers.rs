@@ -8,6 +8,7 @@
     | 0010 0000                         | Reserved for GTP-U. See 3GPP TS 29.281 [41].  |
     | 0100 0000                         | Reserved for GTP-U. See 3GPP TS 29.281 [41].  |
     | 1000 0001                         | Reserved for GTP-U. See 3GPP TS 29.281 [41].  |
+    | 1000 0101                         | PDU Session Container. See 3GPP TS 29.281     |
     | 1100 0000                         | PDCP PDU number                               |
     | 1100 0001                         | Suspend Request                               |
     | 1100 0010                         | Suspend Response                              |
@@ -21,8 +22,17 @@ pub mod suspend_request;
 pub mod suspend_response;
 pub mod udp_port;
 pub mod long_pdcp_pdu_number;
+pub mod pdu_session_container;
 
-#[derive(Copy, Clone, Debug)]
+use core::convert::TryFrom;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum ExtensionHeaderType
 {
     NoMore = 0b0000_0000,
@@ -34,29 +44,31 @@ pub enum ExtensionHeaderType
     LongPdcpPduNumber = 0b1000_0010,
     // XwRANContainer = 0b1000_0011, NOT IMPLEMENTED
     // NRRANContainer = 0b1000_0100, NOT IMPLEMENTED
-    // PDUSessionContainer = 0b1000_0101, NOT IMPLEMENTED
+    PDUSessionContainer = 0b1000_0101,
     PdcpPduNum = 0b1100_0000,
     SuspendReq = 0b1100_0001,
     SuspendRes = 0b1100_0010
 }
 
-impl From<u8> for ExtensionHeaderType {
-    fn from(v: u8) -> Self {
+impl TryFrom<u8> for ExtensionHeaderType {
+    type Error = String;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
         match v {
-            0b0000_0000 => ExtensionHeaderType::NoMore,
-            0b0000_0001 => ExtensionHeaderType::MbmsSi,
-            0b0000_0010 => ExtensionHeaderType::MsInfoChange,
+            0b0000_0000 => Ok(ExtensionHeaderType::NoMore),
+            0b0000_0001 => Ok(ExtensionHeaderType::MbmsSi),
+            0b0000_0010 => Ok(ExtensionHeaderType::MsInfoChange),
             // 0b0010_0000 => ExtensionHeaderType::ServiceClassIndicator,
-            0b0100_0000 => ExtensionHeaderType::UDPPort,
+            0b0100_0000 => Ok(ExtensionHeaderType::UDPPort),
             // 0b1000_0001 => ExtensionHeaderType::RANContainer,
             // 0b1000_0010 => ExtensionHeaderType::LongPdcpPduNumber,
             // 0b1000_0011 => ExtensionHeaderType::XwRANContainer,
             // 0b1000_0100 => ExtensionHeaderType::NRRANContainer,
-            // 0b1000_0101 => ExtensionHeaderType::PDUSessionContainer,
-            0b1100_0000 => ExtensionHeaderType::PdcpPduNum,
-            0b1100_0001 => ExtensionHeaderType::SuspendReq,
-            0b1100_0010 => ExtensionHeaderType::SuspendRes,
-            _ => panic!(format!("Unsupported Extension Header ({})", v))
+            0b1000_0101 => Ok(ExtensionHeaderType::PDUSessionContainer),
+            0b1100_0000 => Ok(ExtensionHeaderType::PdcpPduNum),
+            0b1100_0001 => Ok(ExtensionHeaderType::SuspendReq),
+            0b1100_0010 => Ok(ExtensionHeaderType::SuspendRes),
+            _ => Err(format!("Unsupported Extension Header ({})", v))
         }
     }
 }
@@ -69,11 +81,14 @@ pub trait ExtensionHeaderTraits {
     fn generate(&self, buffer: &mut[u8]) -> usize;
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum ExtensionHeader {
     LongPdcpPduNumber(long_pdcp_pdu_number::ExtensionHeader),
     MbmsSi(mbms_support_indication::ExtensionHeader),
     MsInfoChange(ms_info_change_reporting_support_indication::ExtensionHeader),
     PdcpPduNum(pdcp_pdu_number::ExtensionHeader),
+    PDUSessionContainer(pdu_session_container::ExtensionHeader),
     SuspendReq(suspend_request::ExtensionHeader),
     SuspendRes(suspend_response::ExtensionHeader),
     UDPPort(udp_port::ExtensionHeader)
@@ -87,6 +102,7 @@ impl ExtensionHeaderTraits for ExtensionHeader
             ExtensionHeader::MbmsSi(eh) => eh.extension_header_type(),
             ExtensionHeader::MsInfoChange(eh) => eh.extension_header_type(),
             ExtensionHeader::PdcpPduNum(eh) => eh.extension_header_type(),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.extension_header_type(),
             ExtensionHeader::SuspendReq(eh) => eh.extension_header_type(),
             ExtensionHeader::SuspendRes(eh) => eh.extension_header_type(),
             ExtensionHeader::UDPPort(eh) => eh.extension_header_type(),
@@ -98,6 +114,7 @@ impl ExtensionHeaderTraits for ExtensionHeader
             ExtensionHeader::MbmsSi(eh) => eh.set_next_extension_header_type(next_extension_header_type),
             ExtensionHeader::MsInfoChange(eh) => eh.set_next_extension_header_type(next_extension_header_type),
             ExtensionHeader::PdcpPduNum(eh) => eh.set_next_extension_header_type(next_extension_header_type),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.set_next_extension_header_type(next_extension_header_type),
             ExtensionHeader::SuspendReq(eh) => eh.set_next_extension_header_type(next_extension_header_type),
             ExtensionHeader::SuspendRes(eh) => eh.set_next_extension_header_type(next_extension_header_type),
             ExtensionHeader::UDPPort(eh) => eh.set_next_extension_header_type(next_extension_header_type),
@@ -109,6 +126,7 @@ impl ExtensionHeaderTraits for ExtensionHeader
             ExtensionHeader::MbmsSi(eh) => eh.next_extension_header_type(),
             ExtensionHeader::MsInfoChange(eh) => eh.next_extension_header_type(),
             ExtensionHeader::PdcpPduNum(eh) => eh.next_extension_header_type(),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.next_extension_header_type(),
             ExtensionHeader::SuspendReq(eh) => eh.next_extension_header_type(),
             ExtensionHeader::SuspendRes(eh) => eh.next_extension_header_type(),
             ExtensionHeader::UDPPort(eh) => eh.next_extension_header_type(),
@@ -120,6 +138,7 @@ impl ExtensionHeaderTraits for ExtensionHeader
             ExtensionHeader::MbmsSi(eh) => eh.length(),
             ExtensionHeader::MsInfoChange(eh) => eh.length(),
             ExtensionHeader::PdcpPduNum(eh) => eh.length(),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.length(),
             ExtensionHeader::SuspendReq(eh) => eh.length(),
             ExtensionHeader::SuspendRes(eh) => eh.length(),
             ExtensionHeader::UDPPort(eh) => eh.length(),
@@ -131,9 +150,32 @@ impl ExtensionHeaderTraits for ExtensionHeader
             ExtensionHeader::MbmsSi(eh) => eh.generate(buffer),
             ExtensionHeader::MsInfoChange(eh) => eh.generate(buffer),
             ExtensionHeader::PdcpPduNum(eh) => eh.generate(buffer),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.generate(buffer),
             ExtensionHeader::SuspendReq(eh) => eh.generate(buffer),
             ExtensionHeader::SuspendRes(eh) => eh.generate(buffer),
             ExtensionHeader::UDPPort(eh) => eh.generate(buffer),
         }
     }
-}
\ No newline at end of file
+}
+
+impl Dissect for ExtensionHeader {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            ExtensionHeader::LongPdcpPduNumber(eh) => eh.dissect(d),
+            ExtensionHeader::MbmsSi(eh) => eh.dissect(d),
+            ExtensionHeader::MsInfoChange(eh) => eh.dissect(d),
+            ExtensionHeader::PdcpPduNum(eh) => eh.dissect(d),
+            ExtensionHeader::PDUSessionContainer(eh) => eh.dissect(d),
+            ExtensionHeader::SuspendReq(eh) => eh.dissect(d),
+            ExtensionHeader::SuspendRes(eh) => eh.dissect(d),
+            ExtensionHeader::UDPPort(eh) => eh.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_extension_header<T: ExtensionHeaderTraits, F: FnOnce(&mut Dissector)>(eh: &T, d: &mut Dissector, f: F) {
+    // Writes the extension header type followed by the fields of the extension header written by f
+    let eh_type = eh.extension_header_type();
+
+    d.tree(&format!("{:?} ({:#04x}), Length: {}", eh_type, eh_type as u8, eh.length()), f);
+}
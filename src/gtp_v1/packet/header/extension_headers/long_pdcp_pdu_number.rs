@@ -1,7 +1,15 @@
-use byteorder::{ByteOrder, NetworkEndian};
+use core::convert::TryFrom;
 
-use super::{ExtensionHeaderTraits, ExtensionHeaderType};
+use super::{ExtensionHeaderTraits, ExtensionHeaderType, dissect_extension_header};
 
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct ExtensionHeader {
     /*
                                         Bits
@@ -32,6 +40,10 @@ impl ExtensionHeader {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 8 {
+            return None
+        }
+
         let mut pos = 0;
 
         // Parse the length
@@ -39,14 +51,17 @@ impl ExtensionHeader {
         pos = pos + 1;
 
         // Read pdcp_pdu_number
-        let pdcp_pdu_number = NetworkEndian::read_uint(&buffer[1..4], 3) as u32;
+        let pdcp_pdu_number = uint::parse_u24(&buffer[1..])?;
         pos = pos + 3;
 
         // Padding
         pos = pos + 3;
 
         // Read the next extension header type in last octet
-        let next_extension_header_type: ExtensionHeaderType = buffer[pos].into();
+        let next_extension_header_type = match ExtensionHeaderType::try_from(buffer[pos]) {
+            Ok(next_extension_header_type) => next_extension_header_type,
+            Err(_) => return None,
+        };
         pos = pos + 1;
 
         Some(
@@ -98,7 +113,7 @@ impl ExtensionHeaderTraits for ExtensionHeader {
         buffer[0] = self.length()/4;
 
         // Write the pdcp_pdu_number
-        NetworkEndian::write_uint(&mut buffer[1..4], self.pdcp_pdu_number as u64, 3);
+        uint::generate_u24_lenient(self.pdcp_pdu_number, &mut buffer[1..]);
 
         // Write next extension header type in last octet
         buffer[self.length() as usize - 1] = self.next_extension_header_type as u8;
@@ -107,6 +122,14 @@ impl ExtensionHeaderTraits for ExtensionHeader {
     }
 }
 
+impl Dissect for ExtensionHeader {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_extension_header(self, d, |d| {
+            d.field("PDCP PDU Number", self.pdcp_pdu_number);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
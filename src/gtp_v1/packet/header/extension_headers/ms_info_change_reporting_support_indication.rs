@@ -1,7 +1,13 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{ExtensionHeaderTraits, ExtensionHeaderType};
+use core::convert::TryFrom;
 
+use super::{ExtensionHeaderTraits, ExtensionHeaderType, dissect_extension_header};
+
+use crate::dissect::{Dissect, Dissector};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct ExtensionHeader {
     /*
                                         Bits
@@ -25,6 +31,10 @@ impl ExtensionHeader {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         // Parse the length
         let _length = buffer[0];
@@ -34,7 +44,10 @@ impl ExtensionHeader {
         pos = pos + 2;
 
         // Read the next extension header type in last octet
-        let next_extension_header_type: ExtensionHeaderType = buffer[pos].into();
+        let next_extension_header_type = match ExtensionHeaderType::try_from(buffer[pos]) {
+            Ok(next_extension_header_type) => next_extension_header_type,
+            Err(_) => return None,
+        };
         pos = pos + 1;
 
         Some(
@@ -78,6 +91,12 @@ impl ExtensionHeaderTraits for ExtensionHeader {
     }
 }
 
+impl Dissect for ExtensionHeader {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_extension_header(self, d, |_d| {});
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
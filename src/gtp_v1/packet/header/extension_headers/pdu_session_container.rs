@@ -0,0 +1,399 @@
+use core::convert::TryFrom;
+
+use super::{ExtensionHeaderTraits, ExtensionHeaderType, dissect_extension_header};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum PduType {
+    DlPduSessionInformation = 0,
+    UlPduSessionInformation = 1,
+}
+
+impl TryFrom<u8> for PduType
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PduType::DlPduSessionInformation),
+            1 => Ok(PduType::UlPduSessionInformation),
+            _ => Err(format!("Unsupported PDU Type ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ExtensionHeader {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | Extension Header Length (n)                                   |
+        2       | PDU Type                      | Spare                         |
+        3       | PPP   | RQI   | QFI                                           |
+        4       | PPI                   | Spare (Present if PPP is set)         |
+        ...     | Padding (0x00)                                                |
+        4n      | Next Extension Header Type                                    |
+                |---------------------------------------------------------------|
+
+        The PDU Session Container content is specified in TS 38.415. Octet 3 is shown for
+        DL PDU SESSION INFORMATION (PDU Type 0). For UL PDU SESSION INFORMATION (PDU Type 1)
+        bits 8 and 7 of octet 3 are spare and the PPI octet is not present.
+
+        Optional fields (e.g. QoS monitoring timestamps) are skipped when parsing and not generated.
+    */
+    pdu_type: PduType,
+    qfi: u8, // Max size is 6 bits
+    rqi: bool,
+    ppi: Option<u8>, // Max size is 3 bits
+    next_extension_header_type: ExtensionHeaderType,
+}
+
+impl ExtensionHeader {
+    pub fn new(pdu_type: PduType) -> ExtensionHeader {
+        ExtensionHeader {
+            pdu_type: pdu_type,
+            qfi: 0,
+            rqi: false,
+            ppi: None,
+            next_extension_header_type: ExtensionHeaderType::NoMore
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 1 {
+            return None
+        }
+
+        // Parse the length
+        let length = buffer[0] as usize * 4;
+
+        if length < 4 || buffer.len() < length {
+            // The extension header is truncated
+            return None
+        }
+
+        let pdu_type = match PduType::try_from(buffer[1] >> 4) {
+            Ok(pdu_type) => pdu_type,
+            Err(_) => return None,
+        };
+
+        let qfi = buffer[2] & 0x3F;
+
+        let mut rqi = false;
+        let mut ppi = None;
+
+        if pdu_type == PduType::DlPduSessionInformation {
+            rqi = (buffer[2] >> 6) & 0b1 == 1;
+
+            if (buffer[2] >> 7) & 0b1 == 1 {
+                if length < 8 {
+                    // There is no room for the PPI octet before the next extension header type
+                    return None
+                }
+                ppi = Some(buffer[3] >> 5);
+            }
+        }
+
+        // Read the next extension header type in last octet
+        let next_extension_header_type = match ExtensionHeaderType::try_from(buffer[length - 1]) {
+            Ok(next_extension_header_type) => next_extension_header_type,
+            Err(_) => return None,
+        };
+
+        Some(
+            (
+                ExtensionHeader {
+                    pdu_type: pdu_type,
+                    qfi: qfi,
+                    rqi: rqi,
+                    ppi: ppi,
+                    next_extension_header_type: next_extension_header_type
+                },
+                length
+            )
+        )
+    }
+
+    pub fn pdu_type(&self) -> PduType {
+        self.pdu_type
+    }
+
+    pub fn set_qfi(&mut self, value: u8) -> Result<u8,String> {
+        if value > 0x3F {
+            return Err(format!("QFI ({}) can not be > 0x3F.", value));
+        }
+        else
+        {
+            self.qfi = value;
+        }
+        Ok(self.qfi)
+    }
+
+    pub fn qfi(&self) -> u8 {
+        self.qfi
+    }
+
+    pub fn set_rqi(&mut self, value: bool) -> Result<bool,String> {
+        if value && self.pdu_type != PduType::DlPduSessionInformation {
+            return Err("RQI is only present in DL PDU Session Information".to_string());
+        }
+        else
+        {
+            self.rqi = value;
+        }
+        Ok(self.rqi)
+    }
+
+    pub fn rqi(&self) -> bool {
+        self.rqi
+    }
+
+    pub fn set_ppi(&mut self, value: u8) -> Result<u8,String> {
+        if self.pdu_type != PduType::DlPduSessionInformation {
+            return Err("PPI is only present in DL PDU Session Information".to_string());
+        }
+        else if value > 0x7 {
+            return Err(format!("PPI ({}) can not be > 0x7.", value));
+        }
+        else
+        {
+            self.ppi = Some(value);
+        }
+        Ok(value)
+    }
+
+    pub fn unset_ppi(&mut self) {
+        self.ppi = None;
+    }
+
+    pub fn ppi(&self) -> Option<u8> {
+        self.ppi
+    }
+}
+
+impl ExtensionHeaderTraits for ExtensionHeader {
+    fn extension_header_type(&self) -> ExtensionHeaderType {
+        ExtensionHeaderType::PDUSessionContainer
+    }
+
+    fn set_next_extension_header_type(&mut self, next_extension_header_type: ExtensionHeaderType) {
+        self.next_extension_header_type = next_extension_header_type;
+    }
+
+    fn next_extension_header_type(&self) -> ExtensionHeaderType {
+        self.next_extension_header_type
+    }
+
+    fn length(&self) -> u8 {
+        // Length octet + PDU Type + QFI + (PPI) + Next Extension Header Type padded to a multiple of 4 octets
+        let mut length: u8 = 1 + 1 + 1 + 1;
+
+        if let Some(_) = self.ppi {
+            length = length + 1;
+        }
+
+        length.div_ceil(4) * 4
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the length
+        buffer[pos] = self.length()/4;
+        pos = pos + 1;
+
+        buffer[pos] = (self.pdu_type as u8) << 4;
+        pos = pos + 1;
+
+        match self.pdu_type {
+            PduType::DlPduSessionInformation => {
+                let ppp = if self.ppi.is_some() { 1 } else { 0 };
+                let rqi = if self.rqi { 1 } else { 0 };
+
+                buffer[pos] = (ppp << 7) | (rqi << 6) | (self.qfi & 0x3F);
+                pos = pos + 1;
+
+                if let Some(ppi) = self.ppi {
+                    buffer[pos] = (ppi & 0x7) << 5;
+                    pos = pos + 1;
+                }
+            },
+            PduType::UlPduSessionInformation => {
+                buffer[pos] = self.qfi & 0x3F;
+                pos = pos + 1;
+            }
+        }
+
+        // Padding
+        while pos < self.length() as usize - 1 {
+            buffer[pos] = 0x00;
+            pos = pos + 1;
+        }
+
+        // Write next extension header type in last octet
+        buffer[pos] = self.next_extension_header_type as u8;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for ExtensionHeader {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_extension_header(self, d, |d| {
+            d.field("PDU Type", format!("{:?} ({})", self.pdu_type, self.pdu_type as u8));
+            d.field("QoS Flow Identifier (QFI)", self.qfi);
+            d.flag("Reflective QoS Indicator (RQI)", self.rqi);
+
+            if let Some(ppi) = self.ppi {
+                d.field("Paging Policy Indicator (PPI)", ppi);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::header::extension_headers::{ExtensionHeaderTraits, ExtensionHeaderType};
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+        eh.set_qfi(9).unwrap();
+        eh.set_rqi(true).unwrap();
+
+        let pos = eh.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0x1, 0x00, 0b0100_1001, ExtensionHeaderType::NoMore as u8]);
+
+        let mut buffer = [0; MTU];
+
+        let mut eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+        eh.set_qfi(1).unwrap();
+        eh.set_ppi(5).unwrap();
+
+        let pos = eh.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0x2, 0x00, 0b1000_0001, 0b1010_0000, 0x00, 0x00, 0x00, ExtensionHeaderType::NoMore as u8]);
+
+        let mut buffer = [0; MTU];
+
+        let mut eh = ExtensionHeader::new(PduType::UlPduSessionInformation);
+        eh.set_qfi(0x3F).unwrap();
+
+        let pos = eh.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0x1, 0x10, 0x3F, ExtensionHeaderType::NoMore as u8]);
+    }
+
+    #[test]
+    fn test_set_next_extension_header_type() {
+        let mut buffer = [0; MTU];
+
+        let mut eh = ExtensionHeader::new(PduType::UlPduSessionInformation);
+
+        assert_eq!(eh.next_extension_header_type() as u8, ExtensionHeaderType::NoMore as u8);
+
+        eh.set_next_extension_header_type(ExtensionHeaderType::UDPPort);
+
+        assert_eq!(eh.next_extension_header_type() as u8, ExtensionHeaderType::UDPPort as u8);
+
+        let pos = eh.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0x1, 0x10, 0x00, ExtensionHeaderType::UDPPort as u8]);
+    }
+
+    #[test]
+    fn test_set_qfi() {
+        let mut eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+
+        assert_eq!(eh.set_qfi(0x3F), Ok(0x3F));
+        assert!(eh.set_qfi(0x40).is_err());
+        assert_eq!(eh.qfi(), 0x3F);
+    }
+
+    #[test]
+    fn test_set_ppi() {
+        let mut eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+
+        assert_eq!(eh.set_ppi(7), Ok(7));
+        assert!(eh.set_ppi(8).is_err());
+        assert_eq!(eh.ppi(), Some(7));
+
+        let mut eh = ExtensionHeader::new(PduType::UlPduSessionInformation);
+
+        assert!(eh.set_ppi(1).is_err());
+        assert!(eh.set_rqi(true).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let mut eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+        assert_eq!(eh.length(), 4);
+
+        eh.set_ppi(1).unwrap();
+        assert_eq!(eh.length(), 8);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let eh = ExtensionHeader::new(PduType::DlPduSessionInformation);
+        assert_eq!(eh.extension_header_type() as u8, ExtensionHeaderType::PDUSessionContainer as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let eh_bytes = [0x02, 0x00, 0b1100_0101, 0b0110_0000, 0x00, 0x00, 0x00, ExtensionHeaderType::NoMore as u8];
+
+        if let Some((eh, pos)) = ExtensionHeader::parse(&eh_bytes) {
+            assert_eq!(eh.next_extension_header_type() as u8, ExtensionHeaderType::NoMore as u8);
+            assert_eq!(eh.pdu_type(), PduType::DlPduSessionInformation);
+            assert_eq!(eh.qfi(), 5);
+            assert_eq!(eh.rqi(), true);
+            assert_eq!(eh.ppi(), Some(3));
+            assert_eq!(pos, 8)
+        }
+        else {
+            assert!(false);
+        }
+
+        let eh_bytes = [0x01, 0x10, 0x07, ExtensionHeaderType::NoMore as u8];
+
+        if let Some((eh, pos)) = ExtensionHeader::parse(&eh_bytes) {
+            assert_eq!(eh.pdu_type(), PduType::UlPduSessionInformation);
+            assert_eq!(eh.qfi(), 7);
+            assert_eq!(eh.rqi(), false);
+            assert_eq!(eh.ppi(), None);
+            assert_eq!(pos, 4)
+        }
+        else {
+            assert!(false);
+        }
+
+        let eh_bytes = [0x01, 0x20, 0x07, ExtensionHeaderType::NoMore as u8];
+
+        assert!(ExtensionHeader::parse(&eh_bytes).is_none());
+        // Truncated
+        let eh_bytes = [0x02, 0x00, 0b1000_0101, 0b0110_0000];
+
+        assert!(ExtensionHeader::parse(&eh_bytes).is_none());
+        assert!(ExtensionHeader::parse(&[]).is_none());
+
+        // PPP set but the length leaves no room for the PPI
+        let eh_bytes = [0x01, 0x00, 0b1000_0101, ExtensionHeaderType::NoMore as u8];
+
+        assert!(ExtensionHeader::parse(&eh_bytes).is_none());
+    }
+}
@@ -1,44 +1,115 @@
 pub mod echo_request;
 pub mod echo_response;
 pub mod create_pdp_context_request;
+pub mod create_pdp_context_response;
+pub mod sgsn_context_request;
+pub mod sgsn_context_response;
+pub mod sgsn_context_acknowledge;
+pub mod forward_relocation_request;
+pub mod forward_relocation_response;
+pub mod forward_relocation_complete;
 pub mod information_elements;
 pub mod g_pdu;
+pub mod end_marker;
+pub mod error_indication;
 
-use information_elements::InformationElement;
+use core::convert::TryFrom;
 
-#[derive(Copy, Clone)]
+use information_elements::{InformationElement, InformationElementTraits};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum MessageType
 {
     EchoRequest = 1,
     EchoResponse = 2,
     CreatePDPContextRequest = 16,
+    CreatePDPContextResponse = 17,
+    ErrorIndication = 26,
+    SGSNContextRequest = 50,
+    SGSNContextResponse = 51,
+    SGSNContextAcknowledge = 52,
+    ForwardRelocationRequest = 53,
+    ForwardRelocationResponse = 54,
+    ForwardRelocationComplete = 55,
+    EndMarker = 254,
     GPDU = 255,
 }
 
-impl From<u8> for MessageType {
-    fn from(v: u8) -> Self {
+impl TryFrom<u8> for MessageType {
+    type Error = String;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
         match v {
-            1 => MessageType::EchoRequest,
-            2 => MessageType::EchoResponse,
-            16 => MessageType::CreatePDPContextRequest,
-            255 => MessageType::GPDU,
-            _ => panic!(format!("Unsupported Message Type ({})",v ))
+            1 => Ok(MessageType::EchoRequest),
+            2 => Ok(MessageType::EchoResponse),
+            16 => Ok(MessageType::CreatePDPContextRequest),
+            17 => Ok(MessageType::CreatePDPContextResponse),
+            26 => Ok(MessageType::ErrorIndication),
+            50 => Ok(MessageType::SGSNContextRequest),
+            51 => Ok(MessageType::SGSNContextResponse),
+            52 => Ok(MessageType::SGSNContextAcknowledge),
+            53 => Ok(MessageType::ForwardRelocationRequest),
+            54 => Ok(MessageType::ForwardRelocationResponse),
+            55 => Ok(MessageType::ForwardRelocationComplete),
+            254 => Ok(MessageType::EndMarker),
+            255 => Ok(MessageType::GPDU),
+            _ => Err(format!("Unsupported Message Type ({})",v ))
         }
     }
 }
 
+// The IEs of a message in the order they are generated
+pub type Ies<'a> = alloc::vec::IntoIter<&'a dyn InformationElementTraits>;
+
 pub trait MessageTraits {
     fn message_type(&self) -> MessageType;
-    fn length(&self) -> u16;
-    fn generate(&self, buffer: &mut[u8]) -> usize;
     fn push_ie(&mut self, ie: InformationElement);
     fn pop_ie(&mut self) -> Option<InformationElement>;
+    fn ies(&self) -> Ies<'_>;
+
+    // Messages that aren't made of IEs (i.e. G-PDU) override length and generate
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+}
+
+// The IEs of a message that keeps them in a list
+pub fn ies_of(information_elements: &[InformationElement]) -> Ies<'_> {
+    information_elements.iter().map(|ie| ie as &dyn InformationElementTraits).collect::<Vec<_>>().into_iter()
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum Message {
     EchoRequest(echo_request::Message),
     EchoResponse(echo_response::Message),
     CreatePDPContextRequest(create_pdp_context_request::Message),
+    CreatePDPContextResponse(create_pdp_context_response::Message),
+    ErrorIndication(error_indication::Message),
+    SGSNContextRequest(sgsn_context_request::Message),
+    SGSNContextResponse(sgsn_context_response::Message),
+    SGSNContextAcknowledge(sgsn_context_acknowledge::Message),
+    ForwardRelocationRequest(forward_relocation_request::Message),
+    ForwardRelocationResponse(forward_relocation_response::Message),
+    ForwardRelocationComplete(forward_relocation_complete::Message),
+    EndMarker(end_marker::Message),
     GPDU(g_pdu::Message)
 }
 
@@ -84,6 +155,123 @@ impl Message {
                     None
                 }
             },
+            MessageType::CreatePDPContextResponse => {
+                if let Some((m, pos)) = create_pdp_context_response::Message::parse(buffer) {
+                    Some(
+                        (
+                            Message::CreatePDPContextResponse(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::ErrorIndication => {
+                if let Some((m, pos)) = error_indication::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::ErrorIndication(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::SGSNContextRequest => {
+                if let Some((m, pos)) = sgsn_context_request::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::SGSNContextRequest(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::SGSNContextResponse => {
+                if let Some((m, pos)) = sgsn_context_response::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::SGSNContextResponse(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::SGSNContextAcknowledge => {
+                if let Some((m, pos)) = sgsn_context_acknowledge::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::SGSNContextAcknowledge(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::ForwardRelocationRequest => {
+                if let Some((m, pos)) = forward_relocation_request::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::ForwardRelocationRequest(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::ForwardRelocationResponse => {
+                if let Some((m, pos)) = forward_relocation_response::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::ForwardRelocationResponse(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::ForwardRelocationComplete => {
+                if let Some((m, pos)) = forward_relocation_complete::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::ForwardRelocationComplete(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
+            MessageType::EndMarker => {
+                if let Some((m, pos)) = end_marker::Message::parse(buffer) {
+                    Some(
+                        (   
+                            Message::EndMarker(m),
+                            pos
+                        )
+                    )
+                }
+                else {
+                    None
+                }
+            },
             MessageType::GPDU => {
                 if let Some((m, pos)) = g_pdu::Message::parse(buffer) {
                     Some(
@@ -107,6 +295,15 @@ impl MessageTraits for Message {
             Message::EchoRequest(m) => m.message_type(),
             Message::EchoResponse(m) => m.message_type(),
             Message::CreatePDPContextRequest(m) => m.message_type(),
+            Message::CreatePDPContextResponse(m) => m.message_type(),
+            Message::ErrorIndication(m) => m.message_type(),
+            Message::SGSNContextRequest(m) => m.message_type(),
+            Message::SGSNContextResponse(m) => m.message_type(),
+            Message::SGSNContextAcknowledge(m) => m.message_type(),
+            Message::ForwardRelocationRequest(m) => m.message_type(),
+            Message::ForwardRelocationResponse(m) => m.message_type(),
+            Message::ForwardRelocationComplete(m) => m.message_type(),
+            Message::EndMarker(m) => m.message_type(),
             Message::GPDU(m)=> m.message_type(),
         }
     }
@@ -116,6 +313,15 @@ impl MessageTraits for Message {
             Message::EchoRequest(m) => m.length(),
             Message::EchoResponse(m) => m.length(),
             Message::CreatePDPContextRequest(m) => m.length(),
+            Message::CreatePDPContextResponse(m) => m.length(),
+            Message::ErrorIndication(m) => m.length(),
+            Message::SGSNContextRequest(m) => m.length(),
+            Message::SGSNContextResponse(m) => m.length(),
+            Message::SGSNContextAcknowledge(m) => m.length(),
+            Message::ForwardRelocationRequest(m) => m.length(),
+            Message::ForwardRelocationResponse(m) => m.length(),
+            Message::ForwardRelocationComplete(m) => m.length(),
+            Message::EndMarker(m) => m.length(),
             Message::GPDU(m)=> m.length(),
         }
     }
@@ -125,15 +331,51 @@ impl MessageTraits for Message {
             Message::EchoRequest(m) => m.generate(buffer),
             Message::EchoResponse(m) => m.generate(buffer),
             Message::CreatePDPContextRequest(m) => m.generate(buffer),
+            Message::CreatePDPContextResponse(m) => m.generate(buffer),
+            Message::ErrorIndication(m) => m.generate(buffer),
+            Message::SGSNContextRequest(m) => m.generate(buffer),
+            Message::SGSNContextResponse(m) => m.generate(buffer),
+            Message::SGSNContextAcknowledge(m) => m.generate(buffer),
+            Message::ForwardRelocationRequest(m) => m.generate(buffer),
+            Message::ForwardRelocationResponse(m) => m.generate(buffer),
+            Message::ForwardRelocationComplete(m) => m.generate(buffer),
+            Message::EndMarker(m) => m.generate(buffer),
             Message::GPDU(m)=> m.generate(buffer),
         }
     }
 
+    fn ies(&self) -> Ies<'_> {
+        match self {
+            Message::EchoRequest(m) => m.ies(),
+            Message::EchoResponse(m) => m.ies(),
+            Message::CreatePDPContextRequest(m) => m.ies(),
+            Message::CreatePDPContextResponse(m) => m.ies(),
+            Message::ErrorIndication(m) => m.ies(),
+            Message::SGSNContextRequest(m) => m.ies(),
+            Message::SGSNContextResponse(m) => m.ies(),
+            Message::SGSNContextAcknowledge(m) => m.ies(),
+            Message::ForwardRelocationRequest(m) => m.ies(),
+            Message::ForwardRelocationResponse(m) => m.ies(),
+            Message::ForwardRelocationComplete(m) => m.ies(),
+            Message::EndMarker(m) => m.ies(),
+            Message::GPDU(m)=> m.ies(),
+        }
+    }
+
     fn push_ie(&mut self, ie: information_elements::InformationElement) {
         match self {
             Message::EchoRequest(m) => m.push_ie(ie),
             Message::EchoResponse(m) =>  m.push_ie(ie),
             Message::CreatePDPContextRequest(m) =>  m.push_ie(ie),
+            Message::CreatePDPContextResponse(m) =>  m.push_ie(ie),
+            Message::ErrorIndication(m) => m.push_ie(ie),
+            Message::SGSNContextRequest(m) => m.push_ie(ie),
+            Message::SGSNContextResponse(m) => m.push_ie(ie),
+            Message::SGSNContextAcknowledge(m) => m.push_ie(ie),
+            Message::ForwardRelocationRequest(m) => m.push_ie(ie),
+            Message::ForwardRelocationResponse(m) => m.push_ie(ie),
+            Message::ForwardRelocationComplete(m) => m.push_ie(ie),
+            Message::EndMarker(m) => m.push_ie(ie),
             Message::GPDU(m)=> m.push_ie(ie),
         }
     }
@@ -142,7 +384,43 @@ impl MessageTraits for Message {
             Message::EchoRequest(m) => m.pop_ie(),
             Message::EchoResponse(m) =>  m.pop_ie(),
             Message::CreatePDPContextRequest(m) =>  m.pop_ie(),
+            Message::CreatePDPContextResponse(m) =>  m.pop_ie(),
+            Message::ErrorIndication(m) => m.pop_ie(),
+            Message::SGSNContextRequest(m) => m.pop_ie(),
+            Message::SGSNContextResponse(m) => m.pop_ie(),
+            Message::SGSNContextAcknowledge(m) => m.pop_ie(),
+            Message::ForwardRelocationRequest(m) => m.pop_ie(),
+            Message::ForwardRelocationResponse(m) => m.pop_ie(),
+            Message::ForwardRelocationComplete(m) => m.pop_ie(),
+            Message::EndMarker(m) => m.pop_ie(),
             Message::GPDU(m)=> m.pop_ie(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            Message::EchoRequest(m) => m.dissect(d),
+            Message::EchoResponse(m) => m.dissect(d),
+            Message::CreatePDPContextRequest(m) => m.dissect(d),
+            Message::CreatePDPContextResponse(m) => m.dissect(d),
+            Message::ErrorIndication(m) => m.dissect(d),
+            Message::SGSNContextRequest(m) => m.dissect(d),
+            Message::SGSNContextResponse(m) => m.dissect(d),
+            Message::SGSNContextAcknowledge(m) => m.dissect(d),
+            Message::ForwardRelocationRequest(m) => m.dissect(d),
+            Message::ForwardRelocationResponse(m) => m.dissect(d),
+            Message::ForwardRelocationComplete(m) => m.dissect(d),
+            Message::EndMarker(m) => m.dissect(d),
+            Message::GPDU(m) => m.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_message<T: MessageTraits, F: FnOnce(&mut Dissector)>(m: &T, d: &mut Dissector, f: F) {
+    // Writes the message type followed by the IEs of the message written by f
+    let message_type = m.message_type();
+
+    d.tree(&format!("{:?} ({}), Length: {}", message_type, message_type as u8, m.length()), f);
+}
@@ -1,10 +1,19 @@
 use super::{
-    MessageTraits, 
+    Ies,
+    MessageTraits,
     MessageType,
+    dissect_message,
+    ies_of,
 };
 
-use super::information_elements::{InformationElementTraits, InformationElement};
+use super::information_elements::InformationElement;
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
     /*
         --------------------------------------------|---------------------------|-------------------------                             
@@ -82,26 +91,19 @@ impl MessageTraits for Message {
         MessageType::CreatePDPContextRequest
     }
 
-    fn length(&self) -> u16 {
-        let mut length = 0;
-
-        for ie in self.information_elements.iter() {
-            length = length + ie.length();
-        }
-
-        length
-    }
-    fn generate(&self, buffer: &mut[u8]) -> usize {
+    fn ies(&self) -> Ies<'_> {
         // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
-        let mut pos = 0;
-
-        for ie in self.information_elements.iter() {
-            let ie_size = ie.generate(&mut buffer[pos..]);
-
-            pos = pos + ie_size;
-        }
+        ies_of(&self.information_elements)
+    }
+}
 
-        pos
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
     }
 }
 
@@ -0,0 +1,221 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::{InformationElement, cause};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   7.7.1
+        Reordering Required                         |    Conditional            |   7.7.6
+        Recovery                                    |    Optional               |   7.7.11
+        Tunnel Endpoint Identifier Data I           |    Conditional            |   7.7.13
+        Tunnel Endpoint Identifier Control Plane    |    Conditional            |   7.7.14
+        NSAPI                                       |    Conditional            |   7.7.17
+        Charging ID                                 |    Conditional            |   7.7.26
+        End User Address                            |    Conditional            |   7.7.27
+        Protocol Configuration Options              |    Optional               |   7.7.31
+        GGSN Address for Control Plane              |    Conditional            |   GSN Address 7.7.32
+        GGSN Address for user traffic               |    Conditional            |   GSN Address 7.7.32
+        Alternative GGSN Address for Control Plane  |    Conditional            |   GSN Address 7.7.32
+        Alternative GGSN Address for user traffic   |    Conditional            |   GSN Address 7.7.32
+        Quality of Service Profile                  |    Conditional            |   7.7.34
+        Charging Gateway Address                    |    Optional               |   7.7.44
+        Alternative Charging Gateway Address        |    Optional               |   7.7.44
+        Common Flags                                |    Optional               |   7.7.48
+        APN Restriction                             |    Optional               |   7.7.49
+        MS Info Change Reporting Action             |    Optional               |   7.7.80
+        Bearer Control Mode                         |    Optional               |   7.7.83
+        Evolved Allocation/Retention Priority I     |    Optional               |   7.7.91
+        Extended Common Flags                       |    Optional               |   7.7.93
+        CSG Information Reporting Action            |    Optional               |   7.7.95
+        APN-AMBR                                    |    Optional               |   7.7.98
+        GGSN Back-Off Time                          |    Optional               |   7.7.102
+        Extended Common Flags II                    |    Optional               |   7.7.118
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new(cause_code: cause::CauseCode) -> Self {
+        // The Cause is mandatory and has the lowest IE type so it is always first
+        Message {
+            information_elements: vec![InformationElement::Cause(cause::InformationElement::new(cause_code))]
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+
+    pub fn cause(&self) -> Option<&cause::InformationElement> {
+        self.information_elements.iter().find_map(|ie| {
+            match ie {
+                InformationElement::Cause(ie) => Some(ie),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.cause().map_or(false, |ie| ie.is_accepted())
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::CreatePDPContextResponse
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new(cause::CauseCode::RequestAccepted);
+
+        m.push_ie(
+            InformationElement::TeidDataI(information_elements::teid_data_i::InformationElement::new(0x12345678))
+        );
+
+        m.push_ie(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8, 128,
+            InformationElementType::TeidDataI as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = Message::new(cause::CauseCode::RequestAccepted);
+
+        assert_eq!(m.length(), 2);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new(cause::CauseCode::RequestAccepted);
+        assert_eq!(m.message_type() as u8, MessageType::CreatePDPContextResponse as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::Cause as u8, 211,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]) {
+            assert_eq!(pos, 9);
+            assert_eq!(m.information_elements.len(), 2);
+
+            assert_eq!(m.cause().map(|ie| ie.cause_code), Some(cause::CauseCode::AllDynamicPDPAddressesAreOccupied));
+            assert!(!m.is_accepted());
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((m, _pos)) = Message::parse(&[InformationElementType::Cause as u8, 129]) {
+            assert!(m.is_accepted());
+        }
+        else {
+            assert!(false);
+        }
+
+        // Without a Cause the response isn't accepted
+        if let Some((m, _pos)) = Message::parse(&[]) {
+            assert_eq!(m.cause(), None);
+            assert!(!m.is_accepted());
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,38 +1,89 @@
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, dissect_message};
 
-use super::information_elements::InformationElement;
+use super::information_elements::{InformationElementTraits, InformationElement, private_extension};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub private_extension: Option<private_extension::InformationElement>,
 }
 
 impl Message {
     pub fn new() -> Message {
-        Message {}
+        Message {
+            private_extension: None,
+        }
     }
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        Some((Message::new(),0))
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut m = Message::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                // IEs that don't belong in an Echo Request are ignored
+                m.push_ie(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some((m, pos))
     }
 }
 
 impl MessageTraits for Message {
-    fn push_ie(&mut self, _ie: InformationElement)
+    fn push_ie(&mut self, ie: InformationElement)
     {
-        ()
+        if let InformationElement::PrivateExtension(ie) = ie {
+            self.private_extension = Some(ie);
+        }
     }
 
     fn pop_ie(&mut self) -> Option<InformationElement>
     {
-        None
+        self.private_extension.take().map(InformationElement::PrivateExtension)
     }
 
     fn message_type(&self) -> MessageType {
         MessageType::EchoRequest
     }
-    fn length(&self) -> u16 {
-        0
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ie) = &self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
     }
-    fn generate(&self, _buffer: &mut[u8]) -> usize {
-        0
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ie) = &self.private_extension {
+                ie.dissect(d);
+            }
+        });
     }
 }
 
@@ -41,7 +92,8 @@ mod tests {
     use super::*;
     use crate::MTU;
     use crate::gtp_v1::packet::messages::MessageTraits;
-    use crate::gtp_v1::packet::messages::MessageType; 
+    use crate::gtp_v1::packet::messages::MessageType;
+    use crate::gtp_v1::packet::messages::information_elements::{InformationElementType, recovery};
 
     #[test]
     fn test_generate() {
@@ -52,12 +104,22 @@ mod tests {
         let pos = m.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [0u8; 0]);
+
+        let mut m = Message::new();
+        m.private_extension = Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56]);
     }
-    
+
     #[test]
     fn test_length() {
-        let m = Message::new();
-        assert_eq!(m.length(), 0)
+        let mut m = Message::new();
+        assert_eq!(m.length(), 0);
+
+        m.private_extension = Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap());
+        assert_eq!(m.length(), 6);
     }
 
     #[test]
@@ -66,8 +128,41 @@ mod tests {
         assert_eq!(m.message_type() as u8, MessageType::EchoRequest as u8)
     }
 
+    #[test]
+    fn test_push_pop_ie() {
+        let mut m = Message::new();
+
+        // Not part of an Echo Request
+        m.push_ie(InformationElement::Recovery(recovery::InformationElement::new(1)));
+        assert_eq!(m, Message::new());
+
+        let ie = private_extension::InformationElement::new(0x1234, &[0x56]).unwrap();
+        m.push_ie(InformationElement::PrivateExtension(ie.clone()));
+        assert_eq!(m.private_extension, Some(ie.clone()));
+
+        assert_eq!(m.pop_ie(), Some(InformationElement::PrivateExtension(ie)));
+        assert_eq!(m.pop_ie(), None);
+    }
+
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert_eq!(m, Message::new());
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((m, pos)) = Message::parse(&[InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56]) {
+            assert_eq!(pos, 6);
+            assert_eq!(m.private_extension, Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap()));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Unknown TV format IE
+        assert!(Message::parse(&[100, 0]).is_none());
     }
 }
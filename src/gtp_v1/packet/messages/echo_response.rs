@@ -1,38 +1,114 @@
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, dissect_message};
 
-use super::information_elements::InformationElement;
+use super::information_elements::{InformationElementTraits, InformationElement, recovery, private_extension};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Recovery                                    |    Mandatory              |   7.7.11
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub recovery: recovery::InformationElement,
+    pub private_extension: Option<private_extension::InformationElement>,
 }
 
 impl Message {
-    pub fn new() -> Message {
-        Message {}
+    pub fn new(recovery: recovery::InformationElement) -> Message {
+        Message {
+            recovery,
+            private_extension: None,
+        }
     }
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        Some((Message::new(),0))
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut recovery = None;
+        let mut private_extension = None;
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                match ie {
+                    InformationElement::Recovery(ie) => recovery = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // IEs that don't belong in an Echo Response are ignored
+                    ie => debug!("Ignoring {:?} IE in {:?}", ie.information_element_type(), MessageType::EchoResponse),
+                }
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        // Recovery is mandatory
+        let recovery = recovery?;
+
+        Some(
+            (
+                Message {
+                    recovery,
+                    private_extension,
+                },
+                pos
+            )
+        )
     }
 }
 
 impl MessageTraits for Message {
-    fn push_ie(&mut self, _ie: InformationElement)
+    fn push_ie(&mut self, ie: InformationElement)
     {
-        ()
+        match ie {
+            InformationElement::Recovery(ie) => self.recovery = ie,
+            InformationElement::PrivateExtension(ie) => self.private_extension = Some(ie),
+            _ => (),
+        }
     }
 
     fn pop_ie(&mut self) -> Option<InformationElement>
     {
-        None
+        // Recovery is mandatory so it is never removed
+        self.private_extension.take().map(InformationElement::PrivateExtension)
     }
 
     fn message_type(&self) -> MessageType {
         MessageType::EchoResponse
     }
-    fn length(&self) -> u16 {
-        0
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = vec![&self.recovery];
+
+        if let Some(ie) = &self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
     }
-    fn generate(&self, _buffer: &mut[u8]) -> usize {
-        0
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.recovery.dissect(d);
+
+            if let Some(ie) = &self.private_extension {
+                ie.dissect(d);
+            }
+        });
     }
 }
 
@@ -41,33 +117,86 @@ mod tests {
     use super::*;
     use crate::MTU;
     use crate::gtp_v1::packet::messages::MessageTraits;
-    use crate::gtp_v1::packet::messages::MessageType; 
+    use crate::gtp_v1::packet::messages::MessageType;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
 
     #[test]
     fn test_generate() {
         let mut buffer = [0; MTU];
 
-        let m = Message::new();
+        let mut m = Message::new(recovery::InformationElement::new(5));
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Recovery as u8, 5]);
+
+        m.private_extension = Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap());
 
         let pos = m.generate(&mut buffer);
 
-        assert_eq!(buffer[..pos], [0u8; 0]);
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Recovery as u8, 5,
+            InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56,
+        ]);
     }
-    
+
     #[test]
     fn test_length() {
-        let m = Message::new();
-        assert_eq!(m.length(), 0)
+        let mut m = Message::new(recovery::InformationElement::new(5));
+        assert_eq!(m.length(), 2);
+
+        m.private_extension = Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap());
+        assert_eq!(m.length(), 8);
+    }
+
+    #[test]
+    fn test_ies() {
+        let mut m = Message::new(recovery::InformationElement::new(5));
+        m.private_extension = Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap());
+
+        let types: Vec<u8> = m.ies().map(|ie| ie.information_element_type() as u8).collect();
+
+        assert_eq!(types, [InformationElementType::Recovery as u8, InformationElementType::PrivateExtension as u8]);
+        assert_eq!(m.ies().map(|ie| ie.length()).sum::<u16>(), m.length());
     }
 
     #[test]
     fn test_message_type() {
-        let m = Message::new();
+        let m = Message::new(recovery::InformationElement::new(0));
         assert_eq!(m.message_type() as u8, MessageType::EchoResponse as u8)
     }
 
+    #[test]
+    fn test_push_pop_ie() {
+        let mut m = Message::new(recovery::InformationElement::new(0));
+
+        m.push_ie(InformationElement::Recovery(recovery::InformationElement::new(7)));
+        assert_eq!(m.recovery.restart_counter(), 7);
+
+        let ie = private_extension::InformationElement::new(0x1234, &[0x56]).unwrap();
+        m.push_ie(InformationElement::PrivateExtension(ie.clone()));
+
+        assert_eq!(m.pop_ie(), Some(InformationElement::PrivateExtension(ie)));
+        assert_eq!(m.pop_ie(), None);
+        assert_eq!(m, Message::new(recovery::InformationElement::new(7)));
+    }
+
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::Recovery as u8, 5,
+            InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56,
+        ]) {
+            assert_eq!(pos, 8);
+            assert_eq!(m.recovery.restart_counter(), 5);
+            assert_eq!(m.private_extension, Some(private_extension::InformationElement::new(0x1234, &[0x56]).unwrap()));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Missing Recovery
+        assert!(Message::parse(&[]).is_none());
+        assert!(Message::parse(&[InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56]).is_none());
     }
 }
@@ -0,0 +1,144 @@
+use super::{Ies, MessageTraits, MessageType, dissect_message};
+
+use super::information_elements::{InformationElementTraits, InformationElement, private_extension};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        Sent on the old path after switching a tunnel (e.g. at handover) to indicate that no more
+        G-PDUs will follow on it (TS 29.281 7.3.2). Only a Private Extension IE may be present.
+        Any extension headers are carried in the header.
+    */
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            private_extension: None,
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut m = Message::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                // IEs that don't belong in an End Marker are ignored
+                m.push_ie(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some((m, pos))
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        if let InformationElement::PrivateExtension(ie) = ie {
+            self.private_extension = Some(ie);
+        }
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.private_extension.take().map(InformationElement::PrivateExtension)
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::EndMarker
+    }
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ie) = &self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ie) = &self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::MessageTraits;
+    use crate::gtp_v1::packet::messages::MessageType; 
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::new();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0u8; 0]);
+    }
+    
+    #[test]
+    fn test_length() {
+        let m = Message::new();
+        assert_eq!(m.length(), 0)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::EndMarker as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[]) {
+            assert_eq!(m.length(), 0);
+            assert_eq!(pos, 0);
+        }
+        else {
+            assert!(false);
+        }
+
+        let message_bytes = [InformationElementType::PrivateExtension as u8, 0, 4, 0x28, 0xAF, 0xDE, 0xAD];
+
+        if let Some((m, pos)) = Message::parse(&message_bytes) {
+            assert_eq!(pos, 7);
+            assert_eq!(m.private_extension, Some(private_extension::InformationElement::new(10415, &[0xDE, 0xAD]).unwrap()));
+
+            // Generated as received
+            let mut buffer = [0; MTU];
+            let pos = m.generate(&mut buffer);
+            assert_eq!(buffer[..pos], message_bytes);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
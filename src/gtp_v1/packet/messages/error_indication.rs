@@ -0,0 +1,212 @@
+use core::net::IpAddr;
+
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::{InformationElement, teid_data_i, gsn_address};
+
+use crate::gtp_v1::packet::header::Header;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Tunnel Endpoint Identifier Data I           |    Mandatory              |   7.7.13
+        GTP-U Peer Address                          |    Mandatory              |   7.7.32
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+
+        Sent when a G-PDU is received for a TEID that doesn't exist (TS 29.281 7.3.1). See Packet::error_indication
+        for the header that goes with it.
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new(teid: u32, peer_address: IpAddr) -> Self {
+        // teid is the TEID of the G-PDU that was rejected and peer_address is the address it was sent to
+        Message {
+            information_elements: vec![
+                InformationElement::TeidDataI(teid_data_i::InformationElement::new(teid)),
+                InformationElement::GsnAddress(gsn_address::InformationElement::new(peer_address)),
+            ]
+        }
+    }
+
+    pub fn for_g_pdu(header: &Header, local_address: IpAddr) -> Self {
+        // An Error Indication rejecting a received G-PDU. local_address is the address the G-PDU was received on
+        Self::new(header.teid(), local_address)
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        let m = Message {
+            information_elements
+        };
+
+        // Without both mandatory IEs the receiver can't tell which tunnel was rejected
+        if m.teid().is_none() || m.peer_address().is_none() {
+            return None
+        }
+
+        Some((m, pos))
+    }
+
+    pub fn teid(&self) -> Option<u32> {
+        // The TEID of the G-PDU that was rejected
+        self.information_elements.iter().find_map(|ie| {
+            match ie {
+                InformationElement::TeidDataI(ie) => Some(ie.teid()),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn peer_address(&self) -> Option<IpAddr> {
+        // The address of the node that rejected the G-PDU
+        self.information_elements.iter().find_map(|ie| {
+            match ie {
+                InformationElement::GsnAddress(ie) => Some(ie.gsn_address()),
+                _ => None,
+            }
+        })
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::ErrorIndication
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use std::net::Ipv4Addr;
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::new(0x12345678, IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::TeidDataI as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = Message::new(0x12345678, IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+
+        assert_eq!(m.length(), 12);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new(0x12345678, IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+        assert_eq!(m.message_type() as u8, MessageType::ErrorIndication as u8)
+    }
+
+    #[test]
+    fn test_for_g_pdu() {
+        let mut h = Header::new(MessageType::GPDU);
+        h.set_teid(0x87654321);
+
+        let m = Message::for_g_pdu(&h, IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        assert_eq!(m.teid(), Some(0x87654321));
+        assert_eq!(m.peer_address(), Some(IpAddr::V4(Ipv4Addr::new(10,0,0,1))));
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::TeidDataI as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+            InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56,
+        ]) {
+            assert_eq!(pos, 18);
+            assert_eq!(m.information_elements.len(), 3);
+            assert_eq!(m.teid(), Some(0x12345678));
+            assert_eq!(m.peer_address(), Some(IpAddr::V4(Ipv4Addr::new(192,168,0,1))));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Missing GTP-U Peer Address
+        assert!(Message::parse(&[InformationElementType::TeidDataI as u8, 0x12, 0x34, 0x56, 0x78]).is_none());
+
+        // Missing TEID Data I
+        assert!(Message::parse(&[InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1]).is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::InformationElement;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new() -> Self {
+
+        Message {
+            information_elements: Vec::new()
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::ForwardRelocationComplete
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::new();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [0u8; 0]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = Message::new();
+
+        assert_eq!(m.length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::ForwardRelocationComplete as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert_eq!(m.information_elements.len(), 0);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((m, pos)) = Message::parse(&[InformationElementType::PrivateExtension as u8, 0, 3, 0x12, 0x34, 0x56]) {
+            assert_eq!(pos, 6);
+            assert_eq!(m.information_elements.len(), 1);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::InformationElement;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Conditional            |   7.7.2
+        Tunnel Endpoint Identifier Control Plane    |    Mandatory              |   7.7.14
+        RANAP Cause                                 |    Mandatory              |   7.7.18
+        Packet Flow ID                              |    Optional               |   7.7.22
+        Charging Characteristics                    |    Optional               |   7.7.23
+        MM Context                                  |    Mandatory              |   7.7.28
+        PDP Context                                 |    Conditional            |   7.7.29
+        SGSN Address for Control plane              |    Mandatory              |   7.7.32
+        Target Identification                       |    Mandatory              |   7.7.37
+        UTRAN transparent container                 |    Mandatory              |   7.7.38
+        PDP Context Prioritization                  |    Optional               |   7.7.45
+        MBMS UE Context                             |    Optional               |   7.7.55
+        Selected PLMN ID                            |    Optional               |   7.7.64
+        BSS Container                               |    Optional               |   7.7.72
+        Cell Identification                         |    Optional               |   7.7.73
+        BSSGP Cause                                 |    Optional               |   7.7.75
+        PS Handover XID Parameters                  |    Optional               |   7.7.79
+        Direct Tunnel Flags                         |    Optional               |   7.7.81
+        Reliable INTER RAT HANDOVER INFO            |    Optional               |   7.7.87
+        Subscribed RFSP Index                       |    Optional               |   7.7.88
+        RFSP Index in use                           |    Optional               |   7.7.88
+        Co-located GGSN-PGW FQDN                    |    Optional               |   7.7.90
+        Evolved Allocation/Retention Priority II    |    Optional               |   7.7.92
+        Extended Common Flags                       |    Optional               |   7.7.93
+        CSG ID                                      |    Optional               |   7.7.96
+        CSG Membership Indication                   |    Optional               |   7.7.97
+        UE Network Capability                       |    Optional               |   7.7.99
+        UE-AMBR                                     |    Optional               |   7.7.100
+        APN-AMBR with NSAPI                         |    Optional               |   7.7.101
+        Signalling Priority Indication with NSAPI   |    Optional               |   7.7.104
+        Higher bitrates than 16 Mbps flag           |    Optional               |   7.7.105
+        Additional MM context for SRVCC             |    Optional               |   7.7.107
+        Additional flags for SRVCC                  |    Optional               |   7.7.108
+        STN-SR                                      |    Optional               |   7.7.109
+        C-MSISDN                                    |    Optional               |   7.7.110
+        Extended RANAP Cause                        |    Optional               |   7.7.111
+        eNodeB ID                                   |    Optional               |   7.7.112
+        Selection Mode with NSAPI                   |    Optional               |   7.7.113
+        UE Usage Type                               |    Optional               |   7.7.117
+        Extended Common Flags II                    |    Optional               |   7.7.118
+        UE SCEF PDN Connection                      |    Optional               |   7.7.121
+        Alternative GGSN Address for control Plane  |    Optional               |   7.7.32
+        Alternative GGSN Address for user traffic   |    Optional               |   7.7.32
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new() -> Self {
+
+        Message {
+            information_elements: Vec::new()
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::ForwardRelocationRequest
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::RanapCause(information_elements::ranap_cause::InformationElement::new(43))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::RanapCause as u8, 43,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::RanapCause(information_elements::ranap_cause::InformationElement::new(43))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        assert_eq!(m.length(), 14);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::ForwardRelocationRequest as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::RanapCause(information_elements::ranap_cause::InformationElement::new(43))
+        );
+
+        m.information_elements.push(
+            InformationElement::MmContext(information_elements::mm_context::InformationElement::new(
+                information_elements::mm_context::SecurityContext::UmtsKeysAndQuintuplets {
+                    ksi: 1,
+                    ck: [0xC0; 16],
+                    ik: [0x1C; 16],
+                    quintuplets: vec![],
+                },
+                0x0A0B
+            ).unwrap())
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        m.information_elements.push(
+            InformationElement::TargetIdentification(information_elements::target_identification::InformationElement::new(
+                PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, None
+            ).unwrap())
+        );
+
+        m.information_elements.push(
+            InformationElement::UtranTransparentContainer(information_elements::utran_transparent_container::InformationElement::new(&[0xDE, 0xAD, 0xBE, 0xEF]).unwrap())
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        if let Some((m, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(m.information_elements.len(), 6);
+
+            if let InformationElement::MmContext(ie) = &m.information_elements[2] {
+                assert_eq!(ie.security_context.security_mode(), information_elements::mm_context::SecurityMode::UmtsKeysAndQuintuplets);
+                assert_eq!(ie.drx_parameter, 0x0A0B);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::GsnAddress(ie) = &m.information_elements[3] {
+                assert_eq!(ie.gsn_address(), IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::TargetIdentification(ie) = &m.information_elements[4] {
+                assert_eq!(ie.lac, 0x1234);
+                assert_eq!(ie.rnc_id, 0x789);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::UtranTransparentContainer(ie) = &m.information_elements[5] {
+                assert_eq!(ie.utran_transparent_field, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
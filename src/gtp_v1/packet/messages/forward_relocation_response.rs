@@ -0,0 +1,212 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::InformationElement;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   7.7.1
+        Tunnel Endpoint Identifier Control Plane    |    Conditional            |   7.7.14
+        Tunnel Endpoint Identifier Data II          |    Optional               |   7.7.15
+        RANAP Cause                                 |    Conditional            |   7.7.18
+        SGSN Address for Control plane              |    Conditional            |   7.7.32
+        SGSN Address for User Traffic               |    Optional               |   7.7.32
+        UTRAN transparent container                 |    Optional               |   7.7.38
+        RAB Setup Information                       |    Conditional            |   7.7.39
+        Additional RAB Setup Information            |    Conditional            |   7.7.45A
+        SGSN Number                                 |    Optional               |   7.7.47
+        BSS Container                               |    Optional               |   7.7.72
+        BSSGP Cause                                 |    Optional               |   7.7.75
+        List of set-up PFCs                         |    Optional               |   7.7.78
+        Extended RANAP Cause                        |    Optional               |   7.7.111
+        Node Identifier                             |    Optional               |   7.7.119
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new() -> Self {
+
+        Message {
+            information_elements: Vec::new()
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::ForwardRelocationResponse
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Cause(information_elements::cause::InformationElement::new(information_elements::cause::CauseCode::RequestAccepted))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::RanapCause(information_elements::ranap_cause::InformationElement::new(43))
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8, 128,
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::RanapCause as u8, 43,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Cause(information_elements::cause::InformationElement::new(information_elements::cause::CauseCode::RequestAccepted))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::RanapCause(information_elements::ranap_cause::InformationElement::new(43))
+        );
+
+        assert_eq!(m.length(), 9);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::ForwardRelocationResponse as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::Cause as u8, 128,
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::RanapCause as u8, 43,
+        ]) {
+            assert_eq!(pos, 9);
+            assert_eq!(m.information_elements.len(), 3);
+
+            if let InformationElement::Cause(ie) = &m.information_elements[0] {
+                assert_eq!(ie.cause(), 128);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::TeidControlPlane(ie) = &m.information_elements[1] {
+                assert_eq!(ie.teid(), 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::RanapCause(ie) = &m.information_elements[2] {
+                assert_eq!(ie.ranap_cause(), 43);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,9 +1,17 @@
-use std::convert::TryInto;
+use core::convert::TryInto;
 
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, dissect_message};
 
 use super::information_elements::InformationElement;
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
     pub t_pdu: Vec<u8>
 }
@@ -61,6 +69,11 @@ impl MessageTraits for Message {
         MessageType::GPDU
     }
 
+    fn ies(&self) -> Ies<'_> {
+        // The T-PDU isn't made of IEs
+        Vec::new().into_iter()
+    }
+
     fn length(&self) -> u16 {
         self.t_pdu.len().try_into().unwrap()
     }
@@ -74,6 +87,14 @@ impl MessageTraits for Message {
     }
 }
 
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            d.hex("T-PDU", &self.t_pdu);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
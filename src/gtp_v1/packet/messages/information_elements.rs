@@ -1,17 +1,111 @@
+pub mod cause;
 pub mod imsi;
+pub mod rai;
+pub mod recovery;
 pub mod teid_data_i;
+pub mod teid_control_plane;
+pub mod teid_data_ii;
 pub mod nsapi;
+pub mod ranap_cause;
 pub mod gsn_address;
 pub mod qos_profile;
+pub mod mm_context;
+pub mod target_identification;
+pub mod utran_transparent_container;
+pub mod private_extension;
+pub mod end_user_address;
 
-#[derive(Copy, Clone, Debug)]
+use core::convert::{TryFrom, TryInto};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum InformationElementType
 {
+    Cause = 1,
     Imsi = 2,
+    Rai = 3,
+    Recovery = 14,
     TeidDataI = 16,
+    TeidControlPlane = 17,
+    TeidDataII = 18,
     Nsapi = 20,
+    RanapCause = 21,
+    EndUserAddress = 128,
+    MmContext = 129,
     GsnAddress = 133,
     QoSProfile = 135,
+    TargetIdentification = 138,
+    UtranTransparentContainer = 139,
+    PrivateExtension = 255,
+}
+
+impl TryFrom<u8> for InformationElementType
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(InformationElementType::Cause),
+            2 => Ok(InformationElementType::Imsi),
+            3 => Ok(InformationElementType::Rai),
+            14 => Ok(InformationElementType::Recovery),
+            16 => Ok(InformationElementType::TeidDataI),
+            17 => Ok(InformationElementType::TeidControlPlane),
+            18 => Ok(InformationElementType::TeidDataII),
+            20 => Ok(InformationElementType::Nsapi),
+            21 => Ok(InformationElementType::RanapCause),
+            128 => Ok(InformationElementType::EndUserAddress),
+            129 => Ok(InformationElementType::MmContext),
+            133 => Ok(InformationElementType::GsnAddress),
+            135 => Ok(InformationElementType::QoSProfile),
+            138 => Ok(InformationElementType::TargetIdentification),
+            139 => Ok(InformationElementType::UtranTransparentContainer),
+            255 => Ok(InformationElementType::PrivateExtension),
+            _ => Err(format!("Unsupported IE type ({})", value).to_string())
+        }
+    }
+}
+
+// Length of the TV format IEs (including the type octet) from TS 29.060 Table 37.
+// IE types >= 128 are TLV format and carry their own length.
+pub fn tv_length(ie_type: u8) -> Option<usize> {
+    match ie_type {
+        1 => Some(2),   // Cause
+        2 => Some(9),   // IMSI
+        3 => Some(7),   // RAI
+        4 => Some(5),   // TLLI
+        5 => Some(5),   // P-TMSI
+        8 => Some(2),   // Reordering Required
+        9 => Some(29),  // Authentication Triplet
+        11 => Some(2),  // MAP Cause
+        12 => Some(4),  // P-TMSI Signature
+        13 => Some(2),  // MS Validated
+        14 => Some(2),  // Recovery
+        15 => Some(2),  // Selection Mode
+        16 => Some(5),  // TEID Data I
+        17 => Some(5),  // TEID Control Plane
+        18 => Some(6),  // TEID Data II
+        19 => Some(2),  // Teardown Ind
+        20 => Some(2),  // NSAPI
+        21 => Some(2),  // RANAP Cause
+        22 => Some(10), // RAB Context
+        23 => Some(2),  // Radio Priority SMS
+        24 => Some(2),  // Radio Priority
+        25 => Some(3),  // Packet Flow Id
+        26 => Some(3),  // Charging Characteristics
+        27 => Some(3),  // Trace Reference
+        28 => Some(3),  // Trace Type
+        29 => Some(2),  // MS Not Reachable Reason
+        127 => Some(5), // Charging ID
+        _ => None
+    }
 }
 
 pub trait InformationElementTraits {
@@ -20,44 +114,356 @@ pub trait InformationElementTraits {
     fn information_element_type(&self) -> InformationElementType;
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum InformationElement
 {
+    Cause(cause::InformationElement),
     Imsi(imsi::InformationElement),
+    Rai(rai::InformationElement),
+    Recovery(recovery::InformationElement),
     TeidDataI(teid_data_i::InformationElement),
+    TeidControlPlane(teid_control_plane::InformationElement),
+    TeidDataII(teid_data_ii::InformationElement),
     Nsapi(nsapi::InformationElement),
+    RanapCause(ranap_cause::InformationElement),
+    EndUserAddress(end_user_address::InformationElement),
+    MmContext(mm_context::InformationElement),
     GsnAddress(gsn_address::InformationElement),
     QoSProfile(qos_profile::InformationElement),
+    TargetIdentification(target_identification::InformationElement),
+    UtranTransparentContainer(utran_transparent_container::InformationElement),
+    PrivateExtension(private_extension::InformationElement),
+}
+
+impl InformationElement {
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        // First we parse the first byte to determine the type of the IE.
+        // Then we pass off the parsing to the specific IE implementation
+
+        if buffer.len() < 1 {
+            return None
+        }
+
+        let ie_type = buffer[0];
+
+        if let Ok(ie_type) = ie_type.try_into() {
+            match ie_type {
+                InformationElementType::Cause => {
+                    if let Some((ie, pos)) = cause::InformationElement::parse(buffer) {
+                        Some((InformationElement::Cause(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Imsi => {
+                    if let Some((ie, pos)) = imsi::InformationElement::parse(buffer) {
+                        Some((InformationElement::Imsi(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Rai => {
+                    if let Some((ie, pos)) = rai::InformationElement::parse(buffer) {
+                        Some((InformationElement::Rai(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Recovery => {
+                    if let Some((ie, pos)) = recovery::InformationElement::parse(buffer) {
+                        Some((InformationElement::Recovery(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TeidDataI => {
+                    if let Some((ie, pos)) = teid_data_i::InformationElement::parse(buffer) {
+                        Some((InformationElement::TeidDataI(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TeidControlPlane => {
+                    if let Some((ie, pos)) = teid_control_plane::InformationElement::parse(buffer) {
+                        Some((InformationElement::TeidControlPlane(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TeidDataII => {
+                    if let Some((ie, pos)) = teid_data_ii::InformationElement::parse(buffer) {
+                        Some((InformationElement::TeidDataII(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Nsapi => {
+                    if let Some((ie, pos)) = nsapi::InformationElement::parse(buffer) {
+                        Some((InformationElement::Nsapi(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::RanapCause => {
+                    if let Some((ie, pos)) = ranap_cause::InformationElement::parse(buffer) {
+                        Some((InformationElement::RanapCause(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::EndUserAddress => {
+                    if let Some((ie, pos)) = end_user_address::InformationElement::parse(buffer) {
+                        Some((InformationElement::EndUserAddress(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::MmContext => {
+                    if let Some((ie, pos)) = mm_context::InformationElement::parse(buffer) {
+                        Some((InformationElement::MmContext(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::GsnAddress => {
+                    if let Some((ie, pos)) = gsn_address::InformationElement::parse(buffer) {
+                        Some((InformationElement::GsnAddress(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::QoSProfile => {
+                    if let Some((ie, pos)) = qos_profile::InformationElement::parse(buffer) {
+                        Some((InformationElement::QoSProfile(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TargetIdentification => {
+                    if let Some((ie, pos)) = target_identification::InformationElement::parse(buffer) {
+                        Some((InformationElement::TargetIdentification(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::UtranTransparentContainer => {
+                    if let Some((ie, pos)) = utran_transparent_container::InformationElement::parse(buffer) {
+                        Some((InformationElement::UtranTransparentContainer(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PrivateExtension => {
+                    if let Some((ie, pos)) = private_extension::InformationElement::parse(buffer) {
+                        Some((InformationElement::PrivateExtension(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+            }
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn skip_parsing(buffer: &[u8]) -> Option<usize> {
+        // This function will return the number of bytes to advance the buffer to skip over parsing this IE.
+        // Unlike GTPv2 not every IE carries a length. TV format IEs have a fixed length so we can only skip
+        // the ones we know about. If we can't determine the length we return None as we can't continue parsing.
+
+        if buffer.len() < 1 {
+            return None
+        }
+
+        let ie_type = buffer[0];
+
+        if ie_type >= 128 {
+            if buffer.len() < 3 {
+                debug!("Can't skip truncated IE type {}", ie_type);
+                return None
+            }
+
+            // Read length of IE and add 3 for the IE header
+            let length = NetworkEndian::read_u16(&buffer[1..3]) as usize + 3;
+
+            if buffer.len() < length {
+                debug!("Can't skip IE type {} of {} bytes, only {} remain", ie_type, length, buffer.len());
+                return None
+            }
+
+            debug!("Skipping IE type {} of {} bytes that couldn't be parsed", ie_type, length);
+            Some(length)
+        }
+        else {
+            match tv_length(ie_type) {
+                Some(length) if buffer.len() >= length => {
+                    debug!("Skipping IE type {} of {} bytes that couldn't be parsed", ie_type, length);
+                    Some(length)
+                },
+                _ => {
+                    debug!("Can't skip IE type {} as its length is unknown or it is truncated", ie_type);
+                    None
+                }
+            }
+        }
+    }
 }
 
 impl InformationElementTraits for InformationElement
 {
     fn length(&self) -> u16 {
         match self {
+            InformationElement::Cause(ie) => ie.length(),
             InformationElement::Imsi(ie) => ie.length(),
+            InformationElement::Rai(ie) => ie.length(),
+            InformationElement::Recovery(ie) => ie.length(),
             InformationElement::TeidDataI(ie) => ie.length(),
+            InformationElement::TeidControlPlane(ie) => ie.length(),
+            InformationElement::TeidDataII(ie) => ie.length(),
             InformationElement::Nsapi(ie) => ie.length(),
+            InformationElement::RanapCause(ie) => ie.length(),
+            InformationElement::EndUserAddress(ie) => ie.length(),
+            InformationElement::MmContext(ie) => ie.length(),
             InformationElement::GsnAddress(ie) => ie.length(),
             InformationElement::QoSProfile(ie) => ie.length(),
+            InformationElement::TargetIdentification(ie) => ie.length(),
+            InformationElement::UtranTransparentContainer(ie) => ie.length(),
+            InformationElement::PrivateExtension(ie) => ie.length(),
         }
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
         match self {
+            InformationElement::Cause(ie) => ie.generate(buffer),
             InformationElement::Imsi(ie) => ie.generate(buffer),
+            InformationElement::Rai(ie) => ie.generate(buffer),
+            InformationElement::Recovery(ie) => ie.generate(buffer),
             InformationElement::TeidDataI(ie) => ie.generate(buffer),
+            InformationElement::TeidControlPlane(ie) => ie.generate(buffer),
+            InformationElement::TeidDataII(ie) => ie.generate(buffer),
             InformationElement::Nsapi(ie) => ie.generate(buffer),
+            InformationElement::RanapCause(ie) => ie.generate(buffer),
+            InformationElement::EndUserAddress(ie) => ie.generate(buffer),
+            InformationElement::MmContext(ie) => ie.generate(buffer),
             InformationElement::GsnAddress(ie) => ie.generate(buffer),
             InformationElement::QoSProfile(ie) => ie.generate(buffer),
+            InformationElement::TargetIdentification(ie) => ie.generate(buffer),
+            InformationElement::UtranTransparentContainer(ie) => ie.generate(buffer),
+            InformationElement::PrivateExtension(ie) => ie.generate(buffer),
         }
     }
 
     fn information_element_type(&self) -> InformationElementType {
         match self {
+            InformationElement::Cause(ie) => ie.information_element_type(),
             InformationElement::Imsi(ie) => ie.information_element_type(),
+            InformationElement::Rai(ie) => ie.information_element_type(),
+            InformationElement::Recovery(ie) => ie.information_element_type(),
             InformationElement::TeidDataI(ie) => ie.information_element_type(),
+            InformationElement::TeidControlPlane(ie) => ie.information_element_type(),
+            InformationElement::TeidDataII(ie) => ie.information_element_type(),
             InformationElement::Nsapi(ie) => ie.information_element_type(),
+            InformationElement::RanapCause(ie) => ie.information_element_type(),
+            InformationElement::EndUserAddress(ie) => ie.information_element_type(),
+            InformationElement::MmContext(ie) => ie.information_element_type(),
             InformationElement::GsnAddress(ie) => ie.information_element_type(),
             InformationElement::QoSProfile(ie) => ie.information_element_type(),
+            InformationElement::TargetIdentification(ie) => ie.information_element_type(),
+            InformationElement::UtranTransparentContainer(ie) => ie.information_element_type(),
+            InformationElement::PrivateExtension(ie) => ie.information_element_type(),
+        }
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            InformationElement::Cause(ie) => ie.dissect(d),
+            InformationElement::Imsi(ie) => ie.dissect(d),
+            InformationElement::Rai(ie) => ie.dissect(d),
+            InformationElement::Recovery(ie) => ie.dissect(d),
+            InformationElement::TeidDataI(ie) => ie.dissect(d),
+            InformationElement::TeidControlPlane(ie) => ie.dissect(d),
+            InformationElement::TeidDataII(ie) => ie.dissect(d),
+            InformationElement::Nsapi(ie) => ie.dissect(d),
+            InformationElement::RanapCause(ie) => ie.dissect(d),
+            InformationElement::EndUserAddress(ie) => ie.dissect(d),
+            InformationElement::MmContext(ie) => ie.dissect(d),
+            InformationElement::GsnAddress(ie) => ie.dissect(d),
+            InformationElement::QoSProfile(ie) => ie.dissect(d),
+            InformationElement::TargetIdentification(ie) => ie.dissect(d),
+            InformationElement::UtranTransparentContainer(ie) => ie.dissect(d),
+            InformationElement::PrivateExtension(ie) => ie.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_ie<T: InformationElementTraits, F: FnOnce(&mut Dissector)>(ie: &T, d: &mut Dissector, f: F) {
+    // Writes the IE type followed by the fields of the IE written by f
+    let ie_type = ie.information_element_type();
+
+    d.tree(&format!("{:?} ({})", ie_type, ie_type as u8), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        if let Some((InformationElement::TeidControlPlane(ie), pos)) = InformationElement::parse(&[InformationElementType::TeidControlPlane as u8, 0x87, 0x65, 0x43, 0x21]) {
+            assert_eq!(ie.teid(), 0x87654321);
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((InformationElement::Recovery(ie), pos)) = InformationElement::parse(&[InformationElementType::Recovery as u8, 5]) {
+            assert_eq!(ie.restart_counter(), 5);
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
         }
+
+        // Selection Mode is not supported
+        assert!(InformationElement::parse(&[15, 0]).is_none());
+        assert!(InformationElement::parse(&[]).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_skip_parsing() {
+        // TV format
+        assert_eq!(InformationElement::skip_parsing(&[14, 0]), Some(2));
+        assert_eq!(InformationElement::skip_parsing(&[InformationElementType::Imsi as u8, 0x5F, 0x50, 0x10, 0x43, 0x58, 0x90, 0x40, 0x40]), Some(9));
+
+        // TLV format
+        assert_eq!(InformationElement::skip_parsing(&[131, 0, 2, 1, 0x61]), Some(5));
+
+        // Truncated
+        assert_eq!(InformationElement::skip_parsing(&[131, 0, 3, 1, 0x61]), None);
+        assert_eq!(InformationElement::skip_parsing(&[InformationElementType::Imsi as u8, 0x5F]), None);
+
+        // Unknown TV format IE
+        assert_eq!(InformationElement::skip_parsing(&[100, 0]), None);
+    }
+}
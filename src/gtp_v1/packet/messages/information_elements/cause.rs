@@ -0,0 +1,362 @@
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+
+// The cause values of TS 29.060 Table 38. They are a different value space to the GTPv2 cause values
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum CauseCode {
+    // Request
+    RequestIMSI,
+    RequestIMEI,
+    RequestIMSIAndIMEI,
+    NoIdentityNeeded,
+    MSRefuses,
+    MSIsNotGPRSResponding,
+    ReactivationRequested,
+    PDPAddressInactivityTimerExpires,
+    NetworkFailure,
+    QoSParameterMismatch,
+    // Acceptance in a response
+    RequestAccepted,
+    NewPDPTypeDueToNetworkPreference,
+    NewPDPTypeDueToSingleAddressBearerOnly,
+    // Rejection in a response
+    NonExistent,
+    InvalidMessageFormat,
+    IMSIOrIMEINotKnown,
+    MSIsGPRSDetached,
+    MSIsNotGPRSRespondingRejection,
+    MSRefusesRejection,
+    VersionNotSupported,
+    NoResourcesAvailable,
+    ServiceNotSupported,
+    MandatoryIEIncorrect,
+    MandatoryIEMissing,
+    OptionalIEIncorrect,
+    SystemFailure,
+    RoamingRestriction,
+    PTMSISignatureMismatch,
+    GPRSConnectionSuspended,
+    AuthenticationFailure,
+    UserAuthenticationFailed,
+    ContextNotFound,
+    AllDynamicPDPAddressesAreOccupied,
+    NoMemoryIsAvailable,
+    RelocationFailure,
+    UnknownMandatoryExtensionHeader,
+    SemanticErrorInTheTFTOperation,
+    SyntacticErrorInTheTFTOperation,
+    SemanticErrorsInPacketFilters,
+    SyntacticErrorsInPacketFilters,
+    MissingOrUnknownAPN,
+    UnknownPDPAddressOrPDPType,
+    PDPContextWithoutTFTAlreadyActivated,
+    APNAccessDeniedNoSubscription,
+    APNRestrictionTypeIncompatibilityWithCurrentlyActivePDPContexts,
+    MSMBMSCapabilitiesInsufficient,
+    InvalidCorrelationID,
+    MBMSBearerContextSuperseded,
+    BearerControlModeViolation,
+    CollisionWithNetworkInitiatedRequest,
+    APNCongestion,
+    BearerHandlingNotSupported,
+    TargetAccessRestrictedForTheSubscriber,
+    UEIsTemporarilyNotReachableDueToPowerSaving,
+    RelocationFailureDueToNASMessageRedirection,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
+}
+
+impl From<u8> for CauseCode
+{
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CauseCode::RequestIMSI,
+            1 => CauseCode::RequestIMEI,
+            2 => CauseCode::RequestIMSIAndIMEI,
+            3 => CauseCode::NoIdentityNeeded,
+            4 => CauseCode::MSRefuses,
+            5 => CauseCode::MSIsNotGPRSResponding,
+            6 => CauseCode::ReactivationRequested,
+            7 => CauseCode::PDPAddressInactivityTimerExpires,
+            8 => CauseCode::NetworkFailure,
+            9 => CauseCode::QoSParameterMismatch,
+            128 => CauseCode::RequestAccepted,
+            129 => CauseCode::NewPDPTypeDueToNetworkPreference,
+            130 => CauseCode::NewPDPTypeDueToSingleAddressBearerOnly,
+            192 => CauseCode::NonExistent,
+            193 => CauseCode::InvalidMessageFormat,
+            194 => CauseCode::IMSIOrIMEINotKnown,
+            195 => CauseCode::MSIsGPRSDetached,
+            196 => CauseCode::MSIsNotGPRSRespondingRejection,
+            197 => CauseCode::MSRefusesRejection,
+            198 => CauseCode::VersionNotSupported,
+            199 => CauseCode::NoResourcesAvailable,
+            200 => CauseCode::ServiceNotSupported,
+            201 => CauseCode::MandatoryIEIncorrect,
+            202 => CauseCode::MandatoryIEMissing,
+            203 => CauseCode::OptionalIEIncorrect,
+            204 => CauseCode::SystemFailure,
+            205 => CauseCode::RoamingRestriction,
+            206 => CauseCode::PTMSISignatureMismatch,
+            207 => CauseCode::GPRSConnectionSuspended,
+            208 => CauseCode::AuthenticationFailure,
+            209 => CauseCode::UserAuthenticationFailed,
+            210 => CauseCode::ContextNotFound,
+            211 => CauseCode::AllDynamicPDPAddressesAreOccupied,
+            212 => CauseCode::NoMemoryIsAvailable,
+            213 => CauseCode::RelocationFailure,
+            214 => CauseCode::UnknownMandatoryExtensionHeader,
+            215 => CauseCode::SemanticErrorInTheTFTOperation,
+            216 => CauseCode::SyntacticErrorInTheTFTOperation,
+            217 => CauseCode::SemanticErrorsInPacketFilters,
+            218 => CauseCode::SyntacticErrorsInPacketFilters,
+            219 => CauseCode::MissingOrUnknownAPN,
+            220 => CauseCode::UnknownPDPAddressOrPDPType,
+            221 => CauseCode::PDPContextWithoutTFTAlreadyActivated,
+            222 => CauseCode::APNAccessDeniedNoSubscription,
+            223 => CauseCode::APNRestrictionTypeIncompatibilityWithCurrentlyActivePDPContexts,
+            224 => CauseCode::MSMBMSCapabilitiesInsufficient,
+            225 => CauseCode::InvalidCorrelationID,
+            226 => CauseCode::MBMSBearerContextSuperseded,
+            227 => CauseCode::BearerControlModeViolation,
+            228 => CauseCode::CollisionWithNetworkInitiatedRequest,
+            229 => CauseCode::APNCongestion,
+            230 => CauseCode::BearerHandlingNotSupported,
+            231 => CauseCode::TargetAccessRestrictedForTheSubscriber,
+            232 => CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving,
+            233 => CauseCode::RelocationFailureDueToNASMessageRedirection,
+            _ => CauseCode::Other(value),
+        }
+    }
+}
+
+impl From<CauseCode> for u8
+{
+    fn from(value: CauseCode) -> Self {
+        match value {
+            CauseCode::RequestIMSI => 0,
+            CauseCode::RequestIMEI => 1,
+            CauseCode::RequestIMSIAndIMEI => 2,
+            CauseCode::NoIdentityNeeded => 3,
+            CauseCode::MSRefuses => 4,
+            CauseCode::MSIsNotGPRSResponding => 5,
+            CauseCode::ReactivationRequested => 6,
+            CauseCode::PDPAddressInactivityTimerExpires => 7,
+            CauseCode::NetworkFailure => 8,
+            CauseCode::QoSParameterMismatch => 9,
+            CauseCode::RequestAccepted => 128,
+            CauseCode::NewPDPTypeDueToNetworkPreference => 129,
+            CauseCode::NewPDPTypeDueToSingleAddressBearerOnly => 130,
+            CauseCode::NonExistent => 192,
+            CauseCode::InvalidMessageFormat => 193,
+            CauseCode::IMSIOrIMEINotKnown => 194,
+            CauseCode::MSIsGPRSDetached => 195,
+            CauseCode::MSIsNotGPRSRespondingRejection => 196,
+            CauseCode::MSRefusesRejection => 197,
+            CauseCode::VersionNotSupported => 198,
+            CauseCode::NoResourcesAvailable => 199,
+            CauseCode::ServiceNotSupported => 200,
+            CauseCode::MandatoryIEIncorrect => 201,
+            CauseCode::MandatoryIEMissing => 202,
+            CauseCode::OptionalIEIncorrect => 203,
+            CauseCode::SystemFailure => 204,
+            CauseCode::RoamingRestriction => 205,
+            CauseCode::PTMSISignatureMismatch => 206,
+            CauseCode::GPRSConnectionSuspended => 207,
+            CauseCode::AuthenticationFailure => 208,
+            CauseCode::UserAuthenticationFailed => 209,
+            CauseCode::ContextNotFound => 210,
+            CauseCode::AllDynamicPDPAddressesAreOccupied => 211,
+            CauseCode::NoMemoryIsAvailable => 212,
+            CauseCode::RelocationFailure => 213,
+            CauseCode::UnknownMandatoryExtensionHeader => 214,
+            CauseCode::SemanticErrorInTheTFTOperation => 215,
+            CauseCode::SyntacticErrorInTheTFTOperation => 216,
+            CauseCode::SemanticErrorsInPacketFilters => 217,
+            CauseCode::SyntacticErrorsInPacketFilters => 218,
+            CauseCode::MissingOrUnknownAPN => 219,
+            CauseCode::UnknownPDPAddressOrPDPType => 220,
+            CauseCode::PDPContextWithoutTFTAlreadyActivated => 221,
+            CauseCode::APNAccessDeniedNoSubscription => 222,
+            CauseCode::APNRestrictionTypeIncompatibilityWithCurrentlyActivePDPContexts => 223,
+            CauseCode::MSMBMSCapabilitiesInsufficient => 224,
+            CauseCode::InvalidCorrelationID => 225,
+            CauseCode::MBMSBearerContextSuperseded => 226,
+            CauseCode::BearerControlModeViolation => 227,
+            CauseCode::CollisionWithNetworkInitiatedRequest => 228,
+            CauseCode::APNCongestion => 229,
+            CauseCode::BearerHandlingNotSupported => 230,
+            CauseCode::TargetAccessRestrictedForTheSubscriber => 231,
+            CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving => 232,
+            CauseCode::RelocationFailureDueToNASMessageRedirection => 233,
+            CauseCode::Other(value) => value,
+        }
+    }
+}
+
+impl CauseCode {
+    pub fn is_request(&self) -> bool {
+        // Bit 8 is 0 in the cause values sent in a request (TS 29.060 7.7.1)
+        u8::from(*self) & 0x80 == 0
+    }
+
+    pub fn is_acceptance(&self) -> bool {
+        // Bits 8 and 7 are 1 0 in a response that accepts the request
+        u8::from(*self) & 0xC0 == 0x80
+    }
+
+    pub fn is_rejection(&self) -> bool {
+        // Bits 8 and 7 are 1 1 in a response that rejects the request
+        u8::from(*self) & 0xC0 == 0xC0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (1)                                                   |
+        2       | Cause value                                                   |
+                |---------------------------------------------------------------|
+    */
+    pub cause_code: CauseCode,
+}
+
+impl InformationElement {
+    pub fn new(cause_code: CauseCode) -> Self {
+        InformationElement {
+            cause_code
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    cause_code: CauseCode::from(buffer[1])
+                },
+                2
+            )
+        )
+    }
+
+    pub fn cause(&self) -> u8 {
+        u8::from(self.cause_code)
+    }
+    pub fn set_cause(&mut self, cause: u8) {
+        self.cause_code = CauseCode::from(cause);
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        self.cause_code.is_acceptance()
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.cause_code.is_rejection()
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Cause
+    }
+
+    fn length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+
+        pos = pos + 1;
+
+        buffer[pos] = u8::from(self.cause_code);
+
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Cause", format!("{:?} ({})", self.cause_code, u8::from(self.cause_code)));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(CauseCode::RequestAccepted);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Cause as u8, 128]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(CauseCode::RequestAccepted);
+
+        assert_eq!(ie.length(), 2)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(CauseCode::RequestAccepted);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::Cause as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Cause as u8, 192]) {
+            assert_eq!(ie.cause(), 192);
+            assert_eq!(ie.cause_code, CauseCode::NonExistent);
+            assert!(ie.is_rejected());
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_cause_code() {
+        for value in 0..=255u8 {
+            assert_eq!(u8::from(CauseCode::from(value)), value);
+        }
+
+        assert_eq!(CauseCode::from(128), CauseCode::RequestAccepted);
+        assert_eq!(CauseCode::from(10), CauseCode::Other(10));
+
+        assert!(CauseCode::RequestIMSI.is_request());
+        assert!(!CauseCode::RequestIMSI.is_acceptance());
+        assert!(CauseCode::NewPDPTypeDueToNetworkPreference.is_acceptance());
+        assert!(CauseCode::Other(191).is_acceptance());
+        assert!(CauseCode::ContextNotFound.is_rejection());
+        assert!(!CauseCode::ContextNotFound.is_acceptance());
+    }
+}
@@ -0,0 +1,331 @@
+use core::net::{Ipv4Addr, Ipv6Addr};
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+pub const LENGTH: Field = 1..3;
+
+pub const ORGANISATION_ETSI: u8 = 0;
+pub const ORGANISATION_IETF: u8 = 1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum PdpType {
+    Ppp, // ETSI
+    NonIp, // ETSI
+    IPv4, // IETF
+    IPv6, // IETF
+    IPv4v6, // IETF
+    Other(u8, u8), // (PDP Type Organisation, PDP Type Number) that isn't defined above
+}
+
+impl PdpType {
+    pub fn from_organisation_number(organisation: u8, number: u8) -> Self {
+        match (organisation, number) {
+            (ORGANISATION_ETSI, 0x01) => PdpType::Ppp,
+            (ORGANISATION_ETSI, 0x02) => PdpType::NonIp,
+            (ORGANISATION_IETF, 0x21) => PdpType::IPv4,
+            (ORGANISATION_IETF, 0x57) => PdpType::IPv6,
+            (ORGANISATION_IETF, 0x8D) => PdpType::IPv4v6,
+            _ => PdpType::Other(organisation, number),
+        }
+    }
+
+    pub fn organisation(&self) -> u8 {
+        match self {
+            PdpType::Ppp | PdpType::NonIp => ORGANISATION_ETSI,
+            PdpType::IPv4 | PdpType::IPv6 | PdpType::IPv4v6 => ORGANISATION_IETF,
+            PdpType::Other(organisation, _) => *organisation,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            PdpType::Ppp => 0x01,
+            PdpType::NonIp => 0x02,
+            PdpType::IPv4 => 0x21,
+            PdpType::IPv6 => 0x57,
+            PdpType::IPv4v6 => 0x8D,
+            PdpType::Other(_, number) => *number,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (128)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare (1111)                  | PDP Type Organisation         |
+        5       | PDP Type Number                                               |
+        6->n    | PDP Address                                                   |
+                |---------------------------------------------------------------|
+
+        The PDP Address is left out when the SGSN asks for a dynamic address. For IPv4v6 the IPv4 address comes first
+        (TS 29.060 7.7.27)
+    */
+    pdp_type: PdpType,
+    ipv4_address: Option<Ipv4Addr>,
+    ipv6_address: Option<Ipv6Addr>,
+}
+
+impl InformationElement {
+    pub fn new(pdp_type: PdpType, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) -> Result<Self, String> {
+        let allowed = match pdp_type {
+            PdpType::IPv4 => ipv6_address.is_none(),
+            PdpType::IPv6 => ipv4_address.is_none(),
+            PdpType::IPv4v6 => ipv4_address.is_some() == ipv6_address.is_some(),
+            _ => ipv4_address.is_none() && ipv6_address.is_none(),
+        };
+
+        if !allowed {
+            return Err(format!("Addresses don't match the PDP Type {:?}", pdp_type));
+        }
+
+        Ok(
+            InformationElement {
+                pdp_type,
+                ipv4_address,
+                ipv6_address,
+            }
+        )
+    }
+
+    pub fn dynamic(pdp_type: PdpType) -> Self {
+        // Asks the GGSN to allocate the address
+        InformationElement {
+            pdp_type,
+            ipv4_address: None,
+            ipv6_address: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize;
+        pos = pos + 2;
+
+        if length < 2 || buffer.len() < pos + length {
+            return None
+        }
+
+        let pdp_type = PdpType::from_organisation_number(buffer[pos] & 0xF, buffer[pos+1]);
+        pos = pos + 2;
+
+        let (ipv4_address, ipv6_address) = match pdp_type {
+            PdpType::Ppp | PdpType::NonIp | PdpType::Other(_, _) => (None, None),
+            _ => {
+                let addresses = address::parse_dual_stack(&buffer[pos..pos + length - 2])?;
+
+                match (pdp_type, addresses) {
+                    (PdpType::IPv4, (_, Some(_))) | (PdpType::IPv6, (Some(_), _)) => return None,
+                    _ => addresses,
+                }
+            }
+        };
+
+        Some(
+            (
+                InformationElement {
+                    pdp_type,
+                    ipv4_address,
+                    ipv6_address,
+                },
+                length + 3
+            )
+        )
+    }
+
+    pub fn pdp_type(&self) -> PdpType {
+        self.pdp_type
+    }
+
+    pub fn ipv4_address(&self) -> Option<Ipv4Addr> {
+        self.ipv4_address
+    }
+
+    pub fn ipv6_address(&self) -> Option<Ipv6Addr> {
+        self.ipv6_address
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::EndUserAddress
+    }
+
+    fn length(&self) -> u16 {
+        5 + address::dual_stack_length(self.ipv4_address, self.ipv6_address)
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-3);
+        pos = pos + 2;
+
+        buffer[pos] = 0xF0 | (self.pdp_type.organisation() & 0xF);
+        pos = pos + 1;
+
+        buffer[pos] = self.pdp_type.number();
+        pos = pos + 1;
+
+        pos = pos + address::generate_dual_stack(self.ipv4_address, self.ipv6_address, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("PDP Type", format!("{:?}", self.pdp_type));
+
+            if let Some(address) = self.ipv4_address {
+                d.field("IPv4 Address", address);
+            }
+
+            if let Some(address) = self.ipv6_address {
+                d.field("IPv6 Address", address);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PdpType::IPv4, Some(Ipv4Addr::new(10, 0, 0, 1)), None).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::EndUserAddress as u8, 0, 6, 0xF1, 0x21, 10, 0, 0, 1]);
+
+        let ie = InformationElement::new(PdpType::IPv4v6, Some(Ipv4Addr::new(10, 0, 0, 1)), Some(Ipv6Addr::new(0x2001, 0xDB8, 0, 0, 0, 0, 0, 1))).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::EndUserAddress as u8, 0, 22, 0xF1, 0x8D,
+            10, 0, 0, 1,
+            0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+        ]);
+
+        // A dynamic address
+        let ie = InformationElement::dynamic(PdpType::IPv6);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::EndUserAddress as u8, 0, 2, 0xF1, 0x57]);
+
+        let ie = InformationElement::dynamic(PdpType::Ppp);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::EndUserAddress as u8, 0, 2, 0xF0, 0x01]);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new(PdpType::IPv4, None, Some(Ipv6Addr::LOCALHOST)).is_err());
+        assert!(InformationElement::new(PdpType::IPv6, Some(Ipv4Addr::LOCALHOST), None).is_err());
+        assert!(InformationElement::new(PdpType::IPv4v6, Some(Ipv4Addr::LOCALHOST), None).is_err());
+        assert!(InformationElement::new(PdpType::Ppp, Some(Ipv4Addr::LOCALHOST), None).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(InformationElement::dynamic(PdpType::IPv4v6).length(), 5);
+        assert_eq!(InformationElement::new(PdpType::IPv4, Some(Ipv4Addr::LOCALHOST), None).unwrap().length(), 9);
+        assert_eq!(InformationElement::new(PdpType::IPv6, None, Some(Ipv6Addr::LOCALHOST)).unwrap().length(), 21);
+        assert_eq!(InformationElement::new(PdpType::IPv4v6, Some(Ipv4Addr::LOCALHOST), Some(Ipv6Addr::LOCALHOST)).unwrap().length(), 25);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::dynamic(PdpType::IPv4);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::EndUserAddress as u8)
+    }
+
+    #[test]
+    fn test_pdp_type() {
+        for pdp_type in [PdpType::Ppp, PdpType::NonIp, PdpType::IPv4, PdpType::IPv6, PdpType::IPv4v6, PdpType::Other(1, 0x22)] {
+            assert_eq!(PdpType::from_organisation_number(pdp_type.organisation(), pdp_type.number()), pdp_type);
+        }
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::EndUserAddress as u8, 0, 6, 0xF1, 0x21, 10, 0, 0, 1]) {
+            assert_eq!(ie.pdp_type(), PdpType::IPv4);
+            assert_eq!(ie.ipv4_address(), Some(Ipv4Addr::new(10, 0, 0, 1)));
+            assert_eq!(ie.ipv6_address(), None);
+            assert_eq!(pos, 9);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::EndUserAddress as u8, 0, 22, 0xF1, 0x8D,
+            10, 0, 0, 1,
+            0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+        ]) {
+            assert_eq!(ie.pdp_type(), PdpType::IPv4v6);
+            assert_eq!(ie.ipv4_address(), Some(Ipv4Addr::new(10, 0, 0, 1)));
+            assert_eq!(ie.ipv6_address(), Some(Ipv6Addr::new(0x2001, 0xDB8, 0, 0, 0, 0, 0, 1)));
+            assert_eq!(pos, 25);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::EndUserAddress as u8, 0, 2, 0xF1, 0x57]) {
+            assert_eq!(ie, InformationElement::dynamic(PdpType::IPv6));
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        // An IPv4 PDP Type with an IPv6 length address
+        assert!(InformationElement::parse(&[InformationElementType::EndUserAddress as u8, 0, 18, 0xF1, 0x21,
+            0x20, 0x01, 0x0D, 0xB8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+        ]).is_none());
+
+        // Truncated
+        assert!(InformationElement::parse(&[InformationElementType::EndUserAddress as u8, 0, 6, 0xF1, 0x21, 10, 0]).is_none());
+    }
+}
@@ -1,14 +1,19 @@
-use std::net::IpAddr;
+use core::net::IpAddr;
 use byteorder::{ByteOrder, NetworkEndian};
 
 use crate::field::*;
 
-use super::{InformationElementTraits, InformationElementType};
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
 
 pub const LENGTH: Field = 1..3;
 pub const IPV4: Field = 3..7;
-pub const IPV6: Field = 3..13;
+pub const IPV6: Field = 3..19;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -33,8 +38,32 @@ impl InformationElement {
         }
     }
     
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        None
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+
+        if buffer.len() < length as usize + 3 {
+            return None
+        }
+
+        // The length is 4 for an IPv4 address or 16 for an IPv6 address
+        let gsn_address = address::parse(&buffer[3..length as usize + 3])?;
+
+        Some(
+            (
+                InformationElement {
+                    gsn_address
+                },
+                (length+3) as usize
+            )
+        )
+    }
+
+    pub fn gsn_address(&self) -> IpAddr {
+        self.gsn_address
     }
 }
 
@@ -44,11 +73,7 @@ impl InformationElementTraits for InformationElement {
     }
 
     fn length(&self) -> u16 {
-        match self.gsn_address
-        {
-            IpAddr::V4(_) => 3+4,
-            IpAddr::V6(_) => 3+16,
-        }
+        3 + address::length(self.gsn_address)
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -64,26 +89,20 @@ impl InformationElementTraits for InformationElement {
         NetworkEndian::write_u16(&mut buffer[LENGTH],self.length()-3);
         pos = pos + 2;
 
-        match self.gsn_address
-        {
-            IpAddr::V4(a) => {
-                for o in a.octets().iter() {
-                    buffer[pos] = *o;
-                    pos = pos + 1;
-                }
-            },
-            IpAddr::V6(a) => {
-                for o in a.octets().iter() {
-                    buffer[pos] = *o;
-                    pos = pos + 1;
-                }
-            }
-        };
+        pos = pos + address::generate(self.gsn_address, &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("GSN Address", self.gsn_address);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +175,24 @@ mod tests {
 
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1]) {
+            assert_eq!(ie.gsn_address(), IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)));
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::GsnAddress as u8, 0, 16,
+            0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE]) {
+            assert_eq!(ie.gsn_address(), IpAddr::V6(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0xFEED, 0xDEAF, 0xBEAD, 0xFACE)));
+            assert_eq!(pos, 19);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::GsnAddress as u8, 0, 5, 192, 168, 0, 1, 0]).is_none());
+        assert!(InformationElement::parse(&[InformationElementType::GsnAddress as u8, 0, 4, 192, 168]).is_none());
     }
 }
\ No newline at end of file
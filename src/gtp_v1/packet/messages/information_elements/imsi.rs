@@ -1,5 +1,11 @@
-use super::{InformationElementTraits, InformationElementType};
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector, digits_string};
+
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -49,8 +55,45 @@ impl InformationElement {
         })
     }
 
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        None
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 9 {
+            return None
+        }
+
+        let mut imsi = [0; 15];
+
+        // Inverse of generate_tbcd_imsi
+        for n in 0..8 {
+            let first_nibble = buffer[1+n] >> 4;
+            let second_nibble = buffer[1+n] & 0xF;
+
+            if n > 0 {
+                if second_nibble > 9 {
+                    return None
+                }
+                imsi[n*2-1] = second_nibble;
+            }
+
+            if n*2 <= 14 {
+                if first_nibble > 9 {
+                    return None
+                }
+                imsi[n*2] = first_nibble;
+            }
+        }
+
+        Some(
+            (
+                InformationElement {
+                    imsi
+                },
+                9
+            )
+        )
+    }
+
+    pub fn imsi(&self) -> String {
+        self.imsi.iter().map(|d| d.to_string()).collect()
     }
 
     pub fn generate_tbcd_imsi(&self) -> [u8; 8] {
@@ -108,6 +151,14 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("IMSI", digits_string(&self.imsi));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,6 +269,15 @@ mod tests {
 
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Imsi as u8, 0x5F, 0x50, 0x10, 0x43, 0x58, 0x90, 0x40, 0x40]) {
+            assert_eq!(ie.imsi(), "505013485090404");
+            assert_eq!(pos, 9);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::Imsi as u8, 0x5F, 0x5A, 0x10, 0x43, 0x58, 0x90, 0x40, 0x40]).is_none());
+        assert!(InformationElement::parse(&[InformationElementType::Imsi as u8, 0x5F, 0x50]).is_none());
     }
 }
\ No newline at end of file
@@ -0,0 +1,910 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryFrom;
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const LENGTH: Field = 1..3;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum SecurityMode {
+    UsedCipherUmtsKeysAndQuintuplets = 0,
+    GsmKeyAndTriplets = 1,
+    UmtsKeysAndQuintuplets = 2,
+    GsmKeyAndQuintuplets = 3,
+}
+
+impl TryFrom<u8> for SecurityMode
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SecurityMode::UsedCipherUmtsKeysAndQuintuplets),
+            1 => Ok(SecurityMode::GsmKeyAndTriplets),
+            2 => Ok(SecurityMode::UmtsKeysAndQuintuplets),
+            3 => Ok(SecurityMode::GsmKeyAndQuintuplets),
+            _ => Err(format!("Unsupported Security Mode ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Triplet {
+    /*
+        Encoded as the value of the Authentication Triplet IE (7.7.7)
+
+        RAND (16 Octets) | SRES (4 Octets) | Kc (8 Octets)
+    */
+    pub rand: [u8; 16],
+    pub sres: [u8; 4],
+    pub kc: [u8; 8],
+}
+
+impl Triplet {
+    fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 28 {
+            return None
+        }
+
+        let mut t = Triplet {
+            rand: [0; 16],
+            sres: [0; 4],
+            kc: [0; 8],
+        };
+
+        t.rand.copy_from_slice(&buffer[0..16]);
+        t.sres.copy_from_slice(&buffer[16..20]);
+        t.kc.copy_from_slice(&buffer[20..28]);
+
+        Some((t, 28))
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        buffer[0..16].copy_from_slice(&self.rand);
+        buffer[16..20].copy_from_slice(&self.sres);
+        buffer[20..28].copy_from_slice(&self.kc);
+
+        28
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Quintuplet {
+    /*
+        Encoded as the value of the Authentication Quintuplet IE (7.7.35)
+
+        RAND (16 Octets) | XRES Length | XRES | CK (16 Octets) | IK (16 Octets) | AUTN Length | AUTN
+    */
+    pub rand: [u8; 16],
+    pub xres: Vec<u8>,
+    pub ck: [u8; 16],
+    pub ik: [u8; 16],
+    pub autn: Vec<u8>,
+}
+
+impl Quintuplet {
+    fn length(&self) -> u16 {
+        (16 + 1 + self.xres.len() + 16 + 16 + 1 + self.autn.len()) as u16
+    }
+
+    fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        if buffer.len() < 17 {
+            return None
+        }
+
+        let mut rand = [0; 16];
+        rand.copy_from_slice(&buffer[pos..pos+16]);
+        pos = pos + 16;
+
+        let xres_length = buffer[pos] as usize;
+        pos = pos + 1;
+
+        if buffer.len() < pos + xres_length + 32 + 1 {
+            return None
+        }
+
+        let xres = buffer[pos..pos+xres_length].to_vec();
+        pos = pos + xres_length;
+
+        let mut ck = [0; 16];
+        ck.copy_from_slice(&buffer[pos..pos+16]);
+        pos = pos + 16;
+
+        let mut ik = [0; 16];
+        ik.copy_from_slice(&buffer[pos..pos+16]);
+        pos = pos + 16;
+
+        let autn_length = buffer[pos] as usize;
+        pos = pos + 1;
+
+        if buffer.len() < pos + autn_length {
+            return None
+        }
+
+        let autn = buffer[pos..pos+autn_length].to_vec();
+        pos = pos + autn_length;
+
+        Some((Quintuplet { rand, xres, ck, ik, autn }, pos))
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        buffer[pos..pos+16].copy_from_slice(&self.rand);
+        pos = pos + 16;
+
+        buffer[pos] = self.xres.len() as u8;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.xres.len()].copy_from_slice(&self.xres);
+        pos = pos + self.xres.len();
+
+        buffer[pos..pos+16].copy_from_slice(&self.ck);
+        pos = pos + 16;
+
+        buffer[pos..pos+16].copy_from_slice(&self.ik);
+        pos = pos + 16;
+
+        buffer[pos] = self.autn.len() as u8;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.autn.len()].copy_from_slice(&self.autn);
+        pos = pos + self.autn.len();
+
+        pos
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum SecurityContext {
+    GsmKeyAndTriplets {
+        cksn: u8,
+        used_cipher: u8,
+        kc: [u8; 8],
+        triplets: Vec<Triplet>,
+    },
+    GsmKeyAndQuintuplets {
+        cksn: u8,
+        used_cipher: u8,
+        kc: [u8; 8],
+        quintuplets: Vec<Quintuplet>,
+    },
+    UmtsKeysAndQuintuplets {
+        ksi: u8,
+        ck: [u8; 16],
+        ik: [u8; 16],
+        quintuplets: Vec<Quintuplet>,
+    },
+    UsedCipherUmtsKeysAndQuintuplets {
+        ksi: u8,
+        used_cipher: u8,
+        ck: [u8; 16],
+        ik: [u8; 16],
+        quintuplets: Vec<Quintuplet>,
+    },
+}
+
+impl SecurityContext {
+    pub fn security_mode(&self) -> SecurityMode {
+        match self {
+            SecurityContext::GsmKeyAndTriplets { .. } => SecurityMode::GsmKeyAndTriplets,
+            SecurityContext::GsmKeyAndQuintuplets { .. } => SecurityMode::GsmKeyAndQuintuplets,
+            SecurityContext::UmtsKeysAndQuintuplets { .. } => SecurityMode::UmtsKeysAndQuintuplets,
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { .. } => SecurityMode::UsedCipherUmtsKeysAndQuintuplets,
+        }
+    }
+
+    fn number_of_vectors(&self) -> usize {
+        match self {
+            SecurityContext::GsmKeyAndTriplets { triplets, .. } => triplets.len(),
+            SecurityContext::GsmKeyAndQuintuplets { quintuplets, .. } => quintuplets.len(),
+            SecurityContext::UmtsKeysAndQuintuplets { quintuplets, .. } => quintuplets.len(),
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { quintuplets, .. } => quintuplets.len(),
+        }
+    }
+
+    fn key_set_identifier(&self) -> u8 {
+        // CKSN for GSM keys and KSI for UMTS keys
+        match self {
+            SecurityContext::GsmKeyAndTriplets { cksn, .. } => *cksn,
+            SecurityContext::GsmKeyAndQuintuplets { cksn, .. } => *cksn,
+            SecurityContext::UmtsKeysAndQuintuplets { ksi, .. } => *ksi,
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { ksi, .. } => *ksi,
+        }
+    }
+
+    fn used_cipher(&self) -> Option<u8> {
+        match self {
+            SecurityContext::GsmKeyAndTriplets { used_cipher, .. } => Some(*used_cipher),
+            SecurityContext::GsmKeyAndQuintuplets { used_cipher, .. } => Some(*used_cipher),
+            SecurityContext::UmtsKeysAndQuintuplets { .. } => None,
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { used_cipher, .. } => Some(*used_cipher),
+        }
+    }
+
+    fn quintuplets(&self) -> Option<&Vec<Quintuplet>> {
+        match self {
+            SecurityContext::GsmKeyAndTriplets { .. } => None,
+            SecurityContext::GsmKeyAndQuintuplets { quintuplets, .. } => Some(quintuplets),
+            SecurityContext::UmtsKeysAndQuintuplets { quintuplets, .. } => Some(quintuplets),
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { quintuplets, .. } => Some(quintuplets),
+        }
+    }
+
+    fn length(&self) -> u16 {
+        // Octets 4 to m i.e. from the CKSN/KSI octet to the end of the authentication vectors
+        let vectors: u16 = match self {
+            SecurityContext::GsmKeyAndTriplets { triplets, .. } => (triplets.len() * 28) as u16,
+            _ => 2 + self.quintuplets().unwrap().iter().map(|q| q.length()).sum::<u16>(),
+        };
+
+        let keys = match self {
+            SecurityContext::GsmKeyAndTriplets { .. } => 8,
+            SecurityContext::GsmKeyAndQuintuplets { .. } => 8,
+            SecurityContext::UmtsKeysAndQuintuplets { .. } => 32,
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { .. } => 32,
+        };
+
+        1 + 1 + keys + vectors
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (129)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | GUPII | UGIPAI| Used GPRS Int Alg     | CKSN/KSI              |
+        5       | Security Mode | No of Vectors         | Used Cipher           |
+        6->m    | Kc or CK and IK, (Quintuplet Length), Triplets or Quintuplets |
+        m+1     | DRX Parameter Octet 1                                         |
+        m+2     | DRX Parameter Octet 2                                         |
+        m+3     | MS Network Capability Length                                  |
+        m+4->n  | MS Network Capability                                         |
+        n+1     | Container Length Octet 1                                      |
+        n+2     | Container Length Octet 2                                      |
+        n+3->o  | Container                                                     |
+        o+1     | Length of Access Restriction Data                             |
+        o+2     | Spare                                                 | NRSRNA|
+                |---------------------------------------------------------------|
+
+        The layout of octets 6 to m depends on the Security Mode (Figures 40, 41, 42 and 42A of TS 29.060).
+
+        GUPII, UGIPAI and the Used GPRS integrity protection algorithm are only present with UMTS keys. For
+        GSM keys bits 8 to 4 of octet 4 are spare. For UMTS keys and quintuplets (Security Mode 2) the Used
+        Cipher bits are spare.
+
+        The Access Restriction Data is optional. It is not generated when nrsrna is None.
+    */
+    pub security_context: SecurityContext,
+    pub gupii: bool,
+    pub used_gprs_integrity_protection_algorithm: Option<u8>, // UGIPAI is set when this is None
+    pub drx_parameter: u16,
+    pub ms_network_capability: Vec<u8>,
+    pub container: Vec<u8>,
+    pub nrsrna: Option<bool>,
+}
+
+impl InformationElement {
+    pub fn new(security_context: SecurityContext, drx_parameter: u16) -> Result<Self, String> {
+        if security_context.number_of_vectors() > 5 {
+            return Err(format!("No of Vectors is > 5 {}", security_context.number_of_vectors()));
+        }
+
+        if security_context.key_set_identifier() > 7 {
+            return Err(format!("CKSN/KSI is > 7 {}", security_context.key_set_identifier()));
+        }
+
+        if let Some(used_cipher) = security_context.used_cipher() {
+            if used_cipher > 7 {
+                return Err(format!("Used Cipher is > 7 {}", used_cipher));
+            }
+        }
+
+        Ok(
+            InformationElement {
+                security_context,
+                gupii: true,
+                used_gprs_integrity_protection_algorithm: None,
+                drx_parameter,
+                ms_network_capability: Vec::new(),
+                container: Vec::new(),
+                nrsrna: None,
+            }
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize;
+        pos = pos + 2;
+
+        let end = length + 3;
+
+        if buffer.len() < end || length < 2 {
+            return None
+        }
+
+        let key_set_identifier = buffer[pos] & 0b111;
+        let gupii = (buffer[pos] >> 7) & 0b1 == 1;
+        let ugipai = (buffer[pos] >> 6) & 0b1 == 1;
+        let integrity_algorithm = (buffer[pos] >> 3) & 0b111;
+        pos = pos + 1;
+
+        let security_mode = match SecurityMode::try_from(buffer[pos] >> 6) {
+            Ok(security_mode) => security_mode,
+            Err(_) => return None,
+        };
+        let number_of_vectors = ((buffer[pos] >> 3) & 0b111) as usize;
+        let used_cipher = buffer[pos] & 0b111;
+        pos = pos + 1;
+
+        let gsm_keys = security_mode == SecurityMode::GsmKeyAndTriplets || security_mode == SecurityMode::GsmKeyAndQuintuplets;
+
+        let mut kc = [0; 8];
+        let mut ck = [0; 16];
+        let mut ik = [0; 16];
+
+        if gsm_keys {
+            if pos + 8 > end {
+                return None
+            }
+            kc.copy_from_slice(&buffer[pos..pos+8]);
+            pos = pos + 8;
+        }
+        else {
+            if pos + 32 > end {
+                return None
+            }
+            ck.copy_from_slice(&buffer[pos..pos+16]);
+            pos = pos + 16;
+            ik.copy_from_slice(&buffer[pos..pos+16]);
+            pos = pos + 16;
+        }
+
+        let mut triplets = Vec::new();
+        let mut quintuplets = Vec::new();
+
+        if security_mode == SecurityMode::GsmKeyAndTriplets {
+            for _ in 0..number_of_vectors {
+                if let Some((t, t_pos)) = Triplet::parse(&buffer[pos..end]) {
+                    triplets.push(t);
+                    pos = pos + t_pos;
+                }
+                else {
+                    return None
+                }
+            }
+        }
+        else {
+            if pos + 2 > end {
+                return None
+            }
+            let quintuplet_length = NetworkEndian::read_u16(&buffer[pos..pos+2]) as usize;
+            pos = pos + 2;
+
+            if pos + quintuplet_length > end {
+                return None
+            }
+
+            let quintuplet_end = pos + quintuplet_length;
+
+            for _ in 0..number_of_vectors {
+                if let Some((q, q_pos)) = Quintuplet::parse(&buffer[pos..quintuplet_end]) {
+                    quintuplets.push(q);
+                    pos = pos + q_pos;
+                }
+                else {
+                    return None
+                }
+            }
+
+            pos = quintuplet_end;
+        }
+
+        // DRX Parameter and MS Network Capability Length
+        if pos + 3 > end {
+            return None
+        }
+
+        let drx_parameter = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+        pos = pos + 2;
+
+        let ms_network_capability_length = buffer[pos] as usize;
+        pos = pos + 1;
+
+        if pos + ms_network_capability_length > end {
+            return None
+        }
+
+        let ms_network_capability = buffer[pos..pos+ms_network_capability_length].to_vec();
+        pos = pos + ms_network_capability_length;
+
+        let mut container = Vec::new();
+
+        if pos + 2 <= end {
+            let container_length = NetworkEndian::read_u16(&buffer[pos..pos+2]) as usize;
+            pos = pos + 2;
+
+            if pos + container_length > end {
+                return None
+            }
+
+            container = buffer[pos..pos+container_length].to_vec();
+            pos = pos + container_length;
+        }
+
+        let mut nrsrna = None;
+
+        if pos < end {
+            let access_restriction_data_length = buffer[pos] as usize;
+            pos = pos + 1;
+
+            if access_restriction_data_length > 0 && pos < end {
+                nrsrna = Some(buffer[pos] & 0b1 == 1);
+            }
+        }
+
+        let security_context = match security_mode {
+            SecurityMode::GsmKeyAndTriplets => SecurityContext::GsmKeyAndTriplets {
+                cksn: key_set_identifier,
+                used_cipher,
+                kc,
+                triplets,
+            },
+            SecurityMode::GsmKeyAndQuintuplets => SecurityContext::GsmKeyAndQuintuplets {
+                cksn: key_set_identifier,
+                used_cipher,
+                kc,
+                quintuplets,
+            },
+            SecurityMode::UmtsKeysAndQuintuplets => SecurityContext::UmtsKeysAndQuintuplets {
+                ksi: key_set_identifier,
+                ck,
+                ik,
+                quintuplets,
+            },
+            SecurityMode::UsedCipherUmtsKeysAndQuintuplets => SecurityContext::UsedCipherUmtsKeysAndQuintuplets {
+                ksi: key_set_identifier,
+                used_cipher,
+                ck,
+                ik,
+                quintuplets,
+            },
+        };
+
+        let used_gprs_integrity_protection_algorithm = if gsm_keys || ugipai { None } else { Some(integrity_algorithm) };
+
+        Some(
+            (
+                InformationElement {
+                    security_context,
+                    gupii: if gsm_keys { true } else { gupii },
+                    used_gprs_integrity_protection_algorithm,
+                    drx_parameter,
+                    ms_network_capability,
+                    container,
+                    nrsrna,
+                },
+                end
+            )
+        )
+    }
+
+    fn generate_octet_4(&self) -> u8 {
+        let key_set_identifier = self.security_context.key_set_identifier() & 0b111;
+
+        match self.security_context {
+            SecurityContext::GsmKeyAndTriplets { .. } | SecurityContext::GsmKeyAndQuintuplets { .. } => {
+                0b1111_1000 | key_set_identifier
+            },
+            _ => {
+                let gupii = if self.gupii { 1 } else { 0 };
+
+                let (ugipai, integrity_algorithm) = match self.used_gprs_integrity_protection_algorithm {
+                    Some(a) => (0, a & 0b111),
+                    None => (1, 0b111),
+                };
+
+                (gupii << 7) | (ugipai << 6) | (integrity_algorithm << 3) | key_set_identifier
+            }
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::MmContext
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 3 + self.security_context.length();
+
+        // DRX Parameter, MS Network Capability Length and MS Network Capability
+        length = length + 2 + 1 + self.ms_network_capability.len() as u16;
+
+        // Container Length and Container
+        length = length + 2 + self.container.len() as u16;
+
+        if let Some(_) = self.nrsrna {
+            length = length + 2;
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-3);
+        pos = pos + 2;
+
+        buffer[pos] = self.generate_octet_4();
+        pos = pos + 1;
+
+        let used_cipher = match self.security_context.used_cipher() {
+            Some(c) => c & 0b111,
+            None => 0b111, // Spare
+        };
+
+        buffer[pos] = ((self.security_context.security_mode() as u8) << 6) |
+            ((self.security_context.number_of_vectors() as u8 & 0b111) << 3) |
+            used_cipher;
+        pos = pos + 1;
+
+        match self.security_context {
+            SecurityContext::GsmKeyAndTriplets { kc, ref triplets, .. } => {
+                buffer[pos..pos+8].copy_from_slice(&kc);
+                pos = pos + 8;
+
+                for t in triplets.iter() {
+                    pos = pos + t.generate(&mut buffer[pos..]);
+                }
+            },
+            SecurityContext::GsmKeyAndQuintuplets { kc, .. } => {
+                buffer[pos..pos+8].copy_from_slice(&kc);
+                pos = pos + 8;
+            },
+            SecurityContext::UmtsKeysAndQuintuplets { ck, ik, .. } |
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { ck, ik, .. } => {
+                buffer[pos..pos+16].copy_from_slice(&ck);
+                pos = pos + 16;
+                buffer[pos..pos+16].copy_from_slice(&ik);
+                pos = pos + 16;
+            },
+        }
+
+        if let Some(quintuplets) = self.security_context.quintuplets() {
+            let quintuplet_length: u16 = quintuplets.iter().map(|q| q.length()).sum();
+
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], quintuplet_length);
+            pos = pos + 2;
+
+            for q in quintuplets.iter() {
+                pos = pos + q.generate(&mut buffer[pos..]);
+            }
+        }
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.drx_parameter);
+        pos = pos + 2;
+
+        buffer[pos] = self.ms_network_capability.len() as u8;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.ms_network_capability.len()].copy_from_slice(&self.ms_network_capability);
+        pos = pos + self.ms_network_capability.len();
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.container.len() as u16);
+        pos = pos + 2;
+
+        buffer[pos..pos+self.container.len()].copy_from_slice(&self.container);
+        pos = pos + self.container.len();
+
+        if let Some(nrsrna) = self.nrsrna {
+            buffer[pos] = 1;
+            pos = pos + 1;
+
+            buffer[pos] = if nrsrna { 1 } else { 0 };
+            pos = pos + 1;
+        }
+
+        pos
+    }
+}
+
+impl Dissect for Triplet {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("Triplet", |d| {
+            d.hex("RAND", &self.rand);
+            d.hex("SRES", &self.sres);
+            d.hex("Kc", &self.kc);
+        });
+    }
+}
+
+impl Dissect for Quintuplet {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("Quintuplet", |d| {
+            d.hex("RAND", &self.rand);
+            d.hex("XRES", &self.xres);
+            d.hex("CK", &self.ck);
+            d.hex("IK", &self.ik);
+            d.hex("AUTN", &self.autn);
+        });
+    }
+}
+
+impl Dissect for SecurityContext {
+    fn dissect(&self, d: &mut Dissector) {
+        d.field("Security Mode", format!("{:?}", self.security_mode()));
+
+        match self {
+            SecurityContext::GsmKeyAndTriplets { cksn, used_cipher, kc, triplets } => {
+                d.field("CKSN", cksn);
+                d.field("Used Cipher", used_cipher);
+                d.hex("Kc", kc);
+
+                for t in triplets {
+                    t.dissect(d);
+                }
+            },
+            SecurityContext::GsmKeyAndQuintuplets { cksn, used_cipher, kc, quintuplets } => {
+                d.field("CKSN", cksn);
+                d.field("Used Cipher", used_cipher);
+                d.hex("Kc", kc);
+
+                for q in quintuplets {
+                    q.dissect(d);
+                }
+            },
+            SecurityContext::UmtsKeysAndQuintuplets { ksi, ck, ik, quintuplets } => {
+                d.field("KSI", ksi);
+                d.hex("CK", ck);
+                d.hex("IK", ik);
+
+                for q in quintuplets {
+                    q.dissect(d);
+                }
+            },
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets { ksi, used_cipher, ck, ik, quintuplets } => {
+                d.field("KSI", ksi);
+                d.field("Used Cipher", used_cipher);
+                d.hex("CK", ck);
+                d.hex("IK", ik);
+
+                for q in quintuplets {
+                    q.dissect(d);
+                }
+            },
+        }
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.security_context.dissect(d);
+            d.flag("GUPII", self.gupii);
+
+            if let Some(algorithm) = self.used_gprs_integrity_protection_algorithm {
+                d.field("Used GPRS Integrity Protection Algorithm", algorithm);
+            }
+
+            d.field("DRX Parameter", format!("{:#06x}", self.drx_parameter));
+            d.hex("MS Network Capability", &self.ms_network_capability);
+            d.hex("Container", &self.container);
+
+            if let Some(nrsrna) = self.nrsrna {
+                d.flag("NRSRNA", nrsrna);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    fn new_quintuplet() -> Quintuplet {
+        Quintuplet {
+            rand: [0x11; 16],
+            xres: vec![0x22; 8],
+            ck: [0x33; 16],
+            ik: [0x44; 16],
+            autn: vec![0x55; 16],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(
+            SecurityContext::UmtsKeysAndQuintuplets { ksi: 1, ck: [0; 16], ik: [0; 16], quintuplets: vec![new_quintuplet(); 5] },
+            0
+        ).is_ok());
+
+        assert!(InformationElement::new(
+            SecurityContext::UmtsKeysAndQuintuplets { ksi: 1, ck: [0; 16], ik: [0; 16], quintuplets: vec![new_quintuplet(); 6] },
+            0
+        ).is_err());
+
+        assert!(InformationElement::new(
+            SecurityContext::GsmKeyAndTriplets { cksn: 8, used_cipher: 0, kc: [0; 8], triplets: vec![] },
+            0
+        ).is_err());
+
+        assert!(InformationElement::new(
+            SecurityContext::GsmKeyAndTriplets { cksn: 0, used_cipher: 8, kc: [0; 8], triplets: vec![] },
+            0
+        ).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(
+            SecurityContext::GsmKeyAndTriplets {
+                cksn: 3,
+                used_cipher: 1,
+                kc: [0xAA; 8],
+                triplets: vec![Triplet { rand: [0x11; 16], sres: [0x22; 4], kc: [0x33; 8] }],
+            },
+            0x1234
+        ).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        let mut expected = vec![InformationElementType::MmContext as u8,
+            0, 43, // Length
+            0b1111_1011, // Spare and CKSN
+            (1 << 6) | (1 << 3) | 1, // Security Mode, No of Vectors and Used Cipher
+        ];
+        expected.extend_from_slice(&[0xAA; 8]); // Kc
+        expected.extend_from_slice(&[0x11; 16]); // RAND
+        expected.extend_from_slice(&[0x22; 4]); // SRES
+        expected.extend_from_slice(&[0x33; 8]); // Kc
+        expected.extend_from_slice(&[
+            0x12, 0x34, // DRX Parameter
+            0, // MS Network Capability Length
+            0, 0, // Container Length
+        ]);
+
+        assert_eq!(buffer[..pos], expected[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut ie = InformationElement::new(
+            SecurityContext::UmtsKeysAndQuintuplets { ksi: 1, ck: [0; 16], ik: [0; 16], quintuplets: vec![new_quintuplet()] },
+            0
+        ).unwrap();
+
+        // Header + Octets 4 and 5 + CK + IK + Quintuplet Length + Quintuplet + DRX + MS NC Length + Container Length
+        assert_eq!(ie.length(), 3 + 2 + 32 + 2 + 74 + 2 + 1 + 2);
+
+        ie.ms_network_capability = vec![0xE5, 0xE0];
+        ie.nrsrna = Some(true);
+
+        assert_eq!(ie.length(), 3 + 2 + 32 + 2 + 74 + 2 + 1 + 2 + 2 + 2);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(
+            SecurityContext::GsmKeyAndTriplets { cksn: 0, used_cipher: 0, kc: [0; 8], triplets: vec![] },
+            0
+        ).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::MmContext as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let mut ie = InformationElement::new(
+            SecurityContext::UsedCipherUmtsKeysAndQuintuplets {
+                ksi: 2,
+                used_cipher: 3,
+                ck: [0xC0; 16],
+                ik: [0x1C; 16],
+                quintuplets: vec![new_quintuplet(), new_quintuplet()],
+            },
+            0x0A0B
+        ).unwrap();
+
+        ie.used_gprs_integrity_protection_algorithm = Some(4);
+        ie.ms_network_capability = vec![0xE5, 0xE0];
+        ie.container = vec![0x23, 0x09, 0x33, 0x55, 0x44, 0x33, 0x22, 0x11, 0x00, 0xF0, 0x01];
+        ie.nrsrna = Some(true);
+
+        let pos = ie.generate(&mut buffer);
+
+        if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[..pos]) {
+            assert_eq!(ie_pos, pos);
+            assert_eq!(ie.security_context, SecurityContext::UsedCipherUmtsKeysAndQuintuplets {
+                ksi: 2,
+                used_cipher: 3,
+                ck: [0xC0; 16],
+                ik: [0x1C; 16],
+                quintuplets: vec![new_quintuplet(), new_quintuplet()],
+            });
+            assert_eq!(ie.gupii, true);
+            assert_eq!(ie.used_gprs_integrity_protection_algorithm, Some(4));
+            assert_eq!(ie.drx_parameter, 0x0A0B);
+            assert_eq!(ie.ms_network_capability, vec![0xE5, 0xE0]);
+            assert_eq!(ie.container.len(), 11);
+            assert_eq!(ie.nrsrna, Some(true));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&buffer[..pos-10]).is_none());
+
+        // Without the Access Restriction Data
+        let ie_bytes = [InformationElementType::MmContext as u8,
+            0, 15, // Length
+            0b1111_1000, // Spare and CKSN
+            (3 << 6) | (0 << 3) | 0, // Security Mode, No of Vectors and Used Cipher
+            1, 2, 3, 4, 5, 6, 7, 8, // Kc
+            0, 0, // Quintuplet Length
+            0x00, 0x00, // DRX Parameter
+            0, // MS Network Capability Length
+            0, 0, // Container Length
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.security_context, SecurityContext::GsmKeyAndQuintuplets {
+                cksn: 0,
+                used_cipher: 0,
+                kc: [1, 2, 3, 4, 5, 6, 7, 8],
+                quintuplets: vec![],
+            });
+            assert_eq!(ie.nrsrna, None);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,5 +1,12 @@
-use super::{InformationElementTraits, InformationElementType};
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
     /*
                                         Bits
@@ -26,8 +33,23 @@ impl InformationElement {
         })
     }
 
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        None
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    nsapi: buffer[1] & 0xF
+                },
+                2
+            )
+        )
+    }
+
+    pub fn nsapi(&self) -> u8 {
+        self.nsapi
     }
 }
 
@@ -56,6 +78,14 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("NSAPI", self.nsapi);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +176,12 @@ mod tests {
 
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Nsapi as u8, 0x05]) {
+            assert_eq!(ie.nsapi(), 5);
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
     }
 }
\ No newline at end of file
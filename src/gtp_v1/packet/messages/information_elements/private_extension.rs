@@ -0,0 +1,180 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const LENGTH: Field = 1..3;
+pub const EXTENSION_IDENTIFIER: Field = 3..5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (255)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Extension Identifier Octet 1                                  |
+        5       | Extension Identifier Octet 2                                  |
+        6->n    | Extension Value                                               |
+                |---------------------------------------------------------------|
+
+        The Extension Identifier is the vendor's IANA Private Enterprise Number (TS 29.060 7.7.46)
+    */
+    pub extension_identifier: u16,
+    pub extension_value: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(extension_identifier: u16, extension_value: &[u8]) -> Result<Self, String> {
+        if extension_value.len() > (u16::MAX - 5) as usize {
+            return Err(format!("Extension Value is too long {}", extension_value.len()));
+        }
+
+        Ok(
+            InformationElement {
+                extension_identifier,
+                extension_value: extension_value.to_vec(),
+            }
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize;
+        pos = pos + 2;
+
+        if length < 2 || buffer.len() < pos + length {
+            return None
+        }
+
+        let extension_identifier = NetworkEndian::read_u16(&buffer[EXTENSION_IDENTIFIER]);
+        pos = pos + 2;
+
+        let extension_value = buffer[pos..3+length].to_vec();
+        pos = pos + extension_value.len();
+
+        Some(
+            (
+                InformationElement {
+                    extension_identifier,
+                    extension_value,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::PrivateExtension
+    }
+
+    fn length(&self) -> u16 {
+        5 + self.extension_value.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-3);
+        pos = pos + 2;
+
+        NetworkEndian::write_u16(&mut buffer[EXTENSION_IDENTIFIER], self.extension_identifier);
+        pos = pos + 2;
+
+        buffer[pos..pos+self.extension_value.len()].copy_from_slice(&self.extension_value);
+        pos = pos + self.extension_value.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Extension Identifier", self.extension_identifier);
+            d.hex("Extension Value", &self.extension_value);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(0x1234, &[0xDE, 0xAD]).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PrivateExtension as u8, 0, 4, 0x12, 0x34, 0xDE, 0xAD]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(0x1234, &[0xDE, 0xAD]).unwrap();
+
+        assert_eq!(ie.length(), 7);
+
+        let ie = InformationElement::new(0x1234, &[]).unwrap();
+
+        assert_eq!(ie.length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(0x1234, &[0xDE, 0xAD]).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::PrivateExtension as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::PrivateExtension as u8, 0, 4, 0x12, 0x34, 0xDE, 0xAD]) {
+            assert_eq!(ie.extension_identifier, 0x1234);
+            assert_eq!(ie.extension_value, vec![0xDE, 0xAD]);
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&[InformationElementType::PrivateExtension as u8, 0, 5, 0x12, 0x34, 0xDE, 0xAD]).is_none());
+
+        // Too short for the Extension Identifier
+        assert!(InformationElement::parse(&[InformationElementType::PrivateExtension as u8, 0, 1, 0x12, 0x34]).is_none());
+    }
+}
@@ -1,14 +1,35 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
+use core::convert::TryFrom;
+
 use crate::field::*;
 
-use super::{InformationElementTraits, InformationElementType};
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
 
 pub const LENGTH: Field = 1..3;
-pub const IPV4: Field = 3..7;
-pub const IPV6: Field = 3..13;
 
-#[derive(Copy, Clone, Debug)]
+// Lengths of the value part (ie. excluding the type and length octets) for each release of the IE
+pub const R97_LENGTH: u16 = 4;
+pub const R99_LENGTH: u16 = 12;
+pub const R5_LENGTH: u16 = 13;
+pub const R7_LENGTH: u16 = 17;
+pub const R8_LENGTH: u16 = 21;
+
+// The largest bitrate that can be encoded in each of the bitrate octets (TS 24.008 10.5.6.5)
+pub const MAX_BITRATE_KBPS: u32 = 8640;
+pub const MAX_EXTENDED_BITRATE_KBPS: u32 = 256_000;
+pub const MAX_EXTENDED_2_BITRATE_KBPS: u32 = 10_000_000;
+
+pub const MAX_SDU_SIZE: u16 = 1520;
+pub const MAX_TRANSFER_DELAY_MS: u16 = 4000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum ReliabilityClass {
     SubscribedOrReserved = 0,
     Unused = 1,
@@ -19,7 +40,26 @@ pub enum ReliabilityClass {
     Reserved = 7
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for ReliabilityClass
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ReliabilityClass::SubscribedOrReserved),
+            1 => Ok(ReliabilityClass::Unused),
+            2 => Ok(ReliabilityClass::UnAckGTPAckLLCAckRLCProtectedData),
+            3 => Ok(ReliabilityClass::UnAckGTPUnAckLLCAckRLCProtectedData),
+            4 => Ok(ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCProtectedData),
+            5 => Ok(ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCUnProtectedData),
+            7 => Ok(ReliabilityClass::Reserved),
+            _ => Err(format!("Unsupported Reliability Class ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum DelayClass {
     SubscribedOrReserved = 0,
     DelayClass1 = 1,
@@ -29,7 +69,25 @@ pub enum DelayClass {
     Reserved = 7,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for DelayClass
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DelayClass::SubscribedOrReserved),
+            1 => Ok(DelayClass::DelayClass1),
+            2 => Ok(DelayClass::DelayClass2),
+            3 => Ok(DelayClass::DelayClass3),
+            4 => Ok(DelayClass::BestEffort),
+            7 => Ok(DelayClass::Reserved),
+            _ => Err(format!("Unsupported Delay Class ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum PrecedenceClass {
     SubscribedOrReserved = 0,
     HighPriority = 1,
@@ -38,7 +96,24 @@ pub enum PrecedenceClass {
     Reserved = 7,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for PrecedenceClass
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PrecedenceClass::SubscribedOrReserved),
+            1 => Ok(PrecedenceClass::HighPriority),
+            2 => Ok(PrecedenceClass::NormalPriority),
+            3 => Ok(PrecedenceClass::LowPriority),
+            7 => Ok(PrecedenceClass::Reserved),
+            _ => Err(format!("Unsupported Precedence Class ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum PeakThroughput {
     SubscribedOrReserved = 0,
     UpTo1000OctetsPerSecond = 1,
@@ -50,10 +125,33 @@ pub enum PeakThroughput {
     UpTo64000OctetsPerSecond = 7,
     UpTo128000OctetsPerSecond = 8,
     UpTo256000OctetsPerSecond = 9,
-    Reserved = 0xFF,
+    Reserved = 0xF,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for PeakThroughput
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PeakThroughput::SubscribedOrReserved),
+            1 => Ok(PeakThroughput::UpTo1000OctetsPerSecond),
+            2 => Ok(PeakThroughput::UpTo2000OctetsPerSecond),
+            3 => Ok(PeakThroughput::UpTo4000OctetsPerSecond),
+            4 => Ok(PeakThroughput::UpTo8000OctetsPerSecond),
+            5 => Ok(PeakThroughput::UpTo16000OctetsPerSecond),
+            6 => Ok(PeakThroughput::UpTo32000OctetsPerSecond),
+            7 => Ok(PeakThroughput::UpTo64000OctetsPerSecond),
+            8 => Ok(PeakThroughput::UpTo128000OctetsPerSecond),
+            9 => Ok(PeakThroughput::UpTo256000OctetsPerSecond),
+            0xF => Ok(PeakThroughput::Reserved),
+            _ => Err(format!("Unsupported Peak Throughput ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum MeanThroughput {
     SubscribedOrReserved = 0,
     OctetsPerHour100 = 1,
@@ -72,13 +170,46 @@ pub enum MeanThroughput {
     OctetsPerHour2000000 = 14,
     OctetsPerHour5000000 = 15,
     OctetsPerHour10000000 = 16,
-    OctetsPerHour20000000 = 17, 
+    OctetsPerHour20000000 = 17,
     OctetsPerHour50000000 = 18,
     Reserved = 0x1E,
     BestEffort = 0x1F,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for MeanThroughput
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MeanThroughput::SubscribedOrReserved),
+            1 => Ok(MeanThroughput::OctetsPerHour100),
+            2 => Ok(MeanThroughput::OctetsPerHour200),
+            3 => Ok(MeanThroughput::OctetsPerHour500),
+            4 => Ok(MeanThroughput::OctetsPerHour1000),
+            5 => Ok(MeanThroughput::OctetsPerHour2000),
+            6 => Ok(MeanThroughput::OctetsPerHour5000),
+            7 => Ok(MeanThroughput::OctetsPerHour10000),
+            8 => Ok(MeanThroughput::OctetsPerHour20000),
+            9 => Ok(MeanThroughput::OctetsPerHour50000),
+            10 => Ok(MeanThroughput::OctetsPerHour100000),
+            11 => Ok(MeanThroughput::OctetsPerHour200000),
+            12 => Ok(MeanThroughput::OctetsPerHour500000),
+            13 => Ok(MeanThroughput::OctetsPerHour1000000),
+            14 => Ok(MeanThroughput::OctetsPerHour2000000),
+            15 => Ok(MeanThroughput::OctetsPerHour5000000),
+            16 => Ok(MeanThroughput::OctetsPerHour10000000),
+            17 => Ok(MeanThroughput::OctetsPerHour20000000),
+            18 => Ok(MeanThroughput::OctetsPerHour50000000),
+            0x1E => Ok(MeanThroughput::Reserved),
+            0x1F => Ok(MeanThroughput::BestEffort),
+            _ => Err(format!("Unsupported Mean Throughput ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum DeliveryOfErroneusSDUs {
     SubscribedOrReserved = 0,
     NoDetect = 1,
@@ -87,16 +218,48 @@ pub enum DeliveryOfErroneusSDUs {
     Reserved = 7
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for DeliveryOfErroneusSDUs
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DeliveryOfErroneusSDUs::SubscribedOrReserved),
+            1 => Ok(DeliveryOfErroneusSDUs::NoDetect),
+            2 => Ok(DeliveryOfErroneusSDUs::Yes),
+            3 => Ok(DeliveryOfErroneusSDUs::No),
+            7 => Ok(DeliveryOfErroneusSDUs::Reserved),
+            _ => Err(format!("Unsupported Delivery of Erroneous SDUs ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum DeliveryOrder {
     SubscribedOrReserved = 0,
-    NoDetect = 1,
-    Yes = 2,
-    No = 3,
-    Reserved = 7
+    Yes = 1,
+    No = 2,
+    Reserved = 3
+}
+
+impl TryFrom<u8> for DeliveryOrder
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DeliveryOrder::SubscribedOrReserved),
+            1 => Ok(DeliveryOrder::Yes),
+            2 => Ok(DeliveryOrder::No),
+            3 => Ok(DeliveryOrder::Reserved),
+            _ => Err(format!("Unsupported Delivery Order ({})", value))
+        }
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum TrafficClass {
     SubscribedOrReserved = 0,
     Conversational = 1,
@@ -106,7 +269,25 @@ pub enum TrafficClass {
     Reserved = 7
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for TrafficClass
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TrafficClass::SubscribedOrReserved),
+            1 => Ok(TrafficClass::Conversational),
+            2 => Ok(TrafficClass::Streaming),
+            3 => Ok(TrafficClass::Interactive),
+            4 => Ok(TrafficClass::Background),
+            7 => Ok(TrafficClass::Reserved),
+            _ => Err(format!("Unsupported Traffic Class ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum ResidualBitErrorRate {
     SubscribedOrReserved = 0,
     BER5x10POWn2 = 1,
@@ -117,11 +298,34 @@ pub enum ResidualBitErrorRate {
     BER1x10POWn4 = 6,
     BER1x10POWn5 = 7,
     BER1x10POWn6 = 8,
-    BER1x10POWn8 = 9,
-    Reserved = 0xFF,
+    BER6x10POWn8 = 9,
+    Reserved = 0xF,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for ResidualBitErrorRate
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ResidualBitErrorRate::SubscribedOrReserved),
+            1 => Ok(ResidualBitErrorRate::BER5x10POWn2),
+            2 => Ok(ResidualBitErrorRate::BER1x10POWn2),
+            3 => Ok(ResidualBitErrorRate::BER5x10POWn3),
+            4 => Ok(ResidualBitErrorRate::BER4x10POWn3),
+            5 => Ok(ResidualBitErrorRate::BER1x10POWn3),
+            6 => Ok(ResidualBitErrorRate::BER1x10POWn4),
+            7 => Ok(ResidualBitErrorRate::BER1x10POWn5),
+            8 => Ok(ResidualBitErrorRate::BER1x10POWn6),
+            9 => Ok(ResidualBitErrorRate::BER6x10POWn8),
+            0xF => Ok(ResidualBitErrorRate::Reserved),
+            _ => Err(format!("Unsupported Residual BER ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum SDUErrorRatio {
     SubscribedOrReserved = 0,
     SDUER1x10POWn2 = 1,
@@ -131,10 +335,31 @@ pub enum SDUErrorRatio {
     SDUER1x10POWn5 = 5,
     SDUER1x10POWn6 = 6,
     SDUER1x10POWn1 = 7,
-    Reserved = 0xFF,
+    Reserved = 0xF,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for SDUErrorRatio
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SDUErrorRatio::SubscribedOrReserved),
+            1 => Ok(SDUErrorRatio::SDUER1x10POWn2),
+            2 => Ok(SDUErrorRatio::SDUER7x10POWn3),
+            3 => Ok(SDUErrorRatio::SDUER1x10POWn3),
+            4 => Ok(SDUErrorRatio::SDUER1x10POWn4),
+            5 => Ok(SDUErrorRatio::SDUER1x10POWn5),
+            6 => Ok(SDUErrorRatio::SDUER1x10POWn6),
+            7 => Ok(SDUErrorRatio::SDUER1x10POWn1),
+            0xF => Ok(SDUErrorRatio::Reserved),
+            _ => Err(format!("Unsupported SDU Error Ratio ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum TrafficHandlingPriority {
     SubscribedOrReserved = 0,
     PriorityLevel1 = 1,
@@ -142,23 +367,219 @@ pub enum TrafficHandlingPriority {
     PriorityLevel3 = 3,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for TrafficHandlingPriority
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TrafficHandlingPriority::SubscribedOrReserved),
+            1 => Ok(TrafficHandlingPriority::PriorityLevel1),
+            2 => Ok(TrafficHandlingPriority::PriorityLevel2),
+            3 => Ok(TrafficHandlingPriority::PriorityLevel3),
+            _ => Err(format!("Unsupported Traffic Handling Priority ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum SourceStatisticsDescriptor {
     UnknownOrSpare = 0,
     Speech = 1,
 }
 
-#[derive(Copy, Clone, Debug)]
+impl TryFrom<u8> for SourceStatisticsDescriptor
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(SourceStatisticsDescriptor::UnknownOrSpare),
+            1 => Ok(SourceStatisticsDescriptor::Speech),
+            _ => Err(format!("Unsupported Source Statistics Descriptor ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum SignallingIndication {
     NotOptimisedForSignallingTraffic = 0,
     OptimisedForSignallingTraffic = 1,
 }
 
+pub fn kbps_to_bitrate(kbps: u32) -> Result<(u8, u8, u8), String> {
+    // Encodes a bitrate as its (octet, extended octet, extended-2 octet) values.
+    // Bitrates that fall between two steps are rounded up to the next step.
+    if kbps > MAX_EXTENDED_2_BITRATE_KBPS {
+        return Err(format!("Bitrate is > {} kbps ({})", MAX_EXTENDED_2_BITRATE_KBPS, kbps))
+    }
+
+    let bitrate = match kbps {
+        0 => (0xFF, 0, 0),
+        1..=63 => (kbps as u8, 0, 0),
+        64..=568 => (0x40 + (kbps - 64).div_ceil(8) as u8, 0, 0),
+        569..=MAX_BITRATE_KBPS => (0x80 + kbps.saturating_sub(576).div_ceil(64) as u8, 0, 0),
+        8641..=16_000 => (0xFE, (kbps - 8600).div_ceil(100) as u8, 0),
+        16_001..=128_000 => (0xFE, 0x4A + (kbps - 16_000).div_ceil(1000) as u8, 0),
+        128_001..=MAX_EXTENDED_BITRATE_KBPS => (0xFE, 0xBA + (kbps - 128_000).div_ceil(2000) as u8, 0),
+        256_001..=500_000 => (0xFE, 0xFA, (kbps - 256_000).div_ceil(4000) as u8),
+        500_001..=1_500_000 => (0xFE, 0xFA, 0x3D + (kbps - 500_000).div_ceil(10_000) as u8),
+        _ => (0xFE, 0xFA, 0xA1 + (kbps - 1_500_000).div_ceil(100_000) as u8),
+    };
+
+    Ok(bitrate)
+}
+
+pub fn bitrate_to_kbps(bitrate: u8, extended: u8, extended_2: u8) -> Option<u32> {
+    // Decodes a bitrate from its (octet, extended octet, extended-2 octet) values.
+    // None is the subscribed bitrate. Values beyond the last step are treated as the maximum
+    if extended_2 != 0 {
+        let v = extended_2.min(0xF6) as u32;
+
+        match v {
+            0x01..=0x3D => Some(256_000 + v * 4000),
+            0x3E..=0xA1 => Some(500_000 + (v - 0x3D) * 10_000),
+            _ => Some(1_500_000 + (v - 0xA1) * 100_000),
+        }
+    }
+    else if extended != 0 {
+        let v = extended.min(0xFA) as u32;
+
+        match v {
+            0x01..=0x4A => Some(8600 + v * 100),
+            0x4B..=0xBA => Some(16_000 + (v - 0x4A) * 1000),
+            _ => Some(128_000 + (v - 0xBA) * 2000),
+        }
+    }
+    else {
+        let v = bitrate as u32;
+
+        match v {
+            0x00 => None,
+            0x01..=0x3F => Some(v),
+            0x40..=0x7F => Some(64 + (v - 0x40) * 8),
+            0xFF => Some(0),
+            _ => Some(576 + (v - 0x80) * 64),
+        }
+    }
+}
+
+fn encode_bitrate(kbps: Option<u32>) -> (u8, u8, u8) {
+    match kbps {
+        Some(kbps) => kbps_to_bitrate(kbps).unwrap_or((0xFE, 0xFA, 0xF6)),
+        None => (0, 0, 0),
+    }
+}
+
+fn encode_maximum_sdu_size(octets: u16) -> Result<u8, String> {
+    // 0 is the subscribed maximum SDU size. Sizes up to 1500 octets are rounded up to the next 10 octets
+    match octets {
+        0..=1500 => Ok(octets.div_ceil(10) as u8),
+        1502 => Ok(0x97),
+        1510 => Ok(0x98),
+        MAX_SDU_SIZE => Ok(0x99),
+        _ => Err(format!("Unsupported Maximum SDU Size ({})", octets))
+    }
+}
+
+fn decode_maximum_sdu_size(value: u8) -> u16 {
+    match value {
+        0x01..=0x96 => value as u16 * 10,
+        0x97 => 1502,
+        0x98 => 1510,
+        0x99 => 1520,
+        // Subscribed or reserved
+        _ => 0,
+    }
+}
+
+fn encode_transfer_delay(ms: u16) -> Result<u8, String> {
+    // 0 is the subscribed transfer delay. Delays that fall between two steps are rounded up to the next step
+    match ms {
+        0..=150 => Ok(ms.div_ceil(10) as u8),
+        151..=950 => Ok(0x10 + ms.saturating_sub(200).div_ceil(50) as u8),
+        951..=MAX_TRANSFER_DELAY_MS => Ok(0x20 + ms.saturating_sub(1000).div_ceil(100) as u8),
+        _ => Err(format!("Transfer Delay is > {} ms ({})", MAX_TRANSFER_DELAY_MS, ms))
+    }
+}
+
+fn decode_transfer_delay(value: u8) -> u16 {
+    let v = value as u16;
+
+    match v {
+        0x01..=0x0F => v * 10,
+        0x10..=0x1F => 200 + (v - 0x10) * 50,
+        0x20..=0x3E => 1000 + (v - 0x20) * 100,
+        // Subscribed or reserved
+        _ => 0,
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct R99 {
+    // Bitrates are in kbps with None being the subscribed bitrate.
+    // maximum_sdu_size (octets) and transfer_delay (ms) use 0 for the subscribed value.
+    pub traffic_class: TrafficClass,
+    pub delivery_order: DeliveryOrder,
+    pub delivery_of_erroneus_sdus: DeliveryOfErroneusSDUs,
+    pub maximum_sdu_size: u16,
+    pub maximum_uplink_bitrate: Option<u32>,
+    pub maximum_downlink_bitrate: Option<u32>,
+    pub residual_ber: ResidualBitErrorRate,
+    pub sdu_error_ratio: SDUErrorRatio,
+    pub transfer_delay: u16,
+    pub traffic_handling_priority: TrafficHandlingPriority,
+    pub guaranteed_uplink_bitrate: Option<u32>,
+    pub guaranteed_downlink_bitrate: Option<u32>,
+}
+
+impl R99 {
+    pub fn validate(&self) -> Result<(), String> {
+        encode_maximum_sdu_size(self.maximum_sdu_size)?;
+        encode_transfer_delay(self.transfer_delay)?;
+
+        for kbps in self.bitrates().iter().flatten() {
+            kbps_to_bitrate(*kbps)?;
+        }
+
+        Ok(())
+    }
+
+    fn bitrates(&self) -> [Option<u32>; 4] {
+        [
+            self.maximum_uplink_bitrate,
+            self.maximum_downlink_bitrate,
+            self.guaranteed_uplink_bitrate,
+            self.guaranteed_downlink_bitrate,
+        ]
+    }
+
+    fn needs_extended(&self) -> bool {
+        self.bitrates().iter().flatten().any(|kbps| *kbps > MAX_BITRATE_KBPS)
+    }
+
+    fn needs_extended_2(&self) -> bool {
+        self.bitrates().iter().flatten().any(|kbps| *kbps > MAX_EXTENDED_BITRATE_KBPS)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct R5 {
+    pub signalling_indication: SignallingIndication,
+    pub source_statistics_descriptor: SourceStatisticsDescriptor,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
                                         Bits
-                |---------------------------------------------------------------| 
+                |---------------------------------------------------------------|
         Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
                 |---------------------------------------------------------------|
         1       | IE Type (135)                                                 |
@@ -170,24 +591,26 @@ pub struct InformationElement {
         7       | 0     | 0     | 0     | Mean Throughput                       |
         8       | Traffic Class         | Delivery Order| Delivery of E SDU     |
         9       | Maximum SDU Size                                              |
-        11      | Maximum Bitrate for Uplink                                    |
-        12      | Maximum Bitrate for Downlink                                  |
-        13      | Residual BER                  | SDU Error Ratio               |
-        14      | Transfer Delay                                | THP           |
-        15      | Guaranteed Bitrate for Uplink                                 |
-        16      | Guaranteed Bitrate for Downlink                               |
-        17      | 0     | 0     | 0     | SI    | Source Statistics Descriptor  |
-        18      | Maximum Bitrate for Downlink (extended)                       |
-        19      | Guaranteed Bitrate for Downlink (extended)                    |
-        20      | Maximum Bitrate for Uplink (extended)                         |
-        21      | Guaranteed Bitrate for Uplink (extended)                      |
-        22      | Maximum Bitrate for Downlink (extended-2)                     |
-        23      | Guaranteed Bitrate for Downlink (extended-2)                  |
-        24      | Maximum Bitrate for Uplink (extended-2)                       |
-        25      | Guaranteed Bitrate for Uplink (extended-2)                    |
+        10      | Maximum Bitrate for Uplink                                    |
+        11      | Maximum Bitrate for Downlink                                  |
+        12      | Residual BER                  | SDU Error Ratio               |
+        13      | Transfer Delay                                | THP           |
+        14      | Guaranteed Bitrate for Uplink                                 |
+        15      | Guaranteed Bitrate for Downlink                               |
+        16      | 0     | 0     | 0     | SI    | Source Statistics Descriptor  |
+        17      | Maximum Bitrate for Downlink (extended)                       |
+        18      | Guaranteed Bitrate for Downlink (extended)                    |
+        19      | Maximum Bitrate for Uplink (extended)                         |
+        20      | Guaranteed Bitrate for Uplink (extended)                      |
+        21      | Maximum Bitrate for Downlink (extended-2)                     |
+        22      | Guaranteed Bitrate for Downlink (extended-2)                  |
+        23      | Maximum Bitrate for Uplink (extended-2)                       |
+        24      | Guaranteed Bitrate for Uplink (extended-2)                    |
                 |---------------------------------------------------------------|
 
         NOTE: Not all fields are mandatory (only octets 1 - 7 are required)
+        Octets 8 - 15 are the R99 attributes and octet 16 the R5 attributes.
+        The extended octets are only sent when one of the bitrates needs them (TS 24.008 10.5.6.5)
     */
 
     arp: u8,
@@ -196,23 +619,13 @@ pub struct InformationElement {
     peak_throughput: PeakThroughput,
     precedence_class: PrecedenceClass,
     mean_throughput: MeanThroughput,
-    // traffic_class: Option<TrafficClass>,
-    // delivery_order: Option<DeliveryOrder>,
-    // delivery_of_erroneus_sdus: Option<DeliveryOfErroneusSDUs>,
-    // maximum_sdu_size: Option<u8>,
-    // maximum_uplink_bitrate: Option<u32>,
-    // maximum_downlink_bitrate: Option<u32>,
-    // residual_ber: Option<ResidualBitErrorRate>,
-    // sdu_error_ratio: Option<SDUErrorRatio>,
-    // transfer_delay: Option<u32>,
-    // traffic_handling_priority: Option<TrafficHandlingPriority>,
-    // guaranteed_uplink_bitrate: Option<u32>,
-    // guaranteed_downlink_bitrate: Option<u32>,
+    r99: Option<R99>,
+    r5: Option<R5>,
 }
 
 impl InformationElement {
-    pub fn new(arp: u8, 
-            delay_class: DelayClass, 
+    pub fn new(arp: u8,
+            delay_class: DelayClass,
             reliability_class: ReliabilityClass,
             peak_throughput: PeakThroughput,
             precedence_class: PrecedenceClass,
@@ -225,23 +638,131 @@ impl InformationElement {
             peak_throughput,
             precedence_class,
             mean_throughput,
-            // traffic_class: None,
-            // delivery_order: None,
-            // delivery_of_erroneus_sdus: None,
-            // maximum_sdu_size: None,
-            // maximum_uplink_bitrate: None,
-            // maximum_downlink_bitrate: None,
-            // residual_ber: None,
-            // sdu_error_ratio: None,
-            // transfer_delay: None,
-            // traffic_handling_priority: None,
-            // guaranteed_uplink_bitrate: None,
-            // guaranteed_downlink_bitrate: None,
+            r99: None,
+            r5: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+
+        if length < R97_LENGTH || buffer.len() < length as usize + 3 {
+            return None
         }
+
+        let value = &buffer[3..length as usize + 3];
+
+        // Values that aren't defined are interpreted as the spec tells the receiver to (TS 24.008 10.5.6.5)
+        let mut ie = InformationElement::new(
+            value[0],
+            DelayClass::try_from((value[1] >> 3) & 0x07).unwrap_or(DelayClass::BestEffort),
+            ReliabilityClass::try_from(value[1] & 0x07).unwrap_or(ReliabilityClass::UnAckGTPUnAckLLCAckRLCProtectedData),
+            PeakThroughput::try_from(value[2] >> 4).unwrap_or(PeakThroughput::UpTo1000OctetsPerSecond),
+            PrecedenceClass::try_from(value[2] & 0x07).unwrap_or(PrecedenceClass::NormalPriority),
+            MeanThroughput::try_from(value[3] & 0x1F).unwrap_or(MeanThroughput::BestEffort),
+        );
+
+        if length >= R99_LENGTH {
+            // The extended octets are only present in the later releases
+            let extended = |i: u16| if length >= R7_LENGTH { value[i as usize] } else { 0 };
+            let extended_2 = |i: u16| if length >= R8_LENGTH { value[i as usize] } else { 0 };
+
+            ie.r99 = Some(
+                R99 {
+                    traffic_class: TrafficClass::try_from(value[4] >> 5).unwrap_or(TrafficClass::Reserved),
+                    delivery_order: DeliveryOrder::try_from((value[4] >> 3) & 0x03).unwrap_or(DeliveryOrder::Reserved),
+                    delivery_of_erroneus_sdus: DeliveryOfErroneusSDUs::try_from(value[4] & 0x07).unwrap_or(DeliveryOfErroneusSDUs::Reserved),
+                    maximum_sdu_size: decode_maximum_sdu_size(value[5]),
+                    maximum_uplink_bitrate: bitrate_to_kbps(value[6], extended(15), extended_2(19)),
+                    maximum_downlink_bitrate: bitrate_to_kbps(value[7], extended(13), extended_2(17)),
+                    residual_ber: ResidualBitErrorRate::try_from(value[8] >> 4).unwrap_or(ResidualBitErrorRate::Reserved),
+                    sdu_error_ratio: SDUErrorRatio::try_from(value[8] & 0x0F).unwrap_or(SDUErrorRatio::Reserved),
+                    transfer_delay: decode_transfer_delay(value[9] >> 2),
+                    traffic_handling_priority: TrafficHandlingPriority::try_from(value[9] & 0x03).unwrap_or(TrafficHandlingPriority::SubscribedOrReserved),
+                    guaranteed_uplink_bitrate: bitrate_to_kbps(value[10], extended(16), extended_2(20)),
+                    guaranteed_downlink_bitrate: bitrate_to_kbps(value[11], extended(14), extended_2(18)),
+                }
+            );
+        }
+
+        if length >= R5_LENGTH {
+            ie.r5 = Some(
+                R5 {
+                    signalling_indication: if value[12] & 0x10 != 0 {
+                        SignallingIndication::OptimisedForSignallingTraffic
+                    }
+                    else {
+                        SignallingIndication::NotOptimisedForSignallingTraffic
+                    },
+                    source_statistics_descriptor: SourceStatisticsDescriptor::try_from(value[12] & 0x0F).unwrap_or(SourceStatisticsDescriptor::UnknownOrSpare),
+                }
+            );
+        }
+
+        Some((ie, length as usize + 3))
+    }
+
+    pub fn arp(&self) -> u8 {
+        self.arp
+    }
+
+    pub fn delay_class(&self) -> DelayClass {
+        self.delay_class
+    }
+
+    pub fn reliability_class(&self) -> ReliabilityClass {
+        self.reliability_class
+    }
+
+    pub fn peak_throughput(&self) -> PeakThroughput {
+        self.peak_throughput
     }
 
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        None
+    pub fn precedence_class(&self) -> PrecedenceClass {
+        self.precedence_class
+    }
+
+    pub fn mean_throughput(&self) -> MeanThroughput {
+        self.mean_throughput
+    }
+
+    pub fn r99(&self) -> Option<&R99> {
+        self.r99.as_ref()
+    }
+
+    pub fn r5(&self) -> Option<&R5> {
+        self.r5.as_ref()
+    }
+
+    pub fn set_r99(&mut self, r99: R99) -> Result<(), String> {
+        r99.validate()?;
+
+        self.r99 = Some(r99);
+        Ok(())
+    }
+
+    pub fn set_r5(&mut self, r5: R5) -> Result<(), String> {
+        // The R5 attributes follow the R99 attributes so they can't be sent on their own
+        if self.r99.is_none() {
+            return Err("R5 attributes require R99 attributes".to_string())
+        }
+
+        self.r5 = Some(r5);
+        Ok(())
+    }
+
+    fn value_length(&self) -> u16 {
+        match self.r99 {
+            None => R97_LENGTH,
+            Some(r99) if r99.needs_extended_2() => R8_LENGTH,
+            Some(r99) if r99.needs_extended() => R7_LENGTH,
+            Some(_) if self.r5.is_some() => R5_LENGTH,
+            Some(_) => R99_LENGTH,
+        }
     }
 }
 
@@ -251,12 +772,12 @@ impl InformationElementTraits for InformationElement {
     }
 
     fn length(&self) -> u16 {
-        4+3
+        3 + self.value_length()
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
         let mut pos = 0;
-        
+
         // Write the type
         buffer[pos] = self.information_element_type() as u8;
         pos = pos + 1;
@@ -282,18 +803,147 @@ impl InformationElementTraits for InformationElement {
         buffer[pos] = self.mean_throughput as u8;
         pos = pos + 1;
 
-        // TODO: Generate the rest of the optional fields
+        if let Some(r99) = self.r99 {
+            let value_length = self.value_length();
+
+            let maximum_uplink = encode_bitrate(r99.maximum_uplink_bitrate);
+            let maximum_downlink = encode_bitrate(r99.maximum_downlink_bitrate);
+            let guaranteed_uplink = encode_bitrate(r99.guaranteed_uplink_bitrate);
+            let guaranteed_downlink = encode_bitrate(r99.guaranteed_downlink_bitrate);
+
+            // Write Traffic Class, Delivery Order and Delivery of Erroneous SDUs
+            buffer[pos] = (r99.traffic_class as u8) << 5 | (r99.delivery_order as u8) << 3 | (r99.delivery_of_erroneus_sdus as u8);
+            pos = pos + 1;
+
+            // Write Maximum SDU Size
+            buffer[pos] = encode_maximum_sdu_size(r99.maximum_sdu_size).unwrap_or(0x99);
+            pos = pos + 1;
+
+            // Write Maximum Bitrates
+            buffer[pos] = maximum_uplink.0;
+            pos = pos + 1;
+            buffer[pos] = maximum_downlink.0;
+            pos = pos + 1;
+
+            // Write Residual BER and SDU Error Ratio
+            buffer[pos] = (r99.residual_ber as u8) << 4 | (r99.sdu_error_ratio as u8);
+            pos = pos + 1;
+
+            // Write Transfer Delay and Traffic Handling Priority
+            buffer[pos] = encode_transfer_delay(r99.transfer_delay).unwrap_or(0x3E) << 2 | (r99.traffic_handling_priority as u8);
+            pos = pos + 1;
+
+            // Write Guaranteed Bitrates
+            buffer[pos] = guaranteed_uplink.0;
+            pos = pos + 1;
+            buffer[pos] = guaranteed_downlink.0;
+            pos = pos + 1;
+
+            if value_length >= R5_LENGTH {
+                // Write Signalling Indication and Source Statistics Descriptor
+                // This octet is needed before the extended octets even if there are no R5 attributes
+                buffer[pos] = match self.r5 {
+                    Some(r5) => (r5.signalling_indication as u8) << 4 | (r5.source_statistics_descriptor as u8),
+                    None => 0,
+                };
+                pos = pos + 1;
+            }
+
+            if value_length >= R7_LENGTH {
+                // Write the extended bitrates
+                for bitrate in [maximum_downlink, guaranteed_downlink, maximum_uplink, guaranteed_uplink].iter() {
+                    buffer[pos] = bitrate.1;
+                    pos = pos + 1;
+                }
+            }
+
+            if value_length >= R8_LENGTH {
+                // Write the extended-2 bitrates
+                for bitrate in [maximum_downlink, guaranteed_downlink, maximum_uplink, guaranteed_uplink].iter() {
+                    buffer[pos] = bitrate.2;
+                    pos = pos + 1;
+                }
+            }
+        }
 
         pos
     }
 }
 
+fn bitrate_string(kbps: Option<u32>) -> String {
+    match kbps {
+        Some(kbps) => format!("{} kbps", kbps),
+        None => "Subscribed".to_string(),
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Allocation/Retention Priority", self.arp);
+            d.field("Delay Class", format!("{:?}", self.delay_class));
+            d.field("Reliability Class", format!("{:?}", self.reliability_class));
+            d.field("Peak Throughput", format!("{:?}", self.peak_throughput));
+            d.field("Precedence Class", format!("{:?}", self.precedence_class));
+            d.field("Mean Throughput", format!("{:?}", self.mean_throughput));
+
+            if let Some(r99) = self.r99 {
+                d.field("Traffic Class", format!("{:?}", r99.traffic_class));
+                d.field("Delivery Order", format!("{:?}", r99.delivery_order));
+                d.field("Delivery of Erroneous SDUs", format!("{:?}", r99.delivery_of_erroneus_sdus));
+                d.field("Maximum SDU Size", r99.maximum_sdu_size);
+                d.field("Maximum Bitrate for Uplink", bitrate_string(r99.maximum_uplink_bitrate));
+                d.field("Maximum Bitrate for Downlink", bitrate_string(r99.maximum_downlink_bitrate));
+                d.field("Residual BER", format!("{:?}", r99.residual_ber));
+                d.field("SDU Error Ratio", format!("{:?}", r99.sdu_error_ratio));
+                d.field("Transfer Delay (ms)", r99.transfer_delay);
+                d.field("Traffic Handling Priority", format!("{:?}", r99.traffic_handling_priority));
+                d.field("Guaranteed Bitrate for Uplink", bitrate_string(r99.guaranteed_uplink_bitrate));
+                d.field("Guaranteed Bitrate for Downlink", bitrate_string(r99.guaranteed_downlink_bitrate));
+            }
+
+            if let Some(r5) = self.r5 {
+                d.field("Signalling Indication", format!("{:?}", r5.signalling_indication));
+                d.field("Source Statistics Descriptor", format!("{:?}", r5.source_statistics_descriptor));
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::MTU;
     use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
 
+    fn r99() -> R99 {
+        R99 {
+            traffic_class: TrafficClass::Interactive,
+            delivery_order: DeliveryOrder::No,
+            delivery_of_erroneus_sdus: DeliveryOfErroneusSDUs::No,
+            maximum_sdu_size: 1500,
+            maximum_uplink_bitrate: Some(256),
+            maximum_downlink_bitrate: Some(8640),
+            residual_ber: ResidualBitErrorRate::BER1x10POWn5,
+            sdu_error_ratio: SDUErrorRatio::SDUER1x10POWn4,
+            transfer_delay: 0,
+            traffic_handling_priority: TrafficHandlingPriority::PriorityLevel3,
+            guaranteed_uplink_bitrate: None,
+            guaranteed_downlink_bitrate: None,
+        }
+    }
+
+    fn new_ie() -> InformationElement {
+        InformationElement::new(
+            8,
+            DelayClass::BestEffort,
+            ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCUnProtectedData,
+            PeakThroughput::UpTo1000OctetsPerSecond,
+            PrecedenceClass::NormalPriority,
+            MeanThroughput::BestEffort
+        )
+    }
+
     #[test]
     fn test_generate() {
         let mut buffer = [0; MTU];
@@ -316,7 +966,7 @@ mod tests {
 
         let pos = ie.generate(&mut buffer);
 
-        assert_eq!(buffer[..pos], 
+        assert_eq!(buffer[..pos],
             [
                 InformationElementType::QoSProfile as u8,
                 0, 4,
@@ -326,50 +976,258 @@ mod tests {
                 0x1F
         ]);
     }
-    
+
     #[test]
-    fn test_length() {
-        let arp = 8;
-        let delay_class = DelayClass::BestEffort;
-        let reliability_class = ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCUnProtectedData;
-        let peak_throughput = PeakThroughput::UpTo1000OctetsPerSecond;
-        let precedence_class = PrecedenceClass::NormalPriority;
-        let mean_throughput = MeanThroughput::BestEffort;
+    fn test_generate_r99() {
+        let mut buffer = [0; MTU];
 
-        let ie = InformationElement::new(
-            arp,
-            delay_class,
-            reliability_class,
-            peak_throughput,
-            precedence_class,
-            mean_throughput
-        );
+        let mut ie = new_ie();
+        ie.set_r99(r99()).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos],
+            [
+                InformationElementType::QoSProfile as u8,
+                0, 12,
+                8, 0b0010_0101, 0b0001_0010, 0x1F,
+                0b0111_0011, // Traffic Class, Delivery Order and Delivery of Erroneous SDUs
+                150, // Maximum SDU Size
+                0x58, // Maximum Bitrate for Uplink (256 kbps)
+                0xFE, // Maximum Bitrate for Downlink (8640 kbps)
+                0b0111_0100, // Residual BER and SDU Error Ratio
+                0b0000_0011, // Transfer Delay and THP
+                0, 0, // Guaranteed Bitrates
+        ]);
+
+        assert_eq!(ie.length() as usize, pos);
+
+        ie.set_r5(R5 {
+            signalling_indication: SignallingIndication::OptimisedForSignallingTraffic,
+            source_statistics_descriptor: SourceStatisticsDescriptor::Speech,
+        }).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[2], 13);
+        assert_eq!(buffer[pos-1], 0b0001_0001);
+        assert_eq!(ie.length() as usize, pos);
+    }
+
+    #[test]
+    fn test_generate_extended() {
+        let mut buffer = [0; MTU];
+
+        let mut ie = new_ie();
+
+        let mut r = r99();
+        r.maximum_downlink_bitrate = Some(42_000);
+        r.guaranteed_uplink_bitrate = Some(8700);
+        ie.set_r99(r).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(ie.length(), 20);
+        assert_eq!(buffer[..pos],
+            [
+                InformationElementType::QoSProfile as u8,
+                0, 17,
+                8, 0b0010_0101, 0b0001_0010, 0x1F,
+                0b0111_0011, 150, 0x58, 0xFE, 0b0111_0100, 0b0000_0011, 0xFE, 0,
+                0, // No R5 attributes
+                0x64, // Maximum Bitrate for Downlink (extended) (42 Mbps)
+                0, // Guaranteed Bitrate for Downlink (extended)
+                0, // Maximum Bitrate for Uplink (extended)
+                0x01, // Guaranteed Bitrate for Uplink (extended) (8700 kbps)
+        ]);
+
+        r.maximum_uplink_bitrate = Some(1_000_000);
+        ie.set_r99(r).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(ie.length(), 24);
+        assert_eq!(pos, 24);
+        assert_eq!(buffer[9], 0xFE); // Maximum Bitrate for Uplink
+        assert_eq!(buffer[18], 0xFA); // Maximum Bitrate for Uplink (extended)
+        assert_eq!(buffer[20..24], [0, 0, 0x3D + 50, 0]);
+    }
+
+    #[test]
+    fn test_set_r99() {
+        let mut ie = new_ie();
+
+        // R5 attributes need R99 attributes
+        assert!(ie.set_r5(R5 {
+            signalling_indication: SignallingIndication::NotOptimisedForSignallingTraffic,
+            source_statistics_descriptor: SourceStatisticsDescriptor::UnknownOrSpare,
+        }).is_err());
+
+        let mut r = r99();
+        r.maximum_downlink_bitrate = Some(10_000_001);
+        assert!(ie.set_r99(r).is_err());
+
+        let mut r = r99();
+        r.maximum_sdu_size = 1505;
+        assert!(ie.set_r99(r).is_err());
+
+        let mut r = r99();
+        r.transfer_delay = 4001;
+        assert!(ie.set_r99(r).is_err());
+
+        assert!(ie.r99().is_none());
+
+        assert!(ie.set_r99(r99()).is_ok());
+        assert_eq!(ie.r99(), Some(&r99()));
+    }
+
+    #[test]
+    fn test_kbps_to_bitrate() {
+        assert_eq!(kbps_to_bitrate(0), Ok((0xFF, 0, 0)));
+        assert_eq!(kbps_to_bitrate(63), Ok((0x3F, 0, 0)));
+        assert_eq!(kbps_to_bitrate(64), Ok((0x40, 0, 0)));
+        assert_eq!(kbps_to_bitrate(568), Ok((0x7F, 0, 0)));
+        assert_eq!(kbps_to_bitrate(576), Ok((0x80, 0, 0)));
+        assert_eq!(kbps_to_bitrate(8640), Ok((0xFE, 0, 0)));
+        assert_eq!(kbps_to_bitrate(8700), Ok((0xFE, 0x01, 0)));
+        assert_eq!(kbps_to_bitrate(16_000), Ok((0xFE, 0x4A, 0)));
+        assert_eq!(kbps_to_bitrate(128_000), Ok((0xFE, 0xBA, 0)));
+        assert_eq!(kbps_to_bitrate(256_000), Ok((0xFE, 0xFA, 0)));
+        assert_eq!(kbps_to_bitrate(260_000), Ok((0xFE, 0xFA, 0x01)));
+        assert_eq!(kbps_to_bitrate(1_500_000), Ok((0xFE, 0xFA, 0xA1)));
+        assert_eq!(kbps_to_bitrate(10_000_000), Ok((0xFE, 0xFA, 0xF6)));
+        assert!(kbps_to_bitrate(10_000_001).is_err());
+
+        // Rounded up to the next step
+        assert_eq!(kbps_to_bitrate(65), Ok((0x41, 0, 0)));
+        assert_eq!(kbps_to_bitrate(570), Ok((0x80, 0, 0)));
+        assert_eq!(kbps_to_bitrate(8641), Ok((0xFE, 0x01, 0)));
+    }
+
+    #[test]
+    fn test_bitrate_to_kbps() {
+        assert_eq!(bitrate_to_kbps(0, 0, 0), None);
+        assert_eq!(bitrate_to_kbps(0xFF, 0, 0), Some(0));
+        assert_eq!(bitrate_to_kbps(0x3F, 0, 0), Some(63));
+        assert_eq!(bitrate_to_kbps(0x7F, 0, 0), Some(568));
+        assert_eq!(bitrate_to_kbps(0xFE, 0, 0), Some(8640));
+        assert_eq!(bitrate_to_kbps(0xFE, 0x4B, 0), Some(17_000));
+        assert_eq!(bitrate_to_kbps(0xFE, 0xFA, 0), Some(256_000));
+        assert_eq!(bitrate_to_kbps(0xFE, 0xFA, 0x3E), Some(510_000));
+        assert_eq!(bitrate_to_kbps(0xFE, 0xFA, 0xF6), Some(10_000_000));
+
+        // Values beyond the last step are the maximum
+        assert_eq!(bitrate_to_kbps(0xFE, 0xFF, 0), Some(256_000));
+        assert_eq!(bitrate_to_kbps(0xFE, 0xFA, 0xFF), Some(10_000_000));
+
+        // Every encodable value round trips
+        for kbps in [0, 1, 63, 64, 568, 576, 8640, 8700, 16_000, 17_000, 128_000, 130_000, 256_000, 260_000, 500_000, 510_000, 1_500_000, 1_600_000, 10_000_000].iter() {
+            let (b, e, e2) = kbps_to_bitrate(*kbps).unwrap();
+            assert_eq!(bitrate_to_kbps(b, e, e2), Some(*kbps));
+        }
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = new_ie();
 
         assert_eq!(ie.length(), 7);
     }
+
     #[test]
     fn test_message_type() {
-        let arp = 8;
-        let delay_class = DelayClass::BestEffort;
-        let reliability_class = ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCUnProtectedData;
-        let peak_throughput = PeakThroughput::UpTo1000OctetsPerSecond;
-        let precedence_class = PrecedenceClass::NormalPriority;
-        let mean_throughput = MeanThroughput::BestEffort;
-
-        let ie = InformationElement::new(
-            arp,
-            delay_class,
-            reliability_class,
-            peak_throughput,
-            precedence_class,
-            mean_throughput
-        );
+        let ie = new_ie();
 
         assert_eq!(ie.information_element_type() as u8, InformationElementType::QoSProfile as u8)
     }
 
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((ie, pos)) = InformationElement::parse(&[
+            InformationElementType::QoSProfile as u8,
+            0, 4,
+            8, 0b0010_0101, 0b0001_0010, 0x1F,
+        ]) {
+            assert_eq!(pos, 7);
+            assert_eq!(ie.arp(), 8);
+            assert_eq!(ie.delay_class(), DelayClass::BestEffort);
+            assert_eq!(ie.reliability_class(), ReliabilityClass::UnAckGTPUnAckLLCUnAckRLCUnProtectedData);
+            assert_eq!(ie.peak_throughput(), PeakThroughput::UpTo1000OctetsPerSecond);
+            assert_eq!(ie.precedence_class(), PrecedenceClass::NormalPriority);
+            assert_eq!(ie.mean_throughput(), MeanThroughput::BestEffort);
+            assert!(ie.r99().is_none());
+            assert!(ie.r5().is_none());
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&[InformationElementType::QoSProfile as u8, 0, 4, 8, 0b0010_0101]).is_none());
+        assert!(InformationElement::parse(&[InformationElementType::QoSProfile as u8, 0, 3, 8, 0b0010_0101, 0b0001_0010]).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_message_parse_extended() {
+        let buffer = [
+            InformationElementType::QoSProfile as u8,
+            0, 21,
+            8, 0b0010_0101, 0b0001_0010, 0x1F,
+            0b0111_0011, 150, 0xFE, 0xFE, 0b0111_0100, 0b0000_0011, 0xFE, 0,
+            0b0001_0001,
+            0x64, 0, 0xFA, 0x01,
+            0, 0, 0x3D + 50, 0,
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&buffer) {
+            assert_eq!(pos, 24);
+
+            if let Some(r99) = ie.r99() {
+                assert_eq!(r99.traffic_class, TrafficClass::Interactive);
+                assert_eq!(r99.delivery_order, DeliveryOrder::No);
+                assert_eq!(r99.delivery_of_erroneus_sdus, DeliveryOfErroneusSDUs::No);
+                assert_eq!(r99.maximum_sdu_size, 1500);
+                assert_eq!(r99.maximum_uplink_bitrate, Some(1_000_000));
+                assert_eq!(r99.maximum_downlink_bitrate, Some(42_000));
+                assert_eq!(r99.residual_ber, ResidualBitErrorRate::BER1x10POWn5);
+                assert_eq!(r99.sdu_error_ratio, SDUErrorRatio::SDUER1x10POWn4);
+                assert_eq!(r99.transfer_delay, 0);
+                assert_eq!(r99.traffic_handling_priority, TrafficHandlingPriority::PriorityLevel3);
+                assert_eq!(r99.guaranteed_uplink_bitrate, Some(8700));
+                assert_eq!(r99.guaranteed_downlink_bitrate, None);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(r5) = ie.r5() {
+                assert_eq!(r5.signalling_indication, SignallingIndication::OptimisedForSignallingTraffic);
+                assert_eq!(r5.source_statistics_descriptor, SourceStatisticsDescriptor::Speech);
+            }
+            else {
+                assert!(false);
+            }
+
+            let mut out = [0; MTU];
+            let n = ie.generate(&mut out);
+            assert_eq!(out[..n], buffer[..]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_transfer_delay() {
+        assert_eq!(encode_transfer_delay(10), Ok(0x01));
+        assert_eq!(encode_transfer_delay(150), Ok(0x0F));
+        assert_eq!(encode_transfer_delay(160), Ok(0x10));
+        assert_eq!(encode_transfer_delay(950), Ok(0x1F));
+        assert_eq!(encode_transfer_delay(4000), Ok(0x3E));
+        assert!(encode_transfer_delay(4001).is_err());
+
+        assert_eq!(decode_transfer_delay(0x10), 200);
+        assert_eq!(decode_transfer_delay(0x20), 1000);
+        assert_eq!(decode_transfer_delay(0x3F), 0);
+    }
+}
@@ -0,0 +1,160 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (3)                                                   |
+        2       | MCC digit 2                   | MCC digit 1                   |
+        3       | MNC digit 3                   | MCC digit 3                   |
+        4       | MNC digit 2                   | MNC digit 1                   |
+        5       | LAC Octet 1                                                   |
+        6       | LAC Octet 2                                                   |
+        7       | RAC                                                           |
+                |---------------------------------------------------------------|
+    */
+    pub plmn: PLMN,
+    pub lac: u16,
+    pub rac: u8,
+}
+
+impl InformationElement {
+    pub fn new(plmn: PLMN, lac: u16, rac: u8) -> Self {
+        InformationElement {
+            plmn,
+            lac,
+            rac,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 7 {
+            return None
+        }
+
+        let mut pos = 1;
+
+        if let Some((plmn, plmn_pos)) = PLMN::parse(&buffer[pos..]) {
+            pos = pos + plmn_pos;
+
+            let lac = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+            pos = pos + 2;
+
+            let rac = buffer[pos];
+            pos = pos + 1;
+
+            Some(
+                (
+                    InformationElement {
+                        plmn,
+                        lac,
+                        rac,
+                    },
+                    pos
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Rai
+    }
+
+    fn length(&self) -> u16 {
+        7
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+
+        pos = pos + 1;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.lac);
+
+        pos = pos + 2;
+
+        buffer[pos] = self.rac;
+
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+            d.field("RAC", format!("{:#04x}", self.rac));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Rai as u8, 0x05, 0xF5, 0x10, 0x12, 0x34, 0x56]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56);
+
+        assert_eq!(ie.length(), 7)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::Rai as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Rai as u8, 0x05, 0xF5, 0x10, 0x12, 0x34, 0x56]) {
+            assert_eq!(ie.plmn.mcc(), 505);
+            assert_eq!(ie.plmn.mnc(), 1);
+            assert!(!ie.plmn.three_digit_mnc());
+            assert_eq!(ie.lac, 0x1234);
+            assert_eq!(ie.rac, 0x56);
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (21)                                                  |
+        2       | RANAP Cause                                                   |
+                |---------------------------------------------------------------|
+
+        The RANAP Cause values are defined in TS 25.413
+    */
+    ranap_cause: u8
+}
+
+impl InformationElement {
+    pub fn new(ranap_cause: u8) -> Self {
+        InformationElement {
+            ranap_cause: ranap_cause
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    ranap_cause: buffer[1]
+                },
+                2
+            )
+        )
+    }
+
+    pub fn ranap_cause(&self) -> u8 {
+        self.ranap_cause
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::RanapCause
+    }
+
+    fn length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+
+        pos = pos + 1;
+
+        buffer[pos] = self.ranap_cause;
+
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("RANAP Cause", self.ranap_cause);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(43);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::RanapCause as u8, 43]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(43);
+
+        assert_eq!(ie.length(), 2)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(43);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::RanapCause as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::RanapCause as u8, 43]) {
+            assert_eq!(ie.ranap_cause(), 43);
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
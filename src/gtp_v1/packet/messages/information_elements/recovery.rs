@@ -0,0 +1,122 @@
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (14)                                                  |
+        2       | Restart Counter                                               |
+                |---------------------------------------------------------------|
+
+        On GTP-U paths the Restart Counter is always 0 and ignored by the receiver (TS 29.281 8.2)
+    */
+    restart_counter: u8
+}
+
+impl InformationElement {
+    pub fn new(restart_counter: u8) -> Self {
+        InformationElement {
+            restart_counter
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 2 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    restart_counter: buffer[1]
+                },
+                2
+            )
+        )
+    }
+
+    pub fn restart_counter(&self) -> u8 {
+        self.restart_counter
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Recovery
+    }
+
+    fn length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        buffer[pos] = self.restart_counter;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Restart Counter", self.restart_counter);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(0x12);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Recovery as u8, 0x12]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(0x12);
+
+        assert_eq!(ie.length(), 2)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(0x12);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::Recovery as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::Recovery as u8, 0x12]) {
+            assert_eq!(ie.restart_counter(), 0x12);
+            assert_eq!(pos, 2);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::Recovery as u8]).is_none());
+    }
+}
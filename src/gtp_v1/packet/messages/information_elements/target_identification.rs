@@ -0,0 +1,279 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+use crate::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+pub const LENGTH: Field = 1..3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (138)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | MCC digit 2                   | MCC digit 1                   |
+        5       | MNC digit 3                   | MCC digit 3                   |
+        6       | MNC digit 2                   | MNC digit 1                   |
+        7       | LAC Octet 1                                                   |
+        8       | LAC Octet 2                                                   |
+        9       | RAC                                                           |
+        10      | RNC-ID Octet 1                                                |
+        11      | RNC-ID Octet 2                                                |
+        12      | Extended RNC-ID Octet 1                                       |
+        13      | Extended RNC-ID Octet 2                                       |
+                |---------------------------------------------------------------|
+
+        RNC-ID is 12 bits. The Extended RNC-ID is optional and is in the range 4096 to 65535.
+    */
+    pub plmn: PLMN,
+    pub lac: u16,
+    pub rac: u8,
+    pub rnc_id: u16,
+    pub extended_rnc_id: Option<u16>,
+}
+
+impl InformationElement {
+    pub fn new(plmn: PLMN, lac: u16, rac: u8, rnc_id: u16, extended_rnc_id: Option<u16>) -> Result<Self, String> {
+        if rnc_id > 0xFFF {
+            return Err(format!("RNC-ID is > 0xFFF {}", rnc_id));
+        }
+
+        if let Some(e) = extended_rnc_id {
+            if e < 4096 {
+                return Err(format!("Extended RNC-ID is < 4096 {}", e));
+            }
+        }
+
+        Ok(
+            InformationElement {
+                plmn,
+                lac,
+                rac,
+                rnc_id,
+                extended_rnc_id,
+            }
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if (length != 8 && length != 10) || buffer.len() < (length+3) as usize {
+            return None
+        }
+
+        if let Some((plmn, plmn_pos)) = PLMN::parse(&buffer[pos..]) {
+            pos = pos + plmn_pos;
+
+            let lac = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+            pos = pos + 2;
+
+            let rac = buffer[pos];
+            pos = pos + 1;
+
+            let rnc_id = NetworkEndian::read_u16(&buffer[pos..pos+2]) & 0xFFF;
+            pos = pos + 2;
+
+            let mut extended_rnc_id = None;
+
+            if length == 10 {
+                extended_rnc_id = Some(NetworkEndian::read_u16(&buffer[pos..pos+2]));
+                pos = pos + 2;
+            }
+
+            Some(
+                (
+                    InformationElement {
+                        plmn,
+                        lac,
+                        rac,
+                        rnc_id,
+                        extended_rnc_id,
+                    },
+                    pos
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::TargetIdentification
+    }
+
+    fn length(&self) -> u16 {
+        match self.extended_rnc_id {
+            Some(_) => 3+10,
+            None => 3+8,
+        }
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-3);
+        pos = pos + 2;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.lac);
+        pos = pos + 2;
+
+        buffer[pos] = self.rac;
+        pos = pos + 1;
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.rnc_id & 0xFFF);
+        pos = pos + 2;
+
+        if let Some(e) = self.extended_rnc_id {
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], e);
+            pos = pos + 2;
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+            d.field("RAC", format!("{:#04x}", self.rac));
+            d.field("RNC-ID", self.rnc_id);
+
+            if let Some(extended_rnc_id) = self.extended_rnc_id {
+                d.field("Extended RNC-ID", extended_rnc_id);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0xFFF, None).is_ok());
+        assert!(InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x1000, None).is_err());
+        assert!(InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x123, Some(4095)).is_err());
+        assert!(InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x123, Some(4096)).is_ok());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, None).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TargetIdentification as u8,
+            0, 8,
+            0x05, 0xF5, 0x10,
+            0x12, 0x34,
+            0x56,
+            0x07, 0x89
+        ]);
+
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, Some(0xABCD)).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TargetIdentification as u8,
+            0, 10,
+            0x05, 0xF5, 0x10,
+            0x12, 0x34,
+            0x56,
+            0x07, 0x89,
+            0xAB, 0xCD
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, None).unwrap();
+
+        assert_eq!(ie.length(), 11);
+
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, Some(0xABCD)).unwrap();
+
+        assert_eq!(ie.length(), 13);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56, 0x789, None).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::TargetIdentification as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::TargetIdentification as u8,
+            0, 10,
+            0x05, 0xF5, 0x10,
+            0x12, 0x34,
+            0x56,
+            0x07, 0x89,
+            0xAB, 0xCD
+        ]) {
+            assert_eq!(ie.plmn.mcc(), 505);
+            assert_eq!(ie.plmn.mnc(), 1);
+            assert!(!ie.plmn.three_digit_mnc());
+            assert_eq!(ie.lac, 0x1234);
+            assert_eq!(ie.rac, 0x56);
+            assert_eq!(ie.rnc_id, 0x789);
+            assert_eq!(ie.extended_rnc_id, Some(0xABCD));
+            assert_eq!(pos, 13);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Invalid length
+        assert!(InformationElement::parse(&[InformationElementType::TargetIdentification as u8,
+            0, 9,
+            0x05, 0xF5, 0x10,
+            0x12, 0x34,
+            0x56,
+            0x07, 0x89,
+            0xAB
+        ]).is_none());
+    }
+}
@@ -0,0 +1,134 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (17)                                                  |
+        2       | TEID Octet 1                                                  |
+        3       | TEID Octet 2                                                  |
+        4       | TEID Octet 3                                                  |
+        5       | TEID Octet 4                                                  |
+                |---------------------------------------------------------------|
+    */
+    teid: u32
+}
+
+pub const TEID: Field = 1..5;
+
+impl InformationElement {
+    pub fn new(teid: u32) -> Self {
+        InformationElement {
+            teid: teid
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    teid: NetworkEndian::read_u32(&buffer[TEID])
+                },
+                5
+            )
+        )
+    }
+
+    pub fn teid(&self) -> u32 {
+        self.teid
+    }
+    pub fn set_teid(&mut self, teid: u32) {
+        self.teid = teid;
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::TeidControlPlane
+    }
+
+    fn length(&self) -> u16 {
+        5
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+
+        pos = pos + 1;
+
+        NetworkEndian::write_u32(&mut buffer[TEID],self.teid);
+
+        pos = pos + 4;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("TEID Control Plane", format!("{:#010x}", self.teid));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(0x12345678);
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(0x12345678);
+
+        assert_eq!(ie.length(), 5)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(0x12345678);
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::TeidControlPlane as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::TeidControlPlane as u8, 0x87, 0x65, 0x43, 0x21]) {
+            assert_eq!(ie.teid(), 0x87654321);
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
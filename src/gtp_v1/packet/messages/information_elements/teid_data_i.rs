@@ -2,8 +2,14 @@ use byteorder::{ByteOrder, NetworkEndian};
 
 use crate::field::*;
 
-use super::{InformationElementTraits, InformationElementType};
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
     /*
                                         Bits
@@ -29,8 +35,19 @@ impl InformationElement {
         }
     }
 
-    pub fn parse(_buffer: &[u8]) -> Option<(Self, usize)> {
-        None
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    teid: NetworkEndian::read_u32(&buffer[TEID])
+                },
+                5
+            )
+        )
     }
 
     pub fn teid(&self) -> u32 {
@@ -66,6 +83,14 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("TEID Data I", format!("{:#010x}", self.teid));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +144,14 @@ mod tests {
 
     #[test]
     fn test_message_parse() {
-        assert_eq!(1, 1)
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::TeidDataI as u8, 0x12, 0x34, 0x56, 0x78]) {
+            assert_eq!(ie.teid(), 0x12345678);
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::TeidDataI as u8, 0x12, 0x34]).is_none());
     }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (18)                                                  |
+        2       | Spare                         | NSAPI                         |
+        3       | TEID Octet 1                                                  |
+        4       | TEID Octet 2                                                  |
+        5       | TEID Octet 3                                                  |
+        6       | TEID Octet 4                                                  |
+                |---------------------------------------------------------------|
+    */
+    nsapi: u8,
+    teid: u32
+}
+
+pub const TEID: Field = 2..6;
+
+impl InformationElement {
+    pub fn new(nsapi: u8, teid: u32) -> Result<Self, String> {
+        // NSAPI can only be 4 bit i.e. max of 0xF(15)
+
+        if nsapi > 0xF {
+            return Err(format!("NSAPI is out of range. NSAPI = {}", nsapi));
+        }
+
+        Ok(InformationElement {
+            nsapi: nsapi,
+            teid: teid
+        })
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 6 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    nsapi: buffer[1] & 0xF,
+                    teid: NetworkEndian::read_u32(&buffer[TEID])
+                },
+                6
+            )
+        )
+    }
+
+    pub fn nsapi(&self) -> u8 {
+        self.nsapi
+    }
+
+    pub fn teid(&self) -> u32 {
+        self.teid
+    }
+    pub fn set_teid(&mut self, teid: u32) {
+        self.teid = teid;
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::TeidDataII
+    }
+
+    fn length(&self) -> u16 {
+        6
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+
+        pos = pos + 1;
+
+        buffer[pos] = self.nsapi & 0xF;
+
+        pos = pos + 1;
+
+        NetworkEndian::write_u32(&mut buffer[TEID],self.teid);
+
+        pos = pos + 4;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("NSAPI", self.nsapi);
+            d.field("TEID Data II", format!("{:#010x}", self.teid));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(0xF, 0x12345678).is_ok());
+        assert!(InformationElement::new(0x10, 0x12345678).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(5, 0x12345678).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TeidDataII as u8, 5, 0x12, 0x34, 0x56, 0x78]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(5, 0x12345678).unwrap();
+
+        assert_eq!(ie.length(), 6)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(5, 0x12345678).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::TeidDataII as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::TeidDataII as u8, 0xF6, 0x87, 0x65, 0x43, 0x21]) {
+            assert_eq!(ie.nsapi(), 6);
+            assert_eq!(ie.teid(), 0x87654321);
+            assert_eq!(pos, 6);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::*;
+
+use super::{InformationElementTraits, InformationElementType, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const LENGTH: Field = 1..3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+    /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (139)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4->n    | UTRAN Transparent Field                                       |
+                |---------------------------------------------------------------|
+    */
+    pub utran_transparent_field: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(utran_transparent_field: &[u8]) -> Result<Self, String> {
+        if utran_transparent_field.len() > (u16::MAX - 3) as usize {
+            return Err(format!("UTRAN Transparent Field is too long {}", utran_transparent_field.len()));
+        }
+
+        Ok(
+            InformationElement {
+                utran_transparent_field: utran_transparent_field.to_vec(),
+            }
+        )
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize;
+        pos = pos + 2;
+
+        if buffer.len() < pos + length {
+            return None
+        }
+
+        let utran_transparent_field = buffer[pos..pos+length].to_vec();
+        pos = pos + length;
+
+        Some(
+            (
+                InformationElement {
+                    utran_transparent_field,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::UtranTransparentContainer
+    }
+
+    fn length(&self) -> u16 {
+        3 + self.utran_transparent_field.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        // We subtract 3 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-3);
+        pos = pos + 2;
+
+        buffer[pos..pos+self.utran_transparent_field.len()].copy_from_slice(&self.utran_transparent_field);
+        pos = pos + self.utran_transparent_field.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.hex("UTRAN Transparent Field", &self.utran_transparent_field);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(&[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::UtranTransparentContainer as u8, 0, 4, 0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(&[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+
+        assert_eq!(ie.length(), 7)
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(&[0xDE, 0xAD, 0xBE, 0xEF]).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::UtranTransparentContainer as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&[InformationElementType::UtranTransparentContainer as u8, 0, 4, 0xDE, 0xAD, 0xBE, 0xEF]) {
+            assert_eq!(ie.utran_transparent_field, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&[InformationElementType::UtranTransparentContainer as u8, 0, 5, 0xDE, 0xAD, 0xBE, 0xEF]).is_none());
+    }
+}
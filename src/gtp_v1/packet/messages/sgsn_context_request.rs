@@ -0,0 +1,218 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::InformationElement;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Conditional            |   7.7.2
+        Routeing Area Identity (RAI)                |    Mandatory              |   7.7.3
+        Temporary Logical Link Identifier (TLLI)    |    Conditional            |   7.7.4
+        Packet TMSI (P-TMSI)                        |    Conditional            |   7.7.5
+        P-TMSI Signature                            |    Conditional            |   7.7.9
+        MS Validated                                |    Optional               |   7.7.10
+        Tunnel Endpoint Identifier Control Plane    |    Mandatory              |   7.7.14
+        SGSN Address for Control Plane              |    Mandatory              |   7.7.32
+        Alternative SGSN Address for Control Plane  |    Optional               |   7.7.32
+        SGSN Number                                 |    Optional               |   7.7.47
+        RAT Type                                    |    Optional               |   7.7.50
+        Hop Counter                                 |    Optional               |   7.7.63
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new() -> Self {
+
+        Message {
+            information_elements: Vec::new()
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::SGSNContextRequest
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Rai(information_elements::rai::InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Rai as u8, 0x05, 0xF5, 0x10, 0x12, 0x34, 0x56,
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Rai(information_elements::rai::InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x1234, 0x56))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        assert_eq!(m.length(), 19);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::SGSNContextRequest as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::Rai as u8, 0x05, 0xF5, 0x10, 0x12, 0x34, 0x56,
+            12, 0x12, 0x34, 0x56, // P-TMSI Signature (Not supported)
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]) {
+            assert_eq!(pos, 23);
+            assert_eq!(m.information_elements.len(), 3);
+
+            if let InformationElement::Rai(ie) = &m.information_elements[0] {
+                assert_eq!(ie.lac, 0x1234);
+                assert_eq!(ie.rac, 0x56);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::TeidControlPlane(ie) = &m.information_elements[1] {
+                assert_eq!(ie.teid(), 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::GsnAddress(ie) = &m.information_elements[2] {
+                assert_eq!(ie.gsn_address(), IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // Unknown TV format IE
+        assert!(Message::parse(&[100, 0]).is_none());
+    }
+}
@@ -0,0 +1,234 @@
+use super::{
+    Ies,
+    MessageTraits,
+    MessageType,
+    dissect_message,
+    ies_of,
+};
+
+use super::information_elements::InformationElement;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   7.7.1
+        IMSI                                        |    Conditional            |   7.7.2
+        Tunnel Endpoint Identifier Control Plane    |    Conditional            |   7.7.14
+        RAB Context                                 |    Conditional            |   7.7.19
+        Radio Priority SMS                          |    Optional               |   7.7.20
+        Radio Priority                              |    Optional               |   7.7.21
+        Packet Flow Id                              |    Optional               |   7.7.22
+        Charging Characteristics                    |    Optional               |   7.7.23
+        Radio Priority LCS                          |    Optional               |   7.7.25B
+        MM Context                                  |    Conditional            |   7.7.28
+        PDP Context                                 |    Conditional            |   7.7.29
+        SGSN Address for Control Plane              |    Conditional            |   7.7.32
+        PDP Context Prioritization                  |    Optional               |   7.7.45
+        MBMS UE Context                             |    Optional               |   7.7.55
+        Subscribed RFSP Index                       |    Optional               |   7.7.88
+        RFSP Index in use                           |    Optional               |   7.7.88
+        Co-located GGSN-PGW FQDN                    |    Optional               |   7.7.90
+        Evolved Allocation/Retention Priority II    |    Optional               |   7.7.92
+        Extended Common Flags                       |    Optional               |   7.7.93
+        UE Network Capability                       |    Optional               |   7.7.99
+        UE-AMBR                                     |    Optional               |   7.7.100
+        APN-AMBR with NSAPI                         |    Optional               |   7.7.101
+        Signalling Priority Indication with NSAPI   |    Optional               |   7.7.104
+        Higher bitrates than 16 Mbps flag           |    Optional               |   7.7.105
+        Selection Mode with NSAPI                   |    Optional               |   7.7.113
+        Local Home Network ID with NSAPI            |    Optional               |   7.7.115
+        UE Usage Type                               |    Optional               |   7.7.117
+        Extended Common Flags II                    |    Optional               |   7.7.118
+        UE SCEF PDN Connection                      |    Optional               |   7.7.121
+        IOV_updates counter                         |    Optional               |   7.7.122
+        Alternative GGSN Address for control Plane  |    Optional               |   7.7.32
+        Alternative GGSN Address for user traffic   |    Optional               |   7.7.32
+        Private Extension                           |    Optional               |   7.7.46
+        --------------------------------------------|---------------------------|-------------------------
+    */
+    pub information_elements: Vec<InformationElement>
+}
+
+impl Message {
+    pub fn new() -> Self {
+
+        Message {
+            information_elements: Vec::new()
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let mut information_elements = Vec::new();
+
+        while pos < buffer.len() {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]) {
+                information_elements.push(ie);
+                pos = pos + ie_pos;
+            }
+            else if let Some(ie_pos) = InformationElement::skip_parsing(&buffer[pos..]) {
+                // Skip over IEs we don't support
+                pos = pos + ie_pos;
+            }
+            else {
+                // We can't determine the length of this IE so we can't continue parsing
+                return None
+            }
+        }
+
+        Some(
+            (
+                Message {
+                    information_elements
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl MessageTraits for Message {
+    fn push_ie(&mut self, ie: InformationElement)
+    {
+        // TODO: Check here that the ie we are adding is allowed for this message
+        self.information_elements.push(ie);
+    }
+
+    fn pop_ie(&mut self) -> Option<InformationElement>
+    {
+        self.information_elements.pop()
+    }
+
+    fn message_type(&self) -> MessageType {
+        MessageType::SGSNContextResponse
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        // NOTE: The list should be sorted by IE Type. We assume here they have been added in the correct order
+        ies_of(&self.information_elements)
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.information_elements {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use crate::gtp_v1::packet::messages::{
+        MessageTraits
+    };
+
+    use crate::gtp_v1::packet::messages::information_elements;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Cause(information_elements::cause::InformationElement::new(information_elements::cause::CauseCode::RequestAccepted))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8, 128,
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let mut m = Message::new();
+
+        m.information_elements.push(
+            InformationElement::Cause(information_elements::cause::InformationElement::new(information_elements::cause::CauseCode::RequestAccepted))
+        );
+
+        m.information_elements.push(
+            InformationElement::TeidControlPlane(information_elements::teid_control_plane::InformationElement::new(0x12345678))
+        );
+
+        m.information_elements.push(
+            InformationElement::GsnAddress(information_elements::gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,0,1))))
+        );
+
+        assert_eq!(m.length(), 14);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = Message::new();
+        assert_eq!(m.message_type() as u8, MessageType::SGSNContextResponse as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((m, pos)) = Message::parse(&[
+            InformationElementType::Cause as u8, 128,
+            InformationElementType::TeidControlPlane as u8, 0x12, 0x34, 0x56, 0x78,
+            InformationElementType::GsnAddress as u8, 0, 4, 192, 168, 0, 1,
+        ]) {
+            assert_eq!(pos, 14);
+            assert_eq!(m.information_elements.len(), 3);
+
+            if let InformationElement::Cause(ie) = &m.information_elements[0] {
+                assert_eq!(ie.cause(), 128);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::TeidControlPlane(ie) = &m.information_elements[1] {
+                assert_eq!(ie.teid(), 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let InformationElement::GsnAddress(ie) = &m.information_elements[2] {
+                assert_eq!(ie.gsn_address(), IpAddr::V4(Ipv4Addr::new(192,168,0,1)));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated GSN Address
+        assert!(Message::parse(&[InformationElementType::Cause as u8, 128, InformationElementType::GsnAddress as u8, 0, 4, 192]).is_none());
+    }
+}
@@ -0,0 +1,339 @@
+/* Zero-copy views over GTPv1 packets.
+
+A view borrows the input buffer and only decodes a field when it is accessed. This is mostly useful for GTP-U
+where only the TEID and the T-PDU are needed from each packet. Use to_owned() on a view to fall back to the owned
+API. */
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::Packet;
+use super::header::{LENGTH, TEID};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+pub struct MessageView<'a> {
+    buffer: &'a [u8]
+}
+
+impl<'a> MessageView<'a> {
+    pub fn new_checked(buffer: &'a [u8]) -> Result<Self, String> {
+        // Checks that the header, the extension headers and the length of the message are consistent with the buffer
+
+        if buffer.len() < 8 {
+            return Err(format!("Buffer is too short for a GTPv1 header ({})", buffer.len()));
+        }
+
+        let view = MessageView { buffer };
+
+        if view.version() != 1 {
+            return Err(format!("Not a GTPv1 packet (version {})", view.version()));
+        }
+
+        if view.protocol_type() != 1 {
+            return Err("GTP' is not supported".to_string());
+        }
+
+        if buffer.len() < view.total_length() {
+            return Err(format!("Buffer is shorter than the message length ({} < {})", buffer.len(), view.total_length()));
+        }
+
+        if view.optional_fields_present() {
+            if view.total_length() < 12 {
+                return Err(format!("Message length is too short for the optional fields ({})", view.length()));
+            }
+
+            // Walk the extension headers to make sure they fit inside the message
+            let mut pos = 12;
+            let mut next_extension_header_type = buffer[11];
+
+            while next_extension_header_type != 0 {
+                if pos >= view.total_length() || buffer[pos] == 0 {
+                    return Err("Extension header is malformed".to_string());
+                }
+
+                pos = pos + buffer[pos] as usize * 4;
+
+                if pos > view.total_length() {
+                    return Err("Extension header runs past the end of the message".to_string());
+                }
+
+                next_extension_header_type = buffer[pos - 1];
+            }
+        }
+
+        Ok(view)
+    }
+
+    pub fn into_inner(self) -> &'a [u8] {
+        self.buffer
+    }
+
+    pub fn version(&self) -> u8 {
+        (self.buffer[0] >> 5) & 0b111
+    }
+
+    pub fn protocol_type(&self) -> u8 {
+        (self.buffer[0] >> 4) & 0b1
+    }
+
+    pub fn extension_header_present(&self) -> bool {
+        (self.buffer[0] >> 2) & 0b1 == 1
+    }
+
+    pub fn sequence_number_present(&self) -> bool {
+        (self.buffer[0] >> 1) & 0b1 == 1
+    }
+
+    pub fn n_pdu_number_present(&self) -> bool {
+        self.buffer[0] & 0b1 == 1
+    }
+
+    fn optional_fields_present(&self) -> bool {
+        // If any of E, S or PN are set then the Sequence Number, N-PDU Number and Next Extension Header Type fields are
+        // all present
+        self.buffer[0] & 0b111 != 0
+    }
+
+    pub fn message_type_raw(&self) -> u8 {
+        self.buffer[1]
+    }
+
+    pub fn length(&self) -> u16 {
+        // This is the value of the length field i.e. excluding the first 8 octets
+        NetworkEndian::read_u16(&self.buffer[LENGTH])
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.length() as usize + 8
+    }
+
+    pub fn teid(&self) -> u32 {
+        NetworkEndian::read_u32(&self.buffer[TEID])
+    }
+
+    pub fn sequence_number(&self) -> Option<u16> {
+        if self.sequence_number_present() {
+            Some(NetworkEndian::read_u16(&self.buffer[8..10]))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn n_pdu_number(&self) -> Option<u8> {
+        if self.n_pdu_number_present() {
+            Some(self.buffer[10])
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn extension_headers(&self) -> ExtensionHeaderIter<'a> {
+        if self.extension_header_present() {
+            ExtensionHeaderIter::new(&self.buffer[12..self.total_length()], self.buffer[11])
+        }
+        else {
+            ExtensionHeaderIter::new(&[], 0)
+        }
+    }
+
+    pub fn header_length(&self) -> usize {
+        if self.optional_fields_present() {
+            12 + self.extension_headers().map(|eh| eh.total_length()).sum::<usize>()
+        }
+        else {
+            8
+        }
+    }
+
+    pub fn payload(&self) -> &'a [u8] {
+        // For a G-PDU this is the T-PDU
+        &self.buffer[self.header_length()..self.total_length()]
+    }
+
+    pub fn to_owned(&self) -> Option<Packet> {
+        match Packet::parse(&self.buffer[..self.total_length()]) {
+            Some((p, _pos)) => Some(p),
+            None => None,
+        }
+    }
+}
+
+pub struct ExtensionHeaderView<'a> {
+    extension_header_type: u8,
+    buffer: &'a [u8]
+}
+
+impl<'a> ExtensionHeaderView<'a> {
+    pub fn extension_header_type_raw(&self) -> u8 {
+        self.extension_header_type
+    }
+
+    pub fn length(&self) -> u8 {
+        // This is the value of the length field i.e. in units of 4 octets
+        self.buffer[0]
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.length() as usize * 4
+    }
+
+    pub fn content(&self) -> &'a [u8] {
+        &self.buffer[1..self.total_length()-1]
+    }
+
+    pub fn next_extension_header_type_raw(&self) -> u8 {
+        self.buffer[self.total_length()-1]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.buffer[..self.total_length()]
+    }
+}
+
+pub struct ExtensionHeaderIter<'a> {
+    buffer: &'a [u8],
+    pos: usize,
+    next_extension_header_type: u8,
+}
+
+impl<'a> ExtensionHeaderIter<'a> {
+    pub fn new(buffer: &'a [u8], next_extension_header_type: u8) -> Self {
+        ExtensionHeaderIter {
+            buffer,
+            pos: 0,
+            next_extension_header_type,
+        }
+    }
+}
+
+impl<'a> Iterator for ExtensionHeaderIter<'a> {
+    type Item = ExtensionHeaderView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_extension_header_type == 0 || self.pos >= self.buffer.len() {
+            return None;
+        }
+
+        let length = self.buffer[self.pos] as usize * 4;
+
+        if length == 0 || self.pos + length > self.buffer.len() {
+            // The rest of the buffer is malformed. Stop iterating
+            self.pos = self.buffer.len();
+            return None;
+        }
+
+        let eh = ExtensionHeaderView {
+            extension_header_type: self.next_extension_header_type,
+            buffer: &self.buffer[self.pos..self.pos+length],
+        };
+
+        self.next_extension_header_type = eh.next_extension_header_type_raw();
+        self.pos = self.pos + length;
+
+        Some(eh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v1::packet::messages::MessageType;
+
+    const G_PDU: [u8; 12] = [
+        /* Flags */ 0b0011_0000,
+        /* Message Type */ MessageType::GPDU as u8,
+        /* Length */ 0x00, 0x04,
+        /* TEID */ 0x12, 0x34, 0x56, 0x78,
+        /* T-PDU */ 0xDE, 0xAD, 0xBE, 0xEF,
+    ];
+
+    const G_PDU_EXTENSION_HEADERS: [u8; 26] = [
+        /* Flags */ 0b0011_0110,
+        /* Message Type */ MessageType::GPDU as u8,
+        /* Length */ 0x00, 0x12,
+        /* TEID */ 0x12, 0x34, 0x56, 0x78,
+        /* Sequence Number */ 0xAB, 0xCD,
+        /* N-PDU Number */ 0x00,
+        /* Next Extension Header Type */ 0b1000_0101, // PDU Session Container
+        1, 0x00, 0x01, 0b0100_0000, // UDP Port
+        1, 0x12, 0x34, 0x00,
+        /* T-PDU */ 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE,
+    ];
+
+    #[test]
+    fn test_message_view() {
+        let v = MessageView::new_checked(&G_PDU).unwrap();
+
+        assert_eq!(v.version(), 1);
+        assert_eq!(v.protocol_type(), 1);
+        assert_eq!(v.message_type_raw(), MessageType::GPDU as u8);
+        assert_eq!(v.length(), 4);
+        assert_eq!(v.teid(), 0x12345678);
+        assert_eq!(v.sequence_number(), None);
+        assert_eq!(v.n_pdu_number(), None);
+        assert_eq!(v.header_length(), 8);
+        assert_eq!(v.payload(), &[0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(v.extension_headers().count(), 0);
+    }
+
+    #[test]
+    fn test_message_view_extension_headers() {
+        let v = MessageView::new_checked(&G_PDU_EXTENSION_HEADERS).unwrap();
+
+        assert_eq!(v.sequence_number(), Some(0xABCD));
+        assert_eq!(v.n_pdu_number(), None);
+        assert_eq!(v.header_length(), 20);
+        assert_eq!(v.payload(), &[0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE]);
+
+        let ehs: Vec<ExtensionHeaderView> = v.extension_headers().collect();
+
+        assert_eq!(ehs.len(), 2);
+        assert_eq!(ehs[0].extension_header_type_raw(), 0b1000_0101);
+        assert_eq!(ehs[0].content(), &[0x00, 0x01]);
+        assert_eq!(ehs[0].next_extension_header_type_raw(), 0b0100_0000);
+        assert_eq!(ehs[1].extension_header_type_raw(), 0b0100_0000);
+        assert_eq!(ehs[1].content(), &[0x12, 0x34]);
+        assert_eq!(ehs[1].next_extension_header_type_raw(), 0);
+    }
+
+    #[test]
+    fn test_message_view_checked() {
+        assert!(MessageView::new_checked(&G_PDU[..7]).is_err());
+        assert!(MessageView::new_checked(&G_PDU[..11]).is_err());
+
+        let mut buffer = G_PDU;
+        buffer[0] = 0b0101_0000;
+        assert!(MessageView::new_checked(&buffer).is_err());
+
+        // GTP'
+        let mut buffer = G_PDU;
+        buffer[0] = 0b0010_0000;
+        assert!(MessageView::new_checked(&buffer).is_err());
+
+        // Extension header runs past the end of the message
+        let mut buffer = G_PDU_EXTENSION_HEADERS;
+        buffer[16] = 4;
+        assert!(MessageView::new_checked(&buffer).is_err());
+
+        // Zero length extension header
+        let mut buffer = G_PDU_EXTENSION_HEADERS;
+        buffer[12] = 0;
+        assert!(MessageView::new_checked(&buffer).is_err());
+    }
+
+    #[test]
+    fn test_to_owned() {
+        let v = MessageView::new_checked(&G_PDU).unwrap();
+
+        if let Some(p) = v.to_owned() {
+            assert_eq!(p.header.teid(), 0x12345678);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,356 @@
+// This monitors the GTP-U paths to peers by periodically sending Echo Requests (TS 29.281 7.2.1)
+// A path is considered down once max_failures Echo Requests in a row go unanswered
+// Echo Requests and Responses sent and received, and the round trip times, are reported to the MetricsSink if one is set
+
+use std::collections::HashMap;
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, UdpSocket};
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use std::time::{Duration, Instant};
+
+use super::packet::Packet as GtpPacket;
+use super::packet::messages::{Message, echo_request};
+use super::packet::messages::information_elements::recovery;
+
+use crate::MTU;
+use crate::socket;
+use crate::metrics::MetricsSink;
+
+// TS 29.281 doesn't allow Echo Requests to be sent more often than every 60 seconds
+pub const DEFAULT_ECHO_INTERVAL: Duration = Duration::from_secs(60);
+pub const DEFAULT_MAX_FAILURES: u32 = 3;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathEvent {
+    Down(SocketAddr),
+    Up(SocketAddr),
+}
+
+struct Path {
+    // The sequence number and send time of the last Echo Request if it hasn't been answered yet
+    outstanding: Option<(u16, Instant)>,
+    failures: u32,
+    up: bool,
+}
+
+pub struct PathManager
+{
+    socket: UdpSocket,
+    echo_interval: Duration,
+    max_failures: u32,
+    sequence_number: u16,
+    paths: HashMap<SocketAddr, Path>,
+    events: Sender<PathEvent>,
+    metrics: Option<Box<dyn MetricsSink + Send>>,
+}
+
+impl PathManager {
+    pub fn new(socket: UdpSocket, echo_interval: Duration, max_failures: u32) -> (Self, Receiver<PathEvent>) {
+        let (events, receiver) = channel();
+
+        (
+            PathManager {
+                socket,
+                echo_interval,
+                max_failures,
+                sequence_number: 0,
+                paths: HashMap::new(),
+                events,
+                metrics: None,
+            },
+            receiver
+        )
+    }
+
+    pub fn set_metrics_sink<S: MetricsSink + Send + 'static>(&mut self, sink: S) {
+        self.metrics = Some(Box::new(sink));
+    }
+
+    pub fn add_peer(&mut self, peer: SocketAddr) {
+        // Paths start as up until proven otherwise
+        self.paths.entry(peer).or_insert(
+            Path {
+                outstanding: None,
+                failures: 0,
+                up: true,
+            }
+        );
+    }
+
+    pub fn remove_peer(&mut self, peer: SocketAddr) -> bool {
+        self.paths.remove(&peer).is_some()
+    }
+
+    pub fn is_up(&self, peer: SocketAddr) -> Option<bool> {
+        self.paths.get(&peer).map(|p| p.up)
+    }
+
+    pub fn failures(&self, peer: SocketAddr) -> Option<u32> {
+        self.paths.get(&peer).map(|p| p.failures)
+    }
+
+    pub fn send_echo_requests(&mut self) -> usize {
+        // Any Echo Request still outstanding from the last round has failed
+        let mut sent = 0;
+
+        for (peer, path) in self.paths.iter_mut() {
+            if path.outstanding.is_some() {
+                path.failures = path.failures + 1;
+
+                if path.up && path.failures >= self.max_failures {
+                    path.up = false;
+                    let _ = self.events.send(PathEvent::Down(*peer));
+                }
+            }
+
+            self.sequence_number = self.sequence_number.wrapping_add(1);
+
+            let mut echo_request = GtpPacket::new(Message::EchoRequest(echo_request::Message::new()));
+            echo_request.header.enable_sequence_number();
+            echo_request.header.set_sequence_number(self.sequence_number);
+
+            // A request that couldn't be sent is treated the same as one that wasn't answered
+            path.outstanding = Some((self.sequence_number, Instant::now()));
+
+            if echo_request.send_to(&self.socket, peer).is_ok() {
+                sent = sent + 1;
+
+                if let Some(metrics) = self.metrics.as_mut() {
+                    metrics.message_sent(*peer, echo_request.header.message_type() as u8);
+                }
+            }
+        }
+
+        sent
+    }
+
+    pub fn handle_packet(&mut self, packet: GtpPacket, src: SocketAddr) -> bool {
+        // Returns true if the packet was an Echo Request or Echo Response that was handled
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.message_received(src, packet.header.message_type() as u8);
+        }
+
+        match packet.message {
+            Message::EchoRequest(_) => {
+                // Answer the peer's keepalive with the same sequence number. The Restart Counter is always 0 for GTP-U.
+                if let Some(mut echo_response) = packet.echo_response(recovery::InformationElement::new(0)) {
+                    if echo_response.send_to(&self.socket, src).is_ok() {
+                        if let Some(metrics) = self.metrics.as_mut() {
+                            metrics.message_sent(src, echo_response.header.message_type() as u8);
+                        }
+                        return true
+                    }
+                }
+
+                false
+            },
+            Message::EchoResponse(_) => {
+                let sequence_number = packet.header.sequence_number();
+
+                if let Some(path) = self.paths.get_mut(&src) {
+                    if let Some((_sequence_number, sent)) = path.outstanding.filter(|(s, _sent)| *s == sequence_number) {
+                        path.outstanding = None;
+                        path.failures = 0;
+
+                        if let Some(metrics) = self.metrics.as_mut() {
+                            metrics.echo_rtt(src, sent.elapsed());
+                        }
+
+                        if !path.up {
+                            path.up = true;
+                            let _ = self.events.send(PathEvent::Up(src));
+                        }
+
+                        return true
+                    }
+                }
+
+                false
+            },
+            _ => false,
+        }
+    }
+
+    pub fn poll(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        // Waits up to timeout for a single packet and handles it
+        let mut buffer = [0; MTU];
+
+        socket::set_poll_timeout(&self.socket, timeout)?;
+
+        match self.socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, src_addr)) => {
+                if let Some((packet, _pos)) = GtpPacket::parse(&buffer[..number_of_bytes]) {
+                    Ok(self.handle_packet(packet, src_addr))
+                }
+                else {
+                    if let Some(metrics) = self.metrics.as_mut() {
+                        metrics.parse_failure(src_addr);
+                    }
+                    Ok(false)
+                }
+            },
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn run(&mut self) -> std::io::Result<()> {
+        // Sends Echo Requests every echo_interval and handles the responses until the socket fails
+        let mut next_echo = Instant::now();
+
+        loop {
+            let now = Instant::now();
+
+            if now >= next_echo {
+                self.send_echo_requests();
+                next_echo = now + self.echo_interval;
+            }
+
+            self.poll(next_echo.saturating_duration_since(now))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddrV4};
+    use std::sync::{Arc, Mutex};
+
+    use crate::metrics::Metrics;
+    use super::super::packet::messages::{MessageType, echo_response};
+
+    fn new_manager(max_failures: u32) -> (PathManager, Receiver<PathEvent>) {
+        let socket = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+
+        PathManager::new(socket, DEFAULT_ECHO_INTERVAL, max_failures)
+    }
+
+    fn echo_response(sequence_number: u16) -> GtpPacket {
+        let mut p = GtpPacket::new(Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0))));
+        p.header.enable_sequence_number();
+        p.header.set_sequence_number(sequence_number);
+        p
+    }
+
+    #[test]
+    fn test_send_echo_requests() {
+        let (mut m, _events) = new_manager(DEFAULT_MAX_FAILURES);
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        m.add_peer(peer.local_addr().unwrap());
+
+        assert_eq!(m.send_echo_requests(), 1);
+
+        let mut buffer = [0; MTU];
+        let (n, _src) = peer.recv_from(&mut buffer).unwrap();
+
+        if let Some((p, _pos)) = GtpPacket::parse(&buffer[..n]) {
+            if let Message::EchoRequest(_) = p.message {
+                assert_eq!(p.header.sequence_number(), 1);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_path_failure() {
+        let (mut m, events) = new_manager(2);
+
+        // Nothing is listening here so the Echo Requests won't be answered
+        let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127,0,0,1), 9));
+
+        m.add_peer(peer);
+
+        m.send_echo_requests();
+        assert_eq!(m.failures(peer), Some(0));
+        assert_eq!(m.is_up(peer), Some(true));
+
+        m.send_echo_requests();
+        assert_eq!(m.failures(peer), Some(1));
+        assert_eq!(m.is_up(peer), Some(true));
+        assert!(events.try_recv().is_err());
+
+        m.send_echo_requests();
+        assert_eq!(m.failures(peer), Some(2));
+        assert_eq!(m.is_up(peer), Some(false));
+        assert_eq!(events.try_recv(), Ok(PathEvent::Down(peer)));
+
+        // Only notify once per failure
+        m.send_echo_requests();
+        assert!(events.try_recv().is_err());
+
+        // A response to an old Echo Request doesn't restore the path
+        assert!(!m.handle_packet(echo_response(3), peer));
+        assert_eq!(m.is_up(peer), Some(false));
+
+        // A response to the outstanding Echo Request does
+        assert!(m.handle_packet(echo_response(4), peer));
+        assert_eq!(m.is_up(peer), Some(true));
+        assert_eq!(m.failures(peer), Some(0));
+        assert_eq!(events.try_recv(), Ok(PathEvent::Up(peer)));
+    }
+
+    #[test]
+    fn test_unknown_peer() {
+        let (mut m, _events) = new_manager(DEFAULT_MAX_FAILURES);
+
+        let peer = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(127,0,0,1), 9));
+
+        assert!(!m.handle_packet(echo_response(1), peer));
+        assert_eq!(m.is_up(peer), None);
+
+        m.add_peer(peer);
+        assert!(m.remove_peer(peer));
+        assert!(!m.remove_peer(peer));
+    }
+
+    #[test]
+    fn test_poll() {
+        let (mut m, _events) = new_manager(DEFAULT_MAX_FAILURES);
+
+        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        m.set_metrics_sink(metrics.clone());
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        let peer_addr = peer.local_addr().unwrap();
+
+        m.add_peer(peer_addr);
+        m.send_echo_requests();
+
+        let mut buffer = [0; MTU];
+        let (n, src) = peer.recv_from(&mut buffer).unwrap();
+
+        let (p, _pos) = GtpPacket::parse(&buffer[..n]).unwrap();
+
+        // Answer the Echo Request
+        echo_response(p.header.sequence_number()).send_to(&peer, src).unwrap();
+
+        assert_eq!(m.poll(Duration::from_secs(1)).unwrap(), true);
+        assert_eq!(m.failures(peer_addr), Some(0));
+
+        let p = metrics.lock().unwrap().peer(peer_addr).cloned().unwrap();
+
+        assert_eq!(p.tx.get(&(MessageType::EchoRequest as u8)), Some(&1));
+        assert_eq!(p.rx.get(&(MessageType::EchoResponse as u8)), Some(&1));
+        assert!(p.echo_rtt.is_some());
+
+        m.send_echo_requests();
+        assert_eq!(m.failures(peer_addr), Some(0));
+
+        // Nothing to receive
+        assert_eq!(m.poll(Duration::from_millis(10)).unwrap(), false);
+    }
+}
@@ -0,0 +1,209 @@
+/* TEID remapping for GTP-U relays (e.g. a forwarder between an eNodeB and a SGW).
+
+Each tunnel that is relayed has an entry in the mapping table keyed by the TEID the packets arrive with. The entry
+holds the TEID of the tunnel on the other side and the address of the peer to forward the packets to.
+
+Packets are rewritten in place in the receive buffer. Only the TEID field of the header is changed so the sequence
+number, N-PDU number, extension headers and T-PDU are forwarded unchanged. Only G-PDUs and End Markers are relayed as
+the other GTP-U messages (Echo, Error Indication, Supported Extension Headers Notification) are path management
+messages between the relay and each of its peers. */
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, UdpSocket};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::packet::header::TEID;
+use super::packet::messages::MessageType;
+use super::packet::view::MessageView;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    pub teid: u32, // TEID of the tunnel at the peer
+    pub peer: SocketAddr,
+}
+
+impl Mapping {
+    pub fn new(teid: u32, peer: SocketAddr) -> Self {
+        Mapping {
+            teid,
+            peer,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Relay {
+    mappings: HashMap<u32, Mapping>,
+}
+
+impl Relay {
+    pub fn new() -> Self {
+        Relay {
+            mappings: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, teid: u32, mapping: Mapping) -> Option<Mapping> {
+        // Returns the mapping that was replaced if there was already one for teid
+        self.mappings.insert(teid, mapping)
+    }
+
+    pub fn insert_bidirectional(&mut self, a: (u32, SocketAddr), b: (u32, SocketAddr)) {
+        /* Relays a tunnel in both directions. a and b are the TEIDs allocated by the relay for each side of the tunnel
+        along with the peer on that side. e.g. a G-PDU received with the TEID from a is forwarded to the peer from b. */
+        let (a_teid, a_peer) = a;
+        let (b_teid, b_peer) = b;
+
+        self.mappings.insert(a_teid, Mapping::new(b_teid, b_peer));
+        self.mappings.insert(b_teid, Mapping::new(a_teid, a_peer));
+    }
+
+    pub fn remove(&mut self, teid: u32) -> Option<Mapping> {
+        self.mappings.remove(&teid)
+    }
+
+    pub fn get(&self, teid: u32) -> Option<&Mapping> {
+        self.mappings.get(&teid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    pub fn rewrite(&self, buffer: &mut [u8]) -> Result<(SocketAddr, usize), String> {
+        /* Rewrites the TEID of the GTP-U packet at the start of buffer. Returns the peer to forward the packet to and
+        the length of the packet (anything in buffer after the packet is ignored). */
+        let (message_type, teid, length) = {
+            let view = MessageView::new_checked(buffer)?;
+            (view.message_type_raw(), view.teid(), view.total_length())
+        };
+
+        if message_type != MessageType::GPDU as u8 && message_type != MessageType::EndMarker as u8 {
+            return Err(format!("Message type {} is not relayed", message_type));
+        }
+
+        match self.mappings.get(&teid) {
+            Some(mapping) => {
+                NetworkEndian::write_u32(&mut buffer[TEID], mapping.teid);
+                Ok((mapping.peer, length))
+            },
+            None => Err(format!("No mapping for TEID {:#010x}", teid))
+        }
+    }
+
+    pub fn forward(&self, socket: &UdpSocket, buffer: &mut [u8]) -> Result<usize, String> {
+        // Rewrites the packet in buffer and sends it to the peer of its mapping
+        let (peer, length) = self.rewrite(buffer)?;
+
+        socket.send_to(&buffer[..length], peer).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn enodeb() -> SocketAddr {
+        "10.0.0.1:2152".parse().unwrap()
+    }
+
+    fn sgw() -> SocketAddr {
+        "10.0.0.2:2152".parse().unwrap()
+    }
+
+    const G_PDU_EXTENSION_HEADERS: [u8; 26] = [
+        /* Flags */ 0b0011_0110,
+        /* Message Type */ MessageType::GPDU as u8,
+        /* Length */ 0x00, 0x12,
+        /* TEID */ 0x12, 0x34, 0x56, 0x78,
+        /* Sequence Number */ 0xAB, 0xCD,
+        /* N-PDU Number */ 0x00,
+        /* Next Extension Header Type */ 0b1000_0101, // PDU Session Container
+        1, 0x00, 0x01, 0b0100_0000, // UDP Port
+        1, 0x12, 0x34, 0x00,
+        /* T-PDU */ 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE,
+    ];
+
+    #[test]
+    fn test_rewrite() {
+        let mut relay = Relay::new();
+
+        relay.insert(0x12345678, Mapping::new(0x87654321, sgw()));
+
+        let mut buffer = [0; 32];
+        buffer[..G_PDU_EXTENSION_HEADERS.len()].copy_from_slice(&G_PDU_EXTENSION_HEADERS);
+
+        assert_eq!(relay.rewrite(&mut buffer), Ok((sgw(), G_PDU_EXTENSION_HEADERS.len())));
+
+        // Only the TEID has changed
+        assert_eq!(buffer[4..8], [0x87, 0x65, 0x43, 0x21]);
+        assert_eq!(buffer[..4], G_PDU_EXTENSION_HEADERS[..4]);
+        assert_eq!(buffer[8..26], G_PDU_EXTENSION_HEADERS[8..]);
+
+        // There is no mapping for the new TEID
+        assert!(relay.rewrite(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_invalid() {
+        let mut relay = Relay::new();
+
+        relay.insert(0x12345678, Mapping::new(0x87654321, sgw()));
+
+        let mut buffer = G_PDU_EXTENSION_HEADERS;
+        assert!(relay.rewrite(&mut buffer[..10]).is_err());
+
+        // Echo Requests are answered by the relay rather than forwarded
+        let mut buffer = G_PDU_EXTENSION_HEADERS;
+        buffer[1] = MessageType::EchoRequest as u8;
+        assert!(relay.rewrite(&mut buffer).is_err());
+        assert_eq!(buffer[4..8], G_PDU_EXTENSION_HEADERS[4..8]);
+
+        let mut buffer = G_PDU_EXTENSION_HEADERS;
+        buffer[1] = MessageType::EndMarker as u8;
+        assert!(relay.rewrite(&mut buffer).is_ok());
+    }
+
+    #[test]
+    fn test_bidirectional() {
+        let mut relay = Relay::new();
+
+        relay.insert_bidirectional((0x1000, enodeb()), (0x2000, sgw()));
+
+        assert_eq!(relay.len(), 2);
+        assert_eq!(relay.get(0x1000), Some(&Mapping::new(0x2000, sgw())));
+        assert_eq!(relay.get(0x2000), Some(&Mapping::new(0x1000, enodeb())));
+
+        assert_eq!(relay.remove(0x1000), Some(Mapping::new(0x2000, sgw())));
+        assert!(relay.get(0x1000).is_none());
+    }
+
+    #[test]
+    fn test_forward() {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        let mut relay = Relay::new();
+        relay.insert(0x12345678, Mapping::new(0x87654321, peer.local_addr().unwrap()));
+
+        let mut buffer = [0; crate::MTU];
+        buffer[..G_PDU_EXTENSION_HEADERS.len()].copy_from_slice(&G_PDU_EXTENSION_HEADERS);
+
+        assert_eq!(relay.forward(&socket, &mut buffer), Ok(G_PDU_EXTENSION_HEADERS.len()));
+
+        let mut received = [0; crate::MTU];
+        let (n, src) = peer.recv_from(&mut received).unwrap();
+
+        assert_eq!(src, socket.local_addr().unwrap());
+        assert_eq!(n, G_PDU_EXTENSION_HEADERS.len());
+        assert_eq!(received[4..8], [0x87, 0x65, 0x43, 0x21]);
+    }
+}
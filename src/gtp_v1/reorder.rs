@@ -0,0 +1,313 @@
+/* Reordering of T-PDUs by their GTP-U Sequence Number (TS 29.281 5.1).
+
+A GTP-U sender may number the T-PDUs of a tunnel so the receiver can put them back in order e.g. when testing a path
+that has to deliver in sequence. ReorderBuffer holds T-PDUs that arrive early until the ones before them arrive and
+drops duplicates and T-PDUs that arrive after their place has been passed.
+
+The buffer only waits for T-PDUs within a window after the next expected Sequence Number. When a T-PDU arrives beyond
+the window, the gap at the start of the window is given up on (counted as lost) so the buffer never grows without
+bound. flush() gives up on every gap e.g. when nothing has arrived for a while.
+
+Sequence Numbers wrap from 0xFFFF to 0, so a Sequence Number is before the expected one if it is less than half the
+number space (0x8000) behind it. */
+
+use core::fmt;
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SequenceNumberPolicy {
+    Ignore, // T-PDUs are delivered as they arrive
+    Reorder(u16), // T-PDUs are reordered and deduplicated within a window of this many Sequence Numbers
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReorderStatistics {
+    pub delivered: usize,
+    pub out_of_order: usize, // Arrived before a T-PDU with a lower Sequence Number and had to be held
+    pub duplicates: usize, // Dropped because a T-PDU with the same Sequence Number is held
+    pub late: usize, // Dropped because its Sequence Number had already been passed
+    pub lost: usize, // Sequence Numbers that were given up on
+    pub unnumbered: usize, // Delivered as they arrived because the S flag wasn't set
+}
+
+impl ReorderStatistics {
+    pub fn dropped(&self) -> usize {
+        self.duplicates + self.late
+    }
+}
+
+impl fmt::Display for ReorderStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Reordering: Delivered: {} Out of order: {} Duplicates: {} Late: {} Lost: {} Unnumbered: {}",
+            self.delivered, self.out_of_order, self.duplicates, self.late, self.lost, self.unnumbered
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReorderBuffer<T> {
+    policy: SequenceNumberPolicy,
+    next: Option<u16>, // The Sequence Number of held[0]. None until the first numbered T-PDU arrives
+    held: VecDeque<Option<T>>, // held[i] is the T-PDU with Sequence Number next + i, if it has arrived
+    statistics: ReorderStatistics,
+}
+
+impl<T> ReorderBuffer<T> {
+    pub fn new(policy: SequenceNumberPolicy) -> Result<Self, String> {
+        if let SequenceNumberPolicy::Reorder(window) = policy {
+            if !(1..=0x7FFF).contains(&window) {
+                return Err(format!("Reorder window must be between 1 and 0x7FFF {}", window));
+            }
+        }
+
+        Ok(ReorderBuffer {
+            policy,
+            next: None,
+            held: VecDeque::new(),
+            statistics: ReorderStatistics::default(),
+        })
+    }
+
+    pub fn policy(&self) -> SequenceNumberPolicy {
+        self.policy
+    }
+
+    pub fn statistics(&self) -> ReorderStatistics {
+        self.statistics
+    }
+
+    pub fn next_sequence_number(&self) -> Option<u16> {
+        self.next
+    }
+
+    pub fn held(&self) -> usize {
+        self.held.iter().filter(|t| t.is_some()).count()
+    }
+
+    pub fn push(&mut self, sequence_number: Option<u16>, t_pdu: T) -> Vec<T> {
+        // Returns the T-PDUs that can be delivered now, in order
+        let window = match self.policy {
+            SequenceNumberPolicy::Ignore => {
+                self.statistics.delivered = self.statistics.delivered + 1;
+                return vec![t_pdu];
+            },
+            SequenceNumberPolicy::Reorder(window) => window as usize,
+        };
+
+        let sequence_number = match sequence_number {
+            Some(sequence_number) => sequence_number,
+            None => {
+                self.statistics.unnumbered = self.statistics.unnumbered + 1;
+                self.statistics.delivered = self.statistics.delivered + 1;
+                return vec![t_pdu];
+            }
+        };
+
+        // The first numbered T-PDU starts the sequence
+        let next = *self.next.get_or_insert(sequence_number);
+
+        let offset = sequence_number.wrapping_sub(next) as usize;
+
+        if offset >= 0x8000 {
+            self.statistics.late = self.statistics.late + 1;
+            return Vec::new();
+        }
+
+        let mut delivered = Vec::new();
+
+        if offset >= window {
+            // Give up on the start of the window so the T-PDU fits at the end of it
+            self.skip(offset - window + 1, &mut delivered);
+        }
+
+        let offset = sequence_number.wrapping_sub(self.next.unwrap()) as usize;
+
+        if self.held.len() <= offset {
+            self.held.resize_with(offset + 1, || None);
+        }
+
+        if self.held[offset].is_some() {
+            self.statistics.duplicates = self.statistics.duplicates + 1;
+        }
+        else {
+            if offset > 0 {
+                self.statistics.out_of_order = self.statistics.out_of_order + 1;
+            }
+            self.held[offset] = Some(t_pdu);
+        }
+
+        self.deliver(&mut delivered);
+
+        delivered
+    }
+
+    pub fn flush(&mut self) -> Vec<T> {
+        // Delivers every held T-PDU, giving up on the gaps between them
+        let mut delivered = Vec::new();
+
+        let n = self.held.len();
+        self.skip(n, &mut delivered);
+
+        delivered
+    }
+
+    fn skip(&mut self, n: usize, delivered: &mut Vec<T>) {
+        // Moves the window on by n Sequence Numbers, delivering what has arrived and counting the rest as lost
+        for _ in 0..n {
+            match self.held.pop_front() {
+                Some(Some(t_pdu)) => {
+                    self.statistics.delivered = self.statistics.delivered + 1;
+                    delivered.push(t_pdu);
+                },
+                _ => self.statistics.lost = self.statistics.lost + 1,
+            }
+            self.next = self.next.map(|next| next.wrapping_add(1));
+        }
+    }
+
+    fn deliver(&mut self, delivered: &mut Vec<T>) {
+        // Delivers the T-PDUs at the start of the window up to the first gap
+        while let Some(Some(_)) = self.held.front() {
+            if let Some(Some(t_pdu)) = self.held.pop_front() {
+                self.statistics.delivered = self.statistics.delivered + 1;
+                delivered.push(t_pdu);
+            }
+            self.next = self.next.map(|next| next.wrapping_add(1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reorder(window: u16) -> ReorderBuffer<u16> {
+        ReorderBuffer::new(SequenceNumberPolicy::Reorder(window)).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(ReorderBuffer::<u16>::new(SequenceNumberPolicy::Ignore).is_ok());
+        assert!(ReorderBuffer::<u16>::new(SequenceNumberPolicy::Reorder(1)).is_ok());
+        assert!(ReorderBuffer::<u16>::new(SequenceNumberPolicy::Reorder(0x7FFF)).is_ok());
+        assert!(ReorderBuffer::<u16>::new(SequenceNumberPolicy::Reorder(0)).is_err());
+        assert!(ReorderBuffer::<u16>::new(SequenceNumberPolicy::Reorder(0x8000)).is_err());
+    }
+
+    #[test]
+    fn test_ignore() {
+        let mut b = ReorderBuffer::new(SequenceNumberPolicy::Ignore).unwrap();
+
+        assert_eq!(b.push(Some(2), 2), [2]);
+        assert_eq!(b.push(Some(1), 1), [1]);
+        assert_eq!(b.push(Some(1), 1), [1]);
+        assert_eq!(b.statistics().delivered, 3);
+        assert_eq!(b.statistics().dropped(), 0);
+    }
+
+    #[test]
+    fn test_in_order() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(10), 10), [10]);
+        assert_eq!(b.push(Some(11), 11), [11]);
+        assert_eq!(b.next_sequence_number(), Some(12));
+        assert_eq!(b.statistics().out_of_order, 0);
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(0), 0), [0]);
+        assert_eq!(b.push(Some(3), 3), []);
+        assert_eq!(b.push(Some(2), 2), []);
+        assert_eq!(b.held(), 2);
+        assert_eq!(b.push(Some(1), 1), [1, 2, 3]);
+        assert_eq!(b.held(), 0);
+
+        assert_eq!(b.statistics(), ReorderStatistics {
+            delivered: 4,
+            out_of_order: 2,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_duplicates_and_late() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(0), 0), [0]);
+        assert_eq!(b.push(Some(2), 2), []);
+        assert_eq!(b.push(Some(2), 2), []);
+        assert_eq!(b.push(Some(0), 0), []);
+
+        assert_eq!(b.statistics().duplicates, 1);
+        assert_eq!(b.statistics().late, 1);
+        assert_eq!(b.statistics().dropped(), 2);
+    }
+
+    #[test]
+    fn test_window() {
+        let mut b = reorder(4);
+
+        assert_eq!(b.push(Some(0), 0), [0]);
+        assert_eq!(b.push(Some(2), 2), []);
+
+        // 6 is beyond the window (1 to 4) so 1 and 2 are given up on
+        assert_eq!(b.push(Some(6), 6), [2]);
+        assert_eq!(b.next_sequence_number(), Some(3));
+        assert_eq!(b.statistics().lost, 1);
+
+        // Far beyond the window everything held is delivered
+        assert_eq!(b.push(Some(100), 100), [6]);
+        assert_eq!(b.next_sequence_number(), Some(97));
+        assert_eq!(b.held(), 1);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(0xFFFE), 0xFFFE), [0xFFFE]);
+        assert_eq!(b.push(Some(0), 0), []);
+        assert_eq!(b.push(Some(0xFFFF), 0xFFFF), [0xFFFF, 0]);
+        assert_eq!(b.next_sequence_number(), Some(1));
+
+        // Half the number space behind is late
+        assert_eq!(b.push(Some(0x8001), 0x8001), []);
+        assert_eq!(b.statistics().late, 1);
+    }
+
+    #[test]
+    fn test_unnumbered() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(5), 5), [5]);
+        assert_eq!(b.push(Some(7), 7), []);
+        assert_eq!(b.push(None, 0), [0]);
+        assert_eq!(b.statistics().unnumbered, 1);
+    }
+
+    #[test]
+    fn test_flush() {
+        let mut b = reorder(8);
+
+        assert_eq!(b.push(Some(0), 0), [0]);
+        assert_eq!(b.push(Some(3), 3), []);
+        assert_eq!(b.push(Some(5), 5), []);
+
+        assert_eq!(b.flush(), [3, 5]);
+        assert_eq!(b.next_sequence_number(), Some(6));
+        assert_eq!(b.statistics().lost, 3);
+
+        assert_eq!(b.flush(), []);
+    }
+}
@@ -0,0 +1,543 @@
+/* Checks that a GTPv2 message conforms to TS 29.274 on a given interface.
+
+Which IEs a message must or must not carry (and which F-TEID Interface Types it uses) depends on the interface it is
+sent on. Parsing only enforces the IEs that are mandatory on every interface. check() reports the rest:
+
+    -------------------------------------------|-------------------------------------------------------------------
+    Message                                    | Checks
+    -------------------------------------------|-------------------------------------------------------------------
+    All                                        | The message is used on the interface (TS 29.274 Table 6.1-1)
+    Create Session Request                     | Sender F-TEID Interface Type. ULI, Serving Network, PGW S5/S8 F-TEID,
+                                               | Selection Mode, PDN Type, PAA and APN-AMBR are present where required.
+                                               | Node specific IEs (e.g. FQ-CSIDs, LDNs, UE/HeNB local addresses) are
+                                               | only sent by the nodes of the interface
+    Create Session Response                    | As for the request. The required IEs are only checked if accepted
+    Modify Bearer Request                      | Sender F-TEID Interface Type and node specific IEs
+    -------------------------------------------|-------------------------------------------------------------------
+
+check_plausibility() is an opt-in check that the identities in a message are consistent with each other e.g. that the
+IMSI is from the PLMN in the Serving Network and ULI (see plmn::validate). These aren't errors (a roaming UE's IMSI is
+from another PLMN) so they are reported as Warnings. It is useful when generating traffic that should look like it came
+from one network.
+*/
+
+use core::fmt;
+
+use super::plmn;
+use super::packet::messages::{
+    Message,
+    MessageTraits,
+    MessageType,
+    create_session_request,
+    create_session_response,
+    modify_bearer_request,
+};
+use super::packet::messages::information_elements::{
+    InformationElementTraits,
+    InformationElementType,
+    f_teid::{self, InterfaceType},
+    user_location_information::PLMN,
+};
+
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Interface {
+    S11, // MME - SGW
+    S4, // S4-SGSN - SGW
+    S5S8, // SGW - PGW
+    S2b, // ePDG - PGW
+}
+
+impl Interface {
+    pub fn is_s11_s4(&self) -> bool {
+        // The interfaces between an MME/SGSN and the SGW
+        match self {
+            Interface::S11 | Interface::S4 => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_used_by(&self, message_type: MessageType) -> bool {
+        match message_type {
+            MessageType::EchoRequest |
+            MessageType::EchoResponse |
+            MessageType::CreateSessionRequest |
+            MessageType::CreateSessionResponse |
+            MessageType::ModifyBearerRequest |
+            MessageType::ModifyBearerResponse |
+            MessageType::DeleteSessionRequest |
+            MessageType::DeleteSessionResponse |
+            MessageType::ModifyBearerCommand |
+            MessageType::ModifyBearerFailureIndication => true,
+            MessageType::BearerResourceCommand |
+            MessageType::BearerResourceFailureIndication |
+            MessageType::SuspendNotification |
+            MessageType::SuspendAcknowledge |
+            MessageType::ResumeNotification |
+            MessageType::ResumeAcknowledge => *self != Interface::S2b,
+            MessageType::DownlinkDataNotification |
+            MessageType::DownlinkDataNotificationAcknowledge |
+            MessageType::DownlinkDataNotificationFailureIndication |
+            MessageType::ReleaseAccessBearersRequest |
+            MessageType::ReleaseAccessBearersResponse => self.is_s11_s4(),
+            MessageType::ModifyAccessBearersRequest |
+            MessageType::ModifyAccessBearersResponse => *self == Interface::S11,
+            MessageType::RemoteUEReportNotification |
+            MessageType::RemoteUEReportAcknowledge => *self == Interface::S11 || *self == Interface::S5S8,
+            // e.g. Context Request (S3/S10/S16), Forward Relocation Request (S3/S10/S16), SRVCC (Sv)
+            _ => false,
+        }
+    }
+
+    fn request_control_plane(&self) -> InterfaceType {
+        // The Sender F-TEID for Control Plane of a request from the MME, SGSN, SGW or ePDG
+        match self {
+            Interface::S11 => InterfaceType::S11MmeGtpC,
+            Interface::S4 => InterfaceType::S4SgsnGtpC,
+            Interface::S5S8 => InterfaceType::S5S8SgwGtpC,
+            Interface::S2b => InterfaceType::S2bEPdgGtpC,
+        }
+    }
+
+    fn response_control_plane(&self) -> InterfaceType {
+        // The Sender F-TEID for Control Plane of a response from the SGW or PGW
+        match self {
+            Interface::S11 | Interface::S4 => InterfaceType::S11S4SgwGtpC,
+            Interface::S5S8 => InterfaceType::S5S8PgwGtpC,
+            Interface::S2b => InterfaceType::S2bPgwGtpC,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    MessageNotUsed(MessageType),
+    IEMissing(InformationElementType, u8), // (IE Type, Instance)
+    IENotExpected(InformationElementType, u8), // (IE Type, Instance)
+    WrongInterfaceType(u8, InterfaceType), // (Instance, Interface Type) of an F-TEID
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::MessageNotUsed(t) => write!(f, "{} is not used on this interface", t.name()),
+            Violation::IEMissing(t, i) => write!(f, "{:?} (instance {}) is missing", t, i),
+            Violation::IENotExpected(t, i) => write!(f, "{:?} (instance {}) is not sent on this interface", t, i),
+            Violation::WrongInterfaceType(i, t) => write!(f, "F-TEID (instance {}) has the wrong Interface Type {:?}", i, t),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    ImsiNotFromPlmn(InformationElementType, PLMN), // The IMSI isn't from the PLMN in the IE e.g. the UE is roaming
+    UliPlmnMismatch(PLMN, PLMN), // (Serving Network, ULI) The ULI has a location in another PLMN
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::ImsiNotFromPlmn(t, plmn) => write!(f, "IMSI is not from the PLMN {} of the {:?}", plmn, t),
+            Warning::UliPlmnMismatch(serving_network, uli) => write!(f, "ULI PLMN {} is not the Serving Network {}", uli, serving_network),
+        }
+    }
+}
+
+pub fn check(m: &Message, interface: Interface) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    if !interface.is_used_by(m.message_type()) {
+        violations.push(Violation::MessageNotUsed(m.message_type()));
+        return violations
+    }
+
+    match m {
+        Message::CreateSessionRequest(m) => check_create_session_request(m, interface, &mut violations),
+        Message::CreateSessionResponse(m) => check_create_session_response(m, interface, &mut violations),
+        Message::ModifyBearerRequest(m) => check_modify_bearer_request(m, interface, &mut violations),
+        _ => { /* Nothing depends on the interface */ }
+    }
+
+    violations
+}
+
+fn check_create_session_request(m: &create_session_request::Message, interface: Interface, violations: &mut Vec<Violation>) {
+    let s11_s4 = interface.is_s11_s4();
+    let s5s8 = interface == Interface::S5S8;
+    let s2b = interface == Interface::S2b;
+
+    interface_type(&m.sender_f_teid_for_control_plane, &[interface.request_control_plane()], violations);
+
+    if s11_s4 {
+        required(&m.uli, InformationElementType::UserLocationInformation, 0, violations);
+        required(&m.pgw_s5_s8_for_control_plane, InformationElementType::FTEID, 1, violations);
+
+        if let Some(ref ie) = m.pgw_s5_s8_for_control_plane {
+            interface_type(ie, &[InterfaceType::S5S8PgwGtpC, InterfaceType::S5S8PgwPmipV6], violations);
+        }
+    }
+    else {
+        not_expected(&m.pgw_s5_s8_for_control_plane, violations);
+    }
+
+    if !s2b {
+        required(&m.serving_network, InformationElementType::ServingNetwork, 0, violations);
+    }
+
+    required(&m.selection_mode, InformationElementType::SelectionMode, 0, violations);
+    required(&m.pdn_type, InformationElementType::PDNType, 0, violations);
+    required(&m.pdn_address_allocation, InformationElementType::PDNAddressAllocation, 0, violations);
+    required(&m.apn_ambr, InformationElementType::AMBR, 0, violations);
+
+    // IEs that only some of the nodes send
+    expected_if(&m.mme_fq_csid, s11_s4, violations);
+    expected_if(&m.mme_s4_sgsn_ldn, s11_s4, violations);
+    expected_if(&m.sgw_fq_csid, s5s8, violations);
+    expected_if(&m.sgw_ldn, s5s8, violations);
+    expected_if(&m.epdg_fq_csid, s2b, violations);
+    expected_if(&m.epdg_ldn, s2b, violations);
+    expected_if(&m.ue_local_ip_address, s2b, violations);
+    expected_if(&m.ue_udp_port, s2b, violations);
+    expected_if(&m.henb_local_ip_address, interface == Interface::S11, violations);
+    expected_if(&m.henb_udp_port, interface == Interface::S11, violations);
+    expected_if(&m.twan_fq_csid, false, violations);
+    expected_if(&m.twan_ldn, false, violations);
+}
+
+fn check_create_session_response(m: &create_session_response::Message, interface: Interface, violations: &mut Vec<Violation>) {
+    let s11_s4 = interface.is_s11_s4();
+
+    if m.cause.cause_code.is_acceptance() {
+        required(&m.sender_f_teid_for_control_plane, InformationElementType::FTEID, 0, violations);
+        required(&m.pdn_address_allocation, InformationElementType::PDNAddressAllocation, 0, violations);
+
+        if interface != Interface::S2b {
+            required(&m.apn_restriction, InformationElementType::APNRestriction, 0, violations);
+        }
+
+        if s11_s4 {
+            required(&m.pgw_s5_s8_for_control_plane, InformationElementType::FTEID, 1, violations);
+        }
+    }
+
+    if let Some(ref ie) = m.sender_f_teid_for_control_plane {
+        interface_type(ie, &[interface.response_control_plane()], violations);
+    }
+
+    if s11_s4 {
+        if let Some(ref ie) = m.pgw_s5_s8_for_control_plane {
+            interface_type(ie, &[InterfaceType::S5S8PgwGtpC, InterfaceType::S5S8PgwPmipV6], violations);
+        }
+    }
+    else {
+        not_expected(&m.pgw_s5_s8_for_control_plane, violations);
+    }
+
+    // The SGW sends its FQ-CSID and LDN towards the MME/SGSN and the PGW sends its own towards the SGW/ePDG
+    expected_if(&m.sgw_fq_csid, s11_s4, violations);
+    expected_if(&m.sgw_ldn, s11_s4, violations);
+    expected_if(&m.pgw_fq_csid, !s11_s4, violations);
+    expected_if(&m.pgw_ldn, !s11_s4, violations);
+}
+
+fn check_modify_bearer_request(m: &modify_bearer_request::Message, interface: Interface, violations: &mut Vec<Violation>) {
+    let s11_s4 = interface.is_s11_s4();
+
+    if let Some(ref ie) = m.sender_f_teid_for_control_plane {
+        interface_type(ie, &[interface.request_control_plane()], violations);
+    }
+
+    expected_if(&m.delay_downlink_packet_notification_request, s11_s4, violations);
+    expected_if(&m.mme_fq_csid, s11_s4, violations);
+    expected_if(&m.sgw_fq_csid, interface == Interface::S5S8, violations);
+    expected_if(&m.change_to_report_flags, interface != Interface::S2b, violations);
+}
+
+pub fn check_plausibility(m: &Message) -> Vec<Warning> {
+    // The PLMNs of the identities in the message. See plmn::validate
+    match m {
+        Message::CreateSessionRequest(m) => plmn::validate(
+            m.serving_network.as_ref().map(|ie| &ie.plmn), m.uli.as_ref(), m.imsi.as_ref()
+        ),
+        Message::ModifyBearerRequest(m) => plmn::validate(
+            m.serving_network.as_ref().map(|ie| &ie.plmn), m.user_location_information.as_ref(), None
+        ),
+        Message::BearerResourceCommand(m) => plmn::validate(
+            m.serving_network.as_ref().map(|ie| &ie.plmn), m.uli.as_ref(), None
+        ),
+        _ => Vec::new(),
+    }
+}
+
+fn required<T>(ie: &Option<T>, ie_type: InformationElementType, instance: u8, violations: &mut Vec<Violation>) {
+    if ie.is_none() {
+        violations.push(Violation::IEMissing(ie_type, instance));
+    }
+}
+
+fn not_expected<T: InformationElementTraits>(ie: &Option<T>, violations: &mut Vec<Violation>) {
+    if let Some(ref ie) = ie {
+        violations.push(Violation::IENotExpected(ie.information_element_type(), ie.instance()));
+    }
+}
+
+fn expected_if<T: InformationElementTraits>(ie: &Option<T>, expected: bool, violations: &mut Vec<Violation>) {
+    if !expected {
+        not_expected(ie, violations);
+    }
+}
+
+fn interface_type(ie: &f_teid::InformationElement, expected: &[InterfaceType], violations: &mut Vec<Violation>) {
+    if !expected.contains(&ie.interface_type) {
+        violations.push(Violation::WrongInterfaceType(ie.instance(), ie.interface_type));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::net::Ipv4Addr;
+
+    use ascii::AsciiString;
+
+    use crate::gtp_v2::packet::messages::{context_request, echo_request};
+    use crate::gtp_v2::packet::messages::information_elements::{
+        ambr,
+        apn,
+        apn_restriction,
+        bearer_context,
+        bearer_qos,
+        cause,
+        ebi,
+        fq_csid,
+        pdn_address_allocation,
+        pdn_type,
+        rat_type,
+        recovery,
+        selection_mode,
+        serving_network,
+        user_location_information::{PLMN, TAI, UliBuilder},
+    };
+
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    fn f_teid(interface_type: InterfaceType, instance: u8) -> f_teid::InformationElement {
+        f_teid::InformationElement::new(interface_type, 0x12345678, Some(Ipv4Addr::new(10, 0, 0, 1)), None, instance).unwrap()
+    }
+
+    fn bearer_context() -> bearer_context::InformationElement {
+        bearer_context::InformationElement::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+            0
+        ).unwrap()
+    }
+
+    fn create_session_request(interface: Interface) -> create_session_request::Message {
+        let mut m = create_session_request::Message::new(
+            rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+            f_teid(interface.request_control_plane(), 0),
+            bearer_context(),
+            apn::InformationElement::new(AsciiString::from_ascii("internet").unwrap(), 0).unwrap(),
+        );
+
+        if interface.is_s11_s4() {
+            m.uli = Some(UliBuilder::new().tai(TAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234)).build().unwrap());
+            m.pgw_s5_s8_for_control_plane = Some(f_teid(InterfaceType::S5S8PgwGtpC, 1));
+        }
+
+        if interface != Interface::S2b {
+            m.serving_network = Some(serving_network::InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap());
+        }
+
+        m.selection_mode = Some(selection_mode::InformationElement::new(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified, 0).unwrap());
+        m.pdn_type = Some(pdn_type::InformationElement::new(pdn_type::PDNType::IPv4, 0).unwrap());
+        m.pdn_address_allocation = Some(pdn_address_allocation::InformationElement::new(pdn_type::PDNType::IPv4, Some(Ipv4Addr::new(0, 0, 0, 0)), None, 0).unwrap());
+        m.apn_ambr = Some(ambr::InformationElement::new(1000, 2000, 0).unwrap());
+
+        m
+    }
+
+    fn create_session_response(interface: Interface) -> create_session_response::Message {
+        let mut m = create_session_response::Message::new(
+            cause::InformationElement::new(cause::CauseCode::RequestAccepted, cause::CauseSource::LocalNode, false, false, None, 0).unwrap(),
+            vec![bearer_context()],
+        );
+
+        m.sender_f_teid_for_control_plane = Some(f_teid(interface.response_control_plane(), 0));
+        m.pdn_address_allocation = Some(pdn_address_allocation::InformationElement::new(pdn_type::PDNType::IPv4, Some(Ipv4Addr::new(10, 45, 0, 1)), None, 0).unwrap());
+
+        if interface != Interface::S2b {
+            m.apn_restriction = Some(apn_restriction::InformationElement::new(apn_restriction::MaximumAPNRestrictionValue::Unrestricted, 0).unwrap());
+        }
+
+        if interface.is_s11_s4() {
+            m.pgw_s5_s8_for_control_plane = Some(f_teid(InterfaceType::S5S8PgwGtpC, 1));
+        }
+
+        m
+    }
+
+    #[test]
+    fn test_message_not_used() {
+        let m = Message::ContextRequest(context_request::Message::new());
+
+        assert_eq!(m.check(Interface::S11), [Violation::MessageNotUsed(MessageType::ContextRequest)]);
+
+        let m = Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap()));
+
+        for interface in [Interface::S11, Interface::S4, Interface::S5S8, Interface::S2b] {
+            assert!(m.check(interface).is_empty());
+        }
+
+        assert!(!Interface::S2b.is_used_by(MessageType::DownlinkDataNotification));
+        assert!(Interface::S4.is_used_by(MessageType::DownlinkDataNotification));
+        assert!(!Interface::S4.is_used_by(MessageType::RemoteUEReportNotification));
+    }
+
+    #[test]
+    fn test_create_session_request() {
+        for interface in [Interface::S11, Interface::S4, Interface::S5S8, Interface::S2b] {
+            assert_eq!(Message::CreateSessionRequest(create_session_request(interface)).check(interface), []);
+        }
+
+        // An S11 request sent on S5/S8
+        let m = Message::CreateSessionRequest(create_session_request(Interface::S11));
+
+        assert_eq!(m.check(Interface::S5S8), [
+            Violation::WrongInterfaceType(0, InterfaceType::S11MmeGtpC),
+            Violation::IENotExpected(InformationElementType::FTEID, 1),
+        ]);
+
+        // An S5/S8 request is missing the S11 only IEs
+        let m = Message::CreateSessionRequest(create_session_request(Interface::S5S8));
+
+        assert_eq!(m.check(Interface::S11), [
+            Violation::WrongInterfaceType(0, InterfaceType::S5S8SgwGtpC),
+            Violation::IEMissing(InformationElementType::UserLocationInformation, 0),
+            Violation::IEMissing(InformationElementType::FTEID, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_create_session_request_node_ies() {
+        let mut m = create_session_request(Interface::S11);
+        m.mme_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 1)), vec![1], 0).unwrap());
+        m.selection_mode = None;
+
+        assert_eq!(Message::CreateSessionRequest(m.clone()).check(Interface::S11), [
+            Violation::IEMissing(InformationElementType::SelectionMode, 0),
+        ]);
+
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 2)), vec![2], 1).unwrap());
+
+        assert_eq!(Message::CreateSessionRequest(m).check(Interface::S11), [
+            Violation::IEMissing(InformationElementType::SelectionMode, 0),
+            Violation::IENotExpected(InformationElementType::FQCSID, 1),
+        ]);
+    }
+
+    #[test]
+    fn test_create_session_response() {
+        for interface in [Interface::S11, Interface::S4, Interface::S5S8, Interface::S2b] {
+            assert_eq!(Message::CreateSessionResponse(create_session_response(interface)).check(interface), []);
+        }
+
+        let m = Message::CreateSessionResponse(create_session_response(Interface::S5S8));
+
+        assert_eq!(m.check(Interface::S11), [
+            Violation::IEMissing(InformationElementType::FTEID, 1),
+            Violation::WrongInterfaceType(0, InterfaceType::S5S8PgwGtpC),
+        ]);
+
+        // A rejection only carries the Cause
+        let m = Message::CreateSessionResponse(create_session_response::Message::reject_with(cause::CauseCode::NoResourcesAvailable).unwrap());
+
+        assert!(m.check(Interface::S11).is_empty());
+    }
+
+    #[test]
+    fn test_modify_bearer_request() {
+        let mut m = modify_bearer_request::Message::new();
+        m.sender_f_teid_for_control_plane = Some(f_teid(InterfaceType::S11MmeGtpC, 0));
+
+        assert!(Message::ModifyBearerRequest(m.clone()).check(Interface::S11).is_empty());
+
+        m.delay_downlink_packet_notification_request = Some(crate::gtp_v2::packet::messages::information_elements::delay_value::InformationElement::new(1, 0).unwrap());
+
+        assert_eq!(Message::ModifyBearerRequest(m).check(Interface::S5S8), [
+            Violation::WrongInterfaceType(0, InterfaceType::S11MmeGtpC),
+            Violation::IENotExpected(InformationElementType::DelayValue, 0),
+        ]);
+    }
+
+    #[test]
+    fn test_check_plausibility() {
+        let mut m = create_session_request(Interface::S11);
+
+        // No IMSI
+        assert_eq!(check_plausibility(&Message::CreateSessionRequest(m.clone())), []);
+
+        m.set_imsi("505099000000001").unwrap();
+        assert_eq!(check_plausibility(&Message::CreateSessionRequest(m.clone())), []);
+
+        // A roaming UE
+        m.set_imsi("310410000000001").unwrap();
+        assert_eq!(check_plausibility(&Message::CreateSessionRequest(m.clone())), [
+            Warning::ImsiNotFromPlmn(InformationElementType::ServingNetwork, PLMN::new(505, 99, true).unwrap()),
+        ]);
+
+        // The cell is in another PLMN to the Serving Network
+        m.set_imsi("505099000000001").unwrap();
+        m.uli = Some(UliBuilder::new().tai(TAI::new(PLMN::new(505, 1, false).unwrap(), 0x1234)).build().unwrap());
+        assert_eq!(check_plausibility(&Message::CreateSessionRequest(m.clone())), [
+            Warning::ImsiNotFromPlmn(InformationElementType::UserLocationInformation, PLMN::new(505, 1, false).unwrap()),
+            Warning::UliPlmnMismatch(PLMN::new(505, 99, true).unwrap(), PLMN::new(505, 1, false).unwrap()),
+        ]);
+
+        // A Modify Bearer Request has no IMSI but its location is checked
+        let mut mbr = modify_bearer_request::Message::new();
+        mbr.serving_network = m.serving_network.clone();
+        mbr.user_location_information = m.uli.clone();
+        assert_eq!(check_plausibility(&Message::ModifyBearerRequest(mbr)), [
+            Warning::UliPlmnMismatch(PLMN::new(505, 99, true).unwrap(), PLMN::new(505, 1, false).unwrap()),
+        ]);
+
+        // Only messages with an IMSI or location are checked
+        let m = Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap()));
+        assert_eq!(check_plausibility(&m), []);
+    }
+
+    #[test]
+    fn test_set_imsi() {
+        let mut m = create_session_request(Interface::S11);
+
+        assert!(m.set_imsi("505099000000001").is_ok());
+        assert_eq!(m.imsi.as_ref().unwrap().to_string(), "505099000000001");
+
+        assert_eq!(m.set_imsi("50599000000000a"), Err("Could not parse IMSI 50599000000000a: Not a decimal digit (a)".to_string()));
+        assert_eq!(m.set_imsi("50599"), Err("IMSI must be 6 to 15 digits 50599".to_string()));
+
+        // The IMSI is unchanged if the new one isn't valid
+        assert_eq!(m.imsi.as_ref().unwrap().to_string(), "505099000000001");
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Violation::MessageNotUsed(MessageType::ContextRequest).to_string(),
+            "Context Request is not used on this interface"
+        );
+        assert_eq!(
+            Violation::IEMissing(InformationElementType::UserLocationInformation, 0).to_string(),
+            "UserLocationInformation (instance 0) is missing"
+        );
+        assert_eq!(
+            Warning::UliPlmnMismatch(PLMN::new(505, 99, true).unwrap(), PLMN::new(505, 1, false).unwrap()).to_string(),
+            "ULI PLMN 505-01 is not the Serving Network 505-099"
+        );
+    }
+}
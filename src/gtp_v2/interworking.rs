@@ -0,0 +1,88 @@
+/* The 5GS view of an EPS PDN connection, for interworking with 5GS over N26 (TS 23.502 4.11.1).
+
+When a UE moves from EPS to 5GS the AMF gets the PDN connections of the UE from the MME in a Forward Relocation Request
+or Context Response. Each PDN connection becomes a PDU session and each EPS bearer becomes a QoS flow of it
+(TS 23.501 5.17.2.2). The APN is used as the DNN, the APN-AMBR as the Session-AMBR and the QCI of a bearer as the 5QI of
+its QoS flow. See pdn_connection::InformationElement::to_5gs.
+
+The PDU Session ID and the QFIs aren't carried by GTPv2. They come from the UE and the SMF so they're left to the caller. */
+
+use ascii::AsciiString;
+
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::bitrate::Bitrate;
+
+use super::packet::messages::information_elements::bearer_qos;
+
+use alloc::vec::Vec;
+
+// PDU Session Type (TS 24.501 9.11.4.11)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PduSessionType {
+    IPv4,
+    IPv6,
+    IPv4v6,
+    Unstructured, // Non-IP PDN connections (TS 23.501 5.17.2.1)
+    Ethernet,
+}
+
+impl From<PduSessionType> for u8 {
+    fn from(value: PduSessionType) -> Self {
+        match value {
+            PduSessionType::IPv4 => 1,
+            PduSessionType::IPv6 => 2,
+            PduSessionType::IPv4v6 => 3,
+            PduSessionType::Unstructured => 4,
+            PduSessionType::Ethernet => 5,
+        }
+    }
+}
+
+impl fmt::Display for PduSessionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PduSessionType::IPv4 => write!(f, "IPv4"),
+            PduSessionType::IPv6 => write!(f, "IPv6"),
+            PduSessionType::IPv4v6 => write!(f, "IPv4v6"),
+            PduSessionType::Unstructured => write!(f, "Unstructured"),
+            PduSessionType::Ethernet => write!(f, "Ethernet"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QosFlow {
+    pub eps_bearer_id: u8, // The EPS bearer the QoS flow is mapped from, so it can be mapped back
+    pub five_qi: u8, // Standardised QCIs have the same value as 5QI (TS 23.501 5.7.4)
+    pub arp: bearer_qos::Arp,
+    pub max_ul: Bitrate, // The MFBR and GFBR are zero for a non-GBR QoS flow
+    pub max_dl: Bitrate,
+    pub guaranteed_ul: Bitrate,
+    pub guaranteed_dl: Bitrate,
+}
+
+impl QosFlow {
+    pub fn is_gbr(&self) -> bool {
+        self.guaranteed_ul.bps() > 0 || self.guaranteed_dl.bps() > 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PduSession {
+    pub dnn: AsciiString, // The Network Identifier of the APN
+    pub pdu_session_type: PduSessionType,
+    pub ipv4_address: Option<Ipv4Addr>,
+    pub ipv6_address: Option<Ipv6Addr>,
+    pub session_ambr_ul: Bitrate,
+    pub session_ambr_dl: Bitrate,
+    pub default_eps_bearer_id: u8, // The Linked EPS Bearer ID. Its QoS flow has the default QoS rule
+    pub qos_flows: Vec<QosFlow>,
+}
+
+impl PduSession {
+    pub fn default_qos_flow(&self) -> Option<&QosFlow> {
+        self.qos_flows.iter().find(|f| f.eps_bearer_id == self.default_eps_bearer_id)
+    }
+}
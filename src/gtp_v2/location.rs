@@ -0,0 +1,204 @@
+// Tracks the location of a UE and builds the Modify Bearer Request that reports a change of it as per TS 29.274 7.2.7
+
+use super::packet::messages::modify_bearer_request;
+use super::packet::messages::information_elements::{
+    change_to_report_flags,
+    indication,
+    rat_type,
+    serving_network,
+    ue_time_zone,
+    user_location_information,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct Location {
+    pub user_location_information: Option<user_location_information::InformationElement>,
+    pub serving_network: Option<serving_network::InformationElement>,
+    pub ue_time_zone: Option<ue_time_zone::InformationElement>,
+    pub rat_type: Option<rat_type::InformationElement>,
+}
+
+impl Location {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn modify_bearer_request(&self, new: &Location, isr_active: bool) -> Option<modify_bearer_request::Message> {
+        /* Builds the Modify Bearer Request that reports the change from self to new. Only the IEs that changed are
+        included. A change of ULI also sets the CLII Indication flag.
+
+        With ISR active the Serving Network and UE Time Zone may already have been reported by the node serving the
+        other RAT so they are not sent. Their change is reported with the Change to Report Flags instead.
+
+        Returns None if there is nothing to report. An IE that is no longer known (i.e. None in new) isn't a change. */
+        let mut m = modify_bearer_request::Message::new();
+
+        if new.user_location_information.is_some() && new.user_location_information != self.user_location_information {
+            m.user_location_information = new.user_location_information.clone();
+            m.indication = indication::InformationElement::with_flags(&[indication::CLII], 0).ok();
+        }
+
+        if new.rat_type.is_some() && new.rat_type != self.rat_type {
+            m.rat_type = new.rat_type.clone();
+        }
+
+        let serving_network_change = new.serving_network.is_some() && new.serving_network != self.serving_network;
+        let time_zone_change = new.ue_time_zone.is_some() && new.ue_time_zone != self.ue_time_zone;
+
+        if isr_active {
+            if serving_network_change || time_zone_change {
+                m.change_to_report_flags = change_to_report_flags::InformationElement::new(serving_network_change, time_zone_change, 0).ok();
+            }
+        }
+        else {
+            if serving_network_change {
+                m.serving_network = new.serving_network.clone();
+            }
+
+            if time_zone_change {
+                m.ue_time_zone = new.ue_time_zone.clone();
+            }
+        }
+
+        if m == modify_bearer_request::Message::new() {
+            None
+        }
+        else {
+            Some(m)
+        }
+    }
+
+    pub fn update(&mut self, m: &modify_bearer_request::Message) {
+        // Takes on the location reported by a Modify Bearer Request (e.g. one received by an SGW)
+        if m.user_location_information.is_some() {
+            self.user_location_information = m.user_location_information.clone();
+        }
+
+        if m.serving_network.is_some() {
+            self.serving_network = m.serving_network.clone();
+        }
+
+        if m.ue_time_zone.is_some() {
+            self.ue_time_zone = m.ue_time_zone.clone();
+        }
+
+        if m.rat_type.is_some() {
+            self.rat_type = m.rat_type.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::user_location_information::{PLMN, TAI, ECGI, UliBuilder};
+    use super::ue_time_zone::DaylightSavingsTimeAdjustment;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use alloc::vec::Vec;
+
+    fn uli(tac: u16, eci: u32) -> user_location_information::InformationElement {
+        UliBuilder::new()
+            .tai(TAI::new(PLMN::new(505, 99, true).unwrap(), tac))
+            .ecgi(ECGI::new(PLMN::new(505, 99, true).unwrap(), eci).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn location() -> Location {
+        Location {
+            user_location_information: Some(uli(0x1234, 0x1000)),
+            serving_network: Some(serving_network::InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap()),
+            ue_time_zone: Some(ue_time_zone::InformationElement::from_minutes(600, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap()),
+            rat_type: Some(rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap()),
+        }
+    }
+
+    fn ie_types(m: &modify_bearer_request::Message) -> Vec<InformationElementType> {
+        m.ies().map(|ie| ie.information_element_type()).collect()
+    }
+
+    #[test]
+    fn test_no_change() {
+        assert!(location().modify_bearer_request(&location(), false).is_none());
+        assert!(location().modify_bearer_request(&location(), true).is_none());
+
+        // Losing an IE isn't reported
+        assert!(location().modify_bearer_request(&Location::new(), false).is_none());
+    }
+
+    #[test]
+    fn test_uli_change() {
+        let mut new = location();
+        new.user_location_information = Some(uli(0x1234, 0x1001));
+
+        if let Some(m) = location().modify_bearer_request(&new, false) {
+            assert_eq!(ie_types(&m), [InformationElementType::UserLocationInformation, InformationElementType::Indication]);
+            assert_eq!(m.user_location_information, new.user_location_information);
+            assert!(m.indication.map(|ie| ie.is_set(indication::CLII)).unwrap_or(false));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_serving_network_and_time_zone_change() {
+        let mut new = location();
+        new.serving_network = Some(serving_network::InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0).unwrap());
+        new.ue_time_zone = Some(ue_time_zone::InformationElement::from_minutes(660, DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap());
+
+        if let Some(m) = location().modify_bearer_request(&new, false) {
+            assert_eq!(ie_types(&m), [InformationElementType::ServingNetwork, InformationElementType::UETimeZone]);
+            assert_eq!(m.serving_network, new.serving_network);
+            assert_eq!(m.ue_time_zone, new.ue_time_zone);
+            assert!(m.indication.is_none());
+            assert!(m.change_to_report_flags.is_none());
+        }
+        else {
+            assert!(false);
+        }
+
+        // With ISR active only the flags are sent
+        if let Some(m) = location().modify_bearer_request(&new, true) {
+            assert_eq!(ie_types(&m), [InformationElementType::ChangeToReportFlags]);
+            assert_eq!(m.change_to_report_flags, Some(change_to_report_flags::InformationElement::new(true, true, 0).unwrap()));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_rat_type_change() {
+        let mut new = location();
+        new.rat_type = Some(rat_type::InformationElement::new(rat_type::RATType::UTRAN, 0).unwrap());
+        new.user_location_information = Some(uli(0x4321, 0x2000));
+
+        if let Some(m) = location().modify_bearer_request(&new, true) {
+            assert_eq!(ie_types(&m), [
+                InformationElementType::UserLocationInformation,
+                InformationElementType::RATType,
+                InformationElementType::Indication,
+            ]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut new = location();
+        new.user_location_information = Some(uli(0x1234, 0x1001));
+        new.ue_time_zone = Some(ue_time_zone::InformationElement::from_minutes(660, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap());
+
+        let mut l = location();
+        l.update(&location().modify_bearer_request(&new, false).unwrap());
+
+        assert_eq!(l, new);
+        assert!(l.modify_bearer_request(&new, false).is_none());
+    }
+}
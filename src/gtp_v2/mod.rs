@@ -1 +1,12 @@
-pub mod packet;
\ No newline at end of file
+pub mod packet;
+pub mod node_selection;
+pub mod sequence;
+pub mod location;
+pub mod conformance;
+pub mod interworking;
+pub mod plmn;
+
+#[cfg(feature = "std")]
+pub mod paa_pool;
+#[cfg(feature = "std")]
+pub mod server;
@@ -0,0 +1,129 @@
+// Helpers for selecting GTP-C peers (e.g. SGW/PGW) based on the APN and serving PLMN as per TS 23.003 and TS 29.303
+
+#[cfg(feature = "std")]
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use ascii::AsciiString;
+
+use super::packet::messages::information_elements::{
+    fqdn,
+    user_location_information::PLMN,
+};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const GTP_C_PORT: u16 = 2123;
+
+pub fn mnc_label(plmn: &PLMN) -> String {
+    // MNC is always encoded with 3 digits in the FQDN. 2 digit MNCs are padded with a leading 0
+    format!("mnc{:03}", plmn.mnc())
+}
+
+pub fn mcc_label(plmn: &PLMN) -> String {
+    format!("mcc{}", plmn.mcc_string())
+}
+
+pub fn apn_fqdn(apn: &str, plmn: &PLMN) -> String {
+    /* Build the APN FQDN used for PGW selection: <APN-NI>.apn.epc.mnc<MNC>.mcc<MCC>.3gppnetwork.org
+    If the APN already includes an APN Operator Identifier (<APN-NI>.mnc<MNC>.mcc<MCC>.gprs) then that is used
+    instead of the serving PLMN */
+
+    let labels: Vec<&str> = apn.trim_end_matches('.').split('.').collect();
+    let n = labels.len();
+
+    if n > 3 &&
+        labels[n-1].eq_ignore_ascii_case("gprs") &&
+        labels[n-2].to_ascii_lowercase().starts_with("mcc") &&
+        labels[n-3].to_ascii_lowercase().starts_with("mnc") {
+        format!(
+            "{}.apn.epc.{}.{}.3gppnetwork.org",
+            labels[..n-3].join("."),
+            labels[n-3].to_ascii_lowercase(),
+            labels[n-2].to_ascii_lowercase()
+        )
+    }
+    else {
+        format!("{}.apn.epc.{}.{}.3gppnetwork.org", labels.join("."), mnc_label(plmn), mcc_label(plmn))
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn resolve_gtp_c_peers(fqdn: &str) -> Result<Vec<SocketAddr>, String> {
+    // NOTE: This only performs A/AAAA lookups via the system resolver. S-NAPTR procedures are not supported
+    match (fqdn, GTP_C_PORT).to_socket_addrs() {
+        Ok(addrs) => {
+            let addrs: Vec<SocketAddr> = addrs.collect();
+
+            if addrs.len() > 0 {
+                Ok(addrs)
+            }
+            else {
+                Err(format!("No addresses found for {}", fqdn))
+            }
+        },
+        Err(e) => Err(format!("Could not resolve {} ({})", fqdn, e)),
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn select_pgw_candidates(apn: &str, plmn: &PLMN) -> Result<Vec<SocketAddr>, String> {
+    resolve_gtp_c_peers(&apn_fqdn(apn, plmn))
+}
+
+pub fn node_name(name: &str, instance: u8) -> Result<fqdn::InformationElement, String> {
+    // Build an FQDN IE suitable for the SGW-U/PGW node name IEs
+    match AsciiString::from_ascii(name) {
+        Ok(name) => fqdn::InformationElement::new(name, instance),
+        Err(_) => Err(format!("Node name is not ASCII {}", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementTraits;
+
+    #[test]
+    fn test_apn_fqdn() {
+        assert_eq!(
+            apn_fqdn("internet", &PLMN::new(505, 99, true).unwrap()),
+            "internet.apn.epc.mnc099.mcc505.3gppnetwork.org"
+        );
+
+        assert_eq!(
+            apn_fqdn("internet", &PLMN::new(505, 1, false).unwrap()),
+            "internet.apn.epc.mnc001.mcc505.3gppnetwork.org"
+        );
+
+        assert_eq!(
+            apn_fqdn("awesome.apn.mnc002.mcc310.gprs", &PLMN::new(505, 99, true).unwrap()),
+            "awesome.apn.apn.epc.mnc002.mcc310.3gppnetwork.org"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_resolve_gtp_c_peers() {
+        use std::net::{IpAddr, Ipv4Addr};
+
+        if let Ok(addrs) = resolve_gtp_c_peers("127.0.0.1") {
+            assert_eq!(addrs, vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127,0,0,1)), GTP_C_PORT)]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_node_name() {
+        let ie = node_name("topon.s5.pgw1.node.epc.mnc099.mcc505.3gppnetwork.org", 0).unwrap();
+
+        assert_eq!(ie.instance(), 0);
+        assert_eq!(ie.fqdn, AsciiString::from_ascii("topon.s5.pgw1.node.epc.mnc099.mcc505.3gppnetwork.org").unwrap());
+
+        assert!(node_name("pgw..epc", 0).is_err());
+    }
+}
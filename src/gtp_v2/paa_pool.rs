@@ -0,0 +1,332 @@
+/* UE IP address pools for building the PDN Address Allocation (PAA) IE in a Create Session Response.
+
+IPv4 addresses are allocated from a prefix (excluding the network and broadcast addresses). IPv6 is allocated as a
+/64 per PDN connection from a shorter prefix as per TS 23.401 5.3.1.2.2, with the Interface Identifier left as 0
+as the UE builds its own. */
+
+use std::collections::HashSet;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::packet::messages::information_elements::{pdn_address_allocation, pdn_type::PDNType};
+
+pub const IPV6_PREFIX_LENGTH: u8 = 64;
+
+pub struct Ipv4Pool {
+    first: u32,
+    size: u32,
+    next: u32, // Offset of the next address to try
+    allocated: HashSet<u32>,
+}
+
+impl Ipv4Pool {
+    pub fn new(network: Ipv4Addr, prefix_length: u8) -> Result<Self, String> {
+        if prefix_length > 32 {
+            return Err(format!("Prefix length ({}) too large.", prefix_length));
+        }
+
+        let mask = if prefix_length == 0 { 0 } else { u32::MAX << (32 - prefix_length) };
+        let network = u32::from(network);
+
+        if network & !mask != 0 {
+            return Err(format!("{} has host bits set for prefix length {}", Ipv4Addr::from(network), prefix_length));
+        }
+
+        let host_bits = 32 - prefix_length as u32;
+
+        // The network and broadcast addresses are only usable in /31 and /32 prefixes
+        let (first, size) = if host_bits <= 1 {
+            (network, 1 << host_bits)
+        }
+        else {
+            (network + 1, ((1u64 << host_bits) - 2) as u32)
+        };
+
+        Ok(Ipv4Pool {
+            first,
+            size,
+            next: 0,
+            allocated: HashSet::new(),
+        })
+    }
+
+    pub fn allocate(&mut self) -> Option<Ipv4Addr> {
+        // Addresses are handed out in order, wrapping around, so a released address isn't reused straight away
+        if self.allocated.len() as u64 >= self.size as u64 {
+            return None
+        }
+
+        loop {
+            let offset = self.next;
+            self.next = if self.next + 1 >= self.size { 0 } else { self.next + 1 };
+
+            if self.allocated.insert(offset) {
+                return Some(Ipv4Addr::from(self.first + offset));
+            }
+        }
+    }
+
+    pub fn allocate_address(&mut self, address: Ipv4Addr) -> Result<Ipv4Addr, String> {
+        // Allocates a specific address e.g. a static address from the subscription
+        let offset = self.offset(address)?;
+
+        if self.allocated.insert(offset) {
+            Ok(address)
+        }
+        else {
+            Err(format!("{} is already allocated", address))
+        }
+    }
+
+    pub fn release(&mut self, address: Ipv4Addr) -> bool {
+        match self.offset(address) {
+            Ok(offset) => self.allocated.remove(&offset),
+            Err(_) => false,
+        }
+    }
+
+    pub fn available(&self) -> u32 {
+        self.size - self.allocated.len() as u32
+    }
+
+    fn offset(&self, address: Ipv4Addr) -> Result<u32, String> {
+        let address = u32::from(address);
+
+        if address < self.first || address - self.first >= self.size {
+            return Err(format!("{} is not in the pool", Ipv4Addr::from(address)));
+        }
+
+        Ok(address - self.first)
+    }
+}
+
+pub struct Ipv6Pool {
+    prefix: u128,
+    size: u64, // Number of /64 prefixes in the pool
+    next: u64,
+    allocated: HashSet<u64>,
+}
+
+impl Ipv6Pool {
+    pub fn new(prefix: Ipv6Addr, prefix_length: u8) -> Result<Self, String> {
+        if prefix_length > IPV6_PREFIX_LENGTH {
+            return Err(format!("Prefix length ({}) must be at most {}.", prefix_length, IPV6_PREFIX_LENGTH));
+        }
+
+        let mask = if prefix_length == 0 { 0 } else { u128::MAX << (128 - prefix_length) };
+        let prefix = u128::from(prefix);
+
+        if prefix & !mask != 0 {
+            return Err(format!("{} has host bits set for prefix length {}", Ipv6Addr::from(prefix), prefix_length));
+        }
+
+        let prefix_bits = (IPV6_PREFIX_LENGTH - prefix_length) as u32;
+
+        Ok(Ipv6Pool {
+            prefix,
+            // A /0 pool has 2^64 prefixes. One fewer can be allocated which doesn't matter in practice
+            size: if prefix_bits == 64 { u64::MAX } else { 1 << prefix_bits },
+            next: 0,
+            allocated: HashSet::new(),
+        })
+    }
+
+    pub fn allocate(&mut self) -> Option<(Ipv6Addr, u8)> {
+        if self.allocated.len() as u64 >= self.size {
+            return None
+        }
+
+        loop {
+            let index = self.next;
+            self.next = if self.next + 1 >= self.size { 0 } else { self.next + 1 };
+
+            if self.allocated.insert(index) {
+                return Some((self.address(index), IPV6_PREFIX_LENGTH));
+            }
+        }
+    }
+
+    pub fn allocate_prefix(&mut self, prefix: Ipv6Addr) -> Result<(Ipv6Addr, u8), String> {
+        let index = self.index(prefix)?;
+
+        if self.allocated.insert(index) {
+            Ok((self.address(index), IPV6_PREFIX_LENGTH))
+        }
+        else {
+            Err(format!("{}/{} is already allocated", self.address(index), IPV6_PREFIX_LENGTH))
+        }
+    }
+
+    pub fn release(&mut self, prefix: Ipv6Addr) -> bool {
+        // Any address within the /64 releases it
+        match self.index(prefix) {
+            Ok(index) => self.allocated.remove(&index),
+            Err(_) => false,
+        }
+    }
+
+    pub fn available(&self) -> u64 {
+        self.size - self.allocated.len() as u64
+    }
+
+    fn address(&self, index: u64) -> Ipv6Addr {
+        Ipv6Addr::from(self.prefix | ((index as u128) << 64))
+    }
+
+    fn index(&self, prefix: Ipv6Addr) -> Result<u64, String> {
+        // The Interface Identifier (the last 64 bits) is ignored
+        let index = (u128::from(prefix) >> 64) as u64;
+        let pool_prefix = (self.prefix >> 64) as u64;
+
+        // The pool's prefix bits must match and the remaining bits must be within the pool
+        if index < pool_prefix || index - pool_prefix >= self.size {
+            return Err(format!("{} is not in the pool", prefix));
+        }
+
+        Ok(index - pool_prefix)
+    }
+}
+
+pub struct PaaPool {
+    pub ipv4: Option<Ipv4Pool>,
+    pub ipv6: Option<Ipv6Pool>,
+}
+
+impl PaaPool {
+    pub fn new(ipv4: Option<Ipv4Pool>, ipv6: Option<Ipv6Pool>) -> Self {
+        PaaPool {
+            ipv4,
+            ipv6,
+        }
+    }
+
+    pub fn allocate(&mut self, pdn_type: PDNType, instance: u8) -> Result<pdn_address_allocation::InformationElement, String> {
+        /* Allocates the addresses for a PDN connection of pdn_type (e.g. the PDN Type from the Create Session Request).
+        An IPv4v6 request falls back to a single address type if the other pool isn't configured or is exhausted
+        (the PDN Type of the returned IE then differs from the one requested, which should be reported with the
+        cause "New PDN type due to single address bearer only" as per TS 29.274 7.2.2). */
+        let ipv4_address = match pdn_type {
+            PDNType::IPv4 | PDNType::IPv4v6 => self.ipv4.as_mut().and_then(|pool| pool.allocate()),
+            _ => None
+        };
+
+        let ipv6_address_and_prefix = match pdn_type {
+            PDNType::IPv6 | PDNType::IPv4v6 => self.ipv6.as_mut().and_then(|pool| pool.allocate()),
+            _ => None
+        };
+
+        let allocated_pdn_type = match (pdn_type, ipv4_address, ipv6_address_and_prefix) {
+            (PDNType::NonIp, _, _) => PDNType::NonIp,
+            (_, Some(_), Some(_)) => PDNType::IPv4v6,
+            (_, Some(_), None) => PDNType::IPv4,
+            (_, None, Some(_)) => PDNType::IPv6,
+            (_, None, None) => return Err(format!("No addresses available for PDN type {:?}", pdn_type)),
+        };
+
+        pdn_address_allocation::InformationElement::new(allocated_pdn_type, ipv4_address, ipv6_address_and_prefix, instance)
+    }
+
+    pub fn release(&mut self, paa: &pdn_address_allocation::InformationElement) {
+        if let (Some(pool), Some(address)) = (self.ipv4.as_mut(), paa.ipv4_address) {
+            pool.release(address);
+        }
+
+        if let (Some(pool), Some((prefix, _prefix_length))) = (self.ipv6.as_mut(), paa.ipv6_address_and_prefix) {
+            pool.release(prefix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipv4_pool() {
+        let mut pool = Ipv4Pool::new(Ipv4Addr::new(10,0,0,0), 30).unwrap();
+
+        assert_eq!(pool.available(), 2);
+
+        assert_eq!(pool.allocate(), Some(Ipv4Addr::new(10,0,0,1)));
+        assert_eq!(pool.allocate(), Some(Ipv4Addr::new(10,0,0,2)));
+        assert_eq!(pool.allocate(), None);
+
+        assert!(pool.release(Ipv4Addr::new(10,0,0,1)));
+        assert!(!pool.release(Ipv4Addr::new(10,0,0,1)));
+        assert!(!pool.release(Ipv4Addr::new(10,0,0,3)));
+
+        assert_eq!(pool.allocate(), Some(Ipv4Addr::new(10,0,0,1)));
+
+        assert!(Ipv4Pool::new(Ipv4Addr::new(10,0,0,1), 24).is_err());
+        assert!(Ipv4Pool::new(Ipv4Addr::new(10,0,0,0), 33).is_err());
+
+        assert_eq!(Ipv4Pool::new(Ipv4Addr::new(10,0,0,0), 31).unwrap().available(), 2);
+        assert_eq!(Ipv4Pool::new(Ipv4Addr::new(0,0,0,0), 0).unwrap().available(), u32::MAX - 1);
+    }
+
+    #[test]
+    fn test_ipv4_pool_allocate_address() {
+        let mut pool = Ipv4Pool::new(Ipv4Addr::new(10,0,0,0), 24).unwrap();
+
+        assert_eq!(pool.allocate_address(Ipv4Addr::new(10,0,0,1)), Ok(Ipv4Addr::new(10,0,0,1)));
+        assert!(pool.allocate_address(Ipv4Addr::new(10,0,0,1)).is_err());
+        assert!(pool.allocate_address(Ipv4Addr::new(10,0,0,255)).is_err());
+        assert!(pool.allocate_address(Ipv4Addr::new(10,0,1,1)).is_err());
+
+        // Allocated addresses are skipped
+        assert_eq!(pool.allocate(), Some(Ipv4Addr::new(10,0,0,2)));
+    }
+
+    #[test]
+    fn test_ipv6_pool() {
+        let mut pool = Ipv6Pool::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 63).unwrap();
+
+        assert_eq!(pool.available(), 2);
+
+        assert_eq!(pool.allocate(), Some((Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 64)));
+        assert_eq!(pool.allocate(), Some((Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0), 64)));
+        assert_eq!(pool.allocate(), None);
+
+        assert!(pool.release(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 1)));
+        assert!(!pool.release(Ipv6Addr::new(0x2001, 0xdb8, 0, 2, 0, 0, 0, 0)));
+
+        assert!(pool.allocate_prefix(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)).is_err());
+        assert!(pool.allocate_prefix(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0)).is_ok());
+
+        assert!(Ipv6Pool::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 1, 0, 0, 0, 0), 48).is_err());
+        assert!(Ipv6Pool::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 65).is_err());
+    }
+
+    #[test]
+    fn test_paa_pool() {
+        let mut pool = PaaPool::new(
+            Some(Ipv4Pool::new(Ipv4Addr::new(10,0,0,0), 30).unwrap()),
+            Some(Ipv6Pool::new(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 64).unwrap())
+        );
+
+        let paa = pool.allocate(PDNType::IPv4v6, 0).unwrap();
+
+        assert_eq!(paa.pdn_type, PDNType::IPv4v6);
+        assert_eq!(paa.ipv4_address, Some(Ipv4Addr::new(10,0,0,1)));
+        assert_eq!(paa.ipv6_address_and_prefix, Some((Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 64)));
+
+        // The IPv6 pool is exhausted so falls back to IPv4 only
+        let paa = pool.allocate(PDNType::IPv4v6, 0).unwrap();
+
+        assert_eq!(paa.pdn_type, PDNType::IPv4);
+        assert_eq!(paa.ipv4_address, Some(Ipv4Addr::new(10,0,0,2)));
+
+        assert!(pool.allocate(PDNType::IPv4, 0).is_err());
+        assert!(pool.allocate(PDNType::IPv6, 0).is_err());
+
+        pool.release(&paa);
+
+        let paa = pool.allocate(PDNType::IPv4, 0).unwrap();
+
+        assert_eq!(paa.ipv4_address, Some(Ipv4Addr::new(10,0,0,2)));
+
+        let paa = pool.allocate(PDNType::NonIp, 0).unwrap();
+
+        assert_eq!(paa.pdn_type, PDNType::NonIp);
+        assert_eq!(paa.ipv4_address, None);
+    }
+}
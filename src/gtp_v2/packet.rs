@@ -1,15 +1,44 @@
 pub mod header;
 pub mod messages;
+pub mod view;
+pub mod template;
 
+use core::fmt;
+
+use byteorder::{ByteOrder, NetworkEndian};
+#[cfg(feature = "std")]
 use std::net::ToSocketAddrs;
 
 use messages::{
     MessageTraits,
     Message,
+    ParseError,
+    bearer_resource_failure_indication,
 };
 
-use crate::MTU;
+use messages::information_elements::{InformationElementType, cause};
+
+use crate::dissect::{self, Dissect, Dissector};
+
+use super::sequence::{self, SequenceGenerator};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    InvalidVersion(u8),
+    TeidMissing(messages::MessageType), // The message type must carry a TEID but the T flag isn't set
+    TeidNotAllowed(messages::MessageType), // Echo Request / Response must not carry a TEID
+    PiggybackNotAllowed(messages::MessageType), // Only a Create Session Response can have a message piggybacked on it
+    CommandSequenceNumberExpected(messages::MessageType, u32), // Commands and the messages they trigger have the MSB set
+    RequestSequenceNumberExpected(messages::MessageType, u32), // Requests not triggered by a Command have the MSB clear
+    SpareBitsSet(u8, u8), // Spare bits of octet 1 and of the last octet of the header, as in Header::spare_bits()
+}
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Packet {
     pub header: header::Header,
     pub message: Message,
@@ -17,57 +46,324 @@ pub struct Packet {
 
 impl Packet {
     pub fn new(message: Message) -> Self {
+        let mut header = header::Header::new(message.message_type());
+
+        // The T flag is set from the message type. It can be overridden with header.enable_teid() / header.disable_teid()
+        if message.message_type().teid_required() {
+            header.enable_teid();
+        }
+
         Packet {
-            header: header::Header::new(message.message_type()),
+            header,
             message: message
         }
     }
-    pub fn generate(&mut self, buffer: &mut[u8]) -> usize {
-        self.header.set_payload_length(self.message.length());
+    pub fn new_request(message: Message, sequence: &mut SequenceGenerator) -> Self {
+        // An Initial message. It gets the next Sequence Number for its message type from sequence
+        let mut p = Packet::new(message);
+
+        // Sequence numbers from SequenceGenerator always fit in the header
+        p.header.set_sequence_number(sequence.next(p.header.message_type())).unwrap();
+
+        p
+    }
+    pub fn new_response(request: &Packet, message: Message) -> Self {
+        // A Triggered message. The Sequence Number is copied from the request
+        let mut p = Packet::new(message);
+
+        p.header.set_sequence_number(request.header.sequence_number()).unwrap();
+
+        p
+    }
+    fn check_teid(&self) -> Result<(), String> {
+        if self.header.message_type().teid_required() && !self.header.teid_enabled() {
+            return Err(format!("{:?} must carry a TEID but the T flag isn't set", self.header.message_type()));
+        }
+        Ok(())
+    }
+    pub fn generate(&mut self, buffer: &mut[u8]) -> Result<usize, String> {
+        self.check_teid()?;
 
         let pos = self.header.generate(buffer);
-        let pos = pos + self.message.generate(&mut buffer[pos..]);
+        let message_length = self.message.generate(&mut buffer[pos..]);
 
-        pos
+        // The length of the message is only known once it has been generated.
+        // Writing the header length afterwards avoids walking the IEs a second time to calculate it.
+        self.header.set_payload_length(message_length as u16);
+        self.header.generate_length(buffer);
+
+        Ok(pos + message_length)
     }
-    pub fn send_to<A: ToSocketAddrs>(&mut self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {        
-        let mut buffer = [0; MTU];
+    pub fn generate_into(&mut self, buffer: &mut Vec<u8>) -> Result<usize, String> {
+        // Appends the packet to the end of buffer, growing it to fit. Returns the number of octets appended.
+        // Unlike generate this isn't limited to the size of a fixed buffer.
+        self.check_teid()?;
+
+        let length = self.wire_size();
+
+        let start = buffer.len();
+        buffer.resize(start + length, 0);
+
+        let pos = self.generate(&mut buffer[start..])?;
+        buffer.truncate(start + pos);
 
-        let pos = self.generate(&mut buffer);
+        Ok(pos)
+    }
 
-        socket.send_to(&buffer[..pos], addr)
+    pub fn wire_size(&self) -> usize {
+        /* The number of octets generate() will write, calculated without generating the packet. It can be used to
+        size a buffer or to check the packet fits in the MTU before sending it, and drop optional IEs if it doesn't */
+        self.header.header_length() + self.message.length() as usize
     }
 
-    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
-        let h = header::Header::parse(&buffer);
+    pub fn wire_size_with_piggyback(&self, piggybacked: &Packet) -> usize {
+        // A piggybacked message follows this packet in the same UDP datagram (TS 29.274 5.5.1)
+        self.wire_size() + piggybacked.wire_size()
+    }
 
-        if let Some((mut h, h_pos)) = h {
-            let m = messages::Message::parse(h.message_type(), &buffer[h_pos..]);
-
-            if let Some((m, m_pos)) = m {
-                h.set_payload_length(m.length());
-                Some(
-                    (
-                        Packet {
-                            header: h,
-                            message: m
-                        },
-                        h_pos + m_pos
-                    )
-                )
-            }
-            else {
-                None
+    pub fn fits(&self, mtu: usize) -> bool {
+        self.wire_size() <= mtu
+    }
+
+    pub fn to_vec(&mut self) -> Result<Vec<u8>, String> {
+        let mut buffer = Vec::new();
+
+        self.generate_into(&mut buffer)?;
+
+        Ok(buffer)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn send_to<A: ToSocketAddrs>(&mut self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {
+        let buffer = self.to_vec().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+        socket.send_to(&buffer, addr)
+    }
+
+    pub fn dissect(&self) -> String {
+        dissect::dissect(self)
+    }
+
+    #[cfg(any(test, debug_assertions, feature = "strategies"))]
+    pub fn verify_round_trip(&self) -> Result<(), Vec<String>> {
+        /* Generates the packet, parses the bytes back and compares the result with the packet. Returns the
+        differences if they aren't the same, see dissect::diff. Only in debug builds or with the strategies feature */
+        let mut generated = self.clone();
+        let bytes = generated.to_vec().map_err(|e| alloc::vec![e])?;
+
+        match Packet::parse(&bytes) {
+            Ok((parsed, pos)) if pos == bytes.len() => {
+                let differences = dissect::diff(&generated, &parsed);
+
+                if differences.is_empty() { Ok(()) } else { Err(differences) }
+            },
+            Ok((_parsed, pos)) => Err(alloc::vec![format!("Only {} of {} octets were parsed", pos, bytes.len())]),
+            Err(e) => Err(alloc::vec![format!("Failed to parse {} ({:?})", dissect::hex_string(&bytes), e)]),
+        }
+    }
+
+    pub fn reject_with(request: &Packet, cause_code: cause::CauseCode) -> Option<Packet> {
+        // Builds a response to request that rejects it with cause_code.
+        // The response is sent to the TEID from the Sender F-TEID for Control Plane of the request.
+        let teid = match request.message {
+            Message::CreateSessionRequest(ref m) => m.sender_f_teid_for_control_plane.teid,
+            Message::ModifyBearerRequest(ref m) => m.sender_f_teid_for_control_plane.as_ref().map_or(0, |ie| ie.teid),
+            Message::DeleteSessionRequest(ref m) => m.sender_f_teid_for_control_plane.as_ref().map_or(0, |ie| ie.teid),
+            Message::BearerResourceCommand(ref m) => m.sender_f_teid_for_control_plane.as_ref().map_or(0, |ie| ie.teid),
+            _ => 0,
+        };
+
+        let cause = match cause::InformationElement::new(cause_code, cause::CauseSource::LocalNode, false, false, None, 0) {
+            Ok(cause) => cause,
+            Err(_) => return None,
+        };
+
+        if let Message::BearerResourceCommand(ref m) = request.message {
+            // The Failure Indication echoes the LBI and PTI of the command
+            return match bearer_resource_failure_indication::Message::reject_command(m, cause) {
+                Ok(m) => Self::respond(Message::BearerResourceFailureIndication(m), request.header.sequence_number(), teid),
+                Err(_) => None,
             }
-            
+        }
+
+        Self::reject(request.header.message_type(), request.header.sequence_number(), teid, cause)
+    }
+
+    pub fn reject_parse_error(request: &[u8], error: &ParseError) -> Option<Packet> {
+        // Builds a response to a request that failed to parse with error. The Cause and Offending IE are taken from
+        // error. If the Sender F-TEID for Control Plane can't be read from the request the response is sent to TEID 0.
+        let request = match view::MessageView::new_checked(request) {
+            Ok(request) => request,
+            Err(_) => return None,
+        };
+
+        let request_type = request.message_type();
+
+        let teid = match request.find_ie(InformationElementType::FTEID, 0) {
+            Some(ie) => ie.teid().unwrap_or(0),
+            None => 0,
+        };
+
+        Self::reject(request_type, request.sequence_number(), teid, error.cause())
+    }
+
+    fn reject(request_type: messages::MessageType, sequence_number: u32, teid: u32, cause: cause::InformationElement) -> Option<Packet> {
+        if let Some(m) = Message::reject_with(request_type, cause) {
+            Self::respond(m, sequence_number, teid)
         }
         else {
             None
         }
     }
+
+    fn respond(m: Message, sequence_number: u32, teid: u32) -> Option<Packet> {
+        let mut p = Packet::new(m);
+
+        // The Sequence Number is copied from the request to the response
+        if let Err(_) = p.header.set_sequence_number(sequence_number) {
+            return None
+        }
+
+        p.header.enable_teid();
+        p.header.set_teid(teid);
+
+        Some(p)
+    }
+
+    pub fn is_triggered_by(&self, command: &Packet) -> bool {
+        /* Whether this packet was triggered by command, e.g. a Bearer Resource Failure Indication for a Bearer
+        Resource Command. A triggered message copies the Sequence Number of the command (TS 29.274 7.6). If both
+        messages carry a PTI it must match too, so that a UE requested procedure can be followed across messages. */
+        if !command.header.message_type().is_command() || self.header.message_type().is_command() {
+            return false
+        }
+
+        if self.header.sequence_number() != command.header.sequence_number() {
+            return false
+        }
+
+        match (self.message.procedure_transaction_id(), command.message.procedure_transaction_id()) {
+            (Some(pti), Some(command_pti)) => pti == command_pti,
+            _ => true,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        /* Checks the header against the rules in TS 29.274 5.5.1 and 7.6. Every violation found is returned.
+        Spare bits are reported too. Strict parsing rejects them, lenient parsing keeps them in the header */
+        let mut errors = Vec::new();
+
+        let message_type = self.header.message_type();
+
+        if self.header.version() != 2 {
+            errors.push(ValidationError::InvalidVersion(self.header.version()));
+        }
+
+        if message_type.teid_required() && !self.header.teid_enabled() {
+            errors.push(ValidationError::TeidMissing(message_type));
+        }
+        else if !message_type.teid_required() && self.header.teid_enabled() {
+            errors.push(ValidationError::TeidNotAllowed(message_type));
+        }
+
+        if self.header.piggyback() && message_type != messages::MessageType::CreateSessionResponse {
+            errors.push(ValidationError::PiggybackNotAllowed(message_type));
+        }
+
+        if self.header.has_spare_bits() {
+            let (spare_flags, spare) = self.header.spare_bits();
+            errors.push(ValidationError::SpareBitsSet(spare_flags, spare));
+        }
+
+        let sequence_number = self.header.sequence_number();
+        let command_sequence_number = sequence_number & sequence::COMMAND_FLAG != 0;
+
+        match message_type {
+            messages::MessageType::ModifyBearerCommand |
+            messages::MessageType::ModifyBearerFailureIndication |
+            messages::MessageType::BearerResourceCommand |
+            messages::MessageType::BearerResourceFailureIndication if !command_sequence_number => {
+                errors.push(ValidationError::CommandSequenceNumberExpected(message_type, sequence_number));
+            },
+            messages::MessageType::EchoRequest |
+            messages::MessageType::CreateSessionRequest |
+            messages::MessageType::DownlinkDataNotification |
+            messages::MessageType::ContextRequest |
+            messages::MessageType::SuspendNotification |
+            messages::MessageType::ResumeNotification |
+            messages::MessageType::ReleaseAccessBearersRequest |
+            messages::MessageType::ModifyAccessBearersRequest if command_sequence_number => {
+                errors.push(ValidationError::RequestSequenceNumberExpected(message_type, sequence_number));
+            },
+            _ => { /* Triggered messages copy the Sequence Number of the message that triggered them so can be either */ }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        }
+        else {
+            Err(errors)
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        Self::parse_with_mode(buffer, header::ParseMode::Lenient)
+    }
+
+    pub fn parse_with_mode(buffer: &[u8], mode: header::ParseMode) -> Result<(Self, usize), ParseError> {
+        /* Lenient parses IEs up to the end of buffer. Strict only parses the octets counted by the header length
+        and rejects spare bits that are set, see header::ParseMode */
+        let h = header::Header::parse_with_mode(&buffer, mode);
+
+        if let Some((mut h, h_pos)) = h {
+            let end = match mode {
+                // parse_with_mode has checked that the length fits in buffer
+                header::ParseMode::Strict => NetworkEndian::read_u16(&buffer[header::LENGTH]) as usize + 4,
+                header::ParseMode::Lenient => buffer.len(),
+            };
+
+            let (m, m_pos) = match messages::Message::parse(h.message_type(), &buffer[h_pos..end]) {
+                Ok(m) => m,
+                Err(e) => {
+                    debug!("Failed to parse {} (sequence number {}): {:?}", h.message_type(), h.sequence_number(), e);
+                    return Err(e)
+                }
+            };
+
+            h.set_payload_length(m.length());
+            Ok(
+                (
+                    Packet {
+                        header: h,
+                        message: m
+                    },
+                    h_pos + m_pos
+                )
+            )
+        }
+        else {
+            debug!("Failed to parse GTPv2 header of {} byte packet", buffer.len());
+            Err(ParseError::InvalidHeader)
+        }
+    }
+}
+
+impl Dissect for Packet {
+    fn dissect(&self, d: &mut Dissector) {
+        self.header.dissect_with_payload_length(self.message.length(), d);
+        self.message.dissect(d);
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", dissect::dissect(self))
+    }
 }
 
 #[cfg(test)]
+// Most of the imports are only used by test_send which needs a socket
+#[cfg_attr(not(feature = "std"), allow(unused_imports))]
 mod tests {
     use super::*;
 
@@ -79,7 +375,7 @@ mod tests {
 
     use ascii::AsciiString;
     use std::net::{Ipv6Addr, Ipv4Addr};
-    use messages::{MessageType, echo_response, echo_request, create_session_request, create_session_response};
+    use messages::{MessageType, echo_response, echo_request, create_session_request, create_session_response, modify_bearer_failure_indication, bearer_resource_command};
 
     use messages::information_elements::{
         InformationElementType,
@@ -102,6 +398,8 @@ mod tests {
         ue_time_zone,
         charging_characteristics,
         cause,
+        pti,
+        tad,
     };
 
     use crate::gtp_v2::packet::messages::information_elements::user_location_information::{self, PLMN, CGI, SAI, RAI, TAI, ECGI, LAI, MeNBID, EMeNBID};
@@ -118,13 +416,13 @@ mod tests {
             )
         );
 
-        assert_eq!(p.header.message_type() as u8, MessageType::EchoRequest as u8);
+        assert_eq!(p.header.message_type(), MessageType::EchoRequest);
 
-        let pos = p.generate(&mut buffer);
+        let pos = p.generate(&mut buffer).unwrap();
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0, 9,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00,
@@ -145,13 +443,13 @@ mod tests {
             )
         );
         
-        assert_eq!(p.header.message_type() as u8, MessageType::EchoResponse as u8);
+        assert_eq!(p.header.message_type(), MessageType::EchoResponse);
 
-        let pos = p.generate(&mut buffer);
+        let pos = p.generate(&mut buffer).unwrap();
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoResponse as u8,
+            /* Message Type */ u8::from(MessageType::EchoResponse),
             /* Length */ 0, 9,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00,
@@ -164,6 +462,217 @@ mod tests {
     }
 
     #[test]
+    fn test_to_vec() {
+        let mut p = Packet::new(
+            Message::EchoRequest(
+                echo_request::Message::new(
+                    recovery::InformationElement::new(0xAB,0).unwrap()
+                )
+            )
+        );
+
+        p.header.enable_teid();
+        p.header.set_teid(0x12345678);
+
+        assert_eq!(p.to_vec().unwrap(), [
+            /* Flags */ 0b0100_1000, 
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0, 13,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x00, 0x00, 0x00, 
+            /* Spare */ 0x00,
+            InformationElementType::Recovery as u8,
+            0, 1, 
+            0, 
+            0xAB
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate_into() {
+        let mut buffer = vec![0xFF, 0xFF];
+
+        let mut p = Packet::new(
+            Message::EchoResponse(
+                echo_response::Message::new(
+                    recovery::InformationElement::new(0xCD,0).unwrap()
+                )
+            )
+        );
+
+        let pos = p.generate_into(&mut buffer).unwrap();
+
+        assert_eq!(pos, 13);
+        assert_eq!(buffer, [
+            0xFF, 0xFF, // Existing contents are kept
+            /* Flags */ 0b0100_0000, 
+            /* Message Type */ u8::from(MessageType::EchoResponse),
+            /* Length */ 0, 9,
+            /* Sequence Number */ 0x00, 0x00, 0x00, 
+            /* Spare */ 0x00,
+            InformationElementType::Recovery as u8,
+            0, 1, 
+            0,
+            0xCD
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wire_size() {
+        let mut p = Packet::new(
+            Message::EchoRequest(
+                echo_request::Message::new(
+                    recovery::InformationElement::new(0xAB,0).unwrap()
+                )
+            )
+        );
+
+        assert_eq!(p.wire_size(), 13);
+        assert_eq!(p.wire_size(), p.to_vec().unwrap().len());
+
+        p.header.enable_teid();
+        assert_eq!(p.wire_size(), 17);
+        assert_eq!(p.wire_size(), p.to_vec().unwrap().len());
+
+        assert!(p.fits(17));
+        assert!(!p.fits(16));
+
+        let mut csr = new_create_session_request();
+        assert_eq!(csr.wire_size(), csr.to_vec().unwrap().len());
+
+        assert_eq!(csr.wire_size_with_piggyback(&p), csr.wire_size() + 17);
+    }
+
+    #[test]
+    fn test_teid_flag() {
+        // Echo never carries a TEID
+        let p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())));
+        assert!(!p.header.teid_enabled());
+
+        // Other messages always carry a TEID
+        let p = new_create_session_request();
+        assert!(p.header.teid_enabled());
+    }
+
+    #[test]
+    fn test_generate_teid_required() {
+        let mut p = new_create_session_request();
+
+        p.header.disable_teid();
+
+        let mut buffer = [0; MTU];
+        assert!(p.generate(&mut buffer).is_err());
+
+        // Nothing is appended if the packet can't be generated
+        let mut buffer = vec![0xFF, 0xFF];
+        assert!(p.generate_into(&mut buffer).is_err());
+        assert_eq!(buffer, [0xFF, 0xFF]);
+
+        assert!(p.to_vec().is_err());
+
+        p.header.enable_teid();
+
+        let mut buffer = [0; MTU];
+        assert!(p.generate(&mut buffer).is_ok());
+    }
+
+    #[test]
+    fn test_new_request_and_response() {
+        let mut sequence = SequenceGenerator::starting_at(0x10).unwrap();
+
+        let request = Packet::new_request(
+            Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())),
+            &mut sequence
+        );
+        assert_eq!(request.header.sequence_number(), 0x10);
+
+        let response = Packet::new_response(
+            &request,
+            Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(0xCD, 0).unwrap()))
+        );
+        assert_eq!(response.header.sequence_number(), 0x10);
+
+        // Each request gets the next Sequence Number
+        let request = Packet::new_request(
+            Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())),
+            &mut sequence
+        );
+        assert_eq!(request.header.sequence_number(), 0x11);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut p = new_create_session_request();
+        p.header.set_sequence_number(0x123456).unwrap();
+        assert_eq!(p.validate(), Ok(()));
+
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())));
+        assert_eq!(p.validate(), Ok(()));
+
+        p.header.enable_teid();
+        p.header.set_sequence_number(0x800001).unwrap();
+        assert_eq!(p.validate(), Err(vec![
+            ValidationError::TeidNotAllowed(MessageType::EchoRequest),
+            ValidationError::RequestSequenceNumberExpected(MessageType::EchoRequest, 0x800001),
+        ]));
+
+        let mut p = new_create_session_request();
+        p.header.set_sequence_number(0x123456).unwrap();
+        p.header.disable_teid();
+        assert_eq!(p.validate(), Err(vec![ValidationError::TeidMissing(MessageType::CreateSessionRequest)]));
+    }
+
+    #[test]
+    fn test_validate_spare_bits() {
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())));
+
+        let mut bytes = p.to_vec().unwrap();
+        bytes[0] = bytes[0] | 0b01;
+        bytes[7] = 0x05;
+
+        assert!(Packet::parse_with_mode(&bytes, header::ParseMode::Strict).is_err());
+
+        // Lenient parsing keeps the spare bits so validate reports them
+        if let Ok((mut p, _)) = Packet::parse_with_mode(&bytes, header::ParseMode::Lenient) {
+            assert_eq!(p.validate(), Err(vec![ValidationError::SpareBitsSet(0b01, 0x05)]));
+
+            p.header.clear_spare_bits();
+            assert_eq!(p.validate(), Ok(()));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_validate_command_sequence_number() {
+        let failure_indication = || {
+            Message::ModifyBearerFailureIndication(
+                modify_bearer_failure_indication::Message::new(
+                    cause::InformationElement::new(cause::CauseCode::ContextNotFound, cause::CauseSource::LocalNode, false, false, None, 0).unwrap()
+                )
+            )
+        };
+
+        let mut p = Packet::new(failure_indication());
+        p.header.set_sequence_number(0x000001).unwrap();
+        assert_eq!(p.validate(), Err(vec![
+            ValidationError::CommandSequenceNumberExpected(MessageType::ModifyBearerFailureIndication, 0x000001)
+        ]));
+
+        // A Sequence Number from the command space is accepted
+        let mut sequence = SequenceGenerator::new();
+        sequence.next_command();
+
+        let mut p = Packet::new(failure_indication());
+        p.header.set_sequence_number(sequence.next_command()).unwrap();
+        assert_eq!(p.validate(), Ok(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn test_send() {
         let socket = UdpSocket::bind("0.0.0.0:0").expect("couldn't bind to address");
 
@@ -211,10 +720,8 @@ mod tests {
                             let mut bc = bearer_context::InformationElement::new(
                                 ebi::InformationElement::new(7, 0).unwrap(), 
                                 bearer_qos::InformationElement::new(
-                                    false,
-                                    9,
-                                    true,
-                                    7,
+                                    bearer_qos::Arp::new(false, 9, true).unwrap(),
+                                    bearer_qos::Qci::Qci7,
                                     1_000_000, 1_000_000,
                                     0, 0,
                                     0
@@ -238,38 +745,38 @@ mod tests {
                     mc.uli = Some(
                         user_location_information::InformationElement::new(
                             Some(CGI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1234,
                                 0x4321,
                             )),
                             Some(SAI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1234,
                                 0x4321,
                             )),
                             Some(RAI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1234,
                                 0x4321,
                             )),
                             Some(TAI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1234,
                             )),
                             Some(ECGI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0xF_FF_FF_FF
                             ).unwrap()),
                             Some(LAI::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1234,
                             )),
                             Some(MeNBID::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0xFFFFF,
                             ).unwrap()),
                             Some(EMeNBID::new(
-                                PLMN::new([5,0,5], [0,9,9]),
+                                PLMN::new(505, 99, true).unwrap(),
                                 0x1FFFFF,
                             ).unwrap()),
                             0
@@ -278,7 +785,7 @@ mod tests {
 
                     mc.mei = Some(mei::InformationElement::new([1,2,3,4,5,6,7,8,9,1,2,3,4,5,6], Some(1), 0).unwrap());
 
-                    mc.serving_network = Some(serving_network::InformationElement::new(PLMN::new([5,0,5], [0,9,9]), 0).unwrap());
+                    mc.serving_network = Some(serving_network::InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap());
 
                     mc.selection_mode = Some(selection_mode::InformationElement::new(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified, 0).unwrap());
 
@@ -286,8 +793,7 @@ mod tests {
 
                     mc.apn_ambr = Some(ambr::InformationElement::new(0x12345678, 0x87654321, 0).unwrap());
 
-                    mc.ue_time_zone = Some(ue_time_zone::InformationElement::new(0x40, ue_time_zone::DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap()); // This is +10
-                    // mc.ue_time_zone = Some(ue_time_zone::InformationElement::new(0b1100_0000, ue_time_zone::DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap()); // This is -10
+                    mc.ue_time_zone = Some(ue_time_zone::InformationElement::from_minutes(600, ue_time_zone::DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap());
 
                     mc.charging_characteristics = Some(charging_characteristics::InformationElement::new(0x1234, 0).unwrap());
 
@@ -313,10 +819,8 @@ mod tests {
                         bearer_context::InformationElement::new(
                             ebi::InformationElement::new(7,0).unwrap(),
                             bearer_qos::InformationElement::new(
-                                false,
-                                9,
-                                true,
-                                7,
+                                bearer_qos::Arp::new(false, 9, true).unwrap(),
+                                bearer_qos::Qci::Qci7,
                                 10_000_000,
                                 10_000_000,
                                 0,
@@ -349,4 +853,293 @@ mod tests {
 
         p.send_to(&socket, "192.168.1.1:2123").expect("Couldn't send data.");
     }
-}
\ No newline at end of file
+
+    fn new_create_session_request() -> Packet {
+        let mut p = Packet::new(
+            Message::CreateSessionRequest(
+                create_session_request::Message::new(
+                    rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+                    f_teid::InformationElement::new(
+                        f_teid::InterfaceType::S11MmeGtpC,
+                        0x12345678,
+                        Some(Ipv4Addr::new(10,0,0,1)),
+                        None,
+                        0,
+                    ).unwrap(),
+                    bearer_context::InformationElement::new(
+                        ebi::InformationElement::new(5, 0).unwrap(),
+                        bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                        0
+                    ).unwrap(),
+                    apn::InformationElement::new(AsciiString::from_ascii("internet").unwrap(), 0).unwrap()
+                )
+            )
+        );
+
+        p.header.enable_teid();
+        p.header.set_sequence_number(0xABCDEF).unwrap();
+
+        p
+    }
+
+    #[test]
+    fn test_reject_with() {
+        let request = new_create_session_request();
+
+        if let Some(p) = Packet::reject_with(&request, cause::CauseCode::NoResourcesAvailable) {
+            assert_eq!(p.header.message_type(), MessageType::CreateSessionResponse);
+            assert_eq!(p.header.sequence_number(), 0xABCDEF);
+            assert_eq!(p.header.teid(), 0x12345678);
+
+            if let Message::CreateSessionResponse(m) = p.message {
+                assert_eq!(m.cause.cause_code, cause::CauseCode::NoResourcesAvailable);
+                assert_eq!(m.cause.cause_source, cause::CauseSource::LocalNode);
+                assert!(m.bearer_contexts_created.is_empty());
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // An acceptance can't be used to reject a request
+        assert!(Packet::reject_with(&request, cause::CauseCode::RequestAccepted).is_none());
+
+        // Echo Response can't carry a Cause
+        let request = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())));
+        assert!(Packet::reject_with(&request, cause::CauseCode::NoResourcesAvailable).is_none());
+    }
+
+    fn new_bearer_resource_command() -> Packet {
+        let mut p = Packet::new(
+            Message::BearerResourceCommand(
+                bearer_resource_command::Message::new(
+                    ebi::InformationElement::new(5, 0).unwrap(),
+                    pti::InformationElement::new(0x23, 0).unwrap(),
+                    tad::InformationElement::new(crate::tft::TrafficFlowTemplate::delete(alloc::vec![1]).unwrap(), 0).unwrap(),
+                )
+            )
+        );
+
+        p.header.set_sequence_number(0x800010).unwrap();
+
+        p
+    }
+
+    #[test]
+    fn test_reject_with_bearer_resource_command() {
+        let command = new_bearer_resource_command();
+
+        if let Some(p) = Packet::reject_with(&command, cause::CauseCode::ServiceDenied) {
+            assert_eq!(p.header.sequence_number(), 0x800010);
+            assert!(p.is_triggered_by(&command));
+
+            if let Message::BearerResourceFailureIndication(m) = p.message {
+                assert_eq!(m.cause.cause_code, cause::CauseCode::ServiceDenied);
+                assert_eq!(m.linked_eps_bearer_id.eps_bearer_id, 5);
+                assert_eq!(m.procedure_transaction_id.procedure_transaction_id, 0x23);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Packet::reject_with(&command, cause::CauseCode::RequestAccepted).is_none());
+    }
+
+    #[test]
+    fn test_is_triggered_by() {
+        let command = new_bearer_resource_command();
+
+        let mut p = Packet::reject_with(&command, cause::CauseCode::ServiceDenied).unwrap();
+        assert!(p.is_triggered_by(&command));
+
+        // A different procedure
+        if let Message::BearerResourceFailureIndication(ref mut m) = p.message {
+            m.procedure_transaction_id.procedure_transaction_id = 0x24;
+        }
+        assert!(!p.is_triggered_by(&command));
+
+        // A different Sequence Number
+        let mut p = Packet::reject_with(&command, cause::CauseCode::ServiceDenied).unwrap();
+        p.header.set_sequence_number(0x800011).unwrap();
+        assert!(!p.is_triggered_by(&command));
+
+        // Messages without a PTI are matched on the Sequence Number
+        let mut p = Packet::new(Message::ModifyBearerFailureIndication(
+            modify_bearer_failure_indication::Message::reject_with_cause(
+                cause::InformationElement::new(cause::CauseCode::ServiceDenied, cause::CauseSource::LocalNode, false, false, None, 0).unwrap()
+            ).unwrap()
+        ));
+        p.header.set_sequence_number(0x800010).unwrap();
+        assert!(p.is_triggered_by(&command));
+
+        // Only commands trigger messages
+        assert!(!command.is_triggered_by(&command));
+        assert!(!p.is_triggered_by(&new_create_session_request()));
+    }
+
+    #[test]
+    fn test_parse_unsupported_message_type() {
+        // Create Bearer Request (95) with no IEs
+        let buffer = [0b0100_1000, 95, 0, 8, 0x12, 0x34, 0x56, 0x78, 0xAB, 0xCD, 0xEF, 0];
+
+        assert_eq!(Packet::parse(&buffer), Err(ParseError::UnsupportedMessageType(MessageType::CreateBearerRequest)));
+        assert_eq!(Packet::reject_parse_error(&buffer, &ParseError::UnsupportedMessageType(MessageType::CreateBearerRequest)), None);
+
+        // A reserved message type is reported rather than treated as an invalid header
+        let mut buffer = buffer;
+        buffer[1] = 42;
+
+        assert_eq!(Packet::parse(&buffer), Err(ParseError::UnsupportedMessageType(MessageType::Unknown(42))));
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(0xAB, 0).unwrap())));
+
+        let bytes = p.to_vec().unwrap();
+
+        assert_eq!(Packet::parse_with_mode(&bytes, header::ParseMode::Strict), Ok((p.clone(), bytes.len())));
+
+        // Trailing octets that aren't counted by the header length
+        let mut trailing = bytes.clone();
+        trailing.extend_from_slice(&[InformationElementType::Recovery as u8, 0, 1, 0, 0xCD]);
+
+        if let Ok((parsed, pos)) = Packet::parse_with_mode(&trailing, header::ParseMode::Strict) {
+            assert_eq!(pos, bytes.len());
+            assert_eq!(parsed.message, p.message);
+        }
+        else {
+            assert!(false);
+        }
+
+        // The header length is longer than the packet
+        let mut long = bytes.clone();
+        long[3] = long[3] + 1;
+        assert_eq!(Packet::parse_with_mode(&long, header::ParseMode::Strict), Err(ParseError::InvalidHeader));
+        assert!(Packet::parse_with_mode(&long, header::ParseMode::Lenient).is_ok());
+
+        // Spare bits are kept by lenient so a relay sends them on unchanged
+        let mut spare = bytes.clone();
+        spare[0] = spare[0] | 0b01;
+        assert_eq!(Packet::parse_with_mode(&spare, header::ParseMode::Strict), Err(ParseError::InvalidHeader));
+
+        if let Ok((mut parsed, _pos)) = Packet::parse(&spare) {
+            assert_eq!(parsed.to_vec().unwrap(), spare);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_reject_parse_error() {
+        let mut buffer = [0; MTU];
+
+        let mut request = new_create_session_request();
+
+        let pos = request.generate(&mut buffer).unwrap();
+
+        // Change the type of the APN IE to an unknown IE so that it is missing from the request
+        let apn_pos = buffer[..pos].windows(9).position(|w| w == b"\x08internet").unwrap() - 4;
+        assert_eq!(buffer[apn_pos], InformationElementType::APN as u8);
+        buffer[apn_pos] = 0xFE;
+
+        if let Err(e) = Packet::parse(&buffer[..pos]) {
+            assert_eq!(e, ParseError::MandatoryIEMissing(vec![(InformationElementType::APN, 0)]));
+
+            if let Some(mut p) = Packet::reject_parse_error(&buffer[..pos], &e) {
+                assert_eq!(p.header.sequence_number(), 0xABCDEF);
+                assert_eq!(p.header.teid(), 0x12345678);
+
+                // Check that the response survives a round trip
+                let mut response = [0; MTU];
+                let response_pos = p.generate(&mut response).unwrap();
+
+                if let Ok((p, _pos)) = Packet::parse(&response[..response_pos]) {
+                    if let Message::CreateSessionResponse(m) = p.message {
+                        assert_eq!(m.cause.cause_code, cause::CauseCode::MandatoryIEMissing);
+                        assert_eq!(m.cause.cause_source, cause::CauseSource::RemoteNode);
+                        assert_eq!(m.cause.offending_ie, Some((InformationElementType::APN, 0)));
+                    }
+                    else {
+                        assert!(false);
+                    }
+                }
+                else {
+                    assert!(false);
+                }
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_dissect() {
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(5, 0).unwrap())));
+
+        p.header.set_sequence_number(0x1234).unwrap();
+
+        let dissection = concat!(
+            "GTPv2 Header\n",
+            "    Version: 2\n",
+            "    Piggybacking Flag (P): False\n",
+            "    TEID Flag (T): False\n",
+            "    Message Priority Flag (MP): False\n",
+            "    Message Type: EchoRequest (1)\n",
+            "    Message Length: 9\n",
+            "    Sequence Number: 0x001234\n",
+            "EchoRequest (1), Length: 5\n",
+            "    Recovery (3), Length: 1, Instance: 0\n",
+            "        Restart Counter: 5\n",
+        );
+
+        // The header length is correct before the packet is generated
+        assert_eq!(p.dissect(), dissection);
+        assert_eq!(format!("{}", p), dissection);
+
+        let mut buffer = [0; MTU];
+        let pos = p.generate(&mut buffer).unwrap();
+
+        if let Ok((p, _pos)) = Packet::parse(&buffer[..pos]) {
+            assert_eq!(p.dissect(), dissection);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_verify_round_trip() {
+        let p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(5, 0).unwrap())));
+
+        assert_eq!(p.verify_round_trip(), Ok(()));
+
+        // The Modify Bearer Request only has a Recovery with instance 0 so this one is dropped when parsing
+        let mut m = messages::modify_bearer_request::Message::new();
+        m.recovery = Some(recovery::InformationElement::new(5, 1).unwrap());
+
+        let mut p = Packet::new(Message::ModifyBearerRequest(m));
+        p.header.set_teid(0x12345678);
+
+        if let Err(differences) = p.verify_round_trip() {
+            assert!(differences.contains(&"-     Recovery (3), Length: 1, Instance: 1".to_string()));
+            assert!(differences.contains(&"-         Restart Counter: 5".to_string()));
+            assert!(differences.iter().all(|line| !line.starts_with("+ ") || line.contains("Length")));
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,10 +1,14 @@
-use std::convert::TryFrom;
-
 use crate::field::*;
 use byteorder::{ByteOrder, NetworkEndian};
 
 use super::messages::MessageType;
 
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+
 /*                                  
                                         Bits
             |---------------------------------------------------------------| 
@@ -28,6 +32,19 @@ use super::messages::MessageType;
 pub const LENGTH: Field = 2..4;
 pub const TEID: Field = 4..8;
 
+// The spare bits of octet 1
+const SPARE_FLAGS: u8 = 0b11;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseMode {
+    /* Strict rejects a header with spare bits set or a length that doesn't match the buffer. Lenient accepts
+    both and keeps the spare bits so a relay regenerates the header as it was received */
+    Strict,
+    Lenient,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Header {
     // MANDATORY FIELDS
     version: u8, // Always present, always set to 1
@@ -47,7 +64,11 @@ pub struct Header {
 
     // OPTIONAL FIELDS
     teid: u32, /* Tunnel Endpoint Identifier (TEID) */
-    message_priority: u8
+    message_priority: u8,
+
+    // SPARE BITS. Zero unless the header was parsed leniently
+    spare_flags: u8, // Bits 2 and 1 of octet 1
+    spare: u8, // Bits 4 to 1 of octet 12. Bits 8 to 5 as well if the MP flag isn't set
 }
 
 impl Header {
@@ -62,6 +83,8 @@ impl Header {
             teid: 0x00000000,
             sequence_number: 0,
             message_priority: 0,
+            spare_flags: 0,
+            spare: 0,
         }
     }
 
@@ -69,7 +92,15 @@ impl Header {
         self.message_type
     }
 
-    fn length(&self) -> u16 {
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn piggyback(&self) -> bool {
+        self.p == 1
+    }
+
+    pub fn length(&self) -> u16 {
         /* Length of Payload in octets. i.e. the rest of the packet following the 
         mandatory part of the GTP header (that is the first 4 octets). The Sequence Number 
         and the TEID shall be considered to be part of the payload */
@@ -92,6 +123,14 @@ impl Header {
         self.t = 1;
     }
 
+    pub fn disable_teid(&mut self) {
+        self.t = 0;
+    }
+
+    pub fn teid_enabled(&self) -> bool {
+        self.t == 1
+    }
+
     pub fn set_teid(&mut self, teid: u32) {
         self.teid = teid;
     }
@@ -101,7 +140,7 @@ impl Header {
     }
 
     pub fn set_sequence_number(&mut self, sequence_number: u32) -> Result<u32,String> {
-        if sequence_number > 0xFFFFFF {
+        if sequence_number > uint::MAX_U24 {
             // Sequence number can only be 3 octets
             return Err(format!("Sequence number ({}) too large.", sequence_number));
         }
@@ -134,12 +173,37 @@ impl Header {
         self.message_priority
     }
 
+    pub fn spare_bits(&self) -> (u8, u8) {
+        // (Spare bits of octet 1, spare bits of octet 12)
+        (self.spare_flags, self.spare)
+    }
+
+    pub fn has_spare_bits(&self) -> bool {
+        self.spare_flags != 0 || self.spare != 0
+    }
+
+    pub fn clear_spare_bits(&mut self) {
+        self.spare_flags = 0;
+        self.spare = 0;
+    }
+
+    pub fn header_length(&self) -> usize {
+        // Octets of the header on the wire. 4 mandatory octets, the TEID if present, the Sequence Number and a spare
+        if self.t == 1 { 12 } else { 8 }
+    }
+
     pub fn set_payload_length(&mut self, payload_length: u16) {
         self.payload_length = payload_length;
     }
 
+    pub fn generate_length(&self, buffer: &mut[u8]) {
+        // Rewrites only the length field of a header that has already been generated into buffer.
+        // This lets the length be filled in after the payload has been generated.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length());
+    }
+
     fn generate_flags(&self) -> u8 {
-        ((self.version & 0b111) << 5) | ((self.p & 0x1) << 4) | ((self.t & 0x1) << 3) | ((self.mp & 0x1) << 2)
+        ((self.version & 0b111) << 5) | ((self.p & 0x1) << 4) | ((self.t & 0x1) << 3) | ((self.mp & 0x1) << 2) | (self.spare_flags & SPARE_FLAGS)
     }
 
     fn parse_flags(flags: u8) -> (u8, u8, u8, u8) {
@@ -157,7 +221,7 @@ impl Header {
         buffer[0] = self.generate_flags();
         pos = pos + 1;
 
-        buffer[1] = self.message_type as u8;
+        buffer[1] = u8::from(self.message_type);
         pos = pos + 1;
 
         NetworkEndian::write_u16(&mut buffer[LENGTH],self.length());
@@ -168,66 +232,179 @@ impl Header {
             pos = pos + 4;
         }
 
-        NetworkEndian::write_uint(&mut buffer[pos..], self.sequence_number as u64, 3);
+        uint::generate_u24_lenient(self.sequence_number, &mut buffer[pos..]);
         pos = pos + 3;
 
+        // The whole octet is written so nothing is left over from whatever was in buffer
         if self.mp == 1 {
-            buffer[pos] = self.message_priority << 4;
+            buffer[pos] = (self.message_priority << 4) | (self.spare & 0xF);
+        }
+        else {
+            buffer[pos] = self.spare;
         }
 
         pos = pos + 1; // This is for the MP/spare octet
 
         pos
     }
-    
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        Self::parse_with_mode(buffer, ParseMode::Lenient)
+    }
+
+    pub fn parse_with_mode(buffer: &[u8], mode: ParseMode) -> Option<(Self, usize)> {
+        if buffer.len() < 8 {
+            return None
+        }
+
         let mut pos = 0;
 
         let (version, p, t, mp) = Self::parse_flags(buffer[0]);
         pos = pos + 1;
 
+        if t == 1 && buffer.len() < 12 {
+            // The header is too short to contain the TEID
+            return None
+        }
+
         if version != 2 {
             // The packet isn't a GTPv2 packet
             return None;
         }
 
-        if let Ok(message_type) = MessageType::try_from(buffer[1]) {
-            pos = pos + 1;
+        // Unknown message types are parsed so the message can be reported and skipped
+        let message_type = MessageType::from(buffer[1]);
+        pos = pos + 1;
 
-            let mut h = Self::new(message_type);
+        let mut h = Self::new(message_type);
 
-            let _length = NetworkEndian::read_u16(&buffer[LENGTH]);
-            pos = pos + 2;
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize;
+        pos = pos + 2;
 
-            if t == 1 {
-                h.set_teid(NetworkEndian::read_u32(&buffer[pos..]));
-                h.enable_teid();
-                pos = pos + 4;
-            }
+        // The length counts everything after the first 4 octets so it must at least cover the rest of the header
+        let header_length = if t == 1 { 12 } else { 8 };
 
-            if p == 1 {
-                // We don't support piggyback
-                return None;
-            }
+        if mode == ParseMode::Strict && (length + 4 < header_length || length + 4 > buffer.len()) {
+            return None;
+        }
 
-            h.set_sequence_number(NetworkEndian::read_uint(&buffer[pos..], 3) as u32).unwrap();
-            pos = pos + 3;
+        h.spare_flags = buffer[0] & SPARE_FLAGS;
 
-            if mp == 1 {
-                h.set_message_priority((buffer[pos] >> 4) & 0xF).unwrap();
-                h.enable_message_priority();
-            }
+        if t == 1 {
+            h.set_teid(NetworkEndian::read_u32(&buffer[pos..]));
+            h.enable_teid();
+            pos = pos + 4;
+        }
 
-            pos = pos + 1; // This is for the MP/spare octet
-    
-            Some((h, pos))
+        if p == 1 {
+            // We don't support piggyback
+            return None;
+        }
+
+        h.set_sequence_number(uint::parse_u24(&buffer[pos..])?).unwrap();
+        pos = pos + 3;
+
+        if mp == 1 {
+            h.set_message_priority((buffer[pos] >> 4) & 0xF).unwrap();
+            h.enable_message_priority();
+            h.spare = buffer[pos] & 0xF;
         }
         else {
-            None
+            h.spare = buffer[pos];
         }
 
-        
+        pos = pos + 1; // This is for the MP/spare octet
+
+        if mode == ParseMode::Strict && h.has_spare_bits() {
+            return None;
+        }
+
+        Some((h, pos))
     }
+
+    pub fn dissect_with_payload_length(&self, payload_length: u16, d: &mut Dissector) {
+        // The payload length is only updated when the packet is generated or parsed so the caller supplies it
+        let length = self.length() - self.payload_length + payload_length;
+
+        d.tree("GTPv2 Header", |d| {
+            d.field("Version", self.version);
+            d.flag("Piggybacking Flag (P)", self.p == 1);
+            d.flag("TEID Flag (T)", self.t == 1);
+            d.flag("Message Priority Flag (MP)", self.mp == 1);
+            d.field("Message Type", format!("{:?} ({})", self.message_type, u8::from(self.message_type)));
+            d.field("Message Length", length);
+
+            if self.t == 1 {
+                d.field("TEID", format!("{:#010x}", self.teid));
+            }
+
+            d.field("Sequence Number", format!("{:#08x}", self.sequence_number));
+
+            if self.mp == 1 {
+                d.field("Message Priority", self.message_priority);
+            }
+
+            if self.has_spare_bits() {
+                d.field("Spare Bits", format!("{:#04b} {:#010b}", self.spare_flags, self.spare));
+            }
+        });
+    }
+}
+
+impl Dissect for Header {
+    fn dissect(&self, d: &mut Dissector) {
+        self.dissect_with_payload_length(self.payload_length, d);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeaderSummary {
+    pub version: u8,
+    pub message_type: u8, // Not checked against MessageType so unknown messages can still be forwarded
+    pub teid: Option<u32>, // Only present if the T flag is set
+    pub payload_offset: usize, // Offset of the first IE from the start of the buffer
+    pub total_length: usize, // Length of the whole message including the first 4 octets of the header
+}
+
+pub fn peek(buffer: &[u8]) -> Option<HeaderSummary> {
+    /* Reads only what a forwarder needs from the header. Unlike parse a piggybacked message isn't rejected.
+    It starts at total_length and can be peeked separately. */
+
+    if buffer.len() < 8 {
+        return None
+    }
+
+    let version = (buffer[0] >> 5) & 0b111;
+
+    if version != 2 {
+        return None
+    }
+
+    let (teid, payload_offset) = if (buffer[0] >> 3) & 0b1 == 1 {
+        if buffer.len() < 12 {
+            return None
+        }
+        (Some(NetworkEndian::read_u32(&buffer[TEID])), 12)
+    }
+    else {
+        (None, 8)
+    };
+
+    let total_length = NetworkEndian::read_u16(&buffer[LENGTH]) as usize + 4;
+
+    if total_length < payload_offset || buffer.len() < total_length {
+        return None
+    }
+
+    Some(
+        HeaderSummary {
+            version,
+            message_type: buffer[1],
+            teid,
+            payload_offset,
+            total_length,
+        }
+    )
 }
 
 #[cfg(test)]
@@ -241,10 +418,10 @@ mod tests {
     #[test]
     fn test_message_type() {
         let h = Header::new(MessageType::EchoRequest);
-        assert_eq!(h.message_type() as u8, MessageType::EchoRequest as u8);
+        assert_eq!(h.message_type(), MessageType::EchoRequest);
 
         let h = Header::new(MessageType::EchoResponse);
-        assert_eq!(h.message_type() as u8, MessageType::EchoResponse as u8);
+        assert_eq!(h.message_type(), MessageType::EchoResponse);
     }
 
     #[test]
@@ -257,7 +434,7 @@ mod tests {
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x00, 0x04,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00
@@ -272,7 +449,7 @@ mod tests {
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoResponse as u8,
+            /* Message Type */ u8::from(MessageType::EchoResponse),
             /* Length */ 0x00, 0x04,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00
@@ -296,7 +473,7 @@ mod tests {
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x00, 0x04,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00
@@ -309,7 +486,7 @@ mod tests {
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_1000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x00, 0x08,
             /* TEID */ 0x12, 0x34, 0x56, 0x78,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
@@ -346,7 +523,7 @@ mod tests {
 
             assert_eq!(buffer[..pos], [
                 /* Flags */ 0b0100_0000, 
-                /* Message Type */ MessageType::EchoRequest as u8,
+                /* Message Type */ u8::from(MessageType::EchoRequest),
                 /* Length */ 0x00, 0x04,
                 /* Sequence Number */ 0x12, 0x34, 0x56, 
                 /* Spare */ 0x00
@@ -355,6 +532,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generate_length() {
+        let mut buffer = [0; MTU];
+
+        let mut h = Header::new(MessageType::EchoRequest);
+
+        let pos = h.generate(&mut buffer);
+
+        h.set_payload_length(0x1230);
+        h.generate_length(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0100_0000, 
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x12, 0x34,
+            /* Sequence Number */ 0x00, 0x00, 0x00, 
+            /* Spare */ 0x00
+            ]);
+    }
+
     #[test]
     fn test_length() {
         let mut buffer = [0; MTU];
@@ -364,12 +561,13 @@ mod tests {
         h.set_payload_length(0x1230);
 
         assert_eq!(h.length(), 0x1230+4);
+        assert_eq!(h.header_length(), 8);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_0000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x12, 0x34,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
             /* Spare */ 0x00
@@ -379,12 +577,13 @@ mod tests {
         h.enable_teid();
 
         assert_eq!(h.length(), 0x1230+4+4);
+        assert_eq!(h.header_length(), 12);
 
         let pos = h.generate(&mut buffer);
 
         assert_eq!(buffer[..pos], [
             /* Flags */ 0b0100_1000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x12, 0x38,
             /* TEID */ 0x12, 0x34, 0x56, 0x78,
             /* Sequence Number */ 0x00, 0x00, 0x00, 
@@ -396,7 +595,7 @@ mod tests {
     fn test_message_parse() {
         let header_bytes = [
             /* Flags */ 0b0100_1000, 
-            /* Message Type */ MessageType::EchoRequest as u8,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
             /* Length */ 0x00, 0x08,
             /* TEID */ 0x12, 0x34, 0x56, 0x78,
             /* Sequence Number */ 0x87, 0x65, 0x43, 
@@ -406,7 +605,167 @@ mod tests {
         if let Some((h, _pos)) = Header::parse(&header_bytes) {
             assert_eq!(h.teid(), 0x12345678);
             assert_eq!(h.sequence_number(), 0x876543);
-            assert_eq!(h.message_type() as u8, MessageType::EchoRequest as u8);
+            assert_eq!(h.message_type(), MessageType::EchoRequest);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_truncated() {
+        let header_bytes = [
+            /* Flags */ 0b0100_1000,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x87, 0x65, 0x43,
+            /* Spare */ 0x00
+            ];
+
+        assert!(Header::parse(&header_bytes).is_some());
+
+        for length in 0..header_bytes.len() {
+            assert!(Header::parse(&header_bytes[..length]).is_none());
+        }
+    }
+
+    #[test]
+    fn test_generate_spare() {
+        // The spare octet is written even if buffer already had something in it
+        let mut buffer = [0xFF; MTU];
+
+        let h = Header::new(MessageType::EchoRequest);
+
+        let pos = h.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            /* Flags */ 0b0100_0000,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x00, 0x04,
+            /* Sequence Number */ 0x00, 0x00, 0x00,
+            /* Spare */ 0x00
+            ]);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let header_bytes = [
+            /* Flags */ 0b0100_1101,
+            /* Message Type */ u8::from(MessageType::CreateSessionRequest),
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x87, 0x65, 0x43,
+            /* Message Priority and Spare */ 0x53
+            ];
+
+        assert!(Header::parse_with_mode(&header_bytes, ParseMode::Strict).is_none());
+
+        // Lenient keeps the spare bits so the header is regenerated as it was received
+        if let Some((h, pos)) = Header::parse_with_mode(&header_bytes, ParseMode::Lenient) {
+            assert_eq!(pos, 12);
+            assert_eq!(h.message_priority(), 5);
+            assert_eq!(h.spare_bits(), (0b01, 0x3));
+
+            let mut buffer = [0; MTU];
+            let pos = h.generate(&mut buffer);
+            assert_eq!(buffer[..pos], header_bytes);
+
+            let mut h = h;
+            h.clear_spare_bits();
+            assert!(!h.has_spare_bits());
+
+            let pos = h.generate(&mut buffer);
+            assert_eq!(buffer[0], 0b0100_1100);
+            assert_eq!(buffer[pos - 1], 0x50);
+        }
+        else {
+            assert!(false);
         }
+
+        // Without the MP flag the whole of octet 12 is spare
+        let mut buffer = header_bytes;
+        buffer[0] = 0b0100_1000;
+        assert!(Header::parse_with_mode(&buffer, ParseMode::Strict).is_none());
+        assert_eq!(Header::parse(&buffer).unwrap().0.spare_bits(), (0, 0x53));
+
+        buffer[11] = 0;
+        assert!(Header::parse_with_mode(&buffer, ParseMode::Strict).is_some());
+
+        // Length doesn't cover the TEID
+        let mut short = buffer;
+        short[3] = 0x04;
+        assert!(Header::parse_with_mode(&short, ParseMode::Strict).is_none());
+        assert!(Header::parse_with_mode(&short, ParseMode::Lenient).is_some());
+
+        // Length is longer than the buffer
+        let mut long = buffer;
+        long[3] = 0x09;
+        assert!(Header::parse_with_mode(&long, ParseMode::Strict).is_none());
+        assert!(Header::parse_with_mode(&long, ParseMode::Lenient).is_some());
+    }
+
+    #[test]
+    fn test_peek() {
+        let message_bytes = [
+            /* Flags */ 0b0100_1000,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x00, 0x0D,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x87, 0x65, 0x43,
+            /* Spare */ 0x00,
+            /* Recovery IE */ 3, 0x00, 0x01, 0x00, 0x05,
+            ];
+
+        assert_eq!(peek(&message_bytes), Some(HeaderSummary {
+            version: 2,
+            message_type: u8::from(MessageType::EchoRequest),
+            teid: Some(0x12345678),
+            payload_offset: 12,
+            total_length: 17,
+        }));
+
+        // No TEID
+        let message_bytes = [
+            /* Flags */ 0b0100_0000,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x00, 0x04,
+            /* Sequence Number */ 0x87, 0x65, 0x43,
+            /* Spare */ 0x00,
+            ];
+
+        if let Some(summary) = peek(&message_bytes) {
+            assert_eq!(summary.teid, None);
+            assert_eq!(summary.payload_offset, 8);
+            assert_eq!(summary.total_length, 8);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_peek_invalid() {
+        let header_bytes = [
+            /* Flags */ 0b0100_1000,
+            /* Message Type */ u8::from(MessageType::EchoRequest),
+            /* Length */ 0x00, 0x08,
+            /* TEID */ 0x12, 0x34, 0x56, 0x78,
+            /* Sequence Number */ 0x87, 0x65, 0x43,
+            /* Spare */ 0x00
+            ];
+
+        assert!(peek(&header_bytes).is_some());
+
+        for length in 0..header_bytes.len() {
+            assert!(peek(&header_bytes[..length]).is_none());
+        }
+
+        // GTPv1
+        let mut buffer = header_bytes;
+        buffer[0] = 0b0011_0000;
+        assert!(peek(&buffer).is_none());
+
+        // Length is too short for the TEID
+        let mut buffer = header_bytes;
+        buffer[3] = 0x04;
+        assert!(peek(&buffer).is_none());
     }
 }
@@ -2,99 +2,2280 @@ pub mod echo_request;
 pub mod echo_response;
 pub mod create_session_request;
 pub mod create_session_response;
+pub mod modify_bearer_request;
+pub mod modify_bearer_response;
+pub mod delete_session_request;
+pub mod delete_session_response;
+pub mod downlink_data_notification;
+pub mod downlink_data_notification_acknowledge;
+pub mod downlink_data_notification_failure_indication;
+pub mod context_request;
+pub mod context_response;
+pub mod context_acknowledge;
+pub mod modify_bearer_command;
+pub mod modify_bearer_failure_indication;
+pub mod bearer_resource_command;
+pub mod bearer_resource_failure_indication;
+pub mod suspend_notification;
+pub mod suspend_acknowledge;
+pub mod resume_notification;
+pub mod resume_acknowledge;
+pub mod forward_relocation_request;
+pub mod release_access_bearers_request;
+pub mod modify_access_bearers_request;
+pub mod release_access_bearers_response;
+pub mod modify_access_bearers_response;
+pub mod remote_ue_report_notification;
+pub mod remote_ue_report_acknowledge;
+#[cfg(feature = "sv-interface")]
+pub mod srvcc_ps_to_cs_request;
+#[cfg(feature = "sv-interface")]
+pub mod srvcc_ps_to_cs_response;
+#[cfg(feature = "sv-interface")]
+pub mod srvcc_ps_to_cs_complete_notification;
+#[cfg(feature = "sv-interface")]
+pub mod srvcc_ps_to_cs_complete_acknowledge;
 pub mod information_elements;
 
-use std::convert::TryFrom;
+use core::convert::TryFrom;
+use core::fmt;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use information_elements::{InformationElementTraits, InformationElementType, cause, recovery};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::gtp_v2::conformance::{self, Interface, Violation, Warning};
+
+use alloc::format;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum MessageType
 {
-    EchoRequest = 1,
-    EchoResponse = 2,
-    CreateSessionRequest = 32,
-    CreateSessionResponse = 33,
+    /*
+        TS 29.274 Table 6.1-1. Reserved values, values for future use and the Sv values when the sv-interface feature
+        is disabled are Unknown so that a message of that type can still be reported and skipped.
+    */
+    EchoRequest,
+    EchoResponse,
+    VersionNotSupportedIndication,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsRequest,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsResponse,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCompleteNotification,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCompleteAcknowledge,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCancelNotification,
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCancelAcknowledge,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsRequest,
+    CreateSessionRequest,
+    CreateSessionResponse,
+    ModifyBearerRequest,
+    ModifyBearerResponse,
+    DeleteSessionRequest,
+    DeleteSessionResponse,
+    ChangeNotificationRequest,
+    ChangeNotificationResponse,
+    RemoteUEReportNotification,
+    RemoteUEReportAcknowledge,
+    ModifyBearerCommand,
+    ModifyBearerFailureIndication,
+    DeleteBearerCommand,
+    DeleteBearerFailureIndication,
+    BearerResourceCommand,
+    BearerResourceFailureIndication,
+    DownlinkDataNotificationFailureIndication,
+    TraceSessionActivation,
+    TraceSessionDeactivation,
+    StopPagingIndication,
+    CreateBearerRequest,
+    CreateBearerResponse,
+    UpdateBearerRequest,
+    UpdateBearerResponse,
+    DeleteBearerRequest,
+    DeleteBearerResponse,
+    DeletePDNConnectionSetRequest,
+    DeletePDNConnectionSetResponse,
+    PGWDownlinkTriggeringNotification,
+    PGWDownlinkTriggeringAcknowledge,
+    IdentificationRequest,
+    IdentificationResponse,
+    ContextRequest,
+    ContextResponse,
+    ContextAcknowledge,
+    ForwardRelocationRequest,
+    ForwardRelocationResponse,
+    ForwardRelocationCompleteNotification,
+    ForwardRelocationCompleteAcknowledge,
+    ForwardAccessContextNotification,
+    ForwardAccessContextAcknowledge,
+    RelocationCancelRequest,
+    RelocationCancelResponse,
+    ConfigurationTransferTunnel,
+    DetachNotification,
+    DetachAcknowledge,
+    CSPagingIndication,
+    RANInformationRelay,
+    AlertMMENotification,
+    AlertMMEAcknowledge,
+    UEActivityNotification,
+    UEActivityAcknowledge,
+    ISRStatusIndication,
+    UERegistrationQueryRequest,
+    UERegistrationQueryResponse,
+    CreateForwardingTunnelRequest,
+    CreateForwardingTunnelResponse,
+    SuspendNotification,
+    SuspendAcknowledge,
+    ResumeNotification,
+    ResumeAcknowledge,
+    CreateIndirectDataForwardingTunnelRequest,
+    CreateIndirectDataForwardingTunnelResponse,
+    DeleteIndirectDataForwardingTunnelRequest,
+    DeleteIndirectDataForwardingTunnelResponse,
+    ReleaseAccessBearersRequest,
+    ReleaseAccessBearersResponse,
+    DownlinkDataNotification,
+    DownlinkDataNotificationAcknowledge,
+    PGWRestartNotification,
+    PGWRestartNotificationAcknowledge,
+    UpdatePDNConnectionSetRequest,
+    UpdatePDNConnectionSetResponse,
+    ModifyAccessBearersRequest,
+    ModifyAccessBearersResponse,
+    MBMSSessionStartRequest,
+    MBMSSessionStartResponse,
+    MBMSSessionUpdateRequest,
+    MBMSSessionUpdateResponse,
+    MBMSSessionStopRequest,
+    MBMSSessionStopResponse,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsResponse,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsCompleteNotification,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsCompleteAcknowledge,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsCancelNotification,
+    #[cfg(feature = "sv-interface")]
+    SRVCCCsToPsCancelAcknowledge,
+    Unknown(u8),
 }
 
-impl TryFrom<u8> for MessageType
+impl From<u8> for MessageType
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            1 => MessageType::EchoRequest,
+            2 => MessageType::EchoResponse,
+            3 => MessageType::VersionNotSupportedIndication,
+            #[cfg(feature = "sv-interface")]
+            25 => MessageType::SRVCCPsToCsRequest,
+            #[cfg(feature = "sv-interface")]
+            26 => MessageType::SRVCCPsToCsResponse,
+            #[cfg(feature = "sv-interface")]
+            27 => MessageType::SRVCCPsToCsCompleteNotification,
+            #[cfg(feature = "sv-interface")]
+            28 => MessageType::SRVCCPsToCsCompleteAcknowledge,
+            #[cfg(feature = "sv-interface")]
+            29 => MessageType::SRVCCPsToCsCancelNotification,
+            #[cfg(feature = "sv-interface")]
+            30 => MessageType::SRVCCPsToCsCancelAcknowledge,
+            #[cfg(feature = "sv-interface")]
+            31 => MessageType::SRVCCCsToPsRequest,
+            32 => MessageType::CreateSessionRequest,
+            33 => MessageType::CreateSessionResponse,
+            34 => MessageType::ModifyBearerRequest,
+            35 => MessageType::ModifyBearerResponse,
+            36 => MessageType::DeleteSessionRequest,
+            37 => MessageType::DeleteSessionResponse,
+            38 => MessageType::ChangeNotificationRequest,
+            39 => MessageType::ChangeNotificationResponse,
+            40 => MessageType::RemoteUEReportNotification,
+            41 => MessageType::RemoteUEReportAcknowledge,
+            64 => MessageType::ModifyBearerCommand,
+            65 => MessageType::ModifyBearerFailureIndication,
+            66 => MessageType::DeleteBearerCommand,
+            67 => MessageType::DeleteBearerFailureIndication,
+            68 => MessageType::BearerResourceCommand,
+            69 => MessageType::BearerResourceFailureIndication,
+            70 => MessageType::DownlinkDataNotificationFailureIndication,
+            71 => MessageType::TraceSessionActivation,
+            72 => MessageType::TraceSessionDeactivation,
+            73 => MessageType::StopPagingIndication,
+            95 => MessageType::CreateBearerRequest,
+            96 => MessageType::CreateBearerResponse,
+            97 => MessageType::UpdateBearerRequest,
+            98 => MessageType::UpdateBearerResponse,
+            99 => MessageType::DeleteBearerRequest,
+            100 => MessageType::DeleteBearerResponse,
+            101 => MessageType::DeletePDNConnectionSetRequest,
+            102 => MessageType::DeletePDNConnectionSetResponse,
+            103 => MessageType::PGWDownlinkTriggeringNotification,
+            104 => MessageType::PGWDownlinkTriggeringAcknowledge,
+            128 => MessageType::IdentificationRequest,
+            129 => MessageType::IdentificationResponse,
+            130 => MessageType::ContextRequest,
+            131 => MessageType::ContextResponse,
+            132 => MessageType::ContextAcknowledge,
+            133 => MessageType::ForwardRelocationRequest,
+            134 => MessageType::ForwardRelocationResponse,
+            135 => MessageType::ForwardRelocationCompleteNotification,
+            136 => MessageType::ForwardRelocationCompleteAcknowledge,
+            137 => MessageType::ForwardAccessContextNotification,
+            138 => MessageType::ForwardAccessContextAcknowledge,
+            139 => MessageType::RelocationCancelRequest,
+            140 => MessageType::RelocationCancelResponse,
+            141 => MessageType::ConfigurationTransferTunnel,
+            149 => MessageType::DetachNotification,
+            150 => MessageType::DetachAcknowledge,
+            151 => MessageType::CSPagingIndication,
+            152 => MessageType::RANInformationRelay,
+            153 => MessageType::AlertMMENotification,
+            154 => MessageType::AlertMMEAcknowledge,
+            155 => MessageType::UEActivityNotification,
+            156 => MessageType::UEActivityAcknowledge,
+            157 => MessageType::ISRStatusIndication,
+            158 => MessageType::UERegistrationQueryRequest,
+            159 => MessageType::UERegistrationQueryResponse,
+            160 => MessageType::CreateForwardingTunnelRequest,
+            161 => MessageType::CreateForwardingTunnelResponse,
+            162 => MessageType::SuspendNotification,
+            163 => MessageType::SuspendAcknowledge,
+            164 => MessageType::ResumeNotification,
+            165 => MessageType::ResumeAcknowledge,
+            166 => MessageType::CreateIndirectDataForwardingTunnelRequest,
+            167 => MessageType::CreateIndirectDataForwardingTunnelResponse,
+            168 => MessageType::DeleteIndirectDataForwardingTunnelRequest,
+            169 => MessageType::DeleteIndirectDataForwardingTunnelResponse,
+            170 => MessageType::ReleaseAccessBearersRequest,
+            171 => MessageType::ReleaseAccessBearersResponse,
+            176 => MessageType::DownlinkDataNotification,
+            177 => MessageType::DownlinkDataNotificationAcknowledge,
+            179 => MessageType::PGWRestartNotification,
+            180 => MessageType::PGWRestartNotificationAcknowledge,
+            200 => MessageType::UpdatePDNConnectionSetRequest,
+            201 => MessageType::UpdatePDNConnectionSetResponse,
+            211 => MessageType::ModifyAccessBearersRequest,
+            212 => MessageType::ModifyAccessBearersResponse,
+            231 => MessageType::MBMSSessionStartRequest,
+            232 => MessageType::MBMSSessionStartResponse,
+            233 => MessageType::MBMSSessionUpdateRequest,
+            234 => MessageType::MBMSSessionUpdateResponse,
+            235 => MessageType::MBMSSessionStopRequest,
+            236 => MessageType::MBMSSessionStopResponse,
+            #[cfg(feature = "sv-interface")]
+            240 => MessageType::SRVCCCsToPsResponse,
+            #[cfg(feature = "sv-interface")]
+            241 => MessageType::SRVCCCsToPsCompleteNotification,
+            #[cfg(feature = "sv-interface")]
+            242 => MessageType::SRVCCCsToPsCompleteAcknowledge,
+            #[cfg(feature = "sv-interface")]
+            243 => MessageType::SRVCCCsToPsCancelNotification,
+            #[cfg(feature = "sv-interface")]
+            244 => MessageType::SRVCCCsToPsCancelAcknowledge,
+            value => MessageType::Unknown(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<MessageType> for u8
+{
+    fn from(value: MessageType) -> Self {
         match value {
-            1 => Ok(MessageType::EchoRequest),
-            2 => Ok(MessageType::EchoResponse),
-            32 => Ok(MessageType::CreateSessionRequest),
-            33 => Ok(MessageType::CreateSessionRequest),
-            _ => Err(format!("Unsupported Message type ({})", value))
+            MessageType::EchoRequest => 1,
+            MessageType::EchoResponse => 2,
+            MessageType::VersionNotSupportedIndication => 3,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsRequest => 25,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsResponse => 26,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteNotification => 27,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteAcknowledge => 28,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCancelNotification => 29,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCancelAcknowledge => 30,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsRequest => 31,
+            MessageType::CreateSessionRequest => 32,
+            MessageType::CreateSessionResponse => 33,
+            MessageType::ModifyBearerRequest => 34,
+            MessageType::ModifyBearerResponse => 35,
+            MessageType::DeleteSessionRequest => 36,
+            MessageType::DeleteSessionResponse => 37,
+            MessageType::ChangeNotificationRequest => 38,
+            MessageType::ChangeNotificationResponse => 39,
+            MessageType::RemoteUEReportNotification => 40,
+            MessageType::RemoteUEReportAcknowledge => 41,
+            MessageType::ModifyBearerCommand => 64,
+            MessageType::ModifyBearerFailureIndication => 65,
+            MessageType::DeleteBearerCommand => 66,
+            MessageType::DeleteBearerFailureIndication => 67,
+            MessageType::BearerResourceCommand => 68,
+            MessageType::BearerResourceFailureIndication => 69,
+            MessageType::DownlinkDataNotificationFailureIndication => 70,
+            MessageType::TraceSessionActivation => 71,
+            MessageType::TraceSessionDeactivation => 72,
+            MessageType::StopPagingIndication => 73,
+            MessageType::CreateBearerRequest => 95,
+            MessageType::CreateBearerResponse => 96,
+            MessageType::UpdateBearerRequest => 97,
+            MessageType::UpdateBearerResponse => 98,
+            MessageType::DeleteBearerRequest => 99,
+            MessageType::DeleteBearerResponse => 100,
+            MessageType::DeletePDNConnectionSetRequest => 101,
+            MessageType::DeletePDNConnectionSetResponse => 102,
+            MessageType::PGWDownlinkTriggeringNotification => 103,
+            MessageType::PGWDownlinkTriggeringAcknowledge => 104,
+            MessageType::IdentificationRequest => 128,
+            MessageType::IdentificationResponse => 129,
+            MessageType::ContextRequest => 130,
+            MessageType::ContextResponse => 131,
+            MessageType::ContextAcknowledge => 132,
+            MessageType::ForwardRelocationRequest => 133,
+            MessageType::ForwardRelocationResponse => 134,
+            MessageType::ForwardRelocationCompleteNotification => 135,
+            MessageType::ForwardRelocationCompleteAcknowledge => 136,
+            MessageType::ForwardAccessContextNotification => 137,
+            MessageType::ForwardAccessContextAcknowledge => 138,
+            MessageType::RelocationCancelRequest => 139,
+            MessageType::RelocationCancelResponse => 140,
+            MessageType::ConfigurationTransferTunnel => 141,
+            MessageType::DetachNotification => 149,
+            MessageType::DetachAcknowledge => 150,
+            MessageType::CSPagingIndication => 151,
+            MessageType::RANInformationRelay => 152,
+            MessageType::AlertMMENotification => 153,
+            MessageType::AlertMMEAcknowledge => 154,
+            MessageType::UEActivityNotification => 155,
+            MessageType::UEActivityAcknowledge => 156,
+            MessageType::ISRStatusIndication => 157,
+            MessageType::UERegistrationQueryRequest => 158,
+            MessageType::UERegistrationQueryResponse => 159,
+            MessageType::CreateForwardingTunnelRequest => 160,
+            MessageType::CreateForwardingTunnelResponse => 161,
+            MessageType::SuspendNotification => 162,
+            MessageType::SuspendAcknowledge => 163,
+            MessageType::ResumeNotification => 164,
+            MessageType::ResumeAcknowledge => 165,
+            MessageType::CreateIndirectDataForwardingTunnelRequest => 166,
+            MessageType::CreateIndirectDataForwardingTunnelResponse => 167,
+            MessageType::DeleteIndirectDataForwardingTunnelRequest => 168,
+            MessageType::DeleteIndirectDataForwardingTunnelResponse => 169,
+            MessageType::ReleaseAccessBearersRequest => 170,
+            MessageType::ReleaseAccessBearersResponse => 171,
+            MessageType::DownlinkDataNotification => 176,
+            MessageType::DownlinkDataNotificationAcknowledge => 177,
+            MessageType::PGWRestartNotification => 179,
+            MessageType::PGWRestartNotificationAcknowledge => 180,
+            MessageType::UpdatePDNConnectionSetRequest => 200,
+            MessageType::UpdatePDNConnectionSetResponse => 201,
+            MessageType::ModifyAccessBearersRequest => 211,
+            MessageType::ModifyAccessBearersResponse => 212,
+            MessageType::MBMSSessionStartRequest => 231,
+            MessageType::MBMSSessionStartResponse => 232,
+            MessageType::MBMSSessionUpdateRequest => 233,
+            MessageType::MBMSSessionUpdateResponse => 234,
+            MessageType::MBMSSessionStopRequest => 235,
+            MessageType::MBMSSessionStopResponse => 236,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsResponse => 240,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCompleteNotification => 241,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCompleteAcknowledge => 242,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCancelNotification => 243,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCancelAcknowledge => 244,
+            MessageType::Unknown(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for MessageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MessageType::Unknown(value) => write!(f, "{} ({})", self.name(), value),
+            _ => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+impl MessageType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageType::EchoRequest => "Echo Request",
+            MessageType::EchoResponse => "Echo Response",
+            MessageType::VersionNotSupportedIndication => "Version Not Supported Indication",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsRequest => "SRVCC PS to CS Request",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsResponse => "SRVCC PS to CS Response",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteNotification => "SRVCC PS to CS Complete Notification",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteAcknowledge => "SRVCC PS to CS Complete Acknowledge",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCancelNotification => "SRVCC PS to CS Cancel Notification",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCancelAcknowledge => "SRVCC PS to CS Cancel Acknowledge",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsRequest => "SRVCC CS to PS Request",
+            MessageType::CreateSessionRequest => "Create Session Request",
+            MessageType::CreateSessionResponse => "Create Session Response",
+            MessageType::ModifyBearerRequest => "Modify Bearer Request",
+            MessageType::ModifyBearerResponse => "Modify Bearer Response",
+            MessageType::DeleteSessionRequest => "Delete Session Request",
+            MessageType::DeleteSessionResponse => "Delete Session Response",
+            MessageType::ChangeNotificationRequest => "Change Notification Request",
+            MessageType::ChangeNotificationResponse => "Change Notification Response",
+            MessageType::RemoteUEReportNotification => "Remote UE Report Notification",
+            MessageType::RemoteUEReportAcknowledge => "Remote UE Report Acknowledge",
+            MessageType::ModifyBearerCommand => "Modify Bearer Command",
+            MessageType::ModifyBearerFailureIndication => "Modify Bearer Failure Indication",
+            MessageType::DeleteBearerCommand => "Delete Bearer Command",
+            MessageType::DeleteBearerFailureIndication => "Delete Bearer Failure Indication",
+            MessageType::BearerResourceCommand => "Bearer Resource Command",
+            MessageType::BearerResourceFailureIndication => "Bearer Resource Failure Indication",
+            MessageType::DownlinkDataNotificationFailureIndication => "Downlink Data Notification Failure Indication",
+            MessageType::TraceSessionActivation => "Trace Session Activation",
+            MessageType::TraceSessionDeactivation => "Trace Session Deactivation",
+            MessageType::StopPagingIndication => "Stop Paging Indication",
+            MessageType::CreateBearerRequest => "Create Bearer Request",
+            MessageType::CreateBearerResponse => "Create Bearer Response",
+            MessageType::UpdateBearerRequest => "Update Bearer Request",
+            MessageType::UpdateBearerResponse => "Update Bearer Response",
+            MessageType::DeleteBearerRequest => "Delete Bearer Request",
+            MessageType::DeleteBearerResponse => "Delete Bearer Response",
+            MessageType::DeletePDNConnectionSetRequest => "Delete PDN Connection Set Request",
+            MessageType::DeletePDNConnectionSetResponse => "Delete PDN Connection Set Response",
+            MessageType::PGWDownlinkTriggeringNotification => "PGW Downlink Triggering Notification",
+            MessageType::PGWDownlinkTriggeringAcknowledge => "PGW Downlink Triggering Acknowledge",
+            MessageType::IdentificationRequest => "Identification Request",
+            MessageType::IdentificationResponse => "Identification Response",
+            MessageType::ContextRequest => "Context Request",
+            MessageType::ContextResponse => "Context Response",
+            MessageType::ContextAcknowledge => "Context Acknowledge",
+            MessageType::ForwardRelocationRequest => "Forward Relocation Request",
+            MessageType::ForwardRelocationResponse => "Forward Relocation Response",
+            MessageType::ForwardRelocationCompleteNotification => "Forward Relocation Complete Notification",
+            MessageType::ForwardRelocationCompleteAcknowledge => "Forward Relocation Complete Acknowledge",
+            MessageType::ForwardAccessContextNotification => "Forward Access Context Notification",
+            MessageType::ForwardAccessContextAcknowledge => "Forward Access Context Acknowledge",
+            MessageType::RelocationCancelRequest => "Relocation Cancel Request",
+            MessageType::RelocationCancelResponse => "Relocation Cancel Response",
+            MessageType::ConfigurationTransferTunnel => "Configuration Transfer Tunnel",
+            MessageType::DetachNotification => "Detach Notification",
+            MessageType::DetachAcknowledge => "Detach Acknowledge",
+            MessageType::CSPagingIndication => "CS Paging Indication",
+            MessageType::RANInformationRelay => "RAN Information Relay",
+            MessageType::AlertMMENotification => "Alert MME Notification",
+            MessageType::AlertMMEAcknowledge => "Alert MME Acknowledge",
+            MessageType::UEActivityNotification => "UE Activity Notification",
+            MessageType::UEActivityAcknowledge => "UE Activity Acknowledge",
+            MessageType::ISRStatusIndication => "ISR Status Indication",
+            MessageType::UERegistrationQueryRequest => "UE Registration Query Request",
+            MessageType::UERegistrationQueryResponse => "UE Registration Query Response",
+            MessageType::CreateForwardingTunnelRequest => "Create Forwarding Tunnel Request",
+            MessageType::CreateForwardingTunnelResponse => "Create Forwarding Tunnel Response",
+            MessageType::SuspendNotification => "Suspend Notification",
+            MessageType::SuspendAcknowledge => "Suspend Acknowledge",
+            MessageType::ResumeNotification => "Resume Notification",
+            MessageType::ResumeAcknowledge => "Resume Acknowledge",
+            MessageType::CreateIndirectDataForwardingTunnelRequest => "Create Indirect Data Forwarding Tunnel Request",
+            MessageType::CreateIndirectDataForwardingTunnelResponse => "Create Indirect Data Forwarding Tunnel Response",
+            MessageType::DeleteIndirectDataForwardingTunnelRequest => "Delete Indirect Data Forwarding Tunnel Request",
+            MessageType::DeleteIndirectDataForwardingTunnelResponse => "Delete Indirect Data Forwarding Tunnel Response",
+            MessageType::ReleaseAccessBearersRequest => "Release Access Bearers Request",
+            MessageType::ReleaseAccessBearersResponse => "Release Access Bearers Response",
+            MessageType::DownlinkDataNotification => "Downlink Data Notification",
+            MessageType::DownlinkDataNotificationAcknowledge => "Downlink Data Notification Acknowledge",
+            MessageType::PGWRestartNotification => "PGW Restart Notification",
+            MessageType::PGWRestartNotificationAcknowledge => "PGW Restart Notification Acknowledge",
+            MessageType::UpdatePDNConnectionSetRequest => "Update PDN Connection Set Request",
+            MessageType::UpdatePDNConnectionSetResponse => "Update PDN Connection Set Response",
+            MessageType::ModifyAccessBearersRequest => "Modify Access Bearers Request",
+            MessageType::ModifyAccessBearersResponse => "Modify Access Bearers Response",
+            MessageType::MBMSSessionStartRequest => "MBMS Session Start Request",
+            MessageType::MBMSSessionStartResponse => "MBMS Session Start Response",
+            MessageType::MBMSSessionUpdateRequest => "MBMS Session Update Request",
+            MessageType::MBMSSessionUpdateResponse => "MBMS Session Update Response",
+            MessageType::MBMSSessionStopRequest => "MBMS Session Stop Request",
+            MessageType::MBMSSessionStopResponse => "MBMS Session Stop Response",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsResponse => "SRVCC CS to PS Response",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCompleteNotification => "SRVCC CS to PS Complete Notification",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCompleteAcknowledge => "SRVCC CS to PS Complete Acknowledge",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCancelNotification => "SRVCC CS to PS Cancel Notification",
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCCsToPsCancelAcknowledge => "SRVCC CS to PS Cancel Acknowledge",
+            MessageType::Unknown(_) => "Unknown",
+        }
+    }
+
+    pub fn teid_required(&self) -> bool {
+        /* TS 29.274 5.5.1: Echo Request, Echo Response and Version Not Supported Indication are sent without a TEID.
+        All other messages carry a TEID even if it is 0 e.g. the first message to a peer */
+        match self {
+            MessageType::EchoRequest | MessageType::EchoResponse | MessageType::VersionNotSupportedIndication => false,
+            _ => true,
+        }
+    }
+
+    pub fn is_command(&self) -> bool {
+        // Command messages are sent with the most significant bit of the Sequence Number set (TS 29.274 7.6)
+        match self {
+            MessageType::ModifyBearerCommand | MessageType::DeleteBearerCommand | MessageType::BearerResourceCommand => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        // Responses, Acknowledges and the Failure Indications sent in reply to a Command (TS 29.274 7.6)
+        match self {
+            MessageType::EchoResponse |
+            MessageType::VersionNotSupportedIndication |
+            MessageType::CreateSessionResponse |
+            MessageType::ModifyBearerResponse |
+            MessageType::DeleteSessionResponse |
+            MessageType::ChangeNotificationResponse |
+            MessageType::RemoteUEReportAcknowledge |
+            MessageType::ModifyBearerFailureIndication |
+            MessageType::DeleteBearerFailureIndication |
+            MessageType::BearerResourceFailureIndication |
+            MessageType::CreateBearerResponse |
+            MessageType::UpdateBearerResponse |
+            MessageType::DeleteBearerResponse |
+            MessageType::DeletePDNConnectionSetResponse |
+            MessageType::PGWDownlinkTriggeringAcknowledge |
+            MessageType::IdentificationResponse |
+            MessageType::ContextResponse |
+            MessageType::ContextAcknowledge |
+            MessageType::ForwardRelocationResponse |
+            MessageType::ForwardRelocationCompleteAcknowledge |
+            MessageType::ForwardAccessContextAcknowledge |
+            MessageType::RelocationCancelResponse |
+            MessageType::DetachAcknowledge |
+            MessageType::AlertMMEAcknowledge |
+            MessageType::UEActivityAcknowledge |
+            MessageType::UERegistrationQueryResponse |
+            MessageType::CreateForwardingTunnelResponse |
+            MessageType::SuspendAcknowledge |
+            MessageType::ResumeAcknowledge |
+            MessageType::CreateIndirectDataForwardingTunnelResponse |
+            MessageType::DeleteIndirectDataForwardingTunnelResponse |
+            MessageType::ReleaseAccessBearersResponse |
+            MessageType::DownlinkDataNotificationAcknowledge |
+            MessageType::PGWRestartNotificationAcknowledge |
+            MessageType::UpdatePDNConnectionSetResponse |
+            MessageType::ModifyAccessBearersResponse |
+            MessageType::MBMSSessionStartResponse |
+            MessageType::MBMSSessionUpdateResponse |
+            MessageType::MBMSSessionStopResponse => true,
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsResponse |
+            MessageType::SRVCCPsToCsCompleteAcknowledge |
+            MessageType::SRVCCPsToCsCancelAcknowledge |
+            MessageType::SRVCCCsToPsResponse |
+            MessageType::SRVCCCsToPsCompleteAcknowledge |
+            MessageType::SRVCCCsToPsCancelAcknowledge => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidHeader,
+    UnsupportedMessageType(MessageType), // The message can be skipped using the length in the header
+    MandatoryIEMissing(Vec<(InformationElementType, u8)>), // (IE Type, Instance) of every missing IE
+    MandatoryIEIncorrect(Vec<(InformationElementType, u8)>), // (IE Type, Instance) of every mandatory IE that failed to parse
+}
+
+impl ParseError {
+    pub fn check_mandatory(missing: Vec<(InformationElementType, u8)>, failed: &[(InformationElementType, u8)]) -> Result<(), ParseError> {
+        /* A mandatory IE that was present in the message but failed to parse is incorrect rather than missing.
+        Incorrect IEs are reported in preference to missing IEs */
+        let (incorrect, missing): (Vec<_>, Vec<_>) = missing.into_iter().partition(|ie| failed.contains(ie));
+
+        if !incorrect.is_empty() {
+            Err(ParseError::MandatoryIEIncorrect(incorrect))
+        }
+        else if !missing.is_empty() {
+            Err(ParseError::MandatoryIEMissing(missing))
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    pub fn cause(&self) -> cause::InformationElement {
+        /* The Cause IE to send in the response to the message that failed to parse.
+        Only one Offending IE can be carried in a Cause so the first missing or incorrect IE is used */
+        match self {
+            ParseError::InvalidHeader => cause::InformationElement::new(
+                cause::CauseCode::InvalidMessageFormat,
+                cause::CauseSource::RemoteNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap(),
+            // Messages we don't support are discarded (TS 29.274 7.7.4) so this is only for completeness
+            ParseError::UnsupportedMessageType(_) => cause::InformationElement::new(
+                cause::CauseCode::ServiceNotSupported,
+                cause::CauseSource::RemoteNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap(),
+            ParseError::MandatoryIEMissing(missing) => cause::InformationElement::new(
+                cause::CauseCode::MandatoryIEMissing,
+                cause::CauseSource::RemoteNode,
+                false,
+                false,
+                missing.first().cloned(),
+                0
+            ).unwrap(),
+            ParseError::MandatoryIEIncorrect(incorrect) => cause::InformationElement::new(
+                cause::CauseCode::MandatoryIEIncorrect,
+                cause::CauseSource::RemoteNode,
+                false,
+                false,
+                incorrect.first().cloned(),
+                0
+            ).unwrap(),
         }
     }
 }
 
+// The IEs of a message in the order they are generated
+pub type Ies<'a> = alloc::vec::IntoIter<&'a dyn InformationElementTraits>;
+
 pub trait MessageTraits {
     fn message_type(&self) -> MessageType;
     fn length(&self) -> u16;
     fn generate(&self, buffer: &mut[u8]) -> usize;
+    fn ies(&self) -> Ies<'_>;
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum Message {
     EchoRequest(echo_request::Message),
     EchoResponse(echo_response::Message),
     CreateSessionRequest(create_session_request::Message),
     CreateSessionResponse(create_session_response::Message),
+    ModifyBearerRequest(modify_bearer_request::Message),
+    ModifyBearerResponse(modify_bearer_response::Message),
+    DeleteSessionRequest(delete_session_request::Message),
+    DeleteSessionResponse(delete_session_response::Message),
+    DownlinkDataNotification(downlink_data_notification::Message),
+    DownlinkDataNotificationAcknowledge(downlink_data_notification_acknowledge::Message),
+    DownlinkDataNotificationFailureIndication(downlink_data_notification_failure_indication::Message),
+    ContextRequest(context_request::Message),
+    ContextResponse(context_response::Message),
+    ContextAcknowledge(context_acknowledge::Message),
+    ModifyBearerCommand(modify_bearer_command::Message),
+    ModifyBearerFailureIndication(modify_bearer_failure_indication::Message),
+    BearerResourceCommand(bearer_resource_command::Message),
+    BearerResourceFailureIndication(bearer_resource_failure_indication::Message),
+    SuspendNotification(suspend_notification::Message),
+    SuspendAcknowledge(suspend_acknowledge::Message),
+    ResumeNotification(resume_notification::Message),
+    ResumeAcknowledge(resume_acknowledge::Message),
+    ForwardRelocationRequest(forward_relocation_request::Message),
+    ReleaseAccessBearersRequest(release_access_bearers_request::Message),
+    ModifyAccessBearersRequest(modify_access_bearers_request::Message),
+    ReleaseAccessBearersResponse(release_access_bearers_response::Message),
+    ModifyAccessBearersResponse(modify_access_bearers_response::Message),
+    RemoteUEReportNotification(remote_ue_report_notification::Message),
+    RemoteUEReportAcknowledge(remote_ue_report_acknowledge::Message),
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsRequest(srvcc_ps_to_cs_request::Message),
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsResponse(srvcc_ps_to_cs_response::Message),
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCompleteNotification(srvcc_ps_to_cs_complete_notification::Message),
+    #[cfg(feature = "sv-interface")]
+    SRVCCPsToCsCompleteAcknowledge(srvcc_ps_to_cs_complete_acknowledge::Message),
 }
 
 impl Message {
-    pub fn parse(message_type: MessageType, buffer: &[u8]) -> Option<(Self, usize)> {
+    pub fn parse(message_type: MessageType, buffer: &[u8]) -> Result<(Self, usize), ParseError> {
         match message_type {
             MessageType::EchoRequest => {
-                if let Some((m, pos)) = echo_request::Message::parse(buffer) {
-                    Some((Message::EchoRequest(m), pos))
-                } else { None }
+                let (m, pos) = echo_request::Message::parse(buffer)?;
+                Ok((Message::EchoRequest(m), pos))
             },
             MessageType::EchoResponse => {
-                if let Some((m, pos)) = echo_response::Message::parse(buffer) {
-                    Some((Message::EchoResponse(m), pos))
-                } else { None }
+                let (m, pos) = echo_response::Message::parse(buffer)?;
+                Ok((Message::EchoResponse(m), pos))
             },
             MessageType::CreateSessionRequest => {
-                if let Some((m, pos)) = create_session_request::Message::parse(buffer) {
-                    Some((Message::CreateSessionRequest(m), pos))
-                } else { None }
+                let (m, pos) = create_session_request::Message::parse(buffer)?;
+                Ok((Message::CreateSessionRequest(m), pos))
             },
             MessageType::CreateSessionResponse => {
-                if let Some((m, pos)) = create_session_response::Message::parse(buffer) {
-                    Some((Message::CreateSessionResponse(m), pos))
-                } else { None }
+                let (m, pos) = create_session_response::Message::parse(buffer)?;
+                Ok((Message::CreateSessionResponse(m), pos))
+            },
+            MessageType::ModifyBearerRequest => {
+                let (m, pos) = modify_bearer_request::Message::parse(buffer)?;
+                Ok((Message::ModifyBearerRequest(m), pos))
+            },
+            MessageType::ModifyBearerResponse => {
+                let (m, pos) = modify_bearer_response::Message::parse(buffer)?;
+                Ok((Message::ModifyBearerResponse(m), pos))
+            },
+            MessageType::DeleteSessionRequest => {
+                let (m, pos) = delete_session_request::Message::parse(buffer)?;
+                Ok((Message::DeleteSessionRequest(m), pos))
+            },
+            MessageType::DeleteSessionResponse => {
+                let (m, pos) = delete_session_response::Message::parse(buffer)?;
+                Ok((Message::DeleteSessionResponse(m), pos))
+            },
+            MessageType::DownlinkDataNotification => {
+                let (m, pos) = downlink_data_notification::Message::parse(buffer)?;
+                Ok((Message::DownlinkDataNotification(m), pos))
+            },
+            MessageType::DownlinkDataNotificationAcknowledge => {
+                let (m, pos) = downlink_data_notification_acknowledge::Message::parse(buffer)?;
+                Ok((Message::DownlinkDataNotificationAcknowledge(m), pos))
+            },
+            MessageType::DownlinkDataNotificationFailureIndication => {
+                let (m, pos) = downlink_data_notification_failure_indication::Message::parse(buffer)?;
+                Ok((Message::DownlinkDataNotificationFailureIndication(m), pos))
+            },
+            MessageType::ContextRequest => {
+                let (m, pos) = context_request::Message::parse(buffer)?;
+                Ok((Message::ContextRequest(m), pos))
+            },
+            MessageType::ContextResponse => {
+                let (m, pos) = context_response::Message::parse(buffer)?;
+                Ok((Message::ContextResponse(m), pos))
+            },
+            MessageType::ContextAcknowledge => {
+                let (m, pos) = context_acknowledge::Message::parse(buffer)?;
+                Ok((Message::ContextAcknowledge(m), pos))
+            },
+            MessageType::ModifyBearerCommand => {
+                let (m, pos) = modify_bearer_command::Message::parse(buffer)?;
+                Ok((Message::ModifyBearerCommand(m), pos))
+            },
+            MessageType::ModifyBearerFailureIndication => {
+                let (m, pos) = modify_bearer_failure_indication::Message::parse(buffer)?;
+                Ok((Message::ModifyBearerFailureIndication(m), pos))
+            },
+            MessageType::BearerResourceCommand => {
+                let (m, pos) = bearer_resource_command::Message::parse(buffer)?;
+                Ok((Message::BearerResourceCommand(m), pos))
+            },
+            MessageType::BearerResourceFailureIndication => {
+                let (m, pos) = bearer_resource_failure_indication::Message::parse(buffer)?;
+                Ok((Message::BearerResourceFailureIndication(m), pos))
+            },
+            MessageType::SuspendNotification => {
+                let (m, pos) = suspend_notification::Message::parse(buffer)?;
+                Ok((Message::SuspendNotification(m), pos))
+            },
+            MessageType::SuspendAcknowledge => {
+                let (m, pos) = suspend_acknowledge::Message::parse(buffer)?;
+                Ok((Message::SuspendAcknowledge(m), pos))
+            },
+            MessageType::ResumeNotification => {
+                let (m, pos) = resume_notification::Message::parse(buffer)?;
+                Ok((Message::ResumeNotification(m), pos))
+            },
+            MessageType::ResumeAcknowledge => {
+                let (m, pos) = resume_acknowledge::Message::parse(buffer)?;
+                Ok((Message::ResumeAcknowledge(m), pos))
+            },
+            MessageType::ForwardRelocationRequest => {
+                let (m, pos) = forward_relocation_request::Message::parse(buffer)?;
+                Ok((Message::ForwardRelocationRequest(m), pos))
+            },
+            MessageType::ReleaseAccessBearersRequest => {
+                let (m, pos) = release_access_bearers_request::Message::parse(buffer)?;
+                Ok((Message::ReleaseAccessBearersRequest(m), pos))
             },
+            MessageType::ModifyAccessBearersRequest => {
+                let (m, pos) = modify_access_bearers_request::Message::parse(buffer)?;
+                Ok((Message::ModifyAccessBearersRequest(m), pos))
+            },
+            MessageType::ReleaseAccessBearersResponse => {
+                let (m, pos) = release_access_bearers_response::Message::parse(buffer)?;
+                Ok((Message::ReleaseAccessBearersResponse(m), pos))
+            },
+            MessageType::ModifyAccessBearersResponse => {
+                let (m, pos) = modify_access_bearers_response::Message::parse(buffer)?;
+                Ok((Message::ModifyAccessBearersResponse(m), pos))
+            },
+            MessageType::RemoteUEReportNotification => {
+                let (m, pos) = remote_ue_report_notification::Message::parse(buffer)?;
+                Ok((Message::RemoteUEReportNotification(m), pos))
+            },
+            MessageType::RemoteUEReportAcknowledge => {
+                let (m, pos) = remote_ue_report_acknowledge::Message::parse(buffer)?;
+                Ok((Message::RemoteUEReportAcknowledge(m), pos))
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsRequest => {
+                let (m, pos) = srvcc_ps_to_cs_request::Message::parse(buffer)?;
+                Ok((Message::SRVCCPsToCsRequest(m), pos))
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsResponse => {
+                let (m, pos) = srvcc_ps_to_cs_response::Message::parse(buffer)?;
+                Ok((Message::SRVCCPsToCsResponse(m), pos))
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteNotification => {
+                let (m, pos) = srvcc_ps_to_cs_complete_notification::Message::parse(buffer)?;
+                Ok((Message::SRVCCPsToCsCompleteNotification(m), pos))
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteAcknowledge => {
+                let (m, pos) = srvcc_ps_to_cs_complete_acknowledge::Message::parse(buffer)?;
+                Ok((Message::SRVCCPsToCsCompleteAcknowledge(m), pos))
+            },
+            message_type => Err(ParseError::UnsupportedMessageType(message_type)),
+        }
+    }
+
+    pub fn check(&self, interface: Interface) -> Vec<Violation> {
+        // The ways the message doesn't conform to TS 29.274 on interface. See gtp_v2::conformance
+        conformance::check(self, interface)
+    }
+
+    pub fn check_plausibility(&self) -> Vec<Warning> {
+        // Whether the identities in the message are consistent. See gtp_v2::conformance
+        conformance::check_plausibility(self)
+    }
+
+    pub fn reject_with(request_type: MessageType, cause: cause::InformationElement) -> Option<Message> {
+        // Builds the response that rejects a request of request_type. Returns None if the request has no response
+        // that can carry a Cause (e.g. Echo Request) or if request_type isn't a request.
+        match request_type {
+            MessageType::CreateSessionRequest => {
+                match create_session_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::CreateSessionResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ModifyBearerRequest => {
+                match modify_bearer_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ModifyBearerResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::DeleteSessionRequest => {
+                match delete_session_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::DeleteSessionResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::DownlinkDataNotification => {
+                match downlink_data_notification_acknowledge::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::DownlinkDataNotificationAcknowledge(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ContextRequest => {
+                match context_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ContextResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ModifyBearerCommand => {
+                match modify_bearer_failure_indication::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ModifyBearerFailureIndication(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::SuspendNotification => {
+                match suspend_acknowledge::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::SuspendAcknowledge(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ResumeNotification => {
+                match resume_acknowledge::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ResumeAcknowledge(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ReleaseAccessBearersRequest => {
+                match release_access_bearers_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ReleaseAccessBearersResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::ModifyAccessBearersRequest => {
+                match modify_access_bearers_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::ModifyAccessBearersResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            MessageType::RemoteUEReportNotification => {
+                match remote_ue_report_acknowledge::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::RemoteUEReportAcknowledge(m)),
+                    Err(_) => None,
+                }
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsRequest => {
+                match srvcc_ps_to_cs_response::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::SRVCCPsToCsResponse(m)),
+                    Err(_) => None,
+                }
+            },
+            #[cfg(feature = "sv-interface")]
+            MessageType::SRVCCPsToCsCompleteNotification => {
+                match srvcc_ps_to_cs_complete_acknowledge::Message::reject_with_cause(cause) {
+                    Ok(m) => Some(Message::SRVCCPsToCsCompleteAcknowledge(m)),
+                    Err(_) => None,
+                }
+            },
+            _ => None,
         }
     }
 }
 
-impl MessageTraits for Message {
-    fn message_type(&self) -> MessageType {
+impl Message {
+    pub fn message_name(&self) -> &'static str {
+        self.message_type().name()
+    }
+
+    pub fn procedure_transaction_id(&self) -> Option<u8> {
+        // The PTI of a message that is part of a UE requested bearer resource procedure
         match self {
-            Message::EchoRequest(m) => m.message_type(),
-            Message::EchoResponse(m) => m.message_type(),
-            Message::CreateSessionRequest(m) => m.message_type(),
-            Message::CreateSessionResponse(m) => m.message_type(),
+            Message::BearerResourceCommand(m) => Some(m.procedure_transaction_id.procedure_transaction_id),
+            Message::BearerResourceFailureIndication(m) => Some(m.procedure_transaction_id.procedure_transaction_id),
+            _ => None,
         }
     }
 
-    fn length(&self) -> u16 {
+    pub fn cause(&self) -> Option<&cause::InformationElement> {
+        // The Cause of a response (or triggered) message. Requests that carry a Cause (e.g. Downlink Data
+        // Notification) aren't included as their Cause doesn't accept or reject anything
         match self {
-            Message::EchoRequest(m) => m.length(),
-            Message::EchoResponse(m) => m.length(),
-            Message::CreateSessionRequest(m) => m.length(),
-            Message::CreateSessionResponse(m) => m.length(),
+            Message::CreateSessionResponse(m) => Some(&m.cause),
+            Message::ModifyBearerResponse(m) => Some(&m.cause),
+            Message::DeleteSessionResponse(m) => Some(&m.cause),
+            Message::DownlinkDataNotificationAcknowledge(m) => Some(&m.cause),
+            Message::DownlinkDataNotificationFailureIndication(m) => Some(&m.cause),
+            Message::ContextResponse(m) => Some(&m.cause),
+            Message::ContextAcknowledge(m) => Some(&m.cause),
+            Message::ModifyBearerFailureIndication(m) => Some(&m.cause),
+            Message::BearerResourceFailureIndication(m) => Some(&m.cause),
+            Message::SuspendAcknowledge(m) => Some(&m.cause),
+            Message::ResumeAcknowledge(m) => Some(&m.cause),
+            Message::ReleaseAccessBearersResponse(m) => Some(&m.cause),
+            Message::ModifyAccessBearersResponse(m) => Some(&m.cause),
+            Message::RemoteUEReportAcknowledge(m) => Some(&m.cause),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => Some(&m.cause),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => Some(&m.cause),
+            _ => None,
         }
     }
 
-    fn generate(&self, buffer: &mut[u8]) -> usize {
+    pub fn is_accepted(&self) -> bool {
+        self.cause().map(|ie| ie.is_accepted()).unwrap_or(false)
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.cause().map(|ie| ie.is_rejected()).unwrap_or(false)
+    }
+
+    pub fn is_temporarily_rejected(&self) -> bool {
+        self.cause().map(|ie| ie.is_temporarily_rejected()).unwrap_or(false)
+    }
+
+    pub fn recovery(&self) -> Option<&recovery::InformationElement> {
         match self {
-            Message::EchoRequest(m) => m.generate(buffer),
-            Message::EchoResponse(m) => m.generate(buffer),
-            Message::CreateSessionRequest(m) => m.generate(buffer),
-            Message::CreateSessionResponse(m) => m.generate(buffer),
+            Message::EchoRequest(m) => Some(m.recovery()),
+            Message::EchoResponse(m) => Some(m.recovery()),
+            Message::CreateSessionRequest(m) => m.recovery.as_ref(),
+            Message::CreateSessionResponse(m) => m.recovery.as_ref(),
+            Message::ModifyBearerRequest(m) => m.recovery.as_ref(),
+            Message::ModifyBearerResponse(m) => m.recovery.as_ref(),
+            Message::DeleteSessionResponse(m) => m.recovery.as_ref(),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.recovery.as_ref(),
+            Message::ModifyBearerFailureIndication(m) => m.recovery.as_ref(),
+            Message::BearerResourceFailureIndication(m) => m.recovery.as_ref(),
+            Message::ReleaseAccessBearersResponse(m) => m.recovery.as_ref(),
+            Message::ModifyAccessBearersResponse(m) => m.recovery.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn set_recovery(&mut self, ie: recovery::InformationElement) -> bool {
+        // Adds the Recovery IE to a message that can carry one but doesn't. Returns true if the message then has one
+        let recovery = match self {
+            Message::EchoRequest(_) | Message::EchoResponse(_) => return true,
+            Message::CreateSessionRequest(m) => &mut m.recovery,
+            Message::CreateSessionResponse(m) => &mut m.recovery,
+            Message::ModifyBearerRequest(m) => &mut m.recovery,
+            Message::ModifyBearerResponse(m) => &mut m.recovery,
+            Message::DeleteSessionResponse(m) => &mut m.recovery,
+            Message::DownlinkDataNotificationAcknowledge(m) => &mut m.recovery,
+            Message::ModifyBearerFailureIndication(m) => &mut m.recovery,
+            Message::BearerResourceFailureIndication(m) => &mut m.recovery,
+            Message::ReleaseAccessBearersResponse(m) => &mut m.recovery,
+            Message::ModifyAccessBearersResponse(m) => &mut m.recovery,
+            _ => return false,
+        };
+
+        if recovery.is_none() {
+            *recovery = Some(ie);
+        }
+
+        true
+    }
+
+    pub fn as_echo_request(&self) -> Option<&echo_request::Message> {
+        match self {
+            Message::EchoRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_echo_response(&self) -> Option<&echo_response::Message> {
+        match self {
+            Message::EchoResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_create_session_request(&self) -> Option<&create_session_request::Message> {
+        match self {
+            Message::CreateSessionRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_create_session_response(&self) -> Option<&create_session_response::Message> {
+        match self {
+            Message::CreateSessionResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_bearer_request(&self) -> Option<&modify_bearer_request::Message> {
+        match self {
+            Message::ModifyBearerRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_bearer_response(&self) -> Option<&modify_bearer_response::Message> {
+        match self {
+            Message::ModifyBearerResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_delete_session_request(&self) -> Option<&delete_session_request::Message> {
+        match self {
+            Message::DeleteSessionRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_delete_session_response(&self) -> Option<&delete_session_response::Message> {
+        match self {
+            Message::DeleteSessionResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_downlink_data_notification(&self) -> Option<&downlink_data_notification::Message> {
+        match self {
+            Message::DownlinkDataNotification(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_downlink_data_notification_acknowledge(&self) -> Option<&downlink_data_notification_acknowledge::Message> {
+        match self {
+            Message::DownlinkDataNotificationAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_downlink_data_notification_failure_indication(&self) -> Option<&downlink_data_notification_failure_indication::Message> {
+        match self {
+            Message::DownlinkDataNotificationFailureIndication(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_context_request(&self) -> Option<&context_request::Message> {
+        match self {
+            Message::ContextRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_context_response(&self) -> Option<&context_response::Message> {
+        match self {
+            Message::ContextResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_context_acknowledge(&self) -> Option<&context_acknowledge::Message> {
+        match self {
+            Message::ContextAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_bearer_command(&self) -> Option<&modify_bearer_command::Message> {
+        match self {
+            Message::ModifyBearerCommand(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_bearer_failure_indication(&self) -> Option<&modify_bearer_failure_indication::Message> {
+        match self {
+            Message::ModifyBearerFailureIndication(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_bearer_resource_command(&self) -> Option<&bearer_resource_command::Message> {
+        match self {
+            Message::BearerResourceCommand(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_bearer_resource_failure_indication(&self) -> Option<&bearer_resource_failure_indication::Message> {
+        match self {
+            Message::BearerResourceFailureIndication(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_suspend_notification(&self) -> Option<&suspend_notification::Message> {
+        match self {
+            Message::SuspendNotification(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_suspend_acknowledge(&self) -> Option<&suspend_acknowledge::Message> {
+        match self {
+            Message::SuspendAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_resume_notification(&self) -> Option<&resume_notification::Message> {
+        match self {
+            Message::ResumeNotification(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_resume_acknowledge(&self) -> Option<&resume_acknowledge::Message> {
+        match self {
+            Message::ResumeAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_forward_relocation_request(&self) -> Option<&forward_relocation_request::Message> {
+        match self {
+            Message::ForwardRelocationRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_release_access_bearers_request(&self) -> Option<&release_access_bearers_request::Message> {
+        match self {
+            Message::ReleaseAccessBearersRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_access_bearers_request(&self) -> Option<&modify_access_bearers_request::Message> {
+        match self {
+            Message::ModifyAccessBearersRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_release_access_bearers_response(&self) -> Option<&release_access_bearers_response::Message> {
+        match self {
+            Message::ReleaseAccessBearersResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_modify_access_bearers_response(&self) -> Option<&modify_access_bearers_response::Message> {
+        match self {
+            Message::ModifyAccessBearersResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_remote_ue_report_notification(&self) -> Option<&remote_ue_report_notification::Message> {
+        match self {
+            Message::RemoteUEReportNotification(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    pub fn as_remote_ue_report_acknowledge(&self) -> Option<&remote_ue_report_acknowledge::Message> {
+        match self {
+            Message::RemoteUEReportAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "sv-interface")]
+    pub fn as_srvcc_ps_to_cs_request(&self) -> Option<&srvcc_ps_to_cs_request::Message> {
+        match self {
+            Message::SRVCCPsToCsRequest(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "sv-interface")]
+    pub fn as_srvcc_ps_to_cs_response(&self) -> Option<&srvcc_ps_to_cs_response::Message> {
+        match self {
+            Message::SRVCCPsToCsResponse(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "sv-interface")]
+    pub fn as_srvcc_ps_to_cs_complete_notification(&self) -> Option<&srvcc_ps_to_cs_complete_notification::Message> {
+        match self {
+            Message::SRVCCPsToCsCompleteNotification(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    #[cfg(feature = "sv-interface")]
+    pub fn as_srvcc_ps_to_cs_complete_acknowledge(&self) -> Option<&srvcc_ps_to_cs_complete_acknowledge::Message> {
+        match self {
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => Some(m),
+            _ => None,
+        }
+    }
+}
+
+impl From<echo_request::Message> for Message {
+    fn from(m: echo_request::Message) -> Self {
+        Message::EchoRequest(m)
+    }
+}
+
+impl TryFrom<Message> for echo_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::EchoRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<echo_response::Message> for Message {
+    fn from(m: echo_response::Message) -> Self {
+        Message::EchoResponse(m)
+    }
+}
+
+impl TryFrom<Message> for echo_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::EchoResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<create_session_request::Message> for Message {
+    fn from(m: create_session_request::Message) -> Self {
+        Message::CreateSessionRequest(m)
+    }
+}
+
+impl TryFrom<Message> for create_session_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::CreateSessionRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<create_session_response::Message> for Message {
+    fn from(m: create_session_response::Message) -> Self {
+        Message::CreateSessionResponse(m)
+    }
+}
+
+impl TryFrom<Message> for create_session_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::CreateSessionResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_bearer_request::Message> for Message {
+    fn from(m: modify_bearer_request::Message) -> Self {
+        Message::ModifyBearerRequest(m)
+    }
+}
+
+impl TryFrom<Message> for modify_bearer_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyBearerRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_bearer_response::Message> for Message {
+    fn from(m: modify_bearer_response::Message) -> Self {
+        Message::ModifyBearerResponse(m)
+    }
+}
+
+impl TryFrom<Message> for modify_bearer_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyBearerResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<delete_session_request::Message> for Message {
+    fn from(m: delete_session_request::Message) -> Self {
+        Message::DeleteSessionRequest(m)
+    }
+}
+
+impl TryFrom<Message> for delete_session_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::DeleteSessionRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<delete_session_response::Message> for Message {
+    fn from(m: delete_session_response::Message) -> Self {
+        Message::DeleteSessionResponse(m)
+    }
+}
+
+impl TryFrom<Message> for delete_session_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::DeleteSessionResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<downlink_data_notification::Message> for Message {
+    fn from(m: downlink_data_notification::Message) -> Self {
+        Message::DownlinkDataNotification(m)
+    }
+}
+
+impl TryFrom<Message> for downlink_data_notification::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::DownlinkDataNotification(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<downlink_data_notification_acknowledge::Message> for Message {
+    fn from(m: downlink_data_notification_acknowledge::Message) -> Self {
+        Message::DownlinkDataNotificationAcknowledge(m)
+    }
+}
+
+impl TryFrom<Message> for downlink_data_notification_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::DownlinkDataNotificationAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<downlink_data_notification_failure_indication::Message> for Message {
+    fn from(m: downlink_data_notification_failure_indication::Message) -> Self {
+        Message::DownlinkDataNotificationFailureIndication(m)
+    }
+}
+
+impl TryFrom<Message> for downlink_data_notification_failure_indication::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::DownlinkDataNotificationFailureIndication(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<context_request::Message> for Message {
+    fn from(m: context_request::Message) -> Self {
+        Message::ContextRequest(m)
+    }
+}
+
+impl TryFrom<Message> for context_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ContextRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<context_response::Message> for Message {
+    fn from(m: context_response::Message) -> Self {
+        Message::ContextResponse(m)
+    }
+}
+
+impl TryFrom<Message> for context_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ContextResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<context_acknowledge::Message> for Message {
+    fn from(m: context_acknowledge::Message) -> Self {
+        Message::ContextAcknowledge(m)
+    }
+}
+
+impl TryFrom<Message> for context_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ContextAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_bearer_command::Message> for Message {
+    fn from(m: modify_bearer_command::Message) -> Self {
+        Message::ModifyBearerCommand(m)
+    }
+}
+
+impl TryFrom<Message> for modify_bearer_command::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyBearerCommand(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_bearer_failure_indication::Message> for Message {
+    fn from(m: modify_bearer_failure_indication::Message) -> Self {
+        Message::ModifyBearerFailureIndication(m)
+    }
+}
+
+impl TryFrom<Message> for modify_bearer_failure_indication::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyBearerFailureIndication(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<bearer_resource_command::Message> for Message {
+    fn from(m: bearer_resource_command::Message) -> Self {
+        Message::BearerResourceCommand(m)
+    }
+}
+
+impl TryFrom<Message> for bearer_resource_command::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::BearerResourceCommand(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<bearer_resource_failure_indication::Message> for Message {
+    fn from(m: bearer_resource_failure_indication::Message) -> Self {
+        Message::BearerResourceFailureIndication(m)
+    }
+}
+
+impl TryFrom<Message> for bearer_resource_failure_indication::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::BearerResourceFailureIndication(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<suspend_notification::Message> for Message {
+    fn from(m: suspend_notification::Message) -> Self {
+        Message::SuspendNotification(m)
+    }
+}
+
+impl TryFrom<Message> for suspend_notification::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SuspendNotification(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<suspend_acknowledge::Message> for Message {
+    fn from(m: suspend_acknowledge::Message) -> Self {
+        Message::SuspendAcknowledge(m)
+    }
+}
+
+impl TryFrom<Message> for suspend_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SuspendAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<resume_notification::Message> for Message {
+    fn from(m: resume_notification::Message) -> Self {
+        Message::ResumeNotification(m)
+    }
+}
+
+impl TryFrom<Message> for resume_notification::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ResumeNotification(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<resume_acknowledge::Message> for Message {
+    fn from(m: resume_acknowledge::Message) -> Self {
+        Message::ResumeAcknowledge(m)
+    }
+}
+
+impl TryFrom<Message> for resume_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ResumeAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<forward_relocation_request::Message> for Message {
+    fn from(m: forward_relocation_request::Message) -> Self {
+        Message::ForwardRelocationRequest(m)
+    }
+}
+
+impl TryFrom<Message> for forward_relocation_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ForwardRelocationRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<release_access_bearers_request::Message> for Message {
+    fn from(m: release_access_bearers_request::Message) -> Self {
+        Message::ReleaseAccessBearersRequest(m)
+    }
+}
+
+impl TryFrom<Message> for release_access_bearers_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ReleaseAccessBearersRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_access_bearers_request::Message> for Message {
+    fn from(m: modify_access_bearers_request::Message) -> Self {
+        Message::ModifyAccessBearersRequest(m)
+    }
+}
+
+impl TryFrom<Message> for modify_access_bearers_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyAccessBearersRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<release_access_bearers_response::Message> for Message {
+    fn from(m: release_access_bearers_response::Message) -> Self {
+        Message::ReleaseAccessBearersResponse(m)
+    }
+}
+
+impl TryFrom<Message> for release_access_bearers_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ReleaseAccessBearersResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<modify_access_bearers_response::Message> for Message {
+    fn from(m: modify_access_bearers_response::Message) -> Self {
+        Message::ModifyAccessBearersResponse(m)
+    }
+}
+
+impl TryFrom<Message> for modify_access_bearers_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::ModifyAccessBearersResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<remote_ue_report_notification::Message> for Message {
+    fn from(m: remote_ue_report_notification::Message) -> Self {
+        Message::RemoteUEReportNotification(m)
+    }
+}
+
+impl TryFrom<Message> for remote_ue_report_notification::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::RemoteUEReportNotification(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl From<remote_ue_report_acknowledge::Message> for Message {
+    fn from(m: remote_ue_report_acknowledge::Message) -> Self {
+        Message::RemoteUEReportAcknowledge(m)
+    }
+}
+
+impl TryFrom<Message> for remote_ue_report_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::RemoteUEReportAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl From<srvcc_ps_to_cs_request::Message> for Message {
+    fn from(m: srvcc_ps_to_cs_request::Message) -> Self {
+        Message::SRVCCPsToCsRequest(m)
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl TryFrom<Message> for srvcc_ps_to_cs_request::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SRVCCPsToCsRequest(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl From<srvcc_ps_to_cs_response::Message> for Message {
+    fn from(m: srvcc_ps_to_cs_response::Message) -> Self {
+        Message::SRVCCPsToCsResponse(m)
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl TryFrom<Message> for srvcc_ps_to_cs_response::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SRVCCPsToCsResponse(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl From<srvcc_ps_to_cs_complete_notification::Message> for Message {
+    fn from(m: srvcc_ps_to_cs_complete_notification::Message) -> Self {
+        Message::SRVCCPsToCsCompleteNotification(m)
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl TryFrom<Message> for srvcc_ps_to_cs_complete_notification::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SRVCCPsToCsCompleteNotification(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl From<srvcc_ps_to_cs_complete_acknowledge::Message> for Message {
+    fn from(m: srvcc_ps_to_cs_complete_acknowledge::Message) -> Self {
+        Message::SRVCCPsToCsCompleteAcknowledge(m)
+    }
+}
+
+#[cfg(feature = "sv-interface")]
+impl TryFrom<Message> for srvcc_ps_to_cs_complete_acknowledge::Message {
+    type Error = Message;
+
+    fn try_from(m: Message) -> Result<Self, Self::Error> {
+        match m {
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => Ok(m),
+            m => Err(m),
+        }
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        match self {
+            Message::EchoRequest(m) => m.message_type(),
+            Message::EchoResponse(m) => m.message_type(),
+            Message::CreateSessionRequest(m) => m.message_type(),
+            Message::CreateSessionResponse(m) => m.message_type(),
+            Message::ModifyBearerRequest(m) => m.message_type(),
+            Message::ModifyBearerResponse(m) => m.message_type(),
+            Message::DeleteSessionRequest(m) => m.message_type(),
+            Message::DeleteSessionResponse(m) => m.message_type(),
+            Message::DownlinkDataNotification(m) => m.message_type(),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.message_type(),
+            Message::DownlinkDataNotificationFailureIndication(m) => m.message_type(),
+            Message::ContextRequest(m) => m.message_type(),
+            Message::ContextResponse(m) => m.message_type(),
+            Message::ContextAcknowledge(m) => m.message_type(),
+            Message::ModifyBearerCommand(m) => m.message_type(),
+            Message::ModifyBearerFailureIndication(m) => m.message_type(),
+            Message::BearerResourceCommand(m) => m.message_type(),
+            Message::BearerResourceFailureIndication(m) => m.message_type(),
+            Message::SuspendNotification(m) => m.message_type(),
+            Message::SuspendAcknowledge(m) => m.message_type(),
+            Message::ResumeNotification(m) => m.message_type(),
+            Message::ResumeAcknowledge(m) => m.message_type(),
+            Message::ForwardRelocationRequest(m) => m.message_type(),
+            Message::ReleaseAccessBearersRequest(m) => m.message_type(),
+            Message::ModifyAccessBearersRequest(m) => m.message_type(),
+            Message::ReleaseAccessBearersResponse(m) => m.message_type(),
+            Message::ModifyAccessBearersResponse(m) => m.message_type(),
+            Message::RemoteUEReportNotification(m) => m.message_type(),
+            Message::RemoteUEReportAcknowledge(m) => m.message_type(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsRequest(m) => m.message_type(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => m.message_type(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteNotification(m) => m.message_type(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => m.message_type(),
+        }
+    }
+
+    fn length(&self) -> u16 {
+        match self {
+            Message::EchoRequest(m) => m.length(),
+            Message::EchoResponse(m) => m.length(),
+            Message::CreateSessionRequest(m) => m.length(),
+            Message::CreateSessionResponse(m) => m.length(),
+            Message::ModifyBearerRequest(m) => m.length(),
+            Message::ModifyBearerResponse(m) => m.length(),
+            Message::DeleteSessionRequest(m) => m.length(),
+            Message::DeleteSessionResponse(m) => m.length(),
+            Message::DownlinkDataNotification(m) => m.length(),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.length(),
+            Message::DownlinkDataNotificationFailureIndication(m) => m.length(),
+            Message::ContextRequest(m) => m.length(),
+            Message::ContextResponse(m) => m.length(),
+            Message::ContextAcknowledge(m) => m.length(),
+            Message::ModifyBearerCommand(m) => m.length(),
+            Message::ModifyBearerFailureIndication(m) => m.length(),
+            Message::BearerResourceCommand(m) => m.length(),
+            Message::BearerResourceFailureIndication(m) => m.length(),
+            Message::SuspendNotification(m) => m.length(),
+            Message::SuspendAcknowledge(m) => m.length(),
+            Message::ResumeNotification(m) => m.length(),
+            Message::ResumeAcknowledge(m) => m.length(),
+            Message::ForwardRelocationRequest(m) => m.length(),
+            Message::ReleaseAccessBearersRequest(m) => m.length(),
+            Message::ModifyAccessBearersRequest(m) => m.length(),
+            Message::ReleaseAccessBearersResponse(m) => m.length(),
+            Message::ModifyAccessBearersResponse(m) => m.length(),
+            Message::RemoteUEReportNotification(m) => m.length(),
+            Message::RemoteUEReportAcknowledge(m) => m.length(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsRequest(m) => m.length(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => m.length(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteNotification(m) => m.length(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => m.length(),
+        }
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        match self {
+            Message::EchoRequest(m) => m.generate(buffer),
+            Message::EchoResponse(m) => m.generate(buffer),
+            Message::CreateSessionRequest(m) => m.generate(buffer),
+            Message::CreateSessionResponse(m) => m.generate(buffer),
+            Message::ModifyBearerRequest(m) => m.generate(buffer),
+            Message::ModifyBearerResponse(m) => m.generate(buffer),
+            Message::DeleteSessionRequest(m) => m.generate(buffer),
+            Message::DeleteSessionResponse(m) => m.generate(buffer),
+            Message::DownlinkDataNotification(m) => m.generate(buffer),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.generate(buffer),
+            Message::DownlinkDataNotificationFailureIndication(m) => m.generate(buffer),
+            Message::ContextRequest(m) => m.generate(buffer),
+            Message::ContextResponse(m) => m.generate(buffer),
+            Message::ContextAcknowledge(m) => m.generate(buffer),
+            Message::ModifyBearerCommand(m) => m.generate(buffer),
+            Message::ModifyBearerFailureIndication(m) => m.generate(buffer),
+            Message::BearerResourceCommand(m) => m.generate(buffer),
+            Message::BearerResourceFailureIndication(m) => m.generate(buffer),
+            Message::SuspendNotification(m) => m.generate(buffer),
+            Message::SuspendAcknowledge(m) => m.generate(buffer),
+            Message::ResumeNotification(m) => m.generate(buffer),
+            Message::ResumeAcknowledge(m) => m.generate(buffer),
+            Message::ForwardRelocationRequest(m) => m.generate(buffer),
+            Message::ReleaseAccessBearersRequest(m) => m.generate(buffer),
+            Message::ModifyAccessBearersRequest(m) => m.generate(buffer),
+            Message::ReleaseAccessBearersResponse(m) => m.generate(buffer),
+            Message::ModifyAccessBearersResponse(m) => m.generate(buffer),
+            Message::RemoteUEReportNotification(m) => m.generate(buffer),
+            Message::RemoteUEReportAcknowledge(m) => m.generate(buffer),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsRequest(m) => m.generate(buffer),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => m.generate(buffer),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteNotification(m) => m.generate(buffer),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => m.generate(buffer),
+        }
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        match self {
+            Message::EchoRequest(m) => m.ies(),
+            Message::EchoResponse(m) => m.ies(),
+            Message::CreateSessionRequest(m) => m.ies(),
+            Message::CreateSessionResponse(m) => m.ies(),
+            Message::ModifyBearerRequest(m) => m.ies(),
+            Message::ModifyBearerResponse(m) => m.ies(),
+            Message::DeleteSessionRequest(m) => m.ies(),
+            Message::DeleteSessionResponse(m) => m.ies(),
+            Message::DownlinkDataNotification(m) => m.ies(),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.ies(),
+            Message::DownlinkDataNotificationFailureIndication(m) => m.ies(),
+            Message::ContextRequest(m) => m.ies(),
+            Message::ContextResponse(m) => m.ies(),
+            Message::ContextAcknowledge(m) => m.ies(),
+            Message::ModifyBearerCommand(m) => m.ies(),
+            Message::ModifyBearerFailureIndication(m) => m.ies(),
+            Message::BearerResourceCommand(m) => m.ies(),
+            Message::BearerResourceFailureIndication(m) => m.ies(),
+            Message::SuspendNotification(m) => m.ies(),
+            Message::SuspendAcknowledge(m) => m.ies(),
+            Message::ResumeNotification(m) => m.ies(),
+            Message::ResumeAcknowledge(m) => m.ies(),
+            Message::ForwardRelocationRequest(m) => m.ies(),
+            Message::ReleaseAccessBearersRequest(m) => m.ies(),
+            Message::ModifyAccessBearersRequest(m) => m.ies(),
+            Message::ReleaseAccessBearersResponse(m) => m.ies(),
+            Message::ModifyAccessBearersResponse(m) => m.ies(),
+            Message::RemoteUEReportNotification(m) => m.ies(),
+            Message::RemoteUEReportAcknowledge(m) => m.ies(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsRequest(m) => m.ies(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => m.ies(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteNotification(m) => m.ies(),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => m.ies(),
+        }
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            Message::EchoRequest(m) => m.dissect(d),
+            Message::EchoResponse(m) => m.dissect(d),
+            Message::CreateSessionRequest(m) => m.dissect(d),
+            Message::CreateSessionResponse(m) => m.dissect(d),
+            Message::ModifyBearerRequest(m) => m.dissect(d),
+            Message::ModifyBearerResponse(m) => m.dissect(d),
+            Message::DeleteSessionRequest(m) => m.dissect(d),
+            Message::DeleteSessionResponse(m) => m.dissect(d),
+            Message::DownlinkDataNotification(m) => m.dissect(d),
+            Message::DownlinkDataNotificationAcknowledge(m) => m.dissect(d),
+            Message::DownlinkDataNotificationFailureIndication(m) => m.dissect(d),
+            Message::ContextRequest(m) => m.dissect(d),
+            Message::ContextResponse(m) => m.dissect(d),
+            Message::ContextAcknowledge(m) => m.dissect(d),
+            Message::ModifyBearerCommand(m) => m.dissect(d),
+            Message::ModifyBearerFailureIndication(m) => m.dissect(d),
+            Message::BearerResourceCommand(m) => m.dissect(d),
+            Message::BearerResourceFailureIndication(m) => m.dissect(d),
+            Message::SuspendNotification(m) => m.dissect(d),
+            Message::SuspendAcknowledge(m) => m.dissect(d),
+            Message::ResumeNotification(m) => m.dissect(d),
+            Message::ResumeAcknowledge(m) => m.dissect(d),
+            Message::ForwardRelocationRequest(m) => m.dissect(d),
+            Message::ReleaseAccessBearersRequest(m) => m.dissect(d),
+            Message::ModifyAccessBearersRequest(m) => m.dissect(d),
+            Message::ReleaseAccessBearersResponse(m) => m.dissect(d),
+            Message::ModifyAccessBearersResponse(m) => m.dissect(d),
+            Message::RemoteUEReportNotification(m) => m.dissect(d),
+            Message::RemoteUEReportAcknowledge(m) => m.dissect(d),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsRequest(m) => m.dissect(d),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsResponse(m) => m.dissect(d),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteNotification(m) => m.dissect(d),
+            #[cfg(feature = "sv-interface")]
+            Message::SRVCCPsToCsCompleteAcknowledge(m) => m.dissect(d),
+        }
+    }
+}
+
+pub fn ignore_ie(message_type: MessageType, ie: &information_elements::InformationElement) {
+    // An IE that doesn't belong in a message of message_type is ignored. It is only reported when logging
+    debug!("Ignoring {:?} IE (instance {}) in {}", ie.information_element_type(), ie.instance(), message_type);
+}
+
+pub fn dissect_message<T: MessageTraits, F: FnOnce(&mut Dissector)>(m: &T, d: &mut Dissector, f: F) {
+    // Writes the message type followed by the IEs of the message written by f
+    let message_type = m.message_type();
+
+    d.tree(&format!("{:?} ({}), Length: {}", message_type, u8::from(message_type), m.length()), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(MessageType::from(1), MessageType::EchoRequest);
+        assert_eq!(MessageType::from(2), MessageType::EchoResponse);
+        assert_eq!(MessageType::from(32), MessageType::CreateSessionRequest);
+        assert_eq!(MessageType::from(33), MessageType::CreateSessionResponse);
+        assert_eq!(MessageType::from(34), MessageType::ModifyBearerRequest);
+        assert_eq!(MessageType::from(176), MessageType::DownlinkDataNotification);
+        assert_eq!(MessageType::from(177), MessageType::DownlinkDataNotificationAcknowledge);
+        assert_eq!(MessageType::from(70), MessageType::DownlinkDataNotificationFailureIndication);
+        assert_eq!(MessageType::from(130), MessageType::ContextRequest);
+        assert_eq!(MessageType::from(131), MessageType::ContextResponse);
+        assert_eq!(MessageType::from(132), MessageType::ContextAcknowledge);
+        assert_eq!(MessageType::from(64), MessageType::ModifyBearerCommand);
+        assert_eq!(MessageType::from(65), MessageType::ModifyBearerFailureIndication);
+        assert_eq!(MessageType::from(68), MessageType::BearerResourceCommand);
+        assert_eq!(MessageType::from(69), MessageType::BearerResourceFailureIndication);
+        assert_eq!(MessageType::from(162), MessageType::SuspendNotification);
+        assert_eq!(MessageType::from(163), MessageType::SuspendAcknowledge);
+        assert_eq!(MessageType::from(164), MessageType::ResumeNotification);
+        assert_eq!(MessageType::from(165), MessageType::ResumeAcknowledge);
+        assert_eq!(MessageType::from(133), MessageType::ForwardRelocationRequest);
+        assert_eq!(MessageType::from(40), MessageType::RemoteUEReportNotification);
+        assert_eq!(MessageType::from(41), MessageType::RemoteUEReportAcknowledge);
+        #[cfg(feature = "sv-interface")]
+        assert_eq!(MessageType::from(25), MessageType::SRVCCPsToCsRequest);
+        #[cfg(feature = "sv-interface")]
+        assert_eq!(MessageType::from(26), MessageType::SRVCCPsToCsResponse);
+        #[cfg(feature = "sv-interface")]
+        assert_eq!(MessageType::from(27), MessageType::SRVCCPsToCsCompleteNotification);
+        #[cfg(feature = "sv-interface")]
+        assert_eq!(MessageType::from(28), MessageType::SRVCCPsToCsCompleteAcknowledge);
+        #[cfg(not(feature = "sv-interface"))]
+        assert_eq!(MessageType::from(25), MessageType::Unknown(25));
+        assert_eq!(MessageType::from(0), MessageType::Unknown(0));
+        assert_eq!(MessageType::from(255), MessageType::Unknown(255));
+
+        // Every value survives the round trip
+        for value in 0..=u8::MAX {
+            assert_eq!(u8::from(MessageType::from(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_message_type_display() {
+        assert_eq!(format!("{}", MessageType::DeleteSessionRequest), "Delete Session Request");
+        assert_eq!(format!("{}", MessageType::from(3)), "Version Not Supported Indication");
+        assert_eq!(format!("{}", MessageType::from(42)), "Unknown (42)");
+    }
+
+    #[test]
+    fn test_teid_required() {
+        assert!(!MessageType::EchoRequest.teid_required());
+        assert!(!MessageType::EchoResponse.teid_required());
+        assert!(!MessageType::VersionNotSupportedIndication.teid_required());
+        assert!(MessageType::CreateSessionRequest.teid_required());
+        assert!(MessageType::ContextAcknowledge.teid_required());
+    }
+
+    #[test]
+    fn test_is_command() {
+        assert!(MessageType::ModifyBearerCommand.is_command());
+        assert!(MessageType::BearerResourceCommand.is_command());
+        assert!(!MessageType::CreateSessionRequest.is_command());
+        assert!(!MessageType::ModifyBearerFailureIndication.is_command());
+    }
+
+    #[test]
+    fn test_is_triggered() {
+        assert!(MessageType::EchoResponse.is_triggered());
+        assert!(MessageType::CreateSessionResponse.is_triggered());
+        assert!(MessageType::BearerResourceFailureIndication.is_triggered());
+        assert!(!MessageType::CreateSessionRequest.is_triggered());
+        assert!(!MessageType::ModifyBearerRequest.is_triggered());
+        assert!(!MessageType::BearerResourceCommand.is_triggered());
+        assert!(!MessageType::DownlinkDataNotificationFailureIndication.is_triggered());
+    }
+
+    #[test]
+    fn test_ies() {
+        let mut m = echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap());
+        m.sending_node_features = Some(information_elements::node_features::InformationElement::new(0x01, 0).unwrap());
+
+        let m = Message::from(m);
+
+        let ies: Vec<(InformationElementType, u8)> = m.ies().map(|ie| (ie.information_element_type(), ie.instance())).collect();
+        assert_eq!(ies, [(InformationElementType::Recovery, 0), (InformationElementType::NodeFeatures, 0)]);
+
+        assert_eq!(m.ies().map(|ie| ie.wire_length()).sum::<u16>(), m.length());
+    }
+
+    #[test]
+    fn test_set_recovery() {
+        let recovery = information_elements::recovery::InformationElement::new(7, 0).unwrap();
+
+        let mut m = Message::CreateSessionResponse(create_session_response::Message::reject_with(cause::CauseCode::NoResourcesAvailable).unwrap());
+        assert_eq!(m.recovery(), None);
+
+        assert!(m.set_recovery(recovery.clone()));
+        assert_eq!(m.recovery(), Some(&recovery));
+
+        // A Recovery IE that is already there isn't replaced
+        assert!(m.set_recovery(information_elements::recovery::InformationElement::new(8, 0).unwrap()));
+        assert_eq!(m.recovery(), Some(&recovery));
+
+        let mut m = Message::from(echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap()));
+        assert!(m.set_recovery(recovery.clone()));
+        assert_eq!(m.recovery().map(|ie| ie.restart_counter), Some(1));
+
+        let mut m = Message::BearerResourceCommand(bearer_resource_command::Message::new(
+            information_elements::ebi::InformationElement::new(5, 0).unwrap(),
+            information_elements::pti::InformationElement::new(0x23, 0).unwrap(),
+            information_elements::tad::InformationElement::new(crate::tft::TrafficFlowTemplate::delete(alloc::vec![1]).unwrap(), 0).unwrap(),
+        ));
+        assert!(!m.set_recovery(recovery));
+        assert_eq!(m.recovery(), None);
+    }
+
+    #[test]
+    fn test_message_name() {
+        assert_eq!(MessageType::EchoRequest.name(), "Echo Request");
+        assert_eq!(MessageType::DownlinkDataNotificationFailureIndication.name(), "Downlink Data Notification Failure Indication");
+
+        let m = Message::from(echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap()));
+        assert_eq!(m.message_name(), "Echo Request");
+    }
+
+    #[test]
+    fn test_procedure_transaction_id() {
+        let m = Message::BearerResourceCommand(bearer_resource_command::Message::new(
+            information_elements::ebi::InformationElement::new(5, 0).unwrap(),
+            information_elements::pti::InformationElement::new(0x23, 0).unwrap(),
+            information_elements::tad::InformationElement::new(crate::tft::TrafficFlowTemplate::delete(alloc::vec![1]).unwrap(), 0).unwrap(),
+        ));
+        assert_eq!(m.procedure_transaction_id(), Some(0x23));
+
+        let m = Message::from(echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap()));
+        assert_eq!(m.procedure_transaction_id(), None);
+    }
+
+    #[test]
+    fn test_cause() {
+        let m = Message::CreateSessionResponse(create_session_response::Message::reject_with(cause::CauseCode::NoResourcesAvailable).unwrap());
+        assert_eq!(m.cause().map(|ie| ie.cause_code), Some(cause::CauseCode::NoResourcesAvailable));
+        assert!(m.is_rejected());
+        assert!(m.is_temporarily_rejected());
+        assert!(!m.is_accepted());
+
+        let m = Message::SuspendAcknowledge(suspend_acknowledge::Message::new(
+            cause::InformationElement::new(cause::CauseCode::RequestAccepted, cause::CauseSource::LocalNode, false, false, None, 0).unwrap()
+        ));
+        assert!(m.is_accepted());
+        assert!(!m.is_rejected());
+
+        // Requests have no Cause to check
+        let m = Message::from(echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap()));
+        assert!(m.cause().is_none());
+        assert!(!m.is_accepted());
+        assert!(!m.is_rejected());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let m: Message = echo_request::Message::new(information_elements::recovery::InformationElement::new(1, 0).unwrap()).into();
+
+        if let Some(m) = m.as_echo_request() {
+            assert_eq!(m.message_type(), MessageType::EchoRequest);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(m.as_echo_response().is_none());
+        assert!(m.as_create_session_request().is_none());
+    }
+
+    #[test]
+    fn test_try_from() {
+        let m = Message::from(echo_response::Message::new(information_elements::recovery::InformationElement::new(2, 0).unwrap()));
+
+        // The wrong type gives back the original message
+        if let Err(m) = echo_request::Message::try_from(m) {
+            if let Ok(m) = echo_response::Message::try_from(m) {
+                assert_eq!(m.message_type(), MessageType::EchoResponse);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
         }
     }
 }
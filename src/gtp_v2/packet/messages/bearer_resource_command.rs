@@ -0,0 +1,601 @@
+use core::convert::TryFrom;
+
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    ebi,
+    pti,
+    flow_qos,
+    tad,
+    rat_type,
+    serving_network,
+    user_location_information,
+    f_teid,
+    epco,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug)]
+enum EbiInstance {
+    LinkedEpsBearerId = 0,
+    EpsBearerId = 1,
+}
+
+impl TryFrom<u8> for EbiInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(EbiInstance::LinkedEpsBearerId),
+            1 => Ok(EbiInstance::EpsBearerId),
+            _ => Err(format!("Unsupported EBI Instance ({})", value))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum FTeidInstance {
+    S4USgsnFTeid = 0,
+    S12RncFTeid = 1,
+    SenderFTeidForControlPlane = 2,
+}
+
+impl TryFrom<u8> for FTeidInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FTeidInstance::S4USgsnFTeid),
+            1 => Ok(FTeidInstance::S12RncFTeid),
+            2 => Ok(FTeidInstance::SenderFTeidForControlPlane),
+            _ => Err(format!("Unsupported F-TEID Instance ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Linked EPS Bearer ID (LBI)                  |    Mandatory              |   8.8
+        Procedure Transaction Id (PTI)              |    Mandatory              |   8.35
+        Flow Quality of Service (Flow QoS)          |    Conditional            |   8.16
+        Traffic Aggregate Description (TAD)         |    Mandatory              |   8.20
+        RAT Type                                    |    Conditional            |   8.17
+        Serving Network                             |    Optional               |   8.18
+        User Location Information (ULI)             |    Optional               |   8.21
+        EPS Bearer ID                               |    Conditional            |   8.8
+        Indication Flags                            |    Conditional Optional   |
+        S4-U SGSN F-TEID                            |    Conditional            |   8.22
+        S12 RNC F-TEID                              |    Conditional            |   8.22
+        Protocol Configuration Options (PCO)        |    Optional               |
+        Signalling Priority Indication              |    Conditional Optional   |
+        MME/S4-SGSN's Overload Control Information  |    Optional               |
+        SGW's Overload Control Information          |    Optional               |
+        NBIFOM Container                            |    Conditional Optional   |
+        Extended Protocol Configuration Options     |    Optional               |   8.128
+        Sender F-TEID for Control Plane             |    Conditional Optional   |   8.22
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub linked_eps_bearer_id: ebi::InformationElement,
+    pub procedure_transaction_id: pti::InformationElement,
+    pub flow_qos: Option<flow_qos::InformationElement>,
+    pub traffic_aggregate_description: tad::InformationElement,
+    pub rat_type: Option<rat_type::InformationElement>,
+    pub serving_network: Option<serving_network::InformationElement>,
+    pub uli: Option<user_location_information::InformationElement>,
+    pub eps_bearer_id: Option<ebi::InformationElement>,
+    pub s4_u_sgsn_f_teid: Option<f_teid::InformationElement>,
+    pub s12_rnc_f_teid: Option<f_teid::InformationElement>,
+    pub epco: Option<epco::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(
+        linked_eps_bearer_id: ebi::InformationElement,
+        procedure_transaction_id: pti::InformationElement,
+        traffic_aggregate_description: tad::InformationElement,
+    ) -> Message {
+        Message {
+            linked_eps_bearer_id,
+            procedure_transaction_id,
+            flow_qos: None,
+            traffic_aggregate_description,
+            rat_type: None,
+            serving_network: None,
+            uli: None,
+            eps_bearer_id: None,
+            s4_u_sgsn_f_teid: None,
+            s12_rnc_f_teid: None,
+            epco: None,
+            sender_f_teid_for_control_plane: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut linked_eps_bearer_id: Option<ebi::InformationElement> = None;
+        let mut procedure_transaction_id: Option<pti::InformationElement> = None;
+        let mut flow_qos: Option<flow_qos::InformationElement> = None;
+        let mut traffic_aggregate_description: Option<tad::InformationElement> = None;
+        let mut rat_type: Option<rat_type::InformationElement> = None;
+        let mut serving_network: Option<serving_network::InformationElement> = None;
+        let mut uli: Option<user_location_information::InformationElement> = None;
+        let mut eps_bearer_id: Option<ebi::InformationElement> = None;
+        let mut s4_u_sgsn_f_teid: Option<f_teid::InformationElement> = None;
+        let mut s12_rnc_f_teid: Option<f_teid::InformationElement> = None;
+        let mut epco: Option<epco::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::EBI(ie) => {
+                        if let Ok(instance) = EbiInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                EbiInstance::LinkedEpsBearerId => linked_eps_bearer_id = Some(ie),
+                                EbiInstance::EpsBearerId => eps_bearer_id = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of EBI that we expect. Just ignore it */ }
+                    },
+                    InformationElement::PTI(ie) => procedure_transaction_id = Some(ie),
+                    InformationElement::FlowQoS(ie) => flow_qos = Some(ie),
+                    InformationElement::TAD(ie) => traffic_aggregate_description = Some(ie),
+                    InformationElement::RATType(ie) => rat_type = Some(ie),
+                    InformationElement::ServingNetwork(ie) => serving_network = Some(ie),
+                    InformationElement::ULI(ie) => uli = Some(ie),
+                    InformationElement::FTEID(ie) => {
+                        if let Ok(instance) = FTeidInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FTeidInstance::S4USgsnFTeid => s4_u_sgsn_f_teid = Some(ie),
+                                FTeidInstance::S12RncFTeid => s12_rnc_f_teid = Some(ie),
+                                FTeidInstance::SenderFTeidForControlPlane => sender_f_teid_for_control_plane = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of F-TEID that we expect. Just ignore it */ }
+                    },
+                    InformationElement::EPCO(ie) => epco = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::BearerResourceCommand, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if linked_eps_bearer_id.is_none() {
+            missing.push((InformationElementType::EBI, EbiInstance::LinkedEpsBearerId as u8));
+        }
+
+        if procedure_transaction_id.is_none() {
+            missing.push((InformationElementType::PTI, 0));
+        }
+
+        if traffic_aggregate_description.is_none() {
+            missing.push((InformationElementType::TAD, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                linked_eps_bearer_id: linked_eps_bearer_id.unwrap(),
+                procedure_transaction_id: procedure_transaction_id.unwrap(),
+                flow_qos,
+                traffic_aggregate_description: traffic_aggregate_description.unwrap(),
+                rat_type,
+                serving_network,
+                uli,
+                eps_bearer_id,
+                s4_u_sgsn_f_teid,
+                s12_rnc_f_teid,
+                epco,
+                sender_f_teid_for_control_plane,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::BearerResourceCommand
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.linked_eps_bearer_id.wire_length();
+        length = length + self.procedure_transaction_id.wire_length();
+
+        if let Some(ref ie) = self.flow_qos {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.traffic_aggregate_description.wire_length();
+
+        if let Some(ref ie) = self.rat_type {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.uli {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.eps_bearer_id {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.s4_u_sgsn_f_teid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.s12_rnc_f_teid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.epco {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.linked_eps_bearer_id.generate(&mut buffer[pos..]);
+        pos = pos + self.procedure_transaction_id.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.flow_qos {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.traffic_aggregate_description.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.rat_type {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.uli {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.eps_bearer_id {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.s4_u_sgsn_f_teid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.s12_rnc_f_teid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.epco {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.linked_eps_bearer_id);
+        ies.push(&self.procedure_transaction_id);
+
+        if let Some(ref ie) = self.flow_qos {
+            ies.push(ie);
+        }
+
+        ies.push(&self.traffic_aggregate_description);
+
+        if let Some(ref ie) = self.rat_type {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.uli {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.eps_bearer_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.s4_u_sgsn_f_teid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.s12_rnc_f_teid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.epco {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.linked_eps_bearer_id.dissect(d);
+            self.procedure_transaction_id.dissect(d);
+
+            if let Some(ref ie) = self.flow_qos {
+                ie.dissect(d);
+            }
+
+            self.traffic_aggregate_description.dissect(d);
+
+            if let Some(ref ie) = self.rat_type {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.serving_network {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.uli {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.eps_bearer_id {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s4_u_sgsn_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s12_rnc_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.epco {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    // Create new TFT with one packet filter matching UDP (17)
+    const TAD: [u8; 6] = [0x21, 0x11, 0x00, 0x02, 0x30, 0x11];
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            pti::InformationElement::new(0x23, 0).unwrap(),
+            tad::InformationElement::new(crate::tft::TrafficFlowTemplate::parse(&TAD).unwrap(), 0).unwrap(),
+        );
+
+        m.flow_qos = Some(flow_qos::InformationElement::new(1, 64, 64, 64, 64, 0).unwrap());
+        m.rat_type = Some(rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap());
+        m.eps_bearer_id = Some(ebi::InformationElement::new(6, 1).unwrap());
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // Linked EPS Bearer ID
+            InformationElementType::PTI as u8,
+            0, 1, // Length
+            0, // Spare
+            0x23, // Procedure Transaction ID
+            InformationElementType::FlowQoS as u8,
+            0, 21, // Length
+            0, // Spare
+            1, // QCI
+            0, 0, 0, 0, 0x40,
+            0, 0, 0, 0, 0x40,
+            0, 0, 0, 0, 0x40,
+            0, 0, 0, 0, 0x40,
+            InformationElementType::TAD as u8,
+            0, 6, // Length
+            0, // Spare
+            0x21, 0x11, 0x00, 0x02, 0x30, 0x11, // Traffic Aggregate Description
+            InformationElementType::RATType as u8,
+            0, 1, // Length
+            0, // Spare
+            u8::from(rat_type::RATType::EUTRAN),
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            1, // Spare and Instance
+            6, // EPS Bearer ID
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 5 + 5 + 25 + 10 + 5 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::BearerResourceCommand)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 55);
+            assert_eq!(m.linked_eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(m.procedure_transaction_id.procedure_transaction_id, 0x23);
+            assert_eq!(m.traffic_aggregate_description.traffic_aggregate_description, crate::tft::TrafficFlowTemplate::parse(&TAD).unwrap());
+
+            if let Some(ie) = m.flow_qos {
+                assert_eq!(ie.qci, 1);
+                assert_eq!(ie.max_ul_bitrate(), 64);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.eps_bearer_id {
+                assert_eq!(ie.eps_bearer_id, 6);
+            }
+            else {
+                assert!(false);
+            }
+
+            assert!(m.rat_type.is_some());
+            assert!(m.uli.is_none());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        // Only the EPS Bearer ID (instance 1) is present, not the Linked EPS Bearer ID
+        let message_bytes = message_bytes();
+
+        assert_eq!(
+            Message::parse(&message_bytes[5..]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::EBI, 0)]))
+        );
+
+        assert_eq!(
+            Message::parse(&message_bytes[..35]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::TAD, 0)]))
+        );
+    }
+
+    #[test]
+    fn test_message_parse_epco() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        let mut pco = crate::pco::ProtocolConfigurationOptions::new();
+        pco.push(crate::pco::DNS_SERVER_IPV4_ADDRESS, &[10, 0, 0, 53]).unwrap();
+
+        m.epco = Some(epco::InformationElement::new(pco, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((parsed, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ref ie) = parsed.epco {
+                assert_eq!(ie.pco.get(crate::pco::DNS_SERVER_IPV4_ADDRESS), Some(&[10, 0, 0, 53][..]));
+            }
+            else { assert!(false); }
+
+            assert_eq!(parsed, m);
+        }
+        else { assert!(false); }
+    }
+}
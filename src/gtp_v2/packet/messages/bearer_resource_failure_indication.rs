@@ -0,0 +1,321 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie, bearer_resource_command};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    ebi,
+    pti,
+    recovery,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   8.4
+        Linked EPS Bearer ID                        |    Mandatory              |   8.8
+        Procedure Transaction ID (PTI)              |    Mandatory              |   8.35
+        Indication Flags                            |    Conditional Optional   |
+        PGW's Overload Control Information          |    Optional               |
+        SGW's Overload Control Information          |    Optional               |
+        Recovery                                    |    Conditional            |   8.5
+        NBIFOM Container                            |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: cause::InformationElement,
+    pub linked_eps_bearer_id: ebi::InformationElement,
+    pub procedure_transaction_id: pti::InformationElement,
+    pub recovery: Option<recovery::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(
+        cause: cause::InformationElement,
+        linked_eps_bearer_id: ebi::InformationElement,
+        procedure_transaction_id: pti::InformationElement,
+    ) -> Message {
+        Message {
+            cause,
+            linked_eps_bearer_id,
+            procedure_transaction_id,
+            recovery: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_command(command: &bearer_resource_command::Message, cause: cause::InformationElement) -> Result<Message, String> {
+        // The LBI and PTI are copied from the Bearer Resource Command so that the MME can find the UE requested
+        // procedure that failed
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause, command.linked_eps_bearer_id.clone(), command.procedure_transaction_id.clone()))
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut linked_eps_bearer_id: Option<ebi::InformationElement> = None;
+        let mut procedure_transaction_id: Option<pti::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::EBI(ie) if ie.instance() == 0 => linked_eps_bearer_id = Some(ie),
+                    InformationElement::PTI(ie) => procedure_transaction_id = Some(ie),
+                    InformationElement::Recovery(ie) => recovery = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::BearerResourceFailureIndication, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        if linked_eps_bearer_id.is_none() {
+            missing.push((InformationElementType::EBI, 0));
+        }
+
+        if procedure_transaction_id.is_none() {
+            missing.push((InformationElementType::PTI, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                linked_eps_bearer_id: linked_eps_bearer_id.unwrap(),
+                procedure_transaction_id: procedure_transaction_id.unwrap(),
+                recovery,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::BearerResourceFailureIndication
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.cause.wire_length();
+        length = length + self.linked_eps_bearer_id.wire_length();
+        length = length + self.procedure_transaction_id.wire_length();
+
+        if let Some(ref ie) = self.recovery {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.cause.generate(&mut buffer[pos..]);
+        pos = pos + self.linked_eps_bearer_id.generate(&mut buffer[pos..]);
+        pos = pos + self.procedure_transaction_id.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.recovery {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.cause);
+        ies.push(&self.linked_eps_bearer_id);
+        ies.push(&self.procedure_transaction_id);
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+            self.linked_eps_bearer_id.dissect(d);
+            self.procedure_transaction_id.dissect(d);
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_message() -> Message {
+        Message::new(
+            cause::InformationElement::new(
+                cause::CauseCode::ServiceDenied,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap(),
+            ebi::InformationElement::new(5, 0).unwrap(),
+            pti::InformationElement::new(0x23, 0).unwrap(),
+        )
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::ServiceDenied),
+            0b00000000,
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // Linked EPS Bearer ID
+            InformationElementType::PTI as u8,
+            0, 1, // Length
+            0, // Spare
+            0x23, // Procedure Transaction ID
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 6 + 5 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::BearerResourceFailureIndication)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 16);
+            assert_eq!(m.cause.cause_code, cause::CauseCode::ServiceDenied);
+            assert_eq!(m.linked_eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(m.procedure_transaction_id.procedure_transaction_id, 0x23);
+            assert!(m.recovery.is_none());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = message_bytes();
+
+        assert_eq!(
+            Message::parse(&message_bytes[..11]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::PTI, 0)]))
+        );
+    }
+
+    #[test]
+    fn test_reject_command() {
+        let command = bearer_resource_command::Message::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            pti::InformationElement::new(0x23, 0).unwrap(),
+            crate::gtp_v2::packet::messages::information_elements::tad::InformationElement::new(crate::tft::TrafficFlowTemplate::delete(alloc::vec![1]).unwrap(), 0).unwrap(),
+        );
+
+        if let Ok(m) = Message::reject_command(&command, new_message().cause) {
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+
+        let accepted = cause::InformationElement::new(cause::CauseCode::RequestAccepted, cause::CauseSource::LocalNode, false, false, None, 0).unwrap();
+        assert!(Message::reject_command(&command, accepted).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    f_teid,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   8.4
+        Indication Flags                            |    Conditional            |
+        Forwarding F-TEID                           |    Conditional            |   8.22
+        Bearer Contexts                             |    Conditional            |
+        SGSN Number                                 |    Conditional Optional   |
+        MME number for MT SMS                       |    Optional               |
+        SGSN Identifier for MT SMS                  |    Optional               |
+        MME Identifier for MT SMS                   |    Optional               |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: cause::InformationElement,
+    pub forwarding_f_teid: Option<f_teid::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            forwarding_f_teid: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut forwarding_f_teid: Option<f_teid::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::FTEID(ie) => forwarding_f_teid = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ContextAcknowledge, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                forwarding_f_teid,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ContextAcknowledge
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.cause.wire_length();
+
+        if let Some(ref ie) = self.forwarding_f_teid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.cause.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.forwarding_f_teid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.cause);
+
+        if let Some(ref ie) = self.forwarding_f_teid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.forwarding_f_teid {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            cause::InformationElement::new(
+                cause::CauseCode::RequestAccepted,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap()
+        );
+
+        m.forwarding_f_teid = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::SgwUpfGtpUForDlDataForwarding,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap()
+        );
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::SgwUpfGtpUForDlDataForwarding)),
+            0x12, 0x34, 0x56, 0x78,
+            10, 0, 0, 1,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 6 + 13);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ContextAcknowledge)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::RequestAccepted);
+
+            if let Some(ie) = m.forwarding_f_teid {
+                assert_eq!(ie.teid, 0x12345678);
+                assert_eq!(ie.ipv4_address, Some(Ipv4Addr::new(10,0,0,1)));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(Message::parse(&[]).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+}
@@ -0,0 +1,386 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    imsi,
+    guti,
+    complete_request_message,
+    f_teid,
+    rat_type,
+    serving_network,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Conditional            |   8.3
+        GUTI                                        |    Conditional            |   8.47
+        Routeing Area Identity (RAI)                |    Conditional            |
+        Packet TMSI (P-TMSI)                        |    Conditional            |
+        P-TMSI Signature                            |    Conditional            |
+        Complete TAU Request message                |    Conditional            |   8.46
+        Address and TEID for Control Plane          |    Conditional            |   8.22
+        UDP Source Port Number                      |    Conditional            |
+        RAT Type                                    |    Conditional            |   8.17
+        Indication                                  |    Conditional Optional   |
+        Hop Counter                                 |    Optional               |
+        Target PLMN ID                              |    Conditional Optional   |   8.18
+        MME/SGSN LDN                                |    Optional               |
+        SGSN node name                              |    Optional               |
+        MME node name                               |    Optional               |
+        SGSN number                                 |    Optional               |
+        SGSN Identifier                             |    Optional               |
+        MME Identifier                              |    Optional               |
+        CIoT Optimizations Support Indication       |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub imsi: Option<imsi::InformationElement>,
+    pub guti: Option<guti::InformationElement>,
+    pub complete_tau_request_message: Option<complete_request_message::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub rat_type: Option<rat_type::InformationElement>,
+    pub target_plmn_id: Option<serving_network::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            imsi: None,
+            guti: None,
+            complete_tau_request_message: None,
+            sender_f_teid_for_control_plane: None,
+            rat_type: None,
+            target_plmn_id: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut guti: Option<guti::InformationElement> = None;
+        let mut complete_tau_request_message: Option<complete_request_message::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut rat_type: Option<rat_type::InformationElement> = None;
+        let mut target_plmn_id: Option<serving_network::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::GUTI(ie) => guti = Some(ie),
+                    InformationElement::CompleteRequestMessage(ie) => complete_tau_request_message = Some(ie),
+                    InformationElement::FTEID(ie) => sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::RATType(ie) => rat_type = Some(ie),
+                    InformationElement::ServingNetwork(ie) => target_plmn_id = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ContextRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed. None of the IEs are mandatory so just skip it
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((
+            Message {
+                imsi,
+                guti,
+                complete_tau_request_message,
+                sender_f_teid_for_control_plane,
+                rat_type,
+                target_plmn_id,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ContextRequest
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        if let Some(ref ie) = self.imsi {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.guti {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.complete_tau_request_message {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.rat_type {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.target_plmn_id {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.guti {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.complete_tau_request_message {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.rat_type {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.target_plmn_id {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.guti {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.complete_tau_request_message {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.rat_type {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.target_plmn_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.guti {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.complete_tau_request_message {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.rat_type {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.target_plmn_id {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::PLMN;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        m.guti = Some(guti::InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x8001, 0x02, 0xC0FFEE01, 0).unwrap());
+        m.complete_tau_request_message = Some(
+            complete_request_message::InformationElement::new(
+                complete_request_message::CompleteRequestMessageType::CompleteTAURequestMessage,
+                &[0x17, 0x48, 0x01],
+                0
+            ).unwrap()
+        );
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S10N26MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap()
+        );
+        m.rat_type = Some(rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap());
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::GUTI as u8,
+            0, 10, // Length
+            0, // Spare
+            0x05, 0xF5, 0x10, // MCC / MNC
+            0x80, 0x01, // MME Group ID
+            0x02, // MME Code
+            0xC0, 0xFF, 0xEE, 0x01, // M-TMSI
+            InformationElementType::CompleteRequestMessage as u8,
+            0, 4, // Length
+            0, // Spare
+            complete_request_message::CompleteRequestMessageType::CompleteTAURequestMessage as u8,
+            0x17, 0x48, 0x01, // Complete Request Message
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S10N26MmeGtpC)),
+            0x12, 0x34, 0x56, 0x78,
+            10, 0, 0, 1,
+            InformationElementType::RATType as u8,
+            0, 1, // Length
+            0, // Spare
+            u8::from(rat_type::RATType::EUTRAN),
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 14 + 8 + 13 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ContextRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.imsi.is_none());
+
+            if let Some(ie) = m.guti {
+                assert_eq!(ie.m_tmsi, 0xC0FFEE01);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.complete_tau_request_message {
+                assert_eq!(ie.complete_request_message, vec![0x17, 0x48, 0x01]);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.sender_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.rat_type {
+                assert_eq!(u8::from(ie.rat_type), u8::from(rat_type::RATType::EUTRAN));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
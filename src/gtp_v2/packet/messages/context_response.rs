@@ -0,0 +1,535 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    imsi,
+    mm_context,
+    pdn_connection,
+    f_teid,
+    fqdn,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug)]
+enum FTeidInstance {
+    SenderFTeidForControlPlane = 0,
+    SgwS11S4FTeidForControlPlane = 1,
+}
+
+impl TryFrom<u8> for FTeidInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FTeidInstance::SenderFTeidForControlPlane),
+            1 => Ok(FTeidInstance::SgwS11S4FTeidForControlPlane),
+            _ => Err(format!("Unsupported F-TEID Instance ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   8.4
+        IMSI                                        |    Conditional            |   8.3
+        MME/SGSN/AMF UE MM Context                  |    Conditional            |   8.38
+        MME/SGSN/AMF UE EPS PDN Connections         |    Conditional            |   8.39
+        Sender F-TEID for Control Plane             |    Conditional            |   8.22
+        SGW S11/S4 IP Address and TEID for CP       |    Conditional            |   8.22
+        SGW node name                               |    Conditional            |   8.66
+        Indication Flags                            |    Conditional            |
+        Trace Information                           |    Conditional            |
+        S101 IP Address                             |    Conditional            |
+        S102 IP Address                             |    Conditional            |
+        Subscribed RFSP Index                       |    Conditional            |
+        RFSP Index in Use                           |    Conditional            |
+        UE Time Zone                                |    Conditional Optional   |
+        MME/SGSN LDN                                |    Optional               |
+        MDT Configuration                           |    Conditional Optional   |
+        SGSN node name                              |    Conditional Optional   |
+        MME node name                               |    Conditional Optional   |
+        UCI                                         |    Conditional Optional   |
+        Monitoring Event Information                |    Conditional Optional   |
+        Remaining Running Service Gap Timer         |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: cause::InformationElement,
+    pub imsi: Option<imsi::InformationElement>,
+    pub mm_context: Option<mm_context::InformationElement>,
+    pub pdn_connections: Vec<pdn_connection::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub sgw_s11_s4_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub sgw_node_name: Option<fqdn::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            imsi: None,
+            mm_context: None,
+            pdn_connections: Vec::new(),
+            sender_f_teid_for_control_plane: None,
+            sgw_s11_s4_f_teid_for_control_plane: None,
+            sgw_node_name: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // A response that only carries the Cause e.g. Context not found
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause))
+    }
+
+    pub fn push_pdn_connection(&mut self, pdn_connection: pdn_connection::InformationElement) {
+        self.pdn_connections.push(pdn_connection);
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut mm_context: Option<mm_context::InformationElement> = None;
+        let mut pdn_connections: Vec<pdn_connection::InformationElement> = Vec::new();
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut sgw_s11_s4_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut sgw_node_name: Option<fqdn::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::MMContext(ie) => mm_context = Some(ie),
+                    InformationElement::PDNConnection(ie) => pdn_connections.push(ie),
+                    InformationElement::FTEID(ie) => {
+                        if let Ok(instance) = FTeidInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FTeidInstance::SenderFTeidForControlPlane => sender_f_teid_for_control_plane = Some(ie),
+                                FTeidInstance::SgwS11S4FTeidForControlPlane => sgw_s11_s4_f_teid_for_control_plane = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of F-TEID that we expect. Just ignore it */ }
+                    },
+                    InformationElement::FQDN(ie) => sgw_node_name = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ContextResponse, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                imsi,
+                mm_context,
+                pdn_connections,
+                sender_f_teid_for_control_plane,
+                sgw_s11_s4_f_teid_for_control_plane,
+                sgw_node_name,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ContextResponse
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.cause.wire_length();
+
+        if let Some(ref ie) = self.imsi {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.mm_context {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.pdn_connections {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.cause.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.mm_context {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.pdn_connections {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.cause);
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.mm_context {
+            ies.push(ie);
+        }
+
+        for ie in &self.pdn_connections {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.mm_context {
+                ie.dissect(d);
+            }
+
+            for ie in &self.pdn_connections {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_node_name {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ascii;
+
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, apn, ambr, ebi, bearer_context, bearer_qos};
+
+    use ascii::AsciiString;
+    use std::str::FromStr;
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            cause::InformationElement::new(
+                cause::CauseCode::RequestAccepted,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap()
+        );
+
+        m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+        m.mm_context = Some(
+            mm_context::InformationElement::new(
+                InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets,
+                &[0x12, 0x34, 0x56, 0x78],
+                0
+            ).unwrap()
+        );
+        m.push_pdn_connection(
+            pdn_connection::InformationElement::new(
+                apn::InformationElement::new(AsciiString::from_str("internet").unwrap(), 0).unwrap(),
+                ebi::InformationElement::new(5, 0).unwrap(),
+                f_teid::InformationElement::new(
+                    f_teid::InterfaceType::S5S8PgwGtpC,
+                    0x12345678,
+                    Some(Ipv4Addr::new(10,0,0,3)),
+                    None,
+                    0
+                ).unwrap(),
+                vec![
+                    bearer_context::InformationElement::new(
+                        ebi::InformationElement::new(5, 0).unwrap(),
+                        bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                        0
+                    ).unwrap()
+                ],
+                ambr::InformationElement::new(0x1000, 0x2000, 0).unwrap(),
+                0
+            ).unwrap()
+        );
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S10N26MmeGtpC,
+                0x87654321,
+                Some(Ipv4Addr::new(10,0,0,2)),
+                None,
+                FTeidInstance::SenderFTeidForControlPlane as u8
+            ).unwrap()
+        );
+        m.sgw_s11_s4_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11S4SgwGtpC,
+                0xCAFEF00D,
+                Some(Ipv4Addr::new(10,0,0,4)),
+                None,
+                FTeidInstance::SgwS11S4FTeidForControlPlane as u8
+            ).unwrap()
+        );
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(pos, 6 + 12 + 8 + 82 + 13 + 13);
+
+        assert_eq!(buffer[..26], [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+            InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets as u8,
+            0, 4, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, 0x78, // MM Context
+        ]);
+
+        assert_eq!(buffer[pos-13..pos], [
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            1, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S11S4SgwGtpC)),
+            0xCA, 0xFE, 0xF0, 0x0D,
+            10, 0, 0, 4,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 6 + 12 + 8 + 82 + 13 + 13);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ContextResponse)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::RequestAccepted);
+
+            if let Some(ie) = m.mm_context {
+                assert_eq!(ie.mm_context, vec![0x12, 0x34, 0x56, 0x78]);
+            }
+            else {
+                assert!(false);
+            }
+
+            assert_eq!(m.pdn_connections.len(), 1);
+            assert_eq!(m.pdn_connections[0].linked_eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(m.pdn_connections[0].bearer_contexts.len(), 1);
+            // The F-TEIDs after the PDN Connection must not end up in its Bearer Context
+            assert!(m.pdn_connections[0].bearer_contexts[0].s1_u_enodeb_f_teid.is_none());
+
+            if let Some(ie) = m.sender_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0x87654321);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.sgw_s11_s4_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0xCAFEF00D);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+        ];
+
+        assert_eq!(Message::parse(&message_bytes).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+
+    #[test]
+    fn test_reject_with_cause() {
+        let cause = cause::InformationElement::new(
+            cause::CauseCode::ContextNotFound,
+            cause::CauseSource::LocalNode,
+            false,
+            false,
+            None,
+            0
+        ).unwrap();
+
+        if let Ok(m) = Message::reject_with_cause(cause) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::ContextNotFound);
+            assert_eq!(m.length(), 6);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
 
 use super::information_elements::{
     InformationElement,
     InformationElementTraits,
+    InformationElementType,
     apn,
     bearer_context,
     f_teid,
@@ -19,11 +20,31 @@ use super::information_elements::{
     ambr,
     ue_time_zone,
     charging_characteristics,
+    fqdn,
+    fq_csid,
+    ldn,
+    node_features,
+    serving_plmn_rate_control,
+    apn_rate_control_status,
+    trace_information,
+    uci,
+    ip_address,
+    node_identifier,
+    port_number,
+    epco,
+    recovery,
+    private_extension,
 };
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::dissect::{Dissect, Dissector};
 
-use std::convert::TryFrom;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 #[derive(Copy, Clone, Debug)]
 enum FTeidInstance {
@@ -44,6 +65,109 @@ impl TryFrom<u8> for FTeidInstance
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+enum FqCsidInstance {
+    MmeFqCsid = 0,
+    SgwFqCsid = 1,
+    EPdgFqCsid = 2,
+    TwanFqCsid = 3,
+}
+
+impl TryFrom<u8> for FqCsidInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FqCsidInstance::MmeFqCsid),
+            1 => Ok(FqCsidInstance::SgwFqCsid),
+            2 => Ok(FqCsidInstance::EPdgFqCsid),
+            3 => Ok(FqCsidInstance::TwanFqCsid),
+            _ => Err(format!("Unsupported FQ-CSID Instance ({})", value)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum LdnInstance {
+    MmeS4SgsnLdn = 0,
+    SgwLdn = 1,
+    EPdgLdn = 2,
+    TwanLdn = 3,
+}
+
+impl TryFrom<u8> for LdnInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LdnInstance::MmeS4SgsnLdn),
+            1 => Ok(LdnInstance::SgwLdn),
+            2 => Ok(LdnInstance::EPdgLdn),
+            3 => Ok(LdnInstance::TwanLdn),
+            _ => Err(format!("Unsupported LDN Instance ({})", value)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum FqdnInstance {
+    SgwUNodeName = 0,
+}
+
+impl TryFrom<u8> for FqdnInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FqdnInstance::SgwUNodeName),
+            _ => Err(format!("Unsupported FQDN Instance ({})", value)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum IpAddressInstance {
+    UeLocalIpAddress = 0,
+    HenbLocalIpAddress = 1,
+    MmeS4SgsnIdentifier = 2,
+}
+
+impl TryFrom<u8> for IpAddressInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(IpAddressInstance::UeLocalIpAddress),
+            1 => Ok(IpAddressInstance::HenbLocalIpAddress),
+            2 => Ok(IpAddressInstance::MmeS4SgsnIdentifier),
+            _ => Err(format!("Unsupported IP Address Instance ({})", value)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum PortNumberInstance {
+    UeUdpPort = 0,
+    HenbUdpPort = 1,
+}
+
+impl TryFrom<u8> for PortNumberInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PortNumberInstance::UeUdpPort),
+            1 => Ok(PortNumberInstance::HenbUdpPort),
+            _ => Err(format!("Unsupported Port Number Instance ({})", value)),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 enum BearerContextInstance {
     ToBeCreated = 0,
@@ -63,6 +187,8 @@ impl TryFrom<u8> for BearerContextInstance
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
     /*
         --------------------------------------------|---------------------------|-------------------------                             
@@ -88,26 +214,26 @@ pub struct Message {
         Protocol Configuration Options (PCO)        |    Conditional            |
         Bearer Contexts to be created               |    Mandatory              |   8.28
         Bearer Contexts to be removed               |    Conditional            |
-        Trace Information                           |    Conditional            |
-        Recovery                                    |    Conditional            |
-        MME-FQ-CSID                                 |    Conditional            |
-        SGW-FQ-CSID                                 |    Conditional            |
-        ePDG-FQ-CSID                                |    Conditional            |
-        TWAN-FQ-CSID                                |    Conditional            |
+        Trace Information                           |    Conditional            |   8.31
+        Recovery                                    |    Conditional            |   8.5
+        MME-FQ-CSID                                 |    Conditional            |   8.62
+        SGW-FQ-CSID                                 |    Conditional            |   8.62
+        ePDG-FQ-CSID                                |    Conditional            |   8.62
+        TWAN-FQ-CSID                                |    Conditional            |   8.62
         UE Time Zone                                |    Conditional            |
-        User CSG Information (UCI)                  |    Conditional Optional   |
+        User CSG Information (UCI)                  |    Conditional Optional   |   8.75
         Charging Characteristics                    |    Conditional            |
-        MME/S4-SGSN LDN                             |    Optional               |
-        SGW LDN                                     |    Optional               |
-        ePDG LDN                                    |    Optional               |
-        TWAN LDN                                    |    Optional               |
+        MME/S4-SGSN LDN                             |    Optional               |   8.82
+        SGW LDN                                     |    Optional               |   8.82
+        ePDG LDN                                    |    Optional               |   8.82
+        TWAN LDN                                    |    Optional               |   8.82
         Signalling Priority Indication              |    Conditional Optional   |
-        UE Local IP Address                         |    Conditional Optional   |
-        UE UDP Port                                 |    Conditional Optional   |
+        UE Local IP Address                         |    Conditional Optional   |   8.9
+        UE UDP Port                                 |    Conditional Optional   |   8.51
         Additional Protocol Configuration Options   |    Conditional Optional   |
-        H(e)NB Local IP Address                     |    Conditional Optional   |
-        H(e)NB UDP Port                             |    Conditional Optional   |
-        MME/S4-SGSN Identifier                      |    Conditional Optional   |
+        H(e)NB Local IP Address                     |    Conditional Optional   |   8.9
+        H(e)NB UDP Port                             |    Conditional Optional   |   8.51
+        MME/S4-SGSN Identifier                      |    Conditional Optional   |   8.9
         TWAN Identifier                             |    Conditional Optional   |
         ePDG IP Address                             |    Optional               |
         CN Operator Selection Entity                |    Conditional Optional   |
@@ -120,17 +246,18 @@ pub struct Message {
         WLAN Location Information                   |    Conditional Optional   |
         NBIFOM Container                            |    Conditional Optional   |
         Remote UE Context Connected                 |    Conditional Optional   |
-        3GPP AAA Server Identifier                  |    Optional               |
-        Extended Protocol Configuration Options     |    Conditional Optional   |
-        Serving PLMN Rate Control                   |    Conditional Optional   |
+        3GPP AAA Server Identifier                  |    Optional               |   8.107
+        Extended Protocol Configuration Options     |    Conditional Optional   |   8.128
+        Serving PLMN Rate Control                   |    Conditional Optional   |   8.129
         MO Exception Data Counter                   |    Conditional Optional   |
         UE TCP Port                                 |    Conditional Optional   |
         Mapped UE Usage Type                        |    Conditional Optional   |
         User Location Information for SGW           |    Conditional Optional   |
-        SGW-U Node Name                             |    Conditional Optional   |
+        SGW-U Node Name                             |    Conditional Optional   |   8.66
         Secondary RAT Usage Data Report             |    Conditional Optional   |
         UP Function Selection Indication Flags      |    Conditional Optional   |
-        APN RATE Control Status                     |    Conditional Optional   |
+        APN RATE Control Status                     |    Conditional Optional   |   8.135
+        Sending Node Features                       |    Optional               |   8.83
         Private Extension                           |    Optional               |
         --------------------------------------------|---------------------------|-------------------------
     */
@@ -151,8 +278,31 @@ pub struct Message {
     pub apn_ambr: Option<ambr::InformationElement>,
     pub bearer_contexts_to_be_created: Vec<bearer_context::InformationElement>,
     pub bearer_contexts_to_be_removed: Vec<bearer_context::InformationElement>,
+    pub trace_information: Option<trace_information::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub mme_fq_csid: Option<fq_csid::InformationElement>,
+    pub sgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub epdg_fq_csid: Option<fq_csid::InformationElement>,
+    pub twan_fq_csid: Option<fq_csid::InformationElement>,
     pub ue_time_zone: Option<ue_time_zone::InformationElement>,
+    pub uci: Option<uci::InformationElement>,
     pub charging_characteristics: Option<charging_characteristics::InformationElement>,
+    pub mme_s4_sgsn_ldn: Option<ldn::InformationElement>,
+    pub sgw_ldn: Option<ldn::InformationElement>,
+    pub epdg_ldn: Option<ldn::InformationElement>,
+    pub twan_ldn: Option<ldn::InformationElement>,
+    pub ue_local_ip_address: Option<ip_address::InformationElement>,
+    pub ue_udp_port: Option<port_number::InformationElement>,
+    pub henb_local_ip_address: Option<ip_address::InformationElement>,
+    pub henb_udp_port: Option<port_number::InformationElement>,
+    pub mme_s4_sgsn_identifier: Option<ip_address::InformationElement>, // For PGW triggered SGW restoration (TS 23.007 27.3)
+    pub aaa_server_identifier: Option<node_identifier::InformationElement>, // The 3GPP AAA server of the UE on S2a/S2b
+    pub epco: Option<epco::InformationElement>,
+    pub serving_plmn_rate_control: Option<serving_plmn_rate_control::InformationElement>,
+    pub sgw_u_node_name: Option<fqdn::InformationElement>,
+    pub apn_rate_control_status: Option<apn_rate_control_status::InformationElement>,
+    pub sending_node_features: Option<node_features::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
 }
 
 impl Message {
@@ -168,6 +318,8 @@ impl Message {
             pgw_s5_s8_for_control_plane: None,
             bearer_contexts_to_be_created: Vec::new(),
             bearer_contexts_to_be_removed: Vec::new(),
+            trace_information: None,
+            recovery: None,
             apn,
             imsi: None,
             pdn_type: None,
@@ -180,14 +332,35 @@ impl Message {
             maximum_apn_restriction: None,
             apn_ambr: None,
             ue_time_zone: None,
+            uci: None,
             charging_characteristics: None,
+            mme_fq_csid: None,
+            sgw_fq_csid: None,
+            epdg_fq_csid: None,
+            twan_fq_csid: None,
+            mme_s4_sgsn_ldn: None,
+            sgw_ldn: None,
+            epdg_ldn: None,
+            twan_ldn: None,
+            ue_local_ip_address: None,
+            ue_udp_port: None,
+            henb_local_ip_address: None,
+            henb_udp_port: None,
+            mme_s4_sgsn_identifier: None,
+            aaa_server_identifier: None,
+            epco: None,
+            serving_plmn_rate_control: None,
+            sgw_u_node_name: None,
+            apn_rate_control_status: None,
+            sending_node_features: None,
+            private_extension: None,
         };
 
         m.push_bearer_context_to_be_created(bearer_context_to_be_created);
 
         m
     }
-    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
         // Keep trying to parse IEs until the end of the buffer
         let mut pos = 0;
 
@@ -207,8 +380,34 @@ impl Message {
         let mut apn_ambr: Option<ambr::InformationElement> = None;
         let mut bearer_contexts_to_be_created: Vec<bearer_context::InformationElement> = Vec::new();
         let mut bearer_contexts_to_be_removed: Vec<bearer_context::InformationElement> = Vec::new();
+        let mut trace_information: Option<trace_information::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
         let mut ue_time_zone: Option<ue_time_zone::InformationElement> = None;
+        let mut uci: Option<uci::InformationElement> = None;
         let mut charging_characteristics: Option<charging_characteristics::InformationElement> = None;
+        let mut mme_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut sgw_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut epdg_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut twan_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut mme_s4_sgsn_ldn: Option<ldn::InformationElement> = None;
+        let mut sgw_ldn: Option<ldn::InformationElement> = None;
+        let mut epdg_ldn: Option<ldn::InformationElement> = None;
+        let mut twan_ldn: Option<ldn::InformationElement> = None;
+        let mut ue_local_ip_address: Option<ip_address::InformationElement> = None;
+        let mut ue_udp_port: Option<port_number::InformationElement> = None;
+        let mut henb_local_ip_address: Option<ip_address::InformationElement> = None;
+        let mut henb_udp_port: Option<port_number::InformationElement> = None;
+        let mut mme_s4_sgsn_identifier: Option<ip_address::InformationElement> = None;
+        let mut aaa_server_identifier: Option<node_identifier::InformationElement> = None;
+        let mut epco: Option<epco::InformationElement> = None;
+        let mut serving_plmn_rate_control: Option<serving_plmn_rate_control::InformationElement> = None;
+        let mut sgw_u_node_name: Option<fqdn::InformationElement> = None;
+        let mut apn_rate_control_status: Option<apn_rate_control_status::InformationElement> = None;
+        let mut sending_node_features: Option<node_features::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
 
         while pos < buffer.len()
         {
@@ -224,6 +423,8 @@ impl Message {
                         if let Ok(instance) = BearerContextInstance::try_from(ie.instance()) {
                             match instance
                             {
+                                // The Bearer Level QoS is mandatory in a bearer context to be created
+                                BearerContextInstance::ToBeCreated if ie.bearer_level_qos.is_none() => failed.push((InformationElementType::BearerContext, 0)),
                                 BearerContextInstance::ToBeCreated => bearer_contexts_to_be_created.push(ie),
                                 BearerContextInstance::ToBeRemoved => bearer_contexts_to_be_removed.push(ie)
                             }
@@ -232,6 +433,7 @@ impl Message {
                     },
                     InformationElement::APN(ie) => apn = Some(ie),
                     InformationElement::UETimeZone(ie) => ue_time_zone = Some(ie),
+                    InformationElement::UCI(ie) => uci = Some(ie),
                     InformationElement::SelectionMode(ie) => selection_mode = Some(ie),
                     InformationElement::FTEID(ie) => {
                         if let Ok(instance) = FTeidInstance::try_from(ie.instance()) {
@@ -248,45 +450,155 @@ impl Message {
                     InformationElement::APNRestriction(ie) => maximum_apn_restriction = Some(ie),
                     InformationElement::AMBR(ie) => apn_ambr = Some(ie),
                     InformationElement::ChargingCharacteristics(ie) => charging_characteristics = Some(ie),
-                    _ =>  { /* Its an IE that we didn't expect. Just ignore it */ }
+                    InformationElement::FQCSID(ie) => {
+                        if let Ok(instance) = FqCsidInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FqCsidInstance::MmeFqCsid => mme_fq_csid = Some(ie),
+                                FqCsidInstance::SgwFqCsid => sgw_fq_csid = Some(ie),
+                                FqCsidInstance::EPdgFqCsid => epdg_fq_csid = Some(ie),
+                                FqCsidInstance::TwanFqCsid => twan_fq_csid = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of FQ-CSID that we expect. Just ignore it */ }
+                    },
+                    InformationElement::LDN(ie) => {
+                        if let Ok(instance) = LdnInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                LdnInstance::MmeS4SgsnLdn => mme_s4_sgsn_ldn = Some(ie),
+                                LdnInstance::SgwLdn => sgw_ldn = Some(ie),
+                                LdnInstance::EPdgLdn => epdg_ldn = Some(ie),
+                                LdnInstance::TwanLdn => twan_ldn = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of LDN that we expect. Just ignore it */ }
+                    },
+                    InformationElement::IPAddress(ie) => {
+                        if let Ok(instance) = IpAddressInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                IpAddressInstance::UeLocalIpAddress => ue_local_ip_address = Some(ie),
+                                IpAddressInstance::HenbLocalIpAddress => henb_local_ip_address = Some(ie),
+                                IpAddressInstance::MmeS4SgsnIdentifier => mme_s4_sgsn_identifier = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of IP Address that we expect. Just ignore it */ }
+                    },
+                    InformationElement::PortNumber(ie) => {
+                        if let Ok(instance) = PortNumberInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                PortNumberInstance::UeUdpPort => ue_udp_port = Some(ie),
+                                PortNumberInstance::HenbUdpPort => henb_udp_port = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of Port Number that we expect. Just ignore it */ }
+                    },
+                    InformationElement::FQDN(ie) => {
+                        if let Ok(instance) = FqdnInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FqdnInstance::SgwUNodeName => sgw_u_node_name = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of FQDN that we expect. Just ignore it */ }
+                    },
+                    InformationElement::NodeIdentifier(ie) if ie.instance() == 0 => aaa_server_identifier = Some(ie),
+                    InformationElement::NodeFeatures(ie) => sending_node_features = Some(ie),
+                    InformationElement::ServingPLMNRateControl(ie) => serving_plmn_rate_control = Some(ie),
+                    InformationElement::APNRateControlStatus(ie) => apn_rate_control_status = Some(ie),
+                    InformationElement::TraceInformation(ie) => trace_information = Some(ie),
+                    InformationElement::EPCO(ie) => epco = Some(ie),
+                    InformationElement::Recovery(ie) => recovery = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::CreateSessionRequest, &ie),
                 }
                 pos = pos + ie_pos;
             }
             else {
                 // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
                 pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
             }
         }
 
-        if rat_type.is_some() && 
-            bearer_contexts_to_be_created.len() > 1 &&
-            sender_f_teid_for_control_plane.is_some() &&
-            apn.is_some() {
-                Some((
-                    Message {
-                        rat_type: rat_type.unwrap(),
-                        sender_f_teid_for_control_plane: sender_f_teid_for_control_plane.unwrap(),
-                        bearer_contexts_to_be_created,
-                        bearer_contexts_to_be_removed,
-                        apn: apn.unwrap(),
-                        pgw_s5_s8_for_control_plane,
-                        imsi,
-                        pdn_type,
-                        pdn_address_allocation,
-                        msisdn,
-                        uli,
-                        mei,
-                        serving_network,
-                        selection_mode,
-                        maximum_apn_restriction,
-                        apn_ambr,
-                        ue_time_zone,
-                        charging_characteristics,
-                    }, 
-                    pos
-                ))
-        }
-        else { None }
+        // Collect every mandatory IE that is absent so the sender can be told which IE was missing
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if rat_type.is_none() {
+            missing.push((InformationElementType::RATType, 0));
+        }
+
+        if sender_f_teid_for_control_plane.is_none() {
+            missing.push((InformationElementType::FTEID, FTeidInstance::SenderFTeidForControlPlane as u8));
+        }
+
+        if apn.is_none() {
+            missing.push((InformationElementType::APN, 0));
+        }
+
+        if bearer_contexts_to_be_created.is_empty() {
+            missing.push((InformationElementType::BearerContext, BearerContextInstance::ToBeCreated as u8));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                rat_type: rat_type.unwrap(),
+                sender_f_teid_for_control_plane: sender_f_teid_for_control_plane.unwrap(),
+                bearer_contexts_to_be_created,
+                bearer_contexts_to_be_removed,
+                trace_information,
+                recovery,
+                apn: apn.unwrap(),
+                pgw_s5_s8_for_control_plane,
+                imsi,
+                pdn_type,
+                pdn_address_allocation,
+                msisdn,
+                uli,
+                mei,
+                serving_network,
+                selection_mode,
+                maximum_apn_restriction,
+                apn_ambr,
+                ue_time_zone,
+                uci,
+                charging_characteristics,
+                mme_fq_csid,
+                sgw_fq_csid,
+                epdg_fq_csid,
+                twan_fq_csid,
+                mme_s4_sgsn_ldn,
+                sgw_ldn,
+                epdg_ldn,
+                twan_ldn,
+                ue_local_ip_address,
+                ue_udp_port,
+                henb_local_ip_address,
+                henb_udp_port,
+                mme_s4_sgsn_identifier,
+                aaa_server_identifier,
+                epco,
+                serving_plmn_rate_control,
+                sgw_u_node_name,
+                apn_rate_control_status,
+                sending_node_features,
+                private_extension,
+            },
+            pos
+        ))
+
+    }
+
+    pub fn set_imsi(&mut self, imsi: &str) -> Result<(), String> {
+        self.imsi = Some(imsi::InformationElement::new(imsi, 0)?);
+        Ok(())
     }
 
     pub fn set_pgw_s5_s8_for_control_plane(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
@@ -323,143 +635,654 @@ impl MessageTraits for Message {
         let mut length = 0;
 
         if let Some(ref ie) = self.imsi {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.msisdn {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.mei {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.uli {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.serving_network {
-            length = length + ie.length();
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.rat_type.wire_length();
+
+        length = length + self.sender_f_teid_for_control_plane.wire_length();
+
+        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.apn.wire_length();
+
+        if let Some(ref ie) = self.selection_mode {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.bearer_contexts_to_be_created {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.bearer_contexts_to_be_removed {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.trace_information {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.recovery {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pdn_type {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pdn_address_allocation {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.maximum_apn_restriction {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.apn_ambr {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.mme_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.epdg_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.twan_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.ue_time_zone {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.uci {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.charging_characteristics {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.mme_s4_sgsn_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.epdg_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.twan_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.ue_local_ip_address {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.ue_udp_port {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.henb_local_ip_address {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.henb_udp_port {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.mme_s4_sgsn_identifier {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.aaa_server_identifier {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.epco {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_u_node_name {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.apn_rate_control_status {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sending_node_features {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.msisdn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.mei {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.uli {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.rat_type.generate(&mut buffer[pos..]);
+
+        pos = pos + self.sender_f_teid_for_control_plane.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.apn.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.selection_mode {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.bearer_contexts_to_be_created {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.bearer_contexts_to_be_removed {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.trace_information {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pdn_type {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pdn_address_allocation {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.maximum_apn_restriction {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.apn_ambr {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.mme_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.epdg_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.twan_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.ue_time_zone {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.uci {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.charging_characteristics {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.mme_s4_sgsn_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.epdg_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.twan_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.ue_local_ip_address {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.ue_udp_port {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.henb_local_ip_address {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.henb_udp_port {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.mme_s4_sgsn_identifier {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.aaa_server_identifier {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.epco {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_u_node_name {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.apn_rate_control_status {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sending_node_features {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.msisdn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.mei {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.uli {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            ies.push(ie);
+        }
+
+        ies.push(&self.rat_type);
+
+        ies.push(&self.sender_f_teid_for_control_plane);
+
+        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+            ies.push(ie);
+        }
+
+        ies.push(&self.apn);
+
+        if let Some(ref ie) = self.selection_mode {
+            ies.push(ie);
+        }
+
+        for ie in &self.bearer_contexts_to_be_created {
+            ies.push(ie);
+        }
+
+        for ie in &self.bearer_contexts_to_be_removed {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.trace_information {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pdn_type {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pdn_address_allocation {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.maximum_apn_restriction {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_ambr {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.mme_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.epdg_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.twan_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.ue_time_zone {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.uci {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.charging_characteristics {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.mme_s4_sgsn_ldn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.epdg_ldn {
+            ies.push(ie);
         }
 
-        length = length + self.rat_type.length();
+        if let Some(ref ie) = self.twan_ldn {
+            ies.push(ie);
+        }
 
-        length = length + self.sender_f_teid_for_control_plane.length();
+        if let Some(ref ie) = self.ue_local_ip_address {
+            ies.push(ie);
+        }
 
-        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
-            length = length + ie.length();
+        if let Some(ref ie) = self.ue_udp_port {
+            ies.push(ie);
         }
 
-        length = length + self.apn.length();
+        if let Some(ref ie) = self.henb_local_ip_address {
+            ies.push(ie);
+        }
 
-        if let Some(ref ie) = self.selection_mode {
-            length = length + ie.length();
+        if let Some(ref ie) = self.henb_udp_port {
+            ies.push(ie);
         }
 
-        for ie in &self.bearer_contexts_to_be_created {
-            length = length + ie.length();
+        if let Some(ref ie) = self.mme_s4_sgsn_identifier {
+            ies.push(ie);
         }
 
-        for ie in &self.bearer_contexts_to_be_removed {
-            length = length + ie.length();
+        if let Some(ref ie) = self.aaa_server_identifier {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.pdn_type {
-            length = length + ie.length();
+        if let Some(ref ie) = self.epco {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.pdn_address_allocation {
-            length = length + ie.length();
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.maximum_apn_restriction {
-            length = length + ie.length();
+        if let Some(ref ie) = self.sgw_u_node_name {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.apn_ambr {
-            length = length + ie.length();
+        if let Some(ref ie) = self.apn_rate_control_status {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.ue_time_zone {
-            length = length + ie.length();
+        if let Some(ref ie) = self.sending_node_features {
+            ies.push(ie);
         }
 
-        if let Some(ref ie) = self.charging_characteristics {
-            length = length + ie.length();
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
         }
 
-        length
+        ies.into_iter()
     }
-    fn generate(&self, buffer: &mut[u8]) -> usize {
-        let mut pos = 0;
+}
 
-        if let Some(ref ie) = self.imsi {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.msisdn {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.msisdn {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.mei {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.mei {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.uli {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.uli {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.serving_network {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.serving_network {
+                ie.dissect(d);
+            }
 
-        pos = pos + self.rat_type.generate(&mut buffer[pos..]);
+            self.rat_type.dissect(d);
 
-        pos = pos + self.sender_f_teid_for_control_plane.generate(&mut buffer[pos..]);
+            self.sender_f_teid_for_control_plane.dissect(d);
 
-        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+                ie.dissect(d);
+            }
 
-        pos = pos + self.apn.generate(&mut buffer[pos..]);
+            self.apn.dissect(d);
 
-        if let Some(ref ie) = self.selection_mode {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.selection_mode {
+                ie.dissect(d);
+            }
 
-        for ie in &self.bearer_contexts_to_be_created {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            for ie in &self.bearer_contexts_to_be_created {
+                ie.dissect(d);
+            }
 
-        for ie in &self.bearer_contexts_to_be_removed {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            for ie in &self.bearer_contexts_to_be_removed {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.pdn_type {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.trace_information {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.pdn_address_allocation {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.maximum_apn_restriction {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.pdn_type {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.apn_ambr {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.pdn_address_allocation {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.ue_time_zone {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.maximum_apn_restriction {
+                ie.dissect(d);
+            }
 
-        if let Some(ref ie) = self.charging_characteristics {
-            pos = pos + ie.generate(&mut buffer[pos..]);
-        }
+            if let Some(ref ie) = self.apn_ambr {
+                ie.dissect(d);
+            }
 
-        pos
+            if let Some(ref ie) = self.mme_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.epdg_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.twan_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ue_time_zone {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.uci {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.charging_characteristics {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.mme_s4_sgsn_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.epdg_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.twan_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ue_local_ip_address {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ue_udp_port {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.henb_local_ip_address {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.henb_udp_port {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.mme_s4_sgsn_identifier {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.aaa_server_identifier {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.epco {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.serving_plmn_rate_control {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_u_node_name {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_rate_control_status {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sending_node_features {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
     }
 }
 
@@ -487,13 +1310,18 @@ mod tests {
         apn_restriction,
         ambr,
         ue_time_zone,
+        uci,
         charging_characteristics,
+        cause,
+        fqdn,
     };
     
     use crate::gtp_v2::packet::messages::information_elements::user_location_information::{PLMN, CGI, SAI, RAI, TAI, ECGI, LAI, MeNBID, EMeNBID};
 
     use ascii::{AsciiString, AsciiChar};
 
+    use std::net::IpAddr;
+
     #[test]
     fn test_generate() {
         let mut buffer = [0; MTU];
@@ -509,10 +1337,8 @@ mod tests {
         );
 
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -559,38 +1385,38 @@ mod tests {
             m.uli = Some(
                 user_location_information::InformationElement::new(
                     Some(CGI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1234,
                         0x4321,
                     )),
                     Some(SAI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1234,
                         0x4321,
                     )),
                     Some(RAI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1234,
                         0x4321,
                     )),
                     Some(TAI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1234,
                     )),
                     Some(ECGI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0xF_FF_FF_FF
                     ).unwrap()),
                     Some(LAI::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1234,
                     )),
                     Some(MeNBID::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0xFFFFF,
                     ).unwrap()),
                     Some(EMeNBID::new(
-                        PLMN::new([5,0,5], [0,9,9]),
+                        PLMN::new(505, 99, true).unwrap(),
                         0x1FFFFF,
                     ).unwrap()),
                     0
@@ -599,7 +1425,7 @@ mod tests {
 
             m.mei = Some(mei::InformationElement::new([1,2,3,4,5,6,7,8,9,1,2,3,4,5,6], Some(1), 0).unwrap());
 
-            m.serving_network = Some(serving_network::InformationElement::new(PLMN::new([5,0,5], [0,9,9]), 0).unwrap());
+            m.serving_network = Some(serving_network::InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap());
 
             m.selection_mode = Some(selection_mode::InformationElement::new(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified, 0).unwrap());
 
@@ -607,7 +1433,7 @@ mod tests {
 
             m.apn_ambr = Some(ambr::InformationElement::new(0x12_34_56_78, 0x87_65_43_21, 0).unwrap());
 
-            m.ue_time_zone = Some(ue_time_zone::InformationElement::new(0x40, ue_time_zone::DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap()); // This is +10
+            m.ue_time_zone = Some(ue_time_zone::InformationElement::from_minutes(600, ue_time_zone::DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap());
 
             m.charging_characteristics = Some(charging_characteristics::InformationElement::new(0x1234, 0).unwrap());
 
@@ -655,7 +1481,7 @@ mod tests {
                 InformationElementType::FTEID as u8,
                 0, 9, // Length
                 0, // Spare and Instance
-                (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S11MmeGtpC as u8),
+                (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S11MmeGtpC)),
                 0x12, 0x34, 0x56, 0x78,
                 10, 0, 0, 1,
                 InformationElementType::APN as u8,
@@ -669,7 +1495,7 @@ mod tests {
                 InformationElementType::SelectionMode as u8,
                 0, 1, // Length
                 0, // Spare
-                selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified as u8, // Selection Mode
+                u8::from(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified), // Selection Mode
                 InformationElementType::BearerContext as u8,
                 0, 31, // Length
                 0, // Spare
@@ -689,11 +1515,11 @@ mod tests {
                 InformationElementType::PDNType as u8,
                 0, 1, // Length
                 0, // Spare
-                pdn_type::PDNType::IPv6 as u8, // PDN Type
+                u8::from(pdn_type::PDNType::IPv6), // PDN Type
                 InformationElementType::PDNAddressAllocation as u8,
                 0, 22, // Length
                 0, // Spare
-                pdn_type::PDNType::IPv4v6 as u8, // PDN Type
+                u8::from(pdn_type::PDNType::IPv4v6), // PDN Type
                 128, // prefix
                 0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE,
                 10,0,0,1,
@@ -742,10 +1568,8 @@ mod tests {
         );
 
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -795,10 +1619,8 @@ mod tests {
         );
 
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -827,7 +1649,7 @@ mod tests {
                 apn.unwrap()
             );
 
-            assert_eq!(m.message_type() as u8, MessageType::CreateSessionRequest as u8)
+            assert_eq!(m.message_type(), MessageType::CreateSessionRequest)
 
         }
         else { assert!(false) }
@@ -855,7 +1677,7 @@ mod tests {
             InformationElementType::FTEID as u8,
             0, 9, // Length
             0, // Spare and Instance
-            (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S11MmeGtpC as u8),
+            (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S11MmeGtpC)),
             0x12, 0x34, 0x56, 0x78,
             10, 0, 0, 1,
             InformationElementType::BearerContext as u8,
@@ -885,11 +1707,11 @@ mod tests {
             InformationElementType::PDNType as u8,
             0, 1, // Length
             0, // Spare
-            pdn_type::PDNType::IPv6 as u8, // PDN Type
+            u8::from(pdn_type::PDNType::IPv6), // PDN Type
             InformationElementType::PDNAddressAllocation as u8,
             0, 18, // Length
             0, // Spare
-            pdn_type::PDNType::IPv6 as u8, // PDN Type
+            u8::from(pdn_type::PDNType::IPv6), // PDN Type
             128, // Prefix
             0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE, // IPv6 Address
             InformationElementType::ServingNetwork as u8,
@@ -899,7 +1721,7 @@ mod tests {
             InformationElementType::SelectionMode as u8,
             0, 1, // Length
             0, // Spare
-            selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified as u8, // Selection Mode
+            u8::from(selection_mode::SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified), // Selection Mode
             InformationElementType::APNRestriction as u8,
             0, 1, // Length
             0, // Spare
@@ -920,7 +1742,7 @@ mod tests {
             0x12, 0x34 // Charging Characteristics
         ];
 
-        if let Some((m, _pos)) = Message::parse(&message_bytes){
+        if let Ok((m, _pos)) = Message::parse(&message_bytes){
 
             if let Some(i) = m.imsi {
                 assert_eq!(i.imsi, [5, 0, 5, 0, 1, 3, 4, 8, 5, 0, 9, 0, 4, 0, 4]);
@@ -932,7 +1754,7 @@ mod tests {
             }
             else { assert!(false); }
 
-            assert_eq!(m.rat_type.rat_type as u8, rat_type::RATType::EUTRAN as u8);
+            assert_eq!(u8::from(m.rat_type.rat_type), u8::from(rat_type::RATType::EUTRAN));
             
             if let Some(a) = m.sender_f_teid_for_control_plane.ipv4_address {
                 assert_eq!(a, Ipv4Addr::new(10,0,0,1));
@@ -941,27 +1763,27 @@ mod tests {
                 assert!(false);
             }
             assert_eq!(m.sender_f_teid_for_control_plane.teid, 0x12345678);
-            assert_eq!(m.sender_f_teid_for_control_plane.interface_type as u8, f_teid::InterfaceType::S11MmeGtpC as u8);
+            assert_eq!(u8::from(m.sender_f_teid_for_control_plane.interface_type), u8::from(f_teid::InterfaceType::S11MmeGtpC));
             
             assert_eq!(m.bearer_contexts_to_be_created[0].eps_bearer_id.eps_bearer_id, 7);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.pvi, true);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.pl(), 9);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.pci, false);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.qci, 7);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.guaranteed_ul_bitrate(), 0);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.guaranteed_dl_bitrate(), 0);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.max_ul_bitrate(), 10_000_000);
-            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.max_dl_bitrate(), 10_000_000);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().arp.pvi, false);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().arp.pl, 9);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().arp.pci, true);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().qci, bearer_qos::Qci::Qci7);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().guaranteed_ul_kbps(), 0);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().guaranteed_dl_kbps(), 0);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().max_ul_kbps(), 10_000_000);
+            assert_eq!(m.bearer_contexts_to_be_created[0].bearer_level_qos.as_ref().unwrap().max_dl_kbps(), 10_000_000);
 
             assert_eq!(m.apn.apn, AsciiString::from_ascii("awesome.apn.mnc099.mcc505.gprs").unwrap());
 
             if let Some(pdn_type) = m.pdn_type {
-                assert_eq!(pdn_type.pdn_type as u8, pdn_type::PDNType::IPv6 as u8);
+                assert_eq!(u8::from(pdn_type.pdn_type), u8::from(pdn_type::PDNType::IPv6));
             }
             else { assert!(false); }
 
             if let Some(pdn_address_allocation) = m.pdn_address_allocation {
-                assert_eq!(pdn_address_allocation.pdn_type as u8, pdn_type::PDNType::IPv6 as u8);
+                assert_eq!(u8::from(pdn_address_allocation.pdn_type), u8::from(pdn_type::PDNType::IPv6));
                 assert_eq!(pdn_address_allocation.ipv6_address_and_prefix, Some((Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0xFEED, 0xDEAF, 0xBEAD, 0xFACE), 128)));
             }
             else { assert!(false); }
@@ -974,7 +1796,7 @@ mod tests {
             else { assert!(false); }
 
             if let Some(ie) = m.serving_network {
-                assert_eq!(ie.plmn, PLMN::new([5,0,5],[0,9,9]));
+                assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
             }
             else { assert!(false); }
 
@@ -990,7 +1812,7 @@ mod tests {
 
             if let Some(ie) = m.apn_ambr {
                 assert_eq!(ie.uplink, 0x12_34_56_78);
-                assert_eq!(ie.uplink, 0x87_65_43_21);
+                assert_eq!(ie.downlink, 0x87_65_43_21);
             }
             else { assert!(false); }
 
@@ -1005,5 +1827,362 @@ mod tests {
             }
             else { assert!(false); }
         }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::RATType as u8,
+            0, 1, // Length
+            0, // Spare
+            u8::from(rat_type::RATType::EUTRAN),
+        ];
+
+        if let Err(e) = Message::parse(&message_bytes) {
+            assert_eq!(e, ParseError::MandatoryIEMissing(vec![
+                (InformationElementType::FTEID, 0),
+                (InformationElementType::APN, 0),
+                (InformationElementType::BearerContext, 0),
+            ]));
+
+            let c = e.cause();
+            assert_eq!(c.cause_code, cause::CauseCode::MandatoryIEMissing);
+            assert_eq!(c.cause_source, cause::CauseSource::RemoteNode);
+            assert_eq!(c.offending_ie, Some((InformationElementType::FTEID, 0)));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_incorrect() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let mut pos = m.generate(&mut buffer);
+
+        // Remove the RAT Type value so that the IE fails to parse
+        assert_eq!(buffer[0], InformationElementType::RATType as u8);
+        buffer[2] = 0;
+        buffer.copy_within(5..pos, 4);
+        pos = pos - 1;
+
+        if let Err(e) = Message::parse(&buffer[..pos]) {
+            assert_eq!(e, ParseError::MandatoryIEIncorrect(vec![(InformationElementType::RATType, 0)]));
+
+            let c = e.cause();
+            assert_eq!(c.cause_code, cause::CauseCode::MandatoryIEIncorrect);
+            assert_eq!(c.offending_ie, Some((InformationElementType::RATType, 0)));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    fn new_message() -> Message {
+        Message::new(
+            rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0,
+            ).unwrap(),
+            bearer_context::InformationElement::new(
+                ebi::InformationElement::new(5, 0).unwrap(),
+                bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                0
+            ).unwrap(),
+            apn::InformationElement::new(AsciiString::from_ascii("internet").unwrap(), 0).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_message_parse_sgw_u_node_name() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.sgw_u_node_name = Some(fqdn::InformationElement::new(AsciiString::from_ascii("sgw-u1.epc").unwrap(), 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.sgw_u_node_name {
+                assert_eq!(ie.fqdn, AsciiString::from_ascii("sgw-u1.epc").unwrap());
+                assert_eq!(ie.instance(), 0);
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+
+        // An FQDN with an instance other than 0 isn't the SGW-U node name
+        let mut m = new_message();
+
+        m.sgw_u_node_name = Some(fqdn::InformationElement::new(AsciiString::from_ascii("sgw-u1.epc").unwrap(), 1).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.sgw_u_node_name.is_none());
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_sending_node_features() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.sending_node_features = Some(node_features::InformationElement::new(node_features::CIOT | node_features::S1UN, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.sending_node_features {
+                assert!(ie.ciot());
+                assert!(ie.s1un());
+                assert!(!ie.prn());
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_mme_s4_sgsn_and_aaa_server_identifier() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.mme_s4_sgsn_identifier = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,0,0,9)), 2).unwrap());
+        m.aaa_server_identifier = Some(node_identifier::InformationElement::new(
+            AsciiString::from_ascii("aaa1").unwrap(),
+            AsciiString::from_ascii("epc.org").unwrap(),
+            0
+        ).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(m.length() as usize, pos);
+
+        if let Ok((parsed, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed.mme_s4_sgsn_identifier.map(|ie| ie.ip_address), Some(IpAddr::V4(Ipv4Addr::new(10,0,0,9))));
+            assert_eq!(parsed.aaa_server_identifier, m.aaa_server_identifier);
+
+            // The MME/S4-SGSN Identifier isn't mistaken for the UE or H(e)NB Local IP Address
+            assert!(parsed.ue_local_ip_address.is_none());
+            assert!(parsed.henb_local_ip_address.is_none());
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_uci() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.uci = Some(uci::InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0x1234567, uci::AccessMode::HybridMode, false, true, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.uci {
+                assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
+                assert_eq!(ie.csg_id, 0x1234567);
+                assert_eq!(ie.access_mode, uci::AccessMode::HybridMode);
+                assert!(!ie.lcsg);
+                assert!(ie.cmi);
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_local_addresses() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.ue_local_ip_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,1,10)), 0).unwrap());
+        m.ue_udp_port = Some(port_number::InformationElement::new(4500, 0).unwrap());
+        m.henb_local_ip_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,168,2,20)), 1).unwrap());
+        m.henb_udp_port = Some(port_number::InformationElement::new(4501, 1).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.ue_local_ip_address.map(|ie| ie.ip_address), Some(IpAddr::V4(Ipv4Addr::new(192,168,1,10))));
+            assert_eq!(m.ue_udp_port.map(|ie| ie.port_number), Some(4500));
+            assert_eq!(m.henb_local_ip_address.map(|ie| ie.ip_address), Some(IpAddr::V4(Ipv4Addr::new(192,168,2,20))));
+            assert_eq!(m.henb_udp_port.map(|ie| ie.port_number), Some(4501));
+        }
+        else { assert!(false); }
+
+        // Unexpected instances are ignored
+        let mut m = new_message();
+
+        m.ue_udp_port = Some(port_number::InformationElement::new(4500, 2).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.ue_udp_port.is_none());
+            assert!(m.henb_udp_port.is_none());
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_rate_control() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.serving_plmn_rate_control = Some(serving_plmn_rate_control::InformationElement::new(20, 0, 0).unwrap());
+        m.apn_rate_control_status = Some(apn_rate_control_status::InformationElement::new(100, 2, 50, 0xE3E4_3A00_0000_0000, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.serving_plmn_rate_control {
+                assert_eq!(ie.uplink_rate_limit, 20);
+                assert!(!ie.downlink_applicable());
+            }
+            else { assert!(false); }
+
+            if let Some(ie) = m.apn_rate_control_status {
+                assert_eq!(ie.uplink_packets_allowed, 100);
+                assert_eq!(ie.additional_exception_reports, 2);
+                assert_eq!(ie.downlink_packets_allowed, 50);
+                assert_eq!(ie.validity_time_seconds(), 0xE3E4_3A00);
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_trace_information() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.trace_information = Some(
+            trace_information::InformationElement::new(
+                PLMN::new(505, 99, true).unwrap(),
+                0x123456,
+                [0xFF; 9],
+                [0x00, 0x01],
+                trace_information::MAXIMUM,
+                [0xFF; 12],
+                IpAddr::V4(Ipv4Addr::new(10,0,0,2)),
+                0
+            ).unwrap()
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.trace_information {
+                assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
+                assert_eq!(ie.trace_id(), 0x123456);
+                assert_eq!(ie.session_trace_depth, trace_information::MAXIMUM);
+                assert_eq!(ie.trace_collection_entity, IpAddr::V4(Ipv4Addr::new(10,0,0,2)));
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+    }
+    #[test]
+    fn test_message_parse_fq_csid_ldn() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        let node_id = fq_csid::NodeId::IPv4(Ipv4Addr::new(10,0,0,1));
+
+        m.mme_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![1], 0).unwrap());
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![2], 1).unwrap());
+        m.epdg_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![3], 2).unwrap());
+        m.twan_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![4], 3).unwrap());
+        m.mme_s4_sgsn_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("MME-1").unwrap(), 0).unwrap());
+        m.sgw_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 1).unwrap());
+        m.epdg_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("EPDG-1").unwrap(), 2).unwrap());
+        m.twan_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("TWAN-1").unwrap(), 3).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.mme_fq_csid.map(|ie| ie.csids), Some(vec![1]));
+            assert_eq!(m.sgw_fq_csid.map(|ie| ie.csids), Some(vec![2]));
+            assert_eq!(m.epdg_fq_csid.map(|ie| ie.csids), Some(vec![3]));
+            assert_eq!(m.twan_fq_csid.map(|ie| ie.csids), Some(vec![4]));
+            assert_eq!(m.mme_s4_sgsn_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("MME-1").unwrap()));
+            assert_eq!(m.sgw_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("SGW-1").unwrap()));
+            assert_eq!(m.epdg_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("EPDG-1").unwrap()));
+            assert_eq!(m.twan_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("TWAN-1").unwrap()));
+        }
+        else { assert!(false); }
+    }
+
+    #[test]
+    fn test_dissect() {
+        let mut m = new_message();
+
+        m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+
+        assert_eq!(crate::dissect::dissect(&m), concat!(
+            "CreateSessionRequest (32), Length: 78\n",
+            "    IMSI (1), Length: 8, Instance: 0\n",
+            "        IMSI: 505013485090404\n",
+            "    RATType (82), Length: 1, Instance: 0\n",
+            "        RAT Type: EUTRAN (6)\n",
+            "    FTEID (87), Length: 9, Instance: 0\n",
+            "        Interface Type: S11MmeGtpC (10)\n",
+            "        TEID/GRE Key: 0x12345678\n",
+            "        IPv4 Address: 10.0.0.1\n",
+            "    APN (71), Length: 9, Instance: 0\n",
+            "        APN: internet\n",
+            "    BearerContext (93), Length: 31, Instance: 0\n",
+            "        EBI (73), Length: 1, Instance: 0\n",
+            "            EPS Bearer ID: 5\n",
+            "        BearerQoS (80), Length: 22, Instance: 0\n",
+            "            Pre-emption Capability (PCI): False\n",
+            "            Priority Level (PL): 9\n",
+            "            Pre-emption Vulnerability (PVI): True\n",
+            "            QCI: 9\n",
+            "            Maximum Bit Rate for Uplink: 0 kbps\n",
+            "            Maximum Bit Rate for Downlink: 0 kbps\n",
+            "            Guaranteed Bit Rate for Uplink: 0 kbps\n",
+            "            Guaranteed Bit Rate for Downlink: 0 kbps\n",
+        ));
+    }
+
+    #[test]
+    fn test_message_parse_epco() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        let mut pco = crate::pco::ProtocolConfigurationOptions::new();
+        pco.push(crate::pco::DNS_SERVER_IPV4_ADDRESS, &[10, 0, 0, 53]).unwrap();
+
+        m.epco = Some(epco::InformationElement::new(pco, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((parsed, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ref ie) = parsed.epco {
+                assert_eq!(ie.pco.get(crate::pco::DNS_SERVER_IPV4_ADDRESS), Some(&[10, 0, 0, 53][..]));
+            }
+            else { assert!(false); }
+
+            assert_eq!(parsed, m);
+        }
+        else { assert!(false); }
     }
 }
@@ -1,19 +1,40 @@
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
 
 use super::information_elements::{
     InformationElement,
     InformationElementTraits,
+    InformationElementType,
     cause,
     bearer_context,
     f_teid,
     pdn_address_allocation,
     apn_restriction,
     ambr,
+    charging_id,
+    fq_csid,
+    ldn,
+    epc_timer,
+    change_reporting_action,
+    csg_information_reporting_action,
+    serving_plmn_rate_control,
+    apn_rate_control_status,
+    epco,
+    recovery,
+    fqdn,
+    ip_address,
+    private_extension,
 };
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::dissect::{Dissect, Dissector};
 
-use std::convert::TryFrom;
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::time::Duration;
+
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 #[derive(Copy, Clone, Debug)]
 enum FTeidInstance {
@@ -34,6 +55,44 @@ impl TryFrom<u8> for FTeidInstance
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+enum FqCsidInstance {
+    PgwFqCsid = 0,
+    SgwFqCsid = 1,
+}
+
+impl TryFrom<u8> for FqCsidInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FqCsidInstance::PgwFqCsid),
+            1 => Ok(FqCsidInstance::SgwFqCsid),
+            _ => Err(format!("Unsupported FQ-CSID Instance ({})", value)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+enum LdnInstance {
+    SgwLdn = 0,
+    PgwLdn = 1,
+}
+
+impl TryFrom<u8> for LdnInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(LdnInstance::SgwLdn),
+            1 => Ok(LdnInstance::PgwLdn),
+            _ => Err(format!("Unsupported LDN Instance ({})", value)),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 enum BearerContextInstance {
     ToBeCreated = 0,
@@ -53,14 +112,16 @@ impl TryFrom<u8> for BearerContextInstance
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
     /*
         --------------------------------------------|---------------------------|-------------------------                             
         Information Element                         |   Presence requirement    |   Reference 
         --------------------------------------------|---------------------------|-------------------------
         Cause                                       |    Mandatory              |    
-        Change Reporting Action                     |    Conditional            |   
-        CSG Information Reporting Action            |    Conditional Optional   |
+        Change Reporting Action                     |    Conditional            |   8.61
+        CSG Information Reporting Action            |    Conditional Optional   |   8.76
         H(e)NB Information Reporting                |    Conditional Optional   |
         Sender F-TEID for Control Plane             |    Conditional            |   8.22
         PGW S5/S8 Address for Control Plane or PMIP |    Conditional            |
@@ -71,14 +132,14 @@ pub struct Message {
         Protocol Configuration Options (PCO)        |    Conditional            |
         Bearer Contexts created                     |    Mandatory              |   8.28
         Bearer Contexts marked for removal          |    Conditional            |
-        Recovery                                    |    Conditional            |
-        Charging Gateway Name                       |    Conditional            |
-        Charging Gateway Address                    |    Conditional            |
-        PGW-FQ-CSID                                 |    Conditional            |
-        SGW-FQ-CSID                                 |    Conditional            |
-        SGW LDN                                     |    Optional               |
-        PGW LDN                                     |    Optional               |
-        PGW Back-Off Time                           |    Optional               |
+        Recovery                                    |    Conditional            |   8.5
+        Charging Gateway Name                       |    Conditional            |   8.66
+        Charging Gateway Address                    |    Conditional            |   8.9
+        PGW-FQ-CSID                                 |    Conditional            |   8.62
+        SGW-FQ-CSID                                 |    Conditional            |   8.62
+        SGW LDN                                     |    Optional               |   8.82
+        PGW LDN                                     |    Optional               |   8.82
+        PGW Back-Off Time                           |    Optional               |   8.87
         Additional Protocol Configuration Options   |    Conditional Optional   |
         Trusted WLAN IPv4 Parameters                |    Conditional Optional   |
         Indication Flags                            |    Conditional Optional   |
@@ -89,13 +150,17 @@ pub struct Message {
         PGW Overload Control Information            |    Optional               |
         SGW Overload Control Information            |    Optional               |
         NBIFOM Container                            |    Conditional Optional   |
-        PDN Connection Charging ID                  |    Conditional Optional   |
-        Extended Protocol Configuration Options     |    Conditional Optional   |
+        PDN Connection Charging ID                  |    Conditional Optional   |   8.29
+        Serving PLMN Rate Control                   |    Optional               |   8.129
+        APN Rate Control Status                     |    Optional               |   8.135
+        Extended Protocol Configuration Options     |    Conditional Optional   |   8.128
         Private Extension                           |    Optional               |
         --------------------------------------------|---------------------------|-------------------------
     */
 
     pub cause: cause::InformationElement,
+    pub change_reporting_action: Option<change_reporting_action::InformationElement>,
+    pub csg_information_reporting_action: Option<csg_information_reporting_action::InformationElement>,
     pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
     pub pgw_s5_s8_for_control_plane: Option<f_teid::InformationElement>,
     pub pdn_address_allocation: Option<pdn_address_allocation::InformationElement>,
@@ -103,6 +168,19 @@ pub struct Message {
     pub apn_ambr: Option<ambr::InformationElement>,
     pub bearer_contexts_created: Vec<bearer_context::InformationElement>,
     pub bearer_contexts_marked_for_removal: Vec<bearer_context::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub charging_gateway_name: Option<fqdn::InformationElement>,
+    pub charging_gateway_address: Option<ip_address::InformationElement>,
+    pub pgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub sgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub sgw_ldn: Option<ldn::InformationElement>,
+    pub pgw_ldn: Option<ldn::InformationElement>,
+    pub pgw_back_off_time: Option<epc_timer::InformationElement>,
+    pub pdn_connection_charging_id: Option<charging_id::InformationElement>,
+    pub serving_plmn_rate_control: Option<serving_plmn_rate_control::InformationElement>,
+    pub apn_rate_control_status: Option<apn_rate_control_status::InformationElement>,
+    pub epco: Option<epco::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
 }
 
 impl Message {
@@ -113,13 +191,28 @@ impl Message {
         let mut m = 
             Message {
                 cause,
+                change_reporting_action: None,
+                csg_information_reporting_action: None,
                 sender_f_teid_for_control_plane: None,
                 pgw_s5_s8_for_control_plane: None,
                 pdn_address_allocation: None,
                 apn_restriction: None,
                 apn_ambr: None,
+                recovery: None,
                 bearer_contexts_created: Vec::new(),
                 bearer_contexts_marked_for_removal: Vec::new(),
+                charging_gateway_name: None,
+                charging_gateway_address: None,
+                pgw_fq_csid: None,
+                sgw_fq_csid: None,
+                sgw_ldn: None,
+                pgw_ldn: None,
+                pgw_back_off_time: None,
+                pdn_connection_charging_id: None,
+                serving_plmn_rate_control: None,
+                apn_rate_control_status: None,
+                epco: None,
+                private_extension: None,
             };
 
         for bc in bearer_contexts_created {
@@ -128,18 +221,90 @@ impl Message {
 
         m
     }
-    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+
+    pub fn reject_with(cause_code: cause::CauseCode) -> Result<Message, String> {
+        // A response that rejects the request because of this node e.g. No resources available
+        Self::reject_with_cause(
+            cause::InformationElement::new(
+                cause_code,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            )?
+        )
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // A response that only carries the Cause. This is useful with ParseError::cause() to reject a malformed request.
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause, Vec::new()))
+    }
+
+    pub fn reject_for_apn_congestion(back_off: Duration) -> Result<Message, String> {
+        // The MME shouldn't send another Create Session Request for the APN until back_off has passed
+        let mut m = Self::reject_with(cause::CauseCode::APNCongestion)?;
+        m.pgw_back_off_time = Some(epc_timer::InformationElement::from_duration(back_off, 0)?);
+
+        Ok(m)
+    }
+
+    pub fn pgw_back_off_time(&self) -> Option<Duration> {
+        // None if there is no PGW Back-Off Time or the timer is infinite
+        match self.pgw_back_off_time {
+            Some(ref ie) => ie.duration(),
+            None => None,
+        }
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        // Fully or partially accepted
+        self.cause.is_accepted()
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.cause.is_rejected()
+    }
+
+    pub fn is_temporarily_rejected(&self) -> bool {
+        // e.g. APN Congestion. The request may be sent again later
+        self.cause.is_temporarily_rejected()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
         // Keep trying to parse IEs until the end of the buffer
         let mut pos = 0;
 
         let mut cause: Option<cause::InformationElement> = None;
+        let mut change_reporting_action: Option<change_reporting_action::InformationElement> = None;
+        let mut csg_information_reporting_action: Option<csg_information_reporting_action::InformationElement> = None;
         let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
         let mut pgw_s5_s8_for_control_plane: Option<f_teid::InformationElement> = None;
         let mut pdn_address_allocation: Option<pdn_address_allocation::InformationElement> = None;
         let mut apn_restriction: Option<apn_restriction::InformationElement> = None;
         let mut apn_ambr: Option<ambr::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
         let mut bearer_contexts_created: Vec<bearer_context::InformationElement> = Vec::new();
         let mut bearer_contexts_marked_for_removal: Vec<bearer_context::InformationElement> = Vec::new();
+        let mut charging_gateway_name: Option<fqdn::InformationElement> = None;
+        let mut charging_gateway_address: Option<ip_address::InformationElement> = None;
+        let mut pgw_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut sgw_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut sgw_ldn: Option<ldn::InformationElement> = None;
+        let mut pgw_ldn: Option<ldn::InformationElement> = None;
+        let mut pgw_back_off_time: Option<epc_timer::InformationElement> = None;
+        let mut pdn_connection_charging_id: Option<charging_id::InformationElement> = None;
+        let mut serving_plmn_rate_control: Option<serving_plmn_rate_control::InformationElement> = None;
+        let mut apn_rate_control_status: Option<apn_rate_control_status::InformationElement> = None;
+        let mut epco: Option<epco::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
 
         while pos < buffer.len()
         {
@@ -169,33 +334,99 @@ impl Message {
                     InformationElement::APNRestriction(ie) => apn_restriction = Some(ie),
                     InformationElement::AMBR(ie) => apn_ambr = Some(ie),
                     InformationElement::Cause(ie) => cause = Some(ie),
-                    _ =>  { /* Its an IE that we didn't expect. Just ignore it */ }
+                    InformationElement::FQCSID(ie) => {
+                        if let Ok(instance) = FqCsidInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FqCsidInstance::PgwFqCsid => pgw_fq_csid = Some(ie),
+                                FqCsidInstance::SgwFqCsid => sgw_fq_csid = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of FQ-CSID that we expect. Just ignore it */ }
+                    },
+                    InformationElement::LDN(ie) => {
+                        if let Ok(instance) = LdnInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                LdnInstance::SgwLdn => sgw_ldn = Some(ie),
+                                LdnInstance::PgwLdn => pgw_ldn = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of LDN that we expect. Just ignore it */ }
+                    },
+                    InformationElement::EPCTimer(ie) => pgw_back_off_time = Some(ie),
+                    InformationElement::ChargingID(ie) => pdn_connection_charging_id = Some(ie),
+                    InformationElement::ChangeReportingAction(ie) => change_reporting_action = Some(ie),
+                    InformationElement::CSGInformationReportingAction(ie) => csg_information_reporting_action = Some(ie),
+                    InformationElement::ServingPLMNRateControl(ie) => serving_plmn_rate_control = Some(ie),
+                    InformationElement::APNRateControlStatus(ie) => apn_rate_control_status = Some(ie),
+                    InformationElement::EPCO(ie) => epco = Some(ie),
+                    InformationElement::Recovery(ie) => recovery = Some(ie),
+                    InformationElement::FQDN(ie) if ie.instance() == 0 => charging_gateway_name = Some(ie),
+                    InformationElement::IPAddress(ie) if ie.instance() == 0 => charging_gateway_address = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::CreateSessionResponse, &ie),
                 }
                 pos = pos + ie_pos;
             }
             else {
                 // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
                 pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
             }
         }
 
-        if cause.is_some() && 
-            bearer_contexts_created.len() > 1 {
-                Some((
-                    Message {
-                        cause: cause.unwrap(),
-                        sender_f_teid_for_control_plane,
-                        pgw_s5_s8_for_control_plane,
-                        pdn_address_allocation,
-                        apn_restriction,
-                        apn_ambr,
-                        bearer_contexts_created,
-                        bearer_contexts_marked_for_removal,
-                    }, 
-                    pos
-                ))
+        // Collect every mandatory IE that is absent so the sender can be told which IE was missing
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        // Bearer Contexts created is only mandatory when the request was accepted
+        let accepted = match cause {
+            Some(ref ie) => ie.cause_code.is_acceptance(),
+            None => false,
+        };
+
+        if accepted && bearer_contexts_created.is_empty() {
+            missing.push((InformationElementType::BearerContext, BearerContextInstance::ToBeCreated as u8));
         }
-        else { None }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                change_reporting_action,
+                csg_information_reporting_action,
+                sender_f_teid_for_control_plane,
+                pgw_s5_s8_for_control_plane,
+                pdn_address_allocation,
+                apn_restriction,
+                apn_ambr,
+                recovery,
+                bearer_contexts_created,
+                bearer_contexts_marked_for_removal,
+                charging_gateway_name,
+                charging_gateway_address,
+                pgw_fq_csid,
+                sgw_fq_csid,
+                sgw_ldn,
+                pgw_ldn,
+                pgw_back_off_time,
+                pdn_connection_charging_id,
+                serving_plmn_rate_control,
+                apn_rate_control_status,
+                epco,
+                private_extension,
+            },
+            pos
+        ))
+
     }
 
     pub fn set_pgw_s5_s8_for_control_plane(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
@@ -245,34 +476,94 @@ impl MessageTraits for Message {
     fn length(&self) -> u16 {
         let mut length = 0;
 
-        length = length + self.cause.length();
+        length = length + self.cause.wire_length();
 
-        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
-            length = length + ie.length();
+        if let Some(ref ie) = self.change_reporting_action {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.csg_information_reporting_action {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         for ie in &self.bearer_contexts_created {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         for ie in &self.bearer_contexts_marked_for_removal {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.pdn_address_allocation {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.apn_restriction {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
         if let Some(ref ie) = self.apn_ambr {
-            length = length + ie.length();
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.recovery {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.charging_gateway_name {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.charging_gateway_address {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pgw_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pgw_ldn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pgw_back_off_time {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.pdn_connection_charging_id {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.apn_rate_control_status {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.epco {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
         }
 
         length
@@ -282,6 +573,14 @@ impl MessageTraits for Message {
 
         pos = pos + self.cause.generate(&mut buffer[pos..]);
 
+        if let Some(ref ie) = self.change_reporting_action {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.csg_information_reporting_action {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
         if let Some(ref ie) = self.sender_f_teid_for_control_plane {
             pos = pos + ie.generate(&mut buffer[pos..]);
         }
@@ -310,8 +609,251 @@ impl MessageTraits for Message {
             pos = pos + ie.generate(&mut buffer[pos..]);
         }
 
+        if let Some(ref ie) = self.recovery {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.charging_gateway_name {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.charging_gateway_address {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pgw_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pgw_ldn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pgw_back_off_time {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.pdn_connection_charging_id {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.apn_rate_control_status {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.epco {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
         pos
     }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.cause);
+
+        if let Some(ref ie) = self.change_reporting_action {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.csg_information_reporting_action {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+            ies.push(ie);
+        }
+
+        for ie in &self.bearer_contexts_created {
+            ies.push(ie);
+        }
+
+        for ie in &self.bearer_contexts_marked_for_removal {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pdn_address_allocation {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_restriction {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_ambr {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.charging_gateway_name {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.charging_gateway_address {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_ldn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pgw_ldn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pgw_back_off_time {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pdn_connection_charging_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.serving_plmn_rate_control {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_rate_control_status {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.epco {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.change_reporting_action {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.csg_information_reporting_action {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pgw_s5_s8_for_control_plane {
+                ie.dissect(d);
+            }
+
+            for ie in &self.bearer_contexts_created {
+                ie.dissect(d);
+            }
+
+            for ie in &self.bearer_contexts_marked_for_removal {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pdn_address_allocation {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_restriction {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_ambr {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.charging_gateway_name {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.charging_gateway_address {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pgw_ldn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pgw_back_off_time {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pdn_connection_charging_id {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.serving_plmn_rate_control {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_rate_control_status {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.epco {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
 }
 
 #[cfg(test)]
@@ -333,6 +875,10 @@ mod tests {
         ambr,
         cause,
     };
+
+    use ascii::AsciiString;
+
+    use core::net::IpAddr;
     
     #[test]
     fn test_generate() {
@@ -351,10 +897,8 @@ mod tests {
                 bearer_context::InformationElement::new(
                     ebi::InformationElement::new(7,0).unwrap(),
                     bearer_qos::InformationElement::new(
-                        false,
-                        9,
-                        true,
-                        7,
+                        bearer_qos::Arp::new(false, 9, true).unwrap(),
+                        bearer_qos::Qci::Qci7,
                         10_000_000,
                         10_000_000,
                         0,
@@ -386,18 +930,18 @@ mod tests {
             InformationElementType::Cause as u8,
             0, 2, // Length
             0, // Spare
-            cause::CauseCode::RequestAccepted as u8,
+            u8::from(cause::CauseCode::RequestAccepted),
             0b00000000,
             InformationElementType::FTEID as u8,
             0, 9, // Length
             FTeidInstance::SenderFTeidForControlPlane as u8, // Spare and Instance
-            (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S11S4SgwGtpC as u8),
+            (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S11S4SgwGtpC)),
             0x87, 0x65, 0x43, 0x21,
             10, 0, 0, 2,
             InformationElementType::FTEID as u8,
             0, 9, // Length
             FTeidInstance::PgwS5S8AddressForControlPlane as u8, // Spare and Instance
-            (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S5S8PgwGtpC as u8),
+            (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S5S8PgwGtpC)),
             0x12, 0x34, 0x56, 0x78,
             10, 0, 0, 3,
             InformationElementType::BearerContext as u8,
@@ -419,7 +963,7 @@ mod tests {
             InformationElementType::PDNAddressAllocation as u8,
             0, 5, // Length
             0, // Spare
-            pdn_type::PDNType::IPv4 as u8, // PDN Type
+            u8::from(pdn_type::PDNType::IPv4), // PDN Type
             10,0,0,1,
             InformationElementType::APNRestriction as u8,
             0, 1, // Length
@@ -457,10 +1001,8 @@ mod tests {
                 bearer_context::InformationElement::new(
                     ebi::InformationElement::new(7,0).unwrap(),
                     bearer_qos::InformationElement::new(
-                        false,
-                        9,
-                        true,
-                        7,
+                        bearer_qos::Arp::new(false, 9, true).unwrap(),
+                        bearer_qos::Qci::Qci7,
                         10_000_000,
                         10_000_000,
                         0,
@@ -506,10 +1048,8 @@ mod tests {
                 bearer_context::InformationElement::new(
                     ebi::InformationElement::new(7,0).unwrap(),
                     bearer_qos::InformationElement::new(
-                        false,
-                        9,
-                        true,
-                        7,
+                        bearer_qos::Arp::new(false, 9, true).unwrap(),
+                        bearer_qos::Qci::Qci7,
                         10_000_000,
                         10_000_000,
                         0,
@@ -546,18 +1086,18 @@ mod tests {
             InformationElementType::Cause as u8,
             0, 2, // Length
             0, // Spare
-            cause::CauseCode::RequestAccepted as u8,
+            u8::from(cause::CauseCode::RequestAccepted),
             0b00000000,
             InformationElementType::FTEID as u8,
             0, 9, // Length
             0, // Spare and Instance
-            (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S11S4SgwGtpC as u8),
+            (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S11S4SgwGtpC)),
             0x87, 0x65, 0x43, 0x21,
             10, 0, 0, 2,
             InformationElementType::FTEID as u8,
             0, 9, // Length
-            0, // Spare and Instance
-            (0b1 << 7) | (0b0 << 6) | (f_teid::InterfaceType::S5S8PgwGtpC as u8),
+            1, // Spare and Instance
+            (0b1 << 7) | (0b0 << 6) | (u8::from(f_teid::InterfaceType::S5S8PgwGtpC)),
             0x12, 0x34, 0x56, 0x78,
             10, 0, 0, 3,
             InformationElementType::BearerContext as u8,
@@ -570,7 +1110,7 @@ mod tests {
             InformationElementType::BearerQoS as u8,
             0, 22, // Length
             0, // Spare and Instance
-            0b01100100, // Flags
+            0b00100101, // Flags
             7, // QCI
             0x00, 0x00, 0x98, 0x96, 0x80,
             0x00, 0x00, 0x98, 0x96, 0x80,
@@ -579,7 +1119,7 @@ mod tests {
             InformationElementType::PDNAddressAllocation as u8,
             0, 5, // Length
             0, // Spare
-            pdn_type::PDNType::IPv4 as u8, // PDN Type
+            u8::from(pdn_type::PDNType::IPv4), // PDN Type
             10,0,0,1,
             InformationElementType::APNRestriction as u8,
             0, 1, // Length
@@ -592,7 +1132,7 @@ mod tests {
             0x87, 0x65, 0x43, 0x21, // AMBR for uplink
         ];
 
-        if let Some((m, _pos)) = Message::parse(&message_bytes){
+        if let Ok((m, _pos)) = Message::parse(&message_bytes){
 
             
 
@@ -617,7 +1157,7 @@ mod tests {
                 else {
                     assert!(false);
                 }
-                assert_eq!(ie.teid, 0x87654321);
+                assert_eq!(ie.teid, 0x12345678);
                 assert_eq!(ie.interface_type, f_teid::InterfaceType::S5S8PgwGtpC);
             }
             else {
@@ -625,14 +1165,14 @@ mod tests {
             }
             
             assert_eq!(m.bearer_contexts_created[0].eps_bearer_id.eps_bearer_id, 7);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.pvi, true);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.pl(), 9);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.pci, false);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.qci, 7);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.guaranteed_ul_bitrate(), 0);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.guaranteed_dl_bitrate(), 0);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.max_ul_bitrate(), 10_000_000);
-            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.max_dl_bitrate(), 10_000_000);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().arp.pvi, false);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().arp.pl, 9);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().arp.pci, true);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().qci, bearer_qos::Qci::Qci7);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().guaranteed_ul_kbps(), 0);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().guaranteed_dl_kbps(), 0);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().max_ul_kbps(), 10_000_000);
+            assert_eq!(m.bearer_contexts_created[0].bearer_level_qos.as_ref().unwrap().max_dl_kbps(), 10_000_000);
 
             if let Some(pdn_address_allocation) = m.pdn_address_allocation {
                 assert_eq!(pdn_address_allocation.pdn_type, pdn_type::PDNType::IPv4);
@@ -647,9 +1187,333 @@ mod tests {
 
             if let Some(ie) = m.apn_ambr {
                 assert_eq!(ie.uplink, 0x12_34_56_78);
-                assert_eq!(ie.uplink, 0x87_65_43_21);
+                assert_eq!(ie.downlink, 0x87_65_43_21);
             }
             else { assert!(false); }
         }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+        ];
+
+        if let Err(e) = Message::parse(&message_bytes) {
+            assert_eq!(e, ParseError::MandatoryIEMissing(vec![(InformationElementType::BearerContext, 0)]));
+            assert_eq!(e.cause().offending_ie, Some((InformationElementType::BearerContext, 0)));
+        }
+        else {
+            assert!(false);
+        }
+
+        // The Cause IE is present but can't be parsed
+        let message_bytes = [
+            InformationElementType::Cause as u8,
+            0, 1, // Length is too short for the flags
+            0, // Spare
+            16, // Cause
+        ];
+
+        if let Err(e) = Message::parse(&message_bytes) {
+            assert_eq!(e, ParseError::MandatoryIEIncorrect(vec![(InformationElementType::Cause, 0)]));
+            assert_eq!(e.cause().cause_code, cause::CauseCode::MandatoryIEIncorrect);
+            assert_eq!(e.cause().offending_ie, Some((InformationElementType::Cause, 0)));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_reject_with() {
+        if let Ok(m) = Message::reject_with(cause::CauseCode::NoResourcesAvailable) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::NoResourcesAvailable);
+            assert_eq!(m.cause.cause_source, cause::CauseSource::LocalNode);
+            assert!(m.bearer_contexts_created.is_empty());
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Message::reject_with(cause::CauseCode::RequestAccepted).is_err());
+
+        if let Ok(m) = Message::reject_with(cause::CauseCode::APNCongestion) {
+            assert!(m.is_rejected());
+            assert!(m.is_temporarily_rejected());
+            assert!(!m.is_accepted());
+        }
+        else {
+            assert!(false);
+        }
+
+        let c = ParseError::MandatoryIEMissing(vec![(InformationElementType::APN, 0)]).cause();
+
+        if let Ok(m) = Message::reject_with_cause(c) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::MandatoryIEMissing);
+            assert_eq!(m.cause.offending_ie, Some((InformationElementType::APN, 0)));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_reject_for_apn_congestion() {
+        let mut buffer = [0; MTU];
+
+        let m = Message::reject_for_apn_congestion(Duration::from_secs(600)).unwrap();
+        assert_eq!(m.cause.cause_code, cause::CauseCode::APNCongestion);
+        assert_eq!(m.pgw_back_off_time(), Some(Duration::from_secs(600)));
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::APNCongestion),
+            0b00000000,
+            InformationElementType::EPCTimer as u8,
+            0, 1, // Length
+            0, // Spare
+            0b001_01010, // 10 minutes
+        ]);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.is_temporarily_rejected());
+            assert_eq!(m.pgw_back_off_time(), Some(Duration::from_secs(600)));
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(Message::reject_with(cause::CauseCode::APNCongestion).unwrap().pgw_back_off_time(), None);
+        assert!(Message::reject_for_apn_congestion(Duration::from_secs(400 * 3600)).is_err());
+    }
+
+    #[test]
+    fn test_message_parse_rejected() {
+        // A rejected request only requires the Cause IE
+        let message_bytes = [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::MandatoryIEMissing),
+            0b00000000,
+        ];
+
+        if let Ok((m, pos)) = Message::parse(&message_bytes) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::MandatoryIEMissing);
+            assert!(m.bearer_contexts_created.is_empty());
+            assert_eq!(pos, 6);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    fn new_message() -> Message {
+        Message::new(
+            cause::InformationElement::new(
+                cause::CauseCode::RequestAccepted,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap(),
+            vec![
+                bearer_context::InformationElement::new(
+                    ebi::InformationElement::new(5,0).unwrap(),
+                    bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                    0
+                ).unwrap()
+            ]
+        )
+    }
+
+    #[test]
+    fn test_message_parse_fq_csid_ldn_charging_id() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        let node_id = fq_csid::NodeId::IPv4(Ipv4Addr::new(10,0,0,1));
+
+        m.pgw_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![1], 0).unwrap());
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![2], 1).unwrap());
+        m.sgw_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 0).unwrap());
+        m.pgw_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("PGW-1").unwrap(), 1).unwrap());
+        m.pdn_connection_charging_id = Some(charging_id::InformationElement::new(0x12345678, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.pgw_fq_csid.map(|ie| ie.csids), Some(vec![1]));
+            assert_eq!(m.sgw_fq_csid.map(|ie| ie.csids), Some(vec![2]));
+            assert_eq!(m.sgw_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("SGW-1").unwrap()));
+            assert_eq!(m.pgw_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("PGW-1").unwrap()));
+            assert_eq!(m.pdn_connection_charging_id.map(|ie| ie.charging_id), Some(0x12345678));
+        }
+        else { assert!(false); }
+
+        // The instance alone decides which node an FQ-CSID or LDN belongs to
+        let mut m = new_message();
+
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(node_id, vec![2], 0).unwrap());
+        m.pgw_ldn = Some(ldn::InformationElement::new(AsciiString::from_ascii("PGW-1").unwrap(), 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.sgw_fq_csid.is_none());
+            assert_eq!(m.pgw_fq_csid.map(|ie| ie.csids), Some(vec![2]));
+            assert!(m.pgw_ldn.is_none());
+            assert_eq!(m.sgw_ldn.map(|ie| ie.ldn), Some(AsciiString::from_ascii("PGW-1").unwrap()));
+        }
+        else { assert!(false); }
+    }
+
+    #[test]
+    fn test_message_parse_charging_gateway() {
+        let mut buffer = [0; MTU];
+
+        for address in [IpAddr::V4(Ipv4Addr::new(10,0,0,5)), IpAddr::V6(Ipv6Addr::new(0x2001,0xdb8,0,0,0,0,0,5))] {
+            let mut m = new_message();
+
+            m.charging_gateway_name = Some(fqdn::InformationElement::new(AsciiString::from_ascii("cg1.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0).unwrap());
+            m.charging_gateway_address = Some(ip_address::InformationElement::new(address, 0).unwrap());
+
+            let pos = m.generate(&mut buffer);
+            assert_eq!(pos, m.length() as usize);
+
+            if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+                assert_eq!(m.charging_gateway_name.map(|ie| ie.fqdn), Some(AsciiString::from_ascii("cg1.epc.mnc099.mcc505.3gppnetwork.org").unwrap()));
+                assert_eq!(m.charging_gateway_address.map(|ie| ie.ip_address), Some(address));
+            }
+            else { assert!(false); }
+        }
+
+        // Only instance 0 is the Charging Gateway
+        let mut m = new_message();
+
+        m.charging_gateway_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,0,0,5)), 1).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.charging_gateway_address.is_none());
+        }
+        else { assert!(false); }
+    }
+
+    #[test]
+    fn test_message_reporting_actions() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.change_reporting_action = Some(
+            change_reporting_action::InformationElement::new(change_reporting_action::Action::StartReportingTaiAndEcgi, 0).unwrap()
+        );
+        m.csg_information_reporting_action = Some(
+            csg_information_reporting_action::InformationElement::new(csg_information_reporting_action::UCICSG, 0).unwrap()
+        );
+
+        let pos = m.generate(&mut buffer);
+
+        // The reporting actions follow the Cause
+        assert_eq!(buffer[6..16], [InformationElementType::ChangeReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            6, // Action
+            InformationElementType::CSGInformationReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0000_0001, // Action
+        ]);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.change_reporting_action.map(|ie| ie.action), Some(change_reporting_action::Action::StartReportingTaiAndEcgi));
+            if let Some(ie) = m.csg_information_reporting_action {
+                assert!(ie.ucicsg());
+                assert!(!ie.ucishc());
+                assert!(!ie.uciuhc());
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+
+        // Without the reporting actions
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert!(m.change_reporting_action.is_none());
+            assert!(m.csg_information_reporting_action.is_none());
+        }
+        else { assert!(false); }
+    }
+
+    #[test]
+    fn test_message_parse_rate_control() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.serving_plmn_rate_control = Some(serving_plmn_rate_control::InformationElement::new(20, 0, 0).unwrap());
+        m.apn_rate_control_status = Some(apn_rate_control_status::InformationElement::new(100, 2, 50, 0xE3E4_3A00_0000_0000, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.serving_plmn_rate_control {
+                assert_eq!(ie.uplink_rate_limit, 20);
+                assert!(!ie.downlink_applicable());
+            }
+            else { assert!(false); }
+
+            if let Some(ie) = m.apn_rate_control_status {
+                assert_eq!(ie.uplink_packets_allowed, 100);
+                assert_eq!(ie.additional_exception_reports, 2);
+                assert_eq!(ie.downlink_packets_allowed, 50);
+                assert_eq!(ie.validity_time_seconds(), 0xE3E4_3A00);
+            }
+            else { assert!(false); }
+        }
+        else { assert!(false); }
+    }
+
+    #[test]
+    fn test_message_parse_epco() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        let mut pco = crate::pco::ProtocolConfigurationOptions::new();
+        pco.push(crate::pco::DNS_SERVER_IPV4_ADDRESS, &[10, 0, 0, 53]).unwrap();
+
+        m.epco = Some(epco::InformationElement::new(pco, 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((parsed, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ref ie) = parsed.epco {
+                assert_eq!(ie.pco.get(crate::pco::DNS_SERVER_IPV4_ADDRESS), Some(&[10, 0, 0, 53][..]));
+            }
+            else { assert!(false); }
+
+            assert_eq!(parsed, m);
+        }
+        else { assert!(false); }
     }
 }
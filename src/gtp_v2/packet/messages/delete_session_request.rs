@@ -0,0 +1,276 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    cause,
+    ebi,
+    user_location_information,
+    indication,
+    f_teid,
+    ue_time_zone,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        Cause                                       |    Conditional            | 0             | 8.4
+        Linked EPS Bearer ID (LBI)                  |    Conditional            | 0             | 8.8
+        User Location Information (ULI)             |    Conditional Optional   | 0             | 8.21
+        Indication Flags                            |    Conditional            | 0             | 8.12
+        Protocol Configuration Options (PCO)        |    Conditional            | 0             |
+        Originating Node                            |    Conditional            | 0             |
+        Sender F-TEID for Control Plane             |    Conditional Optional   | 0             | 8.22
+        UE Time Zone                                |    Conditional Optional   | 0             | 8.44
+        ULI Timestamp                               |    Conditional Optional   | 0             |
+        RAN/NAS Release Cause                       |    Conditional Optional   | 0             |
+        Trusted WLAN Access Network Identifier      |    Conditional Optional   | 0             |
+        TWAN Identifier Timestamp                   |    Conditional Optional   | 0             |
+        MME's Overload Control Information          |    Optional               | 0             |
+        SGW's Overload Control Information          |    Optional               | 1             |
+        ePDG's Overload Control Information         |    Optional               | 2             |
+        WLAN Location Information                   |    Conditional Optional   | 1             |
+        WLAN Location Timestamp                     |    Conditional Optional   | 1             |
+        UE Local IP Address                         |    Conditional Optional   | 0             |
+        UE UDP Port                                 |    Conditional Optional   | 0             |
+        Extended Protocol Configuration Options     |    Conditional Optional   | 0             |
+        UE TCP Port                                 |    Conditional Optional   | 0             |
+        Secondary RAT Usage Data Report             |    Conditional Optional   | 0             |
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        Every IE is conditional. The LBI identifies the PDN connection being deleted (TS 29.274 7.2.9.1)
+    */
+
+    pub cause: Option<cause::InformationElement>,
+    pub linked_eps_bearer_id: Option<ebi::InformationElement>,
+    pub user_location_information: Option<user_location_information::InformationElement>,
+    pub indication: Option<indication::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub ue_time_zone: Option<ue_time_zone::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            cause: None,
+            linked_eps_bearer_id: None,
+            user_location_information: None,
+            indication: None,
+            sender_f_teid_for_control_plane: None,
+            ue_time_zone: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut m = Message::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) if ie.instance() == 0 => m.cause = Some(ie),
+                    InformationElement::EBI(ie) if ie.instance() == 0 => m.linked_eps_bearer_id = Some(ie),
+                    InformationElement::ULI(ie) if ie.instance() == 0 => m.user_location_information = Some(ie),
+                    InformationElement::Indication(ie) if ie.instance() == 0 => m.indication = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => m.sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::UETimeZone(ie) if ie.instance() == 0 => m.ue_time_zone = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => m.private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::DeleteSessionRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((m, pos))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DeleteSessionRequest
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.cause {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.linked_eps_bearer_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.user_location_information {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.ue_time_zone {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.cause {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.linked_eps_bearer_id {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.user_location_information {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ue_time_zone {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        m.linked_eps_bearer_id = Some(ebi::InformationElement::new(5, 0).unwrap());
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap()
+        );
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EBI
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S11MmeGtpC)),
+            0x12, 0x34, 0x56, 0x78,
+            10, 0, 0, 1,
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_message().length(), 5 + 13);
+        assert_eq!(Message::new().length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_message().message_type(), MessageType::DeleteSessionRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 18);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert_eq!(m, Message::new());
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    recovery,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   8.4
+        Recovery                                    |    Conditional            |   8.5
+        Protocol Configuration Options (PCO)        |    Conditional            |
+        Indication Flags                            |    Conditional Optional   |
+        PGW's Node Level Load Control Information   |    Optional               |
+        PGW's APN Level Load Control Information    |    Optional               |
+        SGW's Node Level Load Control Information   |    Optional               |
+        PGW Overload Control Information            |    Optional               |
+        SGW Overload Control Information            |    Optional               |
+        Extended Protocol Configuration Options     |    Conditional Optional   |
+        APN Rate Control Status                     |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: cause::InformationElement,
+    pub recovery: Option<recovery::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            recovery: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // e.g. Context Not Found when the TEID of the Delete Session Request is unknown
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause))
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) if ie.instance() == 0 => cause = Some(ie),
+                    InformationElement::Recovery(ie) if ie.instance() == 0 => recovery = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::DeleteSessionResponse, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                recovery,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DeleteSessionResponse
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = vec![&self.cause];
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_cause(cause_code: cause::CauseCode) -> cause::InformationElement {
+        cause::InformationElement::new(
+            cause_code,
+            cause::CauseSource::LocalNode,
+            false,
+            false,
+            None,
+            0
+        ).unwrap()
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            3, // Restart Counter
+        ]
+    }
+
+    fn new_message() -> Message {
+        let mut m = Message::new(new_cause(cause::CauseCode::RequestAccepted));
+        m.recovery = Some(recovery::InformationElement::new(3, 0).unwrap());
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_message().length(), 6 + 5);
+        assert_eq!(Message::new(new_cause(cause::CauseCode::RequestAccepted)).length(), 6);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_message().message_type(), MessageType::DeleteSessionResponse)
+    }
+
+    #[test]
+    fn test_reject_with_cause() {
+        if let Ok(m) = Message::reject_with_cause(new_cause(cause::CauseCode::ContextNotFound)) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::ContextNotFound);
+            assert_eq!(m.recovery, None);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Message::reject_with_cause(new_cause(cause::CauseCode::RequestAccepted)).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 11);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(Message::parse(&[]).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+}
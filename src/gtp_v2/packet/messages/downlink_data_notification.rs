@@ -0,0 +1,438 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    cause,
+    ebi,
+    arp,
+    imsi,
+    f_teid,
+    indication,
+    paging_and_service_information,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Conditional Optional   |   8.4
+        EPS Bearer ID                               |    Conditional Optional   |   8.8
+        Allocation/Retention Priority (ARP)         |    Conditional Optional   |   8.86
+        IMSI                                        |    Conditional Optional   |   8.3
+        Sender F-TEID for Control Plane             |    Optional               |   8.22
+        Indication Flags                            |    Conditional Optional   |   8.12
+        SGW's node level Load Control Information   |    Optional               |
+        SGW's Overload Control Information          |    Optional               |
+        Paging and Service Information              |    Conditional Optional   |   8.123
+        DL Data Packets Size                        |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: Option<cause::InformationElement>,
+    pub eps_bearer_ids: Vec<ebi::InformationElement>,
+    pub arp: Option<arp::InformationElement>,
+    pub imsi: Option<imsi::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub indication_flags: Option<indication::InformationElement>,
+    pub paging_and_service_informations: Vec<paging_and_service_information::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            cause: None,
+            eps_bearer_ids: Vec::new(),
+            arp: None,
+            imsi: None,
+            sender_f_teid_for_control_plane: None,
+            indication_flags: None,
+            paging_and_service_informations: Vec::new(),
+            private_extension: None,
+        }
+    }
+
+    pub fn push_eps_bearer_id(&mut self, ebi: ebi::InformationElement) {
+        self.eps_bearer_ids.push(ebi);
+    }
+
+    pub fn push_paging_and_service_information(&mut self, ie: paging_and_service_information::InformationElement) {
+        // One per bearer that received the downlink data
+        self.paging_and_service_informations.push(ie);
+    }
+
+    pub fn isr_activated_for_ue(&self) -> bool {
+        // The SGW sends the DDN to both the MME and the SGSN when ISR is active
+        match self.indication_flags {
+            Some(ref ie) => ie.isr_activated_for_ue(),
+            None => false,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut eps_bearer_ids: Vec<ebi::InformationElement> = Vec::new();
+        let mut arp: Option<arp::InformationElement> = None;
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut indication_flags: Option<indication::InformationElement> = None;
+        let mut paging_and_service_informations: Vec<paging_and_service_information::InformationElement> = Vec::new();
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::EBI(ie) => eps_bearer_ids.push(ie),
+                    InformationElement::ARP(ie) => arp = Some(ie),
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::FTEID(ie) => sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::Indication(ie) => indication_flags = Some(ie),
+                    InformationElement::PagingAndServiceInformation(ie) => paging_and_service_informations.push(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::DownlinkDataNotification, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed. None of the IEs are mandatory so just skip it
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((
+            Message {
+                cause,
+                eps_bearer_ids,
+                arp,
+                imsi,
+                sender_f_teid_for_control_plane,
+                indication_flags,
+                paging_and_service_informations,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DownlinkDataNotification
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        if let Some(ref ie) = self.cause {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.eps_bearer_ids {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.arp {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.imsi {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.paging_and_service_informations {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        if let Some(ref ie) = self.cause {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.eps_bearer_ids {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.arp {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.paging_and_service_informations {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.cause {
+            ies.push(ie);
+        }
+
+        for ie in &self.eps_bearer_ids {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.arp {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            ies.push(ie);
+        }
+
+        for ie in &self.paging_and_service_informations {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.cause {
+                ie.dissect(d);
+            }
+
+            for ie in &self.eps_bearer_ids {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.arp {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication_flags {
+                ie.dissect(d);
+            }
+
+            for ie in &self.paging_and_service_informations {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        m.push_eps_bearer_id(ebi::InformationElement::new(5, 0).unwrap());
+        m.push_eps_bearer_id(ebi::InformationElement::new(6, 0).unwrap());
+        m.arp = Some(arp::InformationElement::new(true, 9, false, 0).unwrap());
+        m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11S4SgwGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap()
+        );
+        m.indication_flags = Some(indication::InformationElement::with_flags(&[indication::ISRAU], 0).unwrap());
+        m.push_paging_and_service_information(paging_and_service_information::InformationElement::new(5, Some(0x2E), 0).unwrap());
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            6, // EPS Bearer ID
+            InformationElementType::ARP as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0010_0101, // PCI, PL, PVI
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S11S4SgwGtpC)),
+            0x12, 0x34, 0x56, 0x78,
+            10, 0, 0, 1,
+            InformationElementType::Indication as u8,
+            0, 3, // Length
+            0, // Spare and Instance
+            0, 0, 0b0000_0010, // ISRAU
+            InformationElementType::PagingAndServiceInformation as u8,
+            0, 3, // Length
+            0, // Spare and Instance
+            5, // EPS Bearer ID
+            1, // PPI
+            0x2E, // Paging Policy Indication value
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 5 + 5 + 5 + 12 + 13 + 7 + 7);
+        assert_eq!(Message::new().length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::DownlinkDataNotification)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.cause.is_none(), true);
+            assert_eq!(m.eps_bearer_ids.len(), 2);
+            assert_eq!(m.eps_bearer_ids[0].eps_bearer_id, 5);
+            assert_eq!(m.eps_bearer_ids[1].eps_bearer_id, 6);
+
+            assert!(m.isr_activated_for_ue());
+            assert_eq!(m.paging_and_service_informations.len(), 1);
+            assert_eq!(m.paging_and_service_informations[0].eps_bearer_id, 5);
+            assert_eq!(m.paging_and_service_informations[0].paging_policy_indication, Some(0x2E));
+
+            if let Some(ie) = m.arp {
+                assert_eq!(ie.pci, true);
+                assert_eq!(ie.pl, 9);
+                assert_eq!(ie.pvi, false);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.imsi {
+                assert_eq!(ie.imsi, [5,0,5,0,1,3,4,8,5,0,9,0,4,0,4]);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.sender_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0x12345678);
+                assert_eq!(ie.ipv4_address, Some(Ipv4Addr::new(10,0,0,1)));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // An empty Downlink Data Notification is valid
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(m.eps_bearer_ids.len(), 0);
+            assert!(!m.isr_activated_for_ue());
+            assert_eq!(pos, 0);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
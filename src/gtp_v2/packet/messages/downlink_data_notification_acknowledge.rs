@@ -0,0 +1,353 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    recovery,
+    imsi,
+    node_identifier,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        Cause                                       |    Mandatory              |   8.4
+        Data Notification Delay                     |    Conditional            |
+        Recovery                                    |    Optional               |   8.5
+        DL low priority traffic Throttling          |    Conditional Optional   |
+        IMSI                                        |    Conditional Optional   |   8.3
+        DL Buffering Duration                       |    Conditional Optional   |
+        DL Buffering Suggested Packet Count         |    Conditional Optional   |
+        Node Identifier                             |    Optional               |   8.107
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub cause: cause::InformationElement,
+    pub recovery: Option<recovery::InformationElement>,
+    pub imsi: Option<imsi::InformationElement>,
+    pub node_identifier: Option<node_identifier::InformationElement>, // The Diameter identity of the MME/S4-SGSN paging the UE
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            recovery: None,
+            imsi: None,
+            node_identifier: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // An acknowledge that only carries the Cause e.g. Context not found
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause))
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut node_identifier: Option<node_identifier::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) => cause = Some(ie),
+                    InformationElement::Recovery(ie) => recovery = Some(ie),
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::NodeIdentifier(ie) if ie.instance() == 0 => node_identifier = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::DownlinkDataNotificationAcknowledge, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                recovery,
+                imsi,
+                node_identifier,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::DownlinkDataNotificationAcknowledge
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.cause.wire_length();
+
+        if let Some(ref ie) = self.recovery {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.imsi {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.node_identifier {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.cause.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.recovery {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.node_identifier {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.cause);
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.node_identifier {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.node_identifier {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use ascii::AsciiString;
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            cause::InformationElement::new(
+                cause::CauseCode::RequestAccepted,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                None,
+                0
+            ).unwrap()
+        );
+
+        m.recovery = Some(recovery::InformationElement::new(0xAB, 0).unwrap());
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            0xAB, // Restart Counter
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 6 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::DownlinkDataNotificationAcknowledge)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let message_bytes = [
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::UnableToPageUE),
+            0b00000000,
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+        ];
+
+        if let Ok((m, _pos)) = Message::parse(&message_bytes) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::UnableToPageUE);
+            assert_eq!(m.recovery.is_none(), true);
+
+            if let Some(ie) = m.imsi {
+                assert_eq!(ie.imsi, [5,0,5,0,1,3,4,8,5,0,9,0,4,0,4]);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_node_identifier() {
+        let mut buffer = [0; MTU];
+
+        let mut m = new_message();
+
+        m.node_identifier = Some(node_identifier::InformationElement::new(
+            AsciiString::from_ascii("mme1").unwrap(),
+            AsciiString::from_ascii("epc.org").unwrap(),
+            0
+        ).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(m.length() as usize, pos);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            0xAB, // Restart Counter
+        ];
+
+        assert_eq!(Message::parse(&message_bytes).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+}
@@ -1,56 +1,89 @@
-use super::{MessageTraits, MessageType};
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
 
-use super::information_elements::{InformationElement, InformationElementTraits, recovery};
+use super::information_elements::{InformationElement, InformationElementTraits, InformationElementType, recovery, node_features, private_extension};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct Message {
     /*
         --------------------------------------------|---------------------------|-------------------------                             
         Information Element                         |   Presence requirement    |   Reference 
         --------------------------------------------|---------------------------|-------------------------
         Recovery                                    |    Mandatory              |   8.5
-        Sending Node Features                       |    Conditional Optional   |   
+        Sending Node Features                       |    Conditional Optional   |   8.83
         Private Extensions                          |    Optional               |   
         --------------------------------------------|---------------------------|-------------------------
     */
 
-    recovery: recovery::InformationElement
+    recovery: recovery::InformationElement,
+    pub sending_node_features: Option<node_features::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
 }
 
 impl Message {
     pub fn new(recovery: recovery::InformationElement) -> Message {
         Message {
-            recovery
+            recovery,
+            sending_node_features: None,
+            private_extension: None,
         }
     }
-    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+
+    pub fn recovery(&self) -> &recovery::InformationElement {
+        &self.recovery
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
         let mut pos = 0;
 
         let mut recovery: Option<recovery::InformationElement> = None;
+        let mut sending_node_features: Option<node_features::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
         
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
         while pos < buffer.len()
         {
             if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
                 match ie {
                     InformationElement::Recovery(ie) => recovery = Some(ie),
-                    _ =>  { /* Its an IE that we didn't expect. Just ignore it */ }
+                    InformationElement::NodeFeatures(ie) => sending_node_features = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::EchoRequest, &ie),
                 }
                 pos = pos + ie_pos;
             }
             else {
                 // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
                 pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
             }
         }
 
-        if recovery.is_some() {
-                Some((
-                    Message {
-                        recovery: recovery.unwrap()
-                    }, 
-                    pos
-                ))
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if recovery.is_none() {
+            missing.push((InformationElementType::Recovery, 0));
         }
-        else { None }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                recovery: recovery.unwrap(),
+                sending_node_features,
+                private_extension,
+            },
+            pos
+        ))
     }
 }
 
@@ -62,13 +95,63 @@ impl MessageTraits for Message {
     fn length(&self) -> u16 {
         let mut length = 0;
 
-        length = length + self.recovery.length();
+        length = length + self.recovery.wire_length();
+
+        if let Some(ref ie) = self.sending_node_features {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
 
         length
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
-        self.recovery.generate(buffer)
+        let mut pos = 0;
+
+        pos = pos + self.recovery.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.sending_node_features {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.recovery);
+
+        if let Some(ref ie) = self.sending_node_features {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.recovery.dissect(d);
+            if let Some(ref ie) = self.sending_node_features {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
     }
 }
 
@@ -78,7 +161,8 @@ mod tests {
     use crate::MTU;
     use crate::gtp_v2::packet::messages::MessageTraits;
     use crate::gtp_v2::packet::messages::MessageType;
-    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, recovery};
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, recovery, node_features};
+    use crate::gtp_v2::packet::messages::information_elements::private_extension;
 
     #[test]
     fn test_generate() {
@@ -104,7 +188,7 @@ mod tests {
     #[test]
     fn test_message_type() {
         let m = Message::new(recovery::InformationElement::new(0xCD, 0).unwrap());
-        assert_eq!(m.message_type() as u8, MessageType::EchoRequest as u8)
+        assert_eq!(m.message_type(), MessageType::EchoRequest)
     }
 
     #[test]
@@ -115,9 +199,12 @@ mod tests {
             0xAB, // Restart Counter
         ];
 
-        if let Some((m, _pos)) = Message::parse(&message_bytes){
+        if let Ok((m, _pos)) = Message::parse(&message_bytes){
             assert_eq!(m.recovery.restart_counter, 0xAB);
         }
+        else {
+            assert!(false);
+        }
 
         let message_bytes = [InformationElementType::Recovery as u8,
             0, 1, // Length
@@ -129,8 +216,80 @@ mod tests {
             0xFF
         ];
 
-        if let Some((m, _pos)) = Message::parse(&message_bytes){
+        if let Ok((m, _pos)) = Message::parse(&message_bytes){
             assert_eq!(m.recovery.restart_counter, 0xAB);
         }
+        else {
+            assert!(false);
+        }
+        assert_eq!(Message::parse(&[]).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Recovery, 0)])));
+    }
+
+    #[test]
+    fn test_sending_node_features() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new(recovery::InformationElement::new(0x0F, 0).unwrap());
+        m.sending_node_features = Some(node_features::InformationElement::new(node_features::PRN | node_features::NTSR, 0).unwrap());
+
+        assert_eq!(m.length(), 10);
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            0x0F, // Restart Counter
+            InformationElementType::NodeFeatures as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0000_0101 // Supported Features
+        ]);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            if let Some(ie) = m.sending_node_features {
+                assert!(ie.prn());
+                assert!(ie.ntsr());
+                assert!(!ie.mabr());
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_private_extension() {
+        let mut buffer = [0; MTU];
+
+        let mut m = Message::new(recovery::InformationElement::new(0x0F, 0).unwrap());
+        m.private_extension = Some(private_extension::InformationElement::new(10415, &[0xDE, 0xAD], 0).unwrap());
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            0x0F, // Restart Counter
+            InformationElementType::PrivateExtension as u8,
+            0, 4, // Length
+            0, // Spare
+            0x28, 0xAF, // Enterprise ID
+            0xDE, 0xAD, // Proprietary Value
+        ]);
+
+        // It is included with the other IEs of the message
+        assert_eq!(m.ies().last().map(|ie| ie.information_element_type()), Some(InformationElementType::PrivateExtension));
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+        }
+        else {
+            assert!(false);
+        }
     }
 }
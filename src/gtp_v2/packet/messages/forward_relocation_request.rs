@@ -0,0 +1,500 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    imsi,
+    f_teid,
+    pdn_connection,
+    fqdn,
+    mm_context,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug)]
+enum FTeidInstance {
+    SenderFTeidForControlPlane = 0,
+    SgwS11S4FTeidForControlPlane = 1,
+}
+
+impl TryFrom<u8> for FTeidInstance
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(FTeidInstance::SenderFTeidForControlPlane),
+            1 => Ok(FTeidInstance::SgwS11S4FTeidForControlPlane),
+            _ => Err(format!("Unsupported F-TEID Instance ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Conditional            |   8.3
+        Sender F-TEID for Control Plane             |    Mandatory              |   8.22
+        MME/SGSN/AMF UE EPS PDN Connections         |    Conditional            |   8.39
+        SGW S11/S4 IP Address and TEID for CP       |    Conditional            |   8.22
+        SGW node name                               |    Conditional            |   8.66
+        MME/SGSN/AMF UE MM Context                  |    Mandatory              |   8.38
+        Indication Flags                            |    Conditional            |
+        E-UTRAN Transparent Container               |    Conditional            |
+        UTRAN Transparent Container                 |    Conditional            |
+        BSS Container                               |    Conditional            |
+        Target Identification                       |    Conditional            |
+        HRPD access node S101 IP address            |    Conditional            |
+        1xIWS S102 IP address                       |    Conditional            |
+        S1-AP Cause                                 |    Conditional            |
+        RANAP Cause                                 |    Conditional            |
+        BSSGP Cause                                 |    Conditional            |
+        Source Identification                       |    Conditional            |
+        Selected PLMN ID                            |    Conditional            |
+        Recovery                                    |    Conditional            |
+        Trace Information                           |    Conditional            |
+        Subscribed RFSP Index                       |    Conditional            |
+        RFSP Index in Use                           |    Conditional            |
+        CSG ID                                      |    Conditional            |
+        CSG Membership Indication                   |    Conditional            |
+        UE Time Zone                                |    Conditional            |
+        Serving Network                             |    Conditional            |
+        MME/SGSN LDN                                |    Optional               |
+        Additional MM context for SRVCC             |    Conditional Optional   |
+        Additional flags for SRVCC                  |    Conditional Optional   |
+        STN-SR                                      |    Conditional Optional   |
+        C-MSISDN                                    |    Conditional Optional   |
+        MDT Configuration                           |    Conditional Optional   |
+        SGSN node name                              |    Conditional Optional   |
+        MME node name                               |    Conditional Optional   |
+        User CSG Information                        |    Conditional Optional   |
+        Monitoring Event Information                |    Conditional Optional   |
+        UE Usage Type                               |    Conditional Optional   |
+        MME/SGSN UE SCEF PDN Connections            |    Conditional Optional   |
+        MSISDN                                      |    Conditional Optional   |
+        Source UDP Port Number                      |    Conditional Optional   |
+        Serving PLMN Rate Control                   |    Conditional Optional   |
+        Extended Trace Information                  |    Conditional Optional   |
+        Subscribed Additional RRM Policy Index      |    Conditional Optional   |
+        Additional RRM Policy Index in Use          |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub imsi: Option<imsi::InformationElement>,
+    pub sender_f_teid_for_control_plane: f_teid::InformationElement,
+    pub pdn_connections: Vec<pdn_connection::InformationElement>,
+    pub sgw_s11_s4_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub sgw_node_name: Option<fqdn::InformationElement>,
+    pub mm_context: mm_context::InformationElement,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(
+        sender_f_teid_for_control_plane: f_teid::InformationElement,
+        mm_context: mm_context::InformationElement,
+    ) -> Result<Message, String> {
+        if sender_f_teid_for_control_plane.instance() != FTeidInstance::SenderFTeidForControlPlane as u8 {
+            return Err(format!("Sender F-TEID for Control Plane must be instance {}", FTeidInstance::SenderFTeidForControlPlane as u8));
+        }
+
+        Ok(Message {
+            imsi: None,
+            sender_f_teid_for_control_plane,
+            pdn_connections: Vec::new(),
+            sgw_s11_s4_f_teid_for_control_plane: None,
+            sgw_node_name: None,
+            mm_context,
+            private_extension: None,
+        })
+    }
+
+    pub fn push_pdn_connection(&mut self, pdn_connection: pdn_connection::InformationElement) {
+        self.pdn_connections.push(pdn_connection);
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut pdn_connections: Vec<pdn_connection::InformationElement> = Vec::new();
+        let mut sgw_s11_s4_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut sgw_node_name: Option<fqdn::InformationElement> = None;
+        let mut mm_context: Option<mm_context::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::FTEID(ie) => {
+                        if let Ok(instance) = FTeidInstance::try_from(ie.instance()) {
+                            match instance
+                            {
+                                FTeidInstance::SenderFTeidForControlPlane => sender_f_teid_for_control_plane = Some(ie),
+                                FTeidInstance::SgwS11S4FTeidForControlPlane => sgw_s11_s4_f_teid_for_control_plane = Some(ie),
+                            }
+                        }
+                        else { /* Not an instance of F-TEID that we expect. Just ignore it */ }
+                    },
+                    InformationElement::PDNConnection(ie) => pdn_connections.push(ie),
+                    InformationElement::FQDN(ie) => sgw_node_name = Some(ie),
+                    InformationElement::MMContext(ie) => mm_context = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ForwardRelocationRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if sender_f_teid_for_control_plane.is_none() {
+            missing.push((InformationElementType::FTEID, FTeidInstance::SenderFTeidForControlPlane as u8));
+        }
+
+        if mm_context.is_none() {
+            // Any of the MM Context types satisfies this IE
+            missing.push((InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                imsi,
+                sender_f_teid_for_control_plane: sender_f_teid_for_control_plane.unwrap(),
+                pdn_connections,
+                sgw_s11_s4_f_teid_for_control_plane,
+                sgw_node_name,
+                mm_context: mm_context.unwrap(),
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ForwardRelocationRequest
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        if let Some(ref ie) = self.imsi {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.sender_f_teid_for_control_plane.wire_length();
+
+        for ie in &self.pdn_connections {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.mm_context.wire_length();
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        if let Some(ref ie) = self.imsi {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.sender_f_teid_for_control_plane.generate(&mut buffer[pos..]);
+
+        for ie in &self.pdn_connections {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.mm_context.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.imsi {
+            ies.push(ie);
+        }
+
+        ies.push(&self.sender_f_teid_for_control_plane);
+
+        for ie in &self.pdn_connections {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_node_name {
+            ies.push(ie);
+        }
+
+        ies.push(&self.mm_context);
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.imsi {
+                ie.dissect(d);
+            }
+
+            self.sender_f_teid_for_control_plane.dissect(d);
+
+            for ie in &self.pdn_connections {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_s11_s4_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_node_name {
+                ie.dissect(d);
+            }
+
+            self.mm_context.dissect(d);
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ascii;
+
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, apn, ambr, ebi, bearer_context, bearer_qos, ip_address};
+
+    use ascii::AsciiString;
+    use std::str::FromStr;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S10N26MmeGtpC,
+                0x87654321,
+                Some(Ipv4Addr::new(10,0,0,2)),
+                None,
+                FTeidInstance::SenderFTeidForControlPlane as u8
+            ).unwrap(),
+            mm_context::InformationElement::new(
+                InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets,
+                &[0x12, 0x34, 0x56, 0x78],
+                0
+            ).unwrap()
+        ).unwrap();
+
+        m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+
+        let mut pdn_connection = pdn_connection::InformationElement::new(
+            apn::InformationElement::new(AsciiString::from_str("internet").unwrap(), 0).unwrap(),
+            ebi::InformationElement::new(5, 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S5S8PgwGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,3)),
+                None,
+                0
+            ).unwrap(),
+            vec![
+                bearer_context::InformationElement::new(
+                    ebi::InformationElement::new(5, 0).unwrap(),
+                    bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                    0
+                ).unwrap()
+            ],
+            ambr::InformationElement::new(0x1000, 0x2000, 0).unwrap(),
+            0
+        ).unwrap();
+        pdn_connection.ipv4_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,45,0,1)), 0).unwrap());
+
+        m.push_pdn_connection(pdn_connection);
+
+        m.sgw_s11_s4_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11S4SgwGtpC,
+                0xCAFEF00D,
+                Some(Ipv4Addr::new(10,0,0,4)),
+                None,
+                FTeidInstance::SgwS11S4FTeidForControlPlane as u8
+            ).unwrap()
+        );
+
+        m
+    }
+
+    #[test]
+    fn test_new() {
+        // The Sender F-TEID must be instance 0
+        assert!(Message::new(
+            f_teid::InformationElement::new(f_teid::InterfaceType::S10N26MmeGtpC, 0, None, None, 1).unwrap(),
+            mm_context::InformationElement::new(
+                InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets,
+                &[0x12, 0x34, 0x56, 0x78],
+                0
+            ).unwrap()
+        ).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(pos, 12 + 13 + 90 + 13 + 8);
+
+        assert_eq!(buffer[..25], [
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S10N26MmeGtpC)),
+            0x87, 0x65, 0x43, 0x21,
+            10, 0, 0, 2,
+        ]);
+
+        assert_eq!(buffer[pos-8..pos], [
+            InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets as u8,
+            0, 4, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, 0x78, // MM Context
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 12 + 13 + 90 + 13 + 8);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ForwardRelocationRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, m_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m_pos, pos);
+            assert_eq!(m.sender_f_teid_for_control_plane.teid, 0x87654321);
+            assert_eq!(m.mm_context.mm_context, vec![0x12, 0x34, 0x56, 0x78]);
+
+            assert_eq!(m.pdn_connections.len(), 1);
+            assert_eq!(m.pdn_connections[0].linked_eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(m.pdn_connections[0].ipv4_address.as_ref().map(|a| a.ip_address), Some(IpAddr::V4(Ipv4Addr::new(10,45,0,1))));
+            assert!(m.pdn_connections[0].ipv6_address.is_none());
+
+            if let Some(ie) = m.sgw_s11_s4_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0xCAFEF00D);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+        ];
+
+        assert_eq!(Message::parse(&message_bytes).err(), Some(ParseError::MandatoryIEMissing(vec![
+            (InformationElementType::FTEID, 0),
+            (InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets, 0),
+        ])));
+    }
+}
@@ -18,38 +18,233 @@ pub mod ambr;
 pub mod ue_time_zone;
 pub mod charging_characteristics;
 pub mod cause;
+pub mod fqdn;
+pub mod charging_id;
+pub mod fq_csid;
+pub mod ldn;
+pub mod arp;
+pub mod guti;
+pub mod complete_request_message;
+pub mod mm_context;
+pub mod pdn_connection;
+pub mod grouped_ie;
+pub mod timer;
+pub mod node_features;
+pub mod change_reporting_action;
+pub mod csg_information_reporting_action;
+pub mod serving_plmn_rate_control;
+pub mod apn_rate_control_status;
+pub mod trace_information;
+pub mod flow_qos;
+pub mod tad;
+pub mod pti;
+pub mod delay_value;
+pub mod throttling;
+pub mod epc_timer;
+pub mod ip_address;
+pub mod stn_sr;
+pub mod csg_id;
+pub mod cmi;
+pub mod uci;
+pub mod port_number;
+pub mod remote_user_id;
+pub mod remote_ue_ip_information;
+pub mod remote_ue_context;
+pub mod epco;
+pub mod indication;
+pub mod change_to_report_flags;
+pub mod private_extension;
+pub mod paging_and_service_information;
+pub mod apn_and_relative_capacity;
+pub mod extended_trace_information;
+pub mod node_identifier;
 
 use byteorder::{ByteOrder, NetworkEndian};
 
-use std::convert::TryFrom;
-use std::convert::TryInto;
+use core::convert::TryFrom;
+use core::convert::TryInto;
 
 use crate::field::*;
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
 pub const LENGTH: Field = 1..3;
+// The Type, Length and Spare/Instance octets that precede the contents of every IE
+pub const HEADER_LENGTH: u16 = 4;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum InformationElementType
 {
+    // TS 29.274 Table 8.1-1. 0 is reserved and 4 to 50 are reserved for S101 (TS 29.276) and S121 (TS 29.277)
+    IMSI = 1,
+    Cause = 2,
     Recovery = 3,
-    RATType = 82,
-    FTEID = 87,
+    // 51 to 70 are reserved for Sv (TS 29.280)
+    STNSR = 51,
+    SourceToTargetTransparentContainer = 52,
+    TargetToSourceTransparentContainer = 53,
+    MMContextForEUTRANSRVCC = 54,
+    MMContextForUTRANSRVCC = 55,
+    SRVCCCause = 56,
+    TargetRNCID = 57,
+    TargetGlobalCellID = 58,
+    TEIDC = 59,
+    SvFlags = 60,
+    ServiceAreaIdentifier = 61,
+    MMContextForCSToPSSRVCC = 62,
     APN = 71,
-    BearerContext = 93,
+    AMBR = 72,
     EBI = 73,
-    BearerQoS = 80,
-    IMSI = 1,
-    PDNType = 99,
-    PDNAddressAllocation = 79,
-    MSISDN = 76,
-    UserLocationInformation = 86,
+    IPAddress = 74,
     MEI = 75,
+    MSISDN = 76,
+    Indication = 77,
+    PCO = 78,
+    PDNAddressAllocation = 79,
+    BearerQoS = 80,
+    FlowQoS = 81,
+    RATType = 82,
     ServingNetwork = 83,
-    SelectionMode = 128,
-    APNRestriction = 127,
-    AMBR = 72,
-    UETimeZone = 114,
+    BearerTFT = 84,
+    TAD = 85,
+    UserLocationInformation = 86,
+    FTEID = 87,
+    TMSI = 88,
+    GlobalCNID = 89,
+    S103PDNDataForwardingInfo = 90,
+    S1UDataForwardingInfo = 91,
+    DelayValue = 92,
+    BearerContext = 93,
+    ChargingID = 94,
     ChargingCharacteristics = 95,
-    Cause = 2,
+    TraceInformation = 96,
+    BearerFlags = 97,
+    // 98 is reserved
+    PDNType = 99,
+    PTI = 100,
+    // 101 and 102 are reserved
+    MMContextGSMKeyAndTriplets = 103,
+    MMContextUMTSKeyUsedCipherAndQuintuplets = 104,
+    MMContextGSMKeyUsedCipherAndQuintuplets = 105,
+    MMContextUMTSKeyAndQuintuplets = 106,
+    MMContextEPSSecurityContextQuadrupletsAndQuintuplets = 107,
+    MMContextUMTSKeyQuadrupletsAndQuintuplets = 108,
+    PDNConnection = 109,
+    PDUNumbers = 110,
+    PTMSI = 111,
+    PTMSISignature = 112,
+    HopCounter = 113,
+    UETimeZone = 114,
+    TraceReference = 115,
+    CompleteRequestMessage = 116,
+    GUTI = 117,
+    FContainer = 118,
+    FCause = 119,
+    PLMNID = 120,
+    TargetIdentification = 121,
+    // 122 is reserved
+    PacketFlowID = 123,
+    RABContext = 124,
+    SourceRNCPDCPContextInfo = 125,
+    PortNumber = 126,
+    APNRestriction = 127,
+    SelectionMode = 128,
+    SourceIdentification = 129,
+    // 130 is reserved
+    ChangeReportingAction = 131,
+    FQCSID = 132,
+    ChannelNeeded = 133,
+    EMLPPPriority = 134,
+    NodeType = 135,
+    FQDN = 136,
+    TI = 137,
+    MBMSSessionDuration = 138,
+    MBMSServiceArea = 139,
+    MBMSSessionIdentifier = 140,
+    MBMSFlowIdentifier = 141,
+    MBMSIPMulticastDistribution = 142,
+    MBMSDistributionAcknowledge = 143,
+    RFSPIndex = 144,
+    UCI = 145,
+    CSGInformationReportingAction = 146,
+    CSGID = 147,
+    CMI = 148,
+    ServiceIndicator = 149,
+    DetachType = 150,
+    LDN = 151,
+    NodeFeatures = 152,
+    MBMSTimeToDataTransfer = 153,
+    Throttling = 154,
+    ARP = 155,
+    EPCTimer = 156,
+    SignallingPriorityIndication = 157,
+    TMGI = 158,
+    AdditionalMMContextForSRVCC = 159,
+    AdditionalFlagsForSRVCC = 160,
+    // 161 is reserved
+    MDTConfiguration = 162,
+    APCO = 163,
+    AbsoluteTimeOfMBMSDataTransfer = 164,
+    HeNBInformationReporting = 165,
+    IP4CP = 166,
+    ChangeToReportFlags = 167,
+    ActionIndication = 168,
+    TWANIdentifier = 169,
+    ULITimestamp = 170,
+    MBMSFlags = 171,
+    RANNASCause = 172,
+    CNOperatorSelectionEntity = 173,
+    TWMI = 174,
+    NodeNumber = 175,
+    NodeIdentifier = 176,
+    PresenceReportingAreaAction = 177,
+    PresenceReportingAreaInformation = 178,
+    TWANIdentifierTimestamp = 179,
+    OverloadControlInformation = 180,
+    LoadControlInformation = 181,
+    Metric = 182,
+    SequenceNumber = 183,
+    APNAndRelativeCapacity = 184,
+    WLANOffloadabilityIndication = 185,
+    PagingAndServiceInformation = 186,
+    IntegerNumber = 187,
+    MillisecondTimeStamp = 188,
+    MonitoringEventInformation = 189,
+    ECGIList = 190,
+    RemoteUEContext = 191,
+    RemoteUserID = 192,
+    RemoteUEIPInformation = 193,
+    CIoTOptimizationsSupportIndication = 194,
+    SCEFPDNConnection = 195,
+    HeaderCompressionConfiguration = 196,
+    EPCO = 197,
+    ServingPLMNRateControl = 198,
+    Counter = 199,
+    MappedUEUsageType = 200,
+    SecondaryRATUsageDataReport = 201,
+    UPFunctionSelectionIndicationFlags = 202,
+    MaximumPacketLossRate = 203,
+    APNRateControlStatus = 204,
+    ExtendedTraceInformation = 205,
+    MonitoringEventExtensionInformation = 206,
+    AdditionalRRMPolicyIndex = 207,
+    V2XContext = 208,
+    PC5QoSParameters = 209,
+    ServicesAuthorized = 210,
+    BitRate = 211,
+    PC5QoSFlow = 212,
+    SGiPtPTunnelAddress = 213,
+    PGWChangeInfo = 214,
+    PGWSetFQDN = 215,
+    GroupId = 216,
+    PSCellID = 217,
+    UPSecurityPolicy = 218,
+    AlternativeIMSI = 219,
+    // 220 to 253 are spare
+    IETypeExtension = 254,
+    PrivateExtension = 255,
 }
 
 impl TryFrom<u8> for InformationElementType
@@ -58,26 +253,166 @@ impl TryFrom<u8> for InformationElementType
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
+            1 => Ok(InformationElementType::IMSI),
+            2 => Ok(InformationElementType::Cause),
             3 => Ok(InformationElementType::Recovery),
-            82 => Ok(InformationElementType::RATType),
-            87 => Ok(InformationElementType::FTEID),
+            51 => Ok(InformationElementType::STNSR),
+            52 => Ok(InformationElementType::SourceToTargetTransparentContainer),
+            53 => Ok(InformationElementType::TargetToSourceTransparentContainer),
+            54 => Ok(InformationElementType::MMContextForEUTRANSRVCC),
+            55 => Ok(InformationElementType::MMContextForUTRANSRVCC),
+            56 => Ok(InformationElementType::SRVCCCause),
+            57 => Ok(InformationElementType::TargetRNCID),
+            58 => Ok(InformationElementType::TargetGlobalCellID),
+            59 => Ok(InformationElementType::TEIDC),
+            60 => Ok(InformationElementType::SvFlags),
+            61 => Ok(InformationElementType::ServiceAreaIdentifier),
+            62 => Ok(InformationElementType::MMContextForCSToPSSRVCC),
             71 => Ok(InformationElementType::APN),
-            93 => Ok(InformationElementType::BearerContext),
+            72 => Ok(InformationElementType::AMBR),
             73 => Ok(InformationElementType::EBI),
-            80 => Ok(InformationElementType::BearerQoS),
-            1 => Ok(InformationElementType::IMSI),
-            99 => Ok(InformationElementType::PDNType),
-            79 => Ok(InformationElementType::PDNAddressAllocation),
+            74 => Ok(InformationElementType::IPAddress),
+            75 => Ok(InformationElementType::MEI),
             76 => Ok(InformationElementType::MSISDN),
-            86 => Ok(InformationElementType::UserLocationInformation),
-            75 => Ok(InformationElementType::UserLocationInformation),
+            77 => Ok(InformationElementType::Indication),
+            78 => Ok(InformationElementType::PCO),
+            79 => Ok(InformationElementType::PDNAddressAllocation),
+            80 => Ok(InformationElementType::BearerQoS),
+            81 => Ok(InformationElementType::FlowQoS),
+            82 => Ok(InformationElementType::RATType),
             83 => Ok(InformationElementType::ServingNetwork),
-            128 => Ok(InformationElementType::SelectionMode),
-            127 => Ok(InformationElementType::APNRestriction),
-            72 => Ok(InformationElementType::AMBR),
-            114 => Ok(InformationElementType::UETimeZone),
+            84 => Ok(InformationElementType::BearerTFT),
+            85 => Ok(InformationElementType::TAD),
+            86 => Ok(InformationElementType::UserLocationInformation),
+            87 => Ok(InformationElementType::FTEID),
+            88 => Ok(InformationElementType::TMSI),
+            89 => Ok(InformationElementType::GlobalCNID),
+            90 => Ok(InformationElementType::S103PDNDataForwardingInfo),
+            91 => Ok(InformationElementType::S1UDataForwardingInfo),
+            92 => Ok(InformationElementType::DelayValue),
+            93 => Ok(InformationElementType::BearerContext),
+            94 => Ok(InformationElementType::ChargingID),
             95 => Ok(InformationElementType::ChargingCharacteristics),
-            2 => Ok(InformationElementType::Cause),
+            96 => Ok(InformationElementType::TraceInformation),
+            97 => Ok(InformationElementType::BearerFlags),
+            99 => Ok(InformationElementType::PDNType),
+            100 => Ok(InformationElementType::PTI),
+            103 => Ok(InformationElementType::MMContextGSMKeyAndTriplets),
+            104 => Ok(InformationElementType::MMContextUMTSKeyUsedCipherAndQuintuplets),
+            105 => Ok(InformationElementType::MMContextGSMKeyUsedCipherAndQuintuplets),
+            106 => Ok(InformationElementType::MMContextUMTSKeyAndQuintuplets),
+            107 => Ok(InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets),
+            108 => Ok(InformationElementType::MMContextUMTSKeyQuadrupletsAndQuintuplets),
+            109 => Ok(InformationElementType::PDNConnection),
+            110 => Ok(InformationElementType::PDUNumbers),
+            111 => Ok(InformationElementType::PTMSI),
+            112 => Ok(InformationElementType::PTMSISignature),
+            113 => Ok(InformationElementType::HopCounter),
+            114 => Ok(InformationElementType::UETimeZone),
+            115 => Ok(InformationElementType::TraceReference),
+            116 => Ok(InformationElementType::CompleteRequestMessage),
+            117 => Ok(InformationElementType::GUTI),
+            118 => Ok(InformationElementType::FContainer),
+            119 => Ok(InformationElementType::FCause),
+            120 => Ok(InformationElementType::PLMNID),
+            121 => Ok(InformationElementType::TargetIdentification),
+            123 => Ok(InformationElementType::PacketFlowID),
+            124 => Ok(InformationElementType::RABContext),
+            125 => Ok(InformationElementType::SourceRNCPDCPContextInfo),
+            126 => Ok(InformationElementType::PortNumber),
+            127 => Ok(InformationElementType::APNRestriction),
+            128 => Ok(InformationElementType::SelectionMode),
+            129 => Ok(InformationElementType::SourceIdentification),
+            131 => Ok(InformationElementType::ChangeReportingAction),
+            132 => Ok(InformationElementType::FQCSID),
+            133 => Ok(InformationElementType::ChannelNeeded),
+            134 => Ok(InformationElementType::EMLPPPriority),
+            135 => Ok(InformationElementType::NodeType),
+            136 => Ok(InformationElementType::FQDN),
+            137 => Ok(InformationElementType::TI),
+            138 => Ok(InformationElementType::MBMSSessionDuration),
+            139 => Ok(InformationElementType::MBMSServiceArea),
+            140 => Ok(InformationElementType::MBMSSessionIdentifier),
+            141 => Ok(InformationElementType::MBMSFlowIdentifier),
+            142 => Ok(InformationElementType::MBMSIPMulticastDistribution),
+            143 => Ok(InformationElementType::MBMSDistributionAcknowledge),
+            144 => Ok(InformationElementType::RFSPIndex),
+            145 => Ok(InformationElementType::UCI),
+            146 => Ok(InformationElementType::CSGInformationReportingAction),
+            147 => Ok(InformationElementType::CSGID),
+            148 => Ok(InformationElementType::CMI),
+            149 => Ok(InformationElementType::ServiceIndicator),
+            150 => Ok(InformationElementType::DetachType),
+            151 => Ok(InformationElementType::LDN),
+            152 => Ok(InformationElementType::NodeFeatures),
+            153 => Ok(InformationElementType::MBMSTimeToDataTransfer),
+            154 => Ok(InformationElementType::Throttling),
+            155 => Ok(InformationElementType::ARP),
+            156 => Ok(InformationElementType::EPCTimer),
+            157 => Ok(InformationElementType::SignallingPriorityIndication),
+            158 => Ok(InformationElementType::TMGI),
+            159 => Ok(InformationElementType::AdditionalMMContextForSRVCC),
+            160 => Ok(InformationElementType::AdditionalFlagsForSRVCC),
+            162 => Ok(InformationElementType::MDTConfiguration),
+            163 => Ok(InformationElementType::APCO),
+            164 => Ok(InformationElementType::AbsoluteTimeOfMBMSDataTransfer),
+            165 => Ok(InformationElementType::HeNBInformationReporting),
+            166 => Ok(InformationElementType::IP4CP),
+            167 => Ok(InformationElementType::ChangeToReportFlags),
+            168 => Ok(InformationElementType::ActionIndication),
+            169 => Ok(InformationElementType::TWANIdentifier),
+            170 => Ok(InformationElementType::ULITimestamp),
+            171 => Ok(InformationElementType::MBMSFlags),
+            172 => Ok(InformationElementType::RANNASCause),
+            173 => Ok(InformationElementType::CNOperatorSelectionEntity),
+            174 => Ok(InformationElementType::TWMI),
+            175 => Ok(InformationElementType::NodeNumber),
+            176 => Ok(InformationElementType::NodeIdentifier),
+            177 => Ok(InformationElementType::PresenceReportingAreaAction),
+            178 => Ok(InformationElementType::PresenceReportingAreaInformation),
+            179 => Ok(InformationElementType::TWANIdentifierTimestamp),
+            180 => Ok(InformationElementType::OverloadControlInformation),
+            181 => Ok(InformationElementType::LoadControlInformation),
+            182 => Ok(InformationElementType::Metric),
+            183 => Ok(InformationElementType::SequenceNumber),
+            184 => Ok(InformationElementType::APNAndRelativeCapacity),
+            185 => Ok(InformationElementType::WLANOffloadabilityIndication),
+            186 => Ok(InformationElementType::PagingAndServiceInformation),
+            187 => Ok(InformationElementType::IntegerNumber),
+            188 => Ok(InformationElementType::MillisecondTimeStamp),
+            189 => Ok(InformationElementType::MonitoringEventInformation),
+            190 => Ok(InformationElementType::ECGIList),
+            191 => Ok(InformationElementType::RemoteUEContext),
+            192 => Ok(InformationElementType::RemoteUserID),
+            193 => Ok(InformationElementType::RemoteUEIPInformation),
+            194 => Ok(InformationElementType::CIoTOptimizationsSupportIndication),
+            195 => Ok(InformationElementType::SCEFPDNConnection),
+            196 => Ok(InformationElementType::HeaderCompressionConfiguration),
+            197 => Ok(InformationElementType::EPCO),
+            198 => Ok(InformationElementType::ServingPLMNRateControl),
+            199 => Ok(InformationElementType::Counter),
+            200 => Ok(InformationElementType::MappedUEUsageType),
+            201 => Ok(InformationElementType::SecondaryRATUsageDataReport),
+            202 => Ok(InformationElementType::UPFunctionSelectionIndicationFlags),
+            203 => Ok(InformationElementType::MaximumPacketLossRate),
+            204 => Ok(InformationElementType::APNRateControlStatus),
+            205 => Ok(InformationElementType::ExtendedTraceInformation),
+            206 => Ok(InformationElementType::MonitoringEventExtensionInformation),
+            207 => Ok(InformationElementType::AdditionalRRMPolicyIndex),
+            208 => Ok(InformationElementType::V2XContext),
+            209 => Ok(InformationElementType::PC5QoSParameters),
+            210 => Ok(InformationElementType::ServicesAuthorized),
+            211 => Ok(InformationElementType::BitRate),
+            212 => Ok(InformationElementType::PC5QoSFlow),
+            213 => Ok(InformationElementType::SGiPtPTunnelAddress),
+            214 => Ok(InformationElementType::PGWChangeInfo),
+            215 => Ok(InformationElementType::PGWSetFQDN),
+            216 => Ok(InformationElementType::GroupId),
+            217 => Ok(InformationElementType::PSCellID),
+            218 => Ok(InformationElementType::UPSecurityPolicy),
+            219 => Ok(InformationElementType::AlternativeIMSI),
+            254 => Ok(InformationElementType::IETypeExtension),
+            255 => Ok(InformationElementType::PrivateExtension),
             _ => Err(format!("Unsupported IE type ({})", value).to_string())
         }
     }
@@ -85,12 +420,27 @@ impl TryFrom<u8> for InformationElementType
 
 pub trait InformationElementTraits {
     fn information_element_type(&self) -> InformationElementType;
-    fn length(&self) -> u16;
+
+    // Length of the contents of the IE, EXCLUDING the first 4 octets. This is the value of the Length field.
+    fn payload_length(&self) -> u16;
+
+    // Number of octets written by generate(), INCLUDING the first 4 octets
+    fn wire_length(&self) -> u16 {
+        HEADER_LENGTH + self.payload_length()
+    }
+
+    #[deprecated(note = "use wire_length() for the encoded size or payload_length() for the Length field")]
+    fn length(&self) -> u16 {
+        self.wire_length()
+    }
+
     fn generate(&self, buffer: &mut[u8]) -> usize;
     fn instance(&self) -> u8;
     fn set_instance(&mut self, instance: u8) -> Result<u8, String>;
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum InformationElement
 {
     Recovery(recovery::InformationElement),
@@ -113,6 +463,44 @@ pub enum InformationElement
     UETimeZone(ue_time_zone::InformationElement),
     ChargingCharacteristics(charging_characteristics::InformationElement),
     Cause(cause::InformationElement),
+    FQDN(fqdn::InformationElement),
+    ChargingID(charging_id::InformationElement),
+    FQCSID(fq_csid::InformationElement),
+    LDN(ldn::InformationElement),
+    ARP(arp::InformationElement),
+    GUTI(guti::InformationElement),
+    CompleteRequestMessage(complete_request_message::InformationElement),
+    MMContext(mm_context::InformationElement),
+    PDNConnection(pdn_connection::InformationElement),
+    NodeFeatures(node_features::InformationElement),
+    ChangeReportingAction(change_reporting_action::InformationElement),
+    CSGInformationReportingAction(csg_information_reporting_action::InformationElement),
+    ServingPLMNRateControl(serving_plmn_rate_control::InformationElement),
+    APNRateControlStatus(apn_rate_control_status::InformationElement),
+    TraceInformation(trace_information::InformationElement),
+    FlowQoS(flow_qos::InformationElement),
+    TAD(tad::InformationElement),
+    PTI(pti::InformationElement),
+    DelayValue(delay_value::InformationElement),
+    Throttling(throttling::InformationElement),
+    EPCTimer(epc_timer::InformationElement),
+    IPAddress(ip_address::InformationElement),
+    STNSR(stn_sr::InformationElement),
+    CSGID(csg_id::InformationElement),
+    CMI(cmi::InformationElement),
+    UCI(uci::InformationElement),
+    PortNumber(port_number::InformationElement),
+    RemoteUserID(remote_user_id::InformationElement),
+    RemoteUEIPInformation(remote_ue_ip_information::InformationElement),
+    RemoteUEContext(remote_ue_context::InformationElement),
+    EPCO(epco::InformationElement),
+    Indication(indication::InformationElement),
+    ChangeToReportFlags(change_to_report_flags::InformationElement),
+    PrivateExtension(private_extension::InformationElement),
+    PagingAndServiceInformation(paging_and_service_information::InformationElement),
+    APNAndRelativeCapacity(apn_and_relative_capacity::InformationElement),
+    ExtendedTraceInformation(extended_trace_information::InformationElement),
+    NodeIdentifier(node_identifier::InformationElement),
 }
 
 impl InformationElement {
@@ -120,6 +508,10 @@ impl InformationElement {
         // First we parse the first byte to determine the type of the IE.
         // Then we pass off the parsing to the specific IE implementation
 
+        if buffer.len() < 4 {
+            return None
+        }
+
         let ie_type = buffer[0];
 
         if let Ok(ie_type) = ie_type.try_into() {
@@ -284,9 +676,319 @@ impl InformationElement {
                         None
                     }
                 },
-                // _ => {
-                //     None
-                // }
+                InformationElementType::FQDN => {
+                    if let Some((ie, pos)) = fqdn::InformationElement::parse(buffer) {
+                        Some((InformationElement::FQDN(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ChargingID => {
+                    if let Some((ie, pos)) = charging_id::InformationElement::parse(buffer) {
+                        Some((InformationElement::ChargingID(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::FQCSID => {
+                    if let Some((ie, pos)) = fq_csid::InformationElement::parse(buffer) {
+                        Some((InformationElement::FQCSID(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::LDN => {
+                    if let Some((ie, pos)) = ldn::InformationElement::parse(buffer) {
+                        Some((InformationElement::LDN(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ARP => {
+                    if let Some((ie, pos)) = arp::InformationElement::parse(buffer) {
+                        Some((InformationElement::ARP(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::GUTI => {
+                    if let Some((ie, pos)) = guti::InformationElement::parse(buffer) {
+                        Some((InformationElement::GUTI(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::CompleteRequestMessage => {
+                    if let Some((ie, pos)) = complete_request_message::InformationElement::parse(buffer) {
+                        Some((InformationElement::CompleteRequestMessage(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::MMContextGSMKeyAndTriplets |
+                InformationElementType::MMContextUMTSKeyUsedCipherAndQuintuplets |
+                InformationElementType::MMContextGSMKeyUsedCipherAndQuintuplets |
+                InformationElementType::MMContextUMTSKeyAndQuintuplets |
+                InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets |
+                InformationElementType::MMContextUMTSKeyQuadrupletsAndQuintuplets => {
+                    if let Some((ie, pos)) = mm_context::InformationElement::parse(buffer) {
+                        Some((InformationElement::MMContext(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PDNConnection => {
+                    if let Some((ie, pos)) = pdn_connection::InformationElement::parse(buffer) {
+                        Some((InformationElement::PDNConnection(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::NodeFeatures => {
+                    if let Some((ie, pos)) = node_features::InformationElement::parse(buffer) {
+                        Some((InformationElement::NodeFeatures(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ChangeReportingAction => {
+                    if let Some((ie, pos)) = change_reporting_action::InformationElement::parse(buffer) {
+                        Some((InformationElement::ChangeReportingAction(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::CSGInformationReportingAction => {
+                    if let Some((ie, pos)) = csg_information_reporting_action::InformationElement::parse(buffer) {
+                        Some((InformationElement::CSGInformationReportingAction(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ServingPLMNRateControl => {
+                    if let Some((ie, pos)) = serving_plmn_rate_control::InformationElement::parse(buffer) {
+                        Some((InformationElement::ServingPLMNRateControl(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::APNRateControlStatus => {
+                    if let Some((ie, pos)) = apn_rate_control_status::InformationElement::parse(buffer) {
+                        Some((InformationElement::APNRateControlStatus(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TraceInformation => {
+                    if let Some((ie, pos)) = trace_information::InformationElement::parse(buffer) {
+                        Some((InformationElement::TraceInformation(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::FlowQoS => {
+                    if let Some((ie, pos)) = flow_qos::InformationElement::parse(buffer) {
+                        Some((InformationElement::FlowQoS(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::TAD => {
+                    if let Some((ie, pos)) = tad::InformationElement::parse(buffer) {
+                        Some((InformationElement::TAD(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PTI => {
+                    if let Some((ie, pos)) = pti::InformationElement::parse(buffer) {
+                        Some((InformationElement::PTI(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::DelayValue => {
+                    if let Some((ie, pos)) = delay_value::InformationElement::parse(buffer) {
+                        Some((InformationElement::DelayValue(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Throttling => {
+                    if let Some((ie, pos)) = throttling::InformationElement::parse(buffer) {
+                        Some((InformationElement::Throttling(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::EPCTimer => {
+                    if let Some((ie, pos)) = epc_timer::InformationElement::parse(buffer) {
+                        Some((InformationElement::EPCTimer(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::IPAddress => {
+                    if let Some((ie, pos)) = ip_address::InformationElement::parse(buffer) {
+                        Some((InformationElement::IPAddress(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::STNSR => {
+                    if let Some((ie, pos)) = stn_sr::InformationElement::parse(buffer) {
+                        Some((InformationElement::STNSR(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::CSGID => {
+                    if let Some((ie, pos)) = csg_id::InformationElement::parse(buffer) {
+                        Some((InformationElement::CSGID(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::CMI => {
+                    if let Some((ie, pos)) = cmi::InformationElement::parse(buffer) {
+                        Some((InformationElement::CMI(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::UCI => {
+                    if let Some((ie, pos)) = uci::InformationElement::parse(buffer) {
+                        Some((InformationElement::UCI(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PortNumber => {
+                    if let Some((ie, pos)) = port_number::InformationElement::parse(buffer) {
+                        Some((InformationElement::PortNumber(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::RemoteUserID => {
+                    if let Some((ie, pos)) = remote_user_id::InformationElement::parse(buffer) {
+                        Some((InformationElement::RemoteUserID(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::RemoteUEIPInformation => {
+                    if let Some((ie, pos)) = remote_ue_ip_information::InformationElement::parse(buffer) {
+                        Some((InformationElement::RemoteUEIPInformation(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::RemoteUEContext => {
+                    if let Some((ie, pos)) = remote_ue_context::InformationElement::parse(buffer) {
+                        Some((InformationElement::RemoteUEContext(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::EPCO => {
+                    if let Some((ie, pos)) = epco::InformationElement::parse(buffer) {
+                        Some((InformationElement::EPCO(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::Indication => {
+                    if let Some((ie, pos)) = indication::InformationElement::parse(buffer) {
+                        Some((InformationElement::Indication(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ChangeToReportFlags => {
+                    if let Some((ie, pos)) = change_to_report_flags::InformationElement::parse(buffer) {
+                        Some((InformationElement::ChangeToReportFlags(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PrivateExtension => {
+                    if let Some((ie, pos)) = private_extension::InformationElement::parse(buffer) {
+                        Some((InformationElement::PrivateExtension(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::PagingAndServiceInformation => {
+                    if let Some((ie, pos)) = paging_and_service_information::InformationElement::parse(buffer) {
+                        Some((InformationElement::PagingAndServiceInformation(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::APNAndRelativeCapacity => {
+                    if let Some((ie, pos)) = apn_and_relative_capacity::InformationElement::parse(buffer) {
+                        Some((InformationElement::APNAndRelativeCapacity(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::ExtendedTraceInformation => {
+                    if let Some((ie, pos)) = extended_trace_information::InformationElement::parse(buffer) {
+                        Some((InformationElement::ExtendedTraceInformation(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                InformationElementType::NodeIdentifier => {
+                    if let Some((ie, pos)) = node_identifier::InformationElement::parse(buffer) {
+                        Some((InformationElement::NodeIdentifier(ie), pos))
+                    }
+                    else {
+                        None
+                    }
+                },
+                _ => {
+                    // A known type that isn't decoded yet
+                    None
+                }
             }
         }
         else {
@@ -298,38 +1000,98 @@ impl InformationElement {
         // This function will return the number of bytes to advance the buffer to skip over parsing this IE.
         // This is useful if we encounter an IE that we can't parse but want to continue to try to parse other elements.
 
+        if buffer.len() < 4 {
+            // A truncated IE header so skip the rest of the buffer
+            debug!("Skipping {} bytes of truncated IE header", buffer.len());
+            return buffer.len()
+        }
+
         // Read length of IE
         let length = NetworkEndian::read_u16(&buffer[LENGTH]);
-        
+
+        debug!("Skipping IE type {} (instance {}) of {} bytes that couldn't be parsed", buffer[0], buffer[3] & 0xF, length as usize + 4);
+
         // Add 4 for IE header
-        (length + 4) as usize
+        length as usize + 4
+    }
+
+    pub fn type_and_instance(buffer: &[u8]) -> Option<(InformationElementType, u8)> {
+        // Reads the IE Type and Instance from the IE header without parsing the rest of the IE.
+        // This is useful to identify an IE that failed to parse.
+
+        if buffer.len() < 4 {
+            return None
+        }
+
+        match InformationElementType::try_from(buffer[0]) {
+            Ok(ie_type) => Some((ie_type, buffer[3] & 0xF)),
+            Err(_) => None
+        }
     }
 }
 
 impl InformationElementTraits for InformationElement
 {
-    fn length(&self) -> u16 {
+    fn payload_length(&self) -> u16 {
         match self {
-            InformationElement::Recovery(ie) => ie.length(),
-            InformationElement::RATType(ie) => ie.length(),
-            InformationElement::APN(ie) => ie.length(),
-            InformationElement::FTEID(ie) => ie.length(),
-            InformationElement::BearerContext(ie) => ie.length(),
-            InformationElement::EBI(ie) => ie.length(),
-            InformationElement::BearerQoS(ie) => ie.length(),
-            InformationElement::IMSI(ie) => ie.length(),
-            InformationElement::PDNType(ie) => ie.length(),
-            InformationElement::PDNAddressAllocation(ie) => ie.length(),
-            InformationElement::MSISDN(ie) => ie.length(),
-            InformationElement::ULI(ie) => ie.length(),
-            InformationElement::MEI(ie) => ie.length(),
-            InformationElement::ServingNetwork(ie) => ie.length(),
-            InformationElement::SelectionMode(ie) => ie.length(),
-            InformationElement::APNRestriction(ie) => ie.length(),
-            InformationElement::AMBR(ie) => ie.length(),
-            InformationElement::UETimeZone(ie) => ie.length(),
-            InformationElement::ChargingCharacteristics(ie) => ie.length(),
-            InformationElement::Cause(ie) => ie.length(),
+            InformationElement::Recovery(ie) => ie.payload_length(),
+            InformationElement::RATType(ie) => ie.payload_length(),
+            InformationElement::APN(ie) => ie.payload_length(),
+            InformationElement::FTEID(ie) => ie.payload_length(),
+            InformationElement::BearerContext(ie) => ie.payload_length(),
+            InformationElement::EBI(ie) => ie.payload_length(),
+            InformationElement::BearerQoS(ie) => ie.payload_length(),
+            InformationElement::IMSI(ie) => ie.payload_length(),
+            InformationElement::PDNType(ie) => ie.payload_length(),
+            InformationElement::PDNAddressAllocation(ie) => ie.payload_length(),
+            InformationElement::MSISDN(ie) => ie.payload_length(),
+            InformationElement::ULI(ie) => ie.payload_length(),
+            InformationElement::MEI(ie) => ie.payload_length(),
+            InformationElement::ServingNetwork(ie) => ie.payload_length(),
+            InformationElement::SelectionMode(ie) => ie.payload_length(),
+            InformationElement::APNRestriction(ie) => ie.payload_length(),
+            InformationElement::AMBR(ie) => ie.payload_length(),
+            InformationElement::UETimeZone(ie) => ie.payload_length(),
+            InformationElement::ChargingCharacteristics(ie) => ie.payload_length(),
+            InformationElement::Cause(ie) => ie.payload_length(),
+            InformationElement::LDN(ie) => ie.payload_length(),
+            InformationElement::FQCSID(ie) => ie.payload_length(),
+            InformationElement::ChargingID(ie) => ie.payload_length(),
+            InformationElement::FQDN(ie) => ie.payload_length(),
+            InformationElement::ARP(ie) => ie.payload_length(),
+            InformationElement::GUTI(ie) => ie.payload_length(),
+            InformationElement::CompleteRequestMessage(ie) => ie.payload_length(),
+            InformationElement::MMContext(ie) => ie.payload_length(),
+            InformationElement::PDNConnection(ie) => ie.payload_length(),
+            InformationElement::NodeFeatures(ie) => ie.payload_length(),
+            InformationElement::ChangeReportingAction(ie) => ie.payload_length(),
+            InformationElement::CSGInformationReportingAction(ie) => ie.payload_length(),
+            InformationElement::ServingPLMNRateControl(ie) => ie.payload_length(),
+            InformationElement::APNRateControlStatus(ie) => ie.payload_length(),
+            InformationElement::TraceInformation(ie) => ie.payload_length(),
+            InformationElement::FlowQoS(ie) => ie.payload_length(),
+            InformationElement::TAD(ie) => ie.payload_length(),
+            InformationElement::PTI(ie) => ie.payload_length(),
+            InformationElement::DelayValue(ie) => ie.payload_length(),
+            InformationElement::Throttling(ie) => ie.payload_length(),
+            InformationElement::EPCTimer(ie) => ie.payload_length(),
+            InformationElement::IPAddress(ie) => ie.payload_length(),
+            InformationElement::STNSR(ie) => ie.payload_length(),
+            InformationElement::CSGID(ie) => ie.payload_length(),
+            InformationElement::CMI(ie) => ie.payload_length(),
+            InformationElement::UCI(ie) => ie.payload_length(),
+            InformationElement::PortNumber(ie) => ie.payload_length(),
+            InformationElement::RemoteUserID(ie) => ie.payload_length(),
+            InformationElement::RemoteUEIPInformation(ie) => ie.payload_length(),
+            InformationElement::RemoteUEContext(ie) => ie.payload_length(),
+            InformationElement::EPCO(ie) => ie.payload_length(),
+            InformationElement::Indication(ie) => ie.payload_length(),
+            InformationElement::ChangeToReportFlags(ie) => ie.payload_length(),
+            InformationElement::PrivateExtension(ie) => ie.payload_length(),
+            InformationElement::PagingAndServiceInformation(ie) => ie.payload_length(),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.payload_length(),
+            InformationElement::ExtendedTraceInformation(ie) => ie.payload_length(),
+            InformationElement::NodeIdentifier(ie) => ie.payload_length(),
         }
     }
 
@@ -355,6 +1117,44 @@ impl InformationElementTraits for InformationElement
             InformationElement::UETimeZone(ie) => ie.instance(),
             InformationElement::ChargingCharacteristics(ie) => ie.instance(),
             InformationElement::Cause(ie) => ie.instance(),
+            InformationElement::LDN(ie) => ie.instance(),
+            InformationElement::FQCSID(ie) => ie.instance(),
+            InformationElement::ChargingID(ie) => ie.instance(),
+            InformationElement::FQDN(ie) => ie.instance(),
+            InformationElement::ARP(ie) => ie.instance(),
+            InformationElement::GUTI(ie) => ie.instance(),
+            InformationElement::CompleteRequestMessage(ie) => ie.instance(),
+            InformationElement::MMContext(ie) => ie.instance(),
+            InformationElement::PDNConnection(ie) => ie.instance(),
+            InformationElement::NodeFeatures(ie) => ie.instance(),
+            InformationElement::ChangeReportingAction(ie) => ie.instance(),
+            InformationElement::CSGInformationReportingAction(ie) => ie.instance(),
+            InformationElement::ServingPLMNRateControl(ie) => ie.instance(),
+            InformationElement::APNRateControlStatus(ie) => ie.instance(),
+            InformationElement::TraceInformation(ie) => ie.instance(),
+            InformationElement::FlowQoS(ie) => ie.instance(),
+            InformationElement::TAD(ie) => ie.instance(),
+            InformationElement::PTI(ie) => ie.instance(),
+            InformationElement::DelayValue(ie) => ie.instance(),
+            InformationElement::Throttling(ie) => ie.instance(),
+            InformationElement::EPCTimer(ie) => ie.instance(),
+            InformationElement::IPAddress(ie) => ie.instance(),
+            InformationElement::STNSR(ie) => ie.instance(),
+            InformationElement::CSGID(ie) => ie.instance(),
+            InformationElement::CMI(ie) => ie.instance(),
+            InformationElement::UCI(ie) => ie.instance(),
+            InformationElement::PortNumber(ie) => ie.instance(),
+            InformationElement::RemoteUserID(ie) => ie.instance(),
+            InformationElement::RemoteUEIPInformation(ie) => ie.instance(),
+            InformationElement::RemoteUEContext(ie) => ie.instance(),
+            InformationElement::EPCO(ie) => ie.instance(),
+            InformationElement::Indication(ie) => ie.instance(),
+            InformationElement::ChangeToReportFlags(ie) => ie.instance(),
+            InformationElement::PrivateExtension(ie) => ie.instance(),
+            InformationElement::PagingAndServiceInformation(ie) => ie.instance(),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.instance(),
+            InformationElement::ExtendedTraceInformation(ie) => ie.instance(),
+            InformationElement::NodeIdentifier(ie) => ie.instance(),
         }
     }
 
@@ -380,6 +1180,44 @@ impl InformationElementTraits for InformationElement
             InformationElement::UETimeZone(ie) => ie.set_instance(instance),
             InformationElement::ChargingCharacteristics(ie) => ie.set_instance(instance),
             InformationElement::Cause(ie) => ie.set_instance(instance),
+            InformationElement::LDN(ie) => ie.set_instance(instance),
+            InformationElement::FQCSID(ie) => ie.set_instance(instance),
+            InformationElement::ChargingID(ie) => ie.set_instance(instance),
+            InformationElement::FQDN(ie) => ie.set_instance(instance),
+            InformationElement::ARP(ie) => ie.set_instance(instance),
+            InformationElement::GUTI(ie) => ie.set_instance(instance),
+            InformationElement::CompleteRequestMessage(ie) => ie.set_instance(instance),
+            InformationElement::MMContext(ie) => ie.set_instance(instance),
+            InformationElement::PDNConnection(ie) => ie.set_instance(instance),
+            InformationElement::NodeFeatures(ie) => ie.set_instance(instance),
+            InformationElement::ChangeReportingAction(ie) => ie.set_instance(instance),
+            InformationElement::CSGInformationReportingAction(ie) => ie.set_instance(instance),
+            InformationElement::ServingPLMNRateControl(ie) => ie.set_instance(instance),
+            InformationElement::APNRateControlStatus(ie) => ie.set_instance(instance),
+            InformationElement::TraceInformation(ie) => ie.set_instance(instance),
+            InformationElement::FlowQoS(ie) => ie.set_instance(instance),
+            InformationElement::TAD(ie) => ie.set_instance(instance),
+            InformationElement::PTI(ie) => ie.set_instance(instance),
+            InformationElement::DelayValue(ie) => ie.set_instance(instance),
+            InformationElement::Throttling(ie) => ie.set_instance(instance),
+            InformationElement::EPCTimer(ie) => ie.set_instance(instance),
+            InformationElement::IPAddress(ie) => ie.set_instance(instance),
+            InformationElement::STNSR(ie) => ie.set_instance(instance),
+            InformationElement::CSGID(ie) => ie.set_instance(instance),
+            InformationElement::CMI(ie) => ie.set_instance(instance),
+            InformationElement::UCI(ie) => ie.set_instance(instance),
+            InformationElement::PortNumber(ie) => ie.set_instance(instance),
+            InformationElement::RemoteUserID(ie) => ie.set_instance(instance),
+            InformationElement::RemoteUEIPInformation(ie) => ie.set_instance(instance),
+            InformationElement::RemoteUEContext(ie) => ie.set_instance(instance),
+            InformationElement::EPCO(ie) => ie.set_instance(instance),
+            InformationElement::Indication(ie) => ie.set_instance(instance),
+            InformationElement::ChangeToReportFlags(ie) => ie.set_instance(instance),
+            InformationElement::PrivateExtension(ie) => ie.set_instance(instance),
+            InformationElement::PagingAndServiceInformation(ie) => ie.set_instance(instance),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.set_instance(instance),
+            InformationElement::ExtendedTraceInformation(ie) => ie.set_instance(instance),
+            InformationElement::NodeIdentifier(ie) => ie.set_instance(instance),
         }
     }
 
@@ -405,6 +1243,44 @@ impl InformationElementTraits for InformationElement
             InformationElement::UETimeZone(ie) => ie.generate(buffer),
             InformationElement::ChargingCharacteristics(ie) => ie.generate(buffer),
             InformationElement::Cause(ie) => ie.generate(buffer),
+            InformationElement::LDN(ie) => ie.generate(buffer),
+            InformationElement::FQCSID(ie) => ie.generate(buffer),
+            InformationElement::ChargingID(ie) => ie.generate(buffer),
+            InformationElement::FQDN(ie) => ie.generate(buffer),
+            InformationElement::ARP(ie) => ie.generate(buffer),
+            InformationElement::GUTI(ie) => ie.generate(buffer),
+            InformationElement::CompleteRequestMessage(ie) => ie.generate(buffer),
+            InformationElement::MMContext(ie) => ie.generate(buffer),
+            InformationElement::PDNConnection(ie) => ie.generate(buffer),
+            InformationElement::NodeFeatures(ie) => ie.generate(buffer),
+            InformationElement::ChangeReportingAction(ie) => ie.generate(buffer),
+            InformationElement::CSGInformationReportingAction(ie) => ie.generate(buffer),
+            InformationElement::ServingPLMNRateControl(ie) => ie.generate(buffer),
+            InformationElement::APNRateControlStatus(ie) => ie.generate(buffer),
+            InformationElement::TraceInformation(ie) => ie.generate(buffer),
+            InformationElement::FlowQoS(ie) => ie.generate(buffer),
+            InformationElement::TAD(ie) => ie.generate(buffer),
+            InformationElement::PTI(ie) => ie.generate(buffer),
+            InformationElement::DelayValue(ie) => ie.generate(buffer),
+            InformationElement::Throttling(ie) => ie.generate(buffer),
+            InformationElement::EPCTimer(ie) => ie.generate(buffer),
+            InformationElement::IPAddress(ie) => ie.generate(buffer),
+            InformationElement::STNSR(ie) => ie.generate(buffer),
+            InformationElement::CSGID(ie) => ie.generate(buffer),
+            InformationElement::CMI(ie) => ie.generate(buffer),
+            InformationElement::UCI(ie) => ie.generate(buffer),
+            InformationElement::PortNumber(ie) => ie.generate(buffer),
+            InformationElement::RemoteUserID(ie) => ie.generate(buffer),
+            InformationElement::RemoteUEIPInformation(ie) => ie.generate(buffer),
+            InformationElement::RemoteUEContext(ie) => ie.generate(buffer),
+            InformationElement::EPCO(ie) => ie.generate(buffer),
+            InformationElement::Indication(ie) => ie.generate(buffer),
+            InformationElement::ChangeToReportFlags(ie) => ie.generate(buffer),
+            InformationElement::PrivateExtension(ie) => ie.generate(buffer),
+            InformationElement::PagingAndServiceInformation(ie) => ie.generate(buffer),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.generate(buffer),
+            InformationElement::ExtendedTraceInformation(ie) => ie.generate(buffer),
+            InformationElement::NodeIdentifier(ie) => ie.generate(buffer),
         }
     }
 
@@ -430,6 +1306,226 @@ impl InformationElementTraits for InformationElement
             InformationElement::UETimeZone(ie) => ie.information_element_type(),
             InformationElement::ChargingCharacteristics(ie) => ie.information_element_type(),
             InformationElement::Cause(ie) => ie.information_element_type(),
+            InformationElement::LDN(ie) => ie.information_element_type(),
+            InformationElement::FQCSID(ie) => ie.information_element_type(),
+            InformationElement::ChargingID(ie) => ie.information_element_type(),
+            InformationElement::FQDN(ie) => ie.information_element_type(),
+            InformationElement::ARP(ie) => ie.information_element_type(),
+            InformationElement::GUTI(ie) => ie.information_element_type(),
+            InformationElement::CompleteRequestMessage(ie) => ie.information_element_type(),
+            InformationElement::MMContext(ie) => ie.information_element_type(),
+            InformationElement::PDNConnection(ie) => ie.information_element_type(),
+            InformationElement::NodeFeatures(ie) => ie.information_element_type(),
+            InformationElement::ChangeReportingAction(ie) => ie.information_element_type(),
+            InformationElement::CSGInformationReportingAction(ie) => ie.information_element_type(),
+            InformationElement::ServingPLMNRateControl(ie) => ie.information_element_type(),
+            InformationElement::APNRateControlStatus(ie) => ie.information_element_type(),
+            InformationElement::TraceInformation(ie) => ie.information_element_type(),
+            InformationElement::FlowQoS(ie) => ie.information_element_type(),
+            InformationElement::TAD(ie) => ie.information_element_type(),
+            InformationElement::PTI(ie) => ie.information_element_type(),
+            InformationElement::DelayValue(ie) => ie.information_element_type(),
+            InformationElement::Throttling(ie) => ie.information_element_type(),
+            InformationElement::EPCTimer(ie) => ie.information_element_type(),
+            InformationElement::IPAddress(ie) => ie.information_element_type(),
+            InformationElement::STNSR(ie) => ie.information_element_type(),
+            InformationElement::CSGID(ie) => ie.information_element_type(),
+            InformationElement::CMI(ie) => ie.information_element_type(),
+            InformationElement::UCI(ie) => ie.information_element_type(),
+            InformationElement::PortNumber(ie) => ie.information_element_type(),
+            InformationElement::RemoteUserID(ie) => ie.information_element_type(),
+            InformationElement::RemoteUEIPInformation(ie) => ie.information_element_type(),
+            InformationElement::RemoteUEContext(ie) => ie.information_element_type(),
+            InformationElement::EPCO(ie) => ie.information_element_type(),
+            InformationElement::Indication(ie) => ie.information_element_type(),
+            InformationElement::ChangeToReportFlags(ie) => ie.information_element_type(),
+            InformationElement::PrivateExtension(ie) => ie.information_element_type(),
+            InformationElement::PagingAndServiceInformation(ie) => ie.information_element_type(),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.information_element_type(),
+            InformationElement::ExtendedTraceInformation(ie) => ie.information_element_type(),
+            InformationElement::NodeIdentifier(ie) => ie.information_element_type(),
+        }
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        match self {
+            InformationElement::Recovery(ie) => ie.dissect(d),
+            InformationElement::RATType(ie) => ie.dissect(d),
+            InformationElement::FTEID(ie) => ie.dissect(d),
+            InformationElement::APN(ie) => ie.dissect(d),
+            InformationElement::BearerContext(ie) => ie.dissect(d),
+            InformationElement::EBI(ie) => ie.dissect(d),
+            InformationElement::BearerQoS(ie) => ie.dissect(d),
+            InformationElement::IMSI(ie) => ie.dissect(d),
+            InformationElement::PDNType(ie) => ie.dissect(d),
+            InformationElement::PDNAddressAllocation(ie) => ie.dissect(d),
+            InformationElement::MSISDN(ie) => ie.dissect(d),
+            InformationElement::ULI(ie) => ie.dissect(d),
+            InformationElement::MEI(ie) => ie.dissect(d),
+            InformationElement::ServingNetwork(ie) => ie.dissect(d),
+            InformationElement::SelectionMode(ie) => ie.dissect(d),
+            InformationElement::APNRestriction(ie) => ie.dissect(d),
+            InformationElement::AMBR(ie) => ie.dissect(d),
+            InformationElement::UETimeZone(ie) => ie.dissect(d),
+            InformationElement::ChargingCharacteristics(ie) => ie.dissect(d),
+            InformationElement::Cause(ie) => ie.dissect(d),
+            InformationElement::FQDN(ie) => ie.dissect(d),
+            InformationElement::ChargingID(ie) => ie.dissect(d),
+            InformationElement::FQCSID(ie) => ie.dissect(d),
+            InformationElement::LDN(ie) => ie.dissect(d),
+            InformationElement::ARP(ie) => ie.dissect(d),
+            InformationElement::GUTI(ie) => ie.dissect(d),
+            InformationElement::CompleteRequestMessage(ie) => ie.dissect(d),
+            InformationElement::MMContext(ie) => ie.dissect(d),
+            InformationElement::PDNConnection(ie) => ie.dissect(d),
+            InformationElement::NodeFeatures(ie) => ie.dissect(d),
+            InformationElement::ChangeReportingAction(ie) => ie.dissect(d),
+            InformationElement::CSGInformationReportingAction(ie) => ie.dissect(d),
+            InformationElement::ServingPLMNRateControl(ie) => ie.dissect(d),
+            InformationElement::APNRateControlStatus(ie) => ie.dissect(d),
+            InformationElement::TraceInformation(ie) => ie.dissect(d),
+            InformationElement::FlowQoS(ie) => ie.dissect(d),
+            InformationElement::TAD(ie) => ie.dissect(d),
+            InformationElement::PTI(ie) => ie.dissect(d),
+            InformationElement::DelayValue(ie) => ie.dissect(d),
+            InformationElement::Throttling(ie) => ie.dissect(d),
+            InformationElement::EPCTimer(ie) => ie.dissect(d),
+            InformationElement::IPAddress(ie) => ie.dissect(d),
+            InformationElement::STNSR(ie) => ie.dissect(d),
+            InformationElement::CSGID(ie) => ie.dissect(d),
+            InformationElement::CMI(ie) => ie.dissect(d),
+            InformationElement::UCI(ie) => ie.dissect(d),
+            InformationElement::PortNumber(ie) => ie.dissect(d),
+            InformationElement::RemoteUserID(ie) => ie.dissect(d),
+            InformationElement::RemoteUEIPInformation(ie) => ie.dissect(d),
+            InformationElement::RemoteUEContext(ie) => ie.dissect(d),
+            InformationElement::EPCO(ie) => ie.dissect(d),
+            InformationElement::Indication(ie) => ie.dissect(d),
+            InformationElement::ChangeToReportFlags(ie) => ie.dissect(d),
+            InformationElement::PrivateExtension(ie) => ie.dissect(d),
+            InformationElement::PagingAndServiceInformation(ie) => ie.dissect(d),
+            InformationElement::APNAndRelativeCapacity(ie) => ie.dissect(d),
+            InformationElement::ExtendedTraceInformation(ie) => ie.dissect(d),
+            InformationElement::NodeIdentifier(ie) => ie.dissect(d),
+        }
+    }
+}
+
+pub fn dissect_ie<T: InformationElementTraits, F: FnOnce(&mut Dissector)>(ie: &T, d: &mut Dissector, f: F) {
+    // Writes the IE header followed by the fields of the IE written by f
+    let ie_type = ie.information_element_type();
+
+    d.tree(&format!("{:?} ({}), Length: {}, Instance: {}", ie_type, ie_type as u8, ie.payload_length(), ie.instance()), f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_information_element_type_mei() {
+        // IE type 75 is the MEI. It was previously decoded as a ULI which swallowed the IEs following it.
+        assert_eq!(InformationElementType::try_from(75), Ok(InformationElementType::MEI));
+        assert_eq!(InformationElementType::try_from(86), Ok(InformationElementType::UserLocationInformation));
+    }
+
+    #[test]
+    fn test_information_element_type_bijective() {
+        // Each value decodes to the type that encodes back to it so no two values share a type
+        let mut decoded = 0;
+
+        for value in 0..=u8::MAX {
+            if let Ok(ie_type) = InformationElementType::try_from(value) {
+                assert_eq!(ie_type as u8, value);
+                decoded = decoded + 1;
+            }
+        }
+
+        // 1 to 3, 51 to 62, 71 to 219 less the 6 reserved values, 254 and 255
+        assert_eq!(decoded, 3 + 12 + 149 - 6 + 2);
+
+        assert_eq!(InformationElementType::try_from(0), Err("Unsupported IE type (0)".to_string()));
+        assert!(InformationElementType::try_from(98).is_err());
+        assert!(InformationElementType::try_from(220).is_err());
+
+        assert_eq!(InformationElementType::try_from(77), Ok(InformationElementType::Indication));
+        assert_eq!(InformationElementType::try_from(78), Ok(InformationElementType::PCO));
+        assert_eq!(InformationElementType::try_from(94), Ok(InformationElementType::ChargingID));
+        assert_eq!(InformationElementType::try_from(136), Ok(InformationElementType::FQDN));
+        assert_eq!(InformationElementType::try_from(255), Ok(InformationElementType::PrivateExtension));
+    }
+
+    #[test]
+    fn test_parse_known_unsupported() {
+        // A defined type that isn't decoded yet is skipped rather than misparsed
+        let ie_bytes = [
+            InformationElementType::PCO as u8,
+            0, 2, // Length
+            0, // Spare and Instance
+            0x80, 0x00,
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+        assert_eq!(InformationElement::type_and_instance(&ie_bytes), Some((InformationElementType::PCO, 0)));
+        assert_eq!(InformationElement::skip_parsing(&ie_bytes), 6);
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let ie_bytes = [
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            0b1000_0000 | u8::from(f_teid::InterfaceType::S11MmeGtpC), // Flags and Interface Type
+            0x12, 0x34, 0x56, 0x78, // TEID
+            10, 0, 0, 1, // IPv4 Address
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_some());
+
+        for length in 0..ie_bytes.len() {
+            assert!(InformationElement::parse(&ie_bytes[..length]).is_none());
         }
+
+        // The flags indicate an IPv6 address that isn't in the IE
+        let mut ie_bytes = ie_bytes;
+        ie_bytes[4] = 0b0100_0000 | u8::from(f_teid::InterfaceType::S11MmeGtpC);
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_skip_parsing() {
+        assert_eq!(InformationElement::skip_parsing(&[InformationElementType::Recovery as u8, 0, 1, 0, 5]), 5);
+        assert_eq!(InformationElement::skip_parsing(&[InformationElementType::Recovery as u8, 0xFF, 0xFF, 0]), 0x10003);
+
+        // A truncated IE header skips the rest of the buffer
+        assert_eq!(InformationElement::skip_parsing(&[InformationElementType::Recovery as u8, 0]), 2);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_lengths() {
+        let mut buffer = [0; 64];
+
+        let ie = InformationElement::Cause(
+            cause::InformationElement::new(
+                cause::CauseCode::MandatoryIEMissing,
+                cause::CauseSource::LocalNode,
+                false,
+                false,
+                Some((InformationElementType::IMSI, 0)),
+                0
+            ).unwrap()
+        );
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(ie.payload_length(), 6);
+        assert_eq!(ie.wire_length() as usize, pos);
+        assert_eq!(NetworkEndian::read_u16(&buffer[LENGTH]), ie.payload_length());
+        assert_eq!(ie.length(), ie.wire_length());
+    }
+}
@@ -1,7 +1,15 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector};
+use crate::bitrate::Bitrate;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -22,11 +30,13 @@ pub struct InformationElement {
         11      | APN-AMBR for downlink (Octet 3)                               |
         12      | APN-AMBR for downlink (Octet 4)                               |
                 |---------------------------------------------------------------|
+
+        The APN-AMBR is in kbps so the largest value is 4,294,967,295 kbps
     */
 
     instance: u8,
-    pub uplink: u32,
-    pub downlink: u32,
+    pub uplink: u32, // kbps
+    pub downlink: u32, // kbps
 }
 
 impl InformationElement {
@@ -44,7 +54,45 @@ impl InformationElement {
             )
         }
     }
+
+    pub fn from_bitrates(uplink: Bitrate, downlink: Bitrate, instance: u8) -> Result<Self, String> {
+        if uplink.kbps() > u32::MAX as u64 {
+            Err(format!("APN-AMBR Uplink is > {} kbps {}", u32::MAX, uplink))
+        }
+        else if downlink.kbps() > u32::MAX as u64 {
+            Err(format!("APN-AMBR Downlink is > {} kbps {}", u32::MAX, downlink))
+        }
+        else {
+            Self::new(uplink.kbps() as u32, downlink.kbps() as u32, instance)
+        }
+    }
+
+    pub fn uplink_bitrate(&self) -> Bitrate {
+        Bitrate::from_kbps(self.uplink as u64)
+    }
+
+    pub fn downlink_bitrate(&self) -> Bitrate {
+        Bitrate::from_kbps(self.downlink as u64)
+    }
+
+    pub fn check_limits(&self, uplink: Bitrate, downlink: Bitrate) -> Result<(), String> {
+        // Checks the APN-AMBR against limits set by the operator e.g. the subscribed APN-AMBR
+        if self.uplink_bitrate() > uplink {
+            Err(format!("APN-AMBR Uplink {} exceeds the limit {}", self.uplink_bitrate(), uplink))
+        }
+        else if self.downlink_bitrate() > downlink {
+            Err(format!("APN-AMBR Downlink {} exceeds the limit {}", self.downlink_bitrate(), downlink))
+        }
+        else {
+            Ok(())
+        }
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -59,6 +107,10 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 8 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         let uplink = NetworkEndian::read_u32(&buffer[pos..pos+4]);
         pos = pos + 4;
 
@@ -97,11 +149,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+8
+    fn payload_length(&self) -> u16 {
+        8
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -112,8 +161,7 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
@@ -130,6 +178,15 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("APN-AMBR Uplink", format!("{} kbps", self.uplink));
+            d.field("APN-AMBR Downlink", format!("{} kbps", self.downlink));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,10 +209,47 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_from_bitrates() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::from_bitrates(Bitrate::from_mbps(50), Bitrate::from_bps(100_500), 0).unwrap();
+
+        assert_eq!(ie.uplink, 50_000);
+        // Rounded up to the next kbps
+        assert_eq!(ie.downlink, 101);
+        assert_eq!(ie.uplink_bitrate(), Bitrate::from_mbps(50));
+        assert_eq!(ie.downlink_bitrate(), Bitrate::from_kbps(101));
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::AMBR as u8,
+            0, 8, // Length
+            0, // Spare
+            0x00, 0x00, 0xC3, 0x50, // AMBR for uplink
+            0x00, 0x00, 0x00, 0x65, // AMBR for downlink
+        ]);
+
+        // The largest AMBR is u32::MAX kbps
+        assert!(InformationElement::from_bitrates(Bitrate::from_kbps(u32::MAX as u64), Bitrate::from_kbps(0), 0).is_ok());
+        assert!(InformationElement::from_bitrates(Bitrate::from_kbps(u32::MAX as u64 + 1), Bitrate::from_kbps(0), 0).is_err());
+        assert!(InformationElement::from_bitrates(Bitrate::from_kbps(0), Bitrate::from_gbps(5_000), 0).is_err());
+        assert!(InformationElement::from_bitrates(Bitrate::from_kbps(0), Bitrate::from_kbps(0), 0x10).is_err());
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let ie = InformationElement::from_bitrates(Bitrate::from_mbps(50), Bitrate::from_mbps(100), 0).unwrap();
+
+        assert!(ie.check_limits(Bitrate::from_mbps(50), Bitrate::from_mbps(100)).is_ok());
+        assert!(ie.check_limits(Bitrate::from_mbps(49), Bitrate::from_mbps(100)).is_err());
+        assert!(ie.check_limits(Bitrate::from_mbps(50), Bitrate::from_mbps(99)).is_err());
+    }
+
     #[test]
     fn test_length() {
         let ie = InformationElement::new(0x12345678, 0x87654321, 0).unwrap();
-        assert_eq!(ie.length(), 4+8);
+        assert_eq!(ie.wire_length(), 4+8);
     }
 
     #[test]
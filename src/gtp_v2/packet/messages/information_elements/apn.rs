@@ -2,11 +2,116 @@ extern crate ascii;
 
 use byteorder::{ByteOrder, NetworkEndian};
 
-use std::convert::TryInto;
-use ascii::{AsciiString, AsciiChar, ToAsciiChar};
+use core::convert::TryInto;
+use ascii::{AsciiStr, AsciiString, AsciiChar, ToAsciiChar};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+use super::user_location_information::PLMN;
 
+use crate::gtp_v2::node_selection::{mcc_label, mnc_label};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+pub const MAX_LABEL_LENGTH: usize = 63;
+pub const MAX_LENGTH: usize = 100;
+
+pub fn validate(apn: &AsciiStr) -> Result<(), String> {
+    /* Checks that apn can be encoded as per TS 23.003. Each label must be 1 to 63 octets long and the
+    encoded APN (each label preceded by its length) must be at most 100 octets long */
+
+    if apn.is_empty() {
+        return Ok(())
+    }
+
+    if apn.len() + 1 > MAX_LENGTH {
+        return Err(format!("APN is too long ({} octets)", apn.len() + 1))
+    }
+
+    for l in apn.split(AsciiChar::Dot) {
+        if l.is_empty() {
+            return Err(format!("APN contains an empty label {}", apn))
+        }
+        else if l.len() > MAX_LABEL_LENGTH {
+            return Err(format!("APN label is too long ({} octets) {}", l.len(), l))
+        }
+    }
+
+    Ok(())
+}
+
+pub fn encoded_length(apn: &AsciiStr) -> usize {
+    // Each label is preceded by its length so the dots are replaced and one length octet is added
+    apn.len() + 1
+}
+
+pub fn encode(apn: &AsciiStr, buffer: &mut[u8]) -> usize {
+    let mut pos = 0;
+
+    // Split the apn into labels
+    for l in apn.split(AsciiChar::Dot) {
+        let length = l.len();
+
+        buffer[pos] = length as u8;
+        pos = pos + 1;
+
+        for i in 0..length {
+            buffer[pos] = l[i] as u8;
+            pos = pos + 1;
+        }
+    }
+
+    pos
+}
+
+pub fn decode(buffer: &[u8]) -> Option<AsciiString> {
+    // buffer is the whole encoded APN
+    let mut pos = 0;
+
+    let mut apn = AsciiString::new();
+
+    while pos < buffer.len() {
+        // Each label is encoded with the lenght first then label
+
+        if pos > 0 {
+            // Labels are separated by a dot
+            apn.push(AsciiChar::Dot);
+        }
+
+        let label_length = buffer[pos];
+        pos = pos + 1;
+
+        if pos + label_length as usize > buffer.len() {
+            // The label runs past the end of the APN
+            return None
+        }
+
+        for _ in 0..label_length {
+
+            if let Ok(ch) = buffer[pos].to_ascii_char() {
+                apn.push(ch)
+            }
+            pos = pos + 1;
+        }
+    }
+
+    Some(apn)
+}
+
+fn is_operator_identifier_label(label: &AsciiStr, prefix: &str) -> bool {
+    // Matches mncXXX / mccYYY labels
+    let label = label.as_str();
+
+    label.len() == prefix.len() + 3 &&
+        label[..prefix.len()].eq_ignore_ascii_case(prefix) &&
+        label[prefix.len()..].chars().all(|c| c.is_ascii_digit())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -23,6 +128,7 @@ pub struct InformationElement {
     */
 
     instance: u8,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
     pub apn: AsciiString
 }
 
@@ -33,6 +139,9 @@ impl InformationElement {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if let Err(e) = validate(&apn) {
+            Err(e)
+        }
         else {
             Ok(InformationElement {
                 apn: apn,
@@ -40,7 +149,67 @@ impl InformationElement {
             })
         }
     }
+
+    pub fn from_network_identifier(ni: &str, plmn: &PLMN, instance: u8) -> Result<Self, String> {
+        /* Builds an APN from the APN Network Identifier and the APN Operator Identifier of the PLMN:
+        <APN-NI>.mnc<MNC>.mcc<MCC>.gprs */
+
+        let ni = match AsciiString::from_ascii(ni) {
+            Ok(ni) => ni,
+            Err(_) => return Err(format!("APN Network Identifier is not ASCII {}", ni)),
+        };
+
+        if ni.is_empty() {
+            return Err("APN Network Identifier is empty".to_string())
+        }
+
+        if ni.as_str().to_ascii_lowercase().ends_with(".gprs") {
+            return Err(format!("APN Network Identifier must not end in .gprs {}", ni))
+        }
+
+        let mut apn = ni;
+        apn.push_str(AsciiStr::from_ascii(&format!(".{}.{}.gprs", mnc_label(plmn), mcc_label(plmn))).unwrap());
+
+        Self::new(apn, instance)
+    }
+
+    fn operator_identifier_position(&self) -> Option<usize> {
+        // Returns the index of the first octet of the APN Operator Identifier (if present)
+        let labels: Vec<&AsciiStr> = self.apn.split(AsciiChar::Dot).collect();
+        let n = labels.len();
+
+        if n >= 3 &&
+            labels[n-1].as_str().eq_ignore_ascii_case("gprs") &&
+            is_operator_identifier_label(labels[n-2], "mcc") &&
+            is_operator_identifier_label(labels[n-3], "mnc") {
+            // mncXXX.mccYYY.gprs is 18 octets long
+            Some(self.apn.len() - 18)
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn network_identifier(&self) -> &AsciiStr {
+        match self.operator_identifier_position() {
+            Some(0) => &self.apn[..0],
+            // Don't include the dot before the Operator Identifier
+            Some(pos) => &self.apn[..pos-1],
+            None => &self.apn,
+        }
+    }
+
+    pub fn operator_identifier(&self) -> Option<&AsciiStr> {
+        match self.operator_identifier_position() {
+            Some(pos) => Some(&self.apn[pos..]),
+            None => None,
+        }
+    }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -55,25 +224,11 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        let mut apn = AsciiString::new();
-
-        while pos < (length+4) as usize {
-            // Each label is encoded with the lenght first then label
-
-            let label_length = buffer[pos];
-            pos = pos + 1;
-
-            for _ in 0..label_length {
-
-                if let Ok(ch) = buffer[pos].to_ascii_char() {
-                    apn.push(ch)
-                }
-                pos = pos + 1;
-            }
-            apn.push(AsciiChar::Dot);
+        if buffer.len() < length as usize + 4 {
+            return None
         }
 
-        apn.pop(); // Pop off the last dot
+        let apn = decode(&buffer[pos..(length+4) as usize])?;
 
         Some(
             (
@@ -81,7 +236,7 @@ impl InformationElement {
                     apn,
                     instance,
                 },
-                pos
+                length as usize + 4
             )
         )
     }
@@ -106,11 +261,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        ((4 + self.apn.len()+1) as usize).try_into().unwrap()
+    fn payload_length(&self) -> u16 {
+        encoded_length(&self.apn).try_into().unwrap()
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -121,32 +273,27 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        // Split the apn into labels
-
-        for l in self.apn.split(AsciiChar::Dot) {
-            let length = l.len();
-
-            buffer[pos] = (length as u8).try_into().unwrap();
-            pos = pos + 1;
-
-            for i in 0..length {
-                buffer[pos] = l[i] as u8;
-                pos = pos + 1;
-            }
-        }
+        pos = pos + encode(&self.apn, &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("APN", &self.apn);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,7 +329,7 @@ mod tests {
     #[test]
     fn test_length() {
         let ie = InformationElement::new(AsciiString::from_ascii("awesome.apn.mnc099.mcc505.gprs").unwrap(), 0).unwrap();
-        assert_eq!(ie.length(), 31+4);
+        assert_eq!(ie.wire_length(), 31+4);
     }
 
     #[test]
@@ -212,4 +359,52 @@ mod tests {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_new_invalid() {
+        let label = "a".repeat(MAX_LABEL_LENGTH + 1);
+        assert!(InformationElement::new(AsciiString::from_ascii(label).unwrap(), 0).is_err());
+
+        let apn = ["a".repeat(MAX_LABEL_LENGTH), "b".repeat(MAX_LABEL_LENGTH)].join(".");
+        assert!(InformationElement::new(AsciiString::from_ascii(apn).unwrap(), 0).is_err());
+
+        assert!(InformationElement::new(AsciiString::from_ascii("awesome..apn").unwrap(), 0).is_err());
+
+        assert!(InformationElement::new(AsciiString::from_ascii("awesome.apn").unwrap(), 0x10).is_err());
+    }
+
+    #[test]
+    fn test_from_network_identifier() {
+        let plmn = PLMN::new(505, 99, true).unwrap();
+
+        let ie = InformationElement::from_network_identifier("awesome.apn", &plmn, 0).unwrap();
+        assert_eq!(ie.apn, AsciiString::from_ascii("awesome.apn.mnc099.mcc505.gprs").unwrap());
+
+        // 2 digit MNC
+        let ie = InformationElement::from_network_identifier("internet", &PLMN::new(505, 1, false).unwrap(), 0).unwrap();
+        assert_eq!(ie.apn, AsciiString::from_ascii("internet.mnc001.mcc505.gprs").unwrap());
+
+        assert!(InformationElement::from_network_identifier("", &plmn, 0).is_err());
+        assert!(InformationElement::from_network_identifier("internet.gprs", &plmn, 0).is_err());
+        assert!(InformationElement::from_network_identifier(&"a".repeat(90), &plmn, 0).is_err());
+    }
+
+    #[test]
+    fn test_network_identifier() {
+        let ie = InformationElement::new(AsciiString::from_ascii("awesome.apn.mnc099.mcc505.gprs").unwrap(), 0).unwrap();
+        assert_eq!(ie.network_identifier(), "awesome.apn");
+        assert_eq!(ie.operator_identifier().unwrap(), "mnc099.mcc505.gprs");
+
+        let ie = InformationElement::new(AsciiString::from_ascii("internet").unwrap(), 0).unwrap();
+        assert_eq!(ie.network_identifier(), "internet");
+        assert!(ie.operator_identifier().is_none());
+
+        let ie = InformationElement::new(AsciiString::from_ascii("MNC099.MCC505.GPRS").unwrap(), 0).unwrap();
+        assert_eq!(ie.network_identifier(), "");
+        assert_eq!(ie.operator_identifier().unwrap(), "MNC099.MCC505.GPRS");
+
+        let ie = InformationElement::new(AsciiString::from_ascii("internet.mnc99.mcc505.gprs").unwrap(), 0).unwrap();
+        assert_eq!(ie.network_identifier(), "internet.mnc99.mcc505.gprs");
+        assert!(ie.operator_identifier().is_none());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,250 @@
+extern crate ascii;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use ascii::AsciiString;
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+use super::apn;
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+pub const MIN_RELATIVE_CAPACITY: u8 = 1;
+pub const MAX_RELATIVE_CAPACITY: u8 = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (184)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Relative Capacity                                             |
+        6       | APN Length                                                    |
+        7 -> m  | Access Point Name                                             |
+                |---------------------------------------------------------------|
+
+        Relative Capacity is the percentage (1 to 100) of the node's capacity that is available to the APN. It is
+        carried in the APN level Load Control Information so an APN can be congested before the node is.
+    */
+
+    instance: u8,
+    pub relative_capacity: u8,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
+    pub apn: AsciiString,
+}
+
+impl InformationElement {
+    pub fn new(relative_capacity: u8, apn: AsciiString, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if !(MIN_RELATIVE_CAPACITY..=MAX_RELATIVE_CAPACITY).contains(&relative_capacity) {
+            Err(format!("Relative Capacity is not between 1 and 100 {}", relative_capacity))
+        }
+        else if let Err(e) = apn::validate(&apn) {
+            Err(e)
+        }
+        else {
+            Ok(
+                InformationElement {
+                    relative_capacity,
+                    apn,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let relative_capacity = buffer[pos];
+        pos = pos + 1;
+
+        let apn_length = buffer[pos] as usize;
+        pos = pos + 1;
+
+        if apn_length + 2 > length as usize {
+            // The APN runs past the end of the IE
+            return None
+        }
+
+        let apn = apn::decode(&buffer[pos..pos + apn_length])?;
+
+        Some(
+            (
+                InformationElement {
+                    relative_capacity,
+                    apn,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::APNAndRelativeCapacity
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        2 + apn::encoded_length(&self.apn) as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.relative_capacity;
+        pos = pos + 1;
+
+        buffer[pos] = apn::encoded_length(&self.apn) as u8;
+        pos = pos + 1;
+
+        pos = pos + apn::encode(&self.apn, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Relative Capacity", self.relative_capacity);
+            d.field("APN", &self.apn);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_ie() -> InformationElement {
+        InformationElement::new(40, AsciiString::from_ascii("internet").unwrap(), 0).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let apn = AsciiString::from_ascii("internet").unwrap();
+
+        assert!(InformationElement::new(100, apn.clone(), 0).is_ok());
+        assert!(InformationElement::new(0, apn.clone(), 0).is_err());
+        assert!(InformationElement::new(101, apn.clone(), 0).is_err());
+        assert!(InformationElement::new(50, apn, 0x10).is_err());
+        assert!(InformationElement::new(50, AsciiString::from_ascii("internet..apn").unwrap(), 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_ie().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::APNAndRelativeCapacity as u8,
+            0, 11, // Length
+            0, // Spare and Instance
+            40, // Relative Capacity
+            9, // APN Length
+            8, b'i', b'n', b't', b'e', b'r', b'n', b'e', b't',
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_ie().wire_length(), 15);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_ie().information_element_type() as u8, InformationElementType::APNAndRelativeCapacity as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::APNAndRelativeCapacity as u8,
+            0, 11, // Length
+            1, // Spare and Instance
+            40, // Relative Capacity
+            9, // APN Length
+            8, b'i', b'n', b't', b'e', b'r', b'n', b'e', b't',
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 15);
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(ie.relative_capacity, 40);
+            assert_eq!(ie.apn, "internet");
+        }
+        else {
+            assert!(false);
+        }
+
+        // APN Length is longer than the IE
+        let mut bad = ie_bytes;
+        bad[5] = 10;
+        assert!(InformationElement::parse(&bad).is_none());
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..14]).is_none());
+    }
+}
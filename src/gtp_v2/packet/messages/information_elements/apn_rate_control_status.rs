@@ -0,0 +1,243 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------| 
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (204)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5-8     | Number of Uplink packets allowed                              |
+        9-12    | Number of additional exception reports                        |
+        13-16   | Number of Downlink packets allowed                            |
+        17-24   | APN Rate Control Status validity Time                         |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub uplink_packets_allowed: u32,
+    pub additional_exception_reports: u32,
+    pub downlink_packets_allowed: u32,
+    // Seconds since 00:00:00 1 January 1900. The integer part is in the 32 most significant bits and the fraction in the 32 least significant bits
+    pub validity_time: u64,
+}
+
+impl InformationElement {
+    pub fn new(uplink_packets_allowed: u32, additional_exception_reports: u32, downlink_packets_allowed: u32, validity_time: u64, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    uplink_packets_allowed,
+                    additional_exception_reports,
+                    downlink_packets_allowed,
+                    validity_time,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+        
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 20 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let uplink_packets_allowed = NetworkEndian::read_u32(&buffer[pos..pos+4]);
+        pos = pos + 4;
+
+        let additional_exception_reports = NetworkEndian::read_u32(&buffer[pos..pos+4]);
+        pos = pos + 4;
+
+        let downlink_packets_allowed = NetworkEndian::read_u32(&buffer[pos..pos+4]);
+        pos = pos + 4;
+
+        let validity_time = NetworkEndian::read_u64(&buffer[pos..pos+8]);
+        // pos = pos + 8;
+
+        // Any further octets are only present if explicitly specified and are ignored
+
+        Some(
+            (
+                InformationElement {
+                    uplink_packets_allowed,
+                    additional_exception_reports,
+                    downlink_packets_allowed,
+                    validity_time,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn validity_time_seconds(&self) -> u32 {
+        (self.validity_time >> 32) as u32
+    }
+
+    pub fn validity_time_fraction(&self) -> u32 {
+        self.validity_time as u32
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::APNRateControlStatus
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        20
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+        
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.uplink_packets_allowed);
+        pos = pos + 4;
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.additional_exception_reports);
+        pos = pos + 4;
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.downlink_packets_allowed);
+        pos = pos + 4;
+
+        NetworkEndian::write_u64(&mut buffer[pos..pos+8], self.validity_time);
+        pos = pos + 8;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Number of Uplink packets allowed", self.uplink_packets_allowed);
+            d.field("Number of additional exception reports", self.additional_exception_reports);
+            d.field("Number of Downlink packets allowed", self.downlink_packets_allowed);
+            d.field("Validity Time", format!("{}.{:09} s since 1900", self.validity_time_seconds(), (self.validity_time_fraction() as u64 * 1_000_000_000) >> 32));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(100, 2, 0x12345678, 0xE3E4_3A00_8000_0000, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::APNRateControlStatus as u8,
+            0, 20, // Length
+            0, // Spare
+            0, 0, 0, 100, // Number of Uplink packets allowed
+            0, 0, 0, 2, // Number of additional exception reports
+            0x12, 0x34, 0x56, 0x78, // Number of Downlink packets allowed
+            0xE3, 0xE4, 0x3A, 0x00, 0x80, 0x00, 0x00, 0x00, // Validity Time
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(100, 2, 0x12345678, 0, 0).unwrap();
+        assert_eq!(ie.wire_length(), 4+20);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(100, 2, 0x12345678, 0, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::APNRateControlStatus as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::APNRateControlStatus as u8,
+            0, 20, // Length
+            0, // Spare
+            0, 0, 0, 100, // Number of Uplink packets allowed
+            0, 0, 0, 2, // Number of additional exception reports
+            0x12, 0x34, 0x56, 0x78, // Number of Downlink packets allowed
+            0xE3, 0xE4, 0x3A, 0x00, 0x80, 0x00, 0x00, 0x00, // Validity Time
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.uplink_packets_allowed, 100);
+            assert_eq!(ie.additional_exception_reports, 2);
+            assert_eq!(ie.downlink_packets_allowed, 0x12345678);
+            assert_eq!(ie.validity_time_seconds(), 0xE3E4_3A00);
+            assert_eq!(ie.validity_time_fraction(), 0x8000_0000);
+            assert_eq!(pos, 24);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..23]).is_none());
+    }
+}
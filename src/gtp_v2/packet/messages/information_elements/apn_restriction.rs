@@ -1,34 +1,54 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use alloc::format;
+use alloc::string::String;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum MaximumAPNRestrictionValue {
-    Unrestricted = 0,
-    Public1 = 1,
-    Public2 = 2,
-    Private1 = 3,
-    Private2 = 4,
+    Unrestricted,
+    Public1,
+    Public2,
+    Private1,
+    Private2,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for MaximumAPNRestrictionValue
+impl From<u8> for MaximumAPNRestrictionValue
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            0 => MaximumAPNRestrictionValue::Unrestricted,
+            1 => MaximumAPNRestrictionValue::Public1,
+            2 => MaximumAPNRestrictionValue::Public2,
+            3 => MaximumAPNRestrictionValue::Private1,
+            4 => MaximumAPNRestrictionValue::Private2,
+            _ => MaximumAPNRestrictionValue::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<MaximumAPNRestrictionValue> for u8
+{
+    fn from(value: MaximumAPNRestrictionValue) -> Self {
         match value {
-            0 => Ok(MaximumAPNRestrictionValue::Unrestricted),
-            1 => Ok(MaximumAPNRestrictionValue::Public1),
-            2 => Ok(MaximumAPNRestrictionValue::Public2),
-            3 => Ok(MaximumAPNRestrictionValue::Private1),
-            4 => Ok(MaximumAPNRestrictionValue::Private2),
-            _ => Err(format!("Unsupported Maximum APN Restriction Value type ({})", value))
+            MaximumAPNRestrictionValue::Unrestricted => 0,
+            MaximumAPNRestrictionValue::Public1 => 1,
+            MaximumAPNRestrictionValue::Public2 => 2,
+            MaximumAPNRestrictionValue::Private1 => 3,
+            MaximumAPNRestrictionValue::Private2 => 4,
+            MaximumAPNRestrictionValue::Other(value) => value,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -63,6 +83,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -77,22 +101,23 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(maximum_apn_restriction) = MaximumAPNRestrictionValue::try_from(buffer[pos]){
-            // pos = pos + 1;
-
-            Some(
-                (
-                    InformationElement {
-                        maximum_apn_restriction,
-                        instance,
-                    },
-                    (length + 4) as usize
-                )
-            )
-        }
-        else {
-            None
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
         }
+
+        let maximum_apn_restriction = MaximumAPNRestrictionValue::from(buffer[pos]);
+
+        // pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    maximum_apn_restriction,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
         
     }
 }
@@ -116,11 +141,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+1
+    fn payload_length(&self) -> u16 {
+        1
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -131,21 +153,28 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.maximum_apn_restriction as u8;
+        buffer[pos] = u8::from(self.maximum_apn_restriction);
         pos = pos + 1;
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Maximum APN Restriction", format!("{:?} ({})", self.maximum_apn_restriction, u8::from(self.maximum_apn_restriction)));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,7 +199,7 @@ mod tests {
     #[test]
     fn test_length() {
         let ie = InformationElement::new(MaximumAPNRestrictionValue::Private2, 0).unwrap();
-        assert_eq!(ie.length(), 5);
+        assert_eq!(ie.wire_length(), 5);
     }
 
     #[test]
@@ -196,4 +225,25 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::APNRestriction as u8,
+            0, 1, // Length
+            0, // Spare
+            9 // Restriction Type Value
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.maximum_apn_restriction, MaximumAPNRestrictionValue::Other(9));
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], ie_bytes);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
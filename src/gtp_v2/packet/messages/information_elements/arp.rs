@@ -0,0 +1,224 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (155)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare | PCI   | PL                            | Spare | PVI   |
+                |---------------------------------------------------------------|
+
+        PCI and PVI are encoded as in the Bearer QoS IE i.e. a 0 bit means the capability / vulnerability is enabled
+    */
+
+    instance: u8,
+    pub pci: bool,
+    pub pl: u8,
+    pub pvi: bool,
+}
+
+impl InformationElement {
+    pub fn new(pci: bool, pl: u8, pvi: bool, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if pl > 0xF {
+            Err(format!("PL is > 0xF {}", pl))
+        }
+        else {
+            Ok(InformationElement {
+                pci,
+                pl,
+                pvi,
+                instance,
+            })
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        let flags = buffer[pos];
+        // pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    pci: (flags >> 6) & 0b1 == 0,
+                    pl: (flags >> 2) & 0xF,
+                    pvi: flags & 0b1 == 0,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::ARP
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = (if self.pci {0} else {1} << 6) |
+            ((self.pl & 0xF) << 2) |
+            (if self.pvi {0} else {1});
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.flag("Pre-emption Capability (PCI)", self.pci);
+            d.field("Priority Level (PL)", self.pl);
+            d.flag("Pre-emption Vulnerability (PVI)", self.pvi);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        if let Ok(ie) = InformationElement::new(false, 0xA, true, 0) {
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], [InformationElementType::ARP as u8,
+                0, 1, // Length
+                0, // Spare
+                0b0110_1000 // PCI, PL, PVI
+            ]);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Ok(_) = InformationElement::new(false, 0x10, true, 0) {
+            // This should fail PL must be less than 0xF
+            assert!(false);
+        }
+        else {
+            assert!(true);
+        }
+    }
+
+    #[test]
+    fn test_length() {
+        if let Ok(ie) = InformationElement::new(true, 1, true, 0) {
+            assert_eq!(ie.wire_length(), 5);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_type() {
+        if let Ok(ie) = InformationElement::new(true, 1, true, 0) {
+            assert_eq!(ie.information_element_type() as u8, InformationElementType::ARP as u8);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::ARP as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0010_0101 // PCI, PL, PVI
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.pci, true);
+            assert_eq!(ie.pl, 9);
+            assert_eq!(ie.pvi, false);
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated IE
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
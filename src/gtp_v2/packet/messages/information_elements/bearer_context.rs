@@ -1,11 +1,19 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use std::convert::TryFrom;
+use core::convert::TryFrom;
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use core::net::{Ipv4Addr, Ipv6Addr};
 
-use super::{InformationElementTraits, InformationElementType, InformationElement as IEEnum, LENGTH, bearer_qos, f_teid, ebi};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie, bearer_qos, cause, f_teid, ebi, charging_id};
+use super::grouped_ie::GroupedIe;
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -24,6 +32,7 @@ pub struct InformationElement {
         Information Element         |   Presence requirement    | Instance      |  Reference 
         ----------------------------|---------------------------|---------------|------------------------
         EPS Bearer ID               |   Mandatory               | 0             | 8.8
+        Cause                       |   Conditional             | 0             | 8.4
         TFT                         |   Optional                | 0             |  
         S1-U eNodeB F-TEID          |   Conditional             | 0             | 8.22
         S4-U SGSN F-TEID            |   Conditional             | 1             | 8.22
@@ -32,12 +41,18 @@ pub struct InformationElement {
         S12 RNC F-TEID              |   Conditional             | 4             | 8.22
         S2b-U ePDG F-TEID           |   Conditional             | 5             | 8.22
         S2a-U TWAN F-TEID           |   Conditional             | 6             | 8.22
-        Bearer Level QoS            |   Mandatory               | 0             | 8.15
+        Bearer Level QoS            |   Conditional             | 0             | 8.15
         S11-U MME F-TEID            |   Conditional             | 7             | 8.22
+        Charging ID                 |   Conditional             | 0             | 8.29
     */
 
+    /* The Bearer Level QoS is mandatory in the bearer contexts to be created but not in e.g. the bearer contexts to be
+    modified of a Modify Bearer Request, so only the EPS Bearer ID is required when parsing. The Cause is only sent in
+    the bearer contexts of a response */
+
     instance: u8,
     pub eps_bearer_id: ebi::InformationElement,
+    pub cause: Option<cause::InformationElement>,
     pub s1_u_enodeb_f_teid: Option<f_teid::InformationElement>,
     pub s4_u_sgsn_f_teid: Option<f_teid::InformationElement>,
     pub s5_s8_u_sgw_f_teid: Option<f_teid::InformationElement>,
@@ -46,11 +61,12 @@ pub struct InformationElement {
     pub s2b_u_epdg_f_teid: Option<f_teid::InformationElement>,
     pub s2a_u_twan_f_teid: Option<f_teid::InformationElement>,
     pub s11_u_mme_f_teid: Option<f_teid::InformationElement>,
-    pub bearer_level_qos: bearer_qos::InformationElement
+    pub bearer_level_qos: Option<bearer_qos::InformationElement>,
+    pub charging_id: Option<charging_id::InformationElement>,
 }
 
-#[derive(Copy, Clone, Debug)]
-enum FTeidInstance {
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FTeidInstance {
     S1UENodeB = 0,
     S4USgsn = 1,
     S5S8USgw = 2,
@@ -80,12 +96,49 @@ impl TryFrom<u8> for FTeidInstance
     }
 }
 
+impl FTeidInstance {
+    // In the order they are sent in
+    pub const ALL: [FTeidInstance; 8] = [
+        FTeidInstance::S1UENodeB,
+        FTeidInstance::S4USgsn,
+        FTeidInstance::S5S8USgw,
+        FTeidInstance::S5S8UPgw,
+        FTeidInstance::S12Rnc,
+        FTeidInstance::S2bUEPdg,
+        FTeidInstance::S2aUTwan,
+        FTeidInstance::S11UMme,
+    ];
+
+    pub fn interface_type(&self) -> f_teid::InterfaceType {
+        // The Interface Type of the F-TEID sent with this instance
+        match self {
+            FTeidInstance::S1UENodeB => f_teid::InterfaceType::S1UENodeBGtpU,
+            FTeidInstance::S4USgsn => f_teid::InterfaceType::S4SgsnGtpU,
+            FTeidInstance::S5S8USgw => f_teid::InterfaceType::S5S8SgwGtpU,
+            FTeidInstance::S5S8UPgw => f_teid::InterfaceType::S5S8PgwGtpU,
+            FTeidInstance::S12Rnc => f_teid::InterfaceType::S12RncGtpU,
+            FTeidInstance::S2bUEPdg => f_teid::InterfaceType::S2bUEPdgGtpU,
+            FTeidInstance::S2aUTwan => f_teid::InterfaceType::S2aTwanGtpU,
+            FTeidInstance::S11UMme => f_teid::InterfaceType::S11MmeGtpU,
+        }
+    }
+}
+
 impl InformationElement {
     pub fn new(
         eps_bearer_id: ebi::InformationElement,
         bearer_level_qos: bearer_qos::InformationElement,
         instance: u8,
     ) -> Result<Self,String> {
+        let mut ie = InformationElement::without_qos(eps_bearer_id, instance)?;
+
+        ie.bearer_level_qos = Some(bearer_level_qos);
+
+        Ok(ie)
+    }
+
+    pub fn without_qos(eps_bearer_id: ebi::InformationElement, instance: u8) -> Result<Self,String> {
+        // e.g. a bearer context to be modified that only carries the new F-TEIDs
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
@@ -93,7 +146,8 @@ impl InformationElement {
             Ok(
                 InformationElement {
                     eps_bearer_id,
-                    bearer_level_qos,
+                    cause: None,
+                    bearer_level_qos: None,
                     instance,
                     s1_u_enodeb_f_teid: None,
                     s4_u_sgsn_f_teid: None,
@@ -103,89 +157,36 @@ impl InformationElement {
                     s2b_u_epdg_f_teid: None,
                     s2a_u_twan_f_teid: None,
                     s11_u_mme_f_teid: None,
+                    charging_id: None,
                 }
             )
         }
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
-        // Keep trying to parse IEs until the end of the buffer
-        let mut eps_bearer_id: Option<ebi::InformationElement> = None;
-
-        let mut bearer_level_qos: Option<bearer_qos::InformationElement> = None;
+        let (mut group, pos) = GroupedIe::parse(buffer)?;
 
-        let mut s1_u_enodeb_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s4_u_sgsn_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s5_s8_u_sgw_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s5_s8_u_pgw_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s12_rnc_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s2b_u_epdg_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s2a_u_twan_f_teid: Option<f_teid::InformationElement> = None;
-        let mut s11_u_mme_f_teid: Option<f_teid::InformationElement> = None;
+        let eps_bearer_id: Option<ebi::InformationElement> = group.ies.take(0);
 
-        let mut pos = 0;
-
-        // Read the type
-        let _ie_type = buffer[pos];
-        pos = pos + 1;
-
-        // Read the length
-        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
-        pos = pos + 2;
-
-        //Spare and instance
-        let instance = buffer[pos] & 0xF;
-        pos = pos + 1;
-
-        while pos < buffer.len()
-        {
-            if let Some((ie, ie_pos)) = IEEnum::parse(&buffer[pos..]){
-                match ie {
-                    IEEnum::EBI(ie) => eps_bearer_id = Some(ie),
-                    IEEnum::BearerQoS(ie) => bearer_level_qos = Some(ie),
-                    IEEnum::FTEID(ie) => {
-                        if let Ok(instance) = FTeidInstance::try_from(ie.instance()) {
-                            match instance
-                            {
-                                FTeidInstance::S1UENodeB => s1_u_enodeb_f_teid = Some(ie),
-                                FTeidInstance::S4USgsn => s4_u_sgsn_f_teid = Some(ie),
-                                FTeidInstance::S5S8USgw => s5_s8_u_sgw_f_teid = Some(ie),
-                                FTeidInstance::S5S8UPgw => s5_s8_u_pgw_f_teid = Some(ie),
-                                FTeidInstance::S12Rnc => s12_rnc_f_teid = Some(ie),
-                                FTeidInstance::S2bUEPdg => s2b_u_epdg_f_teid = Some(ie),
-                                FTeidInstance::S2aUTwan => s2a_u_twan_f_teid = Some(ie),
-                                FTeidInstance::S11UMme => s11_u_mme_f_teid = Some(ie),
-                            }
-                        }
-                        else { /* Not an instance of F-TEID that we expect. Just ignore it */ }
-                    },
-                    _ =>  { /* Its an IE that we didn't expect. Just ignore it */ }
-                }
-                pos = pos + ie_pos;
-            }
-            else {
-                // IE parsing failed
-                pos = pos + IEEnum::skip_parsing(&buffer[pos..]);
-            }
-        }
-
-        if eps_bearer_id.is_some() && bearer_level_qos.is_some() {
+        if let Some(eps_bearer_id) = eps_bearer_id {
             Some(
                 (
                     InformationElement {
-                        eps_bearer_id: eps_bearer_id.unwrap(),
-                        bearer_level_qos: bearer_level_qos.unwrap(),
-                        instance,
-                        s1_u_enodeb_f_teid: s1_u_enodeb_f_teid,
-                        s4_u_sgsn_f_teid: s4_u_sgsn_f_teid,
-                        s5_s8_u_sgw_f_teid: s5_s8_u_sgw_f_teid,
-                        s5_s8_u_pgw_f_teid: s5_s8_u_pgw_f_teid,
-                        s12_rnc_f_teid: s12_rnc_f_teid,
-                        s2b_u_epdg_f_teid: s2b_u_epdg_f_teid,
-                        s2a_u_twan_f_teid: s2a_u_twan_f_teid,
-                        s11_u_mme_f_teid: s11_u_mme_f_teid,
+                        eps_bearer_id,
+                        cause: group.ies.take(0),
+                        bearer_level_qos: group.ies.take(0),
+                        instance: group.instance,
+                        s1_u_enodeb_f_teid: group.ies.take(FTeidInstance::S1UENodeB as u8),
+                        s4_u_sgsn_f_teid: group.ies.take(FTeidInstance::S4USgsn as u8),
+                        s5_s8_u_sgw_f_teid: group.ies.take(FTeidInstance::S5S8USgw as u8),
+                        s5_s8_u_pgw_f_teid: group.ies.take(FTeidInstance::S5S8UPgw as u8),
+                        s12_rnc_f_teid: group.ies.take(FTeidInstance::S12Rnc as u8),
+                        s2b_u_epdg_f_teid: group.ies.take(FTeidInstance::S2bUEPdg as u8),
+                        s2a_u_twan_f_teid: group.ies.take(FTeidInstance::S2aUTwan as u8),
+                        s11_u_mme_f_teid: group.ies.take(FTeidInstance::S11UMme as u8),
+                        charging_id: group.ies.take(0),
                     },
-                    (length + 4) as usize
+                    pos
                 )
             )
         }
@@ -194,51 +195,73 @@ impl InformationElement {
         }
     }
 
-    pub fn set_s1_u_enodeb_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s1_u_enodeb_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S1UENodeBGtpU,
+    pub fn f_teid(&self, instance: FTeidInstance) -> Option<&f_teid::InformationElement> {
+        match instance {
+            FTeidInstance::S1UENodeB => self.s1_u_enodeb_f_teid.as_ref(),
+            FTeidInstance::S4USgsn => self.s4_u_sgsn_f_teid.as_ref(),
+            FTeidInstance::S5S8USgw => self.s5_s8_u_sgw_f_teid.as_ref(),
+            FTeidInstance::S5S8UPgw => self.s5_s8_u_pgw_f_teid.as_ref(),
+            FTeidInstance::S12Rnc => self.s12_rnc_f_teid.as_ref(),
+            FTeidInstance::S2bUEPdg => self.s2b_u_epdg_f_teid.as_ref(),
+            FTeidInstance::S2aUTwan => self.s2a_u_twan_f_teid.as_ref(),
+            FTeidInstance::S11UMme => self.s11_u_mme_f_teid.as_ref(),
+        }
+    }
+
+    fn f_teid_mut(&mut self, instance: FTeidInstance) -> &mut Option<f_teid::InformationElement> {
+        match instance {
+            FTeidInstance::S1UENodeB => &mut self.s1_u_enodeb_f_teid,
+            FTeidInstance::S4USgsn => &mut self.s4_u_sgsn_f_teid,
+            FTeidInstance::S5S8USgw => &mut self.s5_s8_u_sgw_f_teid,
+            FTeidInstance::S5S8UPgw => &mut self.s5_s8_u_pgw_f_teid,
+            FTeidInstance::S12Rnc => &mut self.s12_rnc_f_teid,
+            FTeidInstance::S2bUEPdg => &mut self.s2b_u_epdg_f_teid,
+            FTeidInstance::S2aUTwan => &mut self.s2a_u_twan_f_teid,
+            FTeidInstance::S11UMme => &mut self.s11_u_mme_f_teid,
+        }
+    }
+
+    pub fn f_teids(&self) -> impl Iterator<Item = (FTeidInstance, &f_teid::InformationElement)> {
+        // Every F-TEID that is present, in the order they are sent in
+        FTeidInstance::ALL.iter().filter_map(move |instance| self.f_teid(*instance).map(|ie| (*instance, ie)))
+    }
+
+    pub fn set_f_teid(&mut self, instance: FTeidInstance, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
+        // The Interface Type is the one for instance
+        *self.f_teid_mut(instance) = Some(f_teid::InformationElement::new(
+            instance.interface_type(),
             teid,
             ipv4_address,
             ipv6_address,
-            FTeidInstance::S1UENodeB as u8
+            instance as u8
         ).unwrap());
     }
 
+    pub fn unset_f_teid(&mut self, instance: FTeidInstance) {
+        *self.f_teid_mut(instance) = None;
+    }
+
+    pub fn set_s1_u_enodeb_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
+        self.set_f_teid(FTeidInstance::S1UENodeB, teid, ipv4_address, ipv6_address);
+    }
+
     pub fn unset_s1_u_enodeb_f_teid(&mut self) {
         self.s1_u_enodeb_f_teid = None;
     }
 
     pub fn set_s4_u_sgsn_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s4_u_sgsn_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S4SgsnGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S4USgsn as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S4USgsn, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s4_u_sgsn_f_teid(&mut self) {
         self.s4_u_sgsn_f_teid = None;
     }
 
     pub fn set_s5_s8_u_sgw_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s5_s8_u_sgw_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S5S8SgwGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S5S8USgw as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S5S8USgw, teid, ipv4_address, ipv6_address);
     }
 
     pub fn set_s5_s8_u_pgw_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s5_s8_u_pgw_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S5S8PgwGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S5S8UPgw as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S5S8UPgw, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s5_s8_u_pgw_f_teid(&mut self) {
         self.s5_s8_u_pgw_f_teid = None;
@@ -249,39 +272,21 @@ impl InformationElement {
     }
 
     pub fn set_s12_rnc_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s12_rnc_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S12RncGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S12Rnc as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S12Rnc, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s12_rnc_f_teid(&mut self) {
         self.s12_rnc_f_teid = None;
     }
 
     pub fn set_s2b_u_epdg_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s2b_u_epdg_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S2bUEPdgGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S2bUEPdg as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S2bUEPdg, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s2b_u_epdg_f_teid(&mut self) {
         self.s2b_u_epdg_f_teid = None;
     }
 
     pub fn set_s2a_u_twan_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s2a_u_twan_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S2aTwanGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S2aUTwan as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S2aUTwan, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s2a_u_twan_f_teid(&mut self) -> &Self {
         self.s2a_u_twan_f_teid = None;
@@ -290,13 +295,7 @@ impl InformationElement {
     }
 
     pub fn set_s11_u_mme_f_teid(&mut self, teid: u32, ipv4_address: Option<Ipv4Addr>, ipv6_address: Option<Ipv6Addr>) {
-        self.s11_u_mme_f_teid = Some(f_teid::InformationElement::new(
-            f_teid::InterfaceType::S11MmeGtpU,
-            teid,
-            ipv4_address,
-            ipv6_address,
-            FTeidInstance::S11UMme as u8
-        ).unwrap());
+        self.set_f_teid(FTeidInstance::S11UMme, teid, ipv4_address, ipv6_address);
     }
     pub fn unset_s11_u_mme_f_teid(&mut self) {
         self.s11_u_mme_f_teid = None;
@@ -322,40 +321,47 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
+    fn payload_length(&self) -> u16 {
+        let mut length = 0;
 
-        let mut length = 4;
+        length = length + self.eps_bearer_id.wire_length();
 
-        length = length + self.eps_bearer_id.length();
+        if let Some(ref ie) = self.cause {
+            length = length + ie.wire_length();
+        }
 
         if let Some(ref ie) = self.s1_u_enodeb_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s4_u_sgsn_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s5_s8_u_sgw_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s5_s8_u_pgw_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s12_rnc_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s2b_u_epdg_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
         if let Some(ref ie) = self.s2a_u_twan_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
         }
 
-        length = length + self.bearer_level_qos.length();
+        if let Some(ref ie) = self.bearer_level_qos {
+            length = length + ie.wire_length();
+        }
 
         if let Some(ref ie) = self.s11_u_mme_f_teid {
-            length = length + ie.length();
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.charging_id {
+            length = length + ie.wire_length();
         }
 
         length
@@ -368,9 +374,8 @@ impl InformationElementTraits for InformationElement {
         buffer[pos] = self.information_element_type() as u8;
         pos = pos + 1;
 
-        // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        // Skip the length. It is written once the embedded IEs have been generated
+        // so that their lengths don't have to be walked twice.
         pos = pos + 2;
 
         //Spare and instance
@@ -378,6 +383,10 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         pos = pos + self.eps_bearer_id.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.cause {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
         
         if let Some(ref ie) = self.s1_u_enodeb_f_teid {
             pos = pos + ie.generate(&mut buffer[pos..]);
@@ -401,31 +410,93 @@ impl InformationElementTraits for InformationElement {
             pos = pos + ie.generate(&mut buffer[pos..]);
         }
 
-        pos = pos + self.bearer_level_qos.generate(&mut buffer[pos..]);
+        if let Some(ref ie) = self.bearer_level_qos {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
 
         if let Some(ref ie) = self.s11_u_mme_f_teid {
             pos = pos + ie.generate(&mut buffer[pos..]);
         }
 
+        if let Some(ref ie) = self.charging_id {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        // Write the length
+        // We subtract 4 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], (pos-4) as u16);
+
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.eps_bearer_id.dissect(d);
+
+            if let Some(ref ie) = self.cause {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s1_u_enodeb_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s4_u_sgsn_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s5_s8_u_sgw_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s5_s8_u_pgw_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s12_rnc_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s2b_u_epdg_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s2a_u_twan_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.bearer_level_qos {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.s11_u_mme_f_teid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.charging_id {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::MTU;
     use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
 
+    use alloc::vec::Vec;
+
     #[test]
     fn test_generate() {
         let mut buffer = [0; MTU];
 
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -480,10 +551,8 @@ mod tests {
     #[test]
     fn test_length() {
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -500,7 +569,7 @@ mod tests {
                     bearer_qos,
                     0
                 ) {
-                    assert_eq!(ie.length(), 35);
+                    assert_eq!(ie.wire_length(), 35);
                 }
                 else { assert!(false); }
             }
@@ -512,10 +581,8 @@ mod tests {
     #[test]
     fn test_message_type() {
         let bearer_qos = bearer_qos::InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+            bearer_qos::Arp::new(false, 9, true).unwrap(),
+            bearer_qos::Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
@@ -565,17 +632,199 @@ mod tests {
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
             assert_eq!(ie.eps_bearer_id.eps_bearer_id, 7);
-            assert_eq!(ie.bearer_level_qos.pvi, true);
-            assert_eq!(ie.bearer_level_qos.pl(), 9);
-            assert_eq!(ie.bearer_level_qos.pci, false);
-            assert_eq!(ie.bearer_level_qos.qci, 7);
-            assert_eq!(ie.bearer_level_qos.guaranteed_ul_bitrate(), 0);
-            assert_eq!(ie.bearer_level_qos.guaranteed_dl_bitrate(), 0);
-            assert_eq!(ie.bearer_level_qos.max_ul_bitrate(), 10_000_000);
-            assert_eq!(ie.bearer_level_qos.max_dl_bitrate(), 10_000_000);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().arp.pvi, false);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().arp.pl, 9);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().arp.pci, true);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().qci, bearer_qos::Qci::Qci7);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().guaranteed_ul_kbps(), 0);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().guaranteed_dl_kbps(), 0);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().max_ul_kbps(), 10_000_000);
+            assert_eq!(ie.bearer_level_qos.as_ref().unwrap().max_dl_kbps(), 10_000_000);
         }
         else {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+    #[test]
+    fn test_message_parse_trailing_ies() {
+        // IEs after the Bearer Context belong to the enclosing message / grouped IE and must not be parsed into it
+        let ie_bytes = [
+            InformationElementType::BearerContext as u8,
+            0, 31, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            7, // EPS Bearer ID
+            InformationElementType::BearerQoS as u8,
+            0, 22, // Length
+            0, // Spare
+            0b00100101, // Flags
+            7, // QCI
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // Linked EPS Bearer ID
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.eps_bearer_id.eps_bearer_id, 7);
+            assert_eq!(pos, 35);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated Bearer Context
+        assert!(InformationElement::parse(&ie_bytes[..20]).is_none());
+    }
+
+    #[test]
+    fn test_message_parse_without_qos() {
+        let ie_bytes = [
+            InformationElementType::BearerContext as u8,
+            0, 18, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare
+            0b1000_0000, // V4 and Interface Type
+            0x12, 0x34, 0x56, 0x78, // TEID
+            10, 0, 0, 1, // IPv4 Address
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 22);
+            assert_eq!(ie.eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(ie.bearer_level_qos, None);
+            assert_eq!(ie.s1_u_enodeb_f_teid.as_ref().map(|f_teid| f_teid.teid), Some(0x12345678));
+        }
+        else {
+            assert!(false);
+        }
+
+        let mut ie = InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 0).unwrap();
+        ie.set_s1_u_enodeb_f_teid(0x12345678, Some(Ipv4Addr::new(10, 0, 0, 1)), None);
+
+        let mut buffer = [0; MTU];
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], ie_bytes[..]);
+        assert_eq!(ie.wire_length() as usize, pos);
+
+        // The EPS Bearer ID is mandatory
+        assert!(InformationElement::parse(&[InformationElementType::BearerContext as u8, 0, 0, 0]).is_none());
+        assert!(InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 0x10).is_err());
+    }
+
+    #[test]
+    fn test_message_parse_charging_id() {
+        let ie_bytes = [
+            InformationElementType::BearerContext as u8,
+            0, 39, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            7, // EPS Bearer ID
+            InformationElementType::BearerQoS as u8,
+            0, 22, // Length
+            0, // Spare
+            0b00100101, // Flags
+            7, // QCI
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            InformationElementType::ChargingID as u8,
+            0, 4, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, 0x78, // Charging ID
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            if let Some(charging_id) = ie.charging_id {
+                assert_eq!(charging_id.charging_id, 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+            assert_eq!(pos, 43);
+        }
+        else {
+            assert!(false);
+        }
+
+        let mut buffer = [0; MTU];
+
+        let mut ie = InformationElement::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+            0
+        ).unwrap();
+
+        ie.charging_id = Some(charging_id::InformationElement::new(0xCAFEF00D, 0).unwrap());
+
+        let pos = ie.generate(&mut buffer);
+
+        if let Some((ie, _pos)) = InformationElement::parse(&buffer[..pos]) {
+            assert_eq!(ie.charging_id.map(|c| c.charging_id), Some(0xCAFEF00D));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_f_teids() {
+        let mut ie = InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 0).unwrap();
+
+        assert_eq!(ie.f_teids().count(), 0);
+
+        ie.set_f_teid(FTeidInstance::S5S8UPgw, 0x2222, Some(Ipv4Addr::new(10,0,0,2)), None);
+        ie.set_s1_u_enodeb_f_teid(0x1111, Some(Ipv4Addr::new(10,0,0,1)), None);
+
+        // In the order they are sent in, not the order they were set in
+        let f_teids: Vec<(FTeidInstance, u32)> = ie.f_teids().map(|(instance, f)| (instance, f.teid)).collect();
+        assert_eq!(f_teids, [(FTeidInstance::S1UENodeB, 0x1111), (FTeidInstance::S5S8UPgw, 0x2222)]);
+
+        if let Some(f) = ie.f_teid(FTeidInstance::S5S8UPgw) {
+            assert_eq!(f.interface_type, f_teid::InterfaceType::S5S8PgwGtpU);
+            assert_eq!(f.instance(), 3);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Every instance is parsed back into its own field
+        for instance in FTeidInstance::ALL {
+            ie.set_f_teid(instance, instance as u32, Some(Ipv4Addr::new(10,0,0,1)), None);
+        }
+
+        let mut buffer = [0; MTU];
+        let pos = ie.generate(&mut buffer);
+
+        if let Some((ie, _pos)) = InformationElement::parse(&buffer[..pos]) {
+            for (instance, f) in ie.f_teids() {
+                assert_eq!(f.teid, instance as u32);
+                assert_eq!(f.interface_type, instance.interface_type());
+            }
+            assert_eq!(ie.f_teids().count(), 8);
+        }
+        else {
+            assert!(false);
+        }
+
+        ie.unset_f_teid(FTeidInstance::S1UENodeB);
+        assert!(ie.s1_u_enodeb_f_teid.is_none());
+        assert_eq!(ie.f_teids().count(), 7);
+    }
+}
@@ -1,7 +1,203 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::bitrate::Bitrate;
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+
+use core::fmt;
+
+// DSCP code points (RFC 2474, RFC 2597, RFC 3246)
+pub const DSCP_BE: u8 = 0;
+pub const DSCP_AF11: u8 = 10;
+pub const DSCP_AF21: u8 = 18;
+pub const DSCP_AF31: u8 = 26;
+pub const DSCP_AF41: u8 = 34;
+pub const DSCP_CS5: u8 = 40;
+pub const DSCP_EF: u8 = 46;
+
+// The largest bitrate that fits in 5 octets
+pub const MAX_BITRATE_KBPS: u64 = uint::MAX_U40;
+
+pub fn dscp_for_qci(qci: u8) -> u8 {
+    // Recommended marking of the transport for each standardised QCI (TS 23.203 Table 6.1.7) as in GSMA IR.34.
+    // Unknown and operator specific QCIs are best effort.
+    match qci {
+        1 | 65 | 66 => DSCP_EF, // Conversational voice and MC-PTT voice
+        2 | 3 | 4 | 67 => DSCP_AF41, // Conversational and streaming video, real time gaming and MC video
+        5 | 69 => DSCP_CS5, // IMS and MC signalling
+        6 | 70 => DSCP_AF31,
+        7 => DSCP_AF21,
+        8 => DSCP_AF11,
+        _ => DSCP_BE,
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Qci {
+    // Standardised QCIs (TS 23.203 Table 6.1.7-A)
+    Qci1, // Conversational voice
+    Qci2, // Conversational video
+    Qci3, // Real time gaming and V2X messages
+    Qci4, // Non-conversational video
+    Qci5, // IMS signalling
+    Qci6, // Buffered video and TCP based services
+    Qci7, // Voice, live video and interactive gaming
+    Qci8, // Buffered video and TCP based services
+    Qci9, // Buffered video and TCP based services. Usually the default bearer
+    Qci65, // MC-PTT voice
+    Qci66, // Non-MC-PTT voice
+    Qci67, // MC video
+    Qci69, // MC signalling
+    Qci70, // MC data
+    Qci71, // Live uplink streaming
+    Qci72, // Live uplink streaming
+    Qci73, // Live uplink streaming
+    Qci74, // Live uplink streaming
+    Qci75, // V2X messages
+    Qci76, // Live uplink streaming
+    Qci79, // V2X messages
+    Qci80, // Low latency eMBB
+    Qci82, // Discrete automation
+    Qci83, // Discrete automation
+    Qci84, // Intelligent transport systems
+    Qci85, // Electricity distribution
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
+}
+
+impl From<u8> for Qci
+{
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Qci::Qci1,
+            2 => Qci::Qci2,
+            3 => Qci::Qci3,
+            4 => Qci::Qci4,
+            5 => Qci::Qci5,
+            6 => Qci::Qci6,
+            7 => Qci::Qci7,
+            8 => Qci::Qci8,
+            9 => Qci::Qci9,
+            65 => Qci::Qci65,
+            66 => Qci::Qci66,
+            67 => Qci::Qci67,
+            69 => Qci::Qci69,
+            70 => Qci::Qci70,
+            71 => Qci::Qci71,
+            72 => Qci::Qci72,
+            73 => Qci::Qci73,
+            74 => Qci::Qci74,
+            75 => Qci::Qci75,
+            76 => Qci::Qci76,
+            79 => Qci::Qci79,
+            80 => Qci::Qci80,
+            82 => Qci::Qci82,
+            83 => Qci::Qci83,
+            84 => Qci::Qci84,
+            85 => Qci::Qci85,
+            _ => Qci::Other(value),
+        }
+    }
+}
+
+impl From<Qci> for u8
+{
+    fn from(value: Qci) -> Self {
+        match value {
+            Qci::Qci1 => 1,
+            Qci::Qci2 => 2,
+            Qci::Qci3 => 3,
+            Qci::Qci4 => 4,
+            Qci::Qci5 => 5,
+            Qci::Qci6 => 6,
+            Qci::Qci7 => 7,
+            Qci::Qci8 => 8,
+            Qci::Qci9 => 9,
+            Qci::Qci65 => 65,
+            Qci::Qci66 => 66,
+            Qci::Qci67 => 67,
+            Qci::Qci69 => 69,
+            Qci::Qci70 => 70,
+            Qci::Qci71 => 71,
+            Qci::Qci72 => 72,
+            Qci::Qci73 => 73,
+            Qci::Qci74 => 74,
+            Qci::Qci75 => 75,
+            Qci::Qci76 => 76,
+            Qci::Qci79 => 79,
+            Qci::Qci80 => 80,
+            Qci::Qci82 => 82,
+            Qci::Qci83 => 83,
+            Qci::Qci84 => 84,
+            Qci::Qci85 => 85,
+            Qci::Other(value) => value,
+        }
+    }
+}
+
+impl Qci {
+    pub fn is_standardised(&self) -> bool {
+        !matches!(self, Qci::Other(_))
+    }
+
+    pub fn is_operator_specific(&self) -> bool {
+        // TS 29.274 8.15
+        matches!(self, Qci::Other(128..=254))
+    }
+
+    pub fn dscp(&self) -> u8 {
+        dscp_for_qci((*self).into())
+    }
+}
 
+impl fmt::Display for Qci {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Arp {
+    // Allocation and Retention Priority (TS 23.203 6.1.7.3)
+    pub pci: bool, // Pre-emption capability. true means this bearer may pre-empt others
+    pub pl: u8, // Priority level. 1 is the highest priority
+    pub pvi: bool, // Pre-emption vulnerability. true means this bearer may be pre-empted
+}
+
+impl Arp {
+    pub fn new(pci: bool, pl: u8, pvi: bool) -> Result<Self, String> {
+        if pl > 0xF {
+            Err(format!("PL is > 0xF {}", pl))
+        }
+        else {
+            Ok(Arp { pci, pl, pvi })
+        }
+    }
+
+    fn generate(&self) -> u8 {
+        // A PCI / PVI bit of 0 means the capability / vulnerability is enabled
+        (if self.pci {0} else {1} << 6) |
+        ((self.pl & 0xF) << 2) |
+        (if self.pvi {0} else {1})
+    }
+
+    fn parse(flags: u8) -> Self {
+        Arp {
+            pci: ((flags >> 6) & 0b1) == 0,
+            pl: (flags >> 2) & 0xF,
+            pvi: (flags & 0b1) == 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -36,82 +232,80 @@ pub struct InformationElement {
         25      | Guaranteed bitrate for Downlink (Octet 4)                     |
         26      | Guaranteed bitrate for Downlink (Octet 5)                     |
                 |---------------------------------------------------------------|
+
+        Bitrates are in kbps and can use all 40 bits (TS 29.274 8.15)
     */
 
     instance: u8,
-    pub pci: bool,
-    pl: u8,
-    pub pvi: bool,
-    pub qci: u8,
-    max_ul_bitrate: u64,
-    max_dl_bitrate: u64,
-    guaranteed_ul_bitrate: u64,
-    guaranteed_dl_bitrate: u64,
+    pub arp: Arp,
+    pub qci: Qci,
+    max_ul_kbps: u64,
+    max_dl_kbps: u64,
+    guaranteed_ul_kbps: u64,
+    guaranteed_dl_kbps: u64,
 }
 
 impl InformationElement {
     pub fn new(
-        pci: bool,
-        pl: u8,
-        pvi: bool,
-        qci: u8,
-        max_ul_bitrate: u64,
-        max_dl_bitrate: u64,
-        guaranteed_ul_bitrate: u64,
-        guaranteed_dl_bitrate: u64,
+        arp: Arp,
+        qci: Qci,
+        max_ul_kbps: u64,
+        max_dl_kbps: u64,
+        guaranteed_ul_kbps: u64,
+        guaranteed_dl_kbps: u64,
         instance: u8,
     ) -> Result<Self, String> {
 
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
-        else if pl > 0xF {
-            Err(format!("PL is > 0xF {}", pl))
+        else if arp.pl > 0xF {
+            Err(format!("PL is > 0xF {}", arp.pl))
         }
-        else if max_ul_bitrate > 10_000_000 {
-            Err(format!("Max UL Bitrate is > 10,000,000 {}", max_ul_bitrate))
+        else if max_ul_kbps > MAX_BITRATE_KBPS {
+            Err(format!("Max UL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, max_ul_kbps))
         }
-        else if max_dl_bitrate > 10_000_000 {
-            Err(format!("Max DL Bitrate is > 10,000,000 {}", max_dl_bitrate))
+        else if max_dl_kbps > MAX_BITRATE_KBPS {
+            Err(format!("Max DL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, max_dl_kbps))
         }
-        else if guaranteed_ul_bitrate > 10_000_000 {
-            Err(format!("Guaranteed UL Bitrate is > 10,000,000 {}", guaranteed_ul_bitrate))
+        else if guaranteed_ul_kbps > MAX_BITRATE_KBPS {
+            Err(format!("Guaranteed UL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, guaranteed_ul_kbps))
         }
-        else if guaranteed_dl_bitrate > 10_000_000 {
-            Err(format!("Guaranteed DL Bitrate is > 10,000,000 {}", guaranteed_dl_bitrate))
+        else if guaranteed_dl_kbps > MAX_BITRATE_KBPS {
+            Err(format!("Guaranteed DL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, guaranteed_dl_kbps))
         }
         else {
             Ok(InformationElement {
-                pci,
-                pl,
-                pvi,
+                arp,
                 qci,
-                max_ul_bitrate,
-                max_dl_bitrate,
-                guaranteed_ul_bitrate,
-                guaranteed_dl_bitrate,
-                instance: instance,
+                max_ul_kbps,
+                max_dl_kbps,
+                guaranteed_ul_kbps,
+                guaranteed_dl_kbps,
+                instance,
             })
         }
     }
 
-    fn generate_flags(&self) -> u8 {
-        (if self.pci {0} else {1} << 6) | 
-        ((self.pl & 0xF) << 2) | 
-        (if self.pvi {0} else {1})
+    pub fn from_bitrates(
+        arp: Arp,
+        qci: Qci,
+        max_ul: Bitrate,
+        max_dl: Bitrate,
+        guaranteed_ul: Bitrate,
+        guaranteed_dl: Bitrate,
+        instance: u8,
+    ) -> Result<Self, String> {
+        Self::new(arp, qci, max_ul.kbps(), max_dl.kbps(), guaranteed_ul.kbps(), guaranteed_dl.kbps(), instance)
     }
 
-    fn parse_flags(flags: u8) -> (bool, u8, bool) {
-        let pci = ((flags >> 6) & 0b1) == 1;
-        let pl = (flags >> 2) & 0xF;
-        let pvi = ((flags) & 0b1) == 1;
-
-        (pci, pl, pvi)
-    }
-    
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
-        
+
         // Read the type
         let _ie_type = buffer[pos];
         pos = pos + 1;
@@ -124,56 +318,70 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        let (pci, pl, pvi) = Self::parse_flags(buffer[pos]);
+        if length < 22 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let arp = Arp::parse(buffer[pos]);
         pos = pos + 1;
 
-        let qci = buffer[pos];
+        let qci = Qci::from(buffer[pos]);
         pos = pos + 1;
 
-        let max_ul_bitrate = NetworkEndian::read_uint(&buffer[pos..pos+5], 5);
+        let max_ul_kbps = uint::parse_u40(&buffer[pos..])?;
         pos = pos + 5;
 
-        let max_dl_bitrate = NetworkEndian::read_uint(&buffer[pos..pos+5], 5);
+        let max_dl_kbps = uint::parse_u40(&buffer[pos..])?;
         pos = pos + 5;
 
-        let guaranteed_ul_bitrate = NetworkEndian::read_uint(&buffer[pos..pos+5], 5);
+        let guaranteed_ul_kbps = uint::parse_u40(&buffer[pos..])?;
         pos = pos + 5;
 
-        let guaranteed_dl_bitrate = NetworkEndian::read_uint(&buffer[pos..pos+5], 5);
+        let guaranteed_dl_kbps = uint::parse_u40(&buffer[pos..])?;
         // pos = pos + 5;
 
         Some(
             (
                 InformationElement {
-                    pci,
-                    pl,
-                    pvi,
+                    arp,
                     qci,
-                    max_ul_bitrate,
-                    max_dl_bitrate,
-                    guaranteed_ul_bitrate,
-                    guaranteed_dl_bitrate,
-                    instance: instance,
+                    max_ul_kbps,
+                    max_dl_kbps,
+                    guaranteed_ul_kbps,
+                    guaranteed_dl_kbps,
+                    instance,
                 },
                 (length + 4) as usize
             )
-        )        
+        )
     }
 
-    pub fn pl(&self) -> u8 {
-        self.pl
+    pub fn dscp(&self) -> u8 {
+        self.qci.dscp()
+    }
+    pub fn max_ul_kbps(&self) -> u64 {
+        self.max_ul_kbps
+    }
+    pub fn max_dl_kbps(&self) -> u64 {
+        self.max_dl_kbps
+    }
+    pub fn guaranteed_ul_kbps(&self) -> u64 {
+        self.guaranteed_ul_kbps
+    }
+    pub fn guaranteed_dl_kbps(&self) -> u64 {
+        self.guaranteed_dl_kbps
     }
-    pub fn max_ul_bitrate(&self) -> u64 {
-        self.max_ul_bitrate
+    pub fn max_ul(&self) -> Bitrate {
+        Bitrate::from_kbps(self.max_ul_kbps)
     }
-    pub fn max_dl_bitrate(&self) -> u64 {
-        self.max_dl_bitrate
+    pub fn max_dl(&self) -> Bitrate {
+        Bitrate::from_kbps(self.max_dl_kbps)
     }
-    pub fn guaranteed_ul_bitrate(&self) -> u64 {
-        self.guaranteed_ul_bitrate
+    pub fn guaranteed_ul(&self) -> Bitrate {
+        Bitrate::from_kbps(self.guaranteed_ul_kbps)
     }
-    pub fn guaranteed_dl_bitrate(&self) -> u64 {
-        self.guaranteed_dl_bitrate
+    pub fn guaranteed_dl(&self) -> Bitrate {
+        Bitrate::from_kbps(self.guaranteed_dl_kbps)
     }
 }
 
@@ -196,51 +404,58 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+22
+    fn payload_length(&self) -> u16 {
+        22
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
         let mut pos = 0;
-        
+
         // Write the type
         buffer[pos] = self.information_element_type() as u8;
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.generate_flags();
+        buffer[pos] = self.arp.generate();
         pos = pos + 1;
 
-        buffer[pos] = self.qci;
+        buffer[pos] = self.qci.into();
         pos = pos + 1;
 
-        NetworkEndian::write_uint(&mut buffer[pos..pos+6], self.max_ul_bitrate, 5);
-        pos = pos + 5;
+        pos = pos + uint::generate_u40_lenient(self.max_ul_kbps, &mut buffer[pos..]);
 
-        NetworkEndian::write_uint(&mut buffer[pos..pos+6], self.max_dl_bitrate, 5);
-        pos = pos + 5;
+        pos = pos + uint::generate_u40_lenient(self.max_dl_kbps, &mut buffer[pos..]);
 
-        NetworkEndian::write_uint(&mut buffer[pos..pos+6], self.guaranteed_ul_bitrate, 5);
-        pos = pos + 5;
+        pos = pos + uint::generate_u40_lenient(self.guaranteed_ul_kbps, &mut buffer[pos..]);
 
-        NetworkEndian::write_uint(&mut buffer[pos..pos+6], self.guaranteed_dl_bitrate, 5);
-        pos = pos + 5;
+        pos = pos + uint::generate_u40_lenient(self.guaranteed_dl_kbps, &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.flag("Pre-emption Capability (PCI)", self.arp.pci);
+            d.field("Priority Level (PL)", self.arp.pl);
+            d.flag("Pre-emption Vulnerability (PVI)", self.arp.pvi);
+            d.field("QCI", self.qci);
+            d.field("Maximum Bit Rate for Uplink", format!("{} kbps", self.max_ul_kbps));
+            d.field("Maximum Bit Rate for Downlink", format!("{} kbps", self.max_dl_kbps));
+            d.field("Guaranteed Bit Rate for Uplink", format!("{} kbps", self.guaranteed_ul_kbps));
+            d.field("Guaranteed Bit Rate for Downlink", format!("{} kbps", self.guaranteed_dl_kbps));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,161 +466,174 @@ mod tests {
     fn test_generate() {
         let mut buffer = [0; MTU];
 
-        if let Ok(ie) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
+        let ie = InformationElement::new(
+            Arp::new(false, 9, true).unwrap(),
+            Qci::Qci7,
             10_000_000,
             10_000_000,
             0,
             0,
             0
-        ) {
-            let pos = ie.generate(&mut buffer);
-
-            assert_eq!(buffer[..pos], [InformationElementType::BearerQoS as u8,
-                0, 22, // Length
-                0, // Spare
-                0b01100100, // Flags
-                7, // QCI
-                0x00, 0x00, 0x98, 0x96, 0x80,
-                0x00, 0x00, 0x98, 0x96, 0x80,
-                0x00, 0x00, 0x00, 0x00, 0x00,
-                0x00, 0x00, 0x00, 0x00, 0x00,
-            ]);
-        }
-        else {
-            assert!(false);
-        }
+        ).unwrap();
 
-        if let Ok(_) = InformationElement::new(
-            false,
-            0x1F,
-            true,
-            7,
-            10_000_000,
-            10_000_000,
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::BearerQoS as u8,
+            0, 22, // Length
+            0, // Spare
+            0b01100100, // Flags
+            7, // QCI
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+
+        // Bitrates above 10 Gbps use all 5 octets
+        let ie = InformationElement::new(
+            Arp::new(true, 1, false).unwrap(),
+            Qci::Other(128),
+            MAX_BITRATE_KBPS,
+            20_000_000,
             0,
             0,
             0
-        ) {
-            // This should fail PL must be less than 0xF
-            assert!(false);
-        }
-        else {
-            assert!(true);
-        }
+        ).unwrap();
 
-        if let Ok(_) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_001,
-            10_000_000,
-            10_000_000,
-            10_000_000,
-            0
-        ) {
-            // This should fail Max UL Bitrate must be less than 10,000,000
-            assert!(false);
-        }
-        else {
-            assert!(true);
-        }
+        let pos = ie.generate(&mut buffer);
 
-        if let Ok(_) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_000,
-            10_000_001,
-            10_000_000,
-            10_000_000,
+        assert_eq!(buffer[..pos], [InformationElementType::BearerQoS as u8,
+            0, 22, // Length
+            0, // Spare
+            0b00000101, // Flags
+            128, // QCI
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0x00, 0x01, 0x31, 0x2D, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+        ]);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        let arp = Arp::new(false, 9, true).unwrap();
+
+        // PL must be at most 0xF
+        assert!(Arp::new(false, 0x1F, true).is_err());
+        assert!(InformationElement::new(Arp { pci: false, pl: 0x1F, pvi: true }, Qci::Qci7, 0, 0, 0, 0, 0).is_err());
+
+        // Bitrates must fit in 40 bits
+        assert!(InformationElement::new(arp, Qci::Qci7, MAX_BITRATE_KBPS + 1, 0, 0, 0, 0).is_err());
+        assert!(InformationElement::new(arp, Qci::Qci7, 0, MAX_BITRATE_KBPS + 1, 0, 0, 0).is_err());
+        assert!(InformationElement::new(arp, Qci::Qci7, 0, 0, MAX_BITRATE_KBPS + 1, 0, 0).is_err());
+        assert!(InformationElement::new(arp, Qci::Qci7, 0, 0, 0, MAX_BITRATE_KBPS + 1, 0).is_err());
+
+        assert!(InformationElement::new(arp, Qci::Qci7, 0, 0, 0, 0, 0x10).is_err());
+    }
+
+    #[test]
+    fn test_from_bitrates() {
+        let ie = InformationElement::from_bitrates(
+            Arp::new(false, 9, true).unwrap(),
+            Qci::Qci7,
+            Bitrate::from_gbps(10),
+            Bitrate::from_mbps(150),
+            Bitrate::from_bps(64_001),
+            Bitrate::from_kbps(0),
             0
-        ) {
-            // This should fail Max DL Bitrate must be less than 10,000,000
-            assert!(false);
-        }
-        else {
-            assert!(true);
-        }
-        if let Ok(_) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_000,
-            10_000_000,
-            10_000_001,
-            10_000_000,
+        ).unwrap();
+
+        assert_eq!(ie.max_ul_kbps(), 10_000_000);
+        assert_eq!(ie.max_dl_kbps(), 150_000);
+        // Rounded up to the next kbps
+        assert_eq!(ie.guaranteed_ul_kbps(), 65);
+        assert_eq!(ie.guaranteed_dl_kbps(), 0);
+
+        assert_eq!(ie.max_ul(), Bitrate::from_gbps(10));
+        assert_eq!(ie.max_dl(), Bitrate::from_mbps(150));
+        assert_eq!(ie.guaranteed_ul(), Bitrate::from_kbps(65));
+        assert_eq!(ie.guaranteed_dl(), Bitrate::from_kbps(0));
+
+        // Bitrates above 10 Gbps are allowed
+        let ie = InformationElement::from_bitrates(
+            Arp::new(false, 9, true).unwrap(),
+            Qci::Qci9,
+            Bitrate::from_gbps(100),
+            Bitrate::from_gbps(1_000),
+            Bitrate::from_kbps(0),
+            Bitrate::from_kbps(0),
             0
-        ) {
-            // This should fail Guaranteed UL Bitrate must be less than 10,000,000
-            assert!(false);
-        }
-        else {
-            assert!(true);
-        }
-        if let Ok(_) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_000,
-            10_000_000,
-            10_000_000,
-            10_000_001,
+        ).unwrap();
+        assert_eq!(ie.max_dl(), Bitrate::from_gbps(1_000));
+
+        assert!(InformationElement::from_bitrates(
+            Arp::new(false, 9, true).unwrap(),
+            Qci::Qci9,
+            Bitrate::from_kbps(MAX_BITRATE_KBPS + 1),
+            Bitrate::from_kbps(0),
+            Bitrate::from_kbps(0),
+            Bitrate::from_kbps(0),
             0
-        ) {
-            // This should fail Guaranteed DL Bitrate must be less than 10,000,000
-            assert!(false);
+        ).is_err());
+    }
+
+    #[test]
+    fn test_qci() {
+        let standardised = [1, 2, 3, 4, 5, 6, 7, 8, 9, 65, 66, 67, 69, 70, 71, 72, 73, 74, 75, 76, 79, 80, 82, 83, 84, 85];
+
+        for value in 0..=u8::MAX {
+            let qci = Qci::from(value);
+            assert_eq!(u8::from(qci), value);
+            assert_eq!(qci.is_standardised(), standardised.contains(&value));
         }
-        else {
-            assert!(true);
+
+        assert_eq!(Qci::from(9), Qci::Qci9);
+        assert_eq!(Qci::from(85), Qci::Qci85);
+        assert_eq!(Qci::from(68), Qci::Other(68));
+        assert!(Qci::from(128).is_operator_specific());
+        assert!(Qci::from(254).is_operator_specific());
+        assert!(!Qci::from(255).is_operator_specific());
+        assert!(!Qci::Qci9.is_operator_specific());
+    }
+
+    #[test]
+    fn test_arp() {
+        for flags in [0b0000_0000, 0b0100_0001, 0b0010_0100, 0b0011_1101] {
+            assert_eq!(Arp::parse(flags).generate(), flags);
         }
+
+        assert_eq!(Arp::parse(0b0010_0101), Arp::new(true, 9, false).unwrap());
     }
 
     #[test]
     fn test_length() {
-        if let Ok(ie) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_000,
-            10_000_000,
-            0,
-            0,
-            0
-        ) {
-            assert_eq!(ie.length(), 22+4);
-        }
-        else {
-            assert!(false);
-        }
+        let ie = InformationElement::new(Arp::new(false, 9, true).unwrap(), Qci::Qci7, 10_000_000, 10_000_000, 0, 0, 0).unwrap();
+        assert_eq!(ie.wire_length(), 22+4);
+    }
+
+    #[test]
+    fn test_dscp_for_qci() {
+        assert_eq!(dscp_for_qci(1), DSCP_EF);
+        assert_eq!(dscp_for_qci(2), DSCP_AF41);
+        assert_eq!(dscp_for_qci(5), DSCP_CS5);
+        assert_eq!(dscp_for_qci(6), DSCP_AF31);
+        assert_eq!(dscp_for_qci(7), DSCP_AF21);
+        assert_eq!(dscp_for_qci(8), DSCP_AF11);
+        assert_eq!(dscp_for_qci(9), DSCP_BE);
+        assert_eq!(dscp_for_qci(65), DSCP_EF);
+        assert_eq!(dscp_for_qci(0), DSCP_BE);
+        assert_eq!(dscp_for_qci(200), DSCP_BE);
+
+        assert_eq!(Qci::Qci5.dscp(), DSCP_CS5);
+
+        let ie = InformationElement::new(Arp::new(false, 9, true).unwrap(), Qci::Qci1, 0, 0, 0, 0, 0).unwrap();
+        assert_eq!(ie.dscp(), DSCP_EF);
     }
 
     #[test]
     fn test_message_type() {
-        if let Ok(ie) = InformationElement::new(
-            false,
-            9,
-            true,
-            7,
-            10_000_000,
-            10_000_000,
-            0,
-            0,
-            0
-        ) {
-            assert_eq!(ie.information_element_type() as u8, InformationElementType::BearerQoS as u8);
-        }
-        else {
-            assert!(false);
-        }
+        let ie = InformationElement::new(Arp::new(false, 9, true).unwrap(), Qci::Qci7, 10_000_000, 10_000_000, 0, 0, 0).unwrap();
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::BearerQoS as u8);
     }
 
     #[test]
@@ -418,22 +646,22 @@ mod tests {
             0x00, 0x00, 0x98, 0x96, 0x80,
             0x00, 0x00, 0x98, 0x96, 0x80,
             0x00, 0x00, 0x00, 0x00, 0x00,
-            0x00, 0x00, 0x00, 0x00, 0x00,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
         ];
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
-            assert_eq!(ie.pci, false);
-            assert_eq!(ie.pvi, true);
-            assert_eq!(ie.qci, 7);
-            assert_eq!(ie.pl(), 9);
-            assert_eq!(ie.max_ul_bitrate(), 10_000_000);
-            assert_eq!(ie.max_dl_bitrate(), 10_000_000);
-            assert_eq!(ie.guaranteed_ul_bitrate(), 0);
-            assert_eq!(ie.guaranteed_dl_bitrate(), 0);
+            assert_eq!(ie.arp.pci, true);
+            assert_eq!(ie.arp.pvi, false);
+            assert_eq!(ie.arp.pl, 9);
+            assert_eq!(ie.qci, Qci::Qci7);
+            assert_eq!(ie.max_ul_kbps(), 10_000_000);
+            assert_eq!(ie.max_dl_kbps(), 10_000_000);
+            assert_eq!(ie.guaranteed_ul_kbps(), 0);
+            assert_eq!(ie.guaranteed_dl_kbps(), MAX_BITRATE_KBPS);
         }
         else {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,16 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum CauseSource {
     LocalNode = 0,
     RemoteNode = 1,
@@ -23,200 +29,330 @@ impl TryFrom<u8> for CauseSource
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum CauseCode {
     // ReservedShallNotBeSent = 0,
     // Request / Initial Message
-    Reserved = 1,
-    LocalDetach = 2,
-    CompleteDetach = 3,
-    RATChangedfrom3GPPtoNon3GPP = 4,
-    ISRDeactivation = 5,
-    ErrorIndicationReceivedfromRNCeNodeBS4SGSNMME = 6,
-    IMSIDetachOnly = 7,
-    ReactivationRequest = 8,
-    PDNReconnectiontothisAPNDisallowed = 9,
-    AccessChangedfroNon3GPPto3GPP = 10,
-    PDNConnectionInactivityTimerExpires = 11,
-    PGWNotResponding = 12,
-    NetworkFailure = 13,
-    QoSParameterMismatch = 14,
-    EPSto5GSMobility = 15,
+    Reserved,
+    LocalDetach,
+    CompleteDetach,
+    RATChangedfrom3GPPtoNon3GPP,
+    ISRDeactivation,
+    ErrorIndicationReceivedfromRNCeNodeBS4SGSNMME,
+    IMSIDetachOnly,
+    ReactivationRequest,
+    PDNReconnectiontothisAPNDisallowed,
+    AccessChangedfroNon3GPPto3GPP,
+    PDNConnectionInactivityTimerExpires,
+    PGWNotResponding,
+    NetworkFailure,
+    QoSParameterMismatch,
+    EPSto5GSMobility,
     // Acceptance in a Response / Triggered message
-    RequestAccepted = 16,
-    RequestAcceptedPartially = 17,
-    NewPDNTypeDuetoNetworkPerformance = 18,
-    NewPDNTypeDuetoSingleAddressBearerOnly = 19,
+    RequestAccepted,
+    RequestAcceptedPartially,
+    NewPDNTypeDuetoNetworkPerformance,
+    NewPDNTypeDuetoSingleAddressBearerOnly,
     // Rejection in a Response / Triggered message
-    ContextNotFound = 64,
-    InvalidMessageFormat = 65,
-    VersionNotSupportedByNextPeer = 66,
-    InvalidLength = 67,
-    ServiceNotSupported = 68,
-    MandatoryIEIncorrect = 69,
-    MandatoryIEMissing = 70,
+    ContextNotFound,
+    InvalidMessageFormat,
+    VersionNotSupportedByNextPeer,
+    InvalidLength,
+    ServiceNotSupported,
+    MandatoryIEIncorrect,
+    MandatoryIEMissing,
     // ShallNotBeUsed = 71,
-    SystemFailure = 72,
-    NoResourcesAvailable = 73,
-    SemanticErrorintheTFTOperation = 74,
-    SyntacticErrorintheTFTOperation = 75,
-    SemanticErrorsinthePacketFilters = 76,
-    SyntacticErrorsinthePacketFilters = 77,
-    MissingOrUnknownAPN = 78,
+    SystemFailure,
+    NoResourcesAvailable,
+    SemanticErrorintheTFTOperation,
+    SyntacticErrorintheTFTOperation,
+    SemanticErrorsinthePacketFilters,
+    SyntacticErrorsinthePacketFilters,
+    MissingOrUnknownAPN,
     // ShallNotBeUsed = 79,
-    GREKeyNotFound = 80,
-    RelocationFailure = 81,
-    DeniedinRAT = 82,
-    PreferredPDNTypeNotSupported = 83,
-    AllDynamicAddressesAreOccupied = 84,
-    UEContextWithoutTFTAlreadyActivated = 85,
-    ProtocolTypeNotSupported = 86,
-    UENotResponding = 87,
-    UERefuses = 88,
-    ServiceDenied = 89,
-    UnableToPageUE = 90,
-    NoMemoryAvailable = 91,
-    UserAuthenticationFailed = 92,
-    APNAccessDeniedNoSubscription = 93,
-    RequestRejectedReasonNotSpecified = 94,
-    PTMSISignatureMismatch = 95,
-    IMSIorIMEINotKnown = 96,
-    SemanticErrorintheTADOperation = 97,
-    SyntacticErrorintheTADOperation = 98,
+    GREKeyNotFound,
+    RelocationFailure,
+    DeniedinRAT,
+    PreferredPDNTypeNotSupported,
+    AllDynamicAddressesAreOccupied,
+    UEContextWithoutTFTAlreadyActivated,
+    ProtocolTypeNotSupported,
+    UENotResponding,
+    UERefuses,
+    ServiceDenied,
+    UnableToPageUE,
+    NoMemoryAvailable,
+    UserAuthenticationFailed,
+    APNAccessDeniedNoSubscription,
+    RequestRejectedReasonNotSpecified,
+    PTMSISignatureMismatch,
+    IMSIorIMEINotKnown,
+    SemanticErrorintheTADOperation,
+    SyntacticErrorintheTADOperation,
     // ShallNotBeUsed = 99,
-    RemotePeerNotResponding = 100,
-    CollisionWithNetworkInitiatedRequest = 101,
-    UnableToPageUEDueToSuspension = 102,
-    ConditionalIEMissing = 103,
-    APNRestrictiontypeIncompatibleWithCurrentlyActivePDNConnection = 104,
-    InvalidOverallLengthOfTheTriggeredResponseMessageAndAPiggybackedInitialMessage = 105,
-    DataForwardingNotSupported = 106,
-    InvalidReplyFromRemotePeer = 107,
-    FallbackToGTPv1 = 108,
-    InvalidPeer = 109,
-    TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress = 110,
-    ModificationsNotLimitedToS1UBearers = 111,
-    RequestRejectedForAPMIPv6Reason = 112,
-    APNCongestion = 113,
-    BearerHandlingNotSupported = 114,
-    UEAlreadyReAttached = 115,
-    MultiplePDNConnectionsForAGivenAPNNotAllowed = 116,
-    TargetAccessRestrictedForTheSubscriber = 117,
+    RemotePeerNotResponding,
+    CollisionWithNetworkInitiatedRequest,
+    UnableToPageUEDueToSuspension,
+    ConditionalIEMissing,
+    APNRestrictiontypeIncompatibleWithCurrentlyActivePDNConnection,
+    InvalidOverallLengthOfTheTriggeredResponseMessageAndAPiggybackedInitialMessage,
+    DataForwardingNotSupported,
+    InvalidReplyFromRemotePeer,
+    FallbackToGTPv1,
+    InvalidPeer,
+    TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress,
+    ModificationsNotLimitedToS1UBearers,
+    RequestRejectedForAPMIPv6Reason,
+    APNCongestion,
+    BearerHandlingNotSupported,
+    UEAlreadyReAttached,
+    MultiplePDNConnectionsForAGivenAPNNotAllowed,
+    TargetAccessRestrictedForTheSubscriber,
     // ShallNotBeUsed = 118,
-    MMESGSNRefusesDueToVPLMNPolicy = 119,
-    CTPCEntityCongestion = 120,
-    LateOverlappingRequest = 121,
-    TimedOutRequest = 122,
-    UEIsTemporarilyNotReachableDueToPowerSaving = 123,
-    RelocationFailureDueToNASMessageRedirection = 124,
-    UENotAuthorisedByOCSOrExternalAAAServer = 125,
-    MultipleAccessesToAPDNConnectionNotAllowed = 126,
-    RequestRejectedDueToUECapability = 127,
-    S1UPathFailure = 128,
-    _5GCNotAllowed = 129,
-
+    MMESGSNRefusesDueToVPLMNPolicy,
+    CTPCEntityCongestion,
+    LateOverlappingRequest,
+    TimedOutRequest,
+    UEIsTemporarilyNotReachableDueToPowerSaving,
+    RelocationFailureDueToNASMessageRedirection,
+    UENotAuthorisedByOCSOrExternalAAAServer,
+    MultipleAccessesToAPDNConnectionNotAllowed,
+    RequestRejectedDueToUECapability,
+    S1UPathFailure,
+    _5GCNotAllowed,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for CauseCode
+impl From<u8> for CauseCode
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            // ReservedShallNotBeSent = 0,
+            // Request / Initial Message
+            1 => CauseCode::Reserved,
+            2 => CauseCode::LocalDetach,
+            3 => CauseCode::CompleteDetach,
+            4 => CauseCode::RATChangedfrom3GPPtoNon3GPP,
+            5 => CauseCode::ISRDeactivation,
+            6 => CauseCode::ErrorIndicationReceivedfromRNCeNodeBS4SGSNMME,
+            7 => CauseCode::IMSIDetachOnly,
+            8 => CauseCode::ReactivationRequest,
+            9 => CauseCode::PDNReconnectiontothisAPNDisallowed,
+            10 => CauseCode::AccessChangedfroNon3GPPto3GPP,
+            11 => CauseCode::PDNConnectionInactivityTimerExpires,
+            12 => CauseCode::PGWNotResponding,
+            13 => CauseCode::NetworkFailure,
+            14 => CauseCode::QoSParameterMismatch,
+            15 => CauseCode::EPSto5GSMobility,
+            // Acceptance in a Response / Triggered message
+            16 => CauseCode::RequestAccepted,
+            17 => CauseCode::RequestAcceptedPartially,
+            18 => CauseCode::NewPDNTypeDuetoNetworkPerformance,
+            19 => CauseCode::NewPDNTypeDuetoSingleAddressBearerOnly,
+            // Rejection in a Response / Triggered message
+            64 => CauseCode::ContextNotFound,
+            65 => CauseCode::InvalidMessageFormat,
+            66 => CauseCode::VersionNotSupportedByNextPeer,
+            67 => CauseCode::InvalidLength,
+            68 => CauseCode::ServiceNotSupported,
+            69 => CauseCode::MandatoryIEIncorrect,
+            70 => CauseCode::MandatoryIEMissing,
+            // ShallNotBeUsed = 71,
+            72 => CauseCode::SystemFailure,
+            73 => CauseCode::NoResourcesAvailable,
+            74 => CauseCode::SemanticErrorintheTFTOperation,
+            75 => CauseCode::SyntacticErrorintheTFTOperation,
+            76 => CauseCode::SemanticErrorsinthePacketFilters,
+            77 => CauseCode::SyntacticErrorsinthePacketFilters,
+            78 => CauseCode::MissingOrUnknownAPN,
+            // ShallNotBeUsed = 79,
+            80 => CauseCode::GREKeyNotFound,
+            81 => CauseCode::RelocationFailure,
+            82 => CauseCode::DeniedinRAT,
+            83 => CauseCode::PreferredPDNTypeNotSupported,
+            84 => CauseCode::AllDynamicAddressesAreOccupied,
+            85 => CauseCode::UEContextWithoutTFTAlreadyActivated,
+            86 => CauseCode::ProtocolTypeNotSupported,
+            87 => CauseCode::UENotResponding,
+            88 => CauseCode::UERefuses,
+            89 => CauseCode::ServiceDenied,
+            90 => CauseCode::UnableToPageUE,
+            91 => CauseCode::NoMemoryAvailable,
+            92 => CauseCode::UserAuthenticationFailed,
+            93 => CauseCode::APNAccessDeniedNoSubscription,
+            94 => CauseCode::RequestRejectedReasonNotSpecified,
+            95 => CauseCode::PTMSISignatureMismatch,
+            96 => CauseCode::IMSIorIMEINotKnown,
+            97 => CauseCode::SemanticErrorintheTADOperation,
+            98 => CauseCode::SyntacticErrorintheTADOperation,
+            // ShallNotBeUsed = 99,
+            100 => CauseCode::RemotePeerNotResponding,
+            101 => CauseCode::CollisionWithNetworkInitiatedRequest,
+            102 => CauseCode::UnableToPageUEDueToSuspension,
+            103 => CauseCode::ConditionalIEMissing,
+            104 => CauseCode::APNRestrictiontypeIncompatibleWithCurrentlyActivePDNConnection,
+            105 => CauseCode::InvalidOverallLengthOfTheTriggeredResponseMessageAndAPiggybackedInitialMessage,
+            106 => CauseCode::DataForwardingNotSupported,
+            107 => CauseCode::InvalidReplyFromRemotePeer,
+            108 => CauseCode::FallbackToGTPv1,
+            109 => CauseCode::InvalidPeer,
+            110 => CauseCode::TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress,
+            111 => CauseCode::ModificationsNotLimitedToS1UBearers,
+            112 => CauseCode::RequestRejectedForAPMIPv6Reason,
+            113 => CauseCode::APNCongestion,
+            114 => CauseCode::BearerHandlingNotSupported,
+            115 => CauseCode::UEAlreadyReAttached,
+            116 => CauseCode::MultiplePDNConnectionsForAGivenAPNNotAllowed,
+            117 => CauseCode::TargetAccessRestrictedForTheSubscriber,
+            // ShallNotBeUsed = 118,
+            119 => CauseCode::MMESGSNRefusesDueToVPLMNPolicy,
+            120 => CauseCode::CTPCEntityCongestion,
+            121 => CauseCode::LateOverlappingRequest,
+            122 => CauseCode::TimedOutRequest,
+            123 => CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving,
+            124 => CauseCode::RelocationFailureDueToNASMessageRedirection,
+            125 => CauseCode::UENotAuthorisedByOCSOrExternalAAAServer,
+            126 => CauseCode::MultipleAccessesToAPDNConnectionNotAllowed,
+            127 => CauseCode::RequestRejectedDueToUECapability,
+            128 => CauseCode::S1UPathFailure,
+            129 => CauseCode::_5GCNotAllowed,
+            _ => CauseCode::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<CauseCode> for u8
+{
+    fn from(value: CauseCode) -> Self {
         match value {
-            // 0 => Ok(CauseCode::ReservedShallNotBeSent),
+            // ReservedShallNotBeSent = 0,
             // Request / Initial Message
-            1 => Ok(CauseCode::Reserved),
-            2 => Ok(CauseCode::LocalDetach),
-            3 => Ok(CauseCode::CompleteDetach),
-            4 => Ok(CauseCode::RATChangedfrom3GPPtoNon3GPP),
-            5 => Ok(CauseCode::ISRDeactivation),
-            6 => Ok(CauseCode::ErrorIndicationReceivedfromRNCeNodeBS4SGSNMME),
-            7 => Ok(CauseCode::IMSIDetachOnly),
-            8 => Ok(CauseCode::ReactivationRequest),
-            9 => Ok(CauseCode::PDNReconnectiontothisAPNDisallowed),
-            10 => Ok(CauseCode::AccessChangedfroNon3GPPto3GPP),
-            11 => Ok(CauseCode::PDNConnectionInactivityTimerExpires),
-            12 => Ok(CauseCode::PGWNotResponding),
-            13 => Ok(CauseCode::NetworkFailure),
-            14 => Ok(CauseCode::QoSParameterMismatch),
-            15 => Ok(CauseCode::EPSto5GSMobility),
+            CauseCode::Reserved => 1,
+            CauseCode::LocalDetach => 2,
+            CauseCode::CompleteDetach => 3,
+            CauseCode::RATChangedfrom3GPPtoNon3GPP => 4,
+            CauseCode::ISRDeactivation => 5,
+            CauseCode::ErrorIndicationReceivedfromRNCeNodeBS4SGSNMME => 6,
+            CauseCode::IMSIDetachOnly => 7,
+            CauseCode::ReactivationRequest => 8,
+            CauseCode::PDNReconnectiontothisAPNDisallowed => 9,
+            CauseCode::AccessChangedfroNon3GPPto3GPP => 10,
+            CauseCode::PDNConnectionInactivityTimerExpires => 11,
+            CauseCode::PGWNotResponding => 12,
+            CauseCode::NetworkFailure => 13,
+            CauseCode::QoSParameterMismatch => 14,
+            CauseCode::EPSto5GSMobility => 15,
             // Acceptance in a Response / Triggered message
-            16 => Ok(CauseCode::RequestAccepted),
-            17 => Ok(CauseCode::RequestAcceptedPartially),
-            18 => Ok(CauseCode::NewPDNTypeDuetoNetworkPerformance),
-            19 => Ok(CauseCode::NewPDNTypeDuetoSingleAddressBearerOnly),
+            CauseCode::RequestAccepted => 16,
+            CauseCode::RequestAcceptedPartially => 17,
+            CauseCode::NewPDNTypeDuetoNetworkPerformance => 18,
+            CauseCode::NewPDNTypeDuetoSingleAddressBearerOnly => 19,
             // Rejection in a Response / Triggered message
-            64 => Ok(CauseCode::ContextNotFound),
-            65 => Ok(CauseCode::InvalidMessageFormat),
-            66 => Ok(CauseCode::VersionNotSupportedByNextPeer),
-            67 => Ok(CauseCode::InvalidLength),
-            68 => Ok(CauseCode::ServiceNotSupported),
-            69 => Ok(CauseCode::MandatoryIEIncorrect),
-            70 => Ok(CauseCode::MandatoryIEMissing),
-            // 71 => Ok(CauseCode::ShallNotBeUsed),
-            72 => Ok(CauseCode::SystemFailure),
-            73 => Ok(CauseCode::NoResourcesAvailable),
-            74 => Ok(CauseCode::SemanticErrorintheTFTOperation),
-            75 => Ok(CauseCode::SyntacticErrorintheTFTOperation),
-            76 => Ok(CauseCode::SemanticErrorsinthePacketFilters),
-            77 => Ok(CauseCode::SyntacticErrorsinthePacketFilters),
-            78 => Ok(CauseCode::MissingOrUnknownAPN),
-            // 79 => Ok(CauseCode::ShallNotBeUsed),
-            80 => Ok(CauseCode::GREKeyNotFound),
-            81 => Ok(CauseCode::RelocationFailure),
-            82 => Ok(CauseCode::DeniedinRAT),
-            83 => Ok(CauseCode::PreferredPDNTypeNotSupported),
-            84 => Ok(CauseCode::AllDynamicAddressesAreOccupied),
-            85 => Ok(CauseCode::UEContextWithoutTFTAlreadyActivated),
-            86 => Ok(CauseCode::ProtocolTypeNotSupported),
-            87 => Ok(CauseCode::UENotResponding),
-            88 => Ok(CauseCode::UERefuses),
-            89 => Ok(CauseCode::ServiceDenied),
-            90 => Ok(CauseCode::UnableToPageUE),
-            91 => Ok(CauseCode::NoMemoryAvailable),
-            92 => Ok(CauseCode::UserAuthenticationFailed),
-            93 => Ok(CauseCode::APNAccessDeniedNoSubscription),
-            94 => Ok(CauseCode::RequestRejectedReasonNotSpecified),
-            95 => Ok(CauseCode::PTMSISignatureMismatch),
-            96 => Ok(CauseCode::IMSIorIMEINotKnown),
-            97 => Ok(CauseCode::SemanticErrorintheTADOperation),
-            98 => Ok(CauseCode::SyntacticErrorintheTADOperation),
-            // 99 => Ok(CauseCode::ShallNotBeUsed),
-            100 => Ok(CauseCode::RemotePeerNotResponding),
-            101 => Ok(CauseCode::CollisionWithNetworkInitiatedRequest),
-            102 => Ok(CauseCode::UnableToPageUEDueToSuspension),
-            103 => Ok(CauseCode::ConditionalIEMissing),
-            104 => Ok(CauseCode::APNRestrictiontypeIncompatibleWithCurrentlyActivePDNConnection),
-            105 => Ok(CauseCode::InvalidOverallLengthOfTheTriggeredResponseMessageAndAPiggybackedInitialMessage),
-            106 => Ok(CauseCode::DataForwardingNotSupported),
-            107 => Ok(CauseCode::InvalidReplyFromRemotePeer),
-            108 => Ok(CauseCode::FallbackToGTPv1),
-            109 => Ok(CauseCode::InvalidPeer),
-            110 => Ok(CauseCode::TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress),
-            111 => Ok(CauseCode::ModificationsNotLimitedToS1UBearers),
-            112 => Ok(CauseCode::RequestRejectedForAPMIPv6Reason),
-            113 => Ok(CauseCode::APNCongestion),
-            114 => Ok(CauseCode::BearerHandlingNotSupported),
-            115 => Ok(CauseCode::UEAlreadyReAttached),
-            116 => Ok(CauseCode::MultiplePDNConnectionsForAGivenAPNNotAllowed),
-            117 => Ok(CauseCode::TargetAccessRestrictedForTheSubscriber),
-            // 118 => Ok(CauseCode::ShallNotBeUsed),
-            119 => Ok(CauseCode::MMESGSNRefusesDueToVPLMNPolicy),
-            120 => Ok(CauseCode::CTPCEntityCongestion),
-            121 => Ok(CauseCode::LateOverlappingRequest),
-            122 => Ok(CauseCode::TimedOutRequest),
-            123 => Ok(CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving),
-            124 => Ok(CauseCode::RelocationFailureDueToNASMessageRedirection),
-            125 => Ok(CauseCode::UENotAuthorisedByOCSOrExternalAAAServer),
-            126 => Ok(CauseCode::MultipleAccessesToAPDNConnectionNotAllowed),
-            127 => Ok(CauseCode::RequestRejectedDueToUECapability),
-            128 => Ok(CauseCode::S1UPathFailure),
-            129 => Ok(CauseCode::_5GCNotAllowed),
-            _ => Err(format!("Unsupported Cause Code ({})", value))
+            CauseCode::ContextNotFound => 64,
+            CauseCode::InvalidMessageFormat => 65,
+            CauseCode::VersionNotSupportedByNextPeer => 66,
+            CauseCode::InvalidLength => 67,
+            CauseCode::ServiceNotSupported => 68,
+            CauseCode::MandatoryIEIncorrect => 69,
+            CauseCode::MandatoryIEMissing => 70,
+            // ShallNotBeUsed = 71,
+            CauseCode::SystemFailure => 72,
+            CauseCode::NoResourcesAvailable => 73,
+            CauseCode::SemanticErrorintheTFTOperation => 74,
+            CauseCode::SyntacticErrorintheTFTOperation => 75,
+            CauseCode::SemanticErrorsinthePacketFilters => 76,
+            CauseCode::SyntacticErrorsinthePacketFilters => 77,
+            CauseCode::MissingOrUnknownAPN => 78,
+            // ShallNotBeUsed = 79,
+            CauseCode::GREKeyNotFound => 80,
+            CauseCode::RelocationFailure => 81,
+            CauseCode::DeniedinRAT => 82,
+            CauseCode::PreferredPDNTypeNotSupported => 83,
+            CauseCode::AllDynamicAddressesAreOccupied => 84,
+            CauseCode::UEContextWithoutTFTAlreadyActivated => 85,
+            CauseCode::ProtocolTypeNotSupported => 86,
+            CauseCode::UENotResponding => 87,
+            CauseCode::UERefuses => 88,
+            CauseCode::ServiceDenied => 89,
+            CauseCode::UnableToPageUE => 90,
+            CauseCode::NoMemoryAvailable => 91,
+            CauseCode::UserAuthenticationFailed => 92,
+            CauseCode::APNAccessDeniedNoSubscription => 93,
+            CauseCode::RequestRejectedReasonNotSpecified => 94,
+            CauseCode::PTMSISignatureMismatch => 95,
+            CauseCode::IMSIorIMEINotKnown => 96,
+            CauseCode::SemanticErrorintheTADOperation => 97,
+            CauseCode::SyntacticErrorintheTADOperation => 98,
+            // ShallNotBeUsed = 99,
+            CauseCode::RemotePeerNotResponding => 100,
+            CauseCode::CollisionWithNetworkInitiatedRequest => 101,
+            CauseCode::UnableToPageUEDueToSuspension => 102,
+            CauseCode::ConditionalIEMissing => 103,
+            CauseCode::APNRestrictiontypeIncompatibleWithCurrentlyActivePDNConnection => 104,
+            CauseCode::InvalidOverallLengthOfTheTriggeredResponseMessageAndAPiggybackedInitialMessage => 105,
+            CauseCode::DataForwardingNotSupported => 106,
+            CauseCode::InvalidReplyFromRemotePeer => 107,
+            CauseCode::FallbackToGTPv1 => 108,
+            CauseCode::InvalidPeer => 109,
+            CauseCode::TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress => 110,
+            CauseCode::ModificationsNotLimitedToS1UBearers => 111,
+            CauseCode::RequestRejectedForAPMIPv6Reason => 112,
+            CauseCode::APNCongestion => 113,
+            CauseCode::BearerHandlingNotSupported => 114,
+            CauseCode::UEAlreadyReAttached => 115,
+            CauseCode::MultiplePDNConnectionsForAGivenAPNNotAllowed => 116,
+            CauseCode::TargetAccessRestrictedForTheSubscriber => 117,
+            // ShallNotBeUsed = 118,
+            CauseCode::MMESGSNRefusesDueToVPLMNPolicy => 119,
+            CauseCode::CTPCEntityCongestion => 120,
+            CauseCode::LateOverlappingRequest => 121,
+            CauseCode::TimedOutRequest => 122,
+            CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving => 123,
+            CauseCode::RelocationFailureDueToNASMessageRedirection => 124,
+            CauseCode::UENotAuthorisedByOCSOrExternalAAAServer => 125,
+            CauseCode::MultipleAccessesToAPDNConnectionNotAllowed => 126,
+            CauseCode::RequestRejectedDueToUECapability => 127,
+            CauseCode::S1UPathFailure => 128,
+            CauseCode::_5GCNotAllowed => 129,
+            CauseCode::Other(value) => value,
+        }
+    }
+}
+
+impl CauseCode {
+    pub fn is_acceptance(&self) -> bool {
+        // Cause values 16 to 63 indicate acceptance in a response message (TS 29.274 Table 8.4-1)
+        let value = u8::from(*self);
+
+        (16..=63).contains(&value)
+    }
+
+    pub fn is_rejection(&self) -> bool {
+        // Cause values 64 and above indicate rejection in a response message (TS 29.274 Table 8.4-1)
+        u8::from(*self) >= 64
+    }
+
+    pub fn is_temporary_rejection(&self) -> bool {
+        // Rejections caused by a transient condition (e.g. congestion or a procedure in progress) where the request
+        // may succeed if it is sent again later
+        match self {
+            CauseCode::NoResourcesAvailable |
+            CauseCode::NoMemoryAvailable |
+            CauseCode::RemotePeerNotResponding |
+            CauseCode::CollisionWithNetworkInitiatedRequest |
+            CauseCode::UnableToPageUEDueToSuspension |
+            CauseCode::TemporarilyRejectedDueToHandoverOrTAUorRAUProcedureInProgress |
+            CauseCode::APNCongestion |
+            CauseCode::CTPCEntityCongestion |
+            CauseCode::UEIsTemporarilyNotReachableDueToPowerSaving => true,
+            _ => false,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -272,6 +408,18 @@ impl InformationElement {
         }
     }
 
+    pub fn is_accepted(&self) -> bool {
+        self.cause_code.is_acceptance()
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.cause_code.is_rejection()
+    }
+
+    pub fn is_temporarily_rejected(&self) -> bool {
+        self.cause_code.is_temporary_rejection()
+    }
+
     pub fn parseflags(buffer: &[u8]) -> Result<(bool, bool, CauseSource), String> {
         let cause_source = CauseSource::try_from(buffer[0] & 0x1)?;
        
@@ -290,6 +438,10 @@ impl InformationElement {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -304,49 +456,51 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(cause_code) = CauseCode::try_from(buffer[pos]) {
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let cause_code = CauseCode::from(buffer[pos]);
+        pos = pos + 1;
+        if let Ok((pdn_connection_ie_error, bearer_context_ie_error, cause_source)) = Self::parseflags(&buffer[pos..]) {
             pos = pos + 1;
-            if let Ok((pdn_connection_ie_error, bearer_context_ie_error, cause_source)) = Self::parseflags(&buffer[pos..]) {
-                pos = pos + 1;
-                let offending_ie: Option<(InformationElementType, u8)>;
-
-                match length {
-                    2 => offending_ie = None,
-                    6 => {
-                        if let Ok(offending_ie_type) = InformationElementType::try_from(buffer[pos]) {
-                            pos = pos + 1;
-
-                            let _offending_ie_length = NetworkEndian::read_u16(&buffer[pos..pos+2]);
-                            pos = pos + 2;
-                            
-                            let offending_ie_instance = buffer[pos] & 0xF;
-                            // pos = pos + 1;
-
-                            offending_ie = Some((offending_ie_type, offending_ie_instance));
-                        }
-                        else {
-                            return None
-                        }
+            let offending_ie: Option<(InformationElementType, u8)>;
+
+            match length {
+                2 => offending_ie = None,
+                6 => {
+                    if let Ok(offending_ie_type) = InformationElementType::try_from(buffer[pos]) {
+                        pos = pos + 1;
+
+                        let _offending_ie_length = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+                        pos = pos + 2;
+                        
+                        let offending_ie_instance = buffer[pos] & 0xF;
+                        // pos = pos + 1;
+
+                        offending_ie = Some((offending_ie_type, offending_ie_instance));
+                    }
+                    else {
+                        return None
                     }
-                    _ => return None,
                 }
+                _ => return None,
+            }
 
 
-                Some(
-                    (
-                        InformationElement {
-                            cause_code,
-                            cause_source,
-                            bearer_context_ie_error,
-                            pdn_connection_ie_error,
-                            offending_ie,
-                            instance,
-                        },
-                        (length + 4) as usize
-                    )
+            Some(
+                (
+                    InformationElement {
+                        cause_code,
+                        cause_source,
+                        bearer_context_ie_error,
+                        pdn_connection_ie_error,
+                        offending_ie,
+                        instance,
+                    },
+                    (length + 4) as usize
                 )
-            }
-            else { None }
+            )
         }
         else { None }
     }
@@ -371,11 +525,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        let mut length = 4+2;
+    fn payload_length(&self) -> u16 {
+        let mut length = 2;
 
         if let Some(_) = self.offending_ie {
             length = length + 4;
@@ -392,15 +543,14 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.cause_code as u8;
+        buffer[pos] = u8::from(self.cause_code);
         pos = pos + 1;
 
         buffer[pos] = self.generateflags();
@@ -421,6 +571,21 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Cause", format!("{:?} ({})", self.cause_code, u8::from(self.cause_code)));
+            d.field("Cause Source", format!("{:?}", self.cause_source));
+            d.flag("Bearer Context IE Error (BCE)", self.bearer_context_ie_error);
+            d.flag("PDN Connection IE Error (PCE)", self.pdn_connection_ie_error);
+
+            if let Some((ie_type, instance)) = self.offending_ie {
+                d.field("Offending IE", format!("{:?} ({}), Instance: {}", ie_type, ie_type as u8, instance));
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -444,7 +609,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::Cause as u8,
             0, 6, // Length
             0, // Spare
-            CauseCode::NoResourcesAvailable as u8,
+            u8::from(CauseCode::NoResourcesAvailable),
             0b00000100,
             InformationElementType::BearerContext as u8,
             0, 0,
@@ -466,7 +631,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::Cause as u8,
             0, 2, // Length
             0, // Spare
-            CauseCode::RequestAccepted as u8,
+            u8::from(CauseCode::RequestAccepted),
             0b00000000,
         ]);
     }
@@ -481,7 +646,7 @@ mod tests {
             None,
             0).unwrap();
 
-        assert_eq!(ie.length(), 2+4);
+        assert_eq!(ie.wire_length(), 2+4);
     }
 
     #[test]
@@ -502,7 +667,7 @@ mod tests {
         let ie_bytes = [InformationElementType::Cause as u8,
             0, 6, // Length
             0, // Spare
-            CauseCode::NoResourcesAvailable as u8,
+            u8::from(CauseCode::NoResourcesAvailable),
             0b00000100,
             InformationElementType::BearerContext as u8,
             0, 0,
@@ -522,4 +687,60 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_is_acceptance() {
+        assert!(CauseCode::RequestAccepted.is_acceptance());
+        assert!(CauseCode::NewPDNTypeDuetoSingleAddressBearerOnly.is_acceptance());
+        assert!(!CauseCode::ContextNotFound.is_acceptance());
+        assert!(!CauseCode::LocalDetach.is_acceptance());
+    }
+
+    #[test]
+    fn test_is_rejection() {
+        assert!(CauseCode::ContextNotFound.is_rejection());
+        assert!(CauseCode::Other(200).is_rejection());
+        assert!(!CauseCode::RequestAccepted.is_rejection());
+        // Causes sent in requests are neither
+        assert!(!CauseCode::LocalDetach.is_rejection());
+        assert!(!CauseCode::LocalDetach.is_acceptance());
+
+        assert!(CauseCode::APNCongestion.is_temporary_rejection());
+        assert!(CauseCode::NoResourcesAvailable.is_temporary_rejection());
+        assert!(!CauseCode::MissingOrUnknownAPN.is_temporary_rejection());
+        assert!(!CauseCode::RequestAccepted.is_temporary_rejection());
+
+        let ie = InformationElement::new(CauseCode::APNCongestion, CauseSource::LocalNode, false, false, None, 0).unwrap();
+        assert!(ie.is_rejected());
+        assert!(ie.is_temporarily_rejected());
+        assert!(!ie.is_accepted());
+
+        let ie = InformationElement::new(CauseCode::RequestAcceptedPartially, CauseSource::LocalNode, false, false, None, 0).unwrap();
+        assert!(ie.is_accepted());
+        assert!(!ie.is_rejected());
+        assert!(!ie.is_temporarily_rejected());
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            255, // Cause
+            0, // Spare and flags
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.cause_code, CauseCode::Other(255));
+            assert!(!ie.cause_code.is_acceptance());
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], ie_bytes);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Action {
+    StopReporting = 0,
+    StartReportingCgiSai = 1,
+    StartReportingRai = 2,
+    StartReportingTai = 3,
+    StartReportingEcgi = 4,
+    StartReportingCgiSaiAndRai = 5,
+    StartReportingTaiAndEcgi = 6,
+    StartReportingMacroEnbIdAndExtendedMacroEnbId = 7,
+    StartReportingTaiMacroEnbIdAndExtendedMacroEnbId = 8,
+}
+
+impl TryFrom<u8> for Action
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Action::StopReporting),
+            1 => Ok(Action::StartReportingCgiSai),
+            2 => Ok(Action::StartReportingRai),
+            3 => Ok(Action::StartReportingTai),
+            4 => Ok(Action::StartReportingEcgi),
+            5 => Ok(Action::StartReportingCgiSaiAndRai),
+            6 => Ok(Action::StartReportingTaiAndEcgi),
+            7 => Ok(Action::StartReportingMacroEnbIdAndExtendedMacroEnbId),
+            8 => Ok(Action::StartReportingTaiMacroEnbIdAndExtendedMacroEnbId),
+            _ => Err(format!("Unsupported Change Reporting Action ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------| 
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (131)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Action                                                        |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub action: Action
+}
+
+impl InformationElement {
+    pub fn new(action: Action, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    action,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+        
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        if let Ok(action) = Action::try_from(buffer[pos]){
+            // pos = pos + 1;
+
+            Some(
+                (
+                    InformationElement {
+                        action,
+                        instance,
+                    },
+                    (length + 4) as usize
+                )
+            )
+        }
+        else {
+            None
+        }
+        
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::ChangeReportingAction
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+        
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.action as u8;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Action", format!("{:?} ({})", self.action, self.action as u8));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(Action::StartReportingTaiAndEcgi, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::ChangeReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            Action::StartReportingTaiAndEcgi as u8, // Action
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(Action::StartReportingTaiAndEcgi, 0).unwrap();
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(Action::StartReportingTaiAndEcgi, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::ChangeReportingAction as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::ChangeReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            Action::StartReportingTaiAndEcgi as u8, // Action
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.action, Action::StartReportingTaiAndEcgi);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_spare_action() {
+        let ie_bytes = [InformationElementType::ChangeReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            9, // Action (spare)
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,183 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (148)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                                                 | CMI   |
+                |---------------------------------------------------------------|
+
+        CSG Membership Indication. CMI is set if the UE is a member of the CSG of a hybrid cell (TS 29.274 8.64)
+    */
+
+    instance: u8,
+    pub member: bool,
+}
+
+impl InformationElement {
+    pub fn new(member: bool, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    member,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let member = buffer[pos] & 0b1 == 0b1;
+
+        Some(
+            (
+                InformationElement {
+                    member,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::CMI
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.member as u8;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.flag("CSG Member (CMI)", self.member);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(true, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::CMI as u8,
+            0, 1, // Length
+            0, // Spare
+            1, // CMI
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(false, 0).unwrap();
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::CMI as u8,
+            0, 1, // Length
+            0, // Spare
+            0b1111_1110, // Spare and CMI
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert!(!ie.member);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
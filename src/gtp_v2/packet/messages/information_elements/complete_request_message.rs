@@ -0,0 +1,236 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryFrom;
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum CompleteRequestMessageType {
+    CompleteAttachRequestMessage = 0,
+    CompleteTAURequestMessage = 1,
+}
+
+impl TryFrom<u8> for CompleteRequestMessageType
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(CompleteRequestMessageType::CompleteAttachRequestMessage),
+            1 => Ok(CompleteRequestMessageType::CompleteTAURequestMessage),
+            _ => Err(format!("Unsupported Complete Request Message Type ({})", value))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (116)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Complete Request Message Type                                 |
+        6 -> n  | Complete Request Message                                      |
+                |---------------------------------------------------------------|
+
+        The Complete Request Message is the NAS message (e.g. the TAU Request) received from the UE
+    */
+
+    instance: u8,
+    pub complete_request_message_type: CompleteRequestMessageType,
+    pub complete_request_message: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(complete_request_message_type: CompleteRequestMessageType, complete_request_message: &[u8], instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if complete_request_message.len() > 0xFFFF - 1 {
+            Err(format!("Complete Request Message is too long {}", complete_request_message.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    complete_request_message_type,
+                    complete_request_message: complete_request_message.to_vec(),
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if let Ok(complete_request_message_type) = CompleteRequestMessageType::try_from(buffer[pos]) {
+            pos = pos + 1;
+
+            Some(
+                (
+                    InformationElement {
+                        complete_request_message_type,
+                        complete_request_message: buffer[pos..(length + 4) as usize].to_vec(),
+                        instance,
+                    },
+                    (length + 4) as usize
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::CompleteRequestMessage
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1+(self.complete_request_message.len() as u16)
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.complete_request_message_type as u8;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.complete_request_message.len()].copy_from_slice(&self.complete_request_message);
+        pos = pos + self.complete_request_message.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Complete Request Message Type", format!("{:?} ({})", self.complete_request_message_type, self.complete_request_message_type as u8));
+            d.hex("Complete Request Message", &self.complete_request_message);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(CompleteRequestMessageType::CompleteTAURequestMessage, &[0x17, 0x48, 0x01], 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::CompleteRequestMessage as u8,
+            0, 4, // Length
+            0, // Spare
+            CompleteRequestMessageType::CompleteTAURequestMessage as u8,
+            0x17, 0x48, 0x01, // Complete Request Message
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(CompleteRequestMessageType::CompleteTAURequestMessage, &[0x17, 0x48, 0x01], 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 8);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(CompleteRequestMessageType::CompleteAttachRequestMessage, &[], 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::CompleteRequestMessage as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::CompleteRequestMessage as u8,
+            0, 4, // Length
+            0, // Spare
+            CompleteRequestMessageType::CompleteTAURequestMessage as u8,
+            0x17, 0x48, 0x01, // Complete Request Message
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.complete_request_message_type, CompleteRequestMessageType::CompleteTAURequestMessage);
+            assert_eq!(ie.complete_request_message, vec![0x17, 0x48, 0x01]);
+            assert_eq!(pos, 8);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Unknown Complete Request Message Type
+        let mut ie_bytes = ie_bytes;
+        ie_bytes[4] = 2;
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,197 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+// The CSG ID is 27 bits (TS 23.003 4.7)
+pub const MAX_CSG_ID: u32 = 0x7FF_FFFF;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (147)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                                 | CSG ID                |
+        6       | CSG ID                                                        |
+        7       | CSG ID                                                        |
+        8       | CSG ID                                                        |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub csg_id: u32,
+}
+
+impl InformationElement {
+    pub fn new(csg_id: u32, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if csg_id > MAX_CSG_ID {
+            Err(format!("CSG ID is > {:#x} {:#x}", MAX_CSG_ID, csg_id))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    csg_id,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 4 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let csg_id = NetworkEndian::read_u32(&buffer[pos..pos+4]) & MAX_CSG_ID;
+
+        Some(
+            (
+                InformationElement {
+                    csg_id,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::CSGID
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        4
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.csg_id & MAX_CSG_ID);
+        pos = pos + 4;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("CSG ID", format!("{:#09x}", self.csg_id));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(0x7654321, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::CSGID as u8,
+            0, 4, // Length
+            0, // Spare
+            0x07, 0x65, 0x43, 0x21, // CSG ID
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(0x7654321, 0).unwrap();
+        assert_eq!(ie.wire_length(), 8);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new(0x8000000, 0).is_err());
+        assert!(InformationElement::new(1, 0x10).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::CSGID as u8,
+            0, 4, // Length
+            0, // Spare
+            0xFF, 0x65, 0x43, 0x21, // Spare and CSG ID
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.csg_id, 0x7654321);
+            assert_eq!(pos, 8);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..7]).is_none());
+    }
+}
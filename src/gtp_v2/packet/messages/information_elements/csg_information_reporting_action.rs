@@ -0,0 +1,239 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+// CSG Information Reporting Action bits (TS 29.274 8.76)
+pub const UCICSG: u8 = 0b0000_0001; // Report User CSG Info when the UE enters/leaves/accesses a CSG Cell
+pub const UCISHC: u8 = 0b0000_0010; // Report User CSG Info when the UE enters/leaves/accesses a Subscribed Hybrid Cell
+pub const UCIUHC: u8 = 0b0000_0100; // Report User CSG Info when the UE enters/leaves/accesses an Unsubscribed Hybrid Cell
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (146)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                                 | UCIUHC| UCISHC| UCICSG|
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub action: u8,
+}
+
+impl InformationElement {
+    pub fn new(action: u8, instance: u8) -> Result<Self, String> {
+        // action is a combination of UCICSG, UCISHC and UCIUHC. 0 stops reporting
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    action,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        // Any octets after the first are only present if explicitly specified and are ignored
+        let action = buffer[pos];
+
+        Some(
+            (
+                InformationElement {
+                    action,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn stop_reporting() -> Self {
+        InformationElement {
+            action: 0,
+            instance: 0,
+        }
+    }
+
+    pub fn reporting(&self, action: u8) -> bool {
+        self.action & action == action
+    }
+
+    pub fn ucicsg(&self) -> bool {
+        self.reporting(UCICSG)
+    }
+
+    pub fn ucishc(&self) -> bool {
+        self.reporting(UCISHC)
+    }
+
+    pub fn uciuhc(&self) -> bool {
+        self.reporting(UCIUHC)
+    }
+
+    pub fn is_stop_reporting(&self) -> bool {
+        self.action & (UCICSG | UCISHC | UCIUHC) == 0
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::CSGInformationReportingAction
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.action;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Action", format!("{:#04x}", self.action));
+            d.flag("CSG Cell (UCICSG)", self.ucicsg());
+            d.flag("Subscribed Hybrid Cell (UCISHC)", self.ucishc());
+            d.flag("Unsubscribed Hybrid Cell (UCIUHC)", self.uciuhc());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(UCICSG | UCIUHC, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::CSGInformationReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0000_0101 // Action
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(UCICSG, 0).unwrap();
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(UCICSG, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::CSGInformationReportingAction as u8)
+    }
+
+    #[test]
+    fn test_actions() {
+        let ie = InformationElement::new(UCISHC, 0).unwrap();
+
+        assert!(!ie.ucicsg());
+        assert!(ie.ucishc());
+        assert!(!ie.uciuhc());
+        assert!(!ie.is_stop_reporting());
+
+        assert!(InformationElement::stop_reporting().is_stop_reporting());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::CSGInformationReportingAction as u8,
+            0, 1, // Length
+            0, // Spare
+            0b1111_0011, // Action
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert!(ie.ucicsg());
+            assert!(ie.ucishc());
+            assert!(!ie.uciuhc());
+            assert_eq!(pos, 5);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::time::Duration;
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (92)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Delay Value                                                   |
+                |---------------------------------------------------------------|
+
+        The Delay Value is an integer multiple of 50 milliseconds, or zero
+    */
+
+    instance: u8,
+    pub delay_value: u8,
+}
+
+pub const DELAY_VALUE_UNIT_MS: u64 = 50;
+
+impl InformationElement {
+    pub fn new(delay_value: u8, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    delay_value,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn from_duration(duration: Duration, instance: u8) -> Result<Self, String> {
+        // Rounded up to the next multiple of 50 milliseconds
+        let millis = duration.as_millis();
+        let delay_value = millis.div_ceil(DELAY_VALUE_UNIT_MS as u128);
+
+        if delay_value > 0xFF {
+            return Err(format!("Duration is too long for a Delay Value {:?}", duration));
+        }
+
+        Self::new(delay_value as u8, instance)
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.delay_value as u64 * DELAY_VALUE_UNIT_MS)
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    delay_value: buffer[pos],
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::DelayValue
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.delay_value;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Delay Value", format!("{} ({} ms)", self.delay_value, self.duration().as_millis()));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::from_duration(Duration::from_millis(1020), 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::DelayValue as u8,
+            0, 1, // Length
+            0, // Spare
+            21, // Delay Value (1050 ms)
+        ]);
+    }
+
+    #[test]
+    fn test_from_duration() {
+        assert_eq!(InformationElement::from_duration(Duration::from_millis(0), 0).unwrap().delay_value, 0);
+        assert_eq!(InformationElement::from_duration(Duration::from_millis(12750), 0).unwrap().delay_value, 255);
+        assert!(InformationElement::from_duration(Duration::from_millis(12751), 0).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(1, 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(1, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::DelayValue as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::DelayValue as u8,
+            0, 1, // Length
+            0, // Spare
+            4, // Delay Value
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 5);
+            assert_eq!(ie.delay_value, 4);
+            assert_eq!(ie.duration(), Duration::from_millis(200));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
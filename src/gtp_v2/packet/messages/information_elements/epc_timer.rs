@@ -0,0 +1,203 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::time::Duration;
+
+use super::timer::Timer;
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (156)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Timer Unit            | Timer Value                           |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub timer: Timer,
+}
+
+impl InformationElement {
+    pub fn new(timer: Timer, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    timer,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn from_duration(duration: Duration, instance: u8) -> Result<Self, String> {
+        Self::new(Timer::from_duration(duration)?, instance)
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        // None if the timer is infinite
+        self.timer.duration()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    timer: Timer::decode(buffer[pos]),
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::EPCTimer
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.timer.encode();
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Timer Unit", format!("{:?} ({})", self.timer.unit, self.timer.unit as u8));
+            d.field("Timer Value", self.timer.value());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+    use crate::gtp_v2::packet::messages::information_elements::timer::TimerUnit;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::from_duration(Duration::from_secs(300), 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::EPCTimer as u8,
+            0, 1, // Length
+            0, // Spare
+            0b001_00101, // 5 minutes
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(Timer::infinite(), 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(Timer::infinite(), 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::EPCTimer as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::EPCTimer as u8,
+            0, 1, // Length
+            0, // Spare
+            0b111_00000, // Infinite
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 5);
+            assert_eq!(ie.timer.unit, TimerUnit::Infinite);
+            assert_eq!(ie.duration(), None);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
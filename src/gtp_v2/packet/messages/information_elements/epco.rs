@@ -0,0 +1,204 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::pco::ProtocolConfigurationOptions;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (197)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | Extended Protocol Configuration Options                       |
+                |---------------------------------------------------------------|
+
+        The ePCO is coded as in TS 24.301 9.9.4.26 starting from octet 4. This is the same container as the PCO
+        but it can be longer than 253 octets.
+    */
+
+    instance: u8,
+    pub pco: ProtocolConfigurationOptions,
+}
+
+impl InformationElement {
+    pub fn new(pco: ProtocolConfigurationOptions, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if pco.length() > 0xFFFF - 4 {
+            Err(format!("Extended Protocol Configuration Options are too long {}", pco.length()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    pco,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        let pco = ProtocolConfigurationOptions::parse(&buffer[pos..(length as usize + 4)])?;
+
+        Some(
+            (
+                InformationElement {
+                    pco,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::EPCO
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.pco.length() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.pco.generate(&mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.pco.dissect(d);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::pco;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    const IE_BYTES: [u8; 12] = [InformationElementType::EPCO as u8,
+        0, 7, // Length
+        0, // Spare
+        0x80, // Ext and PPP
+        0x00, 0x0D, 0x00, // DNS Server IPv4 Address Request
+        0x00, 0x10, 0x00, // IPv4 Link MTU Request
+        0x00, // This is cut off by the tests where needed
+    ];
+
+    fn new_epco() -> InformationElement {
+        let mut pco = ProtocolConfigurationOptions::new();
+
+        pco.push(pco::DNS_SERVER_IPV4_ADDRESS, &[]).unwrap();
+        pco.push(pco::IPV4_LINK_MTU, &[]).unwrap();
+
+        InformationElement::new(pco, 0).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_epco().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], IE_BYTES[..11]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_epco().wire_length(), 11);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new(ProtocolConfigurationOptions::new(), 0x10).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&IE_BYTES) {
+            assert_eq!(ie, new_epco());
+            assert_eq!(pos, 11);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&IE_BYTES[..10]).is_none());
+
+        // The last option runs past the end of the IE
+        let mut ie_bytes = IE_BYTES;
+        ie_bytes[2] = 8;
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,391 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+use super::trace_information::MAX_TRACE_ID;
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use super::user_location_information::PLMN;
+
+use core::net::IpAddr;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// The lists are given a length octet each
+pub const MAX_LIST_LENGTH: usize = 0xFF;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (205)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | MCC digit 2                   | MCC digit 1                   |
+        6       | MNC digit 3                   | MCC digit 3                   |
+        7       | MNC digit 2                   | MNC digit 1                   |
+        8-10    | Trace ID                                                      |
+        11      | Length of Triggering Events                                   |
+        12-m    | Triggering Events                                             |
+        m+1     | Length of List of NE Types                                    |
+        m+2-n   | List of NE Types                                              |
+        n+1     | Session Trace Depth                                           |
+        n+2     | Length of List of Interfaces                                  |
+        n+3-p   | List of Interfaces                                            |
+        p+1     | Length of IP Address of Trace Collection Entity               |
+        p+2-q   | IP Address of Trace Collection Entity                         |
+                |---------------------------------------------------------------|
+
+        The same as Trace Information except the lists aren't a fixed length, so the NE types and interfaces of
+        5GS (e.g. AMF and SMF, N26) can be traced. The contents are coded as per TS 32.422.
+    */
+
+    instance: u8,
+    pub plmn: PLMN,
+    trace_id: u32,
+    pub triggering_events: Vec<u8>,
+    pub ne_types: Vec<u8>,
+    pub session_trace_depth: u8,
+    pub interfaces: Vec<u8>,
+    pub trace_collection_entity: IpAddr,
+}
+
+impl InformationElement {
+    pub fn new(
+        plmn: PLMN,
+        trace_id: u32,
+        triggering_events: Vec<u8>,
+        ne_types: Vec<u8>,
+        session_trace_depth: u8,
+        interfaces: Vec<u8>,
+        trace_collection_entity: IpAddr,
+        instance: u8
+    ) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if trace_id > MAX_TRACE_ID {
+            Err(format!("Trace ID is > 0xFFFFFF {}", trace_id))
+        }
+        else if triggering_events.len() > MAX_LIST_LENGTH || ne_types.len() > MAX_LIST_LENGTH || interfaces.len() > MAX_LIST_LENGTH {
+            Err(format!("Triggering Events, List of NE Types and List of Interfaces must be <= {} octets", MAX_LIST_LENGTH))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    plmn,
+                    trace_id,
+                    triggering_events,
+                    ne_types,
+                    session_trace_depth,
+                    interfaces,
+                    trace_collection_entity,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 6 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        // Nothing past the end of the IE is read
+        let end = length as usize + 4;
+
+        let (plmn, plmn_pos) = PLMN::parse(&buffer[pos..end])?;
+        pos = pos + plmn_pos;
+
+        let trace_id = uint::parse_u24(&buffer[pos..])?;
+        pos = pos + 3;
+
+        let (triggering_events, list_pos) = parse_list(&buffer[pos..end])?;
+        pos = pos + list_pos;
+
+        let (ne_types, list_pos) = parse_list(&buffer[pos..end])?;
+        pos = pos + list_pos;
+
+        let session_trace_depth = *buffer[pos..end].first()?;
+        pos = pos + 1;
+
+        let (interfaces, list_pos) = parse_list(&buffer[pos..end])?;
+        pos = pos + list_pos;
+
+        let (trace_collection_entity, list_pos) = parse_list(&buffer[pos..end])?;
+        let trace_collection_entity = address::parse(&trace_collection_entity)?;
+        pos = pos + list_pos;
+
+        if pos != end {
+            // Anything after the Trace Collection Entity isn't part of the IE
+            return None
+        }
+
+        Some(
+            (
+                InformationElement {
+                    plmn,
+                    trace_id,
+                    triggering_events,
+                    ne_types,
+                    session_trace_depth,
+                    interfaces,
+                    trace_collection_entity,
+                    instance,
+                },
+                end
+            )
+        )
+    }
+
+    pub fn trace_id(&self) -> u32 {
+        self.trace_id
+    }
+
+    pub fn set_trace_id(&mut self, trace_id: u32) -> Result<u32, String> {
+        if trace_id > MAX_TRACE_ID {
+            Err(format!("Trace ID is > 0xFFFFFF {}", trace_id))
+        }
+        else {
+            self.trace_id = trace_id;
+            Ok(self.trace_id)
+        }
+    }
+}
+
+fn parse_list(buffer: &[u8]) -> Option<(Vec<u8>, usize)> {
+    // A length octet followed by that many octets
+    let length = *buffer.first()? as usize;
+
+    Some((buffer.get(1..1 + length)?.to_vec(), 1 + length))
+}
+
+fn generate_list(list: &[u8], buffer: &mut[u8]) -> usize {
+    buffer[0] = list.len() as u8;
+    buffer[1..1 + list.len()].copy_from_slice(list);
+
+    1 + list.len()
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::ExtendedTraceInformation
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.plmn.length() + 3 +
+            1 + self.triggering_events.len() as u16 +
+            1 + self.ne_types.len() as u16 +
+            1 +
+            1 + self.interfaces.len() as u16 +
+            1 + address::length(self.trace_collection_entity)
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        uint::generate_u24_lenient(self.trace_id, &mut buffer[pos..]);
+        pos = pos + 3;
+
+        pos = pos + generate_list(&self.triggering_events, &mut buffer[pos..]);
+        pos = pos + generate_list(&self.ne_types, &mut buffer[pos..]);
+
+        buffer[pos] = self.session_trace_depth;
+        pos = pos + 1;
+
+        pos = pos + generate_list(&self.interfaces, &mut buffer[pos..]);
+
+        buffer[pos] = address::length(self.trace_collection_entity) as u8;
+        pos = pos + 1;
+
+        pos = pos + address::generate(self.trace_collection_entity, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("Trace ID", format!("{:#08x}", self.trace_id));
+            d.hex("Triggering Events", &self.triggering_events);
+            d.hex("List of NE Types", &self.ne_types);
+            d.field("Session Trace Depth", self.session_trace_depth);
+            d.hex("List of Interfaces", &self.interfaces);
+            d.field("IP Address of Trace Collection Entity", self.trace_collection_entity);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::net::{Ipv4Addr, Ipv6Addr};
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+    use crate::gtp_v2::packet::messages::information_elements::trace_information::MEDIUM;
+
+    use alloc::vec;
+
+    fn new_ie(trace_collection_entity: IpAddr) -> InformationElement {
+        InformationElement::new(
+            PLMN::new(505, 99, true).unwrap(),
+            0x123456,
+            vec![0x01, 0x02, 0x03],
+            vec![0x80, 0x01, 0x02],
+            MEDIUM,
+            vec![0xFF; 4],
+            trace_collection_entity,
+            0
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let plmn = PLMN::new(505, 99, true).unwrap();
+        let tce = IpAddr::V4(Ipv4Addr::new(10,0,0,1));
+
+        assert!(InformationElement::new(plmn, 0x1000000, vec![], vec![], MEDIUM, vec![], tce, 0).is_err());
+        assert!(InformationElement::new(plmn, 0, vec![0; 256], vec![], MEDIUM, vec![], tce, 0).is_err());
+        assert!(InformationElement::new(plmn, 0, vec![], vec![], MEDIUM, vec![], tce, 0x10).is_err());
+        assert!(InformationElement::new(plmn, 0, vec![], vec![], MEDIUM, vec![0; 255], tce, 0xF).is_ok());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::ExtendedTraceInformation as u8,
+            0, 25, // Length
+            0, // Spare
+            0x05, 0x95, 0x90, // PLMN
+            0x12, 0x34, 0x56, // Trace ID
+            3, 0x01, 0x02, 0x03, // Triggering Events
+            3, 0x80, 0x01, 0x02, // List of NE Types
+            MEDIUM, // Session Trace Depth
+            4, 0xFF, 0xFF, 0xFF, 0xFF, // List of Interfaces
+            4, 10, 0, 0, 1, // IP Address of Trace Collection Entity
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+        assert_eq!(ie.wire_length(), 4+25);
+
+        let ie = new_ie(IpAddr::V6(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0, 0, 0, 1)));
+        assert_eq!(ie.wire_length(), 4+37);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::ExtendedTraceInformation as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::ExtendedTraceInformation as u8,
+            0, 30, // Length
+            2, // Spare and Instance
+            0x05, 0x95, 0x90, // PLMN
+            0x12, 0x34, 0x56, // Trace ID
+            1, 0x01, // Triggering Events
+            0, // List of NE Types
+            MEDIUM, // Session Trace Depth
+            2, 0xAA, 0xBB, // List of Interfaces
+            16, 0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0, 0, 0, 0, 0, 0, 0, 1, // IP Address of Trace Collection Entity
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.instance(), 2);
+            assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
+            assert_eq!(ie.trace_id(), 0x123456);
+            assert_eq!(ie.triggering_events, [0x01]);
+            assert!(ie.ne_types.is_empty());
+            assert_eq!(ie.session_trace_depth, MEDIUM);
+            assert_eq!(ie.interfaces, [0xAA, 0xBB]);
+            assert_eq!(ie.trace_collection_entity, IpAddr::V6(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0, 0, 0, 1)));
+            assert_eq!(pos, 34);
+        }
+        else {
+            assert!(false);
+        }
+
+        // A list runs past the end of the IE
+        let mut bad = ie_bytes;
+        bad[14] = 30;
+        assert!(InformationElement::parse(&bad).is_none());
+
+        // The Trace Collection Entity address must be IPv4 or IPv6
+        let mut bad = ie_bytes;
+        bad[17] = 8;
+        assert!(InformationElement::parse(&bad).is_none());
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..32]).is_none());
+    }
+}
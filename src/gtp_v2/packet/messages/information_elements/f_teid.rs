@@ -1,110 +1,165 @@
-extern crate rand;
-
 use byteorder::{ByteOrder, NetworkEndian};
 
-use std::convert::TryFrom;
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum InterfaceType {
-    S1UENodeBGtpU = 0,
-    S1USgwGtpU = 1,
-    S12RncGtpU = 2,
-    S12SgwGtpU = 3,
-    S5S8SgwGtpU = 4,
-    S5S8PgwGtpU = 5,
-    S5S8SgwGtpC = 6,
-    S5S8PgwGtpC = 7,
-    S5S8SgwPmipV6 = 8,
-    S5S8PgwPmipV6 = 9,
-    S11MmeGtpC = 10,
-    S11S4SgwGtpC = 11,
-    S10N26MmeGtpC = 12,
-    S3MmeGtpC = 13,
-    S3SgsnGtpC = 14,
-    S4SgsnGtpU = 15,
-    S4SgwGtpU = 16,
-    S4SgsnGtpC = 17,
-    S16SgsnGtpC = 18,
-    ENodeBGtpUForDlDataForwarding = 19,
-    ENodeBGtpUForUlDataForwarding = 20,
-    RncGtpUForDataForwarding = 21,
-    SgsnGtpUForDataForwarding = 22,
-    SgwUpfGtpUForDlDataForwarding = 23,
-    SmMbmsGwGtpC = 24,
-    SnMbmsGwGtpC = 25,
-    SmMmeGtpC = 26,
-    SnSgsnGtpC = 27,
-    SgwGtpUForUlDataForwarding = 28,
-    SnSgsnGtpU = 29,
-    S2bEPdgGtpC = 30,
-    S2bUEPdgGtpU = 31,
-    S2bPgwGtpC = 32,
-    S2bUPgwGtpU = 33,
-    S2aTwanGtpU = 34,
-    S2aTwanGtpC = 35,
-    S2aPgwGtpC = 36,
-    S2aPgwGtpU = 37,
-    S11MmeGtpU = 38,
-    S11SgwGtpU = 39,
-    N26AmfGtpC = 40,
+    S1UENodeBGtpU,
+    S1USgwGtpU,
+    S12RncGtpU,
+    S12SgwGtpU,
+    S5S8SgwGtpU,
+    S5S8PgwGtpU,
+    S5S8SgwGtpC,
+    S5S8PgwGtpC,
+    S5S8SgwPmipV6,
+    S5S8PgwPmipV6,
+    S11MmeGtpC,
+    S11S4SgwGtpC,
+    S10N26MmeGtpC,
+    S3MmeGtpC,
+    S3SgsnGtpC,
+    S4SgsnGtpU,
+    S4SgwGtpU,
+    S4SgsnGtpC,
+    S16SgsnGtpC,
+    ENodeBGtpUForDlDataForwarding,
+    ENodeBGtpUForUlDataForwarding,
+    RncGtpUForDataForwarding,
+    SgsnGtpUForDataForwarding,
+    SgwUpfGtpUForDlDataForwarding,
+    SmMbmsGwGtpC,
+    SnMbmsGwGtpC,
+    SmMmeGtpC,
+    SnSgsnGtpC,
+    SgwGtpUForUlDataForwarding,
+    SnSgsnGtpU,
+    S2bEPdgGtpC,
+    S2bUEPdgGtpU,
+    S2bPgwGtpC,
+    S2bUPgwGtpU,
+    S2aTwanGtpU,
+    S2aTwanGtpC,
+    S2aPgwGtpC,
+    S2aPgwGtpU,
+    S11MmeGtpU,
+    S11SgwGtpU,
+    N26AmfGtpC,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for InterfaceType
+impl From<u8> for InterfaceType
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            0 => InterfaceType::S1UENodeBGtpU,
+            1 => InterfaceType::S1USgwGtpU,
+            2 => InterfaceType::S12RncGtpU,
+            3 => InterfaceType::S12SgwGtpU,
+            4 => InterfaceType::S5S8SgwGtpU,
+            5 => InterfaceType::S5S8PgwGtpU,
+            6 => InterfaceType::S5S8SgwGtpC,
+            7 => InterfaceType::S5S8PgwGtpC,
+            8 => InterfaceType::S5S8SgwPmipV6,
+            9 => InterfaceType::S5S8PgwPmipV6,
+            10 => InterfaceType::S11MmeGtpC,
+            11 => InterfaceType::S11S4SgwGtpC,
+            12 => InterfaceType::S10N26MmeGtpC,
+            13 => InterfaceType::S3MmeGtpC,
+            14 => InterfaceType::S3SgsnGtpC,
+            15 => InterfaceType::S4SgsnGtpU,
+            16 => InterfaceType::S4SgwGtpU,
+            17 => InterfaceType::S4SgsnGtpC,
+            18 => InterfaceType::S16SgsnGtpC,
+            19 => InterfaceType::ENodeBGtpUForDlDataForwarding,
+            20 => InterfaceType::ENodeBGtpUForUlDataForwarding,
+            21 => InterfaceType::RncGtpUForDataForwarding,
+            22 => InterfaceType::SgsnGtpUForDataForwarding,
+            23 => InterfaceType::SgwUpfGtpUForDlDataForwarding,
+            24 => InterfaceType::SmMbmsGwGtpC,
+            25 => InterfaceType::SnMbmsGwGtpC,
+            26 => InterfaceType::SmMmeGtpC,
+            27 => InterfaceType::SnSgsnGtpC,
+            28 => InterfaceType::SgwGtpUForUlDataForwarding,
+            29 => InterfaceType::SnSgsnGtpU,
+            30 => InterfaceType::S2bEPdgGtpC,
+            31 => InterfaceType::S2bUEPdgGtpU,
+            32 => InterfaceType::S2bPgwGtpC,
+            33 => InterfaceType::S2bUPgwGtpU,
+            34 => InterfaceType::S2aTwanGtpU,
+            35 => InterfaceType::S2aTwanGtpC,
+            36 => InterfaceType::S2aPgwGtpC,
+            37 => InterfaceType::S2aPgwGtpU,
+            38 => InterfaceType::S11MmeGtpU,
+            39 => InterfaceType::S11SgwGtpU,
+            40 => InterfaceType::N26AmfGtpC,
+            _ => InterfaceType::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<InterfaceType> for u8
+{
+    fn from(value: InterfaceType) -> Self {
         match value {
-            0 => Ok(InterfaceType::S1USgwGtpU),
-            1 => Ok(InterfaceType::S1USgwGtpU),
-            2 => Ok(InterfaceType::S12RncGtpU),
-            3 => Ok(InterfaceType::S12SgwGtpU),
-            4 => Ok(InterfaceType::S5S8SgwGtpU),
-            5 => Ok(InterfaceType::S5S8PgwGtpU),
-            6 => Ok(InterfaceType::S5S8SgwGtpC),
-            7 => Ok(InterfaceType::S5S8PgwGtpC),
-            8 => Ok(InterfaceType::S5S8SgwPmipV6),
-            9 => Ok(InterfaceType::S5S8PgwPmipV6),
-            10 => Ok(InterfaceType::S11MmeGtpC),
-            11 => Ok(InterfaceType::S11S4SgwGtpC),
-            12 => Ok(InterfaceType::S10N26MmeGtpC),
-            13 => Ok(InterfaceType::S3MmeGtpC),
-            14 => Ok(InterfaceType::S3SgsnGtpC),
-            15 => Ok(InterfaceType::S4SgsnGtpU),
-            16 => Ok(InterfaceType::S4SgwGtpU),
-            17 => Ok(InterfaceType::S4SgsnGtpC),
-            18 => Ok(InterfaceType::S16SgsnGtpC),
-            19 => Ok(InterfaceType::ENodeBGtpUForDlDataForwarding),
-            20 => Ok(InterfaceType::ENodeBGtpUForUlDataForwarding),
-            21 => Ok(InterfaceType::RncGtpUForDataForwarding),
-            22 => Ok(InterfaceType::SgsnGtpUForDataForwarding),
-            23 => Ok(InterfaceType::SgwUpfGtpUForDlDataForwarding),
-            24 => Ok(InterfaceType::SmMbmsGwGtpC),
-            25 => Ok(InterfaceType::SnMbmsGwGtpC),
-            26 => Ok(InterfaceType::SmMmeGtpC),
-            27 => Ok(InterfaceType::SnSgsnGtpC),
-            28 => Ok(InterfaceType::SgwGtpUForUlDataForwarding),
-            29 => Ok(InterfaceType::SnSgsnGtpU),
-            30 => Ok(InterfaceType::S2bEPdgGtpC),
-            31 => Ok(InterfaceType::S2bUEPdgGtpU),
-            32 => Ok(InterfaceType::S2bPgwGtpC),
-            33 => Ok(InterfaceType::S2bUPgwGtpU),
-            34 => Ok(InterfaceType::S2aTwanGtpU),
-            35 => Ok(InterfaceType::S2aTwanGtpC),
-            36 => Ok(InterfaceType::S2aPgwGtpC),
-            37 => Ok(InterfaceType::S2aPgwGtpU),
-            38 => Ok(InterfaceType::S11MmeGtpU),
-            39 => Ok(InterfaceType::S11SgwGtpU),
-            40 => Ok(InterfaceType::N26AmfGtpC),
-            _ => Err(format!("Unsupported Interface type ({})", value))
+            InterfaceType::S1UENodeBGtpU => 0,
+            InterfaceType::S1USgwGtpU => 1,
+            InterfaceType::S12RncGtpU => 2,
+            InterfaceType::S12SgwGtpU => 3,
+            InterfaceType::S5S8SgwGtpU => 4,
+            InterfaceType::S5S8PgwGtpU => 5,
+            InterfaceType::S5S8SgwGtpC => 6,
+            InterfaceType::S5S8PgwGtpC => 7,
+            InterfaceType::S5S8SgwPmipV6 => 8,
+            InterfaceType::S5S8PgwPmipV6 => 9,
+            InterfaceType::S11MmeGtpC => 10,
+            InterfaceType::S11S4SgwGtpC => 11,
+            InterfaceType::S10N26MmeGtpC => 12,
+            InterfaceType::S3MmeGtpC => 13,
+            InterfaceType::S3SgsnGtpC => 14,
+            InterfaceType::S4SgsnGtpU => 15,
+            InterfaceType::S4SgwGtpU => 16,
+            InterfaceType::S4SgsnGtpC => 17,
+            InterfaceType::S16SgsnGtpC => 18,
+            InterfaceType::ENodeBGtpUForDlDataForwarding => 19,
+            InterfaceType::ENodeBGtpUForUlDataForwarding => 20,
+            InterfaceType::RncGtpUForDataForwarding => 21,
+            InterfaceType::SgsnGtpUForDataForwarding => 22,
+            InterfaceType::SgwUpfGtpUForDlDataForwarding => 23,
+            InterfaceType::SmMbmsGwGtpC => 24,
+            InterfaceType::SnMbmsGwGtpC => 25,
+            InterfaceType::SmMmeGtpC => 26,
+            InterfaceType::SnSgsnGtpC => 27,
+            InterfaceType::SgwGtpUForUlDataForwarding => 28,
+            InterfaceType::SnSgsnGtpU => 29,
+            InterfaceType::S2bEPdgGtpC => 30,
+            InterfaceType::S2bUEPdgGtpU => 31,
+            InterfaceType::S2bPgwGtpC => 32,
+            InterfaceType::S2bUPgwGtpU => 33,
+            InterfaceType::S2aTwanGtpU => 34,
+            InterfaceType::S2aTwanGtpC => 35,
+            InterfaceType::S2aPgwGtpC => 36,
+            InterfaceType::S2aPgwGtpU => 37,
+            InterfaceType::S11MmeGtpU => 38,
+            InterfaceType::S11SgwGtpU => 39,
+            InterfaceType::N26AmfGtpC => 40,
+            InterfaceType::Other(value) => value,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -162,6 +217,10 @@ impl InformationElement {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if u8::from(interface_type) > 0x3F {
+            // Interface Type is only 6 bits
+            Err(format!("Interface Type is > 0x3F {}", u8::from(interface_type)))
+        }
         else {
             Ok(
                 InformationElement {
@@ -175,6 +234,7 @@ impl InformationElement {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn new_rand(
         interface_type: InterfaceType, 
         ipv4_address: Option<Ipv4Addr>, 
@@ -217,6 +277,10 @@ impl InformationElement {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -231,16 +295,25 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 5 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         let (v4, v6) = Self::parse_flags(buffer[pos]);
-        let interface_type = buffer[pos] & 0xF;
+        let interface_type = buffer[pos] & 0x3F;
         pos = pos + 1;
 
+        if (length as usize) < 5 + 4 * v4 as usize + 16 * v6 as usize {
+            // The flags indicate addresses that don't fit in the IE
+            return None
+        }
+
         let teid = NetworkEndian::read_u32(&buffer[pos..pos+4]);
         pos = pos + 4;
 
         let ipv4_address: Option<Ipv4Addr> = 
             if v4 == 1 {
-                Some(Ipv4Addr::new(buffer[pos], buffer[pos+1], buffer[pos+2], buffer[pos+3]))
+                address::parse_ipv4(&buffer[pos..])
             }
             else {
                 None
@@ -250,16 +323,7 @@ impl InformationElement {
 
         let ipv6_address: Option<Ipv6Addr> = 
             if v6 == 1 {
-                Some(Ipv6Addr::new(
-                    NetworkEndian::read_u16(&buffer[pos..pos+2]),
-                    NetworkEndian::read_u16(&buffer[pos+2..pos+4]),
-                    NetworkEndian::read_u16(&buffer[pos+4..pos+6]),
-                    NetworkEndian::read_u16(&buffer[pos+6..pos+8]),
-                    NetworkEndian::read_u16(&buffer[pos+8..pos+10]),
-                    NetworkEndian::read_u16(&buffer[pos+10..pos+12]),
-                    NetworkEndian::read_u16(&buffer[pos+12..pos+14]),
-                    NetworkEndian::read_u16(&buffer[pos+14..pos+16]),
-                ))
+                address::parse_ipv6(&buffer[pos..])
             }
             else {
                 None
@@ -267,23 +331,20 @@ impl InformationElement {
 
             if ipv6_address.is_some() {/*pos = pos + 16*/}
 
-        if let Ok(interface_type) = InterfaceType::try_from(interface_type) {
-            Some(
-                (
-                    InformationElement {
-                        interface_type,
-                        teid,
-                        ipv4_address,
-                        ipv6_address,
-                        instance,
-                    },
-                    (length + 4) as usize
-                )
+        let interface_type = InterfaceType::from(interface_type);
+
+        Some(
+            (
+                InformationElement {
+                    interface_type,
+                    teid,
+                    ipv4_address,
+                    ipv6_address,
+                    instance,
+                },
+                (length + 4) as usize
             )
-        }
-        else {
-            None
-        }
+        )
         
     }
 }
@@ -307,26 +368,10 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        let mut length = 4; // IE Headers
-
-        length = length 
-            + 1 // Flags and Interface type
-            + 4; // TEID
-
-        if let Some(_) = self.ipv4_address {
-            length = length + 4
-        }
-
-        if let Some(_) = self.ipv6_address
-        {
-            length = length + 16
-        }
-
-        length
+    fn payload_length(&self) -> u16 {
+        1 // Flags and Interface type
+            + 4 // TEID
+            + address::dual_stack_length(self.ipv4_address, self.ipv6_address)
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -337,35 +382,39 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.generate_flags() | (self.interface_type as u8);
+        buffer[pos] = self.generate_flags() | (u8::from(self.interface_type));
         pos = pos + 1;
 
         NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.teid);
         pos = pos + 4;
 
-        if let Some(a) = self.ipv4_address {
-            for o in a.octets().iter() {
-                buffer[pos] = *o;
-                pos = pos + 1;
-            }
-        }
+        pos = pos + address::generate_dual_stack(self.ipv4_address, self.ipv6_address, &mut buffer[pos..]);
 
-        if let Some(a) = self.ipv6_address {
-            for o in a.octets().iter() {
-                buffer[pos] = *o;
-                pos = pos + 1;
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Interface Type", format!("{:?} ({})", self.interface_type, u8::from(self.interface_type)));
+            d.field("TEID/GRE Key", format!("{:#010x}", self.teid));
+
+            if let Some(address) = self.ipv4_address {
+                d.field("IPv4 Address", address);
             }
-        }
 
-        pos
+            if let Some(address) = self.ipv6_address {
+                d.field("IPv6 Address", address);
+            }
+        });
     }
 }
 
@@ -376,6 +425,7 @@ mod tests {
     use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_new_rand() {
         let ie = InformationElement::new_rand(
             InterfaceType::S11MmeGtpC,
@@ -404,7 +454,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::FTEID as u8,
             0, 9, // Length
             0, // Spare
-            (0b1 << 7) | (0b0 << 6) | (InterfaceType::S11MmeGtpC as u8),
+            (0b1 << 7) | (0b0 << 6) | (u8::from(InterfaceType::S11MmeGtpC)),
             0x87, 0x65, 0x43, 0x21,
             10, 0, 0, 1
         ]);
@@ -420,7 +470,7 @@ mod tests {
             0
         ).unwrap();
 
-        assert_eq!(ie.length(), 9+4);
+        assert_eq!(ie.wire_length(), 9+4);
 
         let ie = InformationElement::new(
             InterfaceType::S11MmeGtpC,
@@ -430,7 +480,7 @@ mod tests {
             0
         ).unwrap();
             
-        assert_eq!(ie.length(), 25+4);
+        assert_eq!(ie.wire_length(), 25+4);
     }
 
     #[test]
@@ -451,7 +501,7 @@ mod tests {
         let ie_bytes = [InformationElementType::FTEID as u8,
             0, 25, // Length
             0, // Spare
-            (0b1 << 7) | (0b1 << 6) | (InterfaceType::S11MmeGtpC as u8),
+            (0b1 << 7) | (0b1 << 6) | (u8::from(InterfaceType::S11MmeGtpC)),
             0x87, 0x65, 0x43, 0x21,
             10, 0, 0, 1,
             0xFA, 0xDE, 
@@ -466,7 +516,7 @@ mod tests {
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
-            assert_eq!(ie.interface_type as u8, InterfaceType::S11MmeGtpC as u8);
+            assert_eq!(u8::from(ie.interface_type), u8::from(InterfaceType::S11MmeGtpC));
 
             if let Some(a) = ie.ipv4_address {
                 assert_eq!(a, Ipv4Addr::new(10,0,0,1));
@@ -486,4 +536,29 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::FTEID as u8,
+            0, 5, // Length
+            0, // Spare
+            0b0011_1111, // Flags and Interface Type
+            0x12, 0x34, 0x56, 0x78, // TEID
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.interface_type, InterfaceType::Other(0x3F));
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], ie_bytes);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Interface Type is only 6 bits
+        assert!(InformationElement::new(InterfaceType::Other(0x40), 0, None, None, 0).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+use super::bearer_qos::MAX_BITRATE_KBPS;
+
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (81)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Label (QCI)                                                   |
+        6-10    | Maximum bitrate for Uplink                                    |
+        11-15   | Maximum bitrate for Downlink                                  |
+        16-20   | Guaranteed bitrate for Uplink                                 |
+        21-25   | Guaranteed bitrate for Downlink                               |
+                |---------------------------------------------------------------|
+
+        Bitrates are in kbps and can use all 40 bits
+    */
+
+    instance: u8,
+    pub qci: u8,
+    max_ul_bitrate: u64,
+    max_dl_bitrate: u64,
+    guaranteed_ul_bitrate: u64,
+    guaranteed_dl_bitrate: u64,
+}
+
+impl InformationElement {
+    pub fn new(
+        qci: u8,
+        max_ul_bitrate: u64,
+        max_dl_bitrate: u64,
+        guaranteed_ul_bitrate: u64,
+        guaranteed_dl_bitrate: u64,
+        instance: u8,
+    ) -> Result<Self, String> {
+
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if max_ul_bitrate > MAX_BITRATE_KBPS {
+            Err(format!("Max UL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, max_ul_bitrate))
+        }
+        else if max_dl_bitrate > MAX_BITRATE_KBPS {
+            Err(format!("Max DL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, max_dl_bitrate))
+        }
+        else if guaranteed_ul_bitrate > MAX_BITRATE_KBPS {
+            Err(format!("Guaranteed UL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, guaranteed_ul_bitrate))
+        }
+        else if guaranteed_dl_bitrate > MAX_BITRATE_KBPS {
+            Err(format!("Guaranteed DL Bitrate is > {} kbps {}", MAX_BITRATE_KBPS, guaranteed_dl_bitrate))
+        }
+        else {
+            Ok(InformationElement {
+                qci,
+                max_ul_bitrate,
+                max_dl_bitrate,
+                guaranteed_ul_bitrate,
+                guaranteed_dl_bitrate,
+                instance,
+            })
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 21 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let qci = buffer[pos];
+        pos = pos + 1;
+
+        let max_ul_bitrate = uint::parse_u40(&buffer[pos..])?;
+        pos = pos + 5;
+
+        let max_dl_bitrate = uint::parse_u40(&buffer[pos..])?;
+        pos = pos + 5;
+
+        let guaranteed_ul_bitrate = uint::parse_u40(&buffer[pos..])?;
+        pos = pos + 5;
+
+        let guaranteed_dl_bitrate = uint::parse_u40(&buffer[pos..])?;
+        // pos = pos + 5;
+
+        Some(
+            (
+                InformationElement {
+                    qci,
+                    max_ul_bitrate,
+                    max_dl_bitrate,
+                    guaranteed_ul_bitrate,
+                    guaranteed_dl_bitrate,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn max_ul_bitrate(&self) -> u64 {
+        self.max_ul_bitrate
+    }
+    pub fn max_dl_bitrate(&self) -> u64 {
+        self.max_dl_bitrate
+    }
+    pub fn guaranteed_ul_bitrate(&self) -> u64 {
+        self.guaranteed_ul_bitrate
+    }
+    pub fn guaranteed_dl_bitrate(&self) -> u64 {
+        self.guaranteed_dl_bitrate
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::FlowQoS
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        21
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.qci;
+        pos = pos + 1;
+
+        pos = pos + uint::generate_u40_lenient(self.max_ul_bitrate, &mut buffer[pos..]);
+
+        pos = pos + uint::generate_u40_lenient(self.max_dl_bitrate, &mut buffer[pos..]);
+
+        pos = pos + uint::generate_u40_lenient(self.guaranteed_ul_bitrate, &mut buffer[pos..]);
+
+        pos = pos + uint::generate_u40_lenient(self.guaranteed_dl_bitrate, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("QCI", self.qci);
+            d.field("Maximum Bit Rate for Uplink", format!("{} kbps", self.max_ul_bitrate));
+            d.field("Maximum Bit Rate for Downlink", format!("{} kbps", self.max_dl_bitrate));
+            d.field("Guaranteed Bit Rate for Uplink", format!("{} kbps", self.guaranteed_ul_bitrate));
+            d.field("Guaranteed Bit Rate for Downlink", format!("{} kbps", self.guaranteed_dl_bitrate));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        if let Ok(ie) = InformationElement::new(1, 64, 128, 64, 128, 0) {
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], [InformationElementType::FlowQoS as u8,
+                0, 21, // Length
+                0, // Spare
+                1, // QCI
+                0x00, 0x00, 0x00, 0x00, 0x40,
+                0x00, 0x00, 0x00, 0x00, 0x80,
+                0x00, 0x00, 0x00, 0x00, 0x40,
+                0x00, 0x00, 0x00, 0x00, 0x80,
+            ]);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Bitrates above 10 Gbps are allowed
+        assert!(InformationElement::new(1, 20_000_000, 0, 0, 0, 0).is_ok());
+
+        assert!(InformationElement::new(1, MAX_BITRATE_KBPS + 1, 0, 0, 0, 0).is_err());
+        assert!(InformationElement::new(1, 0, MAX_BITRATE_KBPS + 1, 0, 0, 0).is_err());
+        assert!(InformationElement::new(1, 0, 0, MAX_BITRATE_KBPS + 1, 0, 0).is_err());
+        assert!(InformationElement::new(1, 0, 0, 0, MAX_BITRATE_KBPS + 1, 0).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        if let Ok(ie) = InformationElement::new(1, 64, 128, 64, 128, 0) {
+            assert_eq!(ie.wire_length(), 25);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_type() {
+        if let Ok(ie) = InformationElement::new(1, 64, 128, 64, 128, 0) {
+            assert_eq!(ie.information_element_type() as u8, InformationElementType::FlowQoS as u8);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::FlowQoS as u8,
+            0, 21, // Length
+            0, // Spare
+            1, // QCI
+            0x00, 0x00, 0x98, 0x96, 0x80,
+            0x00, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, 0x00, 0x00, 0x40,
+            0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 25);
+            assert_eq!(ie.qci, 1);
+            assert_eq!(ie.max_ul_bitrate(), 10_000_000);
+            assert_eq!(ie.max_dl_bitrate(), 128);
+            assert_eq!(ie.guaranteed_ul_bitrate(), 64);
+            assert_eq!(ie.guaranteed_dl_bitrate(), 0);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..24]).is_none());
+    }
+}
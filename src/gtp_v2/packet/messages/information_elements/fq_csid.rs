@@ -0,0 +1,350 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum NodeId {
+    IPv4(Ipv4Addr),
+    IPv6(Ipv6Addr),
+    /* The most significant 20 bits are MCC * 1000 + MNC and the least significant 12 bits are an
+    integer assigned by the operator */
+    Global(u32),
+}
+
+impl NodeId {
+    pub fn node_id_type(&self) -> u8 {
+        match self {
+            NodeId::IPv4(_) => 0,
+            NodeId::IPv6(_) => 1,
+            NodeId::Global(_) => 2,
+        }
+    }
+
+    pub fn new_global(mcc: u16, mnc: u16, id: u16) -> Result<Self, String> {
+        if mcc > 999 {
+            Err(format!("MCC is > 999 {}", mcc))
+        }
+        else if mnc > 999 {
+            Err(format!("MNC is > 999 {}", mnc))
+        }
+        else if id > 0xFFF {
+            Err(format!("Node ID is > 0xFFF {}", id))
+        }
+        else {
+            Ok(NodeId::Global(((mcc as u32 * 1000 + mnc as u32) << 12) | id as u32))
+        }
+    }
+
+    fn length(&self) -> u16 {
+        match self {
+            NodeId::IPv4(_) => 4,
+            NodeId::IPv6(_) => 16,
+            NodeId::Global(_) => 4,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (132)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Node-ID Type                  | Number of CSIDs = m           |
+        6 -> p  | Node-ID                                                       |
+        p+1     | First PDN Connection Set Identifier (CSID) (Octet 1)          |
+        p+2     | First PDN Connection Set Identifier (CSID) (Octet 2)          |
+        ...     | ...                                                           |
+        q+1     | mth PDN Connection Set Identifier (CSID) (Octet 1)            |
+        q+2     | mth PDN Connection Set Identifier (CSID) (Octet 2)            |
+                |---------------------------------------------------------------|
+
+        Node-ID is 4 octets for IPv4 (Type 0) and Global (Type 2) and 16 octets for IPv6 (Type 1)
+    */
+
+    instance: u8,
+    pub node_id: NodeId,
+    pub csids: Vec<u16>,
+}
+
+impl InformationElement {
+    pub fn new(node_id: NodeId, csids: Vec<u16>, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if csids.len() > 0xF {
+            Err(format!("Number of CSIDs is > 0xF {}", csids.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    node_id,
+                    csids,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let node_id_type = buffer[pos] >> 4;
+        let number_of_csids = (buffer[pos] & 0xF) as usize;
+        pos = pos + 1;
+
+        let node_id_length = match node_id_type {
+            1 => 16,
+            _ => 4,
+        };
+
+        if pos + node_id_length > (length + 4) as usize {
+            return None
+        }
+
+        let node_id = match node_id_type {
+            0 => {
+                let node_id = NodeId::IPv4(address::parse_ipv4(&buffer[pos..])?);
+                pos = pos + 4;
+                node_id
+            },
+            1 => {
+                let node_id = NodeId::IPv6(address::parse_ipv6(&buffer[pos..])?);
+                pos = pos + 16;
+                node_id
+            },
+            2 => {
+                let node_id = NodeId::Global(NetworkEndian::read_u32(&buffer[pos..pos+4]));
+                pos = pos + 4;
+                node_id
+            },
+            _ => return None
+        };
+
+        if pos + number_of_csids * 2 > (length + 4) as usize {
+            return None
+        }
+
+        let mut csids = Vec::new();
+
+        for _ in 0..number_of_csids {
+            csids.push(NetworkEndian::read_u16(&buffer[pos..pos+2]));
+            pos = pos + 2;
+        }
+
+        Some(
+            (
+                InformationElement {
+                    node_id,
+                    csids,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::FQCSID
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1 // Node-ID Type and Number of CSIDs
+            + self.node_id.length()
+            + (self.csids.len() * 2) as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = (self.node_id.node_id_type() << 4) | (self.csids.len() as u8 & 0xF);
+        pos = pos + 1;
+
+        match self.node_id {
+            NodeId::IPv4(a) => pos = pos + address::generate_ipv4(a, &mut buffer[pos..]),
+            NodeId::IPv6(a) => pos = pos + address::generate_ipv6(a, &mut buffer[pos..]),
+            NodeId::Global(v) => {
+                NetworkEndian::write_u32(&mut buffer[pos..pos+4], v);
+                pos = pos + 4;
+            },
+        }
+
+        for csid in self.csids.iter() {
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], *csid);
+            pos = pos + 2;
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            match self.node_id {
+                NodeId::IPv4(address) => d.field("Node-ID", address),
+                NodeId::IPv6(address) => d.field("Node-ID", address),
+                NodeId::Global(node_id) => d.field("Node-ID", format!("{:#010x}", node_id)),
+            }
+
+            for csid in &self.csids {
+                d.field("CSID", format!("{:#06x}", csid));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![1], 0).is_ok());
+        assert!(InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![1], 0x10).is_err());
+        assert!(InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![0; 16], 0).is_err());
+
+        assert_eq!(NodeId::new_global(505, 99, 0xABC).unwrap(), NodeId::Global((505099 << 12) | 0xABC));
+        assert!(NodeId::new_global(505, 99, 0x1000).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![0x1234, 0x5678], 1).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::FQCSID as u8,
+            0, 9, // Length
+            1, // Spare and Instance
+            (0 << 4) | 2, // Node-ID Type and Number of CSIDs
+            10, 0, 0, 1, // Node-ID
+            0x12, 0x34, // CSID 1
+            0x56, 0x78, // CSID 2
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![0x1234, 0x5678], 0).unwrap();
+        assert_eq!(ie.wire_length(), 9+4);
+
+        let ie = InformationElement::new(NodeId::IPv6(Ipv6Addr::new(0x2001,0xdb8,0,0,0,0,0,1)), vec![0x1234], 0).unwrap();
+        assert_eq!(ie.wire_length(), 19+4);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(NodeId::IPv4(Ipv4Addr::new(10,0,0,1)), vec![0x1234], 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::FQCSID as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::FQCSID as u8,
+            0, 19, // Length
+            1, // Spare and Instance
+            (1 << 4) | 1, // Node-ID Type and Number of CSIDs
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // Node-ID
+            0xAB, 0xCD, // CSID 1
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.node_id, NodeId::IPv6(Ipv6Addr::new(0x2001,0xdb8,0,0,0,0,0,1)));
+            assert_eq!(ie.csids, vec![0xABCD]);
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(pos, 23);
+        }
+        else {
+            assert!(false);
+        }
+
+        let ie_bytes = [InformationElementType::FQCSID as u8,
+            0, 7, // Length
+            0, // Spare and Instance
+            (2 << 4) | 1, // Node-ID Type and Number of CSIDs
+            0x7B, 0x50, 0xBA, 0xBC, // Node-ID
+            0x00, 0x01, // CSID 1
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.node_id, NodeId::new_global(505, 99, 0xABC).unwrap());
+            assert_eq!(ie.csids, vec![1]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
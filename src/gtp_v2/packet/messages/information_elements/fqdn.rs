@@ -0,0 +1,274 @@
+extern crate ascii;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryInto;
+use ascii::{AsciiString, AsciiChar, ToAsciiChar};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (136)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | FQDN                                                          |
+                |---------------------------------------------------------------|
+
+        The FQDN is encoded as a sequence of labels as per TS 23.003 without the trailing zero
+        length label
+    */
+
+    instance: u8,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
+    pub fqdn: AsciiString
+}
+
+impl InformationElement {
+    pub fn new(fqdn: AsciiString, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if fqdn.len() == 0 {
+            Err("FQDN is empty".to_string())
+        }
+        else if fqdn.split(AsciiChar::Dot).any(|l| l.len() == 0 || l.len() > 63) {
+            // Each label must be between 1 and 63 octets long
+            Err(format!("FQDN has an invalid label {}", fqdn))
+        }
+        else {
+            Ok(InformationElement {
+                fqdn: fqdn,
+                instance: instance,
+            })
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let mut fqdn = AsciiString::new();
+
+        while pos < (length+4) as usize {
+            // Each label is encoded with the length first then label
+
+            let label_length = buffer[pos];
+            pos = pos + 1;
+
+            if label_length == 0 {
+                // Some implementations include the trailing zero length label. Just skip it
+                continue;
+            }
+
+            if pos + label_length as usize > (length+4) as usize {
+                // The label runs past the end of the IE
+                return None
+            }
+
+            for _ in 0..label_length {
+                if let Ok(ch) = buffer[pos].to_ascii_char() {
+                    fqdn.push(ch)
+                }
+                else {
+                    return None
+                }
+                pos = pos + 1;
+            }
+            fqdn.push(AsciiChar::Dot);
+        }
+
+        let _ = fqdn.pop(); // Pop off the last dot
+
+        Some(
+            (
+                InformationElement {
+                    fqdn,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::FQDN
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        ((self.fqdn.len()+1) as usize).try_into().unwrap()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        // Split the fqdn into labels
+
+        for l in self.fqdn.split(AsciiChar::Dot) {
+            let length = l.len();
+
+            buffer[pos] = length as u8;
+            pos = pos + 1;
+
+            for i in 0..length {
+                buffer[pos] = l[i] as u8;
+                pos = pos + 1;
+            }
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("FQDN", &self.fqdn);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(AsciiString::from_ascii("topon.s5.pgw.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0).is_ok());
+        assert!(InformationElement::new(AsciiString::from_ascii("topon.s5.pgw.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0x10).is_err());
+        assert!(InformationElement::new(AsciiString::from_ascii("").unwrap(), 0).is_err());
+        assert!(InformationElement::new(AsciiString::from_ascii("pgw..epc").unwrap(), 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(AsciiString::from_ascii("pgw1.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        let expected = [InformationElementType::FQDN as u8,
+            0, 39, // Length
+            0, // Spare
+            4, AsciiChar::p as u8, AsciiChar::g as u8, AsciiChar::w as u8, AsciiChar::_1 as u8,
+            3, AsciiChar::e as u8, AsciiChar::p as u8, AsciiChar::c as u8,
+            6, AsciiChar::m as u8, AsciiChar::n as u8, AsciiChar::c as u8, AsciiChar::_0 as u8,  AsciiChar::_9 as u8, AsciiChar::_9 as u8,
+            6, AsciiChar::m as u8, AsciiChar::c as u8, AsciiChar::c as u8, AsciiChar::_5 as u8,  AsciiChar::_0 as u8, AsciiChar::_5 as u8,
+            11, AsciiChar::_3 as u8, AsciiChar::g as u8, AsciiChar::p as u8, AsciiChar::p as u8, AsciiChar::n as u8, AsciiChar::e as u8,
+                AsciiChar::t as u8, AsciiChar::w as u8, AsciiChar::o as u8, AsciiChar::r as u8, AsciiChar::k as u8,
+            3, AsciiChar::o as u8, AsciiChar::r as u8, AsciiChar::g as u8,
+        ];
+
+        assert_eq!(buffer[..pos], expected[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(AsciiString::from_ascii("pgw1.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0).unwrap();
+        assert_eq!(ie.wire_length(), 39+4);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(AsciiString::from_ascii("pgw1.epc.mnc099.mcc505.3gppnetwork.org").unwrap(), 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::FQDN as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::FQDN as u8,
+            0, 10, // Length
+            2, // Spare and Instance
+            4, AsciiChar::p as u8, AsciiChar::g as u8, AsciiChar::w as u8, AsciiChar::_1 as u8,
+            3, AsciiChar::e as u8, AsciiChar::p as u8, AsciiChar::c as u8,
+            0, // Trailing zero length label
+            0, 0, 0, // Next IE
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.fqdn, AsciiString::from_ascii("pgw1.epc").unwrap());
+            assert_eq!(ie.instance(), 2);
+            assert_eq!(pos, 14);
+        }
+        else {
+            assert!(false);
+        }
+
+        let ie_bytes = [InformationElementType::FQDN as u8,
+            0, 5, // Length
+            0, // Spare and Instance
+            9, AsciiChar::p as u8, AsciiChar::g as u8, AsciiChar::w as u8, AsciiChar::_1 as u8, // Label length runs past the IE
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare and Instance
+            0xAB,
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,529 @@
+/* Parsing of grouped IEs i.e. IEs that contain other IEs (Bearer Context, PDN Connection, Overload Control
+Information, Load Control Information, Remote UE Context etc.)
+
+A grouped IE is parsed into a GroupedIe holding the embedded IEs in an IeCollection. The grouped IE implementation
+then takes the IEs it expects out of the collection by type and instance e.g.
+
+    let (mut group, pos) = GroupedIe::parse(buffer)?;
+    let eps_bearer_id: Option<ebi::InformationElement> = group.ies.take(0);
+    let f_teids: Vec<f_teid::InformationElement> = group.ies.take_all(0);
+*/
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryFrom;
+
+use super::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    LENGTH,
+    apn,
+    cause,
+    apn_restriction,
+    selection_mode,
+    ip_address,
+    ebi,
+    f_teid,
+    fqdn,
+    bearer_context,
+    bearer_qos,
+    ambr,
+    charging_characteristics,
+    charging_id,
+    remote_user_id,
+    remote_ue_ip_information,
+    private_extension,
+};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IeCollection {
+    ies: Vec<InformationElement>,
+}
+
+impl IeCollection {
+    pub fn new() -> Self {
+        IeCollection {
+            ies: Vec::new(),
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Self {
+        // Parses every IE in buffer. IEs that fail to parse are skipped
+        let mut ies = Vec::new();
+
+        let mut pos = 0;
+
+        while pos + 4 <= buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                ies.push(ie);
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        IeCollection {
+            ies
+        }
+    }
+
+    pub fn push(&mut self, ie: InformationElement) {
+        self.ies.push(ie);
+    }
+
+    pub fn len(&self) -> usize {
+        self.ies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ies.is_empty()
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, InformationElement> {
+        self.ies.iter()
+    }
+
+    pub fn find(&self, ie_type: InformationElementType, instance: u8) -> Option<&InformationElement> {
+        self.ies.iter().find(|ie| ie.information_element_type() == ie_type && ie.instance() == instance)
+    }
+
+    pub fn take<T: TryFrom<InformationElement, Error = InformationElement>>(&mut self, instance: u8) -> Option<T> {
+        // Removes the first IE of type T with this instance from the collection
+        let mut i = 0;
+
+        while i < self.ies.len() {
+            if self.ies[i].instance() == instance {
+                match T::try_from(self.ies.remove(i)) {
+                    Ok(ie) => return Some(ie),
+                    Err(ie) => self.ies.insert(i, ie),
+                }
+            }
+            i = i + 1;
+        }
+
+        None
+    }
+
+    pub fn take_all<T: TryFrom<InformationElement, Error = InformationElement>>(&mut self, instance: u8) -> Vec<T> {
+        // Removes every IE of type T with this instance from the collection
+        let mut taken = Vec::new();
+
+        while let Some(ie) = self.take(instance) {
+            taken.push(ie);
+        }
+
+        taken
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in &self.ies {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    pub fn length(&self) -> u16 {
+        let mut length = 0;
+
+        for ie in &self.ies {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GroupedIe {
+    pub ie_type: u8,
+    pub instance: u8,
+    pub ies: IeCollection,
+}
+
+impl GroupedIe {
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        // Parses the header of a grouped IE and the IEs embedded in it.
+        // Only the octets covered by the length of the grouped IE are parsed.
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        Some(
+            (
+                GroupedIe {
+                    ie_type,
+                    instance,
+                    ies: IeCollection::parse(&buffer[pos..(length + 4) as usize]),
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn generate_header(ie_type: InformationElementType, instance: u8, payload_length: u16, buffer: &mut[u8]) -> usize {
+        // Writes the header of a grouped IE. payload_length is the length of the embedded IEs, excluding the first 4 octets
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = ie_type as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], payload_length);
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = instance & 0xF;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl TryFrom<InformationElement> for apn::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::APN(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for apn_restriction::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::APNRestriction(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for selection_mode::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::SelectionMode(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for ip_address::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::IPAddress(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for ebi::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::EBI(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for f_teid::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::FTEID(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for fqdn::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::FQDN(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for cause::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::Cause(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for bearer_context::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::BearerContext(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for bearer_qos::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::BearerQoS(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for ambr::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::AMBR(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for charging_characteristics::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::ChargingCharacteristics(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for charging_id::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::ChargingID(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for remote_user_id::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::RemoteUserID(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for remote_ue_ip_information::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::RemoteUEIPInformation(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+impl TryFrom<InformationElement> for private_extension::InformationElement {
+    type Error = InformationElement;
+
+    fn try_from(ie: InformationElement) -> Result<Self, Self::Error> {
+        match ie {
+            InformationElement::PrivateExtension(ie) => Ok(ie),
+            _ => Err(ie)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+
+    #[test]
+    fn test_ie_collection_take() {
+        let ie_bytes = [
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            1, // Spare and Instance
+            6, // EPS Bearer ID
+            InformationElementType::ChargingID as u8,
+            0, 4, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, 0x78, // Charging ID
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            1, // Spare and Instance
+            7, // EPS Bearer ID
+        ];
+
+        let mut ies = IeCollection::parse(&ie_bytes);
+
+        assert_eq!(ies.len(), 4);
+        assert!(ies.find(InformationElementType::ChargingID, 0).is_some());
+        assert!(ies.find(InformationElementType::ChargingID, 1).is_none());
+
+        let ebi: Option<ebi::InformationElement> = ies.take(0);
+        assert_eq!(ebi.map(|ie| ie.eps_bearer_id), Some(5));
+
+        // Already taken
+        let ebi: Option<ebi::InformationElement> = ies.take(0);
+        assert!(ebi.is_none());
+
+        let ebis: Vec<ebi::InformationElement> = ies.take_all(1);
+        assert_eq!(ebis.iter().map(|ie| ie.eps_bearer_id).collect::<Vec<u8>>(), vec![6, 7]);
+
+        let charging_id: Option<charging_id::InformationElement> = ies.take(0);
+        assert_eq!(charging_id.map(|ie| ie.charging_id), Some(0x12345678));
+
+        assert!(ies.is_empty());
+    }
+
+    #[test]
+    fn test_ie_collection_private_extension() {
+        // Vendor specific data inside a grouped IE is kept
+        let ie_bytes = [
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::PrivateExtension as u8,
+            0, 3, // Length
+            0, // Spare
+            0x28, 0xAF, // Enterprise ID
+            0xAB, // Proprietary Value
+        ];
+
+        let mut ies = IeCollection::parse(&ie_bytes);
+
+        let mut buffer = [0; MTU];
+        let pos = ies.generate(&mut buffer);
+        assert_eq!(buffer[..pos], ie_bytes);
+
+        let ie: Option<private_extension::InformationElement> = ies.take(0);
+        assert_eq!(ie, Some(private_extension::InformationElement::new(10415, &[0xAB], 0).unwrap()));
+    }
+
+    #[test]
+    fn test_ie_collection_skips_malformed_ies() {
+        let ie_bytes = [
+            0, // Reserved IE type
+            0, 2, // Length
+            0, // Spare
+            0xAB, 0xCD,
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+        ];
+
+        let ies = IeCollection::parse(&ie_bytes);
+
+        assert_eq!(ies.len(), 1);
+        assert!(ies.find(InformationElementType::EBI, 0).is_some());
+    }
+
+    #[test]
+    fn test_grouped_ie_parse() {
+        let ie_bytes = [
+            InformationElementType::BearerContext as u8,
+            0, 5, // Length
+            2, // Spare and Instance
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            // The next IE is outside the grouped IE
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            6, // EPS Bearer ID
+        ];
+
+        if let Some((group, pos)) = GroupedIe::parse(&ie_bytes) {
+            assert_eq!(group.ie_type, InformationElementType::BearerContext as u8);
+            assert_eq!(group.instance, 2);
+            assert_eq!(group.ies.len(), 1);
+            assert_eq!(pos, 9);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated grouped IE
+        assert!(GroupedIe::parse(&ie_bytes[..6]).is_none());
+    }
+
+    #[test]
+    fn test_grouped_ie_generate() {
+        let mut buffer = [0; MTU];
+
+        let mut ies = IeCollection::new();
+        ies.push(InformationElement::EBI(ebi::InformationElement::new(5, 0).unwrap()));
+
+        let mut pos = GroupedIe::generate_header(InformationElementType::BearerContext, 1, ies.length(), &mut buffer);
+        pos = pos + ies.generate(&mut buffer[pos..]);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::BearerContext as u8,
+            0, 5, // Length
+            1, // Spare and Instance
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+        ]);
+    }
+}
@@ -0,0 +1,244 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use super::user_location_information::PLMN;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (117)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | MCC digit 2                   | MCC digit 1                   |
+        6       | MNC digit 3                   | MCC digit 3                   |
+        7       | MNC digit 2                   | MNC digit 1                   |
+        8       | MME Group ID (Octet 1)                                        |
+        9       | MME Group ID (Octet 2)                                        |
+        10      | MME Code                                                      |
+        11      | M-TMSI (Octet 1)                                              |
+        12      | M-TMSI (Octet 2)                                              |
+        13      | M-TMSI (Octet 3)                                              |
+        14      | M-TMSI (Octet 4)                                              |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub plmn: PLMN,
+    pub mme_group_id: u16,
+    pub mme_code: u8,
+    pub m_tmsi: u32,
+}
+
+impl InformationElement {
+    pub fn new(plmn: PLMN, mme_group_id: u16, mme_code: u8, m_tmsi: u32, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    plmn,
+                    mme_group_id,
+                    mme_code,
+                    m_tmsi,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if length < 10 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if let Some((plmn, plmn_pos)) = PLMN::parse(&buffer[pos..]) {
+            pos = pos + plmn_pos;
+
+            let mme_group_id = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+            pos = pos + 2;
+
+            let mme_code = buffer[pos];
+            pos = pos + 1;
+
+            let m_tmsi = NetworkEndian::read_u32(&buffer[pos..pos+4]);
+            // pos = pos + 4;
+
+            Some(
+                (
+                    InformationElement {
+                        plmn,
+                        mme_group_id,
+                        mme_code,
+                        m_tmsi,
+                        instance,
+                    },
+                    (length + 4) as usize
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::GUTI
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.plmn.length()+2+1+4
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.mme_group_id);
+        pos = pos + 2;
+
+        buffer[pos] = self.mme_code;
+        pos = pos + 1;
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.m_tmsi);
+        pos = pos + 4;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("MME Group ID", format!("{:#06x}", self.mme_group_id));
+            d.field("MME Code", format!("{:#04x}", self.mme_code));
+            d.field("M-TMSI", format!("{:#010x}", self.m_tmsi));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x8001, 0x02, 0xC0FFEE01, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::GUTI as u8,
+            0, 10, // Length
+            0, // Spare
+            0x05, 0xF5, 0x10, // MCC / MNC
+            0x80, 0x01, // MME Group ID
+            0x02, // MME Code
+            0xC0, 0xFF, 0xEE, 0x01, // M-TMSI
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x8001, 0x02, 0xC0FFEE01, 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 14);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(PLMN::new(505, 1, false).unwrap(), 0x8001, 0x02, 0xC0FFEE01, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::GUTI as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::GUTI as u8,
+            0, 10, // Length
+            0, // Spare
+            0x05, 0xF5, 0x10, // MCC / MNC
+            0x80, 0x01, // MME Group ID
+            0x02, // MME Code
+            0xC0, 0xFF, 0xEE, 0x01, // M-TMSI
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.plmn, PLMN::new(505, 1, false).unwrap());
+            assert_eq!(ie.mme_group_id, 0x8001);
+            assert_eq!(ie.mme_code, 0x02);
+            assert_eq!(ie.m_tmsi, 0xC0FFEE01);
+            assert_eq!(pos, 14);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated IE
+        assert!(InformationElement::parse(&ie_bytes[..10]).is_none());
+    }
+}
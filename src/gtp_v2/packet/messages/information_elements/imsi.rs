@@ -1,7 +1,21 @@
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::tbcd;
+
+use super::user_location_information::PLMN;
 
 use byteorder::{ByteOrder, NetworkEndian};
 
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -25,41 +39,45 @@ pub struct InformationElement {
     */
 
     instance: u8,
-    pub imsi: [u8; 15],
+    pub imsi: Vec<u8>,
 }
 
+pub const MIN_DIGITS: usize = 6;
+pub const MAX_DIGITS: usize = 15;
+
 impl InformationElement {
     pub fn new(imsi: &str, instance: u8) -> Result<Self, String> {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
-        else if imsi.len() != 15 {
-            Err("IMSI is not the correct number of digits".to_string())
+        else if imsi.len() < MIN_DIGITS || imsi.len() > MAX_DIGITS {
+            Err(format!("IMSI must be {} to {} digits {}", MIN_DIGITS, MAX_DIGITS, imsi))
         }
         else {
-            let mut parsed_imsi = [0; 15];
-
-            for (i, c) in imsi.chars().enumerate() {
-                let digit = c.to_string().parse::<u8>();
-    
-                let digit = match digit {
-                    Ok(n) => n,
-                    Err(_e) => {
-                        return Err("Could not parse IMSI".to_string())
-                    }
-                };
-    
-                parsed_imsi[i] = digit;
+            match tbcd::parse_digits(imsi) {
+                Ok(imsi) => {
+                    Ok(InformationElement {
+                        imsi,
+                        instance
+                    })
+                },
+                Err(e) => Err(format!("Could not parse IMSI {}: {}", imsi, e))
             }
-    
-            Ok(InformationElement {
-                imsi: parsed_imsi,
-                instance
-            })
         }
     }
 
+    pub fn starts_with_plmn(&self, plmn: &PLMN) -> bool {
+        // Whether the MCC and MNC of the IMSI are those of plmn i.e. the UE's home network is plmn
+        let prefix = format!("{}{}", plmn.mcc_string(), plmn.mnc_string());
+
+        self.to_string().starts_with(&prefix)
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -74,26 +92,20 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        let mut i = 0;
-
-        let mut imsi = [0u8; 15];
-
-        while pos < (length+4) as usize {
-
-            let first_digit = buffer[pos] & 0xF;
-            let second_digit = (buffer[pos] >> 4) & 0xF;
-
-            imsi[i] = first_digit;
-            i = i + 1;
+        if length > 8 || buffer.len() < length as usize + 4 {
+            // An IMSI is at most 15 digits (8 octets)
+            return None
+        }
 
-            if second_digit != 0xF {
-                imsi[i] = second_digit;
-            }
-            i = i + 1;
+        let imsi = tbcd::decode(&buffer[pos..(length+4) as usize])?;
 
-            pos = pos + 1;
+        if imsi.len() > MAX_DIGITS {
+            // Only the filler may follow the 15th digit
+            return None
         }
 
+        pos = pos + length as usize;
+
         Some(
             (
                 InformationElement {
@@ -105,29 +117,22 @@ impl InformationElement {
         )
     }
 
-    pub fn generate_tbcd_imsi(&self) -> [u8; 8] {
-        let mut tbcd_imsi = [0xFF; 8];
-
-        let mut tbcd_index = 0;
-        let mut imsi_index = 0;
+    pub fn generate_tbcd_imsi(&self) -> Vec<u8> {
+        tbcd::encode(&self.imsi)
+    }
+}
 
-        while imsi_index < self.imsi.len() {
-            tbcd_imsi[tbcd_index] = self.imsi[imsi_index] & 0xF;
-            imsi_index = imsi_index + 1;
+impl FromStr for InformationElement {
+    type Err = String;
 
-            if imsi_index < self.imsi.len() {
-                tbcd_imsi[tbcd_index] = tbcd_imsi[tbcd_index] | ((self.imsi[imsi_index] & 0xF) << 4);
-                imsi_index = imsi_index + 1;
-            }
-            else {
-                // For an odd number of imsi digits the high nibble should be filled with 0xF
-                tbcd_imsi[tbcd_index] = tbcd_imsi[tbcd_index] | 0xF0;
-            }
-
-            tbcd_index = tbcd_index + 1;
-        }
+    fn from_str(imsi: &str) -> Result<Self, Self::Err> {
+        Self::new(imsi, 0)
+    }
+}
 
-        tbcd_imsi
+impl fmt::Display for InformationElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tbcd::to_string(&self.imsi))
     }
 }
 
@@ -150,8 +155,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        8+4
+    fn payload_length(&self) -> u16 {
+        tbcd::length(&self.imsi) as u16
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -162,25 +167,27 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        let tbcd_imsi = self.generate_tbcd_imsi();
-
-        for i in 0..tbcd_imsi.len() {
-            buffer[pos] = tbcd_imsi[i];
-            pos = pos + 1;
-        }
+        pos = pos + tbcd::generate(&self.imsi, &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("IMSI", self);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,11 +218,11 @@ mod tests {
                 assert_eq!(false, true)
             }
             Err(e) => {
-                assert_eq!(e, "Could not parse IMSI".to_string())
+                assert_eq!(e, "Could not parse IMSI 5f5013485090404: Not a decimal digit (f)".to_string())
             }
         }
 
-        let imsi_invalid = "50501348509040";
+        let imsi_invalid = "50501";
         let imsi_ie = InformationElement::new(imsi_invalid, 0);
 
         match imsi_ie {
@@ -223,9 +230,59 @@ mod tests {
                 assert_eq!(false, true)
             }
             Err(e) => {
-                assert_eq!(e, "IMSI is not the correct number of digits".to_string())
+                assert_eq!(e, "IMSI must be 6 to 15 digits 50501".to_string())
             }
         }
+
+        let imsi_invalid = "5050134850904041";
+        let imsi_ie = InformationElement::new(imsi_invalid, 0);
+
+        match imsi_ie {
+            Ok(_) => {
+                assert_eq!(false, true)
+            }
+            Err(e) => {
+                assert_eq!(e, "IMSI must be 6 to 15 digits 5050134850904041".to_string())
+            }
+        }
+
+        // IMSIs can be shorter than 15 digits
+        let imsi_ie = InformationElement::new("50501348509040", 0);
+
+        if let Ok(i) = imsi_ie {
+            assert_eq!(i.imsi, [5, 0, 5, 0, 1, 3, 4, 8, 5, 0, 9, 0, 4, 0]);
+            assert_eq!(i.wire_length(), 7+4);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_starts_with_plmn() {
+        let imsi = InformationElement::new("505013485090404", 0).unwrap();
+
+        assert!(imsi.starts_with_plmn(&PLMN::new(505, 1, false).unwrap()));
+        assert!(!imsi.starts_with_plmn(&PLMN::new(505, 2, false).unwrap()));
+        assert!(!imsi.starts_with_plmn(&PLMN::new(310, 1, false).unwrap()));
+        // The MNC digits must match including whether there are 2 or 3 of them
+        assert!(imsi.starts_with_plmn(&PLMN::new(505, 13, true).unwrap()));
+        assert!(!imsi.starts_with_plmn(&PLMN::new(505, 1, true).unwrap()));
+    }
+
+    #[test]
+    fn test_from_str() {
+        let imsi_ie = "505013485090404".parse::<InformationElement>();
+
+        if let Ok(i) = imsi_ie {
+            assert_eq!(i.imsi, [5, 0, 5, 0, 1, 3, 4, 8, 5, 0, 9, 0, 4, 0, 4]);
+            assert_eq!(i.to_string(), "505013485090404");
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!("5f5013485090404".parse::<InformationElement>().is_err());
     }
 
     #[test]
@@ -273,7 +330,7 @@ mod tests {
         let ie = InformationElement::new(imsi, 0);
 
         if let Ok(i) = ie {
-            assert_eq!(i.length(), 8+4)
+            assert_eq!(i.wire_length(), 8+4)
         }
         else {
             assert_eq!(false, true)
@@ -309,5 +366,30 @@ mod tests {
         else {
             assert!(false);
         }
+
+        let ie_bytes = [
+            InformationElementType::IMSI as u8,
+            0, 7, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.imsi, [5, 0, 5, 0, 1, 3, 4, 8, 5, 0, 9, 0, 4, 0]);
+            assert_eq!(pos, 11);
+        }
+        else {
+            assert!(false);
+        }
+
+        // The filler is only allowed in the last octet
+        let ie_bytes = [
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0xF5, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
     }
 }
\ No newline at end of file
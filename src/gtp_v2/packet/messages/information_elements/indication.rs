@@ -0,0 +1,478 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// A flag is the index of its octet in the flags (octet 5 is 0) and its bit
+pub type Flag = (usize, u8);
+
+// Indication flags (TS 29.274 8.12)
+pub const DAF: Flag = (0, 0b1000_0000); // Dual Address Bearer Flag
+pub const DTF: Flag = (0, 0b0100_0000); // Direct Tunnel Flag
+pub const HI: Flag = (0, 0b0010_0000); // Handover Indication
+pub const DFI: Flag = (0, 0b0001_0000); // Direct Forwarding Indication
+pub const OI: Flag = (0, 0b0000_1000); // Operation Indication
+pub const ISRSI: Flag = (0, 0b0000_0100); // Idle mode Signalling Reduction Supported Indication
+pub const ISRAI: Flag = (0, 0b0000_0010); // Idle mode Signalling Reduction Activation Indication
+pub const SGWCI: Flag = (0, 0b0000_0001); // SGW Change Indication
+
+pub const SQCI: Flag = (1, 0b1000_0000); // Subscribed QoS Change Indication
+pub const UIMSI: Flag = (1, 0b0100_0000); // Unauthenticated IMSI
+pub const CFSI: Flag = (1, 0b0010_0000); // Change F-TEID support Indication
+pub const CRSI: Flag = (1, 0b0001_0000); // Change Reporting Support Indication
+pub const P: Flag = (1, 0b0000_1000); // Piggybacking Supported
+pub const PT: Flag = (1, 0b0000_0100); // S5/S8 Protocol Type
+pub const SI: Flag = (1, 0b0000_0010); // Scope Indication
+pub const MSV: Flag = (1, 0b0000_0001); // MS Validated
+
+pub const RETLOC: Flag = (2, 0b1000_0000); // Retrieve Location Indication
+pub const PBIC: Flag = (2, 0b0100_0000); // Propagate BBAI Information Change
+pub const SRNI: Flag = (2, 0b0010_0000); // SGW Restoration Needed Indication
+pub const S6AF: Flag = (2, 0b0001_0000); // Static IPv6 Address Flag
+pub const S4AF: Flag = (2, 0b0000_1000); // Static IPv4 Address Flag
+pub const MBMDT: Flag = (2, 0b0000_0100); // Management Based MDT allowed flag
+pub const ISRAU: Flag = (2, 0b0000_0010); // ISR is activated for the UE
+pub const CCRSI: Flag = (2, 0b0000_0001); // CSG Change Reporting Support Indication
+
+pub const CPRAI: Flag = (3, 0b1000_0000); // Change of Presence Reporting Area information Indication
+pub const ARRL: Flag = (3, 0b0100_0000); // Abnormal Release of Radio Link
+pub const PPOFF: Flag = (3, 0b0010_0000); // PDN Pause Off Indication
+pub const PPON: Flag = (3, 0b0001_0000); // PDN Pause On Indication / PDN Pause Enabled Indication
+pub const PPSI: Flag = (3, 0b0000_1000); // PDN Pause Support Indication
+pub const CSFBI: Flag = (3, 0b0000_0100); // CSFB Indication
+pub const CLII: Flag = (3, 0b0000_0010); // Change of Location Information Indication
+pub const CPSR: Flag = (3, 0b0000_0001); // CS to PS SRVCC indication
+
+pub const NSI: Flag = (4, 0b1000_0000); // NBIFOM Support Indication
+pub const UASI: Flag = (4, 0b0100_0000); // UE Available for Signalling Indication
+pub const DTCI: Flag = (4, 0b0010_0000); // Delay Tolerant Connection Indication
+pub const BDWI: Flag = (4, 0b0001_0000); // Buffered DL Data Waiting Indication
+pub const PSCI: Flag = (4, 0b0000_1000); // Pending Subscription Change Indication
+pub const PCRI: Flag = (4, 0b0000_0100); // P-CSCF Restoration Indication
+pub const AOSI: Flag = (4, 0b0000_0010); // Associate OCI with SGW node's Identity
+pub const AOPI: Flag = (4, 0b0000_0001); // Associate OCI with PGW node's Identity
+
+pub const ROAAI: Flag = (5, 0b1000_0000); // Release Over Any Access Indication
+pub const EPCOSI: Flag = (5, 0b0100_0000); // Extended PCO Support Indication
+pub const CPOPCI: Flag = (5, 0b0010_0000); // Control Plane Only PDN Connection Indication
+pub const PMTSMI: Flag = (5, 0b0001_0000); // Pending MT Short Message Indication
+pub const S11TF: Flag = (5, 0b0000_1000); // S11-U Tunnel Flag
+pub const PNSI: Flag = (5, 0b0000_0100); // Pending Network Initiated PDN Connection Signalling Indication
+pub const UNACCSI: Flag = (5, 0b0000_0010); // UE Not Authorised Cause Code Support Indication
+pub const WPMSI: Flag = (5, 0b0000_0001); // WLCP PDN Connection Modification Support Indication
+
+// Octets 11 and 12 are mostly for interworking with 5GS (TS 23.501 5.17.2)
+pub const _5GSNN26: Flag = (6, 0b1000_0000); // 5GS Interworking without N26 Indication
+pub const REPREFI: Flag = (6, 0b0100_0000); // Return Preferred Indication
+pub const _5GSIWK: Flag = (6, 0b0010_0000); // 5GS Interworking Indication
+pub const EEVRSI: Flag = (6, 0b0001_0000); // Extended EBI Value Range Support Indication
+pub const LTEMUI: Flag = (6, 0b0000_1000); // LTE-M UE Indication
+pub const LTEMPI: Flag = (6, 0b0000_0100); // LTE-M RAT Type reporting to PGW Indication
+pub const ENBCRSI: Flag = (6, 0b0000_0010); // eNB Change Reporting Support Indication
+pub const TSPCMI: Flag = (6, 0b0000_0001); // Triggering SGSN initiated PDP Context Creation/Modification Indication
+
+pub const CSRMFI: Flag = (7, 0b1000_0000); // Create Session Request Message Forwarded Indication
+pub const MTEDTN: Flag = (7, 0b0100_0000); // MT-EDT Not Applicable
+pub const MTEDTA: Flag = (7, 0b0010_0000); // MT-EDT Applicable
+pub const N5GNMI: Flag = (7, 0b0001_0000); // No 5GS N26 Mobility Indication
+pub const _5GCNRS: Flag = (7, 0b0000_1000); // 5GC Not Restricted Support
+pub const _5GCNRI: Flag = (7, 0b0000_0100); // 5GC Not Restricted Indication
+pub const _5SRHOI: Flag = (7, 0b0000_0010); // 5G-SRVCC HO Indication
+pub const ETHPDN: Flag = (7, 0b0000_0001); // Ethernet PDN Support Indication
+
+const FLAGS: [(&str, Flag); 64] = [
+    ("Dual Address Bearer Flag (DAF)", DAF),
+    ("Direct Tunnel Flag (DTF)", DTF),
+    ("Handover Indication (HI)", HI),
+    ("Direct Forwarding Indication (DFI)", DFI),
+    ("Operation Indication (OI)", OI),
+    ("ISR Supported Indication (ISRSI)", ISRSI),
+    ("ISR Activation Indication (ISRAI)", ISRAI),
+    ("SGW Change Indication (SGWCI)", SGWCI),
+    ("Subscribed QoS Change Indication (SQCI)", SQCI),
+    ("Unauthenticated IMSI (UIMSI)", UIMSI),
+    ("Change F-TEID Support Indication (CFSI)", CFSI),
+    ("Change Reporting Support Indication (CRSI)", CRSI),
+    ("Piggybacking Supported (P)", P),
+    ("S5/S8 Protocol Type (PT)", PT),
+    ("Scope Indication (SI)", SI),
+    ("MS Validated (MSV)", MSV),
+    ("Retrieve Location Indication (RetLoc)", RETLOC),
+    ("Propagate BBAI Information Change (PBIC)", PBIC),
+    ("SGW Restoration Needed Indication (SRNI)", SRNI),
+    ("Static IPv6 Address Flag (S6AF)", S6AF),
+    ("Static IPv4 Address Flag (S4AF)", S4AF),
+    ("Management Based MDT Allowed (MBMDT)", MBMDT),
+    ("ISR Activated for the UE (ISRAU)", ISRAU),
+    ("CSG Change Reporting Support Indication (CCRSI)", CCRSI),
+    ("Change of Presence Reporting Area Information Indication (CPRAI)", CPRAI),
+    ("Abnormal Release of Radio Link (ARRL)", ARRL),
+    ("PDN Pause Off Indication (PPOFF)", PPOFF),
+    ("PDN Pause On Indication (PPON)", PPON),
+    ("PDN Pause Support Indication (PPSI)", PPSI),
+    ("CSFB Indication (CSFBI)", CSFBI),
+    ("Change of Location Information Indication (CLII)", CLII),
+    ("CS to PS SRVCC Indication (CPSR)", CPSR),
+    ("NBIFOM Support Indication (NSI)", NSI),
+    ("UE Available for Signalling Indication (UASI)", UASI),
+    ("Delay Tolerant Connection Indication (DTCI)", DTCI),
+    ("Buffered DL Data Waiting Indication (BDWI)", BDWI),
+    ("Pending Subscription Change Indication (PSCI)", PSCI),
+    ("P-CSCF Restoration Indication (PCRI)", PCRI),
+    ("Associate OCI with SGW Node's Identity (AOSI)", AOSI),
+    ("Associate OCI with PGW Node's Identity (AOPI)", AOPI),
+    ("Release Over Any Access Indication (ROAAI)", ROAAI),
+    ("Extended PCO Support Indication (EPCOSI)", EPCOSI),
+    ("Control Plane Only PDN Connection Indication (CPOPCI)", CPOPCI),
+    ("Pending MT Short Message Indication (PMTSMI)", PMTSMI),
+    ("S11-U Tunnel Flag (S11TF)", S11TF),
+    ("Pending Network Initiated PDN Connection Signalling Indication (PNSI)", PNSI),
+    ("UE Not Authorised Cause Code Support Indication (UNACCSI)", UNACCSI),
+    ("WLCP PDN Connection Modification Support Indication (WPMSI)", WPMSI),
+    ("5GS Interworking without N26 Indication (5GSNN26)", _5GSNN26),
+    ("Return Preferred Indication (REPREFI)", REPREFI),
+    ("5GS Interworking Indication (5GSIWK)", _5GSIWK),
+    ("Extended EBI Value Range Support Indication (EEVRSI)", EEVRSI),
+    ("LTE-M UE Indication (LTEMUI)", LTEMUI),
+    ("LTE-M RAT Type Reporting to PGW Indication (LTEMPI)", LTEMPI),
+    ("eNB Change Reporting Support Indication (ENBCRSI)", ENBCRSI),
+    ("Triggering SGSN Initiated PDP Context Creation/Modification Indication (TSPCMI)", TSPCMI),
+    ("Create Session Request Message Forwarded Indication (CSRMFI)", CSRMFI),
+    ("MT-EDT Not Applicable (MTEDTN)", MTEDTN),
+    ("MT-EDT Applicable (MTEDTA)", MTEDTA),
+    ("No 5GS N26 Mobility Indication (N5GNMI)", N5GNMI),
+    ("5GC Not Restricted Support (5GCNRS)", _5GCNRS),
+    ("5GC Not Restricted Indication (5GCNRI)", _5GCNRI),
+    ("5G-SRVCC HO Indication (5SRHOI)", _5SRHOI),
+    ("Ethernet PDN Support Indication (ETHPDN)", ETHPDN),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (77)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | DAF   | DTF   | HI    | DFI   | OI    | ISRSI | ISRAI | SGWCI |
+        6       | SQCI  | UIMSI | CFSI  | CRSI  | P     | PT    | SI    | MSV   |
+        7       | RetLoc| PBIC  | SRNI  | S6AF  | S4AF  | MBMDT | ISRAU | CCRSI |
+        8       | CPRAI | ARRL  | PPOFF | PPON  | PPSI  | CSFBI | CLII  | CPSR  |
+        9       | NSI   | UASI  | DTCI  | BDWI  | PSCI  | PCRI  | AOSI  | AOPI  |
+        10      | ROAAI | EPCOSI| CPOPCI| PMTSMI| S11TF | PNSI  |UNACCSI| WPMSI |
+        11      |5GSNN26|REPREFI|5GSIWK | EEVRSI| LTEMUI| LTEMPI|ENBCRSI| TSPCMI|
+        12      | CSRMFI| MTEDTN| MTEDTA| N5GNMI|5GCNRS |5GCNRI |5SRHOI | ETHPDN|
+        13 -> n | Further flags                                                 |
+                |---------------------------------------------------------------|
+
+        Only as many octets as are received (or needed for the flags that are set) are carried.
+    */
+
+    instance: u8,
+    pub flags: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(instance: u8) -> Result<Self, String> {
+        // No flags set. Use set() to add them
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    flags: Vec::new(),
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn with_flags(flags: &[Flag], instance: u8) -> Result<Self, String> {
+        let mut ie = Self::new(instance)?;
+
+        for flag in flags {
+            ie.set(*flag);
+        }
+
+        Ok(ie)
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let flags = buffer[pos..(length as usize + 4)].to_vec();
+
+        Some(
+            (
+                InformationElement {
+                    flags,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+
+    pub fn set(&mut self, flag: Flag) {
+        let (octet, bit) = flag;
+
+        if self.flags.len() <= octet {
+            self.flags.resize(octet + 1, 0);
+        }
+
+        self.flags[octet] = self.flags[octet] | bit;
+    }
+
+    pub fn clear(&mut self, flag: Flag) {
+        let (octet, bit) = flag;
+
+        if let Some(f) = self.flags.get_mut(octet) {
+            *f = *f & !bit;
+        }
+    }
+
+    pub fn is_set(&self, flag: Flag) -> bool {
+        // Flags in octets that weren't received are treated as not set
+        let (octet, bit) = flag;
+
+        match self.flags.get(octet) {
+            Some(f) => f & bit == bit,
+            None => false,
+        }
+    }
+
+    // Idle mode Signalling Reduction flags
+    pub fn isr_supported(&self) -> bool {
+        self.is_set(ISRSI)
+    }
+
+    pub fn isr_activated(&self) -> bool {
+        self.is_set(ISRAI)
+    }
+
+    pub fn isr_activated_for_ue(&self) -> bool {
+        self.is_set(ISRAU)
+    }
+
+    pub fn set_isr(&mut self, flag: Flag, active: bool) {
+        if active {
+            self.set(flag);
+        }
+        else {
+            self.clear(flag);
+        }
+    }
+
+    // 5GS interworking flags. A UE that can move to 5GS has 5GSIWK set. 5GSNN26 is also set if there is no N26
+    // between the AMF and MME so the PDN connections can't be handed over (TS 23.501 5.17.2.3)
+    pub fn is_5gs_interworking(&self) -> bool {
+        self.is_set(_5GSIWK)
+    }
+
+    pub fn has_n26(&self) -> bool {
+        self.is_5gs_interworking() && !self.is_set(_5GSNN26)
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Indication
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.flags.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.flags.len()].copy_from_slice(&self.flags);
+        pos = pos + self.flags.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            for (name, flag) in FLAGS.iter() {
+                if self.is_set(*flag) {
+                    d.flag(name, true);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::with_flags(&[DAF, SQCI, CLII], 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Indication as u8,
+            0, 4, // Length
+            0, // Spare
+            0b1000_0000, 0b1000_0000, 0x00, 0b0000_0010, // Flags
+        ]);
+
+        // Only the octets that are needed
+        let ie = InformationElement::with_flags(&[HI], 0).unwrap();
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new(0x10).is_err());
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut ie = InformationElement::new(0).unwrap();
+
+        assert!(!ie.is_set(CLII));
+
+        ie.set(CLII);
+        ie.set(OI);
+        assert!(ie.is_set(CLII));
+        assert!(ie.is_set(OI));
+        assert!(!ie.is_set(CPSR));
+
+        ie.clear(CLII);
+        assert!(!ie.is_set(CLII));
+        assert!(ie.is_set(OI));
+
+        // Clearing a flag that was never received does nothing
+        let mut ie = InformationElement::new(0).unwrap();
+        ie.clear(CPSR);
+        assert!(ie.flags.is_empty());
+    }
+
+    #[test]
+    fn test_isr_flags() {
+        let mut ie = InformationElement::new(0).unwrap();
+
+        assert!(!ie.isr_supported());
+        assert!(!ie.isr_activated());
+        assert!(!ie.isr_activated_for_ue());
+
+        ie.set_isr(ISRSI, true);
+        ie.set_isr(ISRAU, true);
+        assert!(ie.isr_supported());
+        assert!(!ie.isr_activated());
+        assert!(ie.isr_activated_for_ue());
+        assert_eq!(ie.flags, [0b0000_0100, 0, 0b0000_0010]);
+
+        ie.set_isr(ISRAU, false);
+        assert!(!ie.isr_activated_for_ue());
+    }
+
+    #[test]
+    fn test_5gs_interworking_flags() {
+        let mut ie = InformationElement::with_flags(&[_5GSIWK], 0).unwrap();
+
+        assert_eq!(ie.flags, [0, 0, 0, 0, 0, 0, 0b0010_0000]);
+        assert!(ie.is_5gs_interworking());
+        assert!(ie.has_n26());
+
+        ie.set(_5GSNN26);
+        assert!(!ie.has_n26());
+
+        ie.clear(_5GSIWK);
+        assert!(!ie.is_5gs_interworking());
+        assert!(!ie.has_n26());
+
+        ie.set(_5GCNRI);
+        assert_eq!(ie.flags[7], 0b0000_0100);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::Indication as u8,
+            0, 2, // Length
+            1, // Spare and Instance
+            0b0000_1000, 0b0001_0000, // Flags
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.instance(), 1);
+            assert!(ie.is_set(OI));
+            assert!(ie.is_set(CRSI));
+            assert!(!ie.is_set(CLII));
+            assert_eq!(pos, 6);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..5]).is_none());
+    }
+}
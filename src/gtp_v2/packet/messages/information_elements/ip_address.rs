@@ -0,0 +1,218 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::net::IpAddr;
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (74)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | IPv4 or IPv6 Address                                          |
+                |---------------------------------------------------------------|
+
+        The length is 4 for an IPv4 Address or 16 for an IPv6 Address
+    */
+
+    instance: u8,
+    pub ip_address: IpAddr,
+}
+
+impl InformationElement {
+    pub fn new(ip_address: IpAddr, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    ip_address,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        // The length is 4 for an IPv4 Address or 16 for an IPv6 Address
+        let ip_address = address::parse(&buffer[pos..pos + length as usize])?;
+
+        Some(
+            (
+                InformationElement {
+                    ip_address,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::IPAddress
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        address::length(self.ip_address)
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + address::generate(self.ip_address, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("IP Address", self.ip_address);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::net::{Ipv4Addr, Ipv6Addr};
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::IPAddress as u8,
+            0, 4, // Length
+            0, // Spare
+            10, 0, 0, 1, // IPv4 Address
+        ]);
+
+        let ie = InformationElement::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 1).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::IPAddress as u8,
+            0, 16, // Length
+            1, // Spare and Instance
+            0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, // IPv6 Address
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 0).unwrap();
+        assert_eq!(ie.wire_length(), 8);
+
+        let ie = InformationElement::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 0).unwrap();
+        assert_eq!(ie.wire_length(), 20);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::IPAddress as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::IPAddress as u8,
+            0, 4, // Length
+            0, // Spare
+            192, 168, 1, 1, // IPv4 Address
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 8);
+            assert_eq!(ie.ip_address, IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..7]).is_none());
+
+        // Only IPv4 and IPv6 lengths are allowed
+        let ie_bytes = [InformationElementType::IPAddress as u8,
+            0, 5, // Length
+            0, // Spare
+            192, 168, 1, 1, 1,
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
@@ -0,0 +1,217 @@
+extern crate ascii;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryInto;
+use ascii::{AsciiString, ToAsciiChar};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (151)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | Local Distinguished Name (LDN)                                |
+                |---------------------------------------------------------------|
+
+        The LDN is a string of 1 to 400 octets as per TS 32.423
+    */
+
+    instance: u8,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
+    pub ldn: AsciiString
+}
+
+impl InformationElement {
+    pub fn new(ldn: AsciiString, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if ldn.len() == 0 || ldn.len() > 400 {
+            Err(format!("LDN must be between 1 and 400 octets long {}", ldn.len()))
+        }
+        else {
+            Ok(InformationElement {
+                ldn: ldn,
+                instance: instance,
+            })
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let mut ldn = AsciiString::new();
+
+        while pos < (length+4) as usize {
+            if let Ok(ch) = buffer[pos].to_ascii_char() {
+                ldn.push(ch)
+            }
+            else {
+                return None
+            }
+            pos = pos + 1;
+        }
+
+        Some(
+            (
+                InformationElement {
+                    ldn,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::LDN
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.ldn.len().try_into().unwrap()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        for ch in self.ldn.chars() {
+            buffer[pos] = ch.as_byte();
+            pos = pos + 1;
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("LDN", &self.ldn);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 0).is_ok());
+        assert!(InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 0x10).is_err());
+        assert!(InformationElement::new(AsciiString::new(), 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 1).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::LDN as u8,
+            0, 5, // Length
+            1, // Spare and Instance
+            b'S', b'G', b'W', b'-', b'1'
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 0).unwrap();
+        assert_eq!(ie.wire_length(), 5+4);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(AsciiString::from_ascii("SGW-1").unwrap(), 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::LDN as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::LDN as u8,
+            0, 5, // Length
+            1, // Spare and Instance
+            b'P', b'G', b'W', b'-', b'2',
+            0, 0, 0, // Next IE
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.ldn, AsciiString::from_ascii("PGW-2").unwrap());
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(pos, 9);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,7 +1,19 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector, digits_string};
+use crate::tbcd;
+
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -13,8 +25,10 @@ pub struct InformationElement {
         2       | Length Octet 1                                                |
         3       | Length Octet 2                                                |
         4       | Spare                         | Instance                      |
-        5       | RAT Type                                                      |
+        5 -> 12 | IMEI or IMEISV (TBCD)                                         |
                 |---------------------------------------------------------------|
+
+        An IMEI is 15 digits so bits 5-8 of octet 12 are the filler (0xF)
     */
 
     instance: u8,
@@ -27,6 +41,9 @@ impl InformationElement {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if imei.iter().chain(sv.iter()).any(|d| *d > 9) {
+            Err("MEI contains a digit > 9".to_string())
+        }
         else {
             Ok(
                 InformationElement {
@@ -37,7 +54,42 @@ impl InformationElement {
             )
         }
     }
+
+    pub fn from_imei(imei: &str, instance: u8) -> Result<Self, String> {
+        // The 14 digits of the TAC and SNR, optionally followed by the check digit which is then validated
+        let mut digits = match tbcd::parse_digits(imei) {
+            Ok(digits) => digits,
+            Err(_e) => return Err("Could not parse IMEI".to_string())
+        };
+
+        match digits.len() {
+            14 => digits.push(check_digit(&digits)),
+            15 => {
+                let cd = check_digit(&digits[..14]);
+
+                if digits[14] != cd {
+                    return Err(format!("IMEI check digit is incorrect {} (expected {})", digits[14], cd))
+                }
+            },
+            _ => return Err("IMEI is not the correct number of digits".to_string())
+        }
+
+        let mut imei = [0u8; 15];
+        imei.copy_from_slice(&digits);
+
+        Self::new(imei, None, instance)
+    }
+
+    pub fn has_valid_check_digit(&self) -> bool {
+        // Only an IMEI has a check digit. The 15th digit of an IMEISV is part of the SV
+        self.sv.is_some() || self.imei[14] == check_digit(&self.imei[..14])
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -52,34 +104,17 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        let mut imei = [0u8; 15];
-
-        let mut sv: Option<u8> = None;
-
-        let mut i = 0;
-
-        for _ in 0..8 {
-            let first_digit = buffer[pos] & 0xF;
-            let second_digit = (buffer[pos] >> 4) & 0xF;
+        if length < 8 || buffer.len() < length as usize + 4 {
+            return None
+        }
 
-            imei[i] = first_digit;
-            i = i + 1;
+        // The 16th digit is either the SV or the filler
+        let digits = tbcd::decode(&buffer[pos..pos+8])?;
 
-            if i == 15 {
-                // This is either the SV or a stop bit
-                if second_digit != 0xF {
-                    // It has an SV
-                    sv = Some(second_digit);
-                }
-            }
-            else {
-                imei[i] = second_digit;
-            }
-            
-            i = i + 1;
+        let mut imei = [0u8; 15];
+        imei.copy_from_slice(&digits[..15]);
 
-            pos = pos + 1;
-        }
+        let sv = digits.get(15).copied();
 
         Some(
             (
@@ -93,34 +128,66 @@ impl InformationElement {
         )
     }
 
+    fn digits(&self) -> Vec<u8> {
+        let mut digits = self.imei.to_vec();
+
+        if let Some(sv) = self.sv {
+            digits.push(sv);
+        }
+
+        digits
+    }
+
     pub fn generate_tbcd_imei(&self) -> [u8; 8] {
         let mut tbcd_imei = [0xFF; 8];
 
-        let mut tbcd_index = 0;
-        let mut imei_index = 0;
+        tbcd::generate(&self.digits(), &mut tbcd_imei);
 
-        while imei_index < self.imei.len() {
-            tbcd_imei[tbcd_index] = self.imei[imei_index] & 0xF;
-            imei_index = imei_index + 1;
+        tbcd_imei
+    }
+}
 
-            if imei_index < self.imei.len() {
-                tbcd_imei[tbcd_index] = tbcd_imei[tbcd_index] | ((self.imei[imei_index] & 0xF) << 4);
-                imei_index = imei_index + 1;
-            }
-            else {
-                // For an imei with no sv, digits the high nibble should be filled with 0xF
-                if let Some(sv) = self.sv {
-                    tbcd_imei[tbcd_index] = tbcd_imei[tbcd_index] | ((sv & 0xF) << 4);
-                }
-                else {
-                    tbcd_imei[tbcd_index] = tbcd_imei[tbcd_index] | 0xF0;
-                }   
-            }
+pub fn check_digit(digits: &[u8]) -> u8 {
+    // The Luhn check digit of the 14 digit TAC and SNR as per TS 23.003 Annex B. Every second digit from the right
+    // is doubled
+    let mut sum = 0;
 
-            tbcd_index = tbcd_index + 1;
+    for (i, d) in digits.iter().rev().enumerate() {
+        let d = if i % 2 == 0 { d * 2 } else { *d };
+        sum = sum + d / 10 + d % 10;
+    }
+
+    (10 - sum % 10) % 10
+}
+
+impl FromStr for InformationElement {
+    type Err = String;
+
+    fn from_str(mei: &str) -> Result<Self, Self::Err> {
+        // Either an IMEI without its check digit, a 15 digit IMEI or an IMEI followed by the SV
+        if mei.len() == 14 {
+            return Self::from_imei(mei, 0)
         }
 
-        tbcd_imei
+        if mei.len() != 15 && mei.len() != 16 {
+            return Err("MEI is not the correct number of digits".to_string())
+        }
+
+        let digits = match tbcd::parse_digits(mei) {
+            Ok(digits) => digits,
+            Err(_e) => return Err("Could not parse MEI".to_string())
+        };
+
+        let mut imei = [0u8; 15];
+        imei.copy_from_slice(&digits[..15]);
+
+        Self::new(imei, digits.get(15).copied(), 0)
+    }
+}
+
+impl fmt::Display for InformationElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tbcd::to_string(&self.digits()))
     }
 }
 
@@ -143,11 +210,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+8
+    fn payload_length(&self) -> u16 {
+        8
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -158,25 +222,31 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        let tbcd_imei = self.generate_tbcd_imei();
-
-        for i in 0..tbcd_imei.len() {
-            buffer[pos] = tbcd_imei[i];
-            pos = pos + 1;
-        }
+        pos = pos + tbcd::generate(&self.digits(), &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("IMEI", digits_string(&self.imei));
+
+            if let Some(sv) = self.sv {
+                d.field("Software Version (SV)", sv);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,12 +280,96 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new([1,2,3,4,5,6,7,8,9,1,2,3,4,5,6], Some(1), 0).is_ok());
+        assert!(InformationElement::new([1,2,3,4,5,6,7,8,9,1,2,3,4,5,0xF], None, 0).is_err());
+        assert!(InformationElement::new([1,2,3,4,5,6,7,8,9,1,2,3,4,5,6], Some(0xA), 0).is_err());
+    }
+
+    #[test]
+    fn test_from_str() {
+        if let Ok(ie) = "123456789123456".parse::<InformationElement>() {
+            assert_eq!(ie.imei, [1,2,3,4,5,6,7,8,9,1,2,3,4,5,6]);
+            assert!(ie.sv.is_none());
+            assert_eq!(ie.to_string(), "123456789123456");
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Ok(ie) = "1234567891234561".parse::<InformationElement>() {
+            assert_eq!(ie.imei, [1,2,3,4,5,6,7,8,9,1,2,3,4,5,6]);
+            assert_eq!(ie.sv, Some(1));
+            assert_eq!(ie.to_string(), "1234567891234561");
+        }
+        else {
+            assert!(false);
+        }
+
+        // The check digit is added to an IMEI without one
+        if let Ok(ie) = "49015420323751".parse::<InformationElement>() {
+            assert_eq!(ie.to_string(), "490154203237518");
+            assert!(ie.sv.is_none());
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!("1234567891234".parse::<InformationElement>().is_err());
+        assert!("12345678912345a".parse::<InformationElement>().is_err());
+    }
+
+    #[test]
+    fn test_check_digit() {
+        assert_eq!(check_digit(&[4,9,0,1,5,4,2,0,3,2,3,7,5,1]), 8);
+        assert_eq!(check_digit(&[3,5,2,0,9,9,0,0,1,7,6,1,4,8]), 1);
+        assert_eq!(check_digit(&[0; 14]), 0);
+
+        assert!(InformationElement::from_imei("490154203237518", 0).unwrap().has_valid_check_digit());
+        assert!(!InformationElement::new([4,9,0,1,5,4,2,0,3,2,3,7,5,1,7], None, 0).unwrap().has_valid_check_digit());
+        assert!(InformationElement::new([4,9,0,1,5,4,2,0,3,2,3,7,5,1,7], Some(2), 0).unwrap().has_valid_check_digit());
+    }
+
+    #[test]
+    fn test_from_imei() {
+        let mut buffer = [0; MTU];
+
+        if let Ok(ie) = InformationElement::from_imei("35209900176148", 1) {
+            assert_eq!(ie.imei, [3,5,2,0,9,9,0,0,1,7,6,1,4,8,1]);
+            assert!(ie.sv.is_none());
+            assert_eq!(ie.instance(), 1);
+
+            // Without an SV the last octet is padded with the filler
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], [InformationElementType::MEI as u8,
+                0, 8, // Length
+                1, // Spare and Instance
+                0x53, 0x02, 0x99, 0x00, 0x71, 0x16, 0x84, 0xF1 // IMEI
+            ]);
+
+            assert_eq!(InformationElement::parse(&buffer[..pos]), Some((ie, 12)));
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::from_imei("352099001761481", 0).is_ok());
+
+        // Wrong check digit, wrong length and not digits
+        assert!(InformationElement::from_imei("352099001761482", 0).is_err());
+        assert!(InformationElement::from_imei("3520990017614", 0).is_err());
+        assert!(InformationElement::from_imei("3520990017614811", 0).is_err());
+        assert!(InformationElement::from_imei("3520990017614a", 0).is_err());
+    }
+
     #[test]
     fn test_length() {
         let imei: [u8; 15] = [1,2,3,4,5,6,7,8,9,1,2,3,4,5,6];
 
         let ie = InformationElement::new(imei, None, 0).unwrap();
-        assert_eq!(ie.length(), 8+4);
+        assert_eq!(ie.wire_length(), 8+4);
     }
 
     #[test]
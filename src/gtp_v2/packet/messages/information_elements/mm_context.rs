@@ -0,0 +1,900 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::convert::TryFrom;
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (103 -> 108)                                          |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | MM Context                                                    |
+                |---------------------------------------------------------------|
+
+        There are six MM Context IEs, one per combination of security context and authentication vectors.
+        The IE type identifies which one is carried.
+
+        ------------|-------------------------------------------------------
+        IE Type     |   MM Context
+        ------------|-------------------------------------------------------
+        103         |   GSM Key and Triplets
+        104         |   UMTS Key, Used Cipher and Quintuplets
+        105         |   GSM Key, Used Cipher and Quintuplets
+        106         |   UMTS Key and Quintuplets
+        107         |   EPS Security Context, Quadruplets and Quintuplets
+        108         |   UMTS Key, Quadruplets and Quintuplets
+        ------------|-------------------------------------------------------
+
+        The MM Context is carried opaquely so that it can be relayed between MMEs / SGSNs unchanged.
+        The EPS Security Context (107) can be decoded with eps_security_context()
+    */
+
+    instance: u8,
+    mm_context_type: InformationElementType,
+    pub mm_context: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(mm_context_type: InformationElementType, mm_context: &[u8], instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if !Self::is_mm_context(mm_context_type) {
+            Err(format!("IE Type is not an MM Context {:?}", mm_context_type))
+        }
+        else if mm_context.len() > 0xFFFF {
+            Err(format!("MM Context is too long {}", mm_context.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    mm_context_type,
+                    mm_context: mm_context.to_vec(),
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn for_eps_security_context(context: &EpsSecurityContext, instance: u8) -> Result<Self, String> {
+        context.validate()?;
+
+        let mut mm_context = vec![0; context.length()];
+        context.generate(&mut mm_context);
+
+        Self::new(InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets, &mm_context, instance)
+    }
+
+    pub fn eps_security_context(&self) -> Option<EpsSecurityContext> {
+        if self.mm_context_type != InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets {
+            return None
+        }
+
+        match EpsSecurityContext::parse(&self.mm_context) {
+            Some((context, _pos)) => Some(context),
+            None => None,
+        }
+    }
+
+    pub fn is_mm_context(ie_type: InformationElementType) -> bool {
+        match ie_type {
+            InformationElementType::MMContextGSMKeyAndTriplets |
+            InformationElementType::MMContextUMTSKeyUsedCipherAndQuintuplets |
+            InformationElementType::MMContextGSMKeyUsedCipherAndQuintuplets |
+            InformationElementType::MMContextUMTSKeyAndQuintuplets |
+            InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets |
+            InformationElementType::MMContextUMTSKeyQuadrupletsAndQuintuplets => true,
+            _ => false,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if let Ok(mm_context_type) = InformationElementType::try_from(ie_type) {
+            if Self::is_mm_context(mm_context_type) {
+                Some(
+                    (
+                        InformationElement {
+                            mm_context_type,
+                            mm_context: buffer[pos..(length + 4) as usize].to_vec(),
+                            instance,
+                        },
+                        (length + 4) as usize
+                    )
+                )
+            }
+            else {
+                None
+            }
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        self.mm_context_type
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.mm_context.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.mm_context.len()].copy_from_slice(&self.mm_context);
+        pos = pos + self.mm_context.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            if let Some(context) = self.eps_security_context() {
+                d.field("Security Mode", context.security_mode);
+                d.field("KSI ASME", context.ksi_asme);
+                d.field("Used NAS Integrity Protection Algorithm", context.used_nas_integrity_protection_algorithm);
+                d.field("Used NAS Cipher", context.used_nas_cipher);
+                d.field("NAS Downlink Count", context.nas_downlink_count);
+                d.field("NAS Uplink Count", context.nas_uplink_count);
+                d.field("Authentication Quadruplets", context.quadruplets.len());
+                d.field("Authentication Quintuplets", context.quintuplets.len());
+                d.hex("UE Network Capability", &context.ue_network_capability);
+                d.hex("MS Network Capability", &context.ms_network_capability);
+                d.hex("MEI", &context.mei);
+            }
+            else {
+                d.hex("MM Context", &self.mm_context);
+            }
+        });
+    }
+}
+
+/*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1 -> 16 | RAND                                                          |
+        17      | XRES Length                                                   |
+        18 -> m | XRES                                                          |
+        m+1     | AUTN Length                                                   |
+        (m+2) -> n | AUTN                                                       |
+        (n+1) -> (n+32) | K_ASME                                                |
+                |---------------------------------------------------------------|
+*/
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthenticationQuadruplet {
+    pub rand: [u8; 16],
+    pub xres: Vec<u8>,
+    pub autn: Vec<u8>,
+    pub k_asme: [u8; 32],
+}
+
+impl AuthenticationQuadruplet {
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let rand = read_array(buffer, &mut pos)?;
+        let xres = read_length_and_value(buffer, &mut pos)?;
+        let autn = read_length_and_value(buffer, &mut pos)?;
+        let k_asme = read_array(buffer, &mut pos)?;
+
+        Some((AuthenticationQuadruplet { rand, xres, autn, k_asme }, pos))
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + write_value(&self.rand, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.xres, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.autn, &mut buffer[pos..]);
+        pos = pos + write_value(&self.k_asme, &mut buffer[pos..]);
+
+        pos
+    }
+
+    pub fn length(&self) -> usize {
+        16 + 1 + self.xres.len() + 1 + self.autn.len() + 32
+    }
+}
+
+/*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1 -> 16 | RAND                                                          |
+        17      | XRES Length                                                   |
+        18 -> m | XRES                                                          |
+        (m+1) -> (m+16) | CK                                                    |
+        (m+17) -> (m+32) | IK                                                   |
+        m+33    | AUTN Length                                                   |
+        (m+34) -> n | AUTN                                                      |
+                |---------------------------------------------------------------|
+*/
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthenticationQuintuplet {
+    pub rand: [u8; 16],
+    pub xres: Vec<u8>,
+    pub ck: [u8; 16],
+    pub ik: [u8; 16],
+    pub autn: Vec<u8>,
+}
+
+impl AuthenticationQuintuplet {
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let mut pos = 0;
+
+        let rand = read_array(buffer, &mut pos)?;
+        let xres = read_length_and_value(buffer, &mut pos)?;
+        let ck = read_array(buffer, &mut pos)?;
+        let ik = read_array(buffer, &mut pos)?;
+        let autn = read_length_and_value(buffer, &mut pos)?;
+
+        Some((AuthenticationQuintuplet { rand, xres, ck, ik, autn }, pos))
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + write_value(&self.rand, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.xres, &mut buffer[pos..]);
+        pos = pos + write_value(&self.ck, &mut buffer[pos..]);
+        pos = pos + write_value(&self.ik, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.autn, &mut buffer[pos..]);
+
+        pos
+    }
+
+    pub fn length(&self) -> usize {
+        16 + 1 + self.xres.len() + 16 + 16 + 1 + self.autn.len()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OldEpsSecurityContext {
+    pub ksi_asme: u8,
+    pub k_asme: [u8; 32],
+    pub nh_and_ncc: Option<([u8; 32], u8)>, // Present if NHI_old is set
+}
+
+/*
+    MM Context for EPS Security Context, Quadruplets and Quintuplets (TS 29.274 Figure 8.38-5)
+
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | Security Mode         | NHI   | DRXI  | KSI ASME              |
+        2       | Nr of Quintuplets     | Nr of Quadruplets     | UAMB RI | OSCI|
+        3       | SAMB RI | Used NAS Integrity    | Used NAS Cipher               |
+        4 -> 6  | NAS Downlink Count                                            |
+        7 -> 9  | NAS Uplink Count                                              |
+        10 -> 41 | K_ASME                                                       |
+        42 -> h | Authentication Quadruplets [0..4]                             |
+        (h+1) -> k | Authentication Quintuplets [0..4]                          |
+        (k+1) -> (k+2) | DRX Parameter (If DRXI is set)                         |
+        p -> (p+31) | NH (If NHI is set)                                        |
+        p+32    | Spare                                 | NCC (If NHI is set)   |
+        (j+1) -> (j+8) | Uplink / Downlink Subscribed UE AMBR (If SAMB RI is set) |
+        (i+1) -> (i+8) | Uplink / Downlink Used UE AMBR (If UAMB RI is set)     |
+        q       | Length of UE Network Capability                               |
+        (q+1) -> r | UE Network Capability                                      |
+        r+1     | Length of MS Network Capability                               |
+        (r+2) -> s | MS Network Capability                                      |
+        s+1     | Length of MEI                                                 |
+        (s+2) -> u | MEI                                                        |
+        u+1     | Spare         | HNNA  | ENA   | INA   | GANA  | GENA  | UNA   |
+        u+2     | NHI_old | Spare               | old KSI ASME (If OSCI is set) |
+        u+3     | Spare                                 | old NCC (If NHI_old)  |
+        (u+4) -> (u+35) | old K_ASME (If OSCI is set)                           |
+        (u+36) -> (u+67) | old NH (If NHI_old is set)                           |
+        v -> n  | Voice Domain Preference etc.                                  |
+                |---------------------------------------------------------------|
+
+    The fields following the old EPS Security Context (added in later releases) are kept as extensions so that
+    they can be relayed unchanged.
+*/
+
+pub const MAX_AUTHENTICATION_VECTORS: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpsSecurityContext {
+    pub security_mode: u8,
+    pub ksi_asme: u8,
+    pub used_nas_integrity_protection_algorithm: u8,
+    pub used_nas_cipher: u8,
+    pub nas_downlink_count: u32, // 3 octets
+    pub nas_uplink_count: u32, // 3 octets
+    pub k_asme: [u8; 32],
+    pub quadruplets: Vec<AuthenticationQuadruplet>,
+    pub quintuplets: Vec<AuthenticationQuintuplet>,
+    pub drx_parameter: Option<[u8; 2]>,
+    pub nh_and_ncc: Option<([u8; 32], u8)>,
+    pub subscribed_ue_ambr: Option<(u32, u32)>, // Uplink and Downlink in kbps
+    pub used_ue_ambr: Option<(u32, u32)>, // Uplink and Downlink in kbps
+    pub ue_network_capability: Vec<u8>,
+    pub ms_network_capability: Vec<u8>,
+    pub mei: Vec<u8>,
+    pub access_restriction_data: u8,
+    pub old_eps_security_context: Option<OldEpsSecurityContext>,
+    pub extensions: Vec<u8>,
+}
+
+impl EpsSecurityContext {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.security_mode > 0x7 {
+            Err(format!("Security Mode is > 0x7 {}", self.security_mode))
+        }
+        else if self.ksi_asme > 0x7 {
+            Err(format!("KSI ASME is > 0x7 {}", self.ksi_asme))
+        }
+        else if self.used_nas_integrity_protection_algorithm > 0x7 {
+            Err(format!("Used NAS Integrity Protection Algorithm is > 0x7 {}", self.used_nas_integrity_protection_algorithm))
+        }
+        else if self.used_nas_cipher > 0xF {
+            Err(format!("Used NAS Cipher is > 0xF {}", self.used_nas_cipher))
+        }
+        else if self.nas_downlink_count > uint::MAX_U24 || self.nas_uplink_count > uint::MAX_U24 {
+            Err("NAS Counts must be <= 0xFFFFFF".to_string())
+        }
+        else if self.quadruplets.len() > MAX_AUTHENTICATION_VECTORS || self.quintuplets.len() > MAX_AUTHENTICATION_VECTORS {
+            Err(format!("At most {} Quadruplets and Quintuplets can be sent", MAX_AUTHENTICATION_VECTORS))
+        }
+        else if self.quadruplets.iter().any(|q| q.xres.len() > 0xFF || q.autn.len() > 0xFF) ||
+            self.quintuplets.iter().any(|q| q.xres.len() > 0xFF || q.autn.len() > 0xFF) {
+            Err("XRES and AUTN must be at most 255 octets".to_string())
+        }
+        else if self.nh_and_ncc.map_or(false, |(_nh, ncc)| ncc > 0x7) {
+            Err("NCC must be <= 0x7".to_string())
+        }
+        else if self.ue_network_capability.len() > 0xFF || self.ms_network_capability.len() > 0xFF || self.mei.len() > 0xFF {
+            Err("UE Network Capability, MS Network Capability and MEI must be at most 255 octets".to_string())
+        }
+        else if self.access_restriction_data > 0x3F {
+            Err(format!("Access Restriction Data is > 0x3F {}", self.access_restriction_data))
+        }
+        else if self.old_eps_security_context.as_ref().map_or(false, |o| o.ksi_asme > 0x7 || o.nh_and_ncc.map_or(false, |(_nh, ncc)| ncc > 0x7)) {
+            Err("Old KSI ASME and old NCC must be <= 0x7".to_string())
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 41 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        let security_mode = buffer[pos] >> 5;
+        let nhi = (buffer[pos] >> 4) & 0b1 == 1;
+        let drxi = (buffer[pos] >> 3) & 0b1 == 1;
+        let ksi_asme = buffer[pos] & 0b111;
+        pos = pos + 1;
+
+        let number_of_quintuplets = buffer[pos] >> 5;
+        let number_of_quadruplets = (buffer[pos] >> 2) & 0b111;
+        let uamb_ri = (buffer[pos] >> 1) & 0b1 == 1;
+        let osci = buffer[pos] & 0b1 == 1;
+        pos = pos + 1;
+
+        let samb_ri = buffer[pos] >> 7 == 1;
+        let used_nas_integrity_protection_algorithm = (buffer[pos] >> 4) & 0b111;
+        let used_nas_cipher = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        let nas_downlink_count = uint::parse_u24(&buffer[pos..])?;
+        pos = pos + 3;
+
+        let nas_uplink_count = uint::parse_u24(&buffer[pos..])?;
+        pos = pos + 3;
+
+        let k_asme = read_array(buffer, &mut pos)?;
+
+        let mut quadruplets = Vec::new();
+
+        for _ in 0..number_of_quadruplets {
+            let (quadruplet, quadruplet_pos) = AuthenticationQuadruplet::parse(&buffer[pos..])?;
+            quadruplets.push(quadruplet);
+            pos = pos + quadruplet_pos;
+        }
+
+        let mut quintuplets = Vec::new();
+
+        for _ in 0..number_of_quintuplets {
+            let (quintuplet, quintuplet_pos) = AuthenticationQuintuplet::parse(&buffer[pos..])?;
+            quintuplets.push(quintuplet);
+            pos = pos + quintuplet_pos;
+        }
+
+        let drx_parameter = if drxi { Some(read_array(buffer, &mut pos)?) } else { None };
+
+        let nh_and_ncc = if nhi {
+            let nh = read_array(buffer, &mut pos)?;
+            let ncc = read_u8(buffer, &mut pos)? & 0b111;
+            Some((nh, ncc))
+        }
+        else {
+            None
+        };
+
+        let subscribed_ue_ambr = if samb_ri { Some(read_ambr(buffer, &mut pos)?) } else { None };
+        let used_ue_ambr = if uamb_ri { Some(read_ambr(buffer, &mut pos)?) } else { None };
+
+        let ue_network_capability = read_length_and_value(buffer, &mut pos)?;
+        let ms_network_capability = read_length_and_value(buffer, &mut pos)?;
+        let mei = read_length_and_value(buffer, &mut pos)?;
+
+        let access_restriction_data = read_u8(buffer, &mut pos)? & 0x3F;
+
+        let old_eps_security_context = if osci {
+            let flags = read_u8(buffer, &mut pos)?;
+            let nhi_old = flags >> 7 == 1;
+
+            let old_ncc = if nhi_old { Some(read_u8(buffer, &mut pos)? & 0b111) } else { None };
+            let k_asme = read_array(buffer, &mut pos)?;
+
+            let nh_and_ncc = if let Some(ncc) = old_ncc { Some((read_array(buffer, &mut pos)?, ncc)) } else { None };
+
+            Some(
+                OldEpsSecurityContext {
+                    ksi_asme: flags & 0b111,
+                    k_asme,
+                    nh_and_ncc,
+                }
+            )
+        }
+        else {
+            None
+        };
+
+        let extensions = buffer[pos..].to_vec();
+        pos = buffer.len();
+
+        Some(
+            (
+                EpsSecurityContext {
+                    security_mode,
+                    ksi_asme,
+                    used_nas_integrity_protection_algorithm,
+                    used_nas_cipher,
+                    nas_downlink_count,
+                    nas_uplink_count,
+                    k_asme,
+                    quadruplets,
+                    quintuplets,
+                    drx_parameter,
+                    nh_and_ncc,
+                    subscribed_ue_ambr,
+                    used_ue_ambr,
+                    ue_network_capability,
+                    ms_network_capability,
+                    mei,
+                    access_restriction_data,
+                    old_eps_security_context,
+                    extensions,
+                },
+                pos
+            )
+        )
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        buffer[pos] = (self.security_mode & 0b111) << 5 |
+            (self.nh_and_ncc.is_some() as u8) << 4 |
+            (self.drx_parameter.is_some() as u8) << 3 |
+            (self.ksi_asme & 0b111);
+        pos = pos + 1;
+
+        buffer[pos] = (self.quintuplets.len() as u8 & 0b111) << 5 |
+            (self.quadruplets.len() as u8 & 0b111) << 2 |
+            (self.used_ue_ambr.is_some() as u8) << 1 |
+            (self.old_eps_security_context.is_some() as u8);
+        pos = pos + 1;
+
+        buffer[pos] = (self.subscribed_ue_ambr.is_some() as u8) << 7 |
+            (self.used_nas_integrity_protection_algorithm & 0b111) << 4 |
+            (self.used_nas_cipher & 0xF);
+        pos = pos + 1;
+
+        uint::generate_u24_lenient(self.nas_downlink_count, &mut buffer[pos..]);
+        pos = pos + 3;
+
+        uint::generate_u24_lenient(self.nas_uplink_count, &mut buffer[pos..]);
+        pos = pos + 3;
+
+        pos = pos + write_value(&self.k_asme, &mut buffer[pos..]);
+
+        for quadruplet in self.quadruplets.iter() {
+            pos = pos + quadruplet.generate(&mut buffer[pos..]);
+        }
+
+        for quintuplet in self.quintuplets.iter() {
+            pos = pos + quintuplet.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(drx_parameter) = self.drx_parameter {
+            pos = pos + write_value(&drx_parameter, &mut buffer[pos..]);
+        }
+
+        if let Some((nh, ncc)) = self.nh_and_ncc {
+            pos = pos + write_value(&nh, &mut buffer[pos..]);
+            buffer[pos] = ncc & 0b111;
+            pos = pos + 1;
+        }
+
+        for (uplink, downlink) in [self.subscribed_ue_ambr, self.used_ue_ambr].iter().flatten() {
+            NetworkEndian::write_u32(&mut buffer[pos..pos+4], *uplink);
+            NetworkEndian::write_u32(&mut buffer[pos+4..pos+8], *downlink);
+            pos = pos + 8;
+        }
+
+        pos = pos + write_length_and_value(&self.ue_network_capability, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.ms_network_capability, &mut buffer[pos..]);
+        pos = pos + write_length_and_value(&self.mei, &mut buffer[pos..]);
+
+        buffer[pos] = self.access_restriction_data & 0x3F;
+        pos = pos + 1;
+
+        if let Some(old) = &self.old_eps_security_context {
+            buffer[pos] = (old.nh_and_ncc.is_some() as u8) << 7 | (old.ksi_asme & 0b111);
+            pos = pos + 1;
+
+            if let Some((_nh, ncc)) = old.nh_and_ncc {
+                buffer[pos] = ncc & 0b111;
+                pos = pos + 1;
+            }
+
+            pos = pos + write_value(&old.k_asme, &mut buffer[pos..]);
+
+            if let Some((nh, _ncc)) = old.nh_and_ncc {
+                pos = pos + write_value(&nh, &mut buffer[pos..]);
+            }
+        }
+
+        pos = pos + write_value(&self.extensions, &mut buffer[pos..]);
+
+        pos
+    }
+
+    pub fn length(&self) -> usize {
+        let mut length = 3 + 3 + 3 + 32;
+
+        length = length + self.quadruplets.iter().map(|q| q.length()).sum::<usize>();
+        length = length + self.quintuplets.iter().map(|q| q.length()).sum::<usize>();
+
+        if self.drx_parameter.is_some() {
+            length = length + 2;
+        }
+
+        if self.nh_and_ncc.is_some() {
+            length = length + 33;
+        }
+
+        if self.subscribed_ue_ambr.is_some() {
+            length = length + 8;
+        }
+
+        if self.used_ue_ambr.is_some() {
+            length = length + 8;
+        }
+
+        length = length + 1 + self.ue_network_capability.len();
+        length = length + 1 + self.ms_network_capability.len();
+        length = length + 1 + self.mei.len();
+        length = length + 1; // Access Restriction Data
+
+        if let Some(old) = &self.old_eps_security_context {
+            length = length + 1 + 32;
+
+            if old.nh_and_ncc.is_some() {
+                length = length + 1 + 32;
+            }
+        }
+
+        length + self.extensions.len()
+    }
+}
+
+fn read_array<const N: usize>(buffer: &[u8], pos: &mut usize) -> Option<[u8; N]> {
+    if buffer.len() < *pos + N {
+        return None
+    }
+
+    let mut value = [0; N];
+    value.copy_from_slice(&buffer[*pos..*pos+N]);
+    *pos = *pos + N;
+
+    Some(value)
+}
+
+fn read_u8(buffer: &[u8], pos: &mut usize) -> Option<u8> {
+    let value = *buffer.get(*pos)?;
+    *pos = *pos + 1;
+
+    Some(value)
+}
+
+fn read_length_and_value(buffer: &[u8], pos: &mut usize) -> Option<Vec<u8>> {
+    let length = read_u8(buffer, pos)? as usize;
+
+    if buffer.len() < *pos + length {
+        return None
+    }
+
+    let value = buffer[*pos..*pos+length].to_vec();
+    *pos = *pos + length;
+
+    Some(value)
+}
+
+fn read_ambr(buffer: &[u8], pos: &mut usize) -> Option<(u32, u32)> {
+    let ambr = read_array::<8>(buffer, pos)?;
+
+    Some((NetworkEndian::read_u32(&ambr[0..4]), NetworkEndian::read_u32(&ambr[4..8])))
+}
+
+fn write_value(value: &[u8], buffer: &mut[u8]) -> usize {
+    buffer[..value.len()].copy_from_slice(value);
+    value.len()
+}
+
+fn write_length_and_value(value: &[u8], buffer: &mut[u8]) -> usize {
+    buffer[0] = value.len() as u8;
+    1 + write_value(value, &mut buffer[1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets, &[0x12, 0x34, 0x56], 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::MMContextEPSSecurityContextQuadrupletsAndQuintuplets as u8,
+            0, 3, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, // MM Context
+        ]);
+
+        if let Ok(_) = InformationElement::new(InformationElementType::Cause, &[], 0) {
+            // This should fail Cause isn't an MM Context
+            assert!(false);
+        }
+        else {
+            assert!(true);
+        }
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(InformationElementType::MMContextGSMKeyAndTriplets, &[0x12, 0x34, 0x56], 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 7);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(InformationElementType::MMContextUMTSKeyAndQuintuplets, &[], 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::MMContextUMTSKeyAndQuintuplets as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::MMContextUMTSKeyQuadrupletsAndQuintuplets as u8,
+            0, 3, // Length
+            0, // Spare
+            0x12, 0x34, 0x56, // MM Context
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.information_element_type(), InformationElementType::MMContextUMTSKeyQuadrupletsAndQuintuplets);
+            assert_eq!(ie.mm_context, vec![0x12, 0x34, 0x56]);
+            assert_eq!(pos, 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated IE
+        assert!(InformationElement::parse(&ie_bytes[..6]).is_none());
+    }
+
+    fn eps_security_context() -> EpsSecurityContext {
+        EpsSecurityContext {
+            security_mode: 4,
+            ksi_asme: 3,
+            used_nas_integrity_protection_algorithm: 2,
+            used_nas_cipher: 1,
+            nas_downlink_count: 0x123456,
+            nas_uplink_count: 0x654321,
+            k_asme: [0xAA; 32],
+            quadruplets: vec![
+                AuthenticationQuadruplet {
+                    rand: [0x11; 16],
+                    xres: vec![0x22; 8],
+                    autn: vec![0x33; 16],
+                    k_asme: [0x44; 32],
+                }
+            ],
+            quintuplets: vec![],
+            drx_parameter: Some([0x0A, 0x0B]),
+            nh_and_ncc: Some(([0xBB; 32], 5)),
+            subscribed_ue_ambr: Some((100_000, 200_000)),
+            used_ue_ambr: None,
+            ue_network_capability: vec![0xE0, 0xE0],
+            ms_network_capability: vec![],
+            mei: vec![0x53, 0x19, 0x00, 0x29, 0x36, 0x22, 0x80, 0x09],
+            access_restriction_data: 0b10_0001,
+            old_eps_security_context: None,
+            extensions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_eps_security_context() {
+        let context = eps_security_context();
+
+        let ie = InformationElement::for_eps_security_context(&context, 0).unwrap();
+
+        assert_eq!(ie.mm_context.len(), context.length());
+        assert_eq!(ie.mm_context[..3], [
+            0b100_1_1_011, // Security Mode, NHI, DRXI and KSI ASME
+            0b000_001_0_0, // Quintuplets, Quadruplets, UAMB RI and OSCI
+            0b1_010_0001, // SAMB RI, Used NAS Integrity Protection Algorithm and Used NAS Cipher
+        ]);
+        assert_eq!(ie.mm_context[3..9], [0x12, 0x34, 0x56, 0x65, 0x43, 0x21]);
+
+        assert_eq!(ie.eps_security_context(), Some(context));
+
+        // Only the EPS Security Context MM Context can be decoded
+        let ie = InformationElement::new(InformationElementType::MMContextUMTSKeyAndQuintuplets, &ie.mm_context, 0).unwrap();
+        assert_eq!(ie.eps_security_context(), None);
+    }
+
+    #[test]
+    fn test_eps_security_context_optional_fields() {
+        let mut context = eps_security_context();
+
+        context.quadruplets = vec![];
+        context.quintuplets = vec![
+            AuthenticationQuintuplet {
+                rand: [0x11; 16],
+                xres: vec![0x22; 4],
+                ck: [0x33; 16],
+                ik: [0x44; 16],
+                autn: vec![0x55; 16],
+            };
+            2
+        ];
+        context.drx_parameter = None;
+        context.nh_and_ncc = None;
+        context.subscribed_ue_ambr = None;
+        context.used_ue_ambr = Some((1, 2));
+        context.old_eps_security_context = Some(
+            OldEpsSecurityContext {
+                ksi_asme: 2,
+                k_asme: [0xCC; 32],
+                nh_and_ncc: Some(([0xDD; 32], 1)),
+            }
+        );
+        context.extensions = vec![1, 0x12];
+
+        let mut buffer = [0; MTU];
+        let pos = context.generate(&mut buffer);
+
+        assert_eq!(pos, context.length());
+
+        if let Some((parsed, parsed_pos)) = EpsSecurityContext::parse(&buffer[..pos]) {
+            assert_eq!(parsed, context);
+            assert_eq!(parsed_pos, pos);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated before the Access Restriction Data
+        let length = pos - context.extensions.len() - 1 - 32 - 1 - 32 - 1 - 1;
+        assert!(EpsSecurityContext::parse(&buffer[..length]).is_none());
+    }
+
+    #[test]
+    fn test_eps_security_context_invalid() {
+        let mut context = eps_security_context();
+        context.ksi_asme = 8;
+        assert!(InformationElement::for_eps_security_context(&context, 0).is_err());
+
+        let mut context = eps_security_context();
+        context.nas_uplink_count = 0x1000000;
+        assert!(InformationElement::for_eps_security_context(&context, 0).is_err());
+
+        let mut context = eps_security_context();
+        context.quadruplets = vec![context.quadruplets[0].clone(); 5];
+        assert!(InformationElement::for_eps_security_context(&context, 0).is_err());
+    }
+}
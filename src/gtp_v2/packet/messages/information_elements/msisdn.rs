@@ -1,9 +1,20 @@
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryInto;
+use crate::dissect::{Dissect, Dissector};
+use crate::tbcd;
+
+use core::convert::TryInto;
+use core::fmt;
+use core::str::FromStr;
 
 use byteorder::{ByteOrder, NetworkEndian};
 
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -25,35 +36,35 @@ pub struct InformationElement {
     pub msisdn: Vec<u8>,
 }
 
+pub const MAX_DIGITS: usize = 15;
+
 impl InformationElement {
     pub fn new(msisdn: &str, instance: u8) -> Result<Self, String> {
+        // An MSISDN is an E.164 number so is at most 15 digits
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if msisdn.is_empty() || msisdn.len() > MAX_DIGITS {
+            Err("MSISDN is not the correct number of digits".to_string())
+        }
         else {
-            let mut parsed_msisdn:Vec<u8> = Vec::new();
-
-            for c in msisdn.chars() {
-                let digit = c.to_string().parse::<u8>();
-    
-                let digit = match digit {
-                    Ok(n) => n,
-                    Err(_e) => {
-                        return Err("Could not parse MSISDN".to_string())
-                    }
-                };
-    
-                parsed_msisdn.push(digit);
+            match tbcd::parse_digits(msisdn) {
+                Ok(msisdn) => {
+                    Ok(InformationElement {
+                        msisdn,
+                        instance
+                    })
+                },
+                Err(_e) => Err("Could not parse MSISDN".to_string())
             }
-    
-            Ok(InformationElement {
-                msisdn: parsed_msisdn,
-                instance
-            })
         }
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -68,20 +79,13 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        let mut msisdn: Vec<u8> = Vec::new();
-
-        while pos < (length+4) as usize {
-            let first_digit = buffer[pos] & 0xF;
-            let second_digit = (buffer[pos] >> 4) & 0xF;
-
-            msisdn.push(first_digit);
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
 
-            if second_digit != 0xF {
-                msisdn.push(second_digit);
-            }
+        let msisdn = tbcd::decode(&buffer[pos..(length+4) as usize])?;
 
-            pos = pos + 1;
-        }
+        pos = pos + length as usize;
 
         Some(
             (
@@ -95,26 +99,21 @@ impl InformationElement {
     }
 
     pub fn generate_tbcd_msisdn(&self) -> Vec<u8> {
-        let mut tbcd_msisdn: Vec<u8> = Vec::new();
-
-        let mut msisdn_index = 0;
+        tbcd::encode(&self.msisdn)
+    }
+}
 
-        while msisdn_index < self.msisdn.len() {
-            let mut tbcd_digit = self.msisdn[msisdn_index] & 0xF;
-            msisdn_index = msisdn_index + 1;
+impl FromStr for InformationElement {
+    type Err = String;
 
-            if msisdn_index < self.msisdn.len() {
-                tbcd_digit = tbcd_digit | ((self.msisdn[msisdn_index] & 0xF) << 4);
-                msisdn_index = msisdn_index + 1;
-            }
-            else {
-                // Top nibble of odd length MSISDN should be 0xF
-                tbcd_digit = tbcd_digit | 0xF0;
-            }
-            tbcd_msisdn.push(tbcd_digit);
-        }
+    fn from_str(msisdn: &str) -> Result<Self, Self::Err> {
+        Self::new(msisdn, 0)
+    }
+}
 
-        tbcd_msisdn
+impl fmt::Display for InformationElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tbcd::to_string(&self.msisdn))
     }
 }
 
@@ -137,8 +136,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        (4 + (self.msisdn.len() / 2) + (self.msisdn.len() % 2)).try_into().unwrap()
+    fn payload_length(&self) -> u16 {
+        tbcd::length(&self.msisdn).try_into().unwrap()
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -149,25 +148,27 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        let tbcd_msisdn = self.generate_tbcd_msisdn();
-
-        for i in 0..tbcd_msisdn.len() {
-            buffer[pos] = tbcd_msisdn[i];
-            pos = pos + 1;
-        }
+        pos = pos + tbcd::generate(&self.msisdn, &mut buffer[pos..]);
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("MSISDN", self);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,6 +202,33 @@ mod tests {
                 assert_eq!(e, "Could not parse MSISDN".to_string())
             }
         }
+
+        let msisdn_invalid = "6144455566677788";
+        let msisdn_ie = InformationElement::new(msisdn_invalid, 0);
+
+        match msisdn_ie {
+            Ok(_) => {
+                assert_eq!(false, true)
+            }
+            Err(e) => {
+                assert_eq!(e, "MSISDN is not the correct number of digits".to_string())
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let msisdn_ie = "61123456789".parse::<InformationElement>();
+
+        if let Ok(i) = msisdn_ie {
+            assert_eq!(i.msisdn, [6, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+            assert_eq!(i.to_string(), "61123456789");
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!("".parse::<InformationElement>().is_err());
     }
 
     #[test]
@@ -260,7 +288,7 @@ mod tests {
         let ie = InformationElement::new(msisdn, 0);
 
         if let Ok(i) = ie {
-            assert_eq!(i.length(), 6+4)
+            assert_eq!(i.wire_length(), 6+4)
         }
         else {
             assert_eq!(false, true)
@@ -269,7 +297,7 @@ mod tests {
         let ie = InformationElement::new(msisdn, 0);
 
         if let Ok(i) = ie {
-            assert_eq!(i.length(), 6+4)
+            assert_eq!(i.wire_length(), 6+4)
         }
         else {
             assert_eq!(false, true)
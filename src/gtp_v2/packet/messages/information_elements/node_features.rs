@@ -0,0 +1,243 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+// Supported-Features bits (TS 29.274 Table 8.83-1)
+pub const PRN: u8 = 0b0000_0001; // PGW Restart Notification
+pub const MABR: u8 = 0b0000_0010; // Modify Access Bearers Request
+pub const NTSR: u8 = 0b0000_0100; // Network Triggered Service Restoration
+pub const CIOT: u8 = 0b0000_1000; // Cellular Internet of Things
+pub const S1UN: u8 = 0b0001_0000; // S1-U path failure notification
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (152)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                 | S1UN  | CIOT  | NTSR  | MABR  | PRN   |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    pub supported_features: u8,
+}
+
+impl InformationElement {
+    pub fn new(supported_features: u8, instance: u8) -> Result<Self, String> {
+        // supported_features is a combination of PRN, MABR, NTSR, CIOT and S1UN
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    supported_features,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        // Any octets after the first are only present if explicitly specified and are ignored
+        let supported_features = buffer[pos];
+
+        Some(
+            (
+                InformationElement {
+                    supported_features,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn supports(&self, feature: u8) -> bool {
+        self.supported_features & feature == feature
+    }
+
+    pub fn prn(&self) -> bool {
+        self.supports(PRN)
+    }
+
+    pub fn mabr(&self) -> bool {
+        self.supports(MABR)
+    }
+
+    pub fn ntsr(&self) -> bool {
+        self.supports(NTSR)
+    }
+
+    pub fn ciot(&self) -> bool {
+        self.supports(CIOT)
+    }
+
+    pub fn s1un(&self) -> bool {
+        self.supports(S1UN)
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::NodeFeatures
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        1
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.supported_features;
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Supported Features", format!("{:#04x}", self.supported_features));
+            d.flag("PGW Restart Notification (PRN)", self.prn());
+            d.flag("Modify Access Bearers Request (MABR)", self.mabr());
+            d.flag("Network Triggered Service Restoration (NTSR)", self.ntsr());
+            d.flag("Cellular Internet of Things (CIOT)", self.ciot());
+            d.flag("S1-U Path Failure Notification (S1UN)", self.s1un());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(PRN | NTSR | S1UN, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::NodeFeatures as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0001_0101 // Supported Features
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(PRN, 0).unwrap();
+        assert_eq!(ie.wire_length(), 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(PRN, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::NodeFeatures as u8)
+    }
+
+    #[test]
+    fn test_features() {
+        let ie = InformationElement::new(MABR | CIOT, 0).unwrap();
+
+        assert!(!ie.prn());
+        assert!(ie.mabr());
+        assert!(!ie.ntsr());
+        assert!(ie.ciot());
+        assert!(!ie.s1un());
+        assert!(ie.supports(MABR | CIOT));
+        assert!(!ie.supports(MABR | PRN));
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::NodeFeatures as u8,
+            0, 2, // Length
+            0, // Spare
+            0b1000_1010, // Supported Features
+            0xFF, // Additional octet
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.supported_features, 0b1000_1010);
+            assert!(ie.mabr());
+            assert!(ie.ciot());
+            assert!(!ie.prn());
+            assert_eq!(pos, 6);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..4]).is_none());
+    }
+}
@@ -0,0 +1,307 @@
+extern crate ascii;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use ascii::{AsciiString, ToAsciiChar};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (176)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Length of Node Name                                           |
+        6 -> m  | Node Name                                                     |
+        m+1     | Length of Node Realm                                          |
+        m+2 ->n | Node Realm                                                    |
+                |---------------------------------------------------------------|
+
+        The Node Name and Node Realm are the Diameter Identity and Realm of the node (TS 29.274 8.107) e.g. the
+        3GPP AAA server or the MME/S4-SGSN. Octets after the Node Realm are ignored.
+    */
+
+    instance: u8,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
+    pub node_name: AsciiString,
+    #[cfg_attr(feature = "fuzzing", arbitrary(with = crate::fuzzing::arbitrary_ascii_string))]
+    pub node_realm: AsciiString,
+}
+
+fn parse_name(buffer: &[u8]) -> Option<(AsciiString, usize)> {
+    // A length octet followed by that many ASCII octets
+    if buffer.is_empty() || buffer.len() < buffer[0] as usize + 1 {
+        return None
+    }
+
+    let length = buffer[0] as usize;
+
+    let mut name = AsciiString::new();
+
+    for b in buffer[1..length+1].iter() {
+        if let Ok(ch) = b.to_ascii_char() {
+            name.push(ch)
+        }
+        else {
+            return None
+        }
+    }
+
+    Some((name, length + 1))
+}
+
+fn generate_name(name: &AsciiString, buffer: &mut[u8]) -> usize {
+    let mut pos = 0;
+
+    buffer[pos] = name.len() as u8;
+    pos = pos + 1;
+
+    for ch in name.chars() {
+        buffer[pos] = ch.as_byte();
+        pos = pos + 1;
+    }
+
+    pos
+}
+
+impl InformationElement {
+    pub fn new(node_name: AsciiString, node_realm: AsciiString, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if node_name.is_empty() || node_name.len() > 0xFF {
+            Err(format!("Node Name must be between 1 and 255 octets long {}", node_name.len()))
+        }
+        else if node_realm.is_empty() || node_realm.len() > 0xFF {
+            Err(format!("Node Realm must be between 1 and 255 octets long {}", node_realm.len()))
+        }
+        else {
+            Ok(InformationElement {
+                node_name,
+                node_realm,
+                instance,
+            })
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let end = length as usize + 4;
+
+        let (node_name, name_length) = parse_name(&buffer[pos..end])?;
+        pos = pos + name_length;
+
+        let (node_realm, _realm_length) = parse_name(&buffer[pos..end])?;
+
+        Some(
+            (
+                InformationElement {
+                    node_name,
+                    node_realm,
+                    instance,
+                },
+                end
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::NodeIdentifier
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        (1 + self.node_name.len() + 1 + self.node_realm.len()) as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + generate_name(&self.node_name, &mut buffer[pos..]);
+        pos = pos + generate_name(&self.node_realm, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Node Name", &self.node_name);
+            d.field("Node Realm", &self.node_realm);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_ie() -> InformationElement {
+        InformationElement::new(
+            AsciiString::from_ascii("aaa1").unwrap(),
+            AsciiString::from_ascii("epc.org").unwrap(),
+            1
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let name = AsciiString::from_ascii("aaa1").unwrap();
+        let realm = AsciiString::from_ascii("epc.org").unwrap();
+
+        assert!(InformationElement::new(name.clone(), realm.clone(), 0).is_ok());
+        assert!(InformationElement::new(name.clone(), realm.clone(), 0x10).is_err());
+        assert!(InformationElement::new(AsciiString::new(), realm.clone(), 0).is_err());
+        assert!(InformationElement::new(name.clone(), AsciiString::new(), 0).is_err());
+
+        let long = AsciiString::from_ascii([b'a'; 256].as_ref()).unwrap();
+        assert!(InformationElement::new(long.clone(), realm, 0).is_err());
+        assert!(InformationElement::new(name, long, 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_ie().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::NodeIdentifier as u8,
+            0, 13, // Length
+            1, // Spare and Instance
+            4, // Length of Node Name
+            b'a', b'a', b'a', b'1',
+            7, // Length of Node Realm
+            b'e', b'p', b'c', b'.', b'o', b'r', b'g',
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_ie().wire_length(), 13+4);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_ie().information_element_type() as u8, InformationElementType::NodeIdentifier as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::NodeIdentifier as u8,
+            0, 13, // Length
+            1, // Spare and Instance
+            4, // Length of Node Name
+            b'a', b'a', b'a', b'1',
+            7, // Length of Node Realm
+            b'e', b'p', b'c', b'.', b'o', b'r', b'g',
+            0, 0, 0, // Next IE
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie, new_ie());
+            assert_eq!(pos, 17);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_trailing_octets() {
+        let ie_bytes = [InformationElementType::NodeIdentifier as u8,
+            0, 8, // Length
+            0, // Spare and Instance
+            2, // Length of Node Name
+            b'n', b'1',
+            2, // Length of Node Realm
+            b'r', b'1',
+            0xAA, 0xBB, // Octets after the Node Realm
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.node_name, AsciiString::from_ascii("n1").unwrap());
+            assert_eq!(ie.node_realm, AsciiString::from_ascii("r1").unwrap());
+            assert_eq!(pos, 12);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_invalid() {
+        // The Node Name is longer than the IE
+        assert!(InformationElement::parse(&[InformationElementType::NodeIdentifier as u8, 0, 3, 0, 5, b'n', b'1']).is_none());
+
+        // There's no Node Realm
+        assert!(InformationElement::parse(&[InformationElementType::NodeIdentifier as u8, 0, 3, 0, 2, b'n', b'1']).is_none());
+
+        // Truncated
+        assert!(InformationElement::parse(&[InformationElementType::NodeIdentifier as u8, 0, 10, 0, 2, b'n', b'1']).is_none());
+    }
+}
@@ -0,0 +1,267 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+pub const MAX_PAGING_POLICY_INDICATION: u8 = 0x3F;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (186)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                         | EPS Bearer ID                 |
+        6       | Spare                                                 | PPI   |
+        7       | Spare         | Paging Policy Indication value                |
+                |---------------------------------------------------------------|
+
+        Octet 7 is only present if PPI is 1. The Paging Policy Indication value is the DSCP of the downlink packet
+        that triggered the Downlink Data Notification (TS 29.274 8.123)
+    */
+
+    instance: u8,
+    pub eps_bearer_id: u8,
+    pub paging_policy_indication: Option<u8>,
+}
+
+impl InformationElement {
+    pub fn new(eps_bearer_id: u8, paging_policy_indication: Option<u8>, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if eps_bearer_id > 0xF {
+            Err(format!("EPS Bearer ID is > 0xF {}", eps_bearer_id))
+        }
+        else if paging_policy_indication.map_or(false, |ppi| ppi > MAX_PAGING_POLICY_INDICATION) {
+            Err(format!("Paging Policy Indication is > {:#x} {:?}", MAX_PAGING_POLICY_INDICATION, paging_policy_indication))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    eps_bearer_id,
+                    paging_policy_indication,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let eps_bearer_id = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        let ppi = buffer[pos] & 0b1;
+        pos = pos + 1;
+
+        let paging_policy_indication = if ppi == 1 {
+            if length < 3 {
+                // PPI is set but the value is missing
+                return None
+            }
+
+            Some(buffer[pos] & MAX_PAGING_POLICY_INDICATION)
+        }
+        else {
+            None
+        };
+
+        Some(
+            (
+                InformationElement {
+                    eps_bearer_id,
+                    paging_policy_indication,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::PagingAndServiceInformation
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        match self.paging_policy_indication {
+            Some(_) => 3,
+            None => 2,
+        }
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.eps_bearer_id & 0xF;
+        pos = pos + 1;
+
+        match self.paging_policy_indication {
+            Some(ppi) => {
+                buffer[pos] = 1;
+                pos = pos + 1;
+
+                buffer[pos] = ppi & MAX_PAGING_POLICY_INDICATION;
+                pos = pos + 1;
+            },
+            None => {
+                buffer[pos] = 0;
+                pos = pos + 1;
+            }
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("EPS Bearer ID", self.eps_bearer_id);
+
+            if let Some(ppi) = self.paging_policy_indication {
+                d.field("Paging Policy Indication", ppi);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(5, Some(0x3F), 0xF).is_ok());
+        assert!(InformationElement::new(5, None, 0x10).is_err());
+        assert!(InformationElement::new(0x10, None, 0).is_err());
+        assert!(InformationElement::new(5, Some(0x40), 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(5, Some(0x2E), 1).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PagingAndServiceInformation as u8,
+            0, 3, // Length
+            1, // Spare and Instance
+            5, // EPS Bearer ID
+            1, // PPI
+            0x2E, // Paging Policy Indication value
+        ]);
+
+        let ie = InformationElement::new(6, None, 0).unwrap();
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PagingAndServiceInformation as u8,
+            0, 2, // Length
+            0, // Spare and Instance
+            6, // EPS Bearer ID
+            0, // PPI
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(InformationElement::new(5, Some(0x2E), 0).unwrap().wire_length(), 7);
+        assert_eq!(InformationElement::new(5, None, 0).unwrap().wire_length(), 6);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(5, None, 0).unwrap();
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::PagingAndServiceInformation as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::PagingAndServiceInformation as u8,
+            0, 3, // Length
+            1, // Spare and Instance
+            0xF5, // Spare and EPS Bearer ID
+            0xFF, // Spare and PPI
+            0xEE, // Spare and Paging Policy Indication value
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 7);
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(ie.eps_bearer_id, 5);
+            assert_eq!(ie.paging_policy_indication, Some(0x2E));
+        }
+        else {
+            assert!(false);
+        }
+
+        // PPI is set without the value
+        assert!(InformationElement::parse(&[InformationElementType::PagingAndServiceInformation as u8, 0, 2, 0, 5, 1]).is_none());
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..6]).is_none());
+    }
+}
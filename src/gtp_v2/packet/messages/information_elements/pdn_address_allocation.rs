@@ -1,13 +1,20 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
 
 use super::pdn_type::PDNType;
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use alloc::format;
+use alloc::string::{String, ToString};
 
-use std::convert::TryFrom;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -60,6 +67,9 @@ impl InformationElement {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if let PDNType::Other(value) = pdn_type {
+            Err(format!("Unknown PDN Type {}", value))
+        }
         else if ( pdn_type == PDNType::IPv4 || pdn_type == PDNType::IPv4v6) && ipv4_address.is_none() {
             Err("ipv4_address must be defined when pdn_type is IPv4 or IPv4v6.".to_string())
         }
@@ -81,6 +91,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -95,58 +109,58 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(pdn_type) = PDNType::try_from(buffer[pos]) {
-            pos = pos + 1;
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
 
-            let ipv6_address_and_prefix: Option<(Ipv6Addr, u8)> = 
-                if pdn_type == PDNType::IPv6 || pdn_type == PDNType::IPv4v6 {
-                    let prefix = buffer[pos];
-                    pos = pos + 1;
-
-                    Some(
-                        (
-                            Ipv6Addr::new(
-                                NetworkEndian::read_u16(&buffer[pos..pos+2]),
-                                NetworkEndian::read_u16(&buffer[pos+2..pos+4]),
-                                NetworkEndian::read_u16(&buffer[pos+4..pos+6]),
-                                NetworkEndian::read_u16(&buffer[pos+6..pos+8]),
-                                NetworkEndian::read_u16(&buffer[pos+8..pos+10]),
-                                NetworkEndian::read_u16(&buffer[pos+10..pos+12]),
-                                NetworkEndian::read_u16(&buffer[pos+12..pos+14]),
-                                NetworkEndian::read_u16(&buffer[pos+14..pos+16]),
-                            ),
-                            prefix
-                        )
-                    )
-                }
-                else {
-                    None
-                };
+        let pdn_type = PDNType::from(buffer[pos] & 0b111); // The upper 5 bits are spare
+        pos = pos + 1;
 
-            if ipv6_address_and_prefix.is_some() {pos = pos + 16}
+        let address_length = match pdn_type {
+            PDNType::IPv4 => 4,
+            PDNType::IPv6 => 17,
+            PDNType::IPv4v6 => 21,
+            PDNType::NonIp => 0,
+            PDNType::Other(_) => return None, // The layout of the PDN Address is unknown
+        };
 
-            let ipv4_address: Option<Ipv4Addr> = 
-            if pdn_type == PDNType::IPv4 || pdn_type == PDNType::IPv4v6 {
-                Some(Ipv4Addr::new(buffer[pos], buffer[pos+1], buffer[pos+2], buffer[pos+3]))
+        if pos + address_length > (length + 4) as usize {
+            return None
+        }
+
+        let ipv6_address_and_prefix: Option<(Ipv6Addr, u8)> = 
+            if pdn_type == PDNType::IPv6 || pdn_type == PDNType::IPv4v6 {
+                let prefix = buffer[pos];
+                pos = pos + 1;
+
+                address::parse_ipv6(&buffer[pos..]).map(|a| (a, prefix))
             }
             else {
                 None
             };
 
+        if ipv6_address_and_prefix.is_some() {pos = pos + 16}
+
+        let ipv4_address: Option<Ipv4Addr> = 
+        if pdn_type == PDNType::IPv4 || pdn_type == PDNType::IPv4v6 {
+            address::parse_ipv4(&buffer[pos..])
+        }
+        else {
+            None
+        };
+
         if ipv4_address.is_some() {/*pos = pos + 4*/}
-            Some(
-                (
-                    InformationElement {
-                        pdn_type,
-                        ipv4_address,
-                        ipv6_address_and_prefix,
-                        instance: instance,
-                    },
-                    (length + 4) as usize
-                )
+        Some(
+            (
+                InformationElement {
+                    pdn_type,
+                    ipv4_address,
+                    ipv6_address_and_prefix,
+                    instance: instance,
+                },
+                (length + 4) as usize
             )
-        }
-        else { None }
+        )
     }
 }
 
@@ -169,14 +183,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        let mut length = 4; // IE Headers
-
-        length = length 
-            + 1; // PDN Type
+    fn payload_length(&self) -> u16 {
+        let mut length = 1; // PDN Type
 
         if let Some(_) = self.ipv6_address_and_prefix
         {
@@ -198,37 +206,46 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = (self.pdn_type as u8) & 0b111;
+        buffer[pos] = (u8::from(self.pdn_type)) & 0b111;
         pos = pos + 1;
 
         if let Some((a, prefix)) = self.ipv6_address_and_prefix {
             buffer[pos] = prefix;
             pos = pos + 1;
-            for o in a.octets().iter() {
-                buffer[pos] = *o;
-                pos = pos + 1;
-            }
+            pos = pos + address::generate_ipv6(a, &mut buffer[pos..]);
         }
 
         if let Some(a) = self.ipv4_address {
-            for o in a.octets().iter() {
-                buffer[pos] = *o;
-                pos = pos + 1;
-            }
+            pos = pos + address::generate_ipv4(a, &mut buffer[pos..]);
         }
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("PDN Type", format!("{:?} ({})", self.pdn_type, u8::from(self.pdn_type)));
+
+            if let Some(address) = self.ipv4_address {
+                d.field("IPv4 Address", address);
+            }
+
+            if let Some((address, prefix_length)) = self.ipv6_address_and_prefix {
+                d.field("IPv6 Address", format!("{}/{}", address, prefix_length));
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,7 +293,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::PDNAddressAllocation as u8,
             0, 5, // Length
             0, // Spare
-            PDNType::IPv4 as u8, // PDN Type
+            u8::from(PDNType::IPv4), // PDN Type
             10,0,0,1,
         ]);
 
@@ -289,7 +306,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::PDNAddressAllocation as u8,
             0, 18, // Length
             0, // Spare
-            PDNType::IPv6 as u8, // PDN Type
+            u8::from(PDNType::IPv6), // PDN Type
             128, // Prefix
             0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE, // IPv6 Address
         ]);
@@ -303,7 +320,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::PDNAddressAllocation as u8,
             0, 22, // Length
             0, // Spare
-            PDNType::IPv4v6 as u8, // PDN Type
+            u8::from(PDNType::IPv4v6), // PDN Type
             128, // Prefix
             0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE, // IPv6 Address
             10,0,0,1,
@@ -318,7 +335,7 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::PDNAddressAllocation as u8,
             0, 1, // Length
             0, // Spare
-            PDNType::NonIp as u8, // PDN Type
+            u8::from(PDNType::NonIp), // PDN Type
         ]);
     }
 
@@ -329,7 +346,7 @@ mod tests {
             Some(Ipv4Addr::new(10,0,0,1)), 
             Some((Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0xFEED, 0xDEAF, 0xBEAD, 0xFACE), 128)),
             0).unwrap();
-        assert_eq!(ie.length(), 26);
+        assert_eq!(ie.wire_length(), 26);
     }
 
     #[test]
@@ -346,9 +363,9 @@ mod tests {
     #[test]
     fn test_message_parse() {
         let ie_bytes = [InformationElementType::PDNAddressAllocation as u8,
-        0, 17, // Length
+        0, 22, // Length
         0, // Spare
-        PDNType::IPv4v6 as u8, // PDN Type
+        u8::from(PDNType::IPv4v6), // PDN Type
         128, // Prefix
         0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE, // IPv6 Address
         10,0,0,1, // IPv4 Address
@@ -364,4 +381,17 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_unknown_pdn_type() {
+        let ie_bytes = [InformationElementType::PDNAddressAllocation as u8,
+            0, 5, // Length
+            0, // Spare
+            6, // PDN Type
+            10,0,0,1,
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+        assert!(InformationElement::new(PDNType::Other(6), None, None, 0).is_err());
+    }
+}
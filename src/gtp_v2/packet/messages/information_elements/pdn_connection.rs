@@ -0,0 +1,592 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{
+    InformationElementTraits,
+    InformationElementType,
+    LENGTH,
+    dissect_ie,
+    apn,
+    apn_restriction,
+    selection_mode,
+    ip_address,
+    ebi,
+    f_teid,
+    fqdn,
+    bearer_context,
+    ambr,
+    charging_characteristics,
+};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::bitrate::Bitrate;
+use crate::gtp_v2::interworking::{PduSession, PduSessionType, QosFlow};
+
+use super::grouped_ie::GroupedIe;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use core::net::IpAddr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (109)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | IEs                                                           |
+                |---------------------------------------------------------------|
+
+        ----------------------------|---------------------------|---------------|------------------------
+        Information Element         |   Presence requirement    | Instance      |  Reference
+        ----------------------------|---------------------------|---------------|------------------------
+        APN                         |   Mandatory               | 0             | 8.6
+        APN Restriction             |   Conditional             | 0             | 8.57
+        Selection Mode              |   Conditional Optional    | 0             | 8.58
+        IPv4 Address                |   Conditional             | 0             | 8.9
+        IPv6 Address                |   Conditional             | 1             | 8.9
+        Linked EPS Bearer ID        |   Mandatory               | 0             | 8.8
+        PGW S5/S8 F-TEID for CP     |   Mandatory               | 0             | 8.22
+        PGW node name               |   Conditional             | 0             | 8.66
+        Bearer Contexts             |   Mandatory               | 0             | 8.28
+        Aggregate Maximum Bitrate   |   Mandatory               | 0             | 8.7
+        Charging characteristics    |   Conditional             | 0             | 8.30
+        Change Reporting Action     |   Conditional             | 0             |
+        CSG Information Reporting   |   Conditional Optional    | 0             |
+        H(e)NB Information Reporting|   Conditional Optional    | 0             |
+        Indication Flags            |   Conditional Optional    | 0             |
+        Signalling Priority Ind.    |   Conditional Optional    | 0             |
+        Change to Report Flags      |   Conditional Optional    | 0             |
+        Local Home Network ID       |   Conditional Optional    | 0             |
+        Presence Reporting Area     |   Conditional Optional    | 0             |
+        WLAN Offloadability Ind.    |   Conditional Optional    | 0             |
+        Remote UE Context Connected |   Conditional Optional    | 0             |
+        PDN Type                    |   Conditional Optional    | 0             |
+        Header Compression Config.  |   Conditional Optional    | 0             |
+        ----------------------------|---------------------------|---------------|------------------------
+
+        The Bearer Contexts in a PDN Connection use the instances from Table 8.39-2 (e.g. instance 0 is the SGW S1/S4/S12
+        F-TEID) rather than the instances used in a Create Session Request
+    */
+
+    instance: u8,
+    pub apn: apn::InformationElement,
+    pub apn_restriction: Option<apn_restriction::InformationElement>,
+    pub selection_mode: Option<selection_mode::InformationElement>,
+    pub ipv4_address: Option<ip_address::InformationElement>,
+    pub ipv6_address: Option<ip_address::InformationElement>,
+    pub linked_eps_bearer_id: ebi::InformationElement,
+    pub pgw_s5_s8_address_for_control_plane: f_teid::InformationElement,
+    pub pgw_node_name: Option<fqdn::InformationElement>,
+    pub bearer_contexts: Vec<bearer_context::InformationElement>,
+    pub apn_ambr: ambr::InformationElement,
+    pub charging_characteristics: Option<charging_characteristics::InformationElement>,
+}
+
+impl InformationElement {
+    pub fn new(
+        apn: apn::InformationElement,
+        linked_eps_bearer_id: ebi::InformationElement,
+        pgw_s5_s8_address_for_control_plane: f_teid::InformationElement,
+        bearer_contexts: Vec<bearer_context::InformationElement>,
+        apn_ambr: ambr::InformationElement,
+        instance: u8,
+    ) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if bearer_contexts.is_empty() {
+            Err("PDN Connection must have at least one Bearer Context".to_string())
+        }
+        else {
+            Ok(
+                InformationElement {
+                    apn,
+                    apn_restriction: None,
+                    selection_mode: None,
+                    ipv4_address: None,
+                    ipv6_address: None,
+                    linked_eps_bearer_id,
+                    pgw_s5_s8_address_for_control_plane,
+                    pgw_node_name: None,
+                    bearer_contexts,
+                    apn_ambr,
+                    charging_characteristics: None,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        // All of the IEs in a PDN Connection are instance 0 except the IPv6 Address
+        let (mut group, pos) = GroupedIe::parse(buffer)?;
+
+        let apn: Option<apn::InformationElement> = group.ies.take(0);
+        let linked_eps_bearer_id: Option<ebi::InformationElement> = group.ies.take(0);
+        let pgw_s5_s8_address_for_control_plane: Option<f_teid::InformationElement> = group.ies.take(0);
+        let bearer_contexts: Vec<bearer_context::InformationElement> = group.ies.take_all(0);
+        let apn_ambr: Option<ambr::InformationElement> = group.ies.take(0);
+
+        if apn.is_some() &&
+            linked_eps_bearer_id.is_some() &&
+            pgw_s5_s8_address_for_control_plane.is_some() &&
+            !bearer_contexts.is_empty() &&
+            apn_ambr.is_some() {
+            Some(
+                (
+                    InformationElement {
+                        apn: apn.unwrap(),
+                        apn_restriction: group.ies.take(0),
+                        selection_mode: group.ies.take(0),
+                        ipv4_address: group.ies.take(0),
+                        ipv6_address: group.ies.take(1),
+                        linked_eps_bearer_id: linked_eps_bearer_id.unwrap(),
+                        pgw_s5_s8_address_for_control_plane: pgw_s5_s8_address_for_control_plane.unwrap(),
+                        pgw_node_name: group.ies.take(0),
+                        bearer_contexts,
+                        apn_ambr: apn_ambr.unwrap(),
+                        charging_characteristics: group.ies.take(0),
+                        instance: group.instance,
+                    },
+                    pos
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElement {
+    pub fn to_5gs(&self) -> Result<PduSession, String> {
+        /* Maps the PDN connection to the PDU session it becomes in 5GS (see gtp_v2::interworking). Without a PDN Type
+        IE the PDU Session Type is taken from the UE addresses, so a PDN connection without any is Unstructured.
+        Every bearer needs a Bearer QoS to be mapped to a QoS flow. */
+        let ipv4_address = match self.ipv4_address {
+            Some(ip_address::InformationElement { ip_address: IpAddr::V4(address), .. }) => Some(address),
+            _ => None,
+        };
+
+        let ipv6_address = match self.ipv6_address {
+            Some(ip_address::InformationElement { ip_address: IpAddr::V6(address), .. }) => Some(address),
+            _ => None,
+        };
+
+        let pdu_session_type = match (ipv4_address, ipv6_address) {
+            (Some(_), Some(_)) => PduSessionType::IPv4v6,
+            (Some(_), None) => PduSessionType::IPv4,
+            (None, Some(_)) => PduSessionType::IPv6,
+            (None, None) => PduSessionType::Unstructured,
+        };
+
+        let mut qos_flows = Vec::new();
+
+        for bearer_context in &self.bearer_contexts {
+            let eps_bearer_id = bearer_context.eps_bearer_id.eps_bearer_id;
+
+            let qos = match bearer_context.bearer_level_qos {
+                Some(ref qos) => qos,
+                None => return Err(format!("Bearer Context {} has no Bearer QoS", eps_bearer_id)),
+            };
+
+            qos_flows.push(
+                QosFlow {
+                    eps_bearer_id,
+                    five_qi: qos.qci.into(),
+                    arp: qos.arp,
+                    max_ul: qos.max_ul(),
+                    max_dl: qos.max_dl(),
+                    guaranteed_ul: qos.guaranteed_ul(),
+                    guaranteed_dl: qos.guaranteed_dl(),
+                }
+            );
+        }
+
+        Ok(
+            PduSession {
+                dnn: self.apn.network_identifier().to_ascii_string(),
+                pdu_session_type,
+                ipv4_address,
+                ipv6_address,
+                session_ambr_ul: Bitrate::from_kbps(self.apn_ambr.uplink as u64),
+                session_ambr_dl: Bitrate::from_kbps(self.apn_ambr.downlink as u64),
+                default_eps_bearer_id: self.linked_eps_bearer_id.eps_bearer_id,
+                qos_flows,
+            }
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::PDNConnection
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.apn.wire_length();
+
+        if let Some(ref ie) = self.apn_restriction {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.selection_mode {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.ipv4_address {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.ipv6_address {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.linked_eps_bearer_id.wire_length();
+
+        length = length + self.pgw_s5_s8_address_for_control_plane.wire_length();
+
+        if let Some(ref ie) = self.pgw_node_name {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.bearer_contexts {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.apn_ambr.wire_length();
+
+        if let Some(ref ie) = self.charging_characteristics {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Skip the length. It is written once the embedded IEs have been generated
+        // so that their lengths don't have to be walked twice.
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.apn.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.apn_restriction {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.selection_mode {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.ipv4_address {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.ipv6_address {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.linked_eps_bearer_id.generate(&mut buffer[pos..]);
+
+        pos = pos + self.pgw_s5_s8_address_for_control_plane.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.pgw_node_name {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.bearer_contexts {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.apn_ambr.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.charging_characteristics {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        // Write the length
+        // We subtract 4 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], (pos-4) as u16);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.apn.dissect(d);
+
+            if let Some(ref ie) = self.apn_restriction {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.selection_mode {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ipv4_address {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ipv6_address {
+                ie.dissect(d);
+            }
+
+            self.linked_eps_bearer_id.dissect(d);
+            self.pgw_s5_s8_address_for_control_plane.dissect(d);
+
+            if let Some(ref ie) = self.pgw_node_name {
+                ie.dissect(d);
+            }
+
+            for ie in &self.bearer_contexts {
+                ie.dissect(d);
+            }
+
+            self.apn_ambr.dissect(d);
+
+            if let Some(ref ie) = self.charging_characteristics {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate ascii;
+
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, bearer_qos};
+
+    use ascii::AsciiString;
+    use std::str::FromStr;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    fn new_pdn_connection() -> InformationElement {
+        InformationElement::new(
+            apn::InformationElement::new(AsciiString::from_str("internet").unwrap(), 0).unwrap(),
+            ebi::InformationElement::new(5, 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S5S8PgwGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,3)),
+                None,
+                0
+            ).unwrap(),
+            vec![
+                bearer_context::InformationElement::new(
+                    ebi::InformationElement::new(5, 0).unwrap(),
+                    bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+                    0
+                ).unwrap()
+            ],
+            ambr::InformationElement::new(0x1000, 0x2000, 0).unwrap(),
+            0
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = new_pdn_connection();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..16], [InformationElementType::PDNConnection as u8,
+            0, 78, // Length
+            0, // Spare
+            InformationElementType::APN as u8,
+            0, 9, // Length
+            0, // Spare
+            8, b'i', b'n', b't', b'e', b'r', b'n', b'e',
+        ]);
+
+        assert_eq!(pos, 82);
+
+        // A PDN Connection must have at least one Bearer Context
+        assert!(InformationElement::new(
+            apn::InformationElement::new(AsciiString::from_str("internet").unwrap(), 0).unwrap(),
+            ebi::InformationElement::new(5, 0).unwrap(),
+            f_teid::InformationElement::new(f_teid::InterfaceType::S5S8PgwGtpC, 0, None, None, 0).unwrap(),
+            Vec::new(),
+            ambr::InformationElement::new(0x1000, 0x2000, 0).unwrap(),
+            0
+        ).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = new_pdn_connection();
+
+        // APN (13) + LBI (5) + F-TEID (13) + Bearer Context (35) + AMBR (12)
+        assert_eq!(ie.wire_length(), 4 + 13 + 5 + 13 + 35 + 12);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = new_pdn_connection();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::PDNConnection as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let mut ie = new_pdn_connection();
+        ie.charging_characteristics = Some(charging_characteristics::InformationElement::new(0x0800, 0).unwrap());
+
+        let pos = ie.generate(&mut buffer);
+
+        // An EBI after the PDN Connection must not be taken as the Linked EPS Bearer ID
+        let trailing = ebi::InformationElement::new(7, 0).unwrap();
+        let end = pos + trailing.generate(&mut buffer[pos..]);
+
+        if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[..end]) {
+            assert_eq!(ie_pos, pos);
+            assert_eq!(ie.linked_eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(ie.pgw_s5_s8_address_for_control_plane.teid, 0x12345678);
+            assert_eq!(ie.bearer_contexts.len(), 1);
+            assert_eq!(ie.bearer_contexts[0].eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(ie.apn_ambr.uplink, 0x1000);
+            assert_eq!(ie.apn_ambr.downlink, 0x2000);
+            assert_eq!(ie.charging_characteristics.map(|c| c.charging_characteristics), Some(0x0800));
+            assert!(ie.apn_restriction.is_none());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_ip_addresses() {
+        let mut buffer = [0; MTU];
+
+        let mut ie = new_pdn_connection();
+        ie.ipv4_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,45,0,1)), 0).unwrap());
+        ie.ipv6_address = Some(ip_address::InformationElement::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 1).unwrap());
+
+        let pos = ie.generate(&mut buffer);
+
+        // IPv4 Address (8) + IPv6 Address (20)
+        assert_eq!(pos, 82 + 8 + 20);
+        assert_eq!(ie.wire_length() as usize, pos);
+
+        if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[..pos]) {
+            assert_eq!(ie_pos, pos);
+            assert_eq!(ie.ipv4_address.map(|a| a.ip_address), Some(IpAddr::V4(Ipv4Addr::new(10,45,0,1))));
+            assert_eq!(ie.ipv6_address.map(|a| a.ip_address), Some(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_to_5gs() {
+        let mut ie = new_pdn_connection();
+        ie.apn = apn::InformationElement::new(AsciiString::from_str("internet.mnc001.mcc505.gprs").unwrap(), 0).unwrap();
+
+        if let Ok(s) = ie.to_5gs() {
+            assert_eq!(s.dnn, "internet");
+            assert_eq!(s.pdu_session_type, PduSessionType::Unstructured);
+            assert_eq!(s.session_ambr_ul, Bitrate::from_kbps(0x1000));
+            assert_eq!(s.session_ambr_dl, Bitrate::from_kbps(0x2000));
+            assert_eq!(s.qos_flows.len(), 1);
+
+            if let Some(f) = s.default_qos_flow() {
+                assert_eq!(f.eps_bearer_id, 5);
+                assert_eq!(f.five_qi, 9);
+                assert_eq!(f.arp.pl, 9);
+                assert!(!f.is_gbr());
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        ie.ipv4_address = Some(ip_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,45,0,1)), 0).unwrap());
+        ie.ipv6_address = Some(ip_address::InformationElement::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), 1).unwrap());
+
+        if let Ok(s) = ie.to_5gs() {
+            assert_eq!(s.pdu_session_type, PduSessionType::IPv4v6);
+            assert_eq!(s.ipv4_address, Some(Ipv4Addr::new(10,45,0,1)));
+            assert_eq!(s.ipv6_address, Some(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)));
+        }
+        else {
+            assert!(false);
+        }
+
+        // A bearer without QoS can't be mapped to a QoS flow
+        ie.bearer_contexts.push(bearer_context::InformationElement::without_qos(ebi::InformationElement::new(6, 0).unwrap(), 0).unwrap());
+        assert!(ie.to_5gs().is_err());
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        // PDN Connection without Bearer Contexts
+        let ie_bytes = [InformationElementType::PDNConnection as u8,
+            0, 30, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // Linked EPS Bearer ID
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare and Instance
+            (0b1 << 7) | (u8::from(f_teid::InterfaceType::S5S8PgwGtpC)),
+            0x12, 0x34, 0x56, 0x78,
+            10, 0, 0, 3,
+            InformationElementType::AMBR as u8,
+            0, 8, // Length
+            0, // Spare
+            0x00, 0x00, 0x10, 0x00, // APN-AMBR for uplink
+            0x00, 0x00, 0x20, 0x00, // APN-AMBR for downlink
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
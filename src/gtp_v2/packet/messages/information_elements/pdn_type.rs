@@ -1,32 +1,51 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use alloc::format;
+use alloc::string::String;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum PDNType {
-    IPv4 = 1,
-    IPv6 = 2,
-    IPv4v6 = 3,
-    NonIp = 4,
+    IPv4,
+    IPv6,
+    IPv4v6,
+    NonIp,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for PDNType
+impl From<u8> for PDNType
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            1 => PDNType::IPv4,
+            2 => PDNType::IPv6,
+            3 => PDNType::IPv4v6,
+            4 => PDNType::NonIp,
+            _ => PDNType::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<PDNType> for u8
+{
+    fn from(value: PDNType) -> Self {
         match value {
-            1 => Ok(PDNType::IPv4),
-            2 => Ok(PDNType::IPv6),
-            3 => Ok(PDNType::IPv4v6),
-            4 => Ok(PDNType::NonIp),
-            _ => Err(format!("Unsupported PDN type ({})", value))
+            PDNType::IPv4 => 1,
+            PDNType::IPv6 => 2,
+            PDNType::IPv4v6 => 3,
+            PDNType::NonIp => 4,
+            PDNType::Other(value) => value,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -51,6 +70,10 @@ impl InformationElement {
         if instance > 0xF {
             Err(format!("Instance is > 0xF {}", instance))
         }
+        else if u8::from(pdn_type) > 0b111 {
+            // PDN Type is only 3 bits
+            Err(format!("PDN Type is > 0b111 {}", u8::from(pdn_type)))
+        }
         else {
             Ok(
                 InformationElement {
@@ -61,6 +84,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -75,22 +102,23 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(pdn_type) = PDNType::try_from(buffer[pos]){
-            // pos = pos + 1;
-
-            Some(
-                (
-                    InformationElement {
-                        pdn_type: pdn_type,
-                        instance,
-                    },
-                    (length + 4) as usize
-                )
-            )
-        }
-        else {
-            None
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
         }
+
+        let pdn_type = PDNType::from(buffer[pos] & 0b111); // The upper 5 bits are spare
+
+        // pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    pdn_type: pdn_type,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
         
     }
 }
@@ -114,11 +142,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+1
+    fn payload_length(&self) -> u16 {
+        1
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -129,21 +154,28 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = (self.pdn_type as u8) & 0b111;
+        buffer[pos] = (u8::from(self.pdn_type)) & 0b111;
         pos = pos + 1;
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("PDN Type", format!("{:?} ({})", self.pdn_type, u8::from(self.pdn_type)));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,14 +193,14 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::PDNType as u8,
             0, 1, // Length
             0, // Spare
-            PDNType::IPv6 as u8 // PDN Type
+            u8::from(PDNType::IPv6) // PDN Type
         ]);
     }
 
     #[test]
     fn test_length() {
         let ie = InformationElement::new(PDNType::IPv4, 0).unwrap();
-        assert_eq!(ie.length(), 5);
+        assert_eq!(ie.wire_length(), 5);
     }
 
     #[test]
@@ -183,15 +215,36 @@ mod tests {
         let ie_bytes = [InformationElementType::PDNType as u8,
             0, 1, // Length
             0, // Spare
-            PDNType::IPv6 as u8 // PDN Type
+            u8::from(PDNType::IPv6) // PDN Type
         ];
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
-            assert_eq!(ie.pdn_type as u8, PDNType::IPv6 as u8);
+            assert_eq!(u8::from(ie.pdn_type), u8::from(PDNType::IPv6));
         }
         else {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::PDNType as u8,
+            0, 1, // Length
+            0, // Spare
+            0b1111_1110 // Spare and PDN Type
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.pdn_type, PDNType::Other(6));
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], [InformationElementType::PDNType as u8, 0, 1, 0, 6]);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
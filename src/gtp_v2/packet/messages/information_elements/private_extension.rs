@@ -0,0 +1,216 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (255)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Enterprise ID Octet 1                                         |
+        6       | Enterprise ID Octet 2                                         |
+        7 -> n  | Proprietary Value                                             |
+                |---------------------------------------------------------------|
+
+        The Enterprise ID is the vendor's IANA Private Enterprise Number. The value is opaque (TS 29.274 8.67)
+    */
+
+    instance: u8,
+    pub enterprise_id: u16,
+    pub proprietary_value: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(enterprise_id: u16, proprietary_value: &[u8], instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if proprietary_value.len() > (u16::MAX - 2) as usize {
+            Err(format!("Proprietary Value is too long {}", proprietary_value.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    enterprise_id,
+                    proprietary_value: proprietary_value.to_vec(),
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let enterprise_id = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+        pos = pos + 2;
+
+        let proprietary_value = buffer[pos..(length as usize + 4)].to_vec();
+
+        Some(
+            (
+                InformationElement {
+                    enterprise_id,
+                    proprietary_value,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::PrivateExtension
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        2 + self.proprietary_value.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.enterprise_id);
+        pos = pos + 2;
+
+        buffer[pos..pos+self.proprietary_value.len()].copy_from_slice(&self.proprietary_value);
+        pos = pos + self.proprietary_value.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Enterprise ID", self.enterprise_id);
+            d.hex("Proprietary Value", &self.proprietary_value);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(10415, &[0xDE, 0xAD, 0xBE, 0xEF], 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::PrivateExtension as u8,
+            0, 6, // Length
+            0, // Spare
+            0x28, 0xAF, // Enterprise ID
+            0xDE, 0xAD, 0xBE, 0xEF, // Proprietary Value
+        ]);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new(10415, &[], 0x10).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(10415, &[0xDE, 0xAD, 0xBE, 0xEF], 0).unwrap();
+        assert_eq!(ie.wire_length(), 10);
+
+        let ie = InformationElement::new(10415, &[], 0).unwrap();
+        assert_eq!(ie.wire_length(), 6);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::PrivateExtension as u8,
+            0, 6, // Length
+            1, // Spare and Instance
+            0x28, 0xAF, // Enterprise ID
+            0xDE, 0xAD, 0xBE, 0xEF, // Proprietary Value
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(ie.enterprise_id, 10415);
+            assert_eq!(ie.proprietary_value, [0xDE, 0xAD, 0xBE, 0xEF]);
+            assert_eq!(pos, 10);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..9]).is_none());
+
+        // Too short for the Enterprise ID
+        assert!(InformationElement::parse(&[InformationElementType::PrivateExtension as u8, 0, 1, 0, 0x28]).is_none());
+    }
+}
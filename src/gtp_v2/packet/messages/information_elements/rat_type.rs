@@ -1,46 +1,72 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug)]
+use alloc::format;
+use alloc::string::String;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum RATType {
-    Reserved = 0,
-    UTRAN = 1,
-    GERAN = 2,
-    WLAN = 3,
-    GAN = 4,
-    HspaEvolution = 5,
-    EUTRAN = 6,
-    Virtual = 7,
-    EutranNbIot = 8,
-    LteM = 9,
-    NR = 10,
+    Reserved,
+    UTRAN,
+    GERAN,
+    WLAN,
+    GAN,
+    HspaEvolution,
+    EUTRAN,
+    Virtual,
+    EutranNbIot,
+    LteM,
+    NR,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for RATType
+impl From<u8> for RATType
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            0 => RATType::Reserved,
+            1 => RATType::UTRAN,
+            2 => RATType::GERAN,
+            3 => RATType::WLAN,
+            4 => RATType::GAN,
+            5 => RATType::HspaEvolution,
+            6 => RATType::EUTRAN,
+            7 => RATType::Virtual,
+            8 => RATType::EutranNbIot,
+            9 => RATType::LteM,
+            10 => RATType::NR,
+            _ => RATType::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<RATType> for u8
+{
+    fn from(value: RATType) -> Self {
         match value {
-            0 => Ok(RATType::Reserved),
-            1 => Ok(RATType::UTRAN),
-            2 => Ok(RATType::GERAN),
-            3 => Ok(RATType::WLAN),
-            4 => Ok(RATType::GAN),
-            5 => Ok(RATType::HspaEvolution),
-            6 => Ok(RATType::EUTRAN),
-            7 => Ok(RATType::Virtual),
-            8 => Ok(RATType::EutranNbIot),
-            9 => Ok(RATType::LteM),
-            10 => Ok(RATType::NR),
-            _ => Err(format!("Unsupported RAT type ({})", value))
+            RATType::Reserved => 0,
+            RATType::UTRAN => 1,
+            RATType::GERAN => 2,
+            RATType::WLAN => 3,
+            RATType::GAN => 4,
+            RATType::HspaEvolution => 5,
+            RATType::EUTRAN => 6,
+            RATType::Virtual => 7,
+            RATType::EutranNbIot => 8,
+            RATType::LteM => 9,
+            RATType::NR => 10,
+            RATType::Other(value) => value,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -75,6 +101,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -89,22 +119,23 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(rat_type) = RATType::try_from(buffer[pos]){
-            // pos = pos + 1;
-
-            Some(
-                (
-                    InformationElement {
-                        rat_type: rat_type,
-                        instance,
-                    },
-                    (length + 4) as usize
-                )
-            )
-        }
-        else {
-            None
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
         }
+
+        let rat_type = RATType::from(buffer[pos]);
+
+        // pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    rat_type: rat_type,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
         
     }
 }
@@ -128,11 +159,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+1
+    fn payload_length(&self) -> u16 {
+        1
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -143,21 +171,28 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.rat_type as u8;
+        buffer[pos] = u8::from(self.rat_type);
         pos = pos + 1;
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("RAT Type", format!("{:?} ({})", self.rat_type, u8::from(self.rat_type)));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,7 +217,7 @@ mod tests {
     #[test]
     fn test_length() {
         let ie = InformationElement::new(RATType::EUTRAN, 0).unwrap();
-        assert_eq!(ie.length(), 5);
+        assert_eq!(ie.wire_length(), 5);
     }
 
     #[test]
@@ -202,10 +237,41 @@ mod tests {
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
-            assert_eq!(ie.rat_type as u8, RATType::EUTRAN as u8);
+            assert_eq!(u8::from(ie.rat_type), u8::from(RATType::EUTRAN));
         }
         else {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::RATType as u8,
+            0, 1, // Length
+            0, // Spare
+            200 // RAT Type
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.rat_type, RATType::Other(200));
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], ie_bytes);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_rat_type_conversion() {
+        for value in 0..=u8::MAX {
+            assert_eq!(u8::from(RATType::from(value)), value);
+        }
+
+        assert_eq!(RATType::from(6), RATType::EUTRAN);
+        assert_eq!(RATType::from(11), RATType::Other(11));
+    }
+}
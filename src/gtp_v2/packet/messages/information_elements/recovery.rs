@@ -1,7 +1,14 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -36,6 +43,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -50,6 +61,10 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         let restart_counter = buffer[pos];
         // pos = pos + 1;
 
@@ -84,11 +99,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+1
+    fn payload_length(&self) -> u16 {
+        1
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -99,8 +111,7 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
@@ -114,6 +125,14 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Restart Counter", self.restart_counter);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,7 +169,7 @@ mod tests {
     #[test]
     fn test_length() {
         let ie = InformationElement::new(0xF, 0).unwrap();
-        assert_eq!(ie.length(), 5);
+        assert_eq!(ie.wire_length(), 5);
     }
 
     #[test]
@@ -0,0 +1,234 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{
+    InformationElementTraits,
+    InformationElementType,
+    LENGTH,
+    dissect_ie,
+    remote_user_id,
+    remote_ue_ip_information,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use super::grouped_ie::GroupedIe;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (191)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | IEs                                                           |
+                |---------------------------------------------------------------|
+
+        ----------------------------|---------------------------|---------------|------------------------
+        Information Element         |   Presence requirement    | Instance      |  Reference
+        ----------------------------|---------------------------|---------------|------------------------
+        Remote User ID              |   Mandatory               | 0             | 8.123
+        Remote UE IP Information    |   Conditional             | 0             | 8.124
+        ----------------------------|---------------------------|---------------|------------------------
+
+        A Remote UE Context is sent with instance 0 for a remote UE that has connected to a ProSe UE-to-Network relay
+        and instance 1 for one that has disconnected. The Remote UE IP Information is only present when connected.
+    */
+
+    instance: u8,
+    pub remote_user_id: remote_user_id::InformationElement,
+    pub remote_ue_ip_information: Option<remote_ue_ip_information::InformationElement>,
+}
+
+impl InformationElement {
+    pub fn new(remote_user_id: remote_user_id::InformationElement, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    remote_user_id,
+                    remote_ue_ip_information: None,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        let (mut group, pos) = GroupedIe::parse(buffer)?;
+
+        let remote_user_id: Option<remote_user_id::InformationElement> = group.ies.take(0);
+
+        if let Some(remote_user_id) = remote_user_id {
+            Some(
+                (
+                    InformationElement {
+                        remote_user_id,
+                        remote_ue_ip_information: group.ies.take(0),
+                        instance: group.instance,
+                    },
+                    pos
+                )
+            )
+        }
+        else {
+            None
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::RemoteUEContext
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.remote_user_id.wire_length();
+
+        if let Some(ref ie) = self.remote_ue_ip_information {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Skip the length. It is written once the embedded IEs have been generated
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.remote_user_id.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.remote_ue_ip_information {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        // Write the length
+        // We subtract 4 octets as the type and length fields aren't included.
+        NetworkEndian::write_u16(&mut buffer[LENGTH], (pos-4) as u16);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.remote_user_id.dissect(d);
+
+            if let Some(ref ie) = self.remote_ue_ip_information {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_remote_ue_context() -> InformationElement {
+        let mut ie = InformationElement::new(
+            remote_user_id::InformationElement::new("505990001234567", None, None, 0).unwrap(),
+            0
+        ).unwrap();
+
+        ie.remote_ue_ip_information = Some(remote_ue_ip_information::InformationElement::new(&[0x01, 10, 45, 0, 7], 0).unwrap());
+
+        ie
+    }
+
+    const IE_BYTES: [u8; 27] = [InformationElementType::RemoteUEContext as u8,
+        0, 23, // Length
+        0, // Spare
+        InformationElementType::RemoteUserID as u8,
+        0, 10, // Length
+        0, // Spare
+        0, // IMEIF and MSISDNF
+        8, 0x05, 0x95, 0x09, 0x00, 0x21, 0x43, 0x65, 0xF7, // IMSI
+        InformationElementType::RemoteUEIPInformation as u8,
+        0, 5, // Length
+        0, // Spare
+        0x01, 10, 45, 0, 7, // Remote UE IP Information
+    ];
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_remote_ue_context().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], IE_BYTES);
+    }
+
+    #[test]
+    fn test_length() {
+        // Remote User ID (14) + Remote UE IP Information (9)
+        assert_eq!(new_remote_ue_context().wire_length(), 4 + 14 + 9);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&IE_BYTES) {
+            assert_eq!(ie, new_remote_ue_context());
+            assert_eq!(pos, 27);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&IE_BYTES[..26]).is_none());
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        // Remote UE Context without a Remote User ID
+        let ie_bytes = [InformationElementType::RemoteUEContext as u8,
+            0, 9, // Length
+            1, // Spare and Instance
+            InformationElementType::RemoteUEIPInformation as u8,
+            0, 5, // Length
+            0, // Spare
+            0x01, 10, 45, 0, 7, // Remote UE IP Information
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
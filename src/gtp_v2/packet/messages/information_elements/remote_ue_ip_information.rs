@@ -0,0 +1,197 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (193)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | Remote UE IP Information                                      |
+                |---------------------------------------------------------------|
+
+        The Remote UE IP Information is coded as the Remote UE IP address in TS 24.301 9.9.4.20
+    */
+
+    instance: u8,
+    pub remote_ue_ip_information: Vec<u8>,
+}
+
+impl InformationElement {
+    pub fn new(remote_ue_ip_information: &[u8], instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if remote_ue_ip_information.len() > 0xFFFF - 4 {
+            Err(format!("Remote UE IP Information is too long {}", remote_ue_ip_information.len()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    remote_ue_ip_information: remote_ue_ip_information.to_vec(),
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    remote_ue_ip_information: buffer[pos..(length as usize + 4)].to_vec(),
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::RemoteUEIPInformation
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.remote_ue_ip_information.len() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos..pos+self.remote_ue_ip_information.len()].copy_from_slice(&self.remote_ue_ip_information);
+        pos = pos + self.remote_ue_ip_information.len();
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.hex("Remote UE IP Information", &self.remote_ue_ip_information);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    // Address type IPv4 followed by 10.45.0.7
+    const REMOTE_UE_IP_INFORMATION: [u8; 5] = [0x01, 10, 45, 0, 7];
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(&REMOTE_UE_IP_INFORMATION, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::RemoteUEIPInformation as u8,
+            0, 5, // Length
+            0, // Spare
+            0x01, 10, 45, 0, 7, // Remote UE IP Information
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(&REMOTE_UE_IP_INFORMATION, 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 9);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(&REMOTE_UE_IP_INFORMATION, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::RemoteUEIPInformation as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::RemoteUEIPInformation as u8,
+            0, 5, // Length
+            0, // Spare
+            0x01, 10, 45, 0, 7, // Remote UE IP Information
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.remote_ue_ip_information, REMOTE_UE_IP_INFORMATION.to_vec());
+            assert_eq!(pos, 9);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..8]).is_none());
+    }
+}
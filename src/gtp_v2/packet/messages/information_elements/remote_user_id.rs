@@ -0,0 +1,335 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie, imsi, msisdn};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::tbcd;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// The IMEI is either an IMEI (15 digits) or an IMEISV (16 digits) as in the MEI IE
+pub const MIN_IMEI_DIGITS: usize = 15;
+pub const MAX_IMEI_DIGITS: usize = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (192)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Spare                                         | IMEIF |MSISDNF|
+        6       | Length of IMSI                                                |
+        7 -> a  | IMSI                                                          |
+        b       | Length of MSISDN                                              |
+        c -> d  | MSISDN                                                        |
+        e       | Length of IMEI                                                |
+        f -> g  | IMEI                                                          |
+                |---------------------------------------------------------------|
+
+        Remote User ID (TS 29.274 8.123). The IMSI, MSISDN and IMEI are TBCD encoded as in the IMSI, MSISDN and MEI
+        IEs. The MSISDN and IMEI are only present if their flag is set.
+    */
+
+    instance: u8,
+    pub imsi: Vec<u8>,
+    pub msisdn: Option<Vec<u8>>,
+    pub imei: Option<Vec<u8>>,
+}
+
+impl InformationElement {
+    pub fn new(imsi: &str, msisdn: Option<&str>, imei: Option<&str>, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            return Err(format!("Instance is > 0xF {}", instance));
+        }
+
+        if imsi.len() < imsi::MIN_DIGITS || imsi.len() > imsi::MAX_DIGITS {
+            return Err("IMSI is not the correct number of digits".to_string());
+        }
+
+        let imsi = tbcd::parse_digits(imsi).map_err(|_e| "Could not parse IMSI".to_string())?;
+
+        let msisdn = match msisdn {
+            Some(msisdn) => {
+                if msisdn.is_empty() || msisdn.len() > msisdn::MAX_DIGITS {
+                    return Err("MSISDN is not the correct number of digits".to_string());
+                }
+                Some(tbcd::parse_digits(msisdn).map_err(|_e| "Could not parse MSISDN".to_string())?)
+            },
+            None => None,
+        };
+
+        let imei = match imei {
+            Some(imei) => {
+                if imei.len() < MIN_IMEI_DIGITS || imei.len() > MAX_IMEI_DIGITS {
+                    return Err("IMEI is not the correct number of digits".to_string());
+                }
+                Some(tbcd::parse_digits(imei).map_err(|_e| "Could not parse IMEI".to_string())?)
+            },
+            None => None,
+        };
+
+        Ok(
+            InformationElement {
+                imsi,
+                msisdn,
+                imei,
+                instance,
+            }
+        )
+    }
+
+    fn parse_digits(buffer: &[u8]) -> Option<(Vec<u8>, usize)> {
+        // A length octet followed by TBCD digits
+        let length = *buffer.first()? as usize;
+
+        if buffer.len() < length + 1 {
+            return None
+        }
+
+        Some((tbcd::decode(&buffer[1..length + 1])?, length + 1))
+    }
+
+    fn generate_digits(digits: &[u8], buffer: &mut[u8]) -> usize {
+        buffer[0] = tbcd::length(digits) as u8;
+
+        1 + tbcd::generate(digits, &mut buffer[1..])
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        // Only the octets covered by the length of the IE are parsed
+        let end = (length + 4) as usize;
+
+        let imeif = (buffer[pos] >> 1) & 0b1 == 0b1;
+        let msisdnf = buffer[pos] & 0b1 == 0b1;
+        pos = pos + 1;
+
+        let (imsi, imsi_pos) = Self::parse_digits(&buffer[pos..end])?;
+        pos = pos + imsi_pos;
+
+        let msisdn = if msisdnf {
+            let (msisdn, msisdn_pos) = Self::parse_digits(&buffer[pos..end])?;
+            pos = pos + msisdn_pos;
+            Some(msisdn)
+        }
+        else {
+            None
+        };
+
+        let imei = if imeif {
+            let (imei, _imei_pos) = Self::parse_digits(&buffer[pos..end])?;
+            Some(imei)
+        }
+        else {
+            None
+        };
+
+        Some(
+            (
+                InformationElement {
+                    imsi,
+                    msisdn,
+                    imei,
+                    instance,
+                },
+                end
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::RemoteUserID
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        let mut length = 1 + 1 + tbcd::length(&self.imsi);
+
+        if let Some(ref msisdn) = self.msisdn {
+            length = length + 1 + tbcd::length(msisdn);
+        }
+
+        if let Some(ref imei) = self.imei {
+            length = length + 1 + tbcd::length(imei);
+        }
+
+        length as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = ((self.imei.is_some() as u8) << 1) | (self.msisdn.is_some() as u8);
+        pos = pos + 1;
+
+        pos = pos + Self::generate_digits(&self.imsi, &mut buffer[pos..]);
+
+        if let Some(ref msisdn) = self.msisdn {
+            pos = pos + Self::generate_digits(msisdn, &mut buffer[pos..]);
+        }
+
+        if let Some(ref imei) = self.imei {
+            pos = pos + Self::generate_digits(imei, &mut buffer[pos..]);
+        }
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("IMSI", tbcd::to_string(&self.imsi));
+
+            if let Some(ref msisdn) = self.msisdn {
+                d.field("MSISDN", tbcd::to_string(msisdn));
+            }
+
+            if let Some(ref imei) = self.imei {
+                d.field("IMEI", tbcd::to_string(imei));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    const IE_BYTES: [u8; 30] = [InformationElementType::RemoteUserID as u8,
+        0, 26, // Length
+        0, // Spare
+        0b0000_0011, // IMEIF and MSISDNF
+        8, 0x05, 0x95, 0x09, 0x00, 0x21, 0x43, 0x65, 0xF7, // IMSI
+        6, 0x16, 0x44, 0x54, 0x55, 0x66, 0xF6, // MSISDN
+        8, 0x53, 0x24, 0x31, 0x20, 0x14, 0x23, 0x20, 0xF1, // IMEI
+    ];
+
+    fn remote_user_id() -> InformationElement {
+        InformationElement::new("505990001234567", Some("61444555666"), Some("354213024132021"), 0).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = remote_user_id().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], IE_BYTES);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(remote_user_id().wire_length(), 30);
+
+        let ie = InformationElement::new("505990001234567", None, None, 0).unwrap();
+        assert_eq!(ie.wire_length(), 4 + 1 + 9);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(InformationElement::new("50599", None, None, 0).is_err());
+        assert!(InformationElement::new("50599000123456a", None, None, 0).is_err());
+        assert!(InformationElement::new("505990001234567", Some(""), None, 0).is_err());
+        assert!(InformationElement::new("505990001234567", None, Some("3542130241320"), 0).is_err());
+        assert!(InformationElement::new("505990001234567", None, None, 0x10).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Some((ie, pos)) = InformationElement::parse(&IE_BYTES) {
+            // Parsing was successful
+            assert_eq!(ie, remote_user_id());
+            assert_eq!(pos, 30);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&IE_BYTES[..29]).is_none());
+    }
+
+    #[test]
+    fn test_message_parse_flags() {
+        // The MSISDN is not present so the IMEI directly follows the IMSI
+        let ie_bytes = [InformationElementType::RemoteUserID as u8,
+            0, 19, // Length
+            0, // Spare
+            0b0000_0010, // IMEIF
+            8, 0x05, 0x95, 0x09, 0x00, 0x21, 0x43, 0x65, 0xF7, // IMSI
+            8, 0x53, 0x24, 0x31, 0x20, 0x14, 0x23, 0x20, 0xF1, // IMEI
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert!(ie.msisdn.is_none());
+            assert_eq!(ie.imei.map(|imei| tbcd::to_string(&imei)), Some("354213024132021".to_string()));
+        }
+        else {
+            assert!(false);
+        }
+
+        // The IMEI length runs past the end of the IE
+        let mut ie_bytes = ie_bytes;
+        ie_bytes[14] = 9;
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
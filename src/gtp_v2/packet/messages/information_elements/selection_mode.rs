@@ -1,32 +1,51 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use alloc::format;
+use alloc::string::String;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum SelectionMode {
-    MSorNetworkProvidedAPNSubscriptionVerified = 0,
-    MSProvidedAPNSubscriptionNotVerified = 1,
-    NetworkProvidedAPNSubscriptionNotVerified = 2,
-    ForFutureUse = 3,
+    MSorNetworkProvidedAPNSubscriptionVerified,
+    MSProvidedAPNSubscriptionNotVerified,
+    NetworkProvidedAPNSubscriptionNotVerified,
+    ForFutureUse,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
 }
 
-impl TryFrom<u8> for SelectionMode
+impl From<u8> for SelectionMode
 {
-    type Error = String;
+    fn from(value: u8) -> Self {
+        match value {
+            0 => SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified,
+            1 => SelectionMode::MSProvidedAPNSubscriptionNotVerified,
+            2 => SelectionMode::NetworkProvidedAPNSubscriptionNotVerified,
+            3 => SelectionMode::ForFutureUse,
+            _ => SelectionMode::Other(value),
+        }
+    }
+}
 
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
+impl From<SelectionMode> for u8
+{
+    fn from(value: SelectionMode) -> Self {
         match value {
-            0 => Ok(SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified),
-            1 => Ok(SelectionMode::MSProvidedAPNSubscriptionNotVerified),
-            2 => Ok(SelectionMode::NetworkProvidedAPNSubscriptionNotVerified),
-            3 => Ok(SelectionMode::ForFutureUse),
-            _ => Err(format!("Unsupported Selection Mode ({})", value))
+            SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified => 0,
+            SelectionMode::MSProvidedAPNSubscriptionNotVerified => 1,
+            SelectionMode::NetworkProvidedAPNSubscriptionNotVerified => 2,
+            SelectionMode::ForFutureUse => 3,
+            SelectionMode::Other(value) => value,
         }
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -61,6 +80,10 @@ impl InformationElement {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -75,22 +98,23 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
-        if let Ok(selection_mode) = SelectionMode::try_from(buffer[pos]){
-            // pos = pos + 1;
-
-            Some(
-                (
-                    InformationElement {
-                        selection_mode,
-                        instance,
-                    },
-                    (length + 4) as usize
-                )
-            )
-        }
-        else {
-            None
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
         }
+
+        let selection_mode = SelectionMode::from(buffer[pos]);
+
+        // pos = pos + 1;
+
+        Some(
+            (
+                InformationElement {
+                    selection_mode,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
         
     }
 }
@@ -114,11 +138,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+1
+    fn payload_length(&self) -> u16 {
+        1
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -129,21 +150,28 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        buffer[pos] = self.selection_mode as u8;
+        buffer[pos] = u8::from(self.selection_mode);
         pos = pos + 1;
 
         pos
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Selection Mode", format!("{:?} ({})", self.selection_mode, u8::from(self.selection_mode)));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,14 +189,14 @@ mod tests {
         assert_eq!(buffer[..pos], [InformationElementType::SelectionMode as u8,
             0, 1, // Length
             0, // Spare
-            SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified as u8, // Selection Mode
+            u8::from(SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified), // Selection Mode
         ]);
     }
 
     #[test]
     fn test_length() {
         let ie = InformationElement::new(SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified, 0).unwrap();
-        assert_eq!(ie.length(), 5);
+        assert_eq!(ie.wire_length(), 5);
     }
 
     #[test]
@@ -183,7 +211,7 @@ mod tests {
         let ie_bytes = [InformationElementType::RATType as u8,
             0, 1, // Length
             0, // Spare
-            SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified as u8, // Selection Mode
+            u8::from(SelectionMode::MSorNetworkProvidedAPNSubscriptionVerified), // Selection Mode
         ];
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
@@ -194,4 +222,25 @@ mod tests {
             assert!(false);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_unknown() {
+        let ie_bytes = [InformationElementType::SelectionMode as u8,
+            0, 1, // Length
+            0, // Spare
+            0xFC // Selection Mode
+        ];
+
+        if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.selection_mode, SelectionMode::Other(0xFC));
+
+            let mut buffer = [0; MTU];
+            let pos = ie.generate(&mut buffer);
+
+            assert_eq!(buffer[..pos], ie_bytes);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -1,9 +1,16 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
 
 use super::user_location_information::PLMN;
 
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -15,7 +22,7 @@ pub struct InformationElement {
         2       | Length Octet 1                                                |
         3       | Length Octet 2                                                |
         4       | Spare                         | Instance                      |
-        5       | RAT Type                                                      |
+        5 -> 7  | MCC and MNC (PLMN)                                            |
                 |---------------------------------------------------------------|
     */
 
@@ -37,7 +44,25 @@ impl InformationElement {
             )
         }
     }
+
+    pub fn from_mcc_mnc(mcc: &str, mnc: &str, instance: u8) -> Result<Self, String> {
+        // e.g. ("505", "01") for a 2 digit MNC or ("310", "410") for a 3 digit MNC
+        Self::new(format!("{}-{}", mcc, mnc).parse()?, instance)
+    }
+
+    pub fn mcc(&self) -> String {
+        self.plmn.mcc_string()
+    }
+
+    pub fn mnc(&self) -> String {
+        self.plmn.mnc_string()
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -52,6 +77,10 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 3 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         if let Some((plmn, _plmn_pos)) = PLMN::parse(&buffer[pos..]) {
             // pos = pos + plmn_pos;
 
@@ -91,11 +120,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+self.plmn.length()
+    fn payload_length(&self) -> u16 {
+        self.plmn.length()
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -106,8 +132,7 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
@@ -120,6 +145,14 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,7 +164,7 @@ mod tests {
     fn test_generate() {
         let mut buffer = [0; MTU];
 
-        let ie = InformationElement::new(PLMN::new([5,0,5], [0,9,9]), 0).unwrap();
+        let ie = InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap();
 
         let pos = ie.generate(&mut buffer);
 
@@ -144,13 +177,13 @@ mod tests {
 
     #[test]
     fn test_length() {
-        let ie = InformationElement::new(PLMN::new([5,0,5], [0,9,9]), 0).unwrap();
-        assert_eq!(ie.length(), 3+4);
+        let ie = InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap();
+        assert_eq!(ie.wire_length(), 3+4);
     }
 
     #[test]
     fn test_message_type() {
-        let ie = InformationElement::new(PLMN::new([5,0,5], [0,9,9]), 0).unwrap();
+        let ie = InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0).unwrap();
 
         assert_eq!(ie.information_element_type() as u8, InformationElementType::ServingNetwork as u8)
     }
@@ -165,10 +198,41 @@ mod tests {
 
         if let Some((ie, _pos)) = InformationElement::parse(&ie_bytes) {
             // Parsing was successful
-            assert_eq!(ie.plmn, PLMN::new([5,0,5], [0,9,9]));
+            assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
         }
         else {
             assert!(false);
         }
     }
+
+    #[test]
+    fn test_from_mcc_mnc() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::from_mcc_mnc("505", "01", 0).unwrap();
+
+        assert_eq!(ie.mcc(), "505");
+        assert_eq!(ie.mnc(), "01");
+
+        // MNC digit 3 is the filler
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::ServingNetwork as u8,
+            0, 3, // Length
+            0, // Spare
+            0x05, 0xF5, 0x10, // PLMN
+        ]);
+
+        assert_eq!(InformationElement::parse(&buffer[..pos]), Some((ie, 7)));
+
+        let ie = InformationElement::from_mcc_mnc("310", "410", 0).unwrap();
+
+        assert_eq!(ie.mcc(), "310");
+        assert_eq!(ie.mnc(), "410");
+
+        assert!(InformationElement::from_mcc_mnc("5050", "01", 0).is_err());
+        assert!(InformationElement::from_mcc_mnc("505", "1", 0).is_err());
+        assert!(InformationElement::from_mcc_mnc("505", "0a", 0).is_err());
+        assert!(InformationElement::from_mcc_mnc("505", "01", 0x10).is_err());
+    }
 }
\ No newline at end of file
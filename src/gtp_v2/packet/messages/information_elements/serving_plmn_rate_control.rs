@@ -0,0 +1,218 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------| 
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (198)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Uplink Rate Limit (Octet 1)                                   |
+        6       | Uplink Rate Limit (Octet 2)                                   |
+        7       | Downlink Rate Limit (Octet 1)                                 |
+        8       | Downlink Rate Limit (Octet 2)                                 |
+                |---------------------------------------------------------------|
+    */
+
+    instance: u8,
+    // The maximum number of NAS Data PDUs per 6 minute interval. 0 means rate control doesn't apply in that direction
+    pub uplink_rate_limit: u16,
+    pub downlink_rate_limit: u16,
+}
+
+impl InformationElement {
+    pub fn new(uplink_rate_limit: u16, downlink_rate_limit: u16, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    uplink_rate_limit,
+                    downlink_rate_limit,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+        
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 4 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let uplink_rate_limit = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+        pos = pos + 2;
+
+        let downlink_rate_limit = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+        // pos = pos + 2;
+
+        // Any further octets are only present if explicitly specified and are ignored
+
+        Some(
+            (
+                InformationElement {
+                    uplink_rate_limit,
+                    downlink_rate_limit,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn uplink_applicable(&self) -> bool {
+        self.uplink_rate_limit != 0
+    }
+
+    pub fn downlink_applicable(&self) -> bool {
+        self.downlink_rate_limit != 0
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::ServingPLMNRateControl
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        4
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+        
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.uplink_rate_limit);
+        pos = pos + 2;
+
+        NetworkEndian::write_u16(&mut buffer[pos..pos+2], self.downlink_rate_limit);
+        pos = pos + 2;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Uplink Rate Limit", format!("{} NAS Data PDUs per 6 minutes", self.uplink_rate_limit));
+            d.field("Downlink Rate Limit", format!("{} NAS Data PDUs per 6 minutes", self.downlink_rate_limit));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(0x1234, 0x5678, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::ServingPLMNRateControl as u8,
+            0, 4, // Length
+            0, // Spare
+            0x12, 0x34, // Uplink Rate Limit
+            0x56, 0x78, // Downlink Rate Limit
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(0x1234, 0x5678, 0).unwrap();
+        assert_eq!(ie.wire_length(), 4+4);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(0x1234, 0x5678, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::ServingPLMNRateControl as u8)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::ServingPLMNRateControl as u8,
+            0, 4, // Length
+            0, // Spare
+            0x00, 0x0B, // Uplink Rate Limit
+            0x00, 0x00, // Downlink Rate Limit
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.uplink_rate_limit, 11);
+            assert_eq!(ie.downlink_rate_limit, 0);
+            assert!(ie.uplink_applicable());
+            assert!(!ie.downlink_applicable());
+            assert_eq!(pos, 8);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..7]).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::tbcd;
+
+use core::convert::TryInto;
+use core::fmt;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+// Nature of Address and Numbering Plan Indicator for an international E.164 number (no extension)
+pub const INTERNATIONAL_E164: u8 = 0x91;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (51)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | NANPI                                                         |
+        6       | Digit 2                       | Digit 1                       |
+        7 -> n  | Digit m                       | Digit m-1                     |
+                |---------------------------------------------------------------|
+
+        The Session Transfer Number for SRVCC (TS 29.280 6.2). The NANPI (Nature of Address and Numbering Plan
+        Indicator) is encoded as in the MAP AddressString (TS 29.002).
+    */
+
+    instance: u8,
+    pub nanpi: u8,
+    pub stn_sr: Vec<u8>,
+}
+
+pub const MAX_DIGITS: usize = 15;
+
+impl InformationElement {
+    pub fn new(stn_sr: &str, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if stn_sr.is_empty() || stn_sr.len() > MAX_DIGITS {
+            Err("STN-SR is not the correct number of digits".to_string())
+        }
+        else {
+            match tbcd::parse_digits(stn_sr) {
+                Ok(stn_sr) => {
+                    Ok(InformationElement {
+                        nanpi: INTERNATIONAL_E164,
+                        stn_sr,
+                        instance
+                    })
+                },
+                Err(_e) => Err("Could not parse STN-SR".to_string())
+            }
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let nanpi = buffer[pos];
+        pos = pos + 1;
+
+        let stn_sr = tbcd::decode(&buffer[pos..(length+4) as usize])?;
+
+        pos = pos + length as usize - 1;
+
+        Some(
+            (
+                InformationElement {
+                    nanpi,
+                    stn_sr,
+                    instance,
+                },
+                pos
+            )
+        )
+    }
+}
+
+impl fmt::Display for InformationElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", tbcd::to_string(&self.stn_sr))
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::STNSR
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        (1 + tbcd::length(&self.stn_sr)).try_into().unwrap()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.nanpi;
+        pos = pos + 1;
+
+        pos = pos + tbcd::generate(&self.stn_sr, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("NANPI", format!("{:#04x}", self.nanpi));
+            d.field("STN-SR", self);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    #[test]
+    fn test_new() {
+        if let Ok(ie) = InformationElement::new("61400111222", 0) {
+            assert_eq!(ie.nanpi, INTERNATIONAL_E164);
+            assert_eq!(ie.to_string(), "61400111222");
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(InformationElement::new("6140011122a", 0).err(), Some("Could not parse STN-SR".to_string()));
+        assert_eq!(InformationElement::new("6140011122233344", 0).err(), Some("STN-SR is not the correct number of digits".to_string()));
+        assert!(InformationElement::new("", 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new("61400111222", 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::STNSR as u8,
+            0, 7, // Length
+            0, // Spare
+            0x91, // NANPI
+            0x16, 0x04, 0x10, 0x11, 0x22, 0xF2,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new("61400111222", 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 4 + 1 + 6);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new("61400111222", 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::STNSR as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [
+            InformationElementType::STNSR as u8,
+            0, 7, // Length
+            0, // Spare
+            0x91, // NANPI
+            0x16, 0x04, 0x10, 0x11, 0x22, 0xF2,
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 11);
+            assert_eq!(ie.nanpi, 0x91);
+            assert_eq!(ie.stn_sr, [6, 1, 4, 0, 0, 1, 1, 1, 2, 2, 2]);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..10]).is_none());
+
+        // No NANPI
+        assert!(InformationElement::parse(&[InformationElementType::STNSR as u8, 0, 0, 0]).is_none());
+    }
+}
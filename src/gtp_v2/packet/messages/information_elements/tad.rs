@@ -0,0 +1,252 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+use crate::tft::TrafficFlowTemplate;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (85)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5 -> n  | Traffic Aggregate Description                                 |
+                |---------------------------------------------------------------|
+
+        The Traffic Aggregate Description is coded as the TFT in TS 24.008 10.5.6.12 starting from octet 3
+    */
+
+    instance: u8,
+    pub traffic_aggregate_description: TrafficFlowTemplate,
+}
+
+impl InformationElement {
+    pub fn new(traffic_aggregate_description: TrafficFlowTemplate, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if traffic_aggregate_description.length() > 0xFFFF - 4 {
+            Err(format!("Traffic Aggregate Description is too long {}", traffic_aggregate_description.length()))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    traffic_aggregate_description,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        if buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        let traffic_aggregate_description = TrafficFlowTemplate::parse(&buffer[pos..(length as usize + 4)])?;
+
+        Some(
+            (
+                InformationElement {
+                    traffic_aggregate_description,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::TAD
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.traffic_aggregate_description.length() as u16
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.traffic_aggregate_description.generate(&mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.tree("Traffic Aggregate Description", |d| self.traffic_aggregate_description.dissect(d));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+    use crate::tft::{Component, Direction, PacketFilter};
+
+    use alloc::vec;
+
+    // Create new TFT with one packet filter matching UDP (17)
+    const TAD: [u8; 6] = [0x21, 0x11, 0x00, 0x02, 0x30, 0x11];
+
+    fn tad() -> TrafficFlowTemplate {
+        TrafficFlowTemplate::create(vec![
+            PacketFilter::new(1, Direction::Downlink, 0, vec![Component::ProtocolIdentifier(17)]).unwrap(),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(tad(), 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TAD as u8,
+            0, 6, // Length
+            0, // Spare
+            0x21, 0x11, 0x00, 0x02, 0x30, 0x11, // Traffic Aggregate Description
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(tad(), 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 10);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(tad(), 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::TAD as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::TAD as u8,
+            0, 6, // Length
+            0, // Spare
+            0x21, 0x11, 0x00, 0x02, 0x30, 0x11, // Traffic Aggregate Description
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(ie.traffic_aggregate_description, tad());
+            assert_eq!(TrafficFlowTemplate::parse(&TAD), Some(tad()));
+            assert_eq!(pos, 10);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..9]).is_none());
+    }
+
+    #[test]
+    fn test_message_parse_invalid_tft() {
+        // The packet filter is cut off by the end of the IE
+        let ie_bytes = [InformationElementType::TAD as u8,
+            0, 5, // Length
+            0, // Spare
+            0x21, 0x11, 0x00, 0x02, 0x30, // Traffic Aggregate Description
+            0x11,
+        ];
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+
+    #[test]
+    fn test_filter_operations() {
+        let mut buffer = [0; MTU];
+
+        let filter = PacketFilter::new(2, Direction::Uplink, 8, vec![Component::SingleRemotePort(443)]).unwrap();
+
+        let ie = InformationElement::new(TrafficFlowTemplate::add(vec![filter.clone()]).unwrap(), 0).unwrap();
+        let pos = ie.generate(&mut buffer);
+        assert_eq!(buffer[..pos], [InformationElementType::TAD as u8,
+            0, 7, // Length
+            0, // Spare
+            0x61, // Add packet filters to existing TFT and 1 packet filter
+            0x22, 0x08, 0x03, 0x50, 0x01, 0xBB, // Packet filter 2
+        ]);
+        assert_eq!(InformationElement::parse(&buffer[..pos]).map(|(ie, _)| ie), Some(ie));
+
+        let ie = InformationElement::new(TrafficFlowTemplate::replace(vec![filter]).unwrap(), 0).unwrap();
+        let pos = ie.generate(&mut buffer);
+        assert_eq!(buffer[4], 0x81);
+        assert_eq!(InformationElement::parse(&buffer[..pos]).map(|(ie, _)| ie), Some(ie));
+
+        let ie = InformationElement::new(TrafficFlowTemplate::delete(vec![1, 2]).unwrap(), 0).unwrap();
+        let pos = ie.generate(&mut buffer);
+        assert_eq!(buffer[..pos], [InformationElementType::TAD as u8,
+            0, 3, // Length
+            0, // Spare
+            0xA2, 0x01, 0x02, // Delete packet filters 1 and 2 from existing TFT
+        ]);
+        assert_eq!(InformationElement::parse(&buffer[..pos]).map(|(ie, _)| ie), Some(ie));
+    }
+}
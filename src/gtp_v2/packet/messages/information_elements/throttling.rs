@@ -0,0 +1,232 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::time::Duration;
+
+use super::timer::Timer;
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (154)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | Throttling Delay Unit | Throttling Delay Value                |
+        6       | Throttling Factor                                             |
+                |---------------------------------------------------------------|
+
+        The Throttling Factor is a percentage (0 to 100). Other values are considered to be 0
+    */
+
+    instance: u8,
+    pub throttling_delay: Timer,
+    throttling_factor: u8,
+}
+
+pub const MAX_THROTTLING_FACTOR: u8 = 100;
+
+impl InformationElement {
+    pub fn new(throttling_delay: Timer, throttling_factor: u8, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if throttling_factor > MAX_THROTTLING_FACTOR {
+            Err(format!("Throttling Factor is > 100 {}", throttling_factor))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    throttling_delay,
+                    throttling_factor,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn throttling_factor(&self) -> u8 {
+        // Values above 100 are considered to be 0
+        if self.throttling_factor > MAX_THROTTLING_FACTOR { 0 } else { self.throttling_factor }
+    }
+
+    pub fn throttling_delay_duration(&self) -> Option<Duration> {
+        // None if throttling is deactivated
+        self.throttling_delay.duration()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let throttling_delay = Timer::decode(buffer[pos]);
+        pos = pos + 1;
+
+        let throttling_factor = buffer[pos];
+
+        Some(
+            (
+                InformationElement {
+                    throttling_delay,
+                    throttling_factor,
+                    instance,
+                },
+                length as usize + 4
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::Throttling
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        2
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        buffer[pos] = self.throttling_delay.encode();
+        pos = pos + 1;
+
+        buffer[pos] = self.throttling_factor();
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            d.field("Throttling Delay Unit", format!("{:?} ({})", self.throttling_delay.unit, self.throttling_delay.unit as u8));
+            d.field("Throttling Delay Value", self.throttling_delay.value());
+            d.field("Throttling Factor", format!("{}%", self.throttling_factor()));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+    use crate::gtp_v2::packet::messages::information_elements::timer::TimerUnit;
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = InformationElement::new(Timer::new(TimerUnit::TwoSeconds, 10).unwrap(), 50, 0).unwrap();
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::Throttling as u8,
+            0, 2, // Length
+            0, // Spare
+            0b000_01010, // 20 seconds
+            50, // Throttling Factor
+        ]);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(InformationElement::new(Timer::infinite(), 100, 0).is_ok());
+        assert!(InformationElement::new(Timer::infinite(), 101, 0).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = InformationElement::new(Timer::infinite(), 0, 0).unwrap();
+
+        assert_eq!(ie.wire_length(), 6);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = InformationElement::new(Timer::infinite(), 0, 0).unwrap();
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::Throttling as u8);
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::Throttling as u8,
+            0, 2, // Length
+            0, // Spare
+            0b010_00011, // 30 minutes
+            0xC8, // Throttling Factor (200 is considered to be 0)
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            assert_eq!(pos, 6);
+            assert_eq!(ie.throttling_delay_duration(), Some(Duration::from_secs(1800)));
+            assert_eq!(ie.throttling_factor(), 0);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Truncated
+        assert!(InformationElement::parse(&ie_bytes[..5]).is_none());
+    }
+}
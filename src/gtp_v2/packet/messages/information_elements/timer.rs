@@ -0,0 +1,174 @@
+// Timer encoding shared by the EPC Timer (8.87) and Throttling (8.85) IEs
+
+use core::convert::TryFrom;
+use core::time::Duration;
+
+use alloc::format;
+use alloc::string::String;
+
+/*
+            Bits
+            |---------------------------------------------------------------|
+            |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+            |---------------------------------------------------------------|
+            | Timer Unit            | Timer Value                           |
+            |---------------------------------------------------------------|
+*/
+
+pub const MAX_TIMER_VALUE: u8 = 0x1F;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum TimerUnit {
+    TwoSeconds = 0,
+    OneMinute = 1,
+    TenMinutes = 2,
+    OneHour = 3,
+    TenHours = 4,
+    Infinite = 7, // The timer is infinite (EPC Timer) or deactivated (Throttling)
+}
+
+impl TryFrom<u8> for TimerUnit
+{
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(TimerUnit::TwoSeconds),
+            1 => Ok(TimerUnit::OneMinute),
+            2 => Ok(TimerUnit::TenMinutes),
+            3 => Ok(TimerUnit::OneHour),
+            4 => Ok(TimerUnit::TenHours),
+            7 => Ok(TimerUnit::Infinite),
+            _ => Err(format!("Unsupported Timer Unit ({})", value))
+        }
+    }
+}
+
+impl TimerUnit {
+    pub fn seconds(&self) -> Option<u64> {
+        match self {
+            TimerUnit::TwoSeconds => Some(2),
+            TimerUnit::OneMinute => Some(60),
+            TimerUnit::TenMinutes => Some(600),
+            TimerUnit::OneHour => Some(3600),
+            TimerUnit::TenHours => Some(36000),
+            TimerUnit::Infinite => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Timer {
+    pub unit: TimerUnit,
+    value: u8,
+}
+
+impl Timer {
+    pub fn new(unit: TimerUnit, value: u8) -> Result<Self, String> {
+        if value > MAX_TIMER_VALUE {
+            Err(format!("Timer Value is > 0x1F {}", value))
+        }
+        else {
+            Ok(Timer {
+                unit,
+                value,
+            })
+        }
+    }
+
+    pub fn infinite() -> Self {
+        Timer {
+            unit: TimerUnit::Infinite,
+            value: 0,
+        }
+    }
+
+    pub fn from_duration(duration: Duration) -> Result<Self, String> {
+        // Uses the smallest unit that can hold duration. The value is rounded up to a whole number of units
+        let seconds = duration.as_secs() + if duration.subsec_nanos() > 0 { 1 } else { 0 };
+
+        for unit in [TimerUnit::TwoSeconds, TimerUnit::OneMinute, TimerUnit::TenMinutes, TimerUnit::OneHour, TimerUnit::TenHours].iter() {
+            let unit_seconds = unit.seconds().unwrap();
+            let value = seconds.div_ceil(unit_seconds);
+
+            if value <= MAX_TIMER_VALUE as u64 {
+                return Timer::new(*unit, value as u8);
+            }
+        }
+
+        Err(format!("Duration is too long for a Timer {:?}", duration))
+    }
+
+    pub fn decode(octet: u8) -> Self {
+        // Unknown units are interpreted as multiples of 1 minute
+        Timer {
+            unit: TimerUnit::try_from(octet >> 5).unwrap_or(TimerUnit::OneMinute),
+            value: octet & MAX_TIMER_VALUE,
+        }
+    }
+
+    pub fn encode(&self) -> u8 {
+        ((self.unit as u8) << 5) | (self.value & MAX_TIMER_VALUE)
+    }
+
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+
+    pub fn duration(&self) -> Option<Duration> {
+        // None if the timer is infinite / deactivated
+        self.unit.seconds().map(|seconds| Duration::from_secs(seconds * self.value as u64))
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        // Timer Unit and Timer Value both set to 0 indicates that the timer is stopped
+        self.unit == TimerUnit::TwoSeconds && self.value == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(Timer::new(TimerUnit::OneMinute, 5).unwrap().encode(), 0b001_00101);
+        assert_eq!(Timer::new(TimerUnit::TenHours, 0x1F).unwrap().encode(), 0b100_11111);
+        assert_eq!(Timer::infinite().encode(), 0b111_00000);
+
+        assert!(Timer::new(TimerUnit::OneMinute, 0x20).is_err());
+    }
+
+    #[test]
+    fn test_decode() {
+        let t = Timer::decode(0b011_00010);
+        assert_eq!(t.unit, TimerUnit::OneHour);
+        assert_eq!(t.value(), 2);
+        assert_eq!(t.duration(), Some(Duration::from_secs(7200)));
+
+        // Unknown units are multiples of 1 minute
+        let t = Timer::decode(0b101_00011);
+        assert_eq!(t.unit, TimerUnit::OneMinute);
+        assert_eq!(t.duration(), Some(Duration::from_secs(180)));
+
+        assert_eq!(Timer::decode(0b111_00001).duration(), None);
+
+        assert!(Timer::decode(0).is_stopped());
+        assert!(!Timer::decode(1).is_stopped());
+    }
+
+    #[test]
+    fn test_from_duration() {
+        assert_eq!(Timer::from_duration(Duration::from_secs(0)).unwrap(), Timer::new(TimerUnit::TwoSeconds, 0).unwrap());
+        assert_eq!(Timer::from_duration(Duration::from_secs(62)).unwrap(), Timer::new(TimerUnit::TwoSeconds, 31).unwrap());
+
+        // Rounded up to the next unit
+        assert_eq!(Timer::from_duration(Duration::from_millis(63_500)).unwrap(), Timer::new(TimerUnit::OneMinute, 2).unwrap());
+        assert_eq!(Timer::from_duration(Duration::from_secs(3600)).unwrap(), Timer::new(TimerUnit::TenMinutes, 6).unwrap());
+        assert_eq!(Timer::from_duration(Duration::from_secs(31 * 36000)).unwrap(), Timer::new(TimerUnit::TenHours, 31).unwrap());
+
+        assert!(Timer::from_duration(Duration::from_secs(31 * 36000 + 1)).is_err());
+    }
+}
@@ -0,0 +1,361 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use super::user_location_information::PLMN;
+
+use core::convert::TryInto;
+use core::net::IpAddr;
+
+use alloc::format;
+use alloc::string::String;
+
+// Session Trace Depth values (TS 32.422 5.3)
+pub const MINIMUM: u8 = 0;
+pub const MEDIUM: u8 = 1;
+pub const MAXIMUM: u8 = 2;
+pub const MINIMUM_WITHOUT_VENDOR_SPECIFIC_EXTENSION: u8 = 3;
+pub const MEDIUM_WITHOUT_VENDOR_SPECIFIC_EXTENSION: u8 = 4;
+pub const MAXIMUM_WITHOUT_VENDOR_SPECIFIC_EXTENSION: u8 = 5;
+
+pub const MAX_TRACE_ID: u32 = uint::MAX_U24;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (96)                                                  |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | MCC digit 2                   | MCC digit 1                   |
+        6       | MNC digit 3                   | MCC digit 3                   |
+        7       | MNC digit 2                   | MNC digit 1                   |
+        8-10    | Trace ID                                                      |
+        11-19   | Triggering Events                                             |
+        20-21   | List of NE Types                                              |
+        22      | Session Trace Depth                                           |
+        23-34   | List of Interfaces                                            |
+        35-n    | IP Address of Trace Collection Entity                         |
+                |---------------------------------------------------------------|
+
+        The MCC, MNC and Trace ID make up the Trace Reference. The Triggering Events, List of NE Types,
+        Session Trace Depth and List of Interfaces are coded as per TS 32.422.
+    */
+
+    instance: u8,
+    pub plmn: PLMN,
+    trace_id: u32,
+    pub triggering_events: [u8; 9],
+    pub ne_types: [u8; 2],
+    pub session_trace_depth: u8,
+    pub interfaces: [u8; 12],
+    pub trace_collection_entity: IpAddr,
+}
+
+impl InformationElement {
+    pub fn new(
+        plmn: PLMN,
+        trace_id: u32,
+        triggering_events: [u8; 9],
+        ne_types: [u8; 2],
+        session_trace_depth: u8,
+        interfaces: [u8; 12],
+        trace_collection_entity: IpAddr,
+        instance: u8
+    ) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if trace_id > MAX_TRACE_ID {
+            Err(format!("Trace ID is > 0xFFFFFF {}", trace_id))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    plmn,
+                    trace_id,
+                    triggering_events,
+                    ne_types,
+                    session_trace_depth,
+                    interfaces,
+                    trace_collection_entity,
+                    instance,
+                }
+            )
+        }
+    }
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 30 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let (plmn, plmn_pos) = PLMN::parse(&buffer[pos..])?;
+        pos = pos + plmn_pos;
+
+        let trace_id = uint::parse_u24(&buffer[pos..])?;
+        pos = pos + 3;
+
+        let triggering_events = buffer[pos..pos+9].try_into().ok()?;
+        pos = pos + 9;
+
+        let ne_types = buffer[pos..pos+2].try_into().ok()?;
+        pos = pos + 2;
+
+        let session_trace_depth = buffer[pos];
+        pos = pos + 1;
+
+        let interfaces = buffer[pos..pos+12].try_into().ok()?;
+        pos = pos + 12;
+
+        // The address family of the Trace Collection Entity is given by the remaining length
+        let trace_collection_entity = address::parse(&buffer[pos..pos + (length - 30) as usize])?;
+
+        Some(
+            (
+                InformationElement {
+                    plmn,
+                    trace_id,
+                    triggering_events,
+                    ne_types,
+                    session_trace_depth,
+                    interfaces,
+                    trace_collection_entity,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+
+    pub fn trace_id(&self) -> u32 {
+        self.trace_id
+    }
+
+    pub fn set_trace_id(&mut self, trace_id: u32) -> Result<u32, String> {
+        if trace_id > MAX_TRACE_ID {
+            Err(format!("Trace ID is > 0xFFFFFF {}", trace_id))
+        }
+        else {
+            self.trace_id = trace_id;
+            Ok(self.trace_id)
+        }
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::TraceInformation
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        let address_length = match self.trace_collection_entity {
+            IpAddr::V4(_) => 4,
+            IpAddr::V6(_) => 16,
+        };
+
+        self.plmn.length()+3+9+2+1+12+address_length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        uint::generate_u24_lenient(self.trace_id, &mut buffer[pos..]);
+        pos = pos + 3;
+
+        buffer[pos..pos+9].copy_from_slice(&self.triggering_events);
+        pos = pos + 9;
+
+        buffer[pos..pos+2].copy_from_slice(&self.ne_types);
+        pos = pos + 2;
+
+        buffer[pos] = self.session_trace_depth;
+        pos = pos + 1;
+
+        buffer[pos..pos+12].copy_from_slice(&self.interfaces);
+        pos = pos + 12;
+
+        pos = pos + address::generate(self.trace_collection_entity, &mut buffer[pos..]);
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("Trace ID", format!("{:#08x}", self.trace_id));
+            d.hex("Triggering Events", &self.triggering_events);
+            d.hex("List of NE Types", &self.ne_types);
+            d.field("Session Trace Depth", self.session_trace_depth);
+            d.hex("List of Interfaces", &self.interfaces);
+            d.field("IP Address of Trace Collection Entity", self.trace_collection_entity);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::net::{Ipv4Addr, Ipv6Addr};
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_ie(trace_collection_entity: IpAddr) -> InformationElement {
+        InformationElement::new(
+            PLMN::new(505, 99, true).unwrap(),
+            0x123456,
+            [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09],
+            [0x80, 0x01],
+            MEDIUM,
+            [0xFF; 12],
+            trace_collection_entity,
+            0
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        let pos = ie.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::TraceInformation as u8,
+            0, 34, // Length
+            0, // Spare
+            0x05, 0x95, 0x90, // PLMN
+            0x12, 0x34, 0x56, // Trace ID
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, // Triggering Events
+            0x80, 0x01, // List of NE Types
+            MEDIUM, // Session Trace Depth
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // List of Interfaces
+            10, 0, 0, 1, // IP Address of Trace Collection Entity
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+        assert_eq!(ie.wire_length(), 4+34);
+
+        let ie = new_ie(IpAddr::V6(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0, 0, 0, 1)));
+        assert_eq!(ie.wire_length(), 4+46);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        assert_eq!(ie.information_element_type() as u8, InformationElementType::TraceInformation as u8)
+    }
+
+    #[test]
+    fn test_trace_id() {
+        let mut ie = new_ie(IpAddr::V4(Ipv4Addr::new(10,0,0,1)));
+
+        assert_eq!(ie.trace_id(), 0x123456);
+        assert!(ie.set_trace_id(0x1000000).is_err());
+        assert_eq!(ie.set_trace_id(0xFFFFFF), Ok(0xFFFFFF));
+
+        assert!(
+            InformationElement::new(
+                PLMN::new(505, 99, true).unwrap(), 0x1000000, [0; 9], [0; 2], MINIMUM, [0; 12], IpAddr::V4(Ipv4Addr::new(10,0,0,1)), 0
+            ).is_err()
+        );
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::TraceInformation as u8,
+            0, 46, // Length
+            0, // Spare
+            0x05, 0x95, 0x90, // PLMN
+            0x12, 0x34, 0x56, // Trace ID
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, // Triggering Events
+            0x80, 0x01, // List of NE Types
+            MAXIMUM, // Session Trace Depth
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // List of Interfaces
+            0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0, 0, 0, 0, 0, 0, 0, 1, // IP Address of Trace Collection Entity
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
+            assert_eq!(ie.trace_id(), 0x123456);
+            assert_eq!(ie.triggering_events, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09]);
+            assert_eq!(ie.ne_types, [0x80, 0x01]);
+            assert_eq!(ie.session_trace_depth, MAXIMUM);
+            assert_eq!(ie.interfaces, [0xFF; 12]);
+            assert_eq!(ie.trace_collection_entity, IpAddr::V6(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0, 0, 0, 1)));
+            assert_eq!(pos, 50);
+        }
+        else {
+            assert!(false);
+        }
+
+        // The Trace Collection Entity address must be IPv4 or IPv6
+        let mut ie_bytes = ie_bytes;
+        ie_bytes[2] = 40;
+
+        assert!(InformationElement::parse(&ie_bytes).is_none());
+    }
+}
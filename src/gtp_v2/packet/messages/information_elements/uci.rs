@@ -0,0 +1,279 @@
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
+
+use crate::dissect::{Dissect, Dissector};
+
+use super::csg_id::MAX_CSG_ID;
+use super::user_location_information::PLMN;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum AccessMode {
+    ClosedMode,
+    HybridMode,
+    Other(u8), // A value that isn't defined above. It is kept so that the IE can be relayed unchanged
+}
+
+impl From<u8> for AccessMode
+{
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AccessMode::ClosedMode,
+            1 => AccessMode::HybridMode,
+            _ => AccessMode::Other(value),
+        }
+    }
+}
+
+impl From<AccessMode> for u8
+{
+    fn from(value: AccessMode) -> Self {
+        match value {
+            AccessMode::ClosedMode => 0,
+            AccessMode::HybridMode => 1,
+            AccessMode::Other(value) => value,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct InformationElement {
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | IE Type (145)                                                 |
+        2       | Length Octet 1                                                |
+        3       | Length Octet 2                                                |
+        4       | Spare                         | Instance                      |
+        5       | MCC digit 2                   | MCC digit 1                   |
+        6       | MNC digit 3                   | MCC digit 3                   |
+        7       | MNC digit 2                   | MNC digit 1                   |
+        8       | Spare                                 | CSG ID                |
+        9       | CSG ID                                                        |
+        10      | CSG ID                                                        |
+        11      | CSG ID                                                        |
+        12      | Access mode   | Spare                         | LCSG  | CMI   |
+                |---------------------------------------------------------------|
+
+        User CSG Information (TS 29.274 8.75). LCSG is set when the UE leaves the CSG cell / hybrid cell and CMI
+        is set if the UE is a member of the CSG. CMI is only meaningful in hybrid mode.
+    */
+
+    instance: u8,
+    pub plmn: PLMN,
+    pub csg_id: u32,
+    pub access_mode: AccessMode,
+    pub lcsg: bool,
+    pub cmi: bool,
+}
+
+impl InformationElement {
+    pub fn new(plmn: PLMN, csg_id: u32, access_mode: AccessMode, lcsg: bool, cmi: bool, instance: u8) -> Result<Self, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else if csg_id > MAX_CSG_ID {
+            Err(format!("CSG ID is > {:#x} {:#x}", MAX_CSG_ID, csg_id))
+        }
+        else if u8::from(access_mode) > 0b11 {
+            // Access mode is only 2 bits
+            Err(format!("Access mode is > 0b11 {}", u8::from(access_mode)))
+        }
+        else {
+            Ok(
+                InformationElement {
+                    plmn,
+                    csg_id,
+                    access_mode,
+                    lcsg,
+                    cmi,
+                    instance,
+                }
+            )
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
+        let mut pos = 0;
+
+        // Read the type
+        let _ie_type = buffer[pos];
+        pos = pos + 1;
+
+        // Read the length
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        pos = pos + 2;
+
+        //Spare and instance
+        let instance = buffer[pos] & 0xF;
+        pos = pos + 1;
+
+        if length < 8 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
+        let (plmn, plmn_pos) = PLMN::parse(&buffer[pos..])?;
+        pos = pos + plmn_pos;
+
+        let csg_id = NetworkEndian::read_u32(&buffer[pos..pos+4]) & MAX_CSG_ID;
+        pos = pos + 4;
+
+        let access_mode = AccessMode::from(buffer[pos] >> 6);
+        let lcsg = (buffer[pos] >> 1) & 0b1 == 0b1;
+        let cmi = buffer[pos] & 0b1 == 0b1;
+
+        Some(
+            (
+                InformationElement {
+                    plmn,
+                    csg_id,
+                    access_mode,
+                    lcsg,
+                    cmi,
+                    instance,
+                },
+                (length + 4) as usize
+            )
+        )
+    }
+}
+
+impl InformationElementTraits for InformationElement {
+    fn information_element_type(&self) -> InformationElementType {
+        InformationElementType::UCI
+    }
+
+    fn instance(&self) -> u8 {
+        self.instance
+    }
+
+    fn set_instance(&mut self, instance: u8) -> Result<u8, String> {
+        if instance > 0xF {
+            Err(format!("Instance is > 0xF {}", instance))
+        }
+        else {
+            self.instance = instance;
+            Ok(self.instance)
+        }
+    }
+
+    fn payload_length(&self) -> u16 {
+        self.plmn.length() + 5
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // Write the type
+        buffer[pos] = self.information_element_type() as u8;
+        pos = pos + 1;
+
+        // Write the length
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
+        pos = pos + 2;
+
+        //Spare and instance
+        buffer[pos] = self.instance & 0xF;
+        pos = pos + 1;
+
+        pos = pos + self.plmn.generate(&mut buffer[pos..]);
+
+        NetworkEndian::write_u32(&mut buffer[pos..pos+4], self.csg_id & MAX_CSG_ID);
+        pos = pos + 4;
+
+        buffer[pos] = ((u8::from(self.access_mode) & 0b11) << 6) | ((self.lcsg as u8) << 1) | (self.cmi as u8);
+        pos = pos + 1;
+
+        pos
+    }
+}
+
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            self.plmn.dissect(d);
+            d.field("CSG ID", format!("{:#09x}", self.csg_id));
+            d.field("Access Mode", format!("{:?} ({})", self.access_mode, u8::from(self.access_mode)));
+            d.flag("Leave CSG (LCSG)", self.lcsg);
+            d.flag("CSG Member (CMI)", self.cmi);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn uci() -> InformationElement {
+        InformationElement::new(PLMN::new(505, 99, true).unwrap(), 0x7654321, AccessMode::HybridMode, false, true, 0).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = uci().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [InformationElementType::UCI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x95, 0x90, // PLMN
+            0x07, 0x65, 0x43, 0x21, // CSG ID
+            0b0100_0001, // Access mode, LCSG and CMI
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(uci().wire_length(), 12);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        let plmn = PLMN::new(505, 99, true).unwrap();
+
+        assert!(InformationElement::new(plmn, 0x8000000, AccessMode::ClosedMode, false, false, 0).is_err());
+        assert!(InformationElement::new(plmn, 1, AccessMode::Other(4), false, false, 0).is_err());
+        assert!(InformationElement::new(plmn, 1, AccessMode::ClosedMode, false, false, 0x10).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let ie_bytes = [InformationElementType::UCI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x95, 0x90, // PLMN
+            0xF7, 0x65, 0x43, 0x21, // Spare and CSG ID
+            0b1000_0010, // Access mode, LCSG and CMI
+        ];
+
+        if let Some((ie, pos)) = InformationElement::parse(&ie_bytes) {
+            // Parsing was successful
+            assert_eq!(ie.plmn, PLMN::new(505, 99, true).unwrap());
+            assert_eq!(ie.csg_id, 0x7654321);
+            assert_eq!(ie.access_mode, AccessMode::Other(2));
+            assert!(ie.lcsg);
+            assert!(!ie.cmi);
+            assert_eq!(pos, 12);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::parse(&ie_bytes[..11]).is_none());
+    }
+}
@@ -1,10 +1,16 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryFrom;
+use crate::dissect::{Dissect, Dissector};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use core::convert::TryFrom;
+
+use alloc::format;
+use alloc::string::String;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub enum DaylightSavingsTimeAdjustment {
     NoAdjustment = 0,
     OneHourAdjustment = 1,
@@ -27,6 +33,24 @@ impl TryFrom<u8> for DaylightSavingsTimeAdjustment
     }
 }
 
+impl DaylightSavingsTimeAdjustment {
+    pub fn minutes(&self) -> Option<u16> {
+        // Spare doesn't have a meaning
+        match self {
+            DaylightSavingsTimeAdjustment::NoAdjustment => Some(0),
+            DaylightSavingsTimeAdjustment::OneHourAdjustment => Some(60),
+            DaylightSavingsTimeAdjustment::TwoHoursAdjustment => Some(120),
+            DaylightSavingsTimeAdjustment::Spare => None,
+        }
+    }
+}
+
+// The time zone is the number of quarter hours as 2 BCD digits. The sign is bit 4 of the tens digit (TS 23.040 9.2.3.11)
+const NEGATIVE: u8 = 0b1000_0000;
+const MAX_QUARTER_HOURS: u16 = 79;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -34,17 +58,21 @@ pub struct InformationElement {
                 |---------------------------------------------------------------| 
         Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
                 |---------------------------------------------------------------|
-        1       | IE Type (82)                                                  |
+        1       | IE Type (114)                                                 |
         2       | Length Octet 1                                                |
         3       | Length Octet 2                                                |
         4       | Spare                         | Instance                      |
         5       | Timezone                                                      |
         6       | Spare                                         | DST           |
                 |---------------------------------------------------------------|
+
+        The Timezone octet has the units digit in bits 8-5 and the tens digit in bits 3-1 with the sign in bit 4.
+        timezone_offset holds it with the nibbles swapped i.e. 0x40 is +40 quarter hours and 0xC0 is -40.
+        Use from_minutes() and offset_minutes() rather than building it by hand.
     */
 
     instance: u8,
-    pub timezone_offset: u8, // Multiples of 15 minutes from UTC
+    pub timezone_offset: u8, // Multiples of 15 minutes from UTC as BCD
     pub dst_adjustment: DaylightSavingsTimeAdjustment,
 }
 
@@ -67,7 +95,54 @@ impl InformationElement {
             )
         }
     }
+    pub fn from_minutes(
+        offset: i16, // Offset of local time from UTC in minutes. This includes any DST adjustment
+        dst_adjustment: DaylightSavingsTimeAdjustment,
+        instance: u8
+    ) -> Result<Self, String> {
+        if offset % 15 != 0 {
+            return Err(format!("Time zone offset is not a multiple of 15 minutes {}", offset));
+        }
+
+        let quarter_hours = offset.unsigned_abs() / 15;
+
+        if quarter_hours > MAX_QUARTER_HOURS {
+            return Err(format!("Time zone offset is out of range {}", offset));
+        }
+
+        let mut timezone_offset = (((quarter_hours / 10) as u8) << 4) | (quarter_hours % 10) as u8;
+
+        if offset < 0 {
+            timezone_offset = timezone_offset | NEGATIVE;
+        }
+
+        Self::new(timezone_offset, dst_adjustment, instance)
+    }
+
+    pub fn offset_minutes(&self) -> Option<i16> {
+        // None if the time zone isn't valid BCD
+        let tens = (self.timezone_offset & !NEGATIVE) >> 4;
+        let units = self.timezone_offset & 0xF;
+
+        if units > 9 {
+            return None
+        }
+
+        let minutes = (tens * 10 + units) as i16 * 15;
+
+        if self.timezone_offset & NEGATIVE == NEGATIVE {
+            Some(-minutes)
+        }
+        else {
+            Some(minutes)
+        }
+    }
+
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -82,6 +157,10 @@ impl InformationElement {
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 2 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         let timezone_offset = ((buffer[pos] & 0xF) << 4) | (buffer[pos] >> 4);
         pos = pos + 1;
 
@@ -124,11 +203,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        4+2
+    fn payload_length(&self) -> u16 {
+        2
     }
 
     fn generate(&self, buffer: &mut[u8]) -> usize {
@@ -139,15 +215,14 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
         buffer[pos] = self.instance & 0xF;
         pos = pos + 1;
 
-        // I don't really understand how this works
+        // Swap the nibbles back so the units digit is in bits 8-5
         buffer[pos] = ((self.timezone_offset & 0xF) << 4) | (self.timezone_offset >> 4);
         pos = pos + 1;
 
@@ -158,6 +233,18 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            match self.offset_minutes() {
+                Some(offset) => d.field("Time Zone", format!("UTC{}{:02}:{:02}", if offset < 0 { "-" } else { "+" }, offset.abs() / 60, offset.abs() % 60)),
+                None => d.field("Time Zone", format!("Invalid (0x{:02x})", self.timezone_offset)),
+            }
+            d.field("Daylight Saving Time", format!("{:?} ({})", self.dst_adjustment, self.dst_adjustment as u8));
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,13 +270,75 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_from_minutes() {
+        let mut buffer = [0; MTU];
+
+        // (Offset in minutes, Timezone octet on the wire)
+        let offsets: [(i16, u8); 7] = [
+            (0, 0x00),
+            (600, 0x04), // +10:00
+            (570, 0x83), // +09:30
+            (345, 0x32), // +05:45
+            (-600, 0x0C), // -10:00
+            (-210, 0x49), // -03:30
+            (-15, 0x18), // -00:15
+        ];
+
+        for (offset, octet) in offsets.iter() {
+            let ie = InformationElement::from_minutes(*offset, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap();
+            assert_eq!(ie.offset_minutes(), Some(*offset));
+
+            let pos = ie.generate(&mut buffer);
+            assert_eq!(buffer[4], *octet);
+
+            let (parsed, _pos) = InformationElement::parse(&buffer[..pos]).unwrap();
+            assert_eq!(parsed.offset_minutes(), Some(*offset));
+        }
+
+        // The existing encoding of +10:00 and -10:00
+        assert_eq!(InformationElement::from_minutes(600, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap().timezone_offset, 0x40);
+        assert_eq!(InformationElement::from_minutes(-600, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap().timezone_offset, 0xC0);
+    }
+
+    #[test]
+    fn test_from_minutes_invalid() {
+        assert!(InformationElement::from_minutes(10, DaylightSavingsTimeAdjustment::NoAdjustment, 0).is_err());
+        assert!(InformationElement::from_minutes(-20 * 60, DaylightSavingsTimeAdjustment::NoAdjustment, 0).is_err());
+        assert!(InformationElement::from_minutes(79 * 15, DaylightSavingsTimeAdjustment::NoAdjustment, 0).is_ok());
+        assert!(InformationElement::from_minutes(600, DaylightSavingsTimeAdjustment::NoAdjustment, 0x10).is_err());
+    }
+
+    #[test]
+    fn test_offset_minutes_invalid_bcd() {
+        let ie = InformationElement::new(0x4A, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap();
+        assert_eq!(ie.offset_minutes(), None);
+    }
+
+    #[test]
+    fn test_dst_minutes() {
+        assert_eq!(DaylightSavingsTimeAdjustment::NoAdjustment.minutes(), Some(0));
+        assert_eq!(DaylightSavingsTimeAdjustment::OneHourAdjustment.minutes(), Some(60));
+        assert_eq!(DaylightSavingsTimeAdjustment::TwoHoursAdjustment.minutes(), Some(120));
+        assert_eq!(DaylightSavingsTimeAdjustment::Spare.minutes(), None);
+    }
+
+    #[test]
+    fn test_dissect() {
+        let ie = InformationElement::from_minutes(-210, DaylightSavingsTimeAdjustment::OneHourAdjustment, 0).unwrap();
+        assert!(crate::dissect::dissect(&ie).contains("Time Zone: UTC-03:30\n"));
+
+        let ie = InformationElement::new(0x4A, DaylightSavingsTimeAdjustment::NoAdjustment, 0).unwrap();
+        assert!(crate::dissect::dissect(&ie).contains("Time Zone: Invalid (0x4a)\n"));
+    }
+
     #[test]
     fn test_length() {
         let ie = InformationElement::new(
             0x40, // +10 Hours
             DaylightSavingsTimeAdjustment::OneHourAdjustment,
             0).unwrap();
-        assert_eq!(ie.length(), 6);
+        assert_eq!(ie.wire_length(), 6);
     }
 
     #[test]
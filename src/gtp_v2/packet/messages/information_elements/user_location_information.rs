@@ -1,37 +1,112 @@
 use byteorder::{ByteOrder, NetworkEndian};
 
-use super::{InformationElementTraits, InformationElementType, LENGTH};
+use super::{InformationElementTraits, InformationElementType, LENGTH, dissect_ie};
 
-use std::convert::TryInto;
+use crate::dissect::{Dissect, Dissector};
+use crate::tbcd;
+use crate::uint;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+use core::fmt;
+use core::str::FromStr;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct PLMN {
-    pub mcc: [u8; 3],
-    pub mnc: [u8; 3],
+
+        /*
+                                        Bits
+                |---------------------------------------------------------------|
+        Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+                |---------------------------------------------------------------|
+        1       | MCC digit 2                   | MCC digit 1                   |
+        2       | MNC digit 3                   | MCC digit 3                   |
+        3       | MNC digit 2                   | MNC digit 1                   |
+                |---------------------------------------------------------------|
+
+        MNC digit 3 is the filler (0xF) if the MNC has 2 digits
+    */
+
+    mcc: u16,
+    mnc: u16,
+    three_digit_mnc: bool,
 }
 
 impl PLMN {
-    pub fn new(mcc: [u8; 3], mnc: [u8; 3])-> Self
-    {
-        Self {
-            mcc,
-            mnc,
+    pub fn new(mcc: u16, mnc: u16, three_digit_mnc: bool) -> Result<Self, String> {
+        if mcc > 999 {
+            Err(format!("MCC is > 999 {}", mcc))
+        }
+        else if (three_digit_mnc && mnc > 999) || (!three_digit_mnc && mnc > 99) {
+            Err(format!("MNC is too large for {} digits {}", if three_digit_mnc { 3 } else { 2 }, mnc))
+        }
+        else {
+            Ok(
+                Self {
+                    mcc,
+                    mnc,
+                    three_digit_mnc,
+                }
+            )
+        }
+    }
+    pub fn mcc(&self) -> u16 {
+        self.mcc
+    }
+    pub fn mnc(&self) -> u16 {
+        self.mnc
+    }
+    pub fn three_digit_mnc(&self) -> bool {
+        self.three_digit_mnc
+    }
+    pub fn mcc_string(&self) -> String {
+        format!("{:03}", self.mcc)
+    }
+    pub fn mnc_string(&self) -> String {
+        if self.three_digit_mnc {
+            format!("{:03}", self.mnc)
+        }
+        else {
+            format!("{:02}", self.mnc)
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
         let mcc = [
-            (buffer[0] & 0xF), ((buffer[0] >> 4) & 0xF), (buffer[1] & 0xF), 
+            (buffer[0] & 0xF), ((buffer[0] >> 4) & 0xF), (buffer[1] & 0xF),
         ];
 
         let mnc = [
-            (buffer[2] & 0xF), ((buffer[2] >> 4) & 0xF), ((buffer[1] >> 4) & 0xF), 
+            (buffer[2] & 0xF), ((buffer[2] >> 4) & 0xF), ((buffer[1] >> 4) & 0xF),
         ];
 
+        let three_digit_mnc = mnc[2] != tbcd::FILLER;
+
+        if mcc.iter().chain(mnc[..2].iter()).any(|d| *d > 9) || (three_digit_mnc && mnc[2] > 9) {
+            return None
+        }
+
+        let mcc = mcc[0] as u16 * 100 + mcc[1] as u16 * 10 + mcc[2] as u16;
+
+        let mnc = if three_digit_mnc {
+            mnc[0] as u16 * 100 + mnc[1] as u16 * 10 + mnc[2] as u16
+        }
+        else {
+            mnc[0] as u16 * 10 + mnc[1] as u16
+        };
+
         Some(
             (
                 Self {
                     mcc,
-                    mnc
+                    mnc,
+                    three_digit_mnc,
                 },
                 3,
             )
@@ -41,13 +116,22 @@ impl PLMN {
     pub fn generate(&self, buffer: &mut[u8]) -> usize {
         let mut pos = 0;
 
-        buffer[pos] = (self.mcc[1] << 4) | (self.mcc[0] & 0xF);
+        let mcc = [(self.mcc / 100 % 10) as u8, (self.mcc / 10 % 10) as u8, (self.mcc % 10) as u8];
+
+        let mnc = if self.three_digit_mnc {
+            [(self.mnc / 100 % 10) as u8, (self.mnc / 10 % 10) as u8, (self.mnc % 10) as u8]
+        }
+        else {
+            [(self.mnc / 10 % 10) as u8, (self.mnc % 10) as u8, tbcd::FILLER]
+        };
+
+        buffer[pos] = (mcc[1] << 4) | mcc[0];
         pos = pos + 1;
 
-        buffer[pos] = (self.mnc[2] << 4) | (self.mcc[2] & 0xF);
+        buffer[pos] = (mnc[2] << 4) | mcc[2];
         pos = pos + 1;
 
-        buffer[pos] = (self.mnc[1] << 4) | (self.mnc[0] & 0xF);
+        buffer[pos] = (mnc[1] << 4) | mnc[0];
         pos = pos + 1;
 
         pos
@@ -55,7 +139,33 @@ impl PLMN {
     pub fn length(&self) -> u16 {3}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+impl FromStr for PLMN {
+    type Err = String;
+
+    fn from_str(plmn: &str) -> Result<Self, Self::Err> {
+        // The MCC and MNC e.g. "505-99" or "310-410". The number of digits given for the MNC is kept.
+        let (mcc, mnc) = match plmn.split_once('-') {
+            Some((mcc, mnc)) => (mcc, mnc),
+            None => return Err(format!("PLMN is not MCC-MNC {}", plmn)),
+        };
+
+        if mcc.len() != 3 || (mnc.len() != 2 && mnc.len() != 3) || !mcc.chars().chain(mnc.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(format!("PLMN is not MCC-MNC {}", plmn))
+        }
+
+        // Only digits so these can't fail
+        Self::new(mcc.parse().unwrap(), mnc.parse().unwrap(), mnc.len() == 3)
+    }
+}
+
+impl fmt::Display for PLMN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.mcc_string(), self.mnc_string())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct CGI {
     pub plmn: PLMN,
     pub lac: u16,
@@ -72,6 +182,10 @@ impl CGI {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 7 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -111,7 +225,8 @@ impl CGI {
     pub fn length(&self) -> u16 {self.plmn.length() + 4}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct SAI {
     pub plmn: PLMN,
     pub lac: u16,
@@ -128,6 +243,10 @@ impl SAI {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 7 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -167,7 +286,8 @@ impl SAI {
     pub fn length(&self) -> u16 {self.plmn.length() + 4}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct RAI {
     pub plmn: PLMN,
     pub lac: u16,
@@ -184,6 +304,10 @@ impl RAI {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 7 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -223,7 +347,8 @@ impl RAI {
     pub fn length(&self) -> u16 {self.plmn.length() + 4}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct TAI {
     pub plmn: PLMN,
     pub tac: u16,
@@ -238,6 +363,10 @@ impl TAI {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -271,7 +400,8 @@ impl TAI {
     pub fn length(&self) -> u16 {self.plmn.length() + 2}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct ECGI {
     pub plmn: PLMN,
     eci: u32
@@ -295,6 +425,10 @@ impl ECGI {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 7 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -327,7 +461,8 @@ impl ECGI {
     pub fn length(&self) -> u16 {self.plmn.length() + 4}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct LAI {
     pub plmn: PLMN,
     pub lac: u16,
@@ -342,6 +477,10 @@ impl LAI {
         }
     }
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 5 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -375,7 +514,8 @@ impl LAI {
 
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct MeNBID {
     pub plmn: PLMN,
     menbid: u32
@@ -399,18 +539,22 @@ impl MeNBID {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 6 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
             pos = pos + plmn_pos;
-            let menbid = NetworkEndian::read_uint(&buffer[pos..pos+3], 3);
+            let menbid = uint::parse_u24(&buffer[pos..])?;
             pos = pos + 3;
 
             Some(
                 (
                     Self {
                         plmn,
-                        menbid: menbid.try_into().unwrap(),
+                        menbid,
                     }, 
                     pos
                 )
@@ -423,7 +567,7 @@ impl MeNBID {
     pub fn generate(&self, buffer: &mut[u8]) -> usize {
         let mut pos = self.plmn.generate(buffer);
 
-        NetworkEndian::write_uint(&mut buffer[pos..pos+3], self.menbid.into(), 3);
+        uint::generate_u24_lenient(self.menbid, &mut buffer[pos..]);
         pos = pos + 3;
 
         pos
@@ -431,7 +575,8 @@ impl MeNBID {
     pub fn length(&self) -> u16 {self.plmn.length() + 3}
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct EMeNBID {
     pub plmn: PLMN,
     menbid: u32
@@ -455,6 +600,10 @@ impl EMeNBID {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 6 {
+            return None
+        }
+
         let mut pos = 0;
 
         if let Some((plmn, plmn_pos)) = PLMN::parse(buffer) {
@@ -463,17 +612,17 @@ impl EMeNBID {
             //Get SMeNB Flag
             let smenb = buffer[pos] >> 7;
 
-            let mut menbid: u64;
+            let mut menbid: u32;
 
             if smenb == 1 {
                 // We have a short Macro eNB ID (18 bits)
-                menbid = NetworkEndian::read_uint(&buffer[pos..pos+3], 3);
+                menbid = uint::parse_u24(&buffer[pos..])?;
                 menbid = 0x3_FF_FF & menbid; // Blank out the top 6 bits
             }
             else 
             {
                 // We have a Long Macro eNB ID (22 bits)
-                menbid = NetworkEndian::read_uint(&buffer[pos..pos+3], 3);
+                menbid = uint::parse_u24(&buffer[pos..])?;
                 menbid = 0x1F_FF_FF & menbid; // Blank out the top 3 bits
             }
 
@@ -483,7 +632,7 @@ impl EMeNBID {
                 (
                     Self {
                         plmn,
-                        menbid: menbid.try_into().unwrap(),
+                        menbid,
                     }, 
                     pos
                 )
@@ -498,11 +647,11 @@ impl EMeNBID {
 
         if self.menbid < 0x3FFFF {
             // We have a short Macro eNB ID (18 bits)
-            NetworkEndian::write_uint(&mut buffer[pos..pos+3], (self.menbid | (0x1 << 23)).into(), 3);
+            uint::generate_u24_lenient(self.menbid | (0x1 << 23), &mut buffer[pos..]);
         }
         else {
             // We have a Long Macro eNB ID (22 bits)
-            NetworkEndian::write_uint(&mut buffer[pos..pos+3], self.menbid.into(), 3);
+            uint::generate_u24_lenient(self.menbid, &mut buffer[pos..]);
         }
         pos = pos + 3;
 
@@ -511,6 +660,90 @@ impl EMeNBID {
     pub fn length(&self) -> u16 {self.plmn.length() + 3}
 }
 
+impl Dissect for PLMN {
+    fn dissect(&self, d: &mut Dissector) {
+        d.field("MCC", self.mcc_string());
+        d.field("MNC", self.mnc_string());
+    }
+}
+
+impl Dissect for CGI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("CGI", |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+            d.field("CI", format!("{:#06x}", self.ci));
+        });
+    }
+}
+
+impl Dissect for SAI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("SAI", |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+            d.field("SAC", format!("{:#06x}", self.sac));
+        });
+    }
+}
+
+impl Dissect for RAI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("RAI", |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+            d.field("RAC", format!("{:#06x}", self.rac));
+        });
+    }
+}
+
+impl Dissect for TAI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("TAI", |d| {
+            self.plmn.dissect(d);
+            d.field("TAC", format!("{:#06x}", self.tac));
+        });
+    }
+}
+
+impl Dissect for ECGI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("ECGI", |d| {
+            self.plmn.dissect(d);
+            d.field("ECI", format!("{:#09x}", self.eci));
+        });
+    }
+}
+
+impl Dissect for LAI {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("LAI", |d| {
+            self.plmn.dissect(d);
+            d.field("LAC", format!("{:#06x}", self.lac));
+        });
+    }
+}
+
+impl Dissect for MeNBID {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("MeNBID", |d| {
+            self.plmn.dissect(d);
+            d.field("Macro eNodeB ID", format!("{:#07x}", self.menbid));
+        });
+    }
+}
+
+impl Dissect for EMeNBID {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree("EMeNBID", |d| {
+            self.plmn.dissect(d);
+            d.field("Extended Macro eNodeB ID", format!("{:#08x}", self.menbid));
+        });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
 pub struct InformationElement {
 
         /*
@@ -617,6 +850,22 @@ impl InformationElement {
         }
     }
 
+    pub fn plmns(&self) -> Vec<PLMN> {
+        // The PLMN of each location that is present. These are usually all the same
+        let mut plmns = Vec::new();
+
+        if let Some(ref cgi) = self.cgi { plmns.push(cgi.plmn) }
+        if let Some(ref sai) = self.sai { plmns.push(sai.plmn) }
+        if let Some(ref rai) = self.rai { plmns.push(rai.plmn) }
+        if let Some(ref tai) = self.tai { plmns.push(tai.plmn) }
+        if let Some(ref ecgi) = self.ecgi { plmns.push(ecgi.plmn) }
+        if let Some(ref lai) = self.lai { plmns.push(lai.plmn) }
+        if let Some(ref menbid) = self.menbid { plmns.push(menbid.plmn) }
+        if let Some(ref emenbid) = self.emenbid { plmns.push(emenbid.plmn) }
+
+        plmns
+    }
+
     pub fn parseflags(buffer: &[u8]) -> (u8, u8, u8, u8, u8, u8, u8, u8) {
         (
             (buffer[0] >> 7) & 0x1, // EMeNBID
@@ -642,6 +891,10 @@ impl InformationElement {
     }
 
     pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 4 {
+            return None
+        }
+
         let mut pos = 0;
         
         // Read the type
@@ -649,13 +902,17 @@ impl InformationElement {
         pos = pos + 1;
 
         // Read the length
-        let _length = NetworkEndian::read_u16(&buffer[LENGTH]);
+        let length = NetworkEndian::read_u16(&buffer[LENGTH]);
         pos = pos + 2;
 
         //Spare and instance
         let instance = buffer[pos] & 0xF;
         pos = pos + 1;
 
+        if length < 1 || buffer.len() < length as usize + 4 {
+            return None
+        }
+
         let (
             emenbid_flag, 
             menbid_flag, 
@@ -678,7 +935,7 @@ impl InformationElement {
         let mut emenbid: Option<EMeNBID> = None;
 
         if cgi_flag == 1 {
-            if let Some((li, li_pos)) = CGI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = CGI::parse(&buffer[pos..(length + 4) as usize]) {
                 cgi = Some(li);
                 pos = pos + li_pos
             }
@@ -688,7 +945,7 @@ impl InformationElement {
         }
 
         if sai_flag == 1 {
-            if let Some((li, li_pos)) = SAI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = SAI::parse(&buffer[pos..(length + 4) as usize]) {
                 sai = Some(li);
                 pos = pos + li_pos
             }
@@ -698,7 +955,7 @@ impl InformationElement {
         }
 
         if rai_flag == 1 {
-            if let Some((li, li_pos)) = RAI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = RAI::parse(&buffer[pos..(length + 4) as usize]) {
                 rai = Some(li);
                 pos = pos + li_pos
             }
@@ -708,7 +965,7 @@ impl InformationElement {
         }
 
         if tai_flag == 1 {
-            if let Some((li, li_pos)) = TAI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = TAI::parse(&buffer[pos..(length + 4) as usize]) {
                 tai = Some(li);
                 pos = pos + li_pos
             }
@@ -718,7 +975,7 @@ impl InformationElement {
         }
 
         if ecgi_flag == 1 {
-            if let Some((li, li_pos)) = ECGI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = ECGI::parse(&buffer[pos..(length + 4) as usize]) {
                 ecgi = Some(li);
                 pos = pos + li_pos
             }
@@ -728,7 +985,7 @@ impl InformationElement {
         }
 
         if lai_flag == 1 {
-            if let Some((li, li_pos)) = LAI::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = LAI::parse(&buffer[pos..(length + 4) as usize]) {
                 lai = Some(li);
                 pos = pos + li_pos
             }
@@ -738,7 +995,7 @@ impl InformationElement {
         }
 
         if menbid_flag == 1 {
-            if let Some((li, li_pos)) = MeNBID::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = MeNBID::parse(&buffer[pos..(length + 4) as usize]) {
                 menbid = Some(li);
                 pos = pos + li_pos
             }
@@ -748,7 +1005,7 @@ impl InformationElement {
         }
 
         if emenbid_flag == 1 {
-            if let Some((li, li_pos)) = EMeNBID::parse(&buffer[pos..]) {
+            if let Some((li, li_pos)) = EMeNBID::parse(&buffer[pos..(length + 4) as usize]) {
                 emenbid = Some(li);
                 pos = pos + li_pos
             }
@@ -773,6 +1030,97 @@ impl InformationElement {
             pos)
         )
     }
+
+    pub fn builder() -> UliBuilder {
+        UliBuilder::new()
+    }
+
+    pub fn for_eutran(plmn: PLMN, tac: u16, eci: u32, instance: u8) -> Result<Self, String> {
+        // TAI and ECGI in the same PLMN. This is what an MME sends for a UE on E-UTRAN
+        Self::builder()
+            .tai(TAI::new(plmn, tac))
+            .ecgi(ECGI::new(plmn, eci)?)
+            .instance(instance)
+            .build()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct UliBuilder {
+    instance: u8,
+    cgi: Option<CGI>,
+    sai: Option<SAI>,
+    rai: Option<RAI>,
+    tai: Option<TAI>,
+    ecgi: Option<ECGI>,
+    lai: Option<LAI>,
+    menbid: Option<MeNBID>,
+    emenbid: Option<EMeNBID>,
+}
+
+impl UliBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn instance(mut self, instance: u8) -> Self {
+        self.instance = instance;
+        self
+    }
+
+    pub fn cgi(mut self, cgi: CGI) -> Self {
+        self.cgi = Some(cgi);
+        self
+    }
+
+    pub fn sai(mut self, sai: SAI) -> Self {
+        self.sai = Some(sai);
+        self
+    }
+
+    pub fn rai(mut self, rai: RAI) -> Self {
+        self.rai = Some(rai);
+        self
+    }
+
+    pub fn tai(mut self, tai: TAI) -> Self {
+        self.tai = Some(tai);
+        self
+    }
+
+    pub fn ecgi(mut self, ecgi: ECGI) -> Self {
+        self.ecgi = Some(ecgi);
+        self
+    }
+
+    pub fn lai(mut self, lai: LAI) -> Self {
+        self.lai = Some(lai);
+        self
+    }
+
+    pub fn menbid(mut self, menbid: MeNBID) -> Self {
+        self.menbid = Some(menbid);
+        self
+    }
+
+    pub fn emenbid(mut self, emenbid: EMeNBID) -> Self {
+        self.emenbid = Some(emenbid);
+        self
+    }
+
+    pub fn build(self) -> Result<InformationElement, String> {
+        InformationElement::new(
+            self.cgi,
+            self.sai,
+            self.rai,
+            self.tai,
+            self.ecgi,
+            self.lai,
+            self.menbid,
+            self.emenbid,
+            self.instance
+        )
+    }
 }
 
 impl InformationElementTraits for InformationElement {
@@ -794,11 +1142,8 @@ impl InformationElementTraits for InformationElement {
         }
     }
 
-    fn length(&self) -> u16 {
-        /* This is the actual length of the Information element INCLUDING the first 4 octets
-        To calculate the length field of the IE you need to subtract 4 from what is returned */
-
-        let mut length = 4;
+    fn payload_length(&self) -> u16 {
+        let mut length = 0;
 
         length = length + 1; // Flags
 
@@ -838,8 +1183,7 @@ impl InformationElementTraits for InformationElement {
         pos = pos + 1;
 
         // Write the length
-        // We subtract 4 octets as the type and length fields aren't included.
-        NetworkEndian::write_u16(&mut buffer[LENGTH], self.length()-4);
+        NetworkEndian::write_u16(&mut buffer[LENGTH], self.payload_length());
         pos = pos + 2;
 
         //Spare and instance
@@ -878,50 +1222,135 @@ impl InformationElementTraits for InformationElement {
     }
 }
 
+impl Dissect for InformationElement {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_ie(self, d, |d| {
+            if let Some(ref li) = self.cgi {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.sai {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.rai {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.tai {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.ecgi {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.lai {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.menbid {
+                li.dissect(d);
+            }
+
+            if let Some(ref li) = self.emenbid {
+                li.dissect(d);
+            }
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::MTU;
     use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
 
+    #[test]
+    fn test_plmn() {
+        let mut buffer = [0; 3];
+
+        // 2 digit MNC
+        let plmn = PLMN::new(505, 1, false).unwrap();
+
+        assert_eq!(plmn.generate(&mut buffer), 3);
+        assert_eq!(buffer, [0x05, 0xF5, 0x10]);
+        assert_eq!(PLMN::parse(&buffer), Some((plmn, 3)));
+        assert_eq!(plmn.to_string(), "505-01");
+
+        // 3 digit MNC
+        let plmn = PLMN::new(310, 410, true).unwrap();
+
+        assert_eq!(plmn.generate(&mut buffer), 3);
+        assert_eq!(buffer, [0x13, 0x00, 0x14]);
+        assert_eq!(PLMN::parse(&buffer), Some((plmn, 3)));
+        assert_eq!(plmn.to_string(), "310-410");
+
+        // A 3 digit MNC with a leading 0 is different to the 2 digit MNC
+        assert_ne!(PLMN::new(505, 1, true).unwrap(), PLMN::new(505, 1, false).unwrap());
+        assert_eq!(PLMN::new(505, 1, true).unwrap().to_string(), "505-001");
+
+        assert!(PLMN::new(1000, 1, false).is_err());
+        assert!(PLMN::new(505, 100, false).is_err());
+        assert!(PLMN::new(505, 1000, true).is_err());
+
+        // Digits > 9 other than the filler in MNC digit 3
+        assert!(PLMN::parse(&[0x0A, 0xF5, 0x10]).is_none());
+        assert!(PLMN::parse(&[0x05, 0xF5, 0xF0]).is_none());
+        assert!(PLMN::parse(&[0x05, 0xF5]).is_none());
+    }
+
+    #[test]
+    fn test_plmn_from_str() {
+        assert_eq!("505-99".parse::<PLMN>(), PLMN::new(505, 99, false));
+        assert_eq!("505-099".parse::<PLMN>(), PLMN::new(505, 99, true));
+        assert_eq!("001-01".parse::<PLMN>(), PLMN::new(1, 1, false));
+
+        assert!("50599".parse::<PLMN>().is_err());
+        assert!("505-9".parse::<PLMN>().is_err());
+        assert!("505-9999".parse::<PLMN>().is_err());
+        assert!("5a5-99".parse::<PLMN>().is_err());
+        assert!("505-+9".parse::<PLMN>().is_err());
+    }
+
     #[test]
     fn test_generate() {
         let mut buffer = [0; MTU];
 
         let ie = InformationElement::new(
             Some(CGI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(SAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(RAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(TAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
             )),
             Some(ECGI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0xF_FF_FF_FF
             ).unwrap()),
             Some(LAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
             )),
             Some(MeNBID::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0xFFFFF,
             ).unwrap()),
             Some(EMeNBID::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1FFFFF,
             ).unwrap()),
             0).unwrap();
@@ -962,43 +1391,84 @@ mod tests {
     fn test_length() {
         let ie = InformationElement::new(
             Some(CGI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(SAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(RAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
                 0x4321,
             )),
             Some(TAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
             )),
             Some(ECGI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1FFFF
             ).unwrap()),
             Some(LAI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1234,
             )),
             Some(MeNBID::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0xFFFFF,
             ).unwrap()),
             Some(EMeNBID::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1FFFFF,
             ).unwrap()),
             0).unwrap();
 
-        assert_eq!(ie.length(), 51+4);
+        assert_eq!(ie.wire_length(), 51+4);
+    }
+
+    #[test]
+    fn test_builder() {
+        let ie = InformationElement::builder()
+            .rai(RAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234, 0x4321))
+            .lai(LAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234))
+            .instance(1)
+            .build();
+
+        if let Ok(ie) = ie {
+            assert_eq!(ie.rai, Some(RAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234, 0x4321)));
+            assert_eq!(ie.lai, Some(LAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234)));
+            assert!(ie.cgi.is_none());
+            assert!(ie.tai.is_none());
+            assert_eq!(ie.instance(), 1);
+            assert_eq!(ie.generateflags(), 0b0010_0100);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(InformationElement::builder().instance(0x10).build().is_err());
+    }
+
+    #[test]
+    fn test_for_eutran() {
+        let ie = InformationElement::for_eutran(PLMN::new(505, 99, true).unwrap(), 0x1234, 0x1234567, 0);
+
+        if let Ok(ie) = ie {
+            assert_eq!(ie.tai, Some(TAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234)));
+            assert_eq!(ie.ecgi, Some(ECGI::new(PLMN::new(505, 99, true).unwrap(), 0x1234567).unwrap()));
+            assert_eq!(ie.generateflags(), 0b0001_1000);
+            assert_eq!(ie.wire_length(), 4 + 1 + 5 + 7);
+        }
+        else {
+            assert!(false);
+        }
+
+        // ECI is only 28 bits
+        assert!(InformationElement::for_eutran(PLMN::new(505, 99, true).unwrap(), 0x1234, 0x1FFFFFFF, 0).is_err());
     }
 
     #[test]
@@ -1009,7 +1479,7 @@ mod tests {
             None,
             None,
             Some(ECGI::new(
-                PLMN::new([5,0,5], [0,9,9]),
+                PLMN::new(505, 99, true).unwrap(),
                 0x1FFFF
             ).unwrap()),
             None,
@@ -1048,7 +1518,7 @@ mod tests {
             // Parsing was successful
             if let Some(li) = ie.cgi 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.lac, 0x1234);
                 assert_eq!(li.ci, 0x4321);
             }
@@ -1056,7 +1526,7 @@ mod tests {
 
             if let Some(li) = ie.sai 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.lac, 0x1234);
                 assert_eq!(li.sac, 0x4321);
             }
@@ -1064,7 +1534,7 @@ mod tests {
 
             if let Some(li) = ie.rai 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.lac, 0x1234);
                 assert_eq!(li.rac, 0x4321);
             }
@@ -1072,35 +1542,35 @@ mod tests {
 
             if let Some(li) = ie.tai 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.tac, 0x1234);
             }
             else { assert!(false); } 
 
             if let Some(li) = ie.ecgi 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.eci, 0xF_FF_FF_FF);
             }
             else { assert!(false); } 
 
             if let Some(li) = ie.lai 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.lac, 0x1234);
             }
             else { assert!(false); } 
 
             if let Some(li) = ie.menbid 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.menbid, 0xFFFFF);
             }
             else { assert!(false); }
 
             if let Some(li) = ie.emenbid 
             {
-                assert_eq!(li.plmn, PLMN::new([5,0,5], [0,9,9]));
+                assert_eq!(li.plmn, PLMN::new(505, 99, true).unwrap());
                 assert_eq!(li.menbid, 0x1FFFFF);
             }
             else { assert!(false); } 
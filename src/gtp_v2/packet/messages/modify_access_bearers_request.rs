@@ -0,0 +1,309 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    bearer_context,
+    indication,
+    f_teid,
+    delay_value,
+    recovery,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        Indication Flags                            |    Conditional            | 0             | 8.12
+        Sender F-TEID for Control Plane             |    Conditional            | 0             | 8.22
+        Delay Downlink Packet Notification Request  |    Conditional            | 0             | 8.27
+        Bearer Contexts to be modified              |    Conditional            | 0             | 8.28
+        Bearer Contexts to be removed               |    Conditional            | 1             | 8.28
+        Recovery                                    |    Conditional            | 0             | 8.5
+        Secondary RAT Usage Data Report             |    Conditional Optional   | 0             |
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        Sent on S11 by an MME that supports SGW relocation-less eNodeB changes (TS 23.401 5.5.1.1.2) instead of a Modify
+        Bearer Request per PDN connection. It modifies the S1-U bearers of every PDN connection of the UE at once, so
+        there's no ULI or Serving Network. The bearer contexts to be modified carry the S1-U eNodeB F-TEID (instance 0)
+        and S11-U MME F-TEID (instance 1) and the bearer contexts to be removed only carry the EPS Bearer ID.
+    */
+
+    pub indication: Option<indication::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub delay_downlink_packet_notification_request: Option<delay_value::InformationElement>,
+    pub bearer_contexts_to_be_modified: Vec<bearer_context::InformationElement>,
+    pub bearer_contexts_to_be_removed: Vec<bearer_context::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            indication: None,
+            sender_f_teid_for_control_plane: None,
+            delay_downlink_packet_notification_request: None,
+            bearer_contexts_to_be_modified: Vec::new(),
+            bearer_contexts_to_be_removed: Vec::new(),
+            recovery: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut m = Message::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Indication(ie) if ie.instance() == 0 => m.indication = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => m.sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::DelayValue(ie) if ie.instance() == 0 => m.delay_downlink_packet_notification_request = Some(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 0 => m.bearer_contexts_to_be_modified.push(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 1 => m.bearer_contexts_to_be_removed.push(ie),
+                    InformationElement::Recovery(ie) if ie.instance() == 0 => m.recovery = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => m.private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ModifyAccessBearersRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((m, pos))
+    }
+
+    pub fn push_bearer_context_to_be_modified(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(0).unwrap();
+        self.bearer_contexts_to_be_modified.push(bearer_context);
+    }
+
+    pub fn push_bearer_context_to_be_removed(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(1).unwrap();
+        self.bearer_contexts_to_be_removed.push(bearer_context);
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ModifyAccessBearersRequest
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.indication {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.delay_downlink_packet_notification_request {
+            ies.push(ie);
+        }
+
+        for ie in self.bearer_contexts_to_be_modified.iter() {
+            ies.push(ie);
+        }
+
+        for ie in self.bearer_contexts_to_be_removed.iter() {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.indication {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.delay_downlink_packet_notification_request {
+                ie.dissect(d);
+            }
+
+            for ie in self.bearer_contexts_to_be_modified.iter() {
+                ie.dissect(d);
+            }
+
+            for ie in self.bearer_contexts_to_be_removed.iter() {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, ebi};
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        let mut bc = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 0).unwrap();
+        bc.set_s1_u_enodeb_f_teid(0x12345678, Some(Ipv4Addr::new(10, 0, 0, 1)), None);
+        m.push_bearer_context_to_be_modified(bc);
+
+        m.recovery = Some(recovery::InformationElement::new(9, 0).unwrap());
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::BearerContext as u8,
+            0, 18, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare
+            0b1000_0000, // V4 and Interface Type
+            0x12, 0x34, 0x56, 0x78, // TEID
+            10, 0, 0, 1, // IPv4 Address
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            9, // Restart Counter
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_message().length(), 22 + 5);
+        assert_eq!(Message::new().length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_message().message_type(), MessageType::ModifyAccessBearersRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 27);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_bearer_contexts() {
+        let mut m = Message::new();
+
+        m.indication = Some(indication::InformationElement::with_flags(&[indication::ISRAI], 0).unwrap());
+        m.sender_f_teid_for_control_plane = Some(f_teid::InformationElement::new(f_teid::InterfaceType::S11MmeGtpC, 1, Some(Ipv4Addr::new(10, 0, 0, 2)), None, 0).unwrap());
+        m.delay_downlink_packet_notification_request = Some(delay_value::InformationElement::new(2, 0).unwrap());
+
+        let mut bc = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 7).unwrap();
+        bc.set_s1_u_enodeb_f_teid(0x12345678, Some(Ipv4Addr::new(10, 0, 0, 1)), None);
+        m.push_bearer_context_to_be_modified(bc);
+
+        m.push_bearer_context_to_be_removed(bearer_context::InformationElement::without_qos(ebi::InformationElement::new(6, 0).unwrap(), 0).unwrap());
+
+        assert_eq!(m.bearer_contexts_to_be_modified[0].instance(), 0);
+        assert_eq!(m.bearer_contexts_to_be_removed[0].instance(), 1);
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(m.length() as usize, pos);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_no_ies() {
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert_eq!(m, Message::new());
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
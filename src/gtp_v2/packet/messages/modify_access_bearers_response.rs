@@ -0,0 +1,370 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    bearer_context,
+    recovery,
+    indication,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        Cause                                       |    Mandatory              | 0             | 8.4
+        Bearer Contexts modified                    |    Conditional            | 0             | 8.28
+        Bearer Contexts marked for removal          |    Conditional            | 1             | 8.28
+        Recovery                                    |    Conditional            | 0             | 8.5
+        Indication Flags                            |    Conditional Optional   | 0             | 8.12
+        SGW's Node Level Load Control Information   |    Optional               | 0             |
+        SGW's Overload Control Information          |    Optional               | 0             |
+        PGW Change Info                             |    Conditional Optional   | 0             |
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        Each bearer context carries the EPS Bearer ID and a Cause. The SGW may accept the request but fail to modify some
+        of the bearers, in which case the Cause is Request accepted partially (TS 29.274 7.2.25). The bearer contexts
+        modified carry the S1-U SGW F-TEID (instance 0) and S11-U SGW F-TEID (instance 1).
+    */
+
+    pub cause: cause::InformationElement,
+    pub bearer_contexts_modified: Vec<bearer_context::InformationElement>,
+    pub bearer_contexts_marked_for_removal: Vec<bearer_context::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub indication: Option<indication::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            bearer_contexts_modified: Vec::new(),
+            bearer_contexts_marked_for_removal: Vec::new(),
+            recovery: None,
+            indication: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // A response that only carries the Cause e.g. Context Not Found when the TEID of the request is unknown
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause))
+    }
+
+    pub fn is_accepted(&self) -> bool {
+        // Fully or partially accepted
+        self.cause.is_accepted()
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        self.cause.is_rejected()
+    }
+
+    pub fn bearers_not_modified(&self) -> Vec<u8> {
+        // The EPS Bearer IDs of the bearer contexts modified whose Cause isn't an acceptance
+        self.bearer_contexts_modified.iter()
+            .filter(|bc| bc.cause.as_ref().map_or(false, |c| !c.cause_code.is_acceptance()))
+            .map(|bc| bc.eps_bearer_id.eps_bearer_id)
+            .collect()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut bearer_contexts_modified: Vec<bearer_context::InformationElement> = Vec::new();
+        let mut bearer_contexts_marked_for_removal: Vec<bearer_context::InformationElement> = Vec::new();
+        let mut recovery: Option<recovery::InformationElement> = None;
+        let mut indication: Option<indication::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) if ie.instance() == 0 => cause = Some(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 0 => bearer_contexts_modified.push(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 1 => bearer_contexts_marked_for_removal.push(ie),
+                    InformationElement::Recovery(ie) if ie.instance() == 0 => recovery = Some(ie),
+                    InformationElement::Indication(ie) if ie.instance() == 0 => indication = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ModifyAccessBearersResponse, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                bearer_contexts_modified,
+                bearer_contexts_marked_for_removal,
+                recovery,
+                indication,
+                private_extension,
+            },
+            pos
+        ))
+    }
+
+    pub fn push_bearer_context_modified(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(0).unwrap();
+        self.bearer_contexts_modified.push(bearer_context);
+    }
+
+    pub fn push_bearer_context_marked_for_removal(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(1).unwrap();
+        self.bearer_contexts_marked_for_removal.push(bearer_context);
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ModifyAccessBearersResponse
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = vec![&self.cause];
+
+        for ie in self.bearer_contexts_modified.iter() {
+            ies.push(ie);
+        }
+
+        for ie in self.bearer_contexts_marked_for_removal.iter() {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            for ie in self.bearer_contexts_modified.iter() {
+                ie.dissect(d);
+            }
+
+            for ie in self.bearer_contexts_marked_for_removal.iter() {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, ebi, f_teid};
+
+    use std::net::Ipv4Addr;
+
+    fn new_cause(cause_code: cause::CauseCode) -> cause::InformationElement {
+        cause::InformationElement::new(
+            cause_code,
+            cause::CauseSource::LocalNode,
+            false,
+            false,
+            None,
+            0
+        ).unwrap()
+    }
+
+    fn bearer_context_modified(eps_bearer_id: u8, cause_code: cause::CauseCode) -> bearer_context::InformationElement {
+        let mut bc = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(eps_bearer_id, 0).unwrap(), 0).unwrap();
+        bc.cause = Some(new_cause(cause_code));
+        bc
+    }
+
+    fn new_message() -> Message {
+        let mut m = Message::new(new_cause(cause::CauseCode::RequestAccepted));
+
+        m.push_bearer_context_modified(bearer_context_modified(5, cause::CauseCode::RequestAccepted));
+        m.recovery = Some(recovery::InformationElement::new(9, 0).unwrap());
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::BearerContext as u8,
+            0, 11, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            9, // Restart Counter
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_message().length(), 6 + 15 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_message().message_type(), MessageType::ModifyAccessBearersResponse)
+    }
+
+    #[test]
+    fn test_reject_with_cause() {
+        if let Ok(m) = Message::reject_with_cause(new_cause(cause::CauseCode::ContextNotFound)) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::ContextNotFound);
+            assert!(m.is_rejected());
+            assert!(m.bearer_contexts_modified.is_empty());
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Message::reject_with_cause(new_cause(cause::CauseCode::RequestAccepted)).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 26);
+            assert_eq!(m, new_message());
+            assert_eq!(m.bearer_contexts_modified[0].cause.as_ref().map(|c| c.cause_code), Some(cause::CauseCode::RequestAccepted));
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(Message::parse(&[]).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+
+    #[test]
+    fn test_partially_accepted() {
+        let mut m = Message::new(new_cause(cause::CauseCode::RequestAcceptedPartially));
+
+        let mut bc = bearer_context_modified(5, cause::CauseCode::RequestAccepted);
+        bc.s1_u_enodeb_f_teid = Some(f_teid::InformationElement::new(f_teid::InterfaceType::S1USgwGtpU, 0x1234, Some(Ipv4Addr::new(10, 0, 0, 1)), None, 0).unwrap());
+        m.push_bearer_context_modified(bc);
+        m.push_bearer_context_modified(bearer_context_modified(6, cause::CauseCode::NoResourcesAvailable));
+        m.push_bearer_context_marked_for_removal(bearer_context_modified(7, cause::CauseCode::RequestAccepted));
+
+        assert!(m.is_accepted());
+        assert_eq!(m.bearers_not_modified(), [6]);
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(m.length() as usize, pos);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+            assert_eq!(parsed.bearer_contexts_marked_for_removal[0].eps_bearer_id.eps_bearer_id, 7);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
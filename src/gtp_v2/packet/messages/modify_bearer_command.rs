@@ -0,0 +1,326 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    ambr,
+    bearer_context,
+    f_teid,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        APN-Aggregate Maximum Bit Rate (APN-AMBR)   |    Mandatory              |   8.7
+        Bearer Context                              |    Mandatory              |   8.28
+        MME/S4-SGSN's Overload Control Information  |    Optional               |
+        SGW's Overload Control Information          |    Optional               |
+        TWAN/ePDG's Overload Control Information    |    Optional               |
+        Sender F-TEID for Control Plane             |    Conditional Optional   |   8.22
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+
+        The Bearer Context represents the Default Bearer
+    */
+
+    pub apn_ambr: ambr::InformationElement,
+    pub bearer_context: bearer_context::InformationElement,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(apn_ambr: ambr::InformationElement, bearer_context: bearer_context::InformationElement) -> Message {
+        Message {
+            apn_ambr,
+            bearer_context,
+            sender_f_teid_for_control_plane: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut apn_ambr: Option<ambr::InformationElement> = None;
+        let mut bearer_context: Option<bearer_context::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::AMBR(ie) => apn_ambr = Some(ie),
+                    // The Bearer Level QoS is mandatory in the bearer context
+                    InformationElement::BearerContext(ie) if ie.instance() == 0 && ie.bearer_level_qos.is_none() => failed.push((InformationElementType::BearerContext, 0)),
+                    InformationElement::BearerContext(ie) if ie.instance() == 0 => bearer_context = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ModifyBearerCommand, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if apn_ambr.is_none() {
+            missing.push((InformationElementType::AMBR, 0));
+        }
+
+        if bearer_context.is_none() {
+            missing.push((InformationElementType::BearerContext, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                apn_ambr: apn_ambr.unwrap(),
+                bearer_context: bearer_context.unwrap(),
+                sender_f_teid_for_control_plane,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ModifyBearerCommand
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.apn_ambr.wire_length();
+        length = length + self.bearer_context.wire_length();
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.apn_ambr.generate(&mut buffer[pos..]);
+        pos = pos + self.bearer_context.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.apn_ambr);
+        ies.push(&self.bearer_context);
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.apn_ambr.dissect(d);
+            self.bearer_context.dissect(d);
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, bearer_qos, ebi};
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let bearer_context = bearer_context::InformationElement::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+            0
+        ).unwrap();
+
+        let mut m = Message::new(
+            ambr::InformationElement::new(1000, 2000, 0).unwrap(),
+            bearer_context
+        );
+
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S5S8SgwGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10, 0, 0, 1)),
+                None,
+                0
+            ).unwrap()
+        );
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::AMBR as u8,
+            0, 8, // Length
+            0, // Spare
+            0x00, 0x00, 0x03, 0xE8, // Uplink
+            0x00, 0x00, 0x07, 0xD0, // Downlink
+            InformationElementType::BearerContext as u8,
+            0, 31, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::BearerQoS as u8,
+            0, 22, // Length
+            0, // Spare
+            0b01100100, // Flags
+            9, // QCI
+            0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0,
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare
+            0b1000_0000 | u8::from(f_teid::InterfaceType::S5S8SgwGtpC),
+            0x12, 0x34, 0x56, 0x78, // TEID
+            10, 0, 0, 1, // IPv4 Address
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 12 + 35 + 13);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ModifyBearerCommand)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 60);
+            assert_eq!(m.apn_ambr.uplink, 1000);
+            assert_eq!(m.apn_ambr.downlink, 2000);
+            assert_eq!(m.bearer_context.eps_bearer_id.eps_bearer_id, 5);
+            assert_eq!(m.bearer_context.bearer_level_qos.as_ref().unwrap().qci, bearer_qos::Qci::Qci9);
+
+            if let Some(ie) = m.sender_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = message_bytes();
+
+        assert_eq!(
+            Message::parse(&message_bytes[12..]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::AMBR, 0)]))
+        );
+
+        assert_eq!(
+            Message::parse(&message_bytes[..12]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::BearerContext, 0)]))
+        );
+    }
+
+    #[test]
+    fn test_message_parse_bearer_context_without_qos() {
+        let mut m = new_message();
+        m.bearer_context.bearer_level_qos = None;
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(
+            Message::parse(&buffer[..pos]).err(),
+            Some(ParseError::MandatoryIEIncorrect(vec![(InformationElementType::BearerContext, 0)]))
+        );
+    }
+}
@@ -0,0 +1,466 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    mei,
+    bearer_context,
+    user_location_information,
+    serving_network,
+    rat_type,
+    indication,
+    f_teid,
+    ambr,
+    delay_value,
+    recovery,
+    ue_time_zone,
+    fq_csid,
+    uci,
+    change_to_report_flags,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        ME Identity (MEI)                           |    Conditional            | 0             | 8.10
+        User Location Information (ULI)             |    Conditional            | 0             | 8.21
+        Serving Network                             |    Conditional Optional   | 0             | 8.18
+        RAT Type                                    |    Conditional            | 0             | 8.17
+        Indication Flags                            |    Conditional Optional   | 0             | 8.12
+        Sender F-TEID for Control Plane             |    Conditional            | 0             | 8.22
+        APN-Aggregate Maximum Bit Rate (APN-AMBR)   |    Conditional            | 0             | 8.7
+        Delay Downlink Packet Notification Request  |    Conditional            | 0             | 8.27
+        Bearer Contexts to be modified              |    Conditional            | 0             | 8.28
+        Bearer Contexts to be removed               |    Conditional            | 1             | 8.28
+        Recovery                                    |    Conditional            | 0             | 8.5
+        UE Time Zone                                |    Conditional Optional   | 0             | 8.44
+        MME-FQ-CSID                                 |    Conditional            | 0             | 8.62
+        SGW-FQ-CSID                                 |    Conditional            | 1             | 8.62
+        User CSG Information (UCI)                  |    Conditional Optional   | 0             | 8.75
+        Change to Report Flags                      |    Conditional            | 0             | 8.101
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        Every IE is conditional. After a mobility event only the IEs that changed are sent (TS 29.274 7.2.7), see
+        gtp_v2::location. After a handover the bearer contexts to be modified carry the new S1-U eNodeB F-TEIDs
+    */
+
+    pub mei: Option<mei::InformationElement>,
+    pub user_location_information: Option<user_location_information::InformationElement>,
+    pub serving_network: Option<serving_network::InformationElement>,
+    pub rat_type: Option<rat_type::InformationElement>,
+    pub indication: Option<indication::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub apn_ambr: Option<ambr::InformationElement>,
+    pub delay_downlink_packet_notification_request: Option<delay_value::InformationElement>,
+    pub bearer_contexts_to_be_modified: Vec<bearer_context::InformationElement>,
+    pub bearer_contexts_to_be_removed: Vec<bearer_context::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub ue_time_zone: Option<ue_time_zone::InformationElement>,
+    pub mme_fq_csid: Option<fq_csid::InformationElement>,
+    pub sgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub uci: Option<uci::InformationElement>,
+    pub change_to_report_flags: Option<change_to_report_flags::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            mei: None,
+            user_location_information: None,
+            serving_network: None,
+            rat_type: None,
+            indication: None,
+            sender_f_teid_for_control_plane: None,
+            apn_ambr: None,
+            delay_downlink_packet_notification_request: None,
+            bearer_contexts_to_be_modified: Vec::new(),
+            bearer_contexts_to_be_removed: Vec::new(),
+            recovery: None,
+            ue_time_zone: None,
+            mme_fq_csid: None,
+            sgw_fq_csid: None,
+            uci: None,
+            change_to_report_flags: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut m = Message::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::MEI(ie) if ie.instance() == 0 => m.mei = Some(ie),
+                    InformationElement::ULI(ie) if ie.instance() == 0 => m.user_location_information = Some(ie),
+                    InformationElement::ServingNetwork(ie) if ie.instance() == 0 => m.serving_network = Some(ie),
+                    InformationElement::RATType(ie) if ie.instance() == 0 => m.rat_type = Some(ie),
+                    InformationElement::Indication(ie) if ie.instance() == 0 => m.indication = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => m.sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::AMBR(ie) if ie.instance() == 0 => m.apn_ambr = Some(ie),
+                    InformationElement::DelayValue(ie) if ie.instance() == 0 => m.delay_downlink_packet_notification_request = Some(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 0 => m.bearer_contexts_to_be_modified.push(ie),
+                    InformationElement::BearerContext(ie) if ie.instance() == 1 => m.bearer_contexts_to_be_removed.push(ie),
+                    InformationElement::Recovery(ie) if ie.instance() == 0 => m.recovery = Some(ie),
+                    InformationElement::UETimeZone(ie) if ie.instance() == 0 => m.ue_time_zone = Some(ie),
+                    InformationElement::FQCSID(ie) if ie.instance() == 0 => m.mme_fq_csid = Some(ie),
+                    InformationElement::FQCSID(ie) if ie.instance() == 1 => m.sgw_fq_csid = Some(ie),
+                    InformationElement::UCI(ie) if ie.instance() == 0 => m.uci = Some(ie),
+                    InformationElement::ChangeToReportFlags(ie) if ie.instance() == 0 => m.change_to_report_flags = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => m.private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ModifyBearerRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((m, pos))
+    }
+
+    pub fn push_bearer_context_to_be_modified(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(0).unwrap();
+        self.bearer_contexts_to_be_modified.push(bearer_context);
+    }
+
+    pub fn push_bearer_context_to_be_removed(&mut self, mut bearer_context: bearer_context::InformationElement) {
+        bearer_context.set_instance(1).unwrap();
+        self.bearer_contexts_to_be_removed.push(bearer_context);
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ModifyBearerRequest
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        if let Some(ref ie) = self.mei {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.user_location_information {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.serving_network {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.rat_type {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_ambr {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.delay_downlink_packet_notification_request {
+            ies.push(ie);
+        }
+
+        for ie in self.bearer_contexts_to_be_modified.iter() {
+            ies.push(ie);
+        }
+
+        for ie in self.bearer_contexts_to_be_removed.iter() {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.ue_time_zone {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.mme_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.uci {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.change_to_report_flags {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            if let Some(ref ie) = self.mei {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.user_location_information {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.serving_network {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.rat_type {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_ambr {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.delay_downlink_packet_notification_request {
+                ie.dissect(d);
+            }
+
+            for ie in self.bearer_contexts_to_be_modified.iter() {
+                ie.dissect(d);
+            }
+
+            for ie in self.bearer_contexts_to_be_removed.iter() {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.ue_time_zone {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.mme_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.uci {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.change_to_report_flags {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, ebi};
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::{PLMN, TAI, ECGI, UliBuilder};
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        m.user_location_information = Some(
+            UliBuilder::new()
+                .tai(TAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234))
+                .ecgi(ECGI::new(PLMN::new(505, 99, true).unwrap(), 0x1234567).unwrap())
+                .build()
+                .unwrap()
+        );
+        m.indication = Some(indication::InformationElement::with_flags(&[indication::CLII], 0).unwrap());
+        m.recovery = Some(recovery::InformationElement::new(9, 0).unwrap());
+        m.change_to_report_flags = Some(change_to_report_flags::InformationElement::new(true, false, 0).unwrap());
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::UserLocationInformation as u8,
+            0, 13, // Length
+            0, // Spare
+            0b0001_1000, // Flags
+            0x05, 0x95, 0x90, // PLMN
+            0x12, 0x34, // TAC
+            0x05, 0x95, 0x90, // PLMN
+            0x01, 0x23, 0x45, 0x67, // ECI
+            InformationElementType::Indication as u8,
+            0, 4, // Length
+            0, // Spare
+            0x00, 0x00, 0x00, 0b0000_0010, // Flags
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            9, // Restart Counter
+            InformationElementType::ChangeToReportFlags as u8,
+            0, 1, // Length
+            0, // Spare
+            0b0000_0001, // SNCR
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 17 + 8 + 5 + 5);
+        assert_eq!(Message::new().length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ModifyBearerRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 35);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_fq_csid() {
+        let mut m = Message::new();
+
+        m.mme_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 1)), vec![1], 0).unwrap());
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 2)), vec![2], 1).unwrap());
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_bearer_contexts() {
+        let mut m = Message::new();
+
+        let mut bc = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 7).unwrap();
+        bc.set_s1_u_enodeb_f_teid(0x12345678, Some(Ipv4Addr::new(10, 0, 0, 1)), None);
+        m.push_bearer_context_to_be_modified(bc);
+
+        m.push_bearer_context_to_be_removed(bearer_context::InformationElement::without_qos(ebi::InformationElement::new(6, 0).unwrap(), 0).unwrap());
+
+        assert_eq!(m.bearer_contexts_to_be_modified[0].instance(), 0);
+        assert_eq!(m.bearer_contexts_to_be_removed[0].instance(), 1);
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(m.length() as usize, pos);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+            assert_eq!(parsed.bearer_contexts_to_be_modified[0].s1_u_enodeb_f_teid.as_ref().map(|ie| ie.teid), Some(0x12345678));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_no_ies() {
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert_eq!(m, Message::new());
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
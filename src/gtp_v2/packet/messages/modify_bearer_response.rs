@@ -0,0 +1,361 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    cause,
+    ebi,
+    apn_restriction,
+    fq_csid,
+    recovery,
+    indication,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        Cause                                       |    Mandatory              | 0             | 8.4
+        MSISDN                                      |    Conditional            | 0             |
+        Linked EPS Bearer ID                        |    Conditional            | 0             | 8.8
+        APN Restriction                             |    Conditional            | 0             |
+        Protocol Configuration Options (PCO)        |    Conditional            | 0             |
+        Bearer Contexts modified                    |    Conditional            | 0             |
+        Bearer Contexts marked for removal          |    Conditional            | 1             |
+        Change Reporting Action                     |    Conditional            | 0             |
+        CSG Information Reporting Action            |    Conditional Optional   | 0             |
+        H(e)NB Information Reporting                |    Conditional Optional   | 0             |
+        Charging Gateway Name                       |    Conditional            | 0             |
+        Charging Gateway Address                    |    Conditional            | 0             |
+        PGW-FQ-CSID                                 |    Conditional            | 0             | 8.62
+        SGW-FQ-CSID                                 |    Conditional            | 1             | 8.62
+        Recovery                                    |    Conditional            | 0             | 8.5
+        SGW LDN                                     |    Optional               | 0             |
+        PGW LDN                                     |    Optional               | 1             |
+        Indication Flags                            |    Conditional Optional   | 0             | 8.12
+        Presence Reporting Area Action              |    Conditional Optional   | 0             |
+        PGW's Node Level Load Control Information   |    Optional               | 0             |
+        PGW's APN Level Load Control Information    |    Optional               | 0             |
+        SGW's Node Level Load Control Information   |    Optional               | 1             |
+        PGW Overload Control Information            |    Optional               | 0             |
+        SGW Overload Control Information            |    Optional               | 1             |
+        PDN Connection Charging ID                  |    Conditional Optional   | 0             |
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        The bearer contexts aren't decoded yet and are skipped when parsing
+    */
+
+    pub cause: cause::InformationElement,
+    pub linked_eps_bearer_id: Option<ebi::InformationElement>,
+    pub apn_restriction: Option<apn_restriction::InformationElement>,
+    pub pgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub sgw_fq_csid: Option<fq_csid::InformationElement>,
+    pub recovery: Option<recovery::InformationElement>,
+    pub indication: Option<indication::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(cause: cause::InformationElement) -> Message {
+        Message {
+            cause,
+            linked_eps_bearer_id: None,
+            apn_restriction: None,
+            pgw_fq_csid: None,
+            sgw_fq_csid: None,
+            recovery: None,
+            indication: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn reject_with_cause(cause: cause::InformationElement) -> Result<Message, String> {
+        // A response that only carries the Cause e.g. Context Not Found when the TEID of the request is unknown
+        if cause.cause_code.is_acceptance() {
+            return Err(format!("Cause Code is not a rejection {:?}", cause.cause_code));
+        }
+
+        Ok(Message::new(cause))
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut cause: Option<cause::InformationElement> = None;
+        let mut linked_eps_bearer_id: Option<ebi::InformationElement> = None;
+        let mut apn_restriction: Option<apn_restriction::InformationElement> = None;
+        let mut pgw_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut sgw_fq_csid: Option<fq_csid::InformationElement> = None;
+        let mut recovery: Option<recovery::InformationElement> = None;
+        let mut indication: Option<indication::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::Cause(ie) if ie.instance() == 0 => cause = Some(ie),
+                    InformationElement::EBI(ie) if ie.instance() == 0 => linked_eps_bearer_id = Some(ie),
+                    InformationElement::APNRestriction(ie) if ie.instance() == 0 => apn_restriction = Some(ie),
+                    InformationElement::FQCSID(ie) if ie.instance() == 0 => pgw_fq_csid = Some(ie),
+                    InformationElement::FQCSID(ie) if ie.instance() == 1 => sgw_fq_csid = Some(ie),
+                    InformationElement::Recovery(ie) if ie.instance() == 0 => recovery = Some(ie),
+                    InformationElement::Indication(ie) if ie.instance() == 0 => indication = Some(ie),
+                    InformationElement::PrivateExtension(ie) if ie.instance() == 0 => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ModifyBearerResponse, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if cause.is_none() {
+            missing.push((InformationElementType::Cause, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                cause: cause.unwrap(),
+                linked_eps_bearer_id,
+                apn_restriction,
+                pgw_fq_csid,
+                sgw_fq_csid,
+                recovery,
+                indication,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ModifyBearerResponse
+    }
+
+    fn length(&self) -> u16 {
+        self.ies().map(|ie| ie.wire_length()).sum()
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in self.ies() {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = vec![&self.cause];
+
+        if let Some(ref ie) = self.linked_eps_bearer_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.apn_restriction {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.pgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sgw_fq_csid {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.recovery {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.cause.dissect(d);
+
+            if let Some(ref ie) = self.linked_eps_bearer_id {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.apn_restriction {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.pgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sgw_fq_csid {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.recovery {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_cause(cause_code: cause::CauseCode) -> cause::InformationElement {
+        cause::InformationElement::new(
+            cause_code,
+            cause::CauseSource::LocalNode,
+            false,
+            false,
+            None,
+            0
+        ).unwrap()
+    }
+
+    fn new_message() -> Message {
+        let mut m = Message::new(new_cause(cause::CauseCode::RequestAccepted));
+
+        m.linked_eps_bearer_id = Some(ebi::InformationElement::new(5, 0).unwrap());
+        m.recovery = Some(recovery::InformationElement::new(9, 0).unwrap());
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::Cause as u8,
+            0, 2, // Length
+            0, // Spare
+            u8::from(cause::CauseCode::RequestAccepted),
+            0b00000000,
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EBI
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            9, // Restart Counter
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(new_message().length(), 6 + 5 + 5);
+    }
+
+    #[test]
+    fn test_message_type() {
+        assert_eq!(new_message().message_type(), MessageType::ModifyBearerResponse)
+    }
+
+    #[test]
+    fn test_reject_with_cause() {
+        if let Ok(m) = Message::reject_with_cause(new_cause(cause::CauseCode::ContextNotFound)) {
+            assert_eq!(m.cause.cause_code, cause::CauseCode::ContextNotFound);
+            assert_eq!(m.linked_eps_bearer_id, None);
+        }
+        else {
+            assert!(false);
+        }
+
+        assert!(Message::reject_with_cause(new_cause(cause::CauseCode::RequestAccepted)).is_err());
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 16);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(Message::parse(&[]).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::Cause, 0)])));
+    }
+
+    #[test]
+    fn test_message_parse_fq_csid() {
+        let mut m = new_message();
+
+        m.pgw_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 1)), vec![1], 0).unwrap());
+        m.sgw_fq_csid = Some(fq_csid::InformationElement::new(fq_csid::NodeId::IPv4(Ipv4Addr::new(10, 0, 0, 2)), vec![2], 1).unwrap());
+
+        let mut buffer = [0; MTU];
+        let pos = m.generate(&mut buffer);
+
+        if let Ok((parsed, parsed_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(parsed_pos, pos);
+            assert_eq!(parsed, m);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    ebi,
+    indication,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        List of RABs                                |    Conditional            |   8.8
+        Originating Node                            |    Conditional Optional   |
+        Indication Flags                            |    Conditional Optional   |   8.12
+        Secondary RAT Usage Data Report             |    Conditional Optional   |
+        PSCell ID                                   |    Conditional Optional   |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+
+        List of RABs is only sent by an S4-SGSN to release some of the bearers of the UE. With ISR active the
+        Originating Node and the ISRAU flag tell the SGW which of the MME and SGSN is releasing its bearers.
+    */
+
+    pub list_of_rabs: Vec<ebi::InformationElement>,
+    pub indication_flags: Option<indication::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            list_of_rabs: Vec::new(),
+            indication_flags: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn push_rab(&mut self, ebi: ebi::InformationElement) {
+        self.list_of_rabs.push(ebi);
+    }
+
+    pub fn is_abnormal_release_of_radio_link(&self) -> bool {
+        match self.indication_flags {
+            Some(ref ie) => ie.is_set(indication::ARRL),
+            None => false,
+        }
+    }
+
+    pub fn isr_activated_for_ue(&self) -> bool {
+        match self.indication_flags {
+            Some(ref ie) => ie.isr_activated_for_ue(),
+            None => false,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut list_of_rabs: Vec<ebi::InformationElement> = Vec::new();
+        let mut indication_flags: Option<indication::InformationElement> = None;
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::EBI(ie) => list_of_rabs.push(ie),
+                    InformationElement::Indication(ie) => indication_flags = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ReleaseAccessBearersRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed. None of the IEs are mandatory so just skip it
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((
+            Message {
+                list_of_rabs,
+                indication_flags,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ReleaseAccessBearersRequest
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        for ie in &self.list_of_rabs {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in &self.list_of_rabs {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        for ie in &self.list_of_rabs {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.indication_flags {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.list_of_rabs {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.indication_flags {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        m.push_rab(ebi::InformationElement::new(5, 0).unwrap());
+        m.indication_flags = Some(indication::InformationElement::with_flags(&[indication::ARRL], 0).unwrap());
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], [
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::Indication as u8,
+            0, 4, // Length
+            0, // Spare and Instance
+            0, 0, 0, 0b0100_0000, // ARRL
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 5 + 8);
+        assert_eq!(Message::new().length(), 0);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ReleaseAccessBearersRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, _pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m.list_of_rabs.len(), 1);
+            assert_eq!(m.list_of_rabs[0].eps_bearer_id, 5);
+            assert!(m.is_abnormal_release_of_radio_link());
+            assert!(!m.isr_activated_for_ue());
+        }
+        else {
+            assert!(false);
+        }
+
+        // An empty Release Access Bearers Request releases all of the bearers
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(m.list_of_rabs.len(), 0);
+            assert!(!m.is_abnormal_release_of_radio_link());
+            assert_eq!(pos, 0);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
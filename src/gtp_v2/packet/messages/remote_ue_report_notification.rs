@@ -0,0 +1,259 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    remote_ue_context,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|---------------|---------
+        Information Element                         |   Presence requirement    | Instance      | Reference
+        --------------------------------------------|---------------------------|---------------|---------
+        Remote UE Context Connected                 |    Conditional            | 0             | 8.122
+        Remote UE Context Disconnected              |    Conditional            | 1             | 8.122
+        Private Extension                           |    Optional               | 0             |
+        --------------------------------------------|---------------------------|---------------|---------
+
+        Sent by a MME (and forwarded by the SGW) when a ProSe UE-to-Network relay reports that remote UEs have
+        connected or disconnected. Both IEs may be repeated, one per remote UE
+    */
+
+    pub remote_ue_contexts_connected: Vec<remote_ue_context::InformationElement>,
+    pub remote_ue_contexts_disconnected: Vec<remote_ue_context::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message {
+            remote_ue_contexts_connected: Vec::new(),
+            remote_ue_contexts_disconnected: Vec::new(),
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut remote_ue_contexts_connected: Vec<remote_ue_context::InformationElement> = Vec::new();
+        let mut remote_ue_contexts_disconnected: Vec<remote_ue_context::InformationElement> = Vec::new();
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::RemoteUEContext(ie) if ie.instance() == 0 => remote_ue_contexts_connected.push(ie),
+                    InformationElement::RemoteUEContext(ie) if ie.instance() == 1 => remote_ue_contexts_disconnected.push(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::RemoteUEReportNotification, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        Ok((
+            Message {
+                remote_ue_contexts_connected,
+                remote_ue_contexts_disconnected,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::RemoteUEReportNotification
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        for ie in &self.remote_ue_contexts_connected {
+            length = length + ie.wire_length();
+        }
+
+        for ie in &self.remote_ue_contexts_disconnected {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        for ie in &self.remote_ue_contexts_connected {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        for ie in &self.remote_ue_contexts_disconnected {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        for ie in &self.remote_ue_contexts_connected {
+            ies.push(ie);
+        }
+
+        for ie in &self.remote_ue_contexts_disconnected {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            for ie in &self.remote_ue_contexts_connected {
+                ie.dissect(d);
+            }
+
+            for ie in &self.remote_ue_contexts_disconnected {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{InformationElementType, remote_user_id, remote_ue_ip_information};
+
+    fn new_message() -> Message {
+        let mut m = Message::new();
+
+        let mut connected = remote_ue_context::InformationElement::new(
+            remote_user_id::InformationElement::new("505990001234567", None, None, 0).unwrap(),
+            0
+        ).unwrap();
+        connected.remote_ue_ip_information = Some(remote_ue_ip_information::InformationElement::new(&[0x01, 10, 45, 0, 7], 0).unwrap());
+
+        m.remote_ue_contexts_connected.push(connected);
+
+        m.remote_ue_contexts_disconnected.push(
+            remote_ue_context::InformationElement::new(
+                remote_user_id::InformationElement::new("505990007654321", None, None, 0).unwrap(),
+                1
+            ).unwrap()
+        );
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::RemoteUEContext as u8,
+            0, 23, // Length
+            0, // Spare
+            InformationElementType::RemoteUserID as u8,
+            0, 10, // Length
+            0, // Spare
+            0, // IMEIF and MSISDNF
+            8, 0x05, 0x95, 0x09, 0x00, 0x21, 0x43, 0x65, 0xF7, // IMSI
+            InformationElementType::RemoteUEIPInformation as u8,
+            0, 5, // Length
+            0, // Spare
+            0x01, 10, 45, 0, 7, // Remote UE IP Information
+            InformationElementType::RemoteUEContext as u8,
+            0, 14, // Length
+            1, // Spare and Instance
+            InformationElementType::RemoteUserID as u8,
+            0, 10, // Length
+            0, // Spare
+            0, // IMEIF and MSISDNF
+            8, 0x05, 0x95, 0x09, 0x00, 0x67, 0x45, 0x23, 0xF1, // IMSI
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 27 + 18);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::RemoteUEReportNotification)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 45);
+            assert_eq!(m, new_message());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_no_ies() {
+        if let Ok((m, pos)) = Message::parse(&[]) {
+            assert_eq!(pos, 0);
+            assert!(m.remote_ue_contexts_connected.is_empty());
+            assert!(m.remote_ue_contexts_disconnected.is_empty());
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
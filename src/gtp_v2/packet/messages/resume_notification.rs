@@ -0,0 +1,293 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    imsi,
+    ebi,
+    f_teid,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Mandatory              |   8.3
+        Linked EPS Bearer ID (LBI)                  |    Conditional Optional   |   8.8
+        Originating Node                            |    Conditional Optional   |
+        Sender F-TEID for Control Plane             |    Conditional Optional   |   8.22
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+    */
+
+    pub imsi: imsi::InformationElement,
+    pub linked_eps_bearer_id: Option<ebi::InformationElement>,
+    pub sender_f_teid_for_control_plane: Option<f_teid::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(imsi: imsi::InformationElement) -> Message {
+        Message {
+            imsi,
+            linked_eps_bearer_id: None,
+            sender_f_teid_for_control_plane: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut linked_eps_bearer_id: Option<ebi::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::EBI(ie) if ie.instance() == 0 => linked_eps_bearer_id = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::ResumeNotification, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if imsi.is_none() {
+            missing.push((InformationElementType::IMSI, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                imsi: imsi.unwrap(),
+                linked_eps_bearer_id,
+                sender_f_teid_for_control_plane,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::ResumeNotification
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.imsi.wire_length();
+
+        if let Some(ref ie) = self.linked_eps_bearer_id {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.imsi.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.linked_eps_bearer_id {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.imsi);
+
+        if let Some(ref ie) = self.linked_eps_bearer_id {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.imsi.dissect(d);
+
+            if let Some(ref ie) = self.linked_eps_bearer_id {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.sender_f_teid_for_control_plane {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new(imsi::InformationElement::new("505013485090404", 0).unwrap());
+
+        m.linked_eps_bearer_id = Some(ebi::InformationElement::new(5, 0).unwrap());
+
+        m.sender_f_teid_for_control_plane = Some(
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10, 0, 0, 1)),
+                None,
+                0
+            ).unwrap()
+        );
+
+        m
+    }
+
+    fn message_bytes() -> Vec<u8> {
+        vec![
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // Linked EPS Bearer ID
+            InformationElementType::FTEID as u8,
+            0, 9, // Length
+            0, // Spare
+            0b1000_0000 | u8::from(f_teid::InterfaceType::S11MmeGtpC),
+            0x12, 0x34, 0x56, 0x78, // TEID
+            10, 0, 0, 1, // IPv4 Address
+        ]
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], message_bytes()[..]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 12 + 5 + 13);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::ResumeNotification)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        if let Ok((m, pos)) = Message::parse(&message_bytes()) {
+            assert_eq!(pos, 30);
+            assert_eq!(m.imsi.imsi, imsi::InformationElement::new("505013485090404", 0).unwrap().imsi);
+
+            if let Some(ie) = m.linked_eps_bearer_id {
+                assert_eq!(ie.eps_bearer_id, 5);
+            }
+            else {
+                assert!(false);
+            }
+
+            if let Some(ie) = m.sender_f_teid_for_control_plane {
+                assert_eq!(ie.teid, 0x12345678);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = message_bytes();
+
+        assert_eq!(
+            Message::parse(&message_bytes[12..]).err(),
+            Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::IMSI, 0)]))
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use super::{Ies, MessageTraits, MessageType, ParseError, dissect_message, ignore_ie};
+
+use super::information_elements::{
+    InformationElement,
+    InformationElementTraits,
+    InformationElementType,
+    imsi,
+    mei,
+    f_teid,
+    msisdn,
+    stn_sr,
+    private_extension,
+};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Message {
+    /*
+        --------------------------------------------|---------------------------|-------------------------
+        Information Element                         |   Presence requirement    |   Reference
+        --------------------------------------------|---------------------------|-------------------------
+        IMSI                                        |    Mandatory              |   8.3
+        ME Identity (MEI)                           |    Conditional            |   8.10
+        Sender F-TEID for Control Plane             |    Mandatory              |   8.22
+        MM Context for E-UTRAN SRVCC                |    Conditional            |
+        MM Context for UTRAN SRVCC                  |    Conditional            |
+        SRVCC Flags                                 |    Conditional            |
+        Service Area Identifier                     |    Conditional            |
+        RAI                                         |    Conditional            |
+        Target RNC ID                               |    Conditional            |
+        Target Global Cell ID                       |    Conditional            |
+        TEID-C                                      |    Conditional            |
+        C-MSISDN                                    |    Conditional            |   8.11
+        Source to Target Transparent Container      |    Mandatory              |
+        STN-SR                                      |    Conditional            |   29.280 6.2
+        Anonymous User                              |    Conditional            |
+        Private Extension                           |    Optional               |
+        --------------------------------------------|---------------------------|-------------------------
+
+        Sent by the MME/SGSN to the MSC Server on Sv (TS 29.280 5.2.2). The C-MSISDN is encoded as an MSISDN IE.
+    */
+
+    pub imsi: imsi::InformationElement,
+    pub mei: Option<mei::InformationElement>,
+    pub sender_f_teid_for_control_plane: f_teid::InformationElement,
+    pub c_msisdn: Option<msisdn::InformationElement>,
+    pub stn_sr: Option<stn_sr::InformationElement>,
+    pub private_extension: Option<private_extension::InformationElement>,
+}
+
+impl Message {
+    pub fn new(imsi: imsi::InformationElement, sender_f_teid_for_control_plane: f_teid::InformationElement) -> Message {
+        Message {
+            imsi,
+            mei: None,
+            sender_f_teid_for_control_plane,
+            c_msisdn: None,
+            stn_sr: None,
+            private_extension: None,
+        }
+    }
+
+    pub fn parse(buffer: &[u8]) -> Result<(Self, usize), ParseError> {
+        // Keep trying to parse IEs until the end of the buffer
+        let mut pos = 0;
+
+        let mut imsi: Option<imsi::InformationElement> = None;
+        let mut mei: Option<mei::InformationElement> = None;
+        let mut sender_f_teid_for_control_plane: Option<f_teid::InformationElement> = None;
+        let mut c_msisdn: Option<msisdn::InformationElement> = None;
+        let mut stn_sr: Option<stn_sr::InformationElement> = None;
+
+        // IEs that were present but failed to parse
+        let mut failed: Vec<(InformationElementType, u8)> = Vec::new();
+        let mut private_extension: Option<private_extension::InformationElement> = None;
+
+        while pos < buffer.len()
+        {
+            if let Some((ie, ie_pos)) = InformationElement::parse(&buffer[pos..]){
+                match ie {
+                    InformationElement::IMSI(ie) => imsi = Some(ie),
+                    InformationElement::MEI(ie) => mei = Some(ie),
+                    InformationElement::FTEID(ie) if ie.instance() == 0 => sender_f_teid_for_control_plane = Some(ie),
+                    InformationElement::MSISDN(ie) => c_msisdn = Some(ie),
+                    InformationElement::STNSR(ie) => stn_sr = Some(ie),
+                    InformationElement::PrivateExtension(ie) => private_extension = Some(ie),
+                    // Its an IE that we didn't expect. Just ignore it
+                    ie => ignore_ie(MessageType::SRVCCPsToCsRequest, &ie),
+                }
+                pos = pos + ie_pos;
+            }
+            else {
+                // IE parsing failed
+                if let Some(ie) = InformationElement::type_and_instance(&buffer[pos..]) {
+                    failed.push(ie);
+                }
+                pos = pos + InformationElement::skip_parsing(&buffer[pos..]);
+            }
+        }
+
+        let mut missing: Vec<(InformationElementType, u8)> = Vec::new();
+
+        if imsi.is_none() {
+            missing.push((InformationElementType::IMSI, 0));
+        }
+
+        if sender_f_teid_for_control_plane.is_none() {
+            missing.push((InformationElementType::FTEID, 0));
+        }
+
+        ParseError::check_mandatory(missing, &failed)?;
+
+        Ok((
+            Message {
+                imsi: imsi.unwrap(),
+                mei,
+                sender_f_teid_for_control_plane: sender_f_teid_for_control_plane.unwrap(),
+                c_msisdn,
+                stn_sr,
+                private_extension,
+            },
+            pos
+        ))
+    }
+}
+
+impl MessageTraits for Message {
+    fn message_type(&self) -> MessageType {
+        MessageType::SRVCCPsToCsRequest
+    }
+
+    fn length(&self) -> u16 {
+        let mut length = 0;
+
+        length = length + self.imsi.wire_length();
+
+        if let Some(ref ie) = self.mei {
+            length = length + ie.wire_length();
+        }
+
+        length = length + self.sender_f_teid_for_control_plane.wire_length();
+
+        if let Some(ref ie) = self.c_msisdn {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.stn_sr {
+            length = length + ie.wire_length();
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            length = length + ie.wire_length();
+        }
+
+        length
+    }
+
+    fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        pos = pos + self.imsi.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.mei {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos = pos + self.sender_f_teid_for_control_plane.generate(&mut buffer[pos..]);
+
+        if let Some(ref ie) = self.c_msisdn {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.stn_sr {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            pos = pos + ie.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+
+    fn ies(&self) -> Ies<'_> {
+        let mut ies: Vec<&dyn InformationElementTraits> = Vec::new();
+
+        ies.push(&self.imsi);
+
+        if let Some(ref ie) = self.mei {
+            ies.push(ie);
+        }
+
+        ies.push(&self.sender_f_teid_for_control_plane);
+
+        if let Some(ref ie) = self.c_msisdn {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.stn_sr {
+            ies.push(ie);
+        }
+
+        if let Some(ref ie) = self.private_extension {
+            ies.push(ie);
+        }
+
+        ies.into_iter()
+    }
+}
+
+impl Dissect for Message {
+    fn dissect(&self, d: &mut Dissector) {
+        dissect_message(self, d, |d| {
+            self.imsi.dissect(d);
+
+            if let Some(ref ie) = self.mei {
+                ie.dissect(d);
+            }
+
+            self.sender_f_teid_for_control_plane.dissect(d);
+
+            if let Some(ref ie) = self.c_msisdn {
+                ie.dissect(d);
+            }
+
+            if let Some(ref ie) = self.stn_sr {
+                ie.dissect(d);
+            }
+            if let Some(ref ie) = self.private_extension {
+                ie.dissect(d);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+    use crate::gtp_v2::packet::messages::MessageType;
+
+    use std::net::Ipv4Addr;
+
+    fn new_message() -> Message {
+        let mut m = Message::new(
+            imsi::InformationElement::new("505013485090404", 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S3MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap()
+        );
+
+        m.c_msisdn = Some(msisdn::InformationElement::new("61400111222", 0).unwrap());
+        m.stn_sr = Some(stn_sr::InformationElement::new("61400999888", 0).unwrap());
+
+        m
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; MTU];
+
+        let m = new_message();
+
+        let pos = m.generate(&mut buffer);
+
+        assert_eq!(pos, 12 + 13 + 10 + 11);
+
+        assert_eq!(buffer[pos-11..pos], [
+            InformationElementType::STNSR as u8,
+            0, 7, // Length
+            0, // Spare
+            0x91, // NANPI
+            0x16, 0x04, 0x90, 0x99, 0x88, 0xF8,
+        ]);
+    }
+
+    #[test]
+    fn test_length() {
+        let m = new_message();
+
+        assert_eq!(m.length(), 12 + 13 + 10 + 11);
+    }
+
+    #[test]
+    fn test_message_type() {
+        let m = new_message();
+
+        assert_eq!(m.message_type(), MessageType::SRVCCPsToCsRequest)
+    }
+
+    #[test]
+    fn test_message_parse() {
+        let mut buffer = [0; MTU];
+
+        let pos = new_message().generate(&mut buffer);
+
+        if let Ok((m, m_pos)) = Message::parse(&buffer[..pos]) {
+            assert_eq!(m_pos, pos);
+            assert_eq!(m.sender_f_teid_for_control_plane.teid, 0x12345678);
+            assert!(m.mei.is_none());
+            assert_eq!(m.c_msisdn.map(|ie| ie.to_string()), Some("61400111222".to_string()));
+            assert_eq!(m.stn_sr.map(|ie| ie.to_string()), Some("61400999888".to_string()));
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_message_parse_mandatory_ie_missing() {
+        let message_bytes = [
+            InformationElementType::IMSI as u8,
+            0, 8, // Length
+            0, // Spare
+            0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+        ];
+
+        assert_eq!(Message::parse(&message_bytes).err(), Some(ParseError::MandatoryIEMissing(vec![(InformationElementType::FTEID, 0)])));
+    }
+}
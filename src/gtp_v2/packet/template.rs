@@ -0,0 +1,253 @@
+/* Templates for stamping out many variants of a GTPv2-C packet e.g. when load testing.
+
+The packet is generated once when the template is created and the positions of the fields that vary between
+variants (header TEID, Sequence Number, IMSI and the TEIDs of the F-TEIDs) are recorded. Each variant then only
+patches those octets in the buffer instead of generating the whole packet again.
+
+Only top level IEs are patched (e.g. not the F-TEIDs within a Bearer Context) and the length of the packet never
+changes so the IMSI must encode to the same number of octets as the IMSI the template was made from. */
+
+#[cfg(feature = "std")]
+use std::net::ToSocketAddrs;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::Field;
+use crate::tbcd;
+use crate::uint;
+
+use super::Packet;
+use super::header::TEID;
+use super::messages::information_elements::{InformationElementType, imsi};
+use super::view::{IeView, MessageView};
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PacketTemplate {
+    buffer: Vec<u8>,
+    teid: Option<Field>, // Only present if the T flag is set
+    sequence_number: Field,
+    imsi: Option<Field>, // The digits of the IMSI IE with instance 0
+    f_teids: Vec<(u8, Field)>, // The instance and TEID of each top level F-TEID
+}
+
+impl PacketTemplate {
+    pub fn new(mut packet: Packet) -> Result<Self, String> {
+        let buffer = packet.to_vec()?;
+
+        let view = MessageView::new_checked(&buffer)?;
+
+        let header_length = view.header_length();
+
+        let teid = if view.teid_present() { Some(TEID) } else { None };
+
+        // The Sequence Number is the 3 octets before the last (spare / Message Priority) octet of the header
+        let sequence_number = header_length-4..header_length-1;
+
+        let mut imsi = None;
+        let mut f_teids = Vec::new();
+
+        let mut pos = header_length;
+
+        while pos < view.total_length() {
+            let ie = IeView::new_checked(&buffer[pos..view.total_length()])?;
+
+            if ie.ie_type_raw() == InformationElementType::IMSI as u8 && ie.instance() == 0 {
+                imsi = Some(pos+4..pos+ie.total_length());
+            }
+            else if ie.ie_type_raw() == InformationElementType::FTEID as u8 && ie.teid().is_some() {
+                // The TEID follows the flags octet
+                f_teids.push((ie.instance(), pos+5..pos+9));
+            }
+
+            pos = pos + ie.total_length();
+        }
+
+        Ok(PacketTemplate {
+            buffer,
+            teid,
+            sequence_number,
+            imsi,
+            f_teids,
+        })
+    }
+
+    pub fn set_teid(&mut self, teid: u32) -> Result<u32, String> {
+        match self.teid.clone() {
+            Some(field) => {
+                NetworkEndian::write_u32(&mut self.buffer[field], teid);
+                Ok(teid)
+            },
+            None => Err("Template has no TEID in its header".to_string())
+        }
+    }
+
+    pub fn set_sequence_number(&mut self, sequence_number: u32) -> Result<u32, String> {
+        // Sequence number can only be 3 octets
+        uint::generate_u24(sequence_number, &mut self.buffer[self.sequence_number.clone()])
+            .ok_or_else(|| format!("Sequence number ({}) too large.", sequence_number))?;
+
+        Ok(sequence_number)
+    }
+
+    pub fn set_imsi(&mut self, imsi: &str) -> Result<(), String> {
+        let field = match self.imsi.clone() {
+            Some(field) => field,
+            None => return Err("Template has no IMSI".to_string())
+        };
+
+        // Checks the number of digits and that they are all decimal
+        let imsi = imsi::InformationElement::new(imsi, 0)?;
+
+        if tbcd::length(&imsi.imsi) != field.len() {
+            return Err(format!("IMSI must be {} octets to fit the template ({})", field.len(), tbcd::length(&imsi.imsi)));
+        }
+
+        tbcd::generate(&imsi.imsi, &mut self.buffer[field]);
+
+        Ok(())
+    }
+
+    pub fn set_f_teid(&mut self, instance: u8, teid: u32) -> Result<u32, String> {
+        // Sets the TEID of the top level F-TEID with instance e.g. 0 for the Sender F-TEID for Control Plane
+        match self.f_teids.iter().find(|(i, _)| *i == instance) {
+            Some((_, field)) => {
+                NetworkEndian::write_u32(&mut self.buffer[field.clone()], teid);
+                Ok(teid)
+            },
+            None => Err(format!("Template has no F-TEID with instance {}", instance))
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> Result<usize, String> {
+        if buffer.len() < self.buffer.len() {
+            return Err(format!("Buffer is too short for the packet ({} < {})", buffer.len(), self.buffer.len()));
+        }
+
+        buffer[..self.buffer.len()].copy_from_slice(&self.buffer);
+
+        Ok(self.buffer.len())
+    }
+
+    pub fn to_packet(&self) -> Option<Packet> {
+        match Packet::parse(&self.buffer) {
+            Ok((p, _pos)) => Some(p),
+            Err(_) => None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn send_to<A: ToSocketAddrs>(&self, socket: &std::net::UdpSocket, addr: A) -> std::io::Result<usize> {
+        socket.send_to(&self.buffer, addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    use ascii::AsciiString;
+
+    use crate::gtp_v2::packet::messages::{Message, create_session_request, echo_request};
+    use crate::gtp_v2::packet::messages::information_elements::{
+        apn,
+        bearer_context,
+        bearer_qos,
+        ebi,
+        f_teid,
+        rat_type,
+        recovery,
+    };
+
+    fn create_session_request() -> Packet {
+        let bearer_context_to_be_created = bearer_context::InformationElement::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 10_000_000, 10_000_000, 0, 0, 0).unwrap(),
+            0
+        ).unwrap();
+
+        let mut m = create_session_request::Message::new(
+            rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0
+            ).unwrap(),
+            bearer_context_to_be_created,
+            apn::InformationElement::new(AsciiString::from_ascii("awesome.apn").unwrap(), 0).unwrap()
+        );
+
+        m.imsi = Some(imsi::InformationElement::new("505013485090404", 0).unwrap());
+
+        Packet::new(Message::CreateSessionRequest(m))
+    }
+
+    #[test]
+    fn test_template() {
+        let mut t = PacketTemplate::new(create_session_request()).unwrap();
+
+        t.set_teid(0xAABBCCDD).unwrap();
+        t.set_sequence_number(0x123456).unwrap();
+        t.set_imsi("505019876543210").unwrap();
+        t.set_f_teid(0, 0x87654321).unwrap();
+
+        // The patched template is the same as generating the packet with the new values
+        let mut p = create_session_request();
+
+        p.header.set_teid(0xAABBCCDD);
+        p.header.set_sequence_number(0x123456).unwrap();
+
+        if let Message::CreateSessionRequest(ref mut m) = p.message {
+            m.imsi = Some(imsi::InformationElement::new("505019876543210", 0).unwrap());
+            m.sender_f_teid_for_control_plane.teid = 0x87654321;
+        }
+
+        assert_eq!(t.as_bytes(), &p.to_vec().unwrap()[..]);
+        assert_eq!(t.to_packet(), Some(p));
+    }
+
+    #[test]
+    fn test_template_invalid() {
+        let mut t = PacketTemplate::new(create_session_request()).unwrap();
+
+        assert!(t.set_sequence_number(0x1000000).is_err());
+        assert!(t.set_imsi("50501987654321").is_err()); // 7 octets rather than 8
+        assert!(t.set_imsi("50501987654321A").is_err());
+        assert!(t.set_f_teid(1, 0x87654321).is_err());
+
+        let mut t = PacketTemplate::new(
+            Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap())))
+        ).unwrap();
+
+        assert!(t.set_teid(0xAABBCCDD).is_err());
+        assert!(t.set_imsi("505019876543210").is_err());
+
+        assert!(t.set_sequence_number(0x123456).is_ok());
+        assert_eq!(t.to_packet().unwrap().header.sequence_number(), 0x123456);
+    }
+
+    #[test]
+    fn test_template_generate() {
+        let t = PacketTemplate::new(create_session_request()).unwrap();
+
+        let mut buffer = [0; crate::MTU];
+
+        let pos = t.generate(&mut buffer).unwrap();
+
+        assert_eq!(pos, t.as_bytes().len());
+        assert_eq!(&buffer[..pos], t.as_bytes());
+
+        assert!(t.generate(&mut buffer[..pos-1]).is_err());
+    }
+}
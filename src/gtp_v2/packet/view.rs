@@ -0,0 +1,477 @@
+/* Zero-copy views over GTPv2-C packets.
+
+A view borrows the input buffer and only decodes a field when it is accessed. This is useful when only a few
+fields are needed from each packet (e.g. when processing a high packet rate capture). Use to_owned() on a view
+to fall back to the owned API. */
+
+use core::convert::TryFrom;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use super::Packet;
+use super::header::LENGTH;
+use super::messages::MessageType;
+use super::messages::information_elements::{InformationElement, InformationElementType, LENGTH as IE_LENGTH};
+use super::messages::information_elements::cause::CauseCode;
+use super::messages::information_elements::f_teid::InterfaceType;
+
+use crate::tbcd;
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+
+pub struct MessageView<'a> {
+    buffer: &'a [u8]
+}
+
+impl<'a> MessageView<'a> {
+    pub fn new_checked(buffer: &'a [u8]) -> Result<Self, String> {
+        // Checks that the header and the length of the message are consistent with the buffer
+
+        if buffer.len() < 4 {
+            return Err(format!("Buffer is too short for a GTPv2 header ({})", buffer.len()));
+        }
+
+        let view = MessageView { buffer };
+
+        if view.version() != 2 {
+            return Err(format!("Not a GTPv2 packet (version {})", view.version()));
+        }
+
+        if view.total_length() < view.header_length() {
+            return Err(format!("Message length is too short for the header ({})", view.length()));
+        }
+
+        if buffer.len() < view.total_length() {
+            return Err(format!("Buffer is shorter than the message length ({} < {})", buffer.len(), view.total_length()));
+        }
+
+        Ok(view)
+    }
+
+    pub fn into_inner(self) -> &'a [u8] {
+        self.buffer
+    }
+
+    pub fn version(&self) -> u8 {
+        (self.buffer[0] >> 5) & 0b111
+    }
+
+    pub fn piggyback(&self) -> bool {
+        (self.buffer[0] >> 4) & 0b1 == 1
+    }
+
+    pub fn teid_present(&self) -> bool {
+        (self.buffer[0] >> 3) & 0b1 == 1
+    }
+
+    pub fn message_priority_present(&self) -> bool {
+        (self.buffer[0] >> 2) & 0b1 == 1
+    }
+
+    pub fn message_type_raw(&self) -> u8 {
+        self.buffer[1]
+    }
+
+    pub fn message_type(&self) -> MessageType {
+        MessageType::from(self.message_type_raw())
+    }
+
+    pub fn length(&self) -> u16 {
+        // This is the value of the length field i.e. excluding the first 4 octets
+        NetworkEndian::read_u16(&self.buffer[LENGTH])
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.length() as usize + 4
+    }
+
+    pub fn header_length(&self) -> usize {
+        if self.teid_present() { 12 } else { 8 }
+    }
+
+    pub fn teid(&self) -> Option<u32> {
+        if self.teid_present() {
+            Some(NetworkEndian::read_u32(&self.buffer[4..8]))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn sequence_number(&self) -> u32 {
+        let pos = self.header_length() - 4;
+        // new_checked() made sure the whole header is in buffer
+        uint::parse_u24(&self.buffer[pos..]).unwrap()
+    }
+
+    pub fn message_priority(&self) -> Option<u8> {
+        if self.message_priority_present() {
+            Some((self.buffer[self.header_length() - 1] >> 4) & 0xF)
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn payload(&self) -> &'a [u8] {
+        &self.buffer[self.header_length()..self.total_length()]
+    }
+
+    pub fn information_elements(&self) -> IeIter<'a> {
+        IeIter::new(self.payload())
+    }
+
+    pub fn find_ie(&self, ie_type: InformationElementType, instance: u8) -> Option<IeView<'a>> {
+        self.information_elements().find(|ie| ie.ie_type_raw() == ie_type as u8 && ie.instance() == instance)
+    }
+
+    pub fn to_owned(&self) -> Option<Packet> {
+        match Packet::parse(&self.buffer[..self.total_length()]) {
+            Ok((p, _pos)) => Some(p),
+            Err(_) => None,
+        }
+    }
+}
+
+pub struct IeView<'a> {
+    buffer: &'a [u8]
+}
+
+impl<'a> IeView<'a> {
+    pub fn new_checked(buffer: &'a [u8]) -> Result<Self, String> {
+        if buffer.len() < 4 {
+            return Err(format!("Buffer is too short for an IE header ({})", buffer.len()));
+        }
+
+        let view = IeView { buffer };
+
+        if buffer.len() < view.total_length() {
+            return Err(format!("Buffer is shorter than the IE length ({} < {})", buffer.len(), view.total_length()));
+        }
+
+        Ok(view)
+    }
+
+    pub fn ie_type_raw(&self) -> u8 {
+        self.buffer[0]
+    }
+
+    pub fn ie_type(&self) -> Result<InformationElementType, String> {
+        InformationElementType::try_from(self.ie_type_raw())
+    }
+
+    pub fn length(&self) -> u16 {
+        // This is the value of the length field i.e. excluding the first 4 octets
+        NetworkEndian::read_u16(&self.buffer[IE_LENGTH])
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.length() as usize + 4
+    }
+
+    pub fn instance(&self) -> u8 {
+        self.buffer[3] & 0xF
+    }
+
+    pub fn value(&self) -> &'a [u8] {
+        &self.buffer[4..self.total_length()]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.buffer[..self.total_length()]
+    }
+
+    pub fn information_elements(&self) -> IeIter<'a> {
+        // Iterate over the IEs within a grouped IE (e.g. Bearer Context)
+        IeIter::new(self.value())
+    }
+
+    fn typed_value(&self, ie_type: InformationElementType, min_length: usize) -> Option<&'a [u8]> {
+        // Returns the value if this IE is of the expected type and is long enough to decode
+        if self.ie_type_raw() == ie_type as u8 && self.value().len() >= min_length {
+            Some(self.value())
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn cause(&self) -> Option<CauseCode> {
+        let value = self.typed_value(InformationElementType::Cause, 2)?;
+
+        Some(CauseCode::from(value[0]))
+    }
+
+    pub fn interface_type(&self) -> Option<InterfaceType> {
+        let value = self.typed_value(InformationElementType::FTEID, 5)?;
+
+        Some(InterfaceType::from(value[0] & 0b0011_1111))
+    }
+
+    pub fn teid(&self) -> Option<u32> {
+        let value = self.typed_value(InformationElementType::FTEID, 5)?;
+
+        Some(NetworkEndian::read_u32(&value[1..5]))
+    }
+
+    pub fn ipv4_address(&self) -> Option<Ipv4Addr> {
+        let value = self.typed_value(InformationElementType::FTEID, 5)?;
+
+        if (value[0] >> 7) & 0b1 == 1 && value.len() >= 9 {
+            Some(Ipv4Addr::new(value[5], value[6], value[7], value[8]))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn ipv6_address(&self) -> Option<Ipv6Addr> {
+        let value = self.typed_value(InformationElementType::FTEID, 5)?;
+
+        // The IPv6 address follows the IPv4 address if both are present
+        let pos = if (value[0] >> 7) & 0b1 == 1 { 9 } else { 5 };
+
+        if (value[0] >> 6) & 0b1 == 1 && value.len() >= pos + 16 {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&value[pos..pos+16]);
+            Some(Ipv6Addr::from(octets))
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn eps_bearer_id(&self) -> Option<u8> {
+        let value = self.typed_value(InformationElementType::EBI, 1)?;
+
+        Some(value[0] & 0xF)
+    }
+
+    pub fn restart_counter(&self) -> Option<u8> {
+        let value = self.typed_value(InformationElementType::Recovery, 1)?;
+
+        Some(value[0])
+    }
+
+    pub fn imsi(&self) -> Option<String> {
+        let value = self.typed_value(InformationElementType::IMSI, 1)?;
+
+        let imsi = tbcd::decode(value)?;
+
+        if imsi.iter().any(|d| *d > 9) {
+            return None
+        }
+
+        Some(tbcd::to_string(&imsi))
+    }
+
+    pub fn to_owned(&self) -> Option<InformationElement> {
+        match InformationElement::parse(self.as_bytes()) {
+            Some((ie, _pos)) => Some(ie),
+            None => None,
+        }
+    }
+}
+
+pub struct IeIter<'a> {
+    buffer: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> IeIter<'a> {
+    pub fn new(buffer: &'a [u8]) -> Self {
+        IeIter {
+            buffer,
+            pos: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for IeIter<'a> {
+    type Item = IeView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.buffer.len() {
+            return None;
+        }
+
+        match IeView::new_checked(&self.buffer[self.pos..]) {
+            Ok(ie) => {
+                self.pos = self.pos + ie.total_length();
+                Some(ie)
+            },
+            Err(_) => {
+                // The rest of the buffer is malformed. Stop iterating
+                self.pos = self.buffer.len();
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementTraits;
+
+    const ECHO_REQUEST: [u8; 13] = [
+        /* Flags */ 0b0100_0000,
+        /* Message Type (Echo Request) */ 1,
+        /* Length */ 0x00, 0x09,
+        /* Sequence Number */ 0x00, 0x12, 0x34,
+        /* Spare */ 0x00,
+        InformationElementType::Recovery as u8,
+        0, 1, // Length
+        0, // Spare
+        0xAB // Restart Counter
+    ];
+
+    #[test]
+    fn test_message_view() {
+        let v = MessageView::new_checked(&ECHO_REQUEST).unwrap();
+
+        assert_eq!(v.version(), 2);
+        assert_eq!(v.message_type(), MessageType::EchoRequest);
+        assert_eq!(v.length(), 9);
+        assert_eq!(v.teid(), None);
+        assert_eq!(v.sequence_number(), 0x1234);
+        assert_eq!(v.message_priority(), None);
+        assert_eq!(v.payload().len(), 5);
+
+        let mut buffer = [0u8; 17];
+        buffer[0] = 0b0100_1100;
+        buffer[1] = u8::from(MessageType::CreateSessionRequest);
+        buffer[3] = 13;
+        buffer[4..8].copy_from_slice(&[0x12, 0x34, 0x56, 0x78]);
+        buffer[8..11].copy_from_slice(&[0xAB, 0xCD, 0xEF]);
+        buffer[11] = 0x50;
+
+        let v = MessageView::new_checked(&buffer).unwrap();
+
+        assert_eq!(v.teid(), Some(0x12345678));
+        assert_eq!(v.sequence_number(), 0xABCDEF);
+        assert_eq!(v.message_priority(), Some(5));
+        assert_eq!(v.payload().len(), 5);
+    }
+
+    #[test]
+    fn test_message_view_checked() {
+        assert!(MessageView::new_checked(&ECHO_REQUEST[..3]).is_err());
+        assert!(MessageView::new_checked(&ECHO_REQUEST[..12]).is_err());
+
+        let mut buffer = ECHO_REQUEST;
+        buffer[0] = 0b0010_0000;
+        assert!(MessageView::new_checked(&buffer).is_err());
+    }
+
+    #[test]
+    fn test_ie_view() {
+        let v = MessageView::new_checked(&ECHO_REQUEST).unwrap();
+
+        let ies: Vec<IeView> = v.information_elements().collect();
+
+        assert_eq!(ies.len(), 1);
+        assert_eq!(ies[0].ie_type(), Ok(InformationElementType::Recovery));
+        assert_eq!(ies[0].instance(), 0);
+        assert_eq!(ies[0].value(), &[0xAB]);
+
+        assert!(v.find_ie(InformationElementType::Recovery, 0).is_some());
+        assert!(v.find_ie(InformationElementType::Recovery, 1).is_none());
+
+        if let Some(InformationElement::Recovery(ie)) = ies[0].to_owned() {
+            assert_eq!(ie.restart_counter, 0xAB);
+            assert_eq!(ie.instance(), 0);
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_ie_view_typed_accessors() {
+        let buffer = [
+            InformationElementType::Cause as u8, 0, 2, 0, 16, 0,
+            InformationElementType::FTEID as u8, 0, 25, 1,
+            0b1100_0000 | 10, // V4, V6 and Interface Type
+            0x12, 0x34, 0x56, 0x78, // TEID
+            192, 168, 0, 1, // IPv4 Address
+            0xFA, 0xDE, 0xDE, 0xAD, 0xBE, 0xEF, 0xCA, 0xFE, 0xFE, 0xED, 0xDE, 0xAF, 0xBE, 0xAD, 0xFA, 0xCE, // IPv6 Address
+            InformationElementType::EBI as u8, 0, 1, 0, 5,
+            InformationElementType::Recovery as u8, 0, 1, 0, 0xAB,
+            InformationElementType::IMSI as u8, 0, 8, 0, 0x05, 0x05, 0x31, 0x84, 0x05, 0x09, 0x04, 0xF4,
+        ];
+
+        let ies: Vec<IeView> = IeIter::new(&buffer).collect();
+
+        assert_eq!(ies.len(), 5);
+
+        assert_eq!(ies[0].cause(), Some(CauseCode::RequestAccepted));
+
+        assert_eq!(ies[1].interface_type(), Some(InterfaceType::S11MmeGtpC));
+        assert_eq!(ies[1].teid(), Some(0x12345678));
+        assert_eq!(ies[1].ipv4_address(), Some(Ipv4Addr::new(192, 168, 0, 1)));
+        assert_eq!(ies[1].ipv6_address(), Some(Ipv6Addr::new(0xFADE, 0xDEAD, 0xBEEF, 0xCAFE, 0xFEED, 0xDEAF, 0xBEAD, 0xFACE)));
+
+        assert_eq!(ies[2].eps_bearer_id(), Some(5));
+        assert_eq!(ies[3].restart_counter(), Some(0xAB));
+        assert_eq!(ies[4].imsi(), Some("505013485090404".to_string()));
+
+        // Accessors for a different IE type return None
+        assert_eq!(ies[0].teid(), None);
+        assert_eq!(ies[1].cause(), None);
+        assert_eq!(ies[2].restart_counter(), None);
+        assert_eq!(ies[3].eps_bearer_id(), None);
+        assert_eq!(ies[3].imsi(), None);
+    }
+
+    #[test]
+    fn test_ie_iter_malformed() {
+        let buffer = [
+            InformationElementType::Recovery as u8, 0, 1, 0, 0xAB,
+            InformationElementType::Recovery as u8, 0, 10, 0, 0xAB, // Length runs past the end of the buffer
+        ];
+
+        let ies: Vec<IeView> = IeIter::new(&buffer).collect();
+
+        assert_eq!(ies.len(), 1);
+    }
+
+    #[test]
+    fn test_grouped_ie_view() {
+        let buffer = [
+            InformationElementType::BearerContext as u8,
+            0, 10, // Length
+            0, // Spare
+            InformationElementType::EBI as u8,
+            0, 1, // Length
+            0, // Spare
+            5, // EPS Bearer ID
+            InformationElementType::Recovery as u8,
+            0, 1, // Length
+            0, // Spare
+            0xAB,
+        ];
+
+        let ie = IeView::new_checked(&buffer).unwrap();
+
+        let types: Vec<u8> = ie.information_elements().map(|ie| ie.ie_type_raw()).collect();
+
+        assert_eq!(types, vec![InformationElementType::EBI as u8, InformationElementType::Recovery as u8]);
+    }
+
+    #[test]
+    fn test_to_owned() {
+        let v = MessageView::new_checked(&ECHO_REQUEST).unwrap();
+
+        if let Some(p) = v.to_owned() {
+            assert_eq!(p.header.sequence_number(), 0x1234);
+        }
+        else {
+            assert!(false);
+        }
+    }
+}
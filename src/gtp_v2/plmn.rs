@@ -0,0 +1,188 @@
+/* Utilities for working with PLMNs (TS 23.003 2.2 and 12.1).
+
+A PLMN is identified by its MCC and MNC, written as MCC-MNC (e.g. 505-01) or, as in the IMSI, without the separator
+(e.g. 50501). Whether a UE is at home or roaming depends on the PLMNs that belong to its home operator, which are often
+more than one (e.g. after a merger) so they are given as a list.
+
+validate() checks that the PLMNs of the Serving Network, the ULI and the IMSI of a message agree. It is used by
+conformance::check_plausibility. */
+
+use super::conformance::Warning;
+use super::packet::messages::information_elements::{
+    InformationElementType,
+    imsi,
+    user_location_information,
+};
+
+pub use super::packet::messages::information_elements::user_location_information::PLMN;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub trait OperatorDatabase {
+    // The name of the operator of plmn, if it is known
+    fn operator_name(&self, plmn: &PLMN) -> Option<&str>;
+}
+
+impl OperatorDatabase for [(PLMN, &str)] {
+    fn operator_name(&self, plmn: &PLMN) -> Option<&str> {
+        self.iter().find(|(p, _name)| p == plmn).map(|(_p, name)| *name)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Roaming {
+    Home,
+    Visited,
+}
+
+pub fn compact(plmn: &PLMN) -> String {
+    // MCC and MNC without a separator, as at the start of an IMSI
+    format!("{}{}", plmn.mcc_string(), plmn.mnc_string())
+}
+
+pub fn describe<D: OperatorDatabase + ?Sized>(plmn: &PLMN, database: &D) -> String {
+    // MCC-MNC followed by the name of the operator if it is known e.g. 505-01 (Telstra)
+    match database.operator_name(plmn) {
+        Some(name) => format!("{} ({})", plmn, name),
+        None => format!("{}", plmn),
+    }
+}
+
+pub fn is_home(plmn: &PLMN, home_plmns: &[PLMN]) -> bool {
+    home_plmns.contains(plmn)
+}
+
+pub fn roaming(serving_network: &PLMN, home_plmns: &[PLMN]) -> Roaming {
+    // Whether a UE served by serving_network is at home or in a visited network
+    if is_home(serving_network, home_plmns) {
+        Roaming::Home
+    }
+    else {
+        Roaming::Visited
+    }
+}
+
+pub fn home_plmn<'a>(imsi: &imsi::InformationElement, plmns: &'a [PLMN]) -> Option<&'a PLMN> {
+    /* The PLMN in plmns that the IMSI is from. The length of the MNC can't be told from the IMSI alone, so it is
+    only known by comparing with PLMNs whose MNC length is known. */
+    plmns.iter().find(|plmn| imsi.starts_with_plmn(plmn))
+}
+
+pub fn validate(
+    serving_network: Option<&PLMN>,
+    uli: Option<&user_location_information::InformationElement>,
+    imsi: Option<&imsi::InformationElement>
+) -> Vec<Warning> {
+    // Each PLMN is only reported once
+    let mut plmns: Vec<(InformationElementType, PLMN)> = Vec::new();
+
+    if let Some(plmn) = serving_network {
+        plmns.push((InformationElementType::ServingNetwork, *plmn));
+    }
+
+    if let Some(ie) = uli {
+        for plmn in ie.plmns() {
+            if !plmns.iter().any(|(_, p)| *p == plmn) {
+                plmns.push((InformationElementType::UserLocationInformation, plmn));
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+
+    if let Some(imsi) = imsi {
+        for (ie_type, plmn) in plmns.iter() {
+            if !imsi.starts_with_plmn(plmn) {
+                warnings.push(Warning::ImsiNotFromPlmn(*ie_type, *plmn));
+            }
+        }
+    }
+
+    if let Some(serving_network) = serving_network {
+        for (ie_type, plmn) in plmns.iter() {
+            if *ie_type == InformationElementType::UserLocationInformation {
+                warnings.push(Warning::UliPlmnMismatch(*serving_network, *plmn));
+            }
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::{TAI, UliBuilder};
+
+    use alloc::vec;
+
+    fn plmn(s: &str) -> PLMN {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(compact(&plmn("505-01")), "50501");
+        assert_eq!(compact(&plmn("310-410")), "310410");
+        assert_eq!(compact(&plmn("001-001")), "001001");
+    }
+
+    #[test]
+    fn test_describe() {
+        let database = [(plmn("505-01"), "Telstra"), (plmn("310-410"), "AT&T")];
+
+        assert_eq!(describe(&plmn("505-01"), &database[..]), "505-01 (Telstra)");
+        assert_eq!(describe(&plmn("310-410"), &database[..]), "310-410 (AT&T)");
+
+        // The MNC length is part of the PLMN
+        assert_eq!(describe(&plmn("505-001"), &database[..]), "505-001");
+    }
+
+    #[test]
+    fn test_roaming() {
+        let home_plmns = vec![plmn("505-01"), plmn("505-11")];
+
+        assert!(is_home(&plmn("505-11"), &home_plmns));
+        assert_eq!(roaming(&plmn("505-01"), &home_plmns), Roaming::Home);
+        assert_eq!(roaming(&plmn("505-02"), &home_plmns), Roaming::Visited);
+        assert_eq!(roaming(&plmn("505-01"), &[]), Roaming::Visited);
+    }
+
+    #[test]
+    fn test_home_plmn() {
+        let plmns = vec![plmn("310-41"), plmn("310-410")];
+
+        // 310410... is from the 3 digit MNC 410 but also starts with 31041
+        let imsi = imsi::InformationElement::new("310410000000001", 0).unwrap();
+        assert_eq!(home_plmn(&imsi, &plmns[1..]), Some(&plmn("310-410")));
+        assert_eq!(home_plmn(&imsi, &plmns), Some(&plmn("310-41")));
+
+        let imsi = imsi::InformationElement::new("505010000000001", 0).unwrap();
+        assert_eq!(home_plmn(&imsi, &plmns), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        let imsi = imsi::InformationElement::new("505010000000001", 0).unwrap();
+        let uli = UliBuilder::new().tai(TAI::new(plmn("505-02"), 0x1234)).build().unwrap();
+
+        assert_eq!(validate(None, None, None), []);
+        assert_eq!(validate(Some(&plmn("505-01")), None, Some(&imsi)), []);
+
+        assert_eq!(validate(Some(&plmn("505-01")), Some(&uli), Some(&imsi)), [
+            Warning::ImsiNotFromPlmn(InformationElementType::UserLocationInformation, plmn("505-02")),
+            Warning::UliPlmnMismatch(plmn("505-01"), plmn("505-02")),
+        ]);
+
+        // The ULI is only compared with the Serving Network if there is one
+        assert_eq!(validate(None, Some(&uli), None), []);
+
+        // The same PLMN is only reported once
+        assert_eq!(validate(Some(&plmn("505-02")), Some(&uli), Some(&imsi)), [
+            Warning::ImsiNotFromPlmn(InformationElementType::ServingNetwork, plmn("505-02")),
+        ]);
+    }
+}
@@ -0,0 +1,120 @@
+// Allocates GTPv2 Sequence Numbers for the Initial messages sent to a peer as per TS 29.274 7.6
+
+use super::packet::messages::MessageType;
+
+use alloc::format;
+use alloc::string::String;
+
+// The Sequence Number is 3 octets. The most significant bit is set for Command messages (and the messages they trigger)
+// and clear for all other requests so that the Sequence Numbers chosen by each peer can't clash.
+pub const MAX_SEQUENCE_NUMBER: u32 = 0xFFFFFF;
+pub const COMMAND_FLAG: u32 = 0x800000;
+
+pub struct SequenceGenerator {
+    next_request: u32,
+    next_command: u32,
+}
+
+impl SequenceGenerator {
+    pub fn new() -> Self {
+        SequenceGenerator {
+            next_request: 0,
+            next_command: COMMAND_FLAG,
+        }
+    }
+
+    pub fn starting_at(sequence_number: u32) -> Result<Self, String> {
+        // Lets the first Sequence Number be chosen e.g. randomly so that it differs after a restart
+        if sequence_number >= COMMAND_FLAG {
+            return Err(format!("Sequence number ({}) too large.", sequence_number));
+        }
+
+        Ok(SequenceGenerator {
+            next_request: sequence_number,
+            next_command: sequence_number | COMMAND_FLAG,
+        })
+    }
+
+    pub fn next_request(&mut self) -> u32 {
+        let sequence_number = self.next_request;
+
+        // Wraps within the Sequence Numbers that have the most significant bit clear
+        self.next_request = (self.next_request + 1) & !COMMAND_FLAG & MAX_SEQUENCE_NUMBER;
+
+        sequence_number
+    }
+
+    pub fn next_command(&mut self) -> u32 {
+        let sequence_number = self.next_command;
+
+        // Wraps within the Sequence Numbers that have the most significant bit set
+        self.next_command = ((self.next_command + 1) & MAX_SEQUENCE_NUMBER) | COMMAND_FLAG;
+
+        sequence_number
+    }
+
+    pub fn next(&mut self, message_type: MessageType) -> u32 {
+        if message_type.is_command() {
+            self.next_command()
+        }
+        else {
+            self.next_request()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_request() {
+        let mut s = SequenceGenerator::new();
+
+        assert_eq!(s.next_request(), 0);
+        assert_eq!(s.next_request(), 1);
+        assert_eq!(s.next_request(), 2);
+    }
+
+    #[test]
+    fn test_next_command() {
+        let mut s = SequenceGenerator::new();
+
+        assert_eq!(s.next_command(), 0x800000);
+        assert_eq!(s.next_command(), 0x800001);
+
+        // Requests and commands are allocated independently
+        assert_eq!(s.next_request(), 0);
+    }
+
+    #[test]
+    fn test_next() {
+        let mut s = SequenceGenerator::new();
+
+        assert_eq!(s.next(MessageType::CreateSessionRequest), 0);
+        assert_eq!(s.next(MessageType::ModifyBearerCommand), 0x800000);
+        assert_eq!(s.next(MessageType::BearerResourceCommand), 0x800001);
+        assert_eq!(s.next(MessageType::EchoRequest), 1);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let mut s = SequenceGenerator::starting_at(0x7FFFFF).unwrap();
+
+        assert_eq!(s.next_request(), 0x7FFFFF);
+        assert_eq!(s.next_request(), 0);
+
+        assert_eq!(s.next_command(), 0xFFFFFF);
+        assert_eq!(s.next_command(), 0x800000);
+    }
+
+    #[test]
+    fn test_starting_at() {
+        let mut s = SequenceGenerator::starting_at(0x1234).unwrap();
+
+        assert_eq!(s.next_request(), 0x1234);
+        assert_eq!(s.next_command(), 0x801234);
+
+        assert!(SequenceGenerator::starting_at(0x800000).is_err());
+    }
+}
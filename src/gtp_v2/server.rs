@@ -0,0 +1,620 @@
+// A GTPv2-C node runtime. Receives packets on a UDP socket, answers Echo Requests (TS 29.274 7.1) and dispatches
+// every other message to the handler registered for its Message Type. A handler can return a packet to send back
+// to the peer e.g. the response to a request. Every message sent and received is reported to the MetricsSink if one
+// is set. The Recovery IE is added to the messages sent to a peer until the peer is known to have received it.
+
+use std::collections::HashMap;
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use std::time::{Duration, Instant};
+
+use super::node_selection::GTP_C_PORT;
+use super::packet::Packet;
+use super::packet::header::ParseMode;
+use super::packet::messages::{Message, MessageType, echo_request, echo_response};
+use super::packet::messages::information_elements::recovery;
+use super::sequence::SequenceGenerator;
+
+use crate::MTU;
+use crate::socket;
+use crate::metrics::MetricsSink;
+
+// How often run() checks whether it has been asked to shut down
+pub const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub type Handler = Box<dyn FnMut(&Packet, SocketAddr) -> Option<Packet> + Send>;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerStatistics {
+    pub rx: usize,
+    pub tx: usize,
+    pub rx_echo_request: usize,
+    pub tx_echo_response: usize,
+    pub rx_unhandled: usize, // Messages with no handler registered for their Message Type
+    pub rx_parse_error: usize,
+}
+
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ShutdownHandle {
+    pub(crate) fn new(shutdown: Arc<AtomicBool>) -> Self {
+        // For the other runtimes that stop the same way e.g. demux::Demux
+        ShutdownHandle {
+            shutdown,
+        }
+    }
+
+    pub fn shutdown(&self) {
+        // run() returns within SHUTDOWN_POLL_INTERVAL
+        self.shutdown.store(true, Ordering::Relaxed);
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum RecoveryState {
+    // Nothing carrying our Restart Counter has been sent to the peer since the Server started
+    #[default]
+    Required,
+    // Sent in the request with this Sequence Number, or in a response if None, but not known to have arrived
+    Sent(Option<u32>),
+    Acknowledged,
+}
+
+#[derive(Default)]
+struct Peer {
+    statistics: PeerStatistics,
+    // The Message Type and Sequence Number of the last message received. A message that repeats them is a retransmission
+    last_received: Option<(MessageType, u32)>,
+    // The Sequence Number and send time of the last Echo Request if it hasn't been answered yet
+    echo_request: Option<(u32, Instant)>,
+    recovery: RecoveryState,
+}
+
+pub struct Server {
+    socket: UdpSocket,
+    restart_counter: u8, // Sent in the Recovery IE of Echo Requests and Responses
+    sequence: SequenceGenerator,
+    handlers: HashMap<MessageType, Handler>,
+    peers: HashMap<SocketAddr, Peer>,
+    metrics: Option<Box<dyn MetricsSink + Send>>,
+    shutdown: Arc<AtomicBool>,
+    parse_mode: ParseMode,
+}
+
+impl Server {
+    pub fn new(socket: UdpSocket, restart_counter: u8) -> Self {
+        Server {
+            socket,
+            restart_counter,
+            sequence: SequenceGenerator::new(),
+            handlers: HashMap::new(),
+            peers: HashMap::new(),
+            metrics: None,
+            shutdown: Arc::new(AtomicBool::new(false)),
+            parse_mode: ParseMode::Lenient,
+        }
+    }
+
+    pub fn bind<A: ToSocketAddrs>(addr: A, restart_counter: u8) -> std::io::Result<Self> {
+        Ok(Server::new(UdpSocket::bind(addr)?, restart_counter))
+    }
+
+    pub fn bind_default(restart_counter: u8) -> std::io::Result<Self> {
+        // Listens on all addresses on the GTP-C port (2123)
+        Server::bind(("0.0.0.0", GTP_C_PORT), restart_counter)
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        // For sending Initial messages to peers from the same port
+        &self.socket
+    }
+
+    pub fn register<F>(&mut self, message_type: MessageType, handler: F)
+        where F: FnMut(&Packet, SocketAddr) -> Option<Packet> + Send + 'static {
+        // Replaces any handler already registered for message_type
+        self.handlers.insert(message_type, Box::new(handler));
+    }
+
+    pub fn unregister(&mut self, message_type: MessageType) -> bool {
+        self.handlers.remove(&message_type).is_some()
+    }
+
+    pub fn set_metrics_sink<S: MetricsSink + Send + 'static>(&mut self, sink: S) {
+        // e.g. an Arc<Mutex<metrics::Metrics>> that is also read by the thread exporting the metrics
+        self.metrics = Some(Box::new(sink));
+    }
+
+    pub fn set_parse_mode(&mut self, mode: ParseMode) {
+        // Lenient by default. Strict counts packets with spare bits set or a bad length as parse errors
+        self.parse_mode = mode;
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            shutdown: self.shutdown.clone(),
+        }
+    }
+
+    pub fn statistics(&self, peer: SocketAddr) -> Option<PeerStatistics> {
+        self.peers.get(&peer).map(|p| p.statistics)
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = (&SocketAddr, &PeerStatistics)> {
+        self.peers.iter().map(|(addr, p)| (addr, &p.statistics))
+    }
+
+    pub fn recovery_acknowledged(&self, peer: SocketAddr) -> bool {
+        // After this the Recovery IE is no longer added to the messages sent to the peer
+        self.peers.get(&peer).map(|p| p.recovery == RecoveryState::Acknowledged).unwrap_or(false)
+    }
+
+    pub fn handle(&mut self, buffer: &[u8], src: SocketAddr) -> Option<Packet> {
+        // Handles a single received packet. Returns the packet that should be sent back to src, if any
+        let restart_counter = self.restart_counter;
+
+        let peer = self.peers.entry(src).or_default();
+        peer.statistics.rx = peer.statistics.rx + 1;
+
+        let packet = match Packet::parse_with_mode(buffer, self.parse_mode) {
+            Ok((packet, _pos)) => packet,
+            Err(e) => {
+                peer.statistics.rx_parse_error = peer.statistics.rx_parse_error + 1;
+
+                if let Some(metrics) = self.metrics.as_mut() {
+                    metrics.parse_failure(src);
+                }
+
+                // Requests that fail to parse are rejected. Anything else is dropped
+                return Packet::reject_parse_error(buffer, &e);
+            }
+        };
+
+        let message_type = packet.header.message_type();
+        let sequence_number = packet.header.sequence_number();
+
+        let retransmission = peer.last_received == Some((message_type, sequence_number));
+        peer.last_received = Some((message_type, sequence_number));
+
+        // The peer has our Restart Counter once it answers a request that carried it or sends a new message after a
+        // response that carried it
+        match peer.recovery {
+            RecoveryState::Sent(Some(s)) if message_type.is_triggered() && s == sequence_number => {
+                peer.recovery = RecoveryState::Acknowledged;
+            },
+            RecoveryState::Sent(None) if !message_type.is_triggered() && !retransmission => {
+                peer.recovery = RecoveryState::Acknowledged;
+            },
+            _ => {}
+        }
+
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.message_received(src, u8::from(message_type));
+
+            if retransmission {
+                metrics.retransmission(src, u8::from(message_type));
+            }
+        }
+
+        if let Message::EchoResponse(_) = packet.message {
+            if let Some((echo_sequence_number, sent)) = peer.echo_request {
+                if echo_sequence_number == sequence_number {
+                    peer.echo_request = None;
+
+                    if let Some(metrics) = self.metrics.as_mut() {
+                        metrics.echo_rtt(src, sent.elapsed());
+                    }
+                }
+            }
+        }
+
+        if let Message::EchoRequest(_) = packet.message {
+            peer.statistics.rx_echo_request = peer.statistics.rx_echo_request + 1;
+
+            if !self.handlers.contains_key(&MessageType::EchoRequest) {
+                let recovery = recovery::InformationElement::new(restart_counter, 0).ok()?;
+
+                return Some(Packet::new_response(&packet, Message::EchoResponse(echo_response::Message::new(recovery))));
+            }
+        }
+
+        match self.handlers.get_mut(&message_type) {
+            Some(handler) => handler(&packet, src),
+            None => {
+                peer.statistics.rx_unhandled = peer.statistics.rx_unhandled + 1;
+                None
+            }
+        }
+    }
+
+    pub fn send_to(&mut self, packet: &mut Packet, dst: SocketAddr) -> std::io::Result<usize> {
+        let peer = self.peers.entry(dst).or_default();
+
+        // The Recovery IE is included when contacting the peer for the first time (e.g. TS 29.274 Table 7.2.1-1). It is
+        // added to every message that can carry it until the peer is known to have received one.
+        let mut recovery_state = peer.recovery;
+
+        if recovery_state != RecoveryState::Acknowledged {
+            let recovery = recovery::InformationElement::new(self.restart_counter, 0)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+            if packet.message.set_recovery(recovery) {
+                if !packet.header.message_type().is_triggered() {
+                    recovery_state = RecoveryState::Sent(Some(packet.header.sequence_number()));
+                }
+                else if recovery_state == RecoveryState::Required {
+                    recovery_state = RecoveryState::Sent(None);
+                }
+            }
+        }
+
+        let n = packet.send_to(&self.socket, dst)?;
+
+        peer.recovery = recovery_state;
+        peer.statistics.tx = peer.statistics.tx + 1;
+
+        match packet.header.message_type() {
+            MessageType::EchoRequest => peer.echo_request = Some((packet.header.sequence_number(), Instant::now())),
+            MessageType::EchoResponse => peer.statistics.tx_echo_response = peer.statistics.tx_echo_response + 1,
+            _ => {}
+        }
+
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.message_sent(dst, u8::from(packet.header.message_type()));
+        }
+
+        Ok(n)
+    }
+
+    pub fn send_echo_request(&mut self, dst: SocketAddr) -> std::io::Result<usize> {
+        // The round trip time is reported to the MetricsSink when the Echo Response is received
+        let recovery = recovery::InformationElement::new(self.restart_counter, 0)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+        let mut echo_request = Packet::new_request(
+            Message::EchoRequest(echo_request::Message::new(recovery)),
+            &mut self.sequence
+        );
+
+        self.send_to(&mut echo_request, dst)
+    }
+
+    pub fn poll(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        // Waits up to timeout for a single packet and handles it. Returns true if a packet was received
+        let mut buffer = [0; MTU];
+
+        socket::set_poll_timeout(&self.socket, timeout)?;
+
+        match self.socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, src_addr)) => {
+                if let Some(mut response) = self.handle(&buffer[..number_of_bytes], src_addr) {
+                    self.send_to(&mut response, src_addr)?;
+                }
+                Ok(true)
+            },
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn run(&mut self) -> std::io::Result<()> {
+        // Handles packets until shutdown() is called on a ShutdownHandle or the socket fails
+        while !self.shutdown.load(Ordering::Relaxed) {
+            self.poll(SHUTDOWN_POLL_INTERVAL)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use std::sync::Mutex;
+
+    use crate::metrics::Metrics;
+
+    use super::super::packet::messages::information_elements::InformationElementType;
+    use super::super::packet::messages::create_session_response;
+    use super::super::packet::messages::information_elements::cause::CauseCode;
+    use super::super::packet::view::MessageView;
+
+    fn new_server() -> Server {
+        Server::bind("127.0.0.1:0", 7).expect("couldn't bind to address")
+    }
+
+    fn echo_request(sequence_number: u32) -> Packet {
+        let mut p = Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap())));
+        p.header.set_sequence_number(sequence_number).unwrap();
+        p
+    }
+
+    fn peer() -> SocketAddr {
+        "127.0.0.1:2123".parse().unwrap()
+    }
+
+    #[test]
+    fn test_echo() {
+        let mut s = new_server();
+
+        let request = echo_request(0x1234).to_vec().unwrap();
+
+        if let Some(mut p) = s.handle(&request, peer()) {
+            assert_eq!(p.header.message_type(), MessageType::EchoResponse);
+            assert_eq!(p.header.sequence_number(), 0x1234);
+
+            let bytes = p.to_vec().unwrap();
+            let v = MessageView::new_checked(&bytes).unwrap();
+
+            assert_eq!(v.find_ie(InformationElementType::Recovery, 0).unwrap().restart_counter(), Some(7));
+        }
+        else {
+            assert!(false);
+        }
+
+        let stats = s.statistics(peer()).unwrap();
+
+        assert_eq!(stats.rx, 1);
+        assert_eq!(stats.rx_echo_request, 1);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut s = new_server();
+
+        let request = echo_request(0x1234).to_vec().unwrap();
+        let mut response = echo_request(0x1234).to_vec().unwrap();
+        response[1] = u8::from(MessageType::EchoResponse);
+
+        // No handler is registered for Echo Response
+        assert_eq!(s.handle(&response, peer()), None);
+        assert_eq!(s.statistics(peer()).unwrap().rx_unhandled, 1);
+
+        s.register(MessageType::EchoResponse, |p, _src| {
+            assert_eq!(p.header.sequence_number(), 0x1234);
+            None
+        });
+
+        assert_eq!(s.handle(&response, peer()), None);
+        assert_eq!(s.statistics(peer()).unwrap().rx_unhandled, 1);
+
+        // A handler for Echo Request replaces the automatic response
+        s.register(MessageType::EchoRequest, |_p, _src| None);
+        assert_eq!(s.handle(&request, peer()), None);
+
+        assert!(s.unregister(MessageType::EchoRequest));
+        assert!(s.handle(&request, peer()).is_some());
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut s = new_server();
+
+        // A Create Session Request with no IEs
+        let request = [0b0100_1000, u8::from(MessageType::CreateSessionRequest), 0, 8, 0, 0, 0, 0, 0, 0x12, 0x34, 0];
+
+        if let Some(p) = s.handle(&request, peer()) {
+            assert_eq!(p.header.message_type(), MessageType::CreateSessionResponse);
+            assert_eq!(p.header.sequence_number(), 0x1234);
+
+            if let Message::CreateSessionResponse(ref m) = p.message {
+                assert_eq!(m.cause.cause_code, CauseCode::MandatoryIEMissing);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        assert_eq!(s.statistics(peer()).unwrap().rx_parse_error, 1);
+
+        // Garbage isn't answered
+        assert_eq!(s.handle(&[0xFF; 4], peer()), None);
+        assert_eq!(s.statistics(peer()).unwrap().rx_parse_error, 2);
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        let mut s = new_server();
+
+        // An Echo Request with a spare bit set in the flags
+        let mut request = echo_request(0x1234).to_vec().unwrap();
+        request[0] = request[0] | 0b01;
+
+        assert!(s.handle(&request, peer()).is_some());
+
+        s.set_parse_mode(ParseMode::Strict);
+
+        assert_eq!(s.handle(&request, peer()), None);
+        assert_eq!(s.statistics(peer()).unwrap().rx_parse_error, 1);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut s = new_server();
+
+        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        s.set_metrics_sink(metrics.clone());
+
+        let request = echo_request(0x1234).to_vec().unwrap();
+
+        s.handle(&request, peer());
+        s.handle(&request, peer());
+        s.handle(&echo_request(0x1235).to_vec().unwrap(), peer());
+        s.handle(&[0xFF; 4], peer());
+
+        let m = metrics.lock().unwrap();
+        let p = m.peer(peer()).unwrap();
+
+        assert_eq!(p.rx.get(&(u8::from(MessageType::EchoRequest))), Some(&3));
+        assert_eq!(p.retransmissions.get(&(u8::from(MessageType::EchoRequest))), Some(&1));
+        assert_eq!(p.parse_failures, 1);
+    }
+
+    #[test]
+    fn test_echo_rtt() {
+        let mut s = new_server();
+
+        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        s.set_metrics_sink(metrics.clone());
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        let peer_addr = peer.local_addr().unwrap();
+
+        s.send_echo_request(peer_addr).unwrap();
+
+        let mut buffer = [0; MTU];
+        let (n, _src) = peer.recv_from(&mut buffer).unwrap();
+
+        let (request, _pos) = Packet::parse(&buffer[..n]).unwrap();
+
+        let mut response = Packet::new_response(
+            &request,
+            Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(1, 0).unwrap()))
+        );
+
+        // Nothing is sent back in response to an Echo Response
+        assert_eq!(s.handle(&response.to_vec().unwrap(), peer_addr), None);
+
+        let m = metrics.lock().unwrap();
+        let p = m.peer(peer_addr).unwrap();
+
+        assert_eq!(p.tx.get(&(u8::from(MessageType::EchoRequest))), Some(&1));
+        assert!(p.echo_rtt.is_some());
+    }
+
+    fn create_session_response() -> Packet {
+        Packet::new(Message::CreateSessionResponse(create_session_response::Message::reject_with(CauseCode::NoResourcesAvailable).unwrap()))
+    }
+
+    fn receive(peer: &UdpSocket) -> Packet {
+        let mut buffer = [0; MTU];
+        let (n, _src) = peer.recv_from(&mut buffer).unwrap();
+
+        Packet::parse(&buffer[..n]).unwrap().0
+    }
+
+    #[test]
+    fn test_recovery_request() {
+        let mut s = new_server();
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        let peer_addr = peer.local_addr().unwrap();
+
+        s.send_echo_request(peer_addr).unwrap();
+        let request = receive(&peer);
+
+        // Until the Echo Request is answered the Recovery IE is added to everything sent to the peer
+        s.send_to(&mut create_session_response(), peer_addr).unwrap();
+        assert_eq!(receive(&peer).message.recovery().map(|ie| ie.restart_counter), Some(7));
+        assert!(!s.recovery_acknowledged(peer_addr));
+
+        let mut response = Packet::new_response(
+            &request,
+            Message::EchoResponse(echo_response::Message::new(recovery::InformationElement::new(1, 0).unwrap()))
+        );
+
+        s.handle(&response.to_vec().unwrap(), peer_addr);
+        assert!(s.recovery_acknowledged(peer_addr));
+
+        s.send_to(&mut create_session_response(), peer_addr).unwrap();
+        assert_eq!(receive(&peer).message.recovery(), None);
+
+        // Echo messages always carry it
+        s.send_echo_request(peer_addr).unwrap();
+        assert!(receive(&peer).message.recovery().is_some());
+    }
+
+    #[test]
+    fn test_recovery_response() {
+        let mut s = new_server();
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        let peer_addr = peer.local_addr().unwrap();
+
+        let request = echo_request(0x1234).to_vec().unwrap();
+
+        let mut response = s.handle(&request, peer_addr).unwrap();
+        s.send_to(&mut response, peer_addr).unwrap();
+        receive(&peer);
+
+        s.send_to(&mut create_session_response(), peer_addr).unwrap();
+        assert!(receive(&peer).message.recovery().is_some());
+
+        // A retransmission means the response may not have arrived
+        s.handle(&request, peer_addr);
+        assert!(!s.recovery_acknowledged(peer_addr));
+
+        s.handle(&echo_request(0x1235).to_vec().unwrap(), peer_addr);
+        assert!(s.recovery_acknowledged(peer_addr));
+
+        s.send_to(&mut create_session_response(), peer_addr).unwrap();
+        assert_eq!(receive(&peer).message.recovery(), None);
+
+        // A Recovery IE added by the handler is left alone
+        let mut p = create_session_response();
+        if let Message::CreateSessionResponse(ref mut m) = p.message {
+            m.recovery = Some(recovery::InformationElement::new(9, 0).unwrap());
+        }
+        s.send_to(&mut p, peer_addr).unwrap();
+        assert_eq!(receive(&peer).message.recovery().map(|ie| ie.restart_counter), Some(9));
+    }
+
+    #[test]
+    fn test_run() {
+        let mut s = new_server();
+
+        let addr = s.local_addr().unwrap();
+        let shutdown = s.shutdown_handle();
+
+        let server = thread::spawn(move || {
+            s.run().unwrap();
+            s
+        });
+
+        let peer = UdpSocket::bind("127.0.0.1:0").expect("couldn't bind to address");
+        peer.set_read_timeout(Some(Duration::from_secs(1))).unwrap();
+
+        echo_request(0x1234).send_to(&peer, addr).unwrap();
+
+        let mut buffer = [0; MTU];
+        let (n, _src) = peer.recv_from(&mut buffer).unwrap();
+
+        let (p, _pos) = Packet::parse(&buffer[..n]).unwrap();
+
+        assert_eq!(p.header.message_type(), MessageType::EchoResponse);
+        assert_eq!(p.header.sequence_number(), 0x1234);
+
+        shutdown.shutdown();
+
+        let s = server.join().unwrap();
+
+        let stats = s.statistics(peer.local_addr().unwrap()).unwrap();
+
+        assert_eq!(stats.rx, 1);
+        assert_eq!(stats.tx, 1);
+        assert_eq!(stats.tx_echo_response, 1);
+    }
+}
@@ -1,8 +1,53 @@
+// Parsing and generation only need core and alloc. Sockets, listeners and servers need the std feature (on by default)
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+// Must come first so the other modules can use its macros
+#[macro_use]
+mod logging;
+
 pub mod gtp_v1;
 pub mod gtp_v2;
+pub mod gtp_prime;
+pub mod dissect;
+pub mod tbcd;
+pub mod pco;
+pub mod tft;
+pub mod bitrate;
+pub mod address;
+pub mod uint;
+pub mod timer;
+pub mod mutation;
+
+#[cfg(feature = "std")]
+pub mod metrics;
+
+#[cfg(feature = "std")]
+pub mod demux;
+
+#[cfg(all(feature = "std", unix))]
+pub mod socket;
+
+#[cfg(feature = "pcap")]
+pub mod pcap;
+
+#[cfg(feature = "pcap")]
+pub mod replay;
+
+#[cfg(feature = "sim")]
+pub mod sim;
+
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+
+#[cfg(any(test, feature = "strategies"))]
+pub mod strategies;
 
 mod field {
     pub type Field = ::core::ops::Range<usize>;
 }
 
+// Size of the fixed buffers used when receiving packets.
+// Packet::to_vec and Packet::generate_into size their output from the packet so aren't limited by it.
 pub const MTU: usize = 1600;
@@ -0,0 +1,66 @@
+/*
+    Debug events for data the parsers discard: packets that fail to parse, IEs that fail to parse, IEs that don't belong
+    in a message and bytes that are skipped. With the logging feature they are sent to the log crate (target is the
+    module path e.g. gtp::gtp_v2::packet). Without it they compile to nothing but the arguments are still type checked.
+*/
+
+#[cfg(feature = "logging")]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        log::debug!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "logging"))]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod tests {
+    use std::sync::Mutex;
+
+    use crate::gtp_v2::packet::Packet;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+
+    struct Logger(Mutex<Vec<String>>);
+
+    impl log::Log for Logger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push(format!("{}", record.args()));
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: Logger = Logger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_debug_events() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        // An Echo Request with a Recovery IE that is too short and a Cause IE that doesn't belong in it
+        let buffer = [
+            0b0100_0000, 1, 0, 16, 0x00, 0x12, 0x34, 0,
+            InformationElementType::Recovery as u8, 0, 0, 0,
+            InformationElementType::Cause as u8, 0, 2, 0, 16, 0,
+        ];
+
+        assert!(Packet::parse(&buffer).is_err());
+
+        let events = LOGGER.0.lock().unwrap();
+
+        assert!(events.contains(&String::from("Skipping IE type 3 (instance 0) of 4 bytes that couldn't be parsed")));
+        assert!(events.contains(&String::from("Ignoring Cause IE (instance 0) in Echo Request")));
+        assert!(events.iter().any(|e| e.starts_with("Failed to parse Echo Request (sequence number 4660)")));
+    }
+}
@@ -0,0 +1,201 @@
+/* Per-peer metrics for GTP nodes.
+
+The send and receive paths of gtp_v2::server::Server and gtp_v1::path_manager::PathManager report each event to a
+MetricsSink. Metrics is a sink that keeps the counters for each peer in memory and renders them in the Prometheus
+text exposition format. Implement MetricsSink to forward the events to any other metrics system.
+
+Message Types are reported as their value on the wire so one sink can be shared by GTPv1 and GTPv2 nodes. */
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub trait MetricsSink {
+    fn message_received(&mut self, _peer: SocketAddr, _message_type: u8) {}
+    fn message_sent(&mut self, _peer: SocketAddr, _message_type: u8) {}
+    fn retransmission(&mut self, _peer: SocketAddr, _message_type: u8) {}
+    fn parse_failure(&mut self, _peer: SocketAddr) {}
+    fn echo_rtt(&mut self, _peer: SocketAddr, _rtt: Duration) {}
+}
+
+impl<T: MetricsSink> MetricsSink for Arc<Mutex<T>> {
+    // Lets the sink be read (e.g. by a thread serving /metrics) while a node reports to it
+    fn message_received(&mut self, peer: SocketAddr, message_type: u8) {
+        self.lock().unwrap().message_received(peer, message_type);
+    }
+
+    fn message_sent(&mut self, peer: SocketAddr, message_type: u8) {
+        self.lock().unwrap().message_sent(peer, message_type);
+    }
+
+    fn retransmission(&mut self, peer: SocketAddr, message_type: u8) {
+        self.lock().unwrap().retransmission(peer, message_type);
+    }
+
+    fn parse_failure(&mut self, peer: SocketAddr) {
+        self.lock().unwrap().parse_failure(peer);
+    }
+
+    fn echo_rtt(&mut self, peer: SocketAddr, rtt: Duration) {
+        self.lock().unwrap().echo_rtt(peer, rtt);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PeerMetrics {
+    pub rx: BTreeMap<u8, u64>, // Messages received by Message Type
+    pub tx: BTreeMap<u8, u64>, // Messages sent by Message Type
+    pub retransmissions: BTreeMap<u8, u64>,
+    pub parse_failures: u64,
+    pub echo_rtt: Option<Duration>, // Of the last answered Echo Request
+}
+
+// Reads one of the per Message Type counters of a peer
+type Counter = fn(&PeerMetrics) -> &BTreeMap<u8, u64>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    peers: HashMap<SocketAddr, PeerMetrics>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics {
+            peers: HashMap::new(),
+        }
+    }
+
+    pub fn peer(&self, peer: SocketAddr) -> Option<&PeerMetrics> {
+        self.peers.get(&peer)
+    }
+
+    pub fn peers(&self) -> impl Iterator<Item = (&SocketAddr, &PeerMetrics)> {
+        self.peers.iter()
+    }
+
+    pub fn prometheus(&self, prefix: &str) -> String {
+        // Renders the metrics with each name starting with prefix e.g. "gtp_v2"
+        let mut peers: Vec<(&SocketAddr, &PeerMetrics)> = self.peers.iter().collect();
+        peers.sort_by_key(|(peer, _m)| **peer);
+
+        let mut output = String::new();
+
+        let counters: [(&str, Counter); 3] = [
+            ("messages_received_total", |m| &m.rx),
+            ("messages_sent_total", |m| &m.tx),
+            ("retransmissions_total", |m| &m.retransmissions),
+        ];
+
+        for (name, counter) in counters.iter() {
+            let _ = writeln!(output, "# TYPE {}_{} counter", prefix, name);
+
+            for (peer, m) in peers.iter() {
+                for (message_type, n) in counter(m) {
+                    let _ = writeln!(output, "{}_{}{{peer=\"{}\",message_type=\"{}\"}} {}", prefix, name, peer, message_type, n);
+                }
+            }
+        }
+
+        let _ = writeln!(output, "# TYPE {}_parse_failures_total counter", prefix);
+
+        for (peer, m) in peers.iter() {
+            let _ = writeln!(output, "{}_parse_failures_total{{peer=\"{}\"}} {}", prefix, peer, m.parse_failures);
+        }
+
+        let _ = writeln!(output, "# TYPE {}_echo_rtt_seconds gauge", prefix);
+
+        for (peer, m) in peers.iter() {
+            if let Some(rtt) = m.echo_rtt {
+                let _ = writeln!(output, "{}_echo_rtt_seconds{{peer=\"{}\"}} {}", prefix, peer, rtt.as_secs_f64());
+            }
+        }
+
+        output
+    }
+}
+
+impl MetricsSink for Metrics {
+    fn message_received(&mut self, peer: SocketAddr, message_type: u8) {
+        *self.peers.entry(peer).or_default().rx.entry(message_type).or_insert(0) += 1;
+    }
+
+    fn message_sent(&mut self, peer: SocketAddr, message_type: u8) {
+        *self.peers.entry(peer).or_default().tx.entry(message_type).or_insert(0) += 1;
+    }
+
+    fn retransmission(&mut self, peer: SocketAddr, message_type: u8) {
+        *self.peers.entry(peer).or_default().retransmissions.entry(message_type).or_insert(0) += 1;
+    }
+
+    fn parse_failure(&mut self, peer: SocketAddr) {
+        self.peers.entry(peer).or_default().parse_failures += 1;
+    }
+
+    fn echo_rtt(&mut self, peer: SocketAddr, rtt: Duration) {
+        self.peers.entry(peer).or_default().echo_rtt = Some(rtt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> SocketAddr {
+        "10.0.0.1:2123".parse().unwrap()
+    }
+
+    #[test]
+    fn test_metrics() {
+        let mut m = Metrics::new();
+
+        m.message_received(peer(), 32);
+        m.message_received(peer(), 32);
+        m.message_sent(peer(), 33);
+        m.retransmission(peer(), 32);
+        m.parse_failure(peer());
+        m.echo_rtt(peer(), Duration::from_millis(5));
+
+        let p = m.peer(peer()).unwrap();
+
+        assert_eq!(p.rx.get(&32), Some(&2));
+        assert_eq!(p.tx.get(&33), Some(&1));
+        assert_eq!(p.retransmissions.get(&32), Some(&1));
+        assert_eq!(p.parse_failures, 1);
+        assert_eq!(p.echo_rtt, Some(Duration::from_millis(5)));
+
+        assert!(m.peer("10.0.0.2:2123".parse().unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_shared_sink() {
+        let m = Arc::new(Mutex::new(Metrics::new()));
+
+        let mut sink = m.clone();
+        sink.message_received(peer(), 1);
+
+        assert_eq!(m.lock().unwrap().peer(peer()).unwrap().rx.get(&1), Some(&1));
+    }
+
+    #[test]
+    fn test_prometheus() {
+        let mut m = Metrics::new();
+
+        m.message_received(peer(), 32);
+        m.message_sent(peer(), 33);
+        m.echo_rtt(peer(), Duration::from_millis(5));
+
+        assert_eq!(m.prometheus("gtp_v2"), "\
+# TYPE gtp_v2_messages_received_total counter
+gtp_v2_messages_received_total{peer=\"10.0.0.1:2123\",message_type=\"32\"} 1
+# TYPE gtp_v2_messages_sent_total counter
+gtp_v2_messages_sent_total{peer=\"10.0.0.1:2123\",message_type=\"33\"} 1
+# TYPE gtp_v2_retransmissions_total counter
+# TYPE gtp_v2_parse_failures_total counter
+gtp_v2_parse_failures_total{peer=\"10.0.0.1:2123\"} 0
+# TYPE gtp_v2_echo_rtt_seconds gauge
+gtp_v2_echo_rtt_seconds{peer=\"10.0.0.1:2123\"} 0.005
+");
+    }
+}
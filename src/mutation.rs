@@ -0,0 +1,253 @@
+/* Corrupting generated packets for negative testing
+
+A negative test is a packet and the list of mutations to apply to its bytes after it has been generated, e.g. a
+Create Session Request with a truncated Bearer Context or an Echo Request whose length field is too long.
+
+    let mut bytes = packet.to_vec()?;
+
+    mutation::apply(&mut bytes, &[
+        Mutation::TruncateIe { ie_type: 93, instance: 0, length: 2 },
+        Mutation::FlipBits { offset: 0, mask: 0b0000_0111 }, // The spare bits of the header
+    ])?;
+
+The mutations are applied in order, so offsets refer to the bytes as left by the previous mutations. The header length
+field is at the same offset for GTPv1, GTPv2 and GTP' so SetLength and AdjustLength work for all of them. The IE
+mutations only find the top level IEs of GTPv2 messages as GTPv1 IEs don't all have a length.
+*/
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::field::Field;
+
+// The header length field of GTPv1, GTPv2 and GTP'
+const LENGTH: Field = 2..4;
+
+// The bytes of the GTPv2 header before the length field isn't counted by it
+const GTP_V2_FIXED_HEADER_LENGTH: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mutation {
+    SetLength(u16), // Overrides the header length field
+    AdjustLength(i32), // Adds to the header length field
+    Truncate(usize), // Removes everything from the offset
+    FlipBits { offset: usize, mask: u8 }, // e.g. to set spare bits or clear flags
+    Set { offset: usize, bytes: Vec<u8> }, // Overwrites bytes from the offset
+    Append(Vec<u8>), // Trailing bytes that aren't counted by the header length
+    SetIeLength { ie_type: u8, instance: u8, length: u16 }, // Overrides the length of the first GTPv2 IE with this type and instance
+    TruncateIe { ie_type: u8, instance: u8, length: u16 }, // Cuts the value of the IE to length and updates the IE and header lengths to match
+}
+
+impl Mutation {
+    pub fn apply(&self, bytes: &mut Vec<u8>) -> Result<(), String> {
+        match self {
+            Mutation::SetLength(length) => {
+                check_range(bytes, LENGTH)?;
+                NetworkEndian::write_u16(&mut bytes[LENGTH], *length);
+            },
+            Mutation::AdjustLength(delta) => {
+                check_range(bytes, LENGTH)?;
+
+                let length = NetworkEndian::read_u16(&bytes[LENGTH]) as i32 + delta;
+
+                if length < 0 || length > u16::MAX as i32 {
+                    return Err(format!("Length {} can't be encoded", length));
+                }
+
+                NetworkEndian::write_u16(&mut bytes[LENGTH], length as u16);
+            },
+            Mutation::Truncate(offset) => {
+                if *offset > bytes.len() {
+                    return Err(format!("Can't truncate {} bytes at {}", bytes.len(), offset));
+                }
+
+                bytes.truncate(*offset);
+            },
+            Mutation::FlipBits { offset, mask } => {
+                check_range(bytes, *offset..*offset + 1)?;
+                bytes[*offset] = bytes[*offset] ^ mask;
+            },
+            Mutation::Set { offset, bytes: value } => {
+                check_range(bytes, *offset..*offset + value.len())?;
+                bytes[*offset..*offset + value.len()].copy_from_slice(value);
+            },
+            Mutation::Append(value) => bytes.extend_from_slice(value),
+            Mutation::SetIeLength { ie_type, instance, length } => {
+                let pos = find_ie(bytes, *ie_type, *instance)?;
+                NetworkEndian::write_u16(&mut bytes[pos + 1..pos + 3], *length);
+            },
+            Mutation::TruncateIe { ie_type, instance, length } => {
+                let pos = find_ie(bytes, *ie_type, *instance)?;
+                let ie_length = NetworkEndian::read_u16(&bytes[pos + 1..pos + 3]);
+
+                if *length > ie_length {
+                    return Err(format!("IE {} is only {} bytes long", ie_type, ie_length));
+                }
+
+                let removed = (ie_length - length) as usize;
+                let value_start = pos + 4;
+
+                bytes.drain(value_start + *length as usize..value_start + ie_length as usize);
+                NetworkEndian::write_u16(&mut bytes[pos + 1..pos + 3], *length);
+
+                let header_length = NetworkEndian::read_u16(&bytes[LENGTH]);
+                NetworkEndian::write_u16(&mut bytes[LENGTH], header_length.saturating_sub(removed as u16));
+            },
+        }
+
+        Ok(())
+    }
+}
+
+pub fn apply(bytes: &mut Vec<u8>, mutations: &[Mutation]) -> Result<(), String> {
+    for mutation in mutations {
+        mutation.apply(bytes)?;
+    }
+
+    Ok(())
+}
+
+fn check_range(bytes: &[u8], range: Field) -> Result<(), String> {
+    if range.end > bytes.len() {
+        return Err(format!("Offset {} is past the end of {} bytes", range.end - 1, bytes.len()));
+    }
+
+    Ok(())
+}
+
+fn find_ie(bytes: &[u8], ie_type: u8, instance: u8) -> Result<usize, String> {
+    // The offset of the first top level IE of a GTPv2 message with the type and instance
+    check_range(bytes, LENGTH)?;
+
+    if (bytes[0] >> 5) != 2 {
+        return Err(String::from("IEs can only be found in GTPv2 messages"));
+    }
+
+    // The T flag adds the TEID to the header
+    let mut pos = if bytes[0] & 0x08 != 0 { 12 } else { 8 };
+
+    let end = (GTP_V2_FIXED_HEADER_LENGTH + NetworkEndian::read_u16(&bytes[LENGTH]) as usize).min(bytes.len());
+
+    while pos + 4 <= end {
+        let length = NetworkEndian::read_u16(&bytes[pos + 1..pos + 3]) as usize;
+
+        if bytes[pos] == ie_type && bytes[pos + 3] & 0xF == instance {
+            if pos + 4 + length > end {
+                return Err(format!("IE {} is longer than the message", ie_type));
+            }
+
+            return Ok(pos);
+        }
+
+        pos = pos + 4 + length;
+    }
+
+    Err(format!("No IE {} instance {}", ie_type, instance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::Packet;
+    use crate::gtp_v2::packet::messages::{Message, MessageType, echo_request};
+    use crate::gtp_v2::packet::messages::information_elements::{
+        InformationElementType,
+        recovery,
+    };
+
+    fn new_echo_request() -> Vec<u8> {
+        let m = echo_request::Message::new(recovery::InformationElement::new(0x12, 0).unwrap());
+
+        let mut p = Packet::new(Message::EchoRequest(m));
+        p.header.set_sequence_number(0x10).unwrap();
+
+        p.to_vec().unwrap()
+    }
+
+    #[test]
+    fn test_length() {
+        let mut bytes = new_echo_request();
+
+        assert_eq!(&bytes[LENGTH], [0x00, 0x09]);
+
+        apply(&mut bytes, &[Mutation::AdjustLength(3)]).unwrap();
+        assert_eq!(&bytes[LENGTH], [0x00, 0x0C]);
+
+        apply(&mut bytes, &[Mutation::SetLength(0x100)]).unwrap();
+        assert_eq!(&bytes[LENGTH], [0x01, 0x00]);
+
+        assert!(Mutation::AdjustLength(-0x101).apply(&mut bytes).is_err());
+        assert!(Mutation::SetLength(0).apply(&mut vec![0x48, 0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_bytes() {
+        let mut bytes = new_echo_request();
+
+        apply(&mut bytes, &[
+            Mutation::FlipBits { offset: 0, mask: 0b0000_0111 },
+            Mutation::Set { offset: 4, bytes: vec![0xAA, 0xBB] },
+            Mutation::Append(vec![0xFF]),
+        ]).unwrap();
+
+        let expected = [
+            /* Flags */ 0x47,
+            /* Message Type */ 0x01,
+            /* Length */ 0x00, 0x09,
+            /* Sequence Number */ 0xAA, 0xBB, 0x10,
+            /* Spare */ 0x00,
+            /* Recovery */ 0x03, 0x00, 0x01, 0x00, 0x12,
+            /* Trailing */ 0xFF,
+        ];
+
+        assert_eq!(bytes, expected);
+
+        apply(&mut bytes, &[Mutation::Truncate(8)]).unwrap();
+        assert_eq!(bytes.len(), 8);
+
+        assert!(Mutation::Truncate(9).apply(&mut bytes).is_err());
+        assert!(Mutation::FlipBits { offset: 8, mask: 1 }.apply(&mut bytes).is_err());
+        assert!(Mutation::Set { offset: 7, bytes: vec![0, 0] }.apply(&mut bytes).is_err());
+    }
+
+    #[test]
+    fn test_ie() {
+        let mut bytes = new_echo_request();
+        let ie_type = InformationElementType::Recovery as u8;
+
+        assert!(Mutation::TruncateIe { ie_type, instance: 0, length: 2 }.apply(&mut bytes).is_err());
+        assert!(Mutation::TruncateIe { ie_type, instance: 1, length: 0 }.apply(&mut bytes).is_err());
+        assert!(Mutation::SetIeLength { ie_type: ie_type + 1, instance: 0, length: 0 }.apply(&mut bytes).is_err());
+
+        apply(&mut bytes, &[Mutation::TruncateIe { ie_type, instance: 0, length: 0 }]).unwrap();
+
+        let expected = [
+            /* Flags */ 0x40,
+            /* Message Type */ 0x01,
+            /* Length */ 0x00, 0x08,
+            /* Sequence Number */ 0x00, 0x00, 0x10,
+            /* Spare */ 0x00,
+            /* Recovery */ 0x03, 0x00, 0x00, 0x00,
+        ];
+
+        assert_eq!(bytes, expected);
+
+        // The header is still valid so only the IE is malformed
+        let (h, _pos) = crate::gtp_v2::packet::header::Header::parse(&bytes).unwrap();
+        assert_eq!(h.message_type(), MessageType::EchoRequest);
+        assert!(Packet::parse(&bytes).is_err());
+
+        apply(&mut bytes, &[Mutation::SetIeLength { ie_type, instance: 0, length: 5 }]).unwrap();
+        assert_eq!(&bytes[9..11], [0x00, 0x05]);
+
+        // The IE is now longer than the message
+        assert!(Mutation::SetIeLength { ie_type, instance: 0, length: 1 }.apply(&mut bytes).is_err());
+
+        // GTPv1 IEs can't be found
+        assert!(Mutation::SetIeLength { ie_type, instance: 0, length: 1 }.apply(&mut vec![0x32, 0x01, 0x00, 0x00]).is_err());
+    }
+}
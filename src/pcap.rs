@@ -0,0 +1,662 @@
+/* Reading and writing of GTP packets from / to capture files
+
+Supports classic pcap and pcapng files with Ethernet, Linux cooked (SLL) or raw IP link types.
+UDP datagrams to or from port 2123 (GTP-C) or 2152 (GTP-U) are extracted and parsed. The GTP version
+of a GTP-C datagram is taken from the version field of its header.
+
+    for packet in gtp::pcap::read_packets("examples/tests.pcapng")? {
+        ...
+    }
+
+Captures are written as classic pcap files with an Ethernet link type. Each packet is wrapped in
+Ethernet / IP / UDP headers so the file can be opened by Wireshark.
+*/
+
+use byteorder::{ByteOrder, BigEndian, LittleEndian, NetworkEndian};
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{gtp_v1, gtp_v2};
+use crate::gtp_v1::inner_ip::checksum;
+
+pub const GTP_C_PORT: u16 = 2123;
+pub const GTP_U_PORT: u16 = 2152;
+
+const PCAP_MAGIC: u32 = 0xA1B2C3D4;
+const PCAP_MAGIC_NANOSECONDS: u32 = 0xA1B23C4D;
+const PCAPNG_SECTION_HEADER_BLOCK: u32 = 0x0A0D0D0A;
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B3C4D;
+const PCAPNG_INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+const PCAPNG_SIMPLE_PACKET_BLOCK: u32 = 3;
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 6;
+
+const LINK_TYPE_ETHERNET: u32 = 1;
+const LINK_TYPE_RAW: u32 = 101;
+const LINK_TYPE_LINUX_SLL: u32 = 113;
+const LINK_TYPE_IPV4: u32 = 228;
+const LINK_TYPE_IPV6: u32 = 229;
+
+const ETHER_TYPE_IPV4: u16 = 0x0800;
+const ETHER_TYPE_IPV6: u16 = 0x86DD;
+const ETHER_TYPE_VLAN: u16 = 0x8100;
+const ETHER_TYPE_QINQ: u16 = 0x88A8;
+
+const IP_PROTOCOL_UDP: u8 = 17;
+
+pub enum Packet {
+    GtpV1(gtp_v1::packet::Packet),
+    GtpV2(gtp_v2::packet::Packet),
+}
+
+impl Packet {
+    pub fn parse(port: u16, buffer: &[u8]) -> Option<(Self, usize)> {
+        // Parses a UDP payload received on port (the GTP port of the datagram)
+        if buffer.is_empty() {
+            return None
+        }
+
+        if port == GTP_U_PORT || buffer[0] >> 5 == 1 {
+            let (p, pos) = gtp_v1::packet::Packet::parse(buffer)?;
+            Some((Packet::GtpV1(p), pos))
+        }
+        else if buffer[0] >> 5 == 2 {
+            match gtp_v2::packet::Packet::parse(buffer) {
+                Ok((p, pos)) => Some((Packet::GtpV2(p), pos)),
+                Err(_) => None,
+            }
+        }
+        else {
+            None
+        }
+    }
+
+    pub fn generate(&mut self, buffer: &mut[u8]) -> Result<usize, String> {
+        match self {
+            Packet::GtpV1(p) => Ok(p.generate(buffer)),
+            Packet::GtpV2(p) => p.generate(buffer),
+        }
+    }
+
+    pub fn to_vec(&mut self) -> Result<Vec<u8>, String> {
+        match self {
+            Packet::GtpV1(p) => Ok(p.to_vec()),
+            Packet::GtpV2(p) => p.to_vec(),
+        }
+    }
+}
+
+pub struct UdpPayload {
+    pub source: SocketAddr,
+    pub destination: SocketAddr,
+    pub payload: Vec<u8>,
+}
+
+impl UdpPayload {
+    pub fn gtp_port(&self) -> Option<u16> {
+        // The GTP port of the datagram. The destination port is checked first as requests may be sent from any port
+        for port in &[self.destination.port(), self.source.port()] {
+            if *port == GTP_C_PORT || *port == GTP_U_PORT {
+                return Some(*port)
+            }
+        }
+
+        None
+    }
+}
+
+pub fn read_packets<P: AsRef<Path>>(path: P) -> Result<Vec<Packet>, String> {
+    // Reads every GTP packet from a capture file. Datagrams that don't contain a valid GTP packet are skipped
+    let mut packets = Vec::new();
+
+    for payload in read_payloads(path)? {
+        if let Some(port) = payload.gtp_port() {
+            if let Some((p, _pos)) = Packet::parse(port, &payload.payload) {
+                packets.push(p);
+            }
+        }
+    }
+
+    Ok(packets)
+}
+
+pub fn read_payloads<P: AsRef<Path>>(path: P) -> Result<Vec<UdpPayload>, String> {
+    // Reads the payload of every UDP datagram to or from a GTP port in a capture file
+    let mut buffer = Vec::new();
+
+    let mut file = File::open(path).map_err(|e| format!("Unable to open capture file: {}", e))?;
+    file.read_to_end(&mut buffer).map_err(|e| format!("Unable to read capture file: {}", e))?;
+
+    parse_capture(&buffer)
+}
+
+pub fn parse_capture(buffer: &[u8]) -> Result<Vec<UdpPayload>, String> {
+    if buffer.len() < 4 {
+        return Err(format!("Capture file is too short {}", buffer.len()))
+    }
+
+    let mut payloads = Vec::new();
+
+    let frames = if LittleEndian::read_u32(&buffer[0..4]) == PCAPNG_SECTION_HEADER_BLOCK {
+        parse_pcapng(buffer)?
+    }
+    else {
+        parse_pcap(buffer)?
+    };
+
+    for (link_type, frame) in frames {
+        if let Some(payload) = parse_frame(link_type, frame) {
+            if payload.gtp_port().is_some() {
+                payloads.push(payload);
+            }
+        }
+    }
+
+    Ok(payloads)
+}
+
+fn read_u16(buffer: &[u8], big_endian: bool) -> u16 {
+    if big_endian { BigEndian::read_u16(buffer) } else { LittleEndian::read_u16(buffer) }
+}
+
+fn read_u32(buffer: &[u8], big_endian: bool) -> u32 {
+    if big_endian { BigEndian::read_u32(buffer) } else { LittleEndian::read_u32(buffer) }
+}
+
+fn parse_pcap(buffer: &[u8]) -> Result<Vec<(u32, &[u8])>, String> {
+    /*
+        Global header (24 octets)
+            Magic (4) | Version Major (2) | Version Minor (2) | Reserved (8) | Snap Length (4) | Link Type (4)
+        Record header (16 octets) followed by the captured octets
+            Timestamp Seconds (4) | Timestamp Fraction (4) | Captured Length (4) | Original Length (4)
+    */
+    if buffer.len() < 24 {
+        return Err(format!("pcap global header is too short {}", buffer.len()))
+    }
+
+    let big_endian = match LittleEndian::read_u32(&buffer[0..4]) {
+        PCAP_MAGIC | PCAP_MAGIC_NANOSECONDS => false,
+        _ => match BigEndian::read_u32(&buffer[0..4]) {
+            PCAP_MAGIC | PCAP_MAGIC_NANOSECONDS => true,
+            magic => return Err(format!("Unknown capture file magic {:#010x}", magic)),
+        }
+    };
+
+    let link_type = read_u32(&buffer[20..24], big_endian) & 0xFFFF;
+
+    let mut frames = Vec::new();
+
+    let mut pos = 24;
+
+    while pos + 16 <= buffer.len() {
+        let captured_length = read_u32(&buffer[pos+8..pos+12], big_endian) as usize;
+        pos = pos + 16;
+
+        if pos + captured_length > buffer.len() {
+            return Err(format!("pcap record is truncated {}", captured_length))
+        }
+
+        frames.push((link_type, &buffer[pos..pos + captured_length]));
+        pos = pos + captured_length;
+    }
+
+    Ok(frames)
+}
+
+fn parse_pcapng(buffer: &[u8]) -> Result<Vec<(u32, &[u8])>, String> {
+    /*
+        Every block has the format
+            Block Type (4) | Block Total Length (4) | Block Body | Block Total Length (4)
+        The byte order of a section is given by the byte order magic of its Section Header Block
+            Block Type (4) | Block Total Length (4) | Byte Order Magic (4) | ...
+    */
+    let mut frames = Vec::new();
+    let mut link_types: Vec<u32> = Vec::new();
+    let mut big_endian = false;
+
+    let mut pos = 0;
+
+    while pos + 12 <= buffer.len() {
+        let block_type = read_u32(&buffer[pos..pos+4], big_endian);
+
+        if block_type == PCAPNG_SECTION_HEADER_BLOCK {
+            big_endian = match LittleEndian::read_u32(&buffer[pos+8..pos+12]) {
+                PCAPNG_BYTE_ORDER_MAGIC => false,
+                _ => match BigEndian::read_u32(&buffer[pos+8..pos+12]) {
+                    PCAPNG_BYTE_ORDER_MAGIC => true,
+                    magic => return Err(format!("Unknown pcapng byte order magic {:#010x}", magic)),
+                }
+            };
+            // Interface IDs are local to a section
+            link_types.clear();
+        }
+
+        let block_length = read_u32(&buffer[pos+4..pos+8], big_endian) as usize;
+
+        if block_length < 12 || pos + block_length > buffer.len() {
+            return Err(format!("pcapng block is truncated {}", block_length))
+        }
+
+        let body = &buffer[pos+8..pos + block_length - 4];
+
+        match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION_BLOCK if body.len() >= 2 => {
+                link_types.push(read_u16(&body[0..2], big_endian) as u32);
+            },
+            PCAPNG_ENHANCED_PACKET_BLOCK if body.len() >= 20 => {
+                // Interface ID (4) | Timestamp (8) | Captured Length (4) | Original Length (4) | Packet Data
+                let interface_id = read_u32(&body[0..4], big_endian) as usize;
+                let captured_length = read_u32(&body[12..16], big_endian) as usize;
+
+                if 20 + captured_length > body.len() {
+                    return Err(format!("pcapng packet is truncated {}", captured_length))
+                }
+
+                if let Some(link_type) = link_types.get(interface_id) {
+                    frames.push((*link_type, &body[20..20 + captured_length]));
+                }
+            },
+            PCAPNG_SIMPLE_PACKET_BLOCK if body.len() >= 4 => {
+                // Original Length (4) | Packet Data. Always captured on the first interface
+                let original_length = read_u32(&body[0..4], big_endian) as usize;
+                let captured_length = std::cmp::min(original_length, body.len() - 4);
+
+                if let Some(link_type) = link_types.first() {
+                    frames.push((*link_type, &body[4..4 + captured_length]));
+                }
+            },
+            _ => { /* Other blocks don't contain packets. Just ignore them */ }
+        }
+
+        pos = pos + block_length;
+    }
+
+    Ok(frames)
+}
+
+fn parse_frame(link_type: u32, frame: &[u8]) -> Option<UdpPayload> {
+    match link_type {
+        LINK_TYPE_ETHERNET => {
+            // Destination MAC (6) | Source MAC (6) | [VLAN Tags (4)] | Ether Type (2)
+            let mut pos = 12;
+
+            loop {
+                if frame.len() < pos + 2 {
+                    return None
+                }
+
+                let ether_type = NetworkEndian::read_u16(&frame[pos..pos+2]);
+                pos = pos + 2;
+
+                match ether_type {
+                    ETHER_TYPE_VLAN | ETHER_TYPE_QINQ => pos = pos + 2,
+                    ETHER_TYPE_IPV4 | ETHER_TYPE_IPV6 => return parse_ip(&frame[pos..]),
+                    _ => return None,
+                }
+            }
+        },
+        LINK_TYPE_LINUX_SLL => {
+            // Packet Type (2) | ARPHRD Type (2) | Address Length (2) | Address (8) | Protocol Type (2)
+            if frame.len() < 16 {
+                return None
+            }
+
+            match NetworkEndian::read_u16(&frame[14..16]) {
+                ETHER_TYPE_IPV4 | ETHER_TYPE_IPV6 => parse_ip(&frame[16..]),
+                _ => None,
+            }
+        },
+        LINK_TYPE_RAW | LINK_TYPE_IPV4 | LINK_TYPE_IPV6 => parse_ip(frame),
+        _ => None,
+    }
+}
+
+fn parse_ip(buffer: &[u8]) -> Option<UdpPayload> {
+    if buffer.is_empty() {
+        return None
+    }
+
+    match buffer[0] >> 4 {
+        4 => {
+            if buffer.len() < 20 {
+                return None
+            }
+
+            let header_length = ((buffer[0] & 0xF) as usize) * 4;
+            let total_length = NetworkEndian::read_u16(&buffer[2..4]) as usize;
+            let fragment = NetworkEndian::read_u16(&buffer[6..8]);
+
+            // Only unfragmented datagrams are supported
+            if fragment & 0x3FFF != 0 || buffer[9] != IP_PROTOCOL_UDP {
+                return None
+            }
+
+            if header_length < 20 || total_length < header_length || buffer.len() < total_length {
+                return None
+            }
+
+            let mut source = [0; 4];
+            source.copy_from_slice(&buffer[12..16]);
+            let mut destination = [0; 4];
+            destination.copy_from_slice(&buffer[16..20]);
+
+            parse_udp(IpAddr::from(source), IpAddr::from(destination), &buffer[header_length..total_length])
+        },
+        6 => {
+            if buffer.len() < 40 {
+                return None
+            }
+
+            let payload_length = NetworkEndian::read_u16(&buffer[4..6]) as usize;
+
+            if buffer.len() < 40 + payload_length {
+                return None
+            }
+
+            let mut source = [0; 16];
+            source.copy_from_slice(&buffer[8..24]);
+            let mut destination = [0; 16];
+            destination.copy_from_slice(&buffer[24..40]);
+
+            let mut next_header = buffer[6];
+            let mut pos = 40;
+
+            // Skip the Hop-by-Hop, Routing and Destination Options extension headers
+            while next_header == 0 || next_header == 43 || next_header == 60 {
+                if 40 + payload_length < pos + 2 {
+                    return None
+                }
+
+                next_header = buffer[pos];
+                pos = pos + ((buffer[pos+1] as usize) + 1) * 8;
+            }
+
+            if next_header != IP_PROTOCOL_UDP || 40 + payload_length < pos {
+                return None
+            }
+
+            parse_udp(IpAddr::from(source), IpAddr::from(destination), &buffer[pos..40 + payload_length])
+        },
+        _ => None,
+    }
+}
+
+fn parse_udp(source: IpAddr, destination: IpAddr, buffer: &[u8]) -> Option<UdpPayload> {
+    // Source Port (2) | Destination Port (2) | Length (2) | Checksum (2)
+    if buffer.len() < 8 {
+        return None
+    }
+
+    let length = NetworkEndian::read_u16(&buffer[4..6]) as usize;
+
+    if length < 8 || buffer.len() < length {
+        return None
+    }
+
+    Some(UdpPayload {
+        source: SocketAddr::new(source, NetworkEndian::read_u16(&buffer[0..2])),
+        destination: SocketAddr::new(destination, NetworkEndian::read_u16(&buffer[2..4])),
+        payload: buffer[8..length].to_vec(),
+    })
+}
+
+pub struct Writer<W: Write> {
+    writer: W,
+}
+
+impl Writer<File> {
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| format!("Unable to create capture file: {}", e))?;
+
+        Writer::new(file)
+    }
+}
+
+impl<W: Write> Writer<W> {
+    pub fn new(mut writer: W) -> Result<Self, String> {
+        // Writes the global header of a little endian pcap file
+        let mut header = [0; 24];
+
+        LittleEndian::write_u32(&mut header[0..4], PCAP_MAGIC);
+        LittleEndian::write_u16(&mut header[4..6], 2); // Version Major
+        LittleEndian::write_u16(&mut header[6..8], 4); // Version Minor
+        LittleEndian::write_u32(&mut header[16..20], 0xFFFF); // Snap Length
+        LittleEndian::write_u32(&mut header[20..24], LINK_TYPE_ETHERNET);
+
+        writer.write_all(&header).map_err(|e| format!("Unable to write capture file: {}", e))?;
+
+        Ok(Writer {
+            writer
+        })
+    }
+
+    pub fn write_packet(&mut self, source: SocketAddr, destination: SocketAddr, packet: &mut Packet) -> Result<usize, String> {
+        let payload = packet.to_vec()?;
+
+        self.write_payload(source, destination, &payload)
+    }
+
+    pub fn write_payload(&mut self, source: SocketAddr, destination: SocketAddr, payload: &[u8]) -> Result<usize, String> {
+        // Wraps payload in Ethernet / IP / UDP headers and writes it as a pcap record. Returns the length of the frame
+        let mut frame = vec![
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x02, // Destination MAC
+            0x02, 0x00, 0x00, 0x00, 0x00, 0x01, // Source MAC
+            0x00, 0x00, // Ether Type
+        ];
+
+        let udp_length = 8 + payload.len();
+
+        let mut udp = vec![0; 8];
+        NetworkEndian::write_u16(&mut udp[0..2], source.port());
+        NetworkEndian::write_u16(&mut udp[2..4], destination.port());
+        NetworkEndian::write_u16(&mut udp[4..6], udp_length as u16);
+        udp.extend_from_slice(payload);
+
+        match (source.ip(), destination.ip()) {
+            (IpAddr::V4(source), IpAddr::V4(destination)) => {
+                if 20 + udp_length > 0xFFFF {
+                    return Err(format!("Payload is too long {}", payload.len()))
+                }
+
+                NetworkEndian::write_u16(&mut frame[12..14], ETHER_TYPE_IPV4);
+
+                let mut ip = [0; 20];
+                ip[0] = 0x45; // Version and IHL
+                NetworkEndian::write_u16(&mut ip[2..4], (20 + udp_length) as u16);
+                NetworkEndian::write_u16(&mut ip[6..8], 0x4000); // Don't Fragment
+                ip[8] = 64; // TTL
+                ip[9] = IP_PROTOCOL_UDP;
+                ip[12..16].copy_from_slice(&source.octets());
+                ip[16..20].copy_from_slice(&destination.octets());
+                let ip_checksum = checksum(&[&ip]);
+                NetworkEndian::write_u16(&mut ip[10..12], ip_checksum);
+
+                // The UDP checksum is optional for IPv4 and is left as 0
+                frame.extend_from_slice(&ip);
+            },
+            (IpAddr::V6(source), IpAddr::V6(destination)) => {
+                if udp_length > 0xFFFF {
+                    return Err(format!("Payload is too long {}", payload.len()))
+                }
+
+                NetworkEndian::write_u16(&mut frame[12..14], ETHER_TYPE_IPV6);
+
+                let mut ip = [0; 40];
+                ip[0] = 0x60; // Version
+                NetworkEndian::write_u16(&mut ip[4..6], udp_length as u16);
+                ip[6] = IP_PROTOCOL_UDP;
+                ip[7] = 64; // Hop Limit
+                ip[8..24].copy_from_slice(&source.octets());
+                ip[24..40].copy_from_slice(&destination.octets());
+
+                // The UDP checksum is mandatory for IPv6. It covers a pseudo header of the addresses, length and protocol
+                let mut pseudo_header = [0; 8];
+                NetworkEndian::write_u32(&mut pseudo_header[0..4], udp_length as u32);
+                pseudo_header[7] = IP_PROTOCOL_UDP;
+                let udp_checksum = match checksum(&[&ip[8..40], &pseudo_header, &udp]) {
+                    0 => 0xFFFF,
+                    udp_checksum => udp_checksum,
+                };
+                NetworkEndian::write_u16(&mut udp[6..8], udp_checksum);
+
+                frame.extend_from_slice(&ip);
+            },
+            _ => return Err(format!("Source {} and destination {} must be the same IP version", source, destination)),
+        }
+
+        frame.extend_from_slice(&udp);
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+
+        let mut record = [0; 16];
+        LittleEndian::write_u32(&mut record[0..4], timestamp.as_secs() as u32);
+        LittleEndian::write_u32(&mut record[4..8], timestamp.subsec_micros());
+        LittleEndian::write_u32(&mut record[8..12], frame.len() as u32);
+        LittleEndian::write_u32(&mut record[12..16], frame.len() as u32);
+
+        self.writer.write_all(&record).map_err(|e| format!("Unable to write capture file: {}", e))?;
+        self.writer.write_all(&frame).map_err(|e| format!("Unable to write capture file: {}", e))?;
+
+        Ok(frame.len())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use gtp_v1::packet::messages::MessageType as V1MessageType;
+    use gtp_v2::packet::messages::{Message, MessageType, echo_request};
+    use gtp_v2::packet::messages::information_elements::recovery;
+
+    fn new_packet() -> Packet {
+        let m = echo_request::Message::new(recovery::InformationElement::new(5, 0).unwrap());
+
+        Packet::GtpV2(gtp_v2::packet::Packet::new(Message::EchoRequest(m)))
+    }
+
+    #[test]
+    fn test_read_packets() {
+        let packets = read_packets(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/tests.pcapng")).unwrap();
+
+        // The capture also contains a Create PDP Context Request which the GTPv1 parser doesn't support yet, and an
+        // Echo Response without the mandatory Recovery IE
+        assert_eq!(packets.len(), 2);
+
+        let message_types: Vec<u8> = packets.iter().map(|p| match p {
+            Packet::GtpV1(p) => p.header.message_type() as u8,
+            Packet::GtpV2(_) => 0,
+        }).collect();
+
+        assert_eq!(message_types, vec![
+            V1MessageType::GPDU as u8,
+            V1MessageType::EchoRequest as u8,
+        ]);
+
+        let payloads = read_payloads(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/tests.pcapng")).unwrap();
+
+        assert_eq!(payloads.len(), 4);
+        assert_eq!(payloads[0].gtp_port(), Some(GTP_U_PORT));
+        assert_eq!(payloads[3].gtp_port(), Some(GTP_C_PORT));
+        assert_eq!(payloads[3].payload[1], V1MessageType::CreatePDPContextRequest as u8);
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        assert!(read_packets("/nonexistent/tests.pcap").is_err());
+    }
+
+    #[test]
+    fn test_write_and_read_ipv4() {
+        let source = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 40000);
+        let destination = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), GTP_C_PORT);
+
+        let mut w = Writer::new(Vec::new()).unwrap();
+        let frame_length = w.write_packet(source, destination, &mut new_packet()).unwrap();
+        let buffer = w.into_inner();
+
+        assert_eq!(frame_length, 14 + 20 + 8 + 8 + 5);
+        assert_eq!(buffer.len(), 24 + 16 + frame_length);
+
+        // IPv4 header checksum must verify
+        assert_eq!(checksum(&[&buffer[24+16+14..24+16+14+20]]), 0);
+
+        let payloads = parse_capture(&buffer).unwrap();
+
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].source, source);
+        assert_eq!(payloads[0].destination, destination);
+        assert_eq!(payloads[0].gtp_port(), Some(GTP_C_PORT));
+
+        if let Some((Packet::GtpV2(p), pos)) = Packet::parse(GTP_C_PORT, &payloads[0].payload) {
+            assert_eq!(p.header.message_type(), MessageType::EchoRequest);
+            assert_eq!(pos, payloads[0].payload.len());
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_write_and_read_ipv6() {
+        let source = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)), GTP_C_PORT);
+        let destination = SocketAddr::new(IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2)), GTP_C_PORT);
+
+        let mut w = Writer::new(Vec::new()).unwrap();
+        w.write_packet(source, destination, &mut new_packet()).unwrap();
+        let buffer = w.into_inner();
+
+        // UDP checksum over the pseudo header and datagram must verify
+        let udp = &buffer[24+16+14+40..];
+        let mut pseudo_header = [0; 8];
+        NetworkEndian::write_u32(&mut pseudo_header[0..4], udp.len() as u32);
+        pseudo_header[7] = IP_PROTOCOL_UDP;
+        assert_eq!(checksum(&[&buffer[24+16+14+8..24+16+14+40], &pseudo_header, udp]), 0);
+
+        let packets = read_packets_from(&buffer);
+
+        assert_eq!(packets.len(), 1);
+    }
+
+    #[test]
+    fn test_write_mixed_ip_versions() {
+        let source = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), GTP_C_PORT);
+        let destination = SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), GTP_C_PORT);
+
+        let mut w = Writer::new(Vec::new()).unwrap();
+
+        assert!(w.write_packet(source, destination, &mut new_packet()).is_err());
+    }
+
+    #[test]
+    fn test_non_gtp_ports_are_skipped() {
+        let source = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 53);
+        let destination = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)), 53);
+
+        let mut w = Writer::new(Vec::new()).unwrap();
+        w.write_packet(source, destination, &mut new_packet()).unwrap();
+
+        assert_eq!(parse_capture(&w.into_inner()).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_unknown_capture_format() {
+        assert!(parse_capture(&[0; 24]).is_err());
+        assert!(parse_capture(&[0; 2]).is_err());
+    }
+
+    fn read_packets_from(buffer: &[u8]) -> Vec<Packet> {
+        parse_capture(buffer).unwrap().iter().filter_map(|payload| {
+            Packet::parse(payload.gtp_port()?, &payload.payload).map(|(p, _pos)| p)
+        }).collect()
+    }
+}
@@ -0,0 +1,212 @@
+/*
+    Protocol Configuration Options (PCO) container as per TS 24.008 10.5.6.3. The same container is carried by
+    the PCO, APCO and ePCO (TS 24.301 9.9.4.26) IEs. Only the length of the IE carrying it differs.
+
+                                        Bits
+            |---------------------------------------------------------------|
+    Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+            |---------------------------------------------------------------|
+    1       | Ext   | Spare                         | Configuration protocol|
+    2 -> 3  | Protocol or Container ID 1                                    |
+    4       | Length of contents of ID 1                                    |
+    5 -> m  | Contents of ID 1                                              |
+    ...     | Protocol or Container ID n etc.                               |
+            |---------------------------------------------------------------|
+*/
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::dissect::{Dissect, Dissector};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Configuration protocol
+pub const PPP: u8 = 0;
+
+// Commonly used protocol and container IDs
+pub const IPCP: u16 = 0x8021;
+pub const PAP: u16 = 0xC023;
+pub const CHAP: u16 = 0xC223;
+pub const P_CSCF_IPV6_ADDRESS: u16 = 0x0001;
+pub const DNS_SERVER_IPV6_ADDRESS: u16 = 0x0003;
+pub const IP_ADDRESS_ALLOCATION_VIA_NAS: u16 = 0x000A;
+pub const P_CSCF_IPV4_ADDRESS: u16 = 0x000C;
+pub const DNS_SERVER_IPV4_ADDRESS: u16 = 0x000D;
+pub const IPV4_LINK_MTU: u16 = 0x0010;
+pub const NON_IP_LINK_MTU: u16 = 0x0015;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ProtocolOption {
+    pub id: u16,
+    pub contents: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct ProtocolConfigurationOptions {
+    pub configuration_protocol: u8,
+    pub options: Vec<ProtocolOption>,
+}
+
+impl ProtocolConfigurationOptions {
+    pub fn new() -> Self {
+        ProtocolConfigurationOptions {
+            configuration_protocol: PPP,
+            options: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, id: u16, contents: &[u8]) -> Result<(), String> {
+        if contents.len() > 0xFF {
+            return Err(format!("Contents of protocol or container ID {:#06x} are too long {}", id, contents.len()));
+        }
+
+        self.options.push(ProtocolOption { id, contents: contents.to_vec() });
+
+        Ok(())
+    }
+
+    pub fn get(&self, id: u16) -> Option<&[u8]> {
+        // The contents of the first option with the id
+        self.options.iter().find(|o| o.id == id).map(|o| &o.contents[..])
+    }
+
+    pub fn length(&self) -> usize {
+        let mut length = 1;
+
+        for o in &self.options {
+            length = length + 3 + o.contents.len();
+        }
+
+        length
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<Self> {
+        // The container takes up the whole of buffer
+        if buffer.is_empty() {
+            return None
+        }
+
+        let configuration_protocol = buffer[0] & 0b111;
+        let mut pos = 1;
+
+        let mut options = Vec::new();
+
+        while pos < buffer.len() {
+            if buffer.len() < pos + 3 {
+                return None
+            }
+
+            let id = NetworkEndian::read_u16(&buffer[pos..pos+2]);
+            let length = buffer[pos+2] as usize;
+            pos = pos + 3;
+
+            if buffer.len() < pos + length {
+                return None
+            }
+
+            options.push(ProtocolOption { id, contents: buffer[pos..pos+length].to_vec() });
+            pos = pos + length;
+        }
+
+        Some(
+            ProtocolConfigurationOptions {
+                configuration_protocol,
+                options,
+            }
+        )
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        // The extension bit is always set
+        buffer[pos] = 0x80 | (self.configuration_protocol & 0b111);
+        pos = pos + 1;
+
+        for o in &self.options {
+            NetworkEndian::write_u16(&mut buffer[pos..pos+2], o.id);
+            buffer[pos+2] = o.contents.len() as u8;
+            pos = pos + 3;
+
+            buffer[pos..pos+o.contents.len()].copy_from_slice(&o.contents);
+            pos = pos + o.contents.len();
+        }
+
+        pos
+    }
+}
+
+impl Dissect for ProtocolConfigurationOptions {
+    fn dissect(&self, d: &mut Dissector) {
+        d.field("Configuration Protocol", self.configuration_protocol);
+
+        for o in &self.options {
+            d.hex(&format!("Protocol or Container ID {:#06x}", o.id), &o.contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PCO: [u8; 12] = [
+        0x80, // Ext and PPP
+        0x80, 0x21, 0x04, 0x03, 0x00, 0x00, 0x04, // IPCP
+        0x00, 0x0D, 0x00, // DNS Server IPv4 Address Request
+        0x00, // This is cut off by the tests where needed
+    ];
+
+    fn pco() -> ProtocolConfigurationOptions {
+        let mut pco = ProtocolConfigurationOptions::new();
+
+        pco.push(IPCP, &[0x03, 0x00, 0x00, 0x04]).unwrap();
+        pco.push(DNS_SERVER_IPV4_ADDRESS, &[]).unwrap();
+
+        pco
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; 64];
+
+        let pos = pco().generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], PCO[..11]);
+        assert_eq!(pco().length(), 11);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ProtocolConfigurationOptions::parse(&PCO[..11]), Some(pco()));
+
+        // Only the configuration protocol
+        assert_eq!(ProtocolConfigurationOptions::parse(&PCO[..1]), Some(ProtocolConfigurationOptions::new()));
+
+        // Truncated contents and ID
+        assert!(ProtocolConfigurationOptions::parse(&PCO[..7]).is_none());
+        assert!(ProtocolConfigurationOptions::parse(&PCO[..12]).is_none());
+        assert!(ProtocolConfigurationOptions::parse(&[]).is_none());
+    }
+
+    #[test]
+    fn test_get() {
+        let pco = pco();
+
+        assert_eq!(pco.get(IPCP), Some(&[0x03, 0x00, 0x00, 0x04][..]));
+        assert_eq!(pco.get(DNS_SERVER_IPV4_ADDRESS), Some(&[][..]));
+        assert_eq!(pco.get(PAP), None);
+    }
+
+    #[test]
+    fn test_push_too_long() {
+        let mut pco = ProtocolConfigurationOptions::new();
+
+        assert!(pco.push(PAP, &[0; 256]).is_err());
+        assert!(pco.options.is_empty());
+    }
+}
@@ -0,0 +1,461 @@
+/* Rewriting of identities when replaying captured GTP traffic e.g. an anonymised load test built from a production trace.
+
+    let rules = gtp::replay::Rules::new()
+        .imsi("505013485090404", "001010000000001")?
+        .teid(0x12345678, 0x00000001)
+        .address("10.1.2.3".parse()?, "192.0.2.1".parse()?)?;
+
+    for payload in rules.replay(&gtp::pcap::read_payloads("trace.pcapng")?) {
+        socket.send_to(&payload.payload, payload.destination)?;
+    }
+
+Each rule maps a value seen in the capture to the value sent instead. Values without a rule are left unchanged.
+    IMSIs are rewritten in IMSI IEs.
+    TEIDs are rewritten in the header and in F-TEID (GTPv2) or TEID (GTPv1) IEs.
+    IP addresses are rewritten in F-TEID, IP Address and PAA IEs (GTPv2), GSN Address and End User Address IEs (GTPv1)
+    and in the source and destination of the datagram.
+IEs within the grouped IEs that carry identities (Bearer Context, PDN Connection, Remote UE Context and SCEF PDN
+Connection) are rewritten too.
+
+Like the GTP-U relay (see gtp_v1::relay) the packet is generated and then rewritten in place so every rule preserves the
+length of the value: an IMSI maps to an IMSI with the same number of digits and an address maps to one of the same
+family. The T-PDU of a G-PDU (the user's IP packet) isn't rewritten. */
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use crate::pcap::{Packet, UdpPayload};
+use crate::gtp_v1::packet::header::Header as V1Header;
+use crate::gtp_v1::packet::messages::MessageType as V1MessageType;
+use crate::gtp_v1::packet::messages::information_elements::{InformationElementTraits as V1InformationElementTraits, InformationElementType as V1InformationElementType, tv_length};
+use crate::gtp_v1::packet::messages::information_elements::imsi::InformationElement as V1Imsi;
+use crate::gtp_v2::packet::messages::information_elements::InformationElementType;
+use crate::tbcd;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    imsis: HashMap<String, String>,
+    teids: HashMap<u32, u32>,
+    addresses: HashMap<IpAddr, IpAddr>,
+}
+
+impl Rules {
+    pub fn new() -> Self {
+        Rules {
+            imsis: HashMap::new(),
+            teids: HashMap::new(),
+            addresses: HashMap::new(),
+        }
+    }
+
+    pub fn imsi(mut self, from: &str, to: &str) -> Result<Self, String> {
+        let from_digits = tbcd::parse_digits(from)?;
+        let to_digits = tbcd::parse_digits(to)?;
+
+        if from_digits.len() != to_digits.len() {
+            Err(format!("IMSI {} must map to an IMSI with the same number of digits {}", from, to))
+        }
+        else {
+            self.imsis.insert(String::from(from), String::from(to));
+            Ok(self)
+        }
+    }
+
+    pub fn teid(mut self, from: u32, to: u32) -> Self {
+        self.teids.insert(from, to);
+        self
+    }
+
+    pub fn address(mut self, from: IpAddr, to: IpAddr) -> Result<Self, String> {
+        if from.is_ipv4() != to.is_ipv4() {
+            Err(format!("Address {} must map to an address of the same family {}", from, to))
+        }
+        else {
+            self.addresses.insert(from, to);
+            Ok(self)
+        }
+    }
+
+    pub fn rewrite(&self, packet: &mut Packet) -> Result<Vec<u8>, String> {
+        // Generates packet with the rules applied
+        let mut buffer = packet.to_vec()?;
+
+        match packet {
+            Packet::GtpV1(_) => self.rewrite_gtp_v1(&mut buffer)?,
+            Packet::GtpV2(_) => self.rewrite_gtp_v2(&mut buffer)?,
+        }
+
+        Ok(buffer)
+    }
+
+    pub fn rewrite_payload(&self, payload: &UdpPayload) -> Option<UdpPayload> {
+        // Returns None if the datagram isn't a GTP packet as it can't be rewritten
+        let (mut packet, _pos) = Packet::parse(payload.gtp_port()?, &payload.payload)?;
+
+        Some(
+            UdpPayload {
+                source: self.rewrite_socket_address(payload.source),
+                destination: self.rewrite_socket_address(payload.destination),
+                payload: self.rewrite(&mut packet).ok()?,
+            }
+        )
+    }
+
+    pub fn replay(&self, payloads: &[UdpPayload]) -> Vec<UdpPayload> {
+        // The datagrams of a capture (see pcap::read_payloads) in order. Datagrams that aren't GTP packets are dropped
+        payloads.iter().filter_map(|p| self.rewrite_payload(p)).collect()
+    }
+
+    fn rewrite_socket_address(&self, address: SocketAddr) -> SocketAddr {
+        match self.addresses.get(&address.ip()) {
+            Some(ip) => SocketAddr::new(*ip, address.port()),
+            None => address,
+        }
+    }
+
+    fn rewrite_teid(&self, buffer: &mut [u8]) {
+        if let Some(teid) = self.teids.get(&NetworkEndian::read_u32(buffer)) {
+            NetworkEndian::write_u32(buffer, *teid);
+        }
+    }
+
+    fn rewrite_address(&self, buffer: &mut [u8]) {
+        // buffer is an IPv4 (4 octets) or IPv6 (16 octets) address
+        let address = match buffer.len() {
+            4 => IpAddr::V4(Ipv4Addr::new(buffer[0], buffer[1], buffer[2], buffer[3])),
+            16 => {
+                let mut octets = [0; 16];
+                octets.copy_from_slice(buffer);
+                IpAddr::V6(Ipv6Addr::from(octets))
+            },
+            _ => return,
+        };
+
+        match self.addresses.get(&address) {
+            Some(IpAddr::V4(a)) => buffer.copy_from_slice(&a.octets()),
+            Some(IpAddr::V6(a)) => buffer.copy_from_slice(&a.octets()),
+            None => (),
+        }
+    }
+
+    fn rewrite_imsi(&self, buffer: &mut [u8]) {
+        // buffer is the TBCD encoded IMSI of a GTPv2 IMSI IE
+        let imsi = match tbcd::decode(buffer) {
+            Some(digits) => tbcd::to_string(&digits),
+            None => return,
+        };
+
+        if let Some(to) = self.imsis.get(&imsi) {
+            if let Ok(digits) = tbcd::parse_digits(to) {
+                let encoded = tbcd::encode(&digits);
+
+                if encoded.len() == buffer.len() {
+                    buffer.copy_from_slice(&encoded);
+                }
+            }
+        }
+    }
+
+    fn rewrite_gtp_v1(&self, buffer: &mut [u8]) -> Result<(), String> {
+        let (header, mut pos) = V1Header::parse(buffer).ok_or("Invalid GTPv1 header")?;
+
+        self.rewrite_teid(&mut buffer[4..8]);
+
+        if header.message_type() as u8 == V1MessageType::GPDU as u8 {
+            return Ok(())
+        }
+
+        while pos < buffer.len() {
+            let length = gtp_v1_ie_length(&buffer[pos..]).ok_or("Unable to determine the length of a GTPv1 IE")?;
+            let ie = &mut buffer[pos..pos+length];
+
+            match ie[0] {
+                t if t == V1InformationElementType::Imsi as u8 => self.rewrite_gtp_v1_imsi(ie),
+                t if t == V1InformationElementType::TeidDataI as u8 => self.rewrite_teid(&mut ie[1..5]),
+                t if t == V1InformationElementType::TeidControlPlane as u8 => self.rewrite_teid(&mut ie[1..5]),
+                t if t == V1InformationElementType::TeidDataII as u8 => self.rewrite_teid(&mut ie[2..6]),
+                t if t == V1InformationElementType::GsnAddress as u8 => self.rewrite_address(&mut ie[3..]),
+                t if t == V1InformationElementType::EndUserAddress as u8 && length > 5 => {
+                    // An IPv4v6 End User Address has the IPv4 address followed by the IPv6 address
+                    if length == 5 + 4 + 16 {
+                        self.rewrite_address(&mut ie[5..9]);
+                        self.rewrite_address(&mut ie[9..]);
+                    }
+                    else {
+                        self.rewrite_address(&mut ie[5..]);
+                    }
+                },
+                _ => (),
+            }
+
+            pos = pos + length;
+        }
+
+        Ok(())
+    }
+
+    fn rewrite_gtp_v1_imsi(&self, buffer: &mut [u8]) {
+        // The GTPv1 IMSI IE has its own digit order so it is rewritten using the IE rather than tbcd
+        if let Some((imsi, _pos)) = V1Imsi::parse(buffer) {
+            if let Some(to) = self.imsis.get(&imsi.imsi()) {
+                if let Ok(ie) = V1Imsi::new(to) {
+                    ie.generate(buffer);
+                }
+            }
+        }
+    }
+
+    fn rewrite_gtp_v2(&self, buffer: &mut [u8]) -> Result<(), String> {
+        if buffer.len() < 8 {
+            return Err(String::from("Invalid GTPv2 header"))
+        }
+
+        // The TEID is only present if the T flag is set
+        let pos = if (buffer[0] >> 3) & 0b1 == 1 {
+            self.rewrite_teid(&mut buffer[4..8]);
+            12
+        }
+        else {
+            8
+        };
+
+        self.rewrite_gtp_v2_ies(&mut buffer[pos..]);
+
+        Ok(())
+    }
+
+    fn rewrite_gtp_v2_ies(&self, buffer: &mut [u8]) {
+        let mut pos = 0;
+
+        while pos + 4 <= buffer.len() {
+            let length = NetworkEndian::read_u16(&buffer[pos+1..pos+3]) as usize;
+
+            if pos + 4 + length > buffer.len() {
+                return
+            }
+
+            let ie_type = buffer[pos];
+            let value = &mut buffer[pos+4..pos+4+length];
+
+            match InformationElementType::try_from(ie_type) {
+                Ok(InformationElementType::IMSI) => self.rewrite_imsi(value),
+                Ok(InformationElementType::FTEID) => self.rewrite_fteid(value),
+                Ok(InformationElementType::IPAddress) => self.rewrite_address(value),
+                Ok(InformationElementType::PDNAddressAllocation) => self.rewrite_paa(value),
+                Ok(InformationElementType::BearerContext) |
+                Ok(InformationElementType::PDNConnection) |
+                Ok(InformationElementType::RemoteUEContext) |
+                Ok(InformationElementType::SCEFPDNConnection) => self.rewrite_gtp_v2_ies(value),
+                _ => (),
+            }
+
+            pos = pos + 4 + length;
+        }
+    }
+
+    fn rewrite_fteid(&self, buffer: &mut [u8]) {
+        // Flags (V4, V6 and Interface Type), TEID, then the IPv4 and / or IPv6 address (TS 29.274 8.22)
+        if buffer.len() < 5 {
+            return
+        }
+
+        let v4 = (buffer[0] >> 7) & 0b1 == 1;
+        let v6 = (buffer[0] >> 6) & 0b1 == 1;
+
+        self.rewrite_teid(&mut buffer[1..5]);
+
+        let mut pos = 5;
+
+        if v4 && buffer.len() >= pos + 4 {
+            self.rewrite_address(&mut buffer[pos..pos+4]);
+            pos = pos + 4;
+        }
+
+        if v6 && buffer.len() >= pos + 16 {
+            self.rewrite_address(&mut buffer[pos..pos+16]);
+        }
+    }
+
+    fn rewrite_paa(&self, buffer: &mut [u8]) {
+        // PDN Type then the address. IPv6 addresses are preceded by the prefix length (TS 29.274 8.14)
+        if buffer.is_empty() {
+            return
+        }
+
+        match buffer[0] & 0b111 {
+            1 if buffer.len() >= 5 => self.rewrite_address(&mut buffer[1..5]),
+            2 if buffer.len() >= 18 => self.rewrite_address(&mut buffer[2..18]),
+            3 if buffer.len() >= 22 => {
+                self.rewrite_address(&mut buffer[2..18]);
+                self.rewrite_address(&mut buffer[18..22]);
+            },
+            _ => (),
+        }
+    }
+}
+
+fn gtp_v1_ie_length(buffer: &[u8]) -> Option<usize> {
+    // IE types >= 128 are TLV format. The others are TV format with a length that depends on the type
+    let length = if buffer[0] >= 128 {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        NetworkEndian::read_u16(&buffer[1..3]) as usize + 3
+    }
+    else {
+        tv_length(buffer[0])?
+    };
+
+    if length <= buffer.len() { Some(length) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ascii::AsciiString;
+
+    use crate::gtp_v1;
+    use crate::gtp_v2;
+    use crate::gtp_v2::packet::messages::{Message, create_session_request};
+    use crate::gtp_v2::packet::messages::information_elements::{apn, bearer_context, bearer_qos, ebi, f_teid, rat_type};
+    use crate::gtp_v1::packet::messages::information_elements::{InformationElement as V1InformationElement, gsn_address, imsi, teid_control_plane};
+    use crate::gtp_v1::packet::messages::MessageTraits;
+
+    fn new_create_session_request() -> Packet {
+        let mut bearer_context = bearer_context::InformationElement::new(
+            ebi::InformationElement::new(5, 0).unwrap(),
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true).unwrap(), bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0).unwrap(),
+            0
+        ).unwrap();
+
+        bearer_context.s1_u_enodeb_f_teid = Some(f_teid::InformationElement::new(
+            f_teid::InterfaceType::S1UENodeBGtpU,
+            0xAABBCCDD,
+            Some(Ipv4Addr::new(10,0,0,2)),
+            None,
+            0,
+        ).unwrap());
+
+        let mut m = create_session_request::Message::new(
+            rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0).unwrap(),
+            f_teid::InformationElement::new(
+                f_teid::InterfaceType::S11MmeGtpC,
+                0x12345678,
+                Some(Ipv4Addr::new(10,0,0,1)),
+                None,
+                0,
+            ).unwrap(),
+            bearer_context,
+            apn::InformationElement::new(AsciiString::from_ascii("internet").unwrap(), 0).unwrap()
+        );
+
+        m.set_imsi("505013485090404").unwrap();
+
+        let mut p = gtp_v2::packet::Packet::new(Message::CreateSessionRequest(m));
+        p.header.enable_teid();
+        p.header.set_teid(0x12345678);
+
+        Packet::GtpV2(p)
+    }
+
+    fn new_rules() -> Rules {
+        Rules::new()
+            .imsi("505013485090404", "001010000000001").unwrap()
+            .teid(0x12345678, 0x1)
+            .teid(0xAABBCCDD, 0x2)
+            .address(IpAddr::V4(Ipv4Addr::new(10,0,0,1)), IpAddr::V4(Ipv4Addr::new(192,0,2,1))).unwrap()
+            .address(IpAddr::V4(Ipv4Addr::new(10,0,0,2)), IpAddr::V4(Ipv4Addr::new(192,0,2,2))).unwrap()
+    }
+
+    #[test]
+    fn test_rules_invalid() {
+        assert!(Rules::new().imsi("505013485090404", "00101000000001").is_err());
+        assert!(Rules::new().imsi("50501348509040a", "001010000000001").is_err());
+        assert!(Rules::new().address(IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_gtp_v2() {
+        let buffer = new_rules().rewrite(&mut new_create_session_request()).unwrap();
+
+        if let Ok((p, _pos)) = gtp_v2::packet::Packet::parse(&buffer) {
+            assert_eq!(p.header.teid(), 0x1);
+
+            if let Message::CreateSessionRequest(m) = p.message {
+                assert_eq!(format!("{}", m.imsi.unwrap()), "001010000000001");
+                assert_eq!(m.sender_f_teid_for_control_plane.teid, 0x1);
+                assert_eq!(m.sender_f_teid_for_control_plane.ipv4_address, Some(Ipv4Addr::new(192,0,2,1)));
+
+                let s1_u = m.bearer_contexts_to_be_created[0].s1_u_enodeb_f_teid.clone().unwrap();
+                assert_eq!(s1_u.teid, 0x2);
+                assert_eq!(s1_u.ipv4_address, Some(Ipv4Addr::new(192,0,2,2)));
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+
+        // Without rules the packet is unchanged
+        assert_eq!(Rules::new().rewrite(&mut new_create_session_request()).unwrap(), new_create_session_request().to_vec().unwrap());
+    }
+
+    #[test]
+    fn test_rewrite_gtp_v1() {
+        let mut m = gtp_v1::packet::messages::sgsn_context_request::Message::new();
+        m.push_ie(V1InformationElement::Imsi(imsi::InformationElement::new("505013485090404").unwrap()));
+        m.push_ie(V1InformationElement::TeidControlPlane(teid_control_plane::InformationElement::new(0x12345678)));
+        m.push_ie(V1InformationElement::GsnAddress(gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(10,0,0,1)))));
+
+        let mut p = Packet::GtpV1(gtp_v1::packet::Packet::new(gtp_v1::packet::messages::Message::SGSNContextRequest(m)));
+
+        let buffer = new_rules().rewrite(&mut p).unwrap();
+
+        if let Some((p, _pos)) = gtp_v1::packet::Packet::parse(&buffer) {
+            if let gtp_v1::packet::messages::Message::SGSNContextRequest(m) = p.message {
+                assert_eq!(m.information_elements, vec![
+                    V1InformationElement::Imsi(imsi::InformationElement::new("001010000000001").unwrap()),
+                    V1InformationElement::TeidControlPlane(teid_control_plane::InformationElement::new(0x1)),
+                    V1InformationElement::GsnAddress(gsn_address::InformationElement::new(IpAddr::V4(Ipv4Addr::new(192,0,2,1)))),
+                ]);
+            }
+            else {
+                assert!(false);
+            }
+        }
+        else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let payloads = vec![
+            UdpPayload {
+                source: "10.0.0.1:2123".parse().unwrap(),
+                destination: "10.0.0.3:2123".parse().unwrap(),
+                payload: new_create_session_request().to_vec().unwrap(),
+            },
+            // Not a GTP packet
+            UdpPayload {
+                source: "10.0.0.1:2123".parse().unwrap(),
+                destination: "10.0.0.3:2123".parse().unwrap(),
+                payload: vec![0, 1, 2, 3],
+            },
+        ];
+
+        let replayed = new_rules().replay(&payloads);
+
+        assert_eq!(replayed.len(), 1);
+        assert_eq!(replayed[0].source, "192.0.2.1:2123".parse().unwrap());
+        assert_eq!(replayed[0].destination, "10.0.0.3:2123".parse().unwrap());
+        assert_eq!(replayed[0].payload, new_rules().rewrite(&mut new_create_session_request()).unwrap());
+    }
+}
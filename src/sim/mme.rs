@@ -0,0 +1,323 @@
+/* The MME side of S11. Sessions are created, modified and deleted by calling create_session(), modify_bearer() and
+delete_session(), which queue the request for the SGW. The session moves back to Active (or is removed) when the
+response arrives. Sessions are identified by the MME's S11 TEID.
+
+handover() gives the SGW the S1-U F-TEID of the eNodeB that now serves the UE. The first call completes the attach
+(TS 23.401 5.3.2.1) and later ones switch the downlink tunnel after an S1 handover (TS 23.401 5.5.1.2.2). */
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use ascii::AsciiString;
+
+use crate::gtp_v2::packet::Packet;
+use crate::gtp_v2::packet::messages::{
+    Message,
+    create_session_request,
+    modify_bearer_request,
+    delete_session_request,
+};
+use crate::gtp_v2::packet::messages::information_elements::{
+    apn,
+    bearer_context,
+    bearer_qos,
+    cause,
+    ebi,
+    f_teid,
+    pdn_address_allocation,
+    pdn_type,
+    rat_type,
+    user_location_information,
+};
+use crate::gtp_v2::sequence::SequenceGenerator;
+
+use super::{Node, Sessions};
+
+// The EPS Bearer ID of the default bearer of every session
+pub const DEFAULT_EBI: u8 = 5;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    CreateSessionRequested,
+    Active,
+    ModifyBearerRequested,
+    DeleteSessionRequested,
+    Rejected, // The Create Session Request was rejected. See Session::cause
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub imsi: String,
+    pub state: State,
+    pub sgw_teid: Option<u32>, // From the Sender F-TEID for Control Plane of the Create Session Response
+    pub paa: Option<pdn_address_allocation::InformationElement>,
+    pub cause: Option<cause::CauseCode>, // Of the last response
+    sequence_number: u32, // Of the request waiting for a response
+}
+
+pub struct Mme {
+    address: Ipv4Addr,
+    sgw: SocketAddr,
+    sequence: SequenceGenerator,
+    sessions: Sessions<Session>,
+    outgoing: Vec<(Packet, SocketAddr)>,
+}
+
+impl Mme {
+    pub fn new(address: Ipv4Addr, sgw: SocketAddr) -> Self {
+        // address is sent in the Sender F-TEID for Control Plane
+        Mme {
+            address,
+            sgw,
+            sequence: SequenceGenerator::new(),
+            sessions: Sessions::new(),
+            outgoing: Vec::new(),
+        }
+    }
+
+    pub fn session(&self, teid: u32) -> Option<&Session> {
+        self.sessions.sessions.get(&teid)
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = (&u32, &Session)> {
+        self.sessions.sessions.iter()
+    }
+
+    pub fn create_session(&mut self, imsi: &str, apn: &str) -> Result<u32, String> {
+        // Queues a Create Session Request for a default bearer with an IPv4 address. Returns the MME's TEID for the session
+        let apn = match AsciiString::from_ascii(apn) {
+            Ok(apn) => apn::InformationElement::new(apn, 0)?,
+            Err(_) => return Err(format!("APN isn't ASCII {}", apn)),
+        };
+
+        let bearer_context = bearer_context::InformationElement::new(
+            ebi::InformationElement::new(DEFAULT_EBI, 0)?,
+            bearer_qos::InformationElement::new(bearer_qos::Arp::new(false, 9, true)?, bearer_qos::Qci::Qci9, 0, 0, 0, 0, 0)?,
+            0
+        )?;
+
+        // The TEID is needed for the Sender F-TEID so the session is added first
+        let teid = self.sessions.insert(
+            Session {
+                imsi: imsi.to_string(),
+                state: State::CreateSessionRequested,
+                sgw_teid: None,
+                paa: None,
+                cause: None,
+                sequence_number: 0,
+            }
+        );
+
+        let m = match self.new_create_session_request(teid, imsi, apn, bearer_context) {
+            Ok(m) => m,
+            Err(e) => {
+                self.sessions.sessions.remove(&teid);
+                return Err(e);
+            }
+        };
+
+        // The SGW's TEID isn't known yet so the request is sent to TEID 0
+        self.send(teid, Message::CreateSessionRequest(m), 0, State::CreateSessionRequested);
+
+        Ok(teid)
+    }
+
+    pub fn modify_bearer(&mut self, teid: u32, uli: Option<user_location_information::InformationElement>) -> Result<(), String> {
+        // Queues a Modify Bearer Request e.g. to report the new location of the UE
+        let sgw_teid = self.active(teid)?;
+
+        let mut m = modify_bearer_request::Message::new();
+        m.user_location_information = uli;
+
+        self.send(teid, Message::ModifyBearerRequest(m), sgw_teid, State::ModifyBearerRequested);
+
+        Ok(())
+    }
+
+    pub fn handover(
+        &mut self,
+        teid: u32,
+        enodeb_teid: u32,
+        enodeb_address: Ipv4Addr,
+        uli: Option<user_location_information::InformationElement>,
+    ) -> Result<(), String> {
+        // Queues a Modify Bearer Request with the new S1-U eNodeB F-TEID of the default bearer
+        let sgw_teid = self.active(teid)?;
+
+        let mut bearer_context = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(DEFAULT_EBI, 0)?, 0)?;
+        bearer_context.set_s1_u_enodeb_f_teid(enodeb_teid, Some(enodeb_address), None);
+
+        let mut m = modify_bearer_request::Message::new();
+        m.user_location_information = uli;
+        m.push_bearer_context_to_be_modified(bearer_context);
+
+        self.send(teid, Message::ModifyBearerRequest(m), sgw_teid, State::ModifyBearerRequested);
+
+        Ok(())
+    }
+
+    pub fn delete_session(&mut self, teid: u32) -> Result<(), String> {
+        let sgw_teid = self.active(teid)?;
+
+        let mut m = delete_session_request::Message::new();
+        m.linked_eps_bearer_id = Some(ebi::InformationElement::new(DEFAULT_EBI, 0)?);
+
+        self.send(teid, Message::DeleteSessionRequest(m), sgw_teid, State::DeleteSessionRequested);
+
+        Ok(())
+    }
+
+    fn new_create_session_request(
+        &self,
+        teid: u32,
+        imsi: &str,
+        apn: apn::InformationElement,
+        bearer_context: bearer_context::InformationElement,
+    ) -> Result<create_session_request::Message, String> {
+        let mut m = create_session_request::Message::new(
+            rat_type::InformationElement::new(rat_type::RATType::EUTRAN, 0)?,
+            f_teid::InformationElement::new(f_teid::InterfaceType::S11MmeGtpC, teid, Some(self.address), None, 0)?,
+            bearer_context,
+            apn,
+        );
+
+        m.set_imsi(imsi)?;
+        m.pdn_type = Some(pdn_type::InformationElement::new(pdn_type::PDNType::IPv4, 0)?);
+
+        // 0.0.0.0 asks the PGW to allocate the address
+        m.pdn_address_allocation = Some(pdn_address_allocation::InformationElement::new(pdn_type::PDNType::IPv4, Some(Ipv4Addr::UNSPECIFIED), None, 0)?);
+
+        Ok(m)
+    }
+
+    fn active(&self, teid: u32) -> Result<u32, String> {
+        // The SGW's TEID of a session that can be modified or deleted
+        match self.session(teid) {
+            Some(Session { state: State::Active, sgw_teid: Some(sgw_teid), .. }) => Ok(*sgw_teid),
+            Some(session) => Err(format!("Session {} is {:?}", teid, session.state)),
+            None => Err(format!("No session {}", teid)),
+        }
+    }
+
+    fn send(&mut self, teid: u32, message: Message, sgw_teid: u32, state: State) {
+        let mut p = Packet::new_request(message, &mut self.sequence);
+        p.header.set_teid(sgw_teid);
+
+        if let Some(session) = self.sessions.sessions.get_mut(&teid) {
+            session.state = state;
+            session.sequence_number = p.header.sequence_number();
+        }
+
+        self.outgoing.push((p, self.sgw));
+    }
+}
+
+impl Node for Mme {
+    fn handle(&mut self, packet: &Packet, _src: SocketAddr) {
+        let teid = packet.header.teid();
+
+        let session = match self.sessions.sessions.get_mut(&teid) {
+            Some(session) if session.sequence_number == packet.header.sequence_number() => session,
+            _ => {
+                debug!("Ignoring {} for unknown session {}", packet.header.message_type(), teid);
+                return
+            }
+        };
+
+        session.cause = packet.message.cause().map(|ie| ie.cause_code);
+
+        match (&packet.message, session.state) {
+            (Message::CreateSessionResponse(m), State::CreateSessionRequested) => {
+                if m.is_accepted() {
+                    session.state = State::Active;
+                    session.sgw_teid = m.sender_f_teid_for_control_plane.as_ref().map(|ie| ie.teid);
+                    session.paa = m.pdn_address_allocation.clone();
+                }
+                else {
+                    session.state = State::Rejected;
+                }
+            },
+            (Message::ModifyBearerResponse(_), State::ModifyBearerRequested) => {
+                // A rejected modification leaves the session as it was
+                session.state = State::Active;
+            },
+            (Message::DeleteSessionResponse(_), State::DeleteSessionRequested) => {
+                // Even if the SGW didn't know the session it is gone
+                self.sessions.sessions.remove(&teid);
+            },
+            (m, state) => debug!("Ignoring {} for session {} in state {:?}", m.message_name(), teid, state),
+        }
+    }
+
+    fn outgoing(&mut self) -> Vec<(Packet, SocketAddr)> {
+        core::mem::take(&mut self.outgoing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::messages::{MessageType, create_session_response};
+
+    fn new_mme() -> Mme {
+        Mme::new(Ipv4Addr::new(10, 0, 0, 1), "10.0.0.2:2123".parse().unwrap())
+    }
+
+    #[test]
+    fn test_create_session() {
+        let mut mme = new_mme();
+
+        let teid = mme.create_session("505990123456789", "internet").unwrap();
+
+        let outgoing = mme.outgoing();
+        assert_eq!(outgoing.len(), 1);
+
+        let (p, dst) = &outgoing[0];
+        assert_eq!(*dst, "10.0.0.2:2123".parse().unwrap());
+        assert_eq!(p.header.message_type(), MessageType::CreateSessionRequest);
+        assert_eq!(p.header.teid(), 0);
+
+        if let Message::CreateSessionRequest(ref m) = p.message {
+            assert_eq!(m.sender_f_teid_for_control_plane.teid, teid);
+            assert_eq!(m.sender_f_teid_for_control_plane.ipv4_address, Some(Ipv4Addr::new(10, 0, 0, 1)));
+            assert_eq!(m.imsi.as_ref().map(|ie| format!("{}", ie)), Some("505990123456789".to_string()));
+        }
+        else {
+            assert!(false);
+        }
+
+        // Nothing else is sent until the response arrives
+        assert!(mme.outgoing().is_empty());
+        assert!(mme.delete_session(teid).is_err());
+
+        assert!(mme.create_session("505990123456789", "intérnet").is_err());
+        assert!(mme.create_session("5059", "internet").is_err());
+        assert_eq!(mme.sessions().count(), 1);
+    }
+
+    #[test]
+    fn test_create_session_rejected() {
+        let mut mme = new_mme();
+
+        let teid = mme.create_session("505990123456789", "internet").unwrap();
+        let (request, _dst) = mme.outgoing().remove(0);
+
+        let mut response = Packet::new_response(
+            &request,
+            Message::CreateSessionResponse(create_session_response::Message::reject_with(cause::CauseCode::NoResourcesAvailable).unwrap())
+        );
+
+        // Responses to other sessions are ignored
+        response.header.set_teid(teid + 1);
+        mme.handle(&response, "10.0.0.2:2123".parse().unwrap());
+        assert_eq!(mme.session(teid).unwrap().state, State::CreateSessionRequested);
+
+        response.header.set_teid(teid);
+        mme.handle(&response, "10.0.0.2:2123".parse().unwrap());
+
+        let session = mme.session(teid).unwrap();
+        assert_eq!(session.state, State::Rejected);
+        assert_eq!(session.cause, Some(cause::CauseCode::NoResourcesAvailable));
+        assert!(mme.modify_bearer(teid, None).is_err());
+    }
+}
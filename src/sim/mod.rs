@@ -0,0 +1,336 @@
+/* A minimal MME, SGW and PGW for building test tools. Each node is a state machine that is given the packets it
+receives with handle() and queues the packets it wants to send. A Runner owns a node and its UDP socket so the nodes
+can be wired together over loopback (see the tests) or pointed at real peers.
+
+Only the Create Session, Modify Bearer and Delete Session procedures are simulated (TS 29.274 7.2). Retransmissions,
+timers, Echo and the user plane aren't, although the SGW queues the End Markers of an S1 handover (see
+Mme::handover and Sgw::end_markers). */
+
+use std::collections::HashMap;
+
+use std::io::ErrorKind;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use std::time::Duration;
+
+use crate::MTU;
+use crate::socket;
+use crate::gtp_v2::packet::Packet;
+use crate::gtp_v2::packet::messages::Message;
+use crate::gtp_v2::packet::messages::information_elements::cause;
+
+pub mod mme;
+pub mod sgw;
+pub mod pgw;
+
+pub trait Node {
+    fn handle(&mut self, packet: &Packet, src: SocketAddr);
+
+    // Takes the packets queued for sending and where to send them
+    fn outgoing(&mut self) -> Vec<(Packet, SocketAddr)>;
+}
+
+pub struct Runner<N: Node> {
+    socket: UdpSocket,
+    pub node: N,
+}
+
+impl<N: Node> Runner<N> {
+    pub fn new(socket: UdpSocket, node: N) -> Self {
+        Runner {
+            socket,
+            node,
+        }
+    }
+
+    pub fn bind<A: ToSocketAddrs>(addr: A, node: N) -> std::io::Result<Self> {
+        Ok(Runner::new(UdpSocket::bind(addr)?, node))
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<usize> {
+        // Sends everything the node has queued. Returns the number of packets sent
+        let outgoing = self.node.outgoing();
+        let n = outgoing.len();
+
+        for (mut packet, dst) in outgoing {
+            packet.send_to(&self.socket, dst)?;
+        }
+
+        Ok(n)
+    }
+
+    pub fn poll(&mut self, timeout: Duration) -> std::io::Result<bool> {
+        // Waits up to timeout for a single packet, gives it to the node and sends anything the node queued as a result.
+        // Returns true if a packet was received
+        let mut buffer = [0; MTU];
+
+        socket::set_poll_timeout(&self.socket, timeout)?;
+
+        match self.socket.recv_from(&mut buffer) {
+            Ok((number_of_bytes, src_addr)) => {
+                match Packet::parse(&buffer[..number_of_bytes]) {
+                    Ok((packet, _pos)) => self.node.handle(&packet, src_addr),
+                    Err(e) => {
+                        // Requests that fail to parse are rejected. Anything else is dropped
+                        if let Some(mut response) = Packet::reject_parse_error(&buffer[..number_of_bytes], &e) {
+                            response.send_to(&self.socket, src_addr)?;
+                        }
+                    }
+                }
+
+                self.flush()?;
+
+                Ok(true)
+            },
+            Err(e) if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+struct Sessions<S> {
+    // Sessions by the TEID the node allocated for them
+    next_teid: u32,
+    sessions: HashMap<u32, S>,
+}
+
+impl<S> Sessions<S> {
+    fn new() -> Self {
+        Sessions {
+            next_teid: 1,
+            sessions: HashMap::new(),
+        }
+    }
+
+    fn insert(&mut self, session: S) -> u32 {
+        // TEID 0 is only used before the peer's TEID is known so it is never allocated
+        while self.next_teid == 0 || self.sessions.contains_key(&self.next_teid) {
+            self.next_teid = self.next_teid.wrapping_add(1);
+        }
+
+        let teid = self.next_teid;
+        self.next_teid = self.next_teid.wrapping_add(1);
+
+        self.sessions.insert(teid, session);
+
+        teid
+    }
+}
+
+fn new_cause(cause_code: cause::CauseCode) -> cause::InformationElement {
+    // The instance is always 0 so this can't fail
+    cause::InformationElement::new(cause_code, cause::CauseSource::LocalNode, false, false, None, 0).unwrap()
+}
+
+fn respond(request: &Packet, message: Message, teid: u32) -> Packet {
+    // The response is sent to the peer's TEID with the Sequence Number of the request
+    let mut p = Packet::new_response(request, message);
+
+    p.header.set_teid(teid);
+
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::Ipv4Addr;
+
+    use crate::gtp_v2::paa_pool::{Ipv4Pool, PaaPool};
+    use crate::gtp_v2::packet::messages::information_elements::user_location_information::{PLMN, TAI, ECGI, UliBuilder};
+
+    use super::mme::{Mme, State};
+    use super::sgw::Sgw;
+    use super::pgw::Pgw;
+
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    struct Network {
+        mme: Runner<Mme>,
+        sgw: Runner<Sgw>,
+        pgw: Runner<Pgw>,
+    }
+
+    impl Network {
+        fn new(pool: Ipv4Pool) -> Network {
+            let pgw = Runner::bind("127.0.0.1:0", Pgw::new(Ipv4Addr::LOCALHOST, PaaPool::new(Some(pool), None))).unwrap();
+            let sgw = Runner::bind("127.0.0.1:0", Sgw::new(Ipv4Addr::LOCALHOST, pgw.local_addr().unwrap())).unwrap();
+            let mme = Runner::bind("127.0.0.1:0", Mme::new(Ipv4Addr::LOCALHOST, sgw.local_addr().unwrap())).unwrap();
+
+            Network { mme, sgw, pgw }
+        }
+
+        fn forwarded_by_sgw(&mut self) {
+            // Request from the MME -> SGW -> PGW and the response back to the MME
+            assert_eq!(self.mme.flush().unwrap(), 1);
+            assert!(self.sgw.poll(TIMEOUT).unwrap());
+            assert!(self.pgw.poll(TIMEOUT).unwrap());
+            assert!(self.sgw.poll(TIMEOUT).unwrap());
+            assert!(self.mme.poll(TIMEOUT).unwrap());
+        }
+
+        fn answered_by_sgw(&mut self) {
+            assert_eq!(self.mme.flush().unwrap(), 1);
+            assert!(self.sgw.poll(TIMEOUT).unwrap());
+            assert!(self.mme.poll(TIMEOUT).unwrap());
+        }
+    }
+
+    fn new_uli() -> crate::gtp_v2::packet::messages::information_elements::user_location_information::InformationElement {
+        UliBuilder::new()
+            .tai(TAI::new(PLMN::new(505, 99, true).unwrap(), 0x1234))
+            .ecgi(ECGI::new(PLMN::new(505, 99, true).unwrap(), 0x1234567).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_attach_modify_detach() {
+        let mut network = Network::new(Ipv4Pool::new(Ipv4Addr::new(10, 45, 0, 0), 24).unwrap());
+
+        // Create Session
+        let teid = network.mme.node.create_session("505990123456789", "internet").unwrap();
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::CreateSessionRequested);
+
+        network.forwarded_by_sgw();
+
+        let session = network.mme.node.session(teid).unwrap();
+        assert_eq!(session.state, State::Active);
+        assert_eq!(session.cause, Some(cause::CauseCode::RequestAccepted));
+        assert_eq!(session.paa.as_ref().and_then(|paa| paa.ipv4_address), Some(Ipv4Addr::new(10, 45, 0, 1)));
+
+        let sgw_teid = session.sgw_teid.unwrap();
+        let sgw_session = network.sgw.node.session(sgw_teid).unwrap();
+        assert_eq!(sgw_session.mme_teid, teid);
+        assert_eq!(network.pgw.node.session(sgw_session.pgw_teid.unwrap()).unwrap().imsi, Some("505990123456789".to_string()));
+        assert_eq!(network.pgw.node.session(sgw_session.pgw_teid.unwrap()).unwrap().apn, "internet");
+
+        // A location change is reported to the PGW
+        network.mme.node.modify_bearer(teid, Some(new_uli())).unwrap();
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::ModifyBearerRequested);
+
+        network.forwarded_by_sgw();
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::Active);
+
+        // Anything else is answered by the SGW
+        network.mme.node.modify_bearer(teid, None).unwrap();
+        network.answered_by_sgw();
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::Active);
+
+        // Delete Session
+        network.mme.node.delete_session(teid).unwrap();
+        network.forwarded_by_sgw();
+
+        assert!(network.mme.node.session(teid).is_none());
+        assert!(network.sgw.node.session(sgw_teid).is_none());
+        assert_eq!(network.pgw.node.sessions().count(), 0);
+
+        // The session is gone so can't be deleted again
+        assert!(network.mme.node.delete_session(teid).is_err());
+        assert!(network.mme.node.modify_bearer(teid, None).is_err());
+    }
+
+    #[test]
+    fn test_handover() {
+        let mut network = Network::new(Ipv4Pool::new(Ipv4Addr::new(10, 45, 0, 0), 24).unwrap());
+
+        let teid = network.mme.node.create_session("505990123456789", "internet").unwrap();
+        network.forwarded_by_sgw();
+
+        let sgw_teid = network.mme.node.session(teid).unwrap().sgw_teid.unwrap();
+
+        // The first eNodeB F-TEID completes the attach
+        network.mme.node.handover(teid, 0x100, Ipv4Addr::new(127, 0, 1, 1), None).unwrap();
+        network.answered_by_sgw();
+
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::Active);
+        assert!(network.sgw.node.end_markers().is_empty());
+
+        // A handover to another eNodeB ends the old tunnel
+        network.mme.node.handover(teid, 0x200, Ipv4Addr::new(127, 0, 1, 2), None).unwrap();
+        network.answered_by_sgw();
+
+        let s1_u = network.sgw.node.session(sgw_teid).unwrap().s1_u_enodeb_f_teids.get(&mme::DEFAULT_EBI).cloned().unwrap();
+        assert_eq!(s1_u.teid, 0x200);
+        assert_eq!(s1_u.ipv4_address, Some(Ipv4Addr::new(127, 0, 1, 2)));
+
+        let mut end_markers = network.sgw.node.end_markers();
+        assert_eq!(end_markers.len(), 1);
+
+        let (mut end_marker, dst) = end_markers.remove(0);
+        assert_eq!(dst, "127.0.1.1:2152".parse().unwrap());
+
+        if let Some((p, _pos)) = crate::gtp_v1::packet::Packet::parse(&end_marker.to_vec()) {
+            assert_eq!(p.header.message_type(), crate::gtp_v1::packet::messages::MessageType::EndMarker);
+            assert_eq!(p.header.teid(), 0x100);
+        }
+        else {
+            assert!(false);
+        }
+
+        // A handover with a new location also goes to the PGW
+        network.mme.node.handover(teid, 0x300, Ipv4Addr::new(127, 0, 1, 3), Some(new_uli())).unwrap();
+        network.forwarded_by_sgw();
+
+        assert_eq!(network.mme.node.session(teid).unwrap().state, State::Active);
+        assert_eq!(network.sgw.node.end_markers().len(), 1);
+    }
+
+    #[test]
+    fn test_create_session_rejected() {
+        // A /32 has a single address
+        let mut network = Network::new(Ipv4Pool::new(Ipv4Addr::new(10, 45, 0, 1), 32).unwrap());
+
+        let first = network.mme.node.create_session("505990123456789", "internet").unwrap();
+        network.forwarded_by_sgw();
+        assert_eq!(network.mme.node.session(first).unwrap().state, State::Active);
+
+        let second = network.mme.node.create_session("505990123456780", "internet").unwrap();
+        network.forwarded_by_sgw();
+
+        let session = network.mme.node.session(second).unwrap();
+        assert_eq!(session.state, State::Rejected);
+        assert_eq!(session.cause, Some(cause::CauseCode::AllDynamicAddressesAreOccupied));
+
+        // Only the accepted session is left in the SGW and PGW
+        assert_eq!(network.sgw.node.sessions().count(), 1);
+        assert_eq!(network.pgw.node.sessions().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let mut network = Network::new(Ipv4Pool::new(Ipv4Addr::new(10, 45, 0, 0), 24).unwrap());
+
+        // A Create Session Request without its mandatory IEs is rejected by the Runner
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.send_to(&[0b0100_1000, 32, 0, 8, 0, 0, 0, 0, 0, 0, 1, 0], network.sgw.local_addr().unwrap()).unwrap();
+
+        assert!(network.sgw.poll(TIMEOUT).unwrap());
+        assert_eq!(network.sgw.node.sessions().count(), 0);
+
+        let mut buffer = [0; MTU];
+        socket.set_read_timeout(Some(TIMEOUT)).unwrap();
+        let (number_of_bytes, _src) = socket.recv_from(&mut buffer).unwrap();
+
+        let (response, _pos) = Packet::parse(&buffer[..number_of_bytes]).unwrap();
+        assert_eq!(response.message.cause().map(|ie| ie.cause_code), Some(cause::CauseCode::MandatoryIEMissing));
+    }
+
+    #[test]
+    fn test_sessions() {
+        let mut sessions = Sessions::new();
+
+        assert_eq!(sessions.insert("a"), 1);
+        assert_eq!(sessions.insert("b"), 2);
+
+        // TEID 0 is skipped when wrapping around
+        sessions.next_teid = u32::MAX;
+        assert_eq!(sessions.insert("c"), u32::MAX);
+        assert_eq!(sessions.insert("d"), 3);
+    }
+}
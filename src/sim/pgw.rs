@@ -0,0 +1,176 @@
+/* The PGW side of S5/S8. A Create Session Request is accepted if an address can be allocated from the pool, a Modify
+Bearer Request is always accepted and a Delete Session Request releases the address. Requests for an unknown TEID are
+rejected with Context Not Found. */
+
+use std::net::{Ipv4Addr, SocketAddr};
+
+use crate::gtp_v2::paa_pool::PaaPool;
+use crate::gtp_v2::packet::Packet;
+use crate::gtp_v2::packet::messages::{
+    Message,
+    create_session_request,
+    create_session_response,
+    modify_bearer_response,
+    delete_session_response,
+};
+use crate::gtp_v2::packet::messages::information_elements::{
+    cause,
+    ebi,
+    f_teid,
+    pdn_address_allocation,
+    pdn_type::PDNType,
+};
+
+use super::{Node, Sessions, new_cause, respond};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub sgw_teid: u32,
+    pub imsi: Option<String>,
+    pub apn: String,
+    pub paa: pdn_address_allocation::InformationElement,
+    pub linked_eps_bearer_id: u8, // The EBI of the default bearer
+}
+
+pub struct Pgw {
+    address: Ipv4Addr,
+    pool: PaaPool,
+    sessions: Sessions<Session>,
+    outgoing: Vec<(Packet, SocketAddr)>,
+}
+
+impl Pgw {
+    pub fn new(address: Ipv4Addr, pool: PaaPool) -> Self {
+        // address is sent in the Sender F-TEID for Control Plane
+        Pgw {
+            address,
+            pool,
+            sessions: Sessions::new(),
+            outgoing: Vec::new(),
+        }
+    }
+
+    pub fn session(&self, teid: u32) -> Option<&Session> {
+        self.sessions.sessions.get(&teid)
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = (&u32, &Session)> {
+        self.sessions.sessions.iter()
+    }
+
+    fn create_session(&mut self, request: &Packet, m: &create_session_request::Message) -> Option<Packet> {
+        let pdn_type = m.pdn_type.as_ref().map_or(PDNType::IPv4, |ie| ie.pdn_type);
+
+        let paa = match self.pool.allocate(pdn_type, 0) {
+            Ok(paa) => paa,
+            Err(_) => return Packet::reject_with(request, cause::CauseCode::AllDynamicAddressesAreOccupied),
+        };
+
+        let bearer_contexts = m.bearer_contexts_to_be_created.clone();
+
+        let linked_eps_bearer_id = match bearer_contexts.first() {
+            Some(ie) => ie.eps_bearer_id.eps_bearer_id,
+            None => {
+                self.pool.release(&paa);
+                return Packet::reject_with(request, cause::CauseCode::MandatoryIEMissing)
+            }
+        };
+
+        let sgw_teid = m.sender_f_teid_for_control_plane.teid;
+
+        let teid = self.sessions.insert(
+            Session {
+                sgw_teid,
+                imsi: m.imsi.as_ref().map(|ie| format!("{}", ie)),
+                apn: m.apn.network_identifier().to_string(),
+                paa: paa.clone(),
+                linked_eps_bearer_id,
+            }
+        );
+
+        let mut response = create_session_response::Message::new(new_cause(cause::CauseCode::RequestAccepted), bearer_contexts);
+
+        // The instance is always 0 so this can't fail
+        response.sender_f_teid_for_control_plane = Some(f_teid::InformationElement::new(f_teid::InterfaceType::S5S8PgwGtpC, teid, Some(self.address), None, 0).unwrap());
+        response.pdn_address_allocation = Some(paa);
+
+        Some(respond(request, Message::CreateSessionResponse(response), sgw_teid))
+    }
+}
+
+impl Node for Pgw {
+    fn handle(&mut self, packet: &Packet, src: SocketAddr) {
+        let teid = packet.header.teid();
+
+        let response = match packet.message {
+            Message::CreateSessionRequest(ref m) => self.create_session(packet, m),
+            Message::ModifyBearerRequest(_) => {
+                match self.sessions.sessions.get(&teid) {
+                    Some(session) => {
+                        let mut m = modify_bearer_response::Message::new(new_cause(cause::CauseCode::RequestAccepted));
+                        m.linked_eps_bearer_id = ebi::InformationElement::new(session.linked_eps_bearer_id, 0).ok();
+
+                        Some(respond(packet, Message::ModifyBearerResponse(m), session.sgw_teid))
+                    },
+                    None => Packet::reject_with(packet, cause::CauseCode::ContextNotFound),
+                }
+            },
+            Message::DeleteSessionRequest(_) => {
+                match self.sessions.sessions.remove(&teid) {
+                    Some(session) => {
+                        self.pool.release(&session.paa);
+
+                        let m = delete_session_response::Message::new(new_cause(cause::CauseCode::RequestAccepted));
+
+                        Some(respond(packet, Message::DeleteSessionResponse(m), session.sgw_teid))
+                    },
+                    None => Packet::reject_with(packet, cause::CauseCode::ContextNotFound),
+                }
+            },
+            ref m => {
+                debug!("Ignoring {} for session {}", m.message_name(), teid);
+                None
+            },
+        };
+
+        if let Some(response) = response {
+            self.outgoing.push((response, src));
+        }
+    }
+
+    fn outgoing(&mut self) -> Vec<(Packet, SocketAddr)> {
+        core::mem::take(&mut self.outgoing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::paa_pool::Ipv4Pool;
+    use crate::gtp_v2::packet::messages::{MessageType, delete_session_request};
+
+    fn new_pgw() -> Pgw {
+        Pgw::new(Ipv4Addr::new(10, 0, 0, 3), PaaPool::new(Some(Ipv4Pool::new(Ipv4Addr::new(10, 45, 0, 0), 24).unwrap()), None))
+    }
+
+    #[test]
+    fn test_unknown_session() {
+        let mut pgw = new_pgw();
+
+        let mut request = Packet::new(Message::DeleteSessionRequest(delete_session_request::Message::new()));
+        request.header.set_teid(7);
+        request.header.set_sequence_number(0x10).unwrap();
+
+        pgw.handle(&request, "10.0.0.2:2123".parse().unwrap());
+
+        let outgoing = pgw.outgoing();
+        assert_eq!(outgoing.len(), 1);
+
+        let (p, dst) = &outgoing[0];
+        assert_eq!(*dst, "10.0.0.2:2123".parse().unwrap());
+        assert_eq!(p.header.message_type(), MessageType::DeleteSessionResponse);
+        assert_eq!(p.header.sequence_number(), 0x10);
+        assert_eq!(p.message.cause().map(|ie| ie.cause_code), Some(cause::CauseCode::ContextNotFound));
+    }
+}
@@ -0,0 +1,326 @@
+/* The SGW between S11 and S5/S8. Create Session and Delete Session Requests from the MME are forwarded to the PGW and
+the PGW's response is relayed back. A Modify Bearer Request is only forwarded if it reports the location of the UE,
+otherwise the SGW answers it. The SGW uses the same TEID for a session on S11 and S5/S8.
+
+The S1-U eNodeB F-TEIDs in the bearer contexts to be modified are recorded for each bearer. When one changes (an S1
+handover) an End Marker is queued for the old tunnel (TS 23.401 5.5.1.1.2). The user plane isn't simulated so they are
+taken with end_markers() and sent by the caller. */
+
+use std::collections::HashMap;
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+use crate::gtp_v1;
+use crate::gtp_v2::packet::Packet;
+use crate::gtp_v2::packet::messages::{Message, modify_bearer_request, modify_bearer_response};
+use crate::gtp_v2::packet::messages::information_elements::{cause, f_teid};
+use crate::gtp_v2::sequence::SequenceGenerator;
+use crate::socket::GTP_U_PORT;
+
+use super::{Node, Sessions, new_cause, respond};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    CreateSessionRequested,
+    Active,
+    ModifyBearerRequested,
+    DeleteSessionRequested,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    pub state: State,
+    pub mme: SocketAddr,
+    pub mme_teid: u32,
+    pub pgw_teid: Option<u32>, // From the Sender F-TEID for Control Plane of the Create Session Response
+    pub s1_u_enodeb_f_teids: HashMap<u8, f_teid::InformationElement>, // By EPS Bearer ID
+    request: Option<Packet>, // The MME's request that was forwarded to the PGW
+}
+
+pub struct Sgw {
+    address: Ipv4Addr,
+    pgw: SocketAddr,
+    sequence: SequenceGenerator,
+    sessions: Sessions<Session>,
+    outgoing: Vec<(Packet, SocketAddr)>,
+    end_markers: Vec<(gtp_v1::packet::Packet, SocketAddr)>,
+}
+
+impl Sgw {
+    pub fn new(address: Ipv4Addr, pgw: SocketAddr) -> Self {
+        // address is sent in the Sender F-TEID for Control Plane
+        Sgw {
+            address,
+            pgw,
+            sequence: SequenceGenerator::new(),
+            sessions: Sessions::new(),
+            outgoing: Vec::new(),
+            end_markers: Vec::new(),
+        }
+    }
+
+    pub fn session(&self, teid: u32) -> Option<&Session> {
+        self.sessions.sessions.get(&teid)
+    }
+
+    pub fn sessions(&self) -> impl Iterator<Item = (&u32, &Session)> {
+        self.sessions.sessions.iter()
+    }
+
+    pub fn end_markers(&mut self) -> Vec<(gtp_v1::packet::Packet, SocketAddr)> {
+        // Takes the End Markers queued for the old eNodeB tunnels and where to send them
+        core::mem::take(&mut self.end_markers)
+    }
+
+    fn handle_request(&mut self, request: &Packet, src: SocketAddr) {
+        let teid = request.header.teid();
+
+        if let Message::CreateSessionRequest(ref m) = request.message {
+            let teid = self.sessions.insert(
+                Session {
+                    state: State::CreateSessionRequested,
+                    mme: src,
+                    mme_teid: m.sender_f_teid_for_control_plane.teid,
+                    pgw_teid: None,
+                    s1_u_enodeb_f_teids: HashMap::new(),
+                    request: Some(request.clone()),
+                }
+            );
+
+            let mut m = m.clone();
+
+            // The sender F-TEID is the only one the instance 0 F-TEID can be so this can't fail
+            m.sender_f_teid_for_control_plane = f_teid::InformationElement::new(f_teid::InterfaceType::S5S8SgwGtpC, teid, Some(self.address), None, 0).unwrap();
+            m.unset_pgw_s5_s8_for_control_plane();
+
+            return self.forward(Message::CreateSessionRequest(m), 0);
+        }
+
+        let session = match self.sessions.sessions.get_mut(&teid) {
+            Some(session) if session.state == State::Active => session,
+            _ => {
+                // Also rejects requests for a session that is already waiting for a response from the PGW
+                if let Some(response) = Packet::reject_with(request, cause::CauseCode::ContextNotFound) {
+                    self.outgoing.push((response, src));
+                }
+                return
+            }
+        };
+
+        let pgw_teid = session.pgw_teid.unwrap_or(0);
+
+        if let Message::ModifyBearerRequest(ref m) = request.message {
+            self.end_markers.extend(switch_s1_u(session, m));
+        }
+
+        match request.message {
+            Message::ModifyBearerRequest(ref m) if m.user_location_information.is_some() => {
+                session.state = State::ModifyBearerRequested;
+                session.request = Some(request.clone());
+
+                // The S1-U F-TEIDs are only meaningful to the SGW
+                let mut m = m.clone();
+                m.sender_f_teid_for_control_plane = None;
+                m.bearer_contexts_to_be_modified.clear();
+
+                self.forward(Message::ModifyBearerRequest(m), pgw_teid);
+            },
+            Message::ModifyBearerRequest(_) => {
+                let m = modify_bearer_response::Message::new(new_cause(cause::CauseCode::RequestAccepted));
+                let mme_teid = session.mme_teid;
+
+                self.outgoing.push((respond(request, Message::ModifyBearerResponse(m), mme_teid), src));
+            },
+            Message::DeleteSessionRequest(ref m) => {
+                session.state = State::DeleteSessionRequested;
+                session.request = Some(request.clone());
+
+                let mut m = m.clone();
+                m.sender_f_teid_for_control_plane = None;
+
+                self.forward(Message::DeleteSessionRequest(m), pgw_teid);
+            },
+            ref m => debug!("Ignoring {} for session {}", m.message_name(), teid),
+        }
+    }
+
+    fn handle_response(&mut self, response: &Packet) {
+        let teid = response.header.teid();
+
+        let session = match self.sessions.sessions.get_mut(&teid) {
+            Some(session) => session,
+            None => {
+                debug!("Ignoring {} for unknown session {}", response.header.message_type(), teid);
+                return
+            }
+        };
+
+        let request = match session.request.take() {
+            Some(request) => request,
+            None => {
+                debug!("Ignoring {} for session {} with no request", response.header.message_type(), teid);
+                return
+            }
+        };
+
+        let (mme, mme_teid) = (session.mme, session.mme_teid);
+
+        let message = match (&response.message, session.state) {
+            (Message::CreateSessionResponse(m), State::CreateSessionRequested) if m.is_accepted() => {
+                session.state = State::Active;
+                session.pgw_teid = m.sender_f_teid_for_control_plane.as_ref().map(|ie| ie.teid);
+
+                // The MME sends its requests to the SGW. The PGW's F-TEID is passed on for the MME to store
+                let pgw_f_teid = m.sender_f_teid_for_control_plane.clone();
+
+                let mut m = m.clone();
+                m.set_sender_f_teid_for_control_plane(teid, Some(self.address), None);
+
+                if let Some(ie) = pgw_f_teid {
+                    m.set_pgw_s5_s8_for_control_plane(ie.teid, ie.ipv4_address, ie.ipv6_address);
+                }
+
+                Message::CreateSessionResponse(m)
+            },
+            (Message::CreateSessionResponse(_), State::CreateSessionRequested) |
+            (Message::DeleteSessionResponse(_), State::DeleteSessionRequested) => {
+                self.sessions.sessions.remove(&teid);
+                response.message.clone()
+            },
+            (Message::ModifyBearerResponse(_), State::ModifyBearerRequested) => {
+                session.state = State::Active;
+                response.message.clone()
+            },
+            (m, state) => {
+                debug!("Ignoring {} for session {} in state {:?}", m.message_name(), teid, state);
+                session.request = Some(request);
+                return
+            }
+        };
+
+        self.outgoing.push((respond(&request, message, mme_teid), mme));
+    }
+
+    fn forward(&mut self, message: Message, pgw_teid: u32) {
+        let mut p = Packet::new_request(message, &mut self.sequence);
+        p.header.set_teid(pgw_teid);
+
+        self.outgoing.push((p, self.pgw));
+    }
+}
+
+fn switch_s1_u(session: &mut Session, m: &modify_bearer_request::Message) -> Vec<(gtp_v1::packet::Packet, SocketAddr)> {
+    // Records the new S1-U eNodeB F-TEIDs. Returns an End Marker for each old tunnel that was replaced
+    let mut end_markers = Vec::new();
+
+    for bc in m.bearer_contexts_to_be_modified.iter() {
+        let f_teid = match bc.s1_u_enodeb_f_teid {
+            Some(ref ie) => ie.clone(),
+            None => continue,
+        };
+
+        if let Some(old) = session.s1_u_enodeb_f_teids.insert(bc.eps_bearer_id.eps_bearer_id, f_teid.clone()) {
+            if old == f_teid {
+                continue
+            }
+
+            let address = match (old.ipv4_address, old.ipv6_address) {
+                (Some(address), _) => IpAddr::V4(address),
+                (None, Some(address)) => IpAddr::V6(address),
+                (None, None) => continue,
+            };
+
+            end_markers.push((gtp_v1::packet::Packet::end_marker(old.teid), SocketAddr::new(address, GTP_U_PORT)));
+        }
+    }
+
+    end_markers
+}
+
+impl Node for Sgw {
+    fn handle(&mut self, packet: &Packet, src: SocketAddr) {
+        if packet.header.message_type().is_triggered() {
+            self.handle_response(packet);
+        }
+        else {
+            self.handle_request(packet, src);
+        }
+    }
+
+    fn outgoing(&mut self) -> Vec<(Packet, SocketAddr)> {
+        core::mem::take(&mut self.outgoing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::gtp_v2::packet::messages::MessageType;
+    use crate::gtp_v2::packet::messages::information_elements::{bearer_context, ebi};
+
+    fn new_sgw() -> Sgw {
+        Sgw::new(Ipv4Addr::new(10, 0, 0, 2), "10.0.0.3:2123".parse().unwrap())
+    }
+
+    #[test]
+    fn test_unknown_session() {
+        let mut sgw = new_sgw();
+
+        let mut request = Packet::new(Message::ModifyBearerRequest(modify_bearer_request::Message::new()));
+        request.header.set_teid(7);
+        request.header.set_sequence_number(0x10).unwrap();
+
+        sgw.handle(&request, "10.0.0.1:2123".parse().unwrap());
+
+        let outgoing = sgw.outgoing();
+        assert_eq!(outgoing.len(), 1);
+
+        let (p, dst) = &outgoing[0];
+        assert_eq!(*dst, "10.0.0.1:2123".parse().unwrap());
+        assert_eq!(p.header.message_type(), MessageType::ModifyBearerResponse);
+        assert_eq!(p.header.sequence_number(), 0x10);
+        assert_eq!(p.message.cause().map(|ie| ie.cause_code), Some(cause::CauseCode::ContextNotFound));
+
+        // Responses for unknown sessions are dropped
+        sgw.handle(&Packet::new_response(&request, p.message.clone()), "10.0.0.3:2123".parse().unwrap());
+        assert!(sgw.outgoing().is_empty());
+    }
+
+    fn new_request(enodeb_teid: u32, enodeb_address: Ipv4Addr) -> modify_bearer_request::Message {
+        let mut bc = bearer_context::InformationElement::without_qos(ebi::InformationElement::new(5, 0).unwrap(), 0).unwrap();
+        bc.set_s1_u_enodeb_f_teid(enodeb_teid, Some(enodeb_address), None);
+
+        let mut m = modify_bearer_request::Message::new();
+        m.push_bearer_context_to_be_modified(bc);
+
+        m
+    }
+
+    #[test]
+    fn test_switch_s1_u() {
+        let mut session = Session {
+            state: State::Active,
+            mme: "10.0.0.1:2123".parse().unwrap(),
+            mme_teid: 1,
+            pgw_teid: Some(1),
+            s1_u_enodeb_f_teids: HashMap::new(),
+            request: None,
+        };
+
+        // The first eNodeB F-TEID doesn't replace a tunnel
+        assert!(switch_s1_u(&mut session, &new_request(0x100, Ipv4Addr::new(10, 0, 1, 1))).is_empty());
+        assert!(switch_s1_u(&mut session, &new_request(0x100, Ipv4Addr::new(10, 0, 1, 1))).is_empty());
+        assert!(switch_s1_u(&mut session, &modify_bearer_request::Message::new()).is_empty());
+
+        let end_markers = switch_s1_u(&mut session, &new_request(0x200, Ipv4Addr::new(10, 0, 1, 2)));
+        assert_eq!(end_markers.len(), 1);
+
+        let (p, dst) = &end_markers[0];
+        assert_eq!(*dst, "10.0.1.1:2152".parse().unwrap());
+        assert_eq!(p.header.message_type(), gtp_v1::packet::messages::MessageType::EndMarker);
+        assert_eq!(p.header.teid(), 0x100);
+
+        assert_eq!(session.s1_u_enodeb_f_teids.get(&5).map(|ie| ie.teid), Some(0x200));
+    }
+}
@@ -0,0 +1,424 @@
+/* UDP sockets for sending and receiving GTP.
+
+Packet::send_to uses whatever socket it is given. GtpSocket binds to a chosen local address so that several nodes
+can be simulated on one host (e.g. an MME on 127.0.0.2 and an SGW on 127.0.0.3, both on port 2123). It can also set
+SO_REUSEADDR before binding and mark the traffic it sends with a DSCP.
+
+    let socket = GtpSocket::builder("10.0.0.1".parse().unwrap(), GTP_C_PORT)
+        .reuse_address(true)
+        .dscp(DSCP_CS3)
+        .bind()?;
+
+    socket.send_v2(&mut packet, "10.0.0.2:2123".parse().unwrap())?;
+
+Whether a socket bound to an IPv6 address (usually the wildcard ::) also receives IPv4, as IPv4-mapped addresses, is
+chosen with ipv6_only. Left unset it depends on the host (net.ipv6.bindv6only on Linux).
+
+    // Dual-stack: one socket for both IPv4 and IPv6 peers
+    let socket = GtpSocket::builder("::".parse().unwrap(), GTP_C_PORT)
+        .ipv6_only(false)
+        .bind()?;
+*/
+
+use std::io;
+use std::mem;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::time::Duration;
+
+use crate::gtp_v1;
+use crate::gtp_v2;
+
+pub const GTP_C_PORT: u16 = 2123;
+pub const GTP_U_PORT: u16 = 2152;
+
+// GTP-C is marked as signalling (CS3). GTP-U is left unmarked unless a DSCP is chosen for it e.g. with
+// bearer_qos::dscp_for_qci
+pub const DSCP_CS3: u8 = 24;
+pub const MAX_DSCP: u8 = 0x3F;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GtpSocketBuilder {
+    local_address: IpAddr,
+    port: u16,
+    reuse_address: bool,
+    dscp: Option<u8>,
+    ipv6_only: Option<bool>, // None leaves IPV6_V6ONLY at the host's default
+}
+
+impl GtpSocketBuilder {
+    pub fn new(local_address: IpAddr, port: u16) -> Self {
+        GtpSocketBuilder {
+            local_address,
+            port,
+            reuse_address: false,
+            dscp: None,
+            ipv6_only: None,
+        }
+    }
+
+    pub fn reuse_address(mut self, reuse_address: bool) -> Self {
+        self.reuse_address = reuse_address;
+        self
+    }
+
+    pub fn dscp(mut self, dscp: u8) -> Self {
+        self.dscp = Some(dscp);
+        self
+    }
+
+    pub fn ipv6_only(mut self, ipv6_only: bool) -> Self {
+        // Only for an IPv6 local address. false makes the socket dual-stack
+        self.ipv6_only = Some(ipv6_only);
+        self
+    }
+
+    pub fn bind(self) -> io::Result<GtpSocket> {
+        let domain = match self.local_address {
+            IpAddr::V4(_) => libc::AF_INET,
+            IpAddr::V6(_) => libc::AF_INET6,
+        };
+
+        if self.ipv6_only.is_some() && self.local_address.is_ipv4() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("IPv6 only can't be set for an IPv4 address {}", self.local_address)))
+        }
+
+        // The UdpSocket owns the fd from here so it is closed if any of the options fail
+        let socket = GtpSocket { socket: new_socket(domain)? };
+        let fd = socket.socket.as_raw_fd();
+
+        if self.reuse_address {
+            set_option(fd, libc::SOL_SOCKET, libc::SO_REUSEADDR, 1)?;
+        }
+
+        // Like SO_REUSEADDR this has to be set before binding
+        if let Some(ipv6_only) = self.ipv6_only {
+            set_option(fd, libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, ipv6_only as libc::c_int)?;
+        }
+
+        bind(fd, SocketAddr::new(self.local_address, self.port))?;
+
+        if let Some(dscp) = self.dscp {
+            socket.set_dscp(dscp)?;
+        }
+
+        Ok(socket)
+    }
+}
+
+pub struct GtpSocket {
+    socket: UdpSocket,
+}
+
+impl GtpSocket {
+    pub fn builder(local_address: IpAddr, port: u16) -> GtpSocketBuilder {
+        GtpSocketBuilder::new(local_address, port)
+    }
+
+    pub fn bind_gtp_c(local_address: IpAddr) -> io::Result<Self> {
+        // GTP-C on port 2123 marked as CS3
+        Self::builder(local_address, GTP_C_PORT).dscp(DSCP_CS3).bind()
+    }
+
+    pub fn bind_gtp_u(local_address: IpAddr) -> io::Result<Self> {
+        Self::builder(local_address, GTP_U_PORT).bind()
+    }
+
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    pub fn into_inner(self) -> UdpSocket {
+        self.socket
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    pub fn set_dscp(&self, dscp: u8) -> io::Result<()> {
+        set_dscp(&self.socket, dscp)
+    }
+
+    pub fn dscp(&self) -> io::Result<u8> {
+        dscp(&self.socket)
+    }
+
+    pub fn send_to(&self, buffer: &[u8], dst: SocketAddr) -> io::Result<usize> {
+        self.socket.send_to(buffer, dst)
+    }
+
+    pub fn send_v1(&self, packet: &mut gtp_v1::packet::Packet, dst: SocketAddr) -> io::Result<usize> {
+        self.socket.send_to(&packet.to_vec(), dst)
+    }
+
+    pub fn send_v2(&self, packet: &mut gtp_v2::packet::Packet, dst: SocketAddr) -> io::Result<usize> {
+        let buffer = packet.to_vec().map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.socket.send_to(&buffer, dst)
+    }
+
+    pub fn recv_from(&self, buffer: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        self.socket.recv_from(buffer)
+    }
+
+    pub fn set_poll_timeout(&self, timeout: Duration) -> io::Result<()> {
+        set_poll_timeout(&self.socket, timeout)
+    }
+}
+
+pub fn set_poll_timeout(socket: &UdpSocket, timeout: Duration) -> io::Result<()> {
+    // How long the next recv_from waits for a packet. A zero timeout isn't allowed so wait at least a millisecond
+    socket.set_read_timeout(Some(timeout.max(Duration::from_millis(1))))
+}
+
+pub fn set_dscp(socket: &UdpSocket, dscp: u8) -> io::Result<()> {
+    // The DSCP is the top 6 bits of the IPv4 TOS / IPv6 Traffic Class. It applies to everything sent afterwards.
+    if dscp > MAX_DSCP {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("DSCP is > {} {}", MAX_DSCP, dscp)))
+    }
+
+    let (level, name) = tos_option(socket)?;
+
+    set_option(socket.as_raw_fd(), level, name, (dscp as libc::c_int) << 2)
+}
+
+pub fn dscp(socket: &UdpSocket) -> io::Result<u8> {
+    let (level, name) = tos_option(socket)?;
+
+    Ok((get_option(socket.as_raw_fd(), level, name)? >> 2) as u8 & MAX_DSCP)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn new_socket(domain: libc::c_int) -> io::Result<UdpSocket> {
+    // Close-on-exec like the sockets std creates, so a child process can't keep the port bound
+    let fd = unsafe { libc::socket(domain, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+
+    if fd < 0 {
+        return Err(io::Error::last_os_error())
+    }
+
+    Ok(unsafe { UdpSocket::from_raw_fd(fd) })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn new_socket(domain: libc::c_int) -> io::Result<UdpSocket> {
+    // SOCK_CLOEXEC isn't available everywhere so close-on-exec is set straight after the socket is created
+    let fd = unsafe { libc::socket(domain, libc::SOCK_DGRAM, 0) };
+
+    if fd < 0 {
+        return Err(io::Error::last_os_error())
+    }
+
+    let socket = unsafe { UdpSocket::from_raw_fd(fd) };
+
+    if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error())
+    }
+
+    Ok(socket)
+}
+
+fn tos_option(socket: &UdpSocket) -> io::Result<(libc::c_int, libc::c_int)> {
+    match socket.local_addr()? {
+        SocketAddr::V4(_) => Ok((libc::IPPROTO_IP, libc::IP_TOS)),
+        SocketAddr::V6(_) => Ok((libc::IPPROTO_IPV6, libc::IPV6_TCLASS)),
+    }
+}
+
+fn set_option(fd: RawFd, level: libc::c_int, name: libc::c_int, value: libc::c_int) -> io::Result<()> {
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const libc::c_int as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t
+        )
+    };
+
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    }
+    else {
+        Ok(())
+    }
+}
+
+fn get_option(fd: RawFd, level: libc::c_int, name: libc::c_int) -> io::Result<libc::c_int> {
+    let mut value: libc::c_int = 0;
+    let mut length = mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+    let result = unsafe {
+        libc::getsockopt(fd, level, name, &mut value as *mut libc::c_int as *mut libc::c_void, &mut length)
+    };
+
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    }
+    else {
+        Ok(value)
+    }
+}
+
+fn bind(fd: RawFd, addr: SocketAddr) -> io::Result<()> {
+    // std only binds when the socket is created, which is too late for SO_REUSEADDR
+    let result = match addr {
+        SocketAddr::V4(addr) => {
+            let mut sin: libc::sockaddr_in = unsafe { mem::zeroed() };
+            sin.sin_family = libc::AF_INET as libc::sa_family_t;
+            sin.sin_port = addr.port().to_be();
+            sin.sin_addr.s_addr = u32::from_ne_bytes(addr.ip().octets());
+
+            unsafe {
+                libc::bind(fd, &sin as *const libc::sockaddr_in as *const libc::sockaddr, mem::size_of::<libc::sockaddr_in>() as libc::socklen_t)
+            }
+        },
+        SocketAddr::V6(addr) => {
+            let mut sin6: libc::sockaddr_in6 = unsafe { mem::zeroed() };
+            sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sin6.sin6_port = addr.port().to_be();
+            sin6.sin6_addr.s6_addr = addr.ip().octets();
+            sin6.sin6_scope_id = addr.scope_id();
+
+            unsafe {
+                libc::bind(fd, &sin6 as *const libc::sockaddr_in6 as *const libc::sockaddr, mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t)
+            }
+        },
+    };
+
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    }
+    else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use crate::MTU;
+    use crate::gtp_v2::packet::messages::{Message, echo_request};
+    use crate::gtp_v2::packet::messages::information_elements::{bearer_qos, recovery};
+
+    fn echo_request() -> gtp_v2::packet::Packet {
+        gtp_v2::packet::Packet::new(Message::EchoRequest(echo_request::Message::new(recovery::InformationElement::new(1, 0).unwrap())))
+    }
+
+    #[test]
+    fn test_bind_local_address() {
+        let a = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+        let b = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+
+        assert_eq!(a.local_addr().unwrap().ip(), IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+        let mut p = echo_request();
+        let sent = a.send_v2(&mut p, b.local_addr().unwrap()).unwrap();
+
+        let mut buffer = [0; MTU];
+        let (received, src) = b.recv_from(&mut buffer).unwrap();
+
+        assert_eq!(received, sent);
+        assert_eq!(src, a.local_addr().unwrap());
+        assert_eq!(gtp_v2::packet::Packet::parse(&buffer[..received]).unwrap().0.message, echo_request().message);
+    }
+
+    #[test]
+    fn test_bind_ipv6() {
+        let a = GtpSocket::builder(IpAddr::V6(Ipv6Addr::LOCALHOST), 0).dscp(DSCP_CS3).bind().unwrap();
+
+        assert_eq!(a.local_addr().unwrap().ip(), IpAddr::V6(Ipv6Addr::LOCALHOST));
+        assert_eq!(a.dscp().unwrap(), DSCP_CS3);
+    }
+
+    #[test]
+    fn test_reuse_address() {
+        let a = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).reuse_address(true).bind().unwrap();
+        let port = a.local_addr().unwrap().port();
+
+        assert!(GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), port).reuse_address(true).bind().is_ok());
+        assert!(GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), port).bind().is_err());
+    }
+
+    #[test]
+    fn test_dscp() {
+        let a = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+
+        assert_eq!(a.dscp().unwrap(), 0);
+
+        a.set_dscp(DSCP_CS3).unwrap();
+        assert_eq!(a.dscp().unwrap(), DSCP_CS3);
+
+        a.set_dscp(46).unwrap();
+        assert_eq!(a.dscp().unwrap(), 46);
+
+        assert!(a.set_dscp(64).is_err());
+    }
+
+    #[test]
+    fn test_ipv6_only() {
+        let a = GtpSocket::builder(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0).ipv6_only(true).bind().unwrap();
+        assert_eq!(get_option(a.socket().as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY).unwrap(), 1);
+
+        let b = GtpSocket::builder(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0).ipv6_only(false).bind().unwrap();
+        assert_eq!(get_option(b.socket().as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_V6ONLY).unwrap(), 0);
+
+        // The dual-stack socket receives IPv4 from an IPv4-mapped address
+        let v4 = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+        let dst = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), b.local_addr().unwrap().port());
+        let sent = v4.send_v2(&mut echo_request(), dst).unwrap();
+
+        let mut buffer = [0; MTU];
+        let (received, src) = b.recv_from(&mut buffer).unwrap();
+
+        assert_eq!(received, sent);
+        assert_eq!(src.ip(), IpAddr::V6(Ipv4Addr::LOCALHOST.to_ipv6_mapped()));
+
+        // Only for IPv6 addresses
+        let e = GtpSocket::builder(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0).ipv6_only(true).bind().err().unwrap();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_close_on_exec() {
+        let a = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+        let flags = unsafe { libc::fcntl(a.socket().as_raw_fd(), libc::F_GETFD) };
+
+        assert!(flags >= 0);
+        assert_ne!(flags & libc::FD_CLOEXEC, 0);
+
+        let b = GtpSocket::builder(IpAddr::V6(Ipv6Addr::LOCALHOST), 0).bind().unwrap();
+        let flags = unsafe { libc::fcntl(b.socket().as_raw_fd(), libc::F_GETFD) };
+
+        assert_ne!(flags & libc::FD_CLOEXEC, 0);
+    }
+
+    #[test]
+    fn test_set_poll_timeout() {
+        let a = GtpSocket::builder(IpAddr::V4(Ipv4Addr::LOCALHOST), 0).bind().unwrap();
+
+        // The kernel rounds the timeout to its clock tick so it is only checked that one is set
+        a.set_poll_timeout(Duration::from_millis(250)).unwrap();
+        assert!(a.socket().read_timeout().unwrap().is_some());
+
+        // A zero timeout would be rejected by the socket
+        assert!(a.socket().set_read_timeout(Some(Duration::ZERO)).is_err());
+        a.set_poll_timeout(Duration::ZERO).unwrap();
+        assert!(a.socket().read_timeout().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_dscp_for_qci() {
+        let a = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        set_dscp(&a, bearer_qos::dscp_for_qci(1)).unwrap();
+        assert_eq!(dscp(&a).unwrap(), bearer_qos::DSCP_EF);
+
+        set_dscp(&a, bearer_qos::dscp_for_qci(9)).unwrap();
+        assert_eq!(dscp(&a).unwrap(), bearer_qos::DSCP_BE);
+    }
+}
@@ -0,0 +1,139 @@
+/* proptest strategies for property based testing
+
+With the strategies feature enabled each protocol module has a function per IE and message that returns a
+proptest Strategy generating valid instances of it, i.e. values that new() would accept, with the instances
+that the parent message expects. The whole surface can then be checked with a round trip e.g.
+
+    proptest! {
+        #[test]
+        fn round_trip(mut packet in gtp::strategies::gtp_v2::packet()) {
+            let bytes = packet.to_vec().unwrap();
+            let (mut parsed, _) = gtp::gtp_v2::packet::Packet::parse(&bytes).unwrap();
+
+            prop_assert_eq!(parsed.to_vec().unwrap(), bytes);
+        }
+    }
+
+The crate runs the same round trips over every strategy in its own tests.
+
+Unlike the Arbitrary implementations behind the fuzzing feature these never generate values that can't be
+encoded, so a failing round trip is a bug rather than an input that the parser is allowed to reject.
+*/
+
+pub mod gtp_v1;
+pub mod gtp_v2;
+pub mod gtp_prime;
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::net::IpAddr;
+
+use ascii::AsciiString;
+use proptest::prelude::*;
+use proptest::sample::select;
+use proptest::string::string_regex;
+
+pub fn digits(min: usize, max: usize) -> impl Strategy<Value = String> {
+    // A string of between min and max decimal digits e.g. an IMSI or MSISDN
+    string_regex(&format!("[0-9]{{{},{}}}", min, max)).unwrap()
+}
+
+pub fn fqdn() -> impl Strategy<Value = AsciiString> {
+    // A dotted name made of 1 to 4 labels that are each 1 to 20 octets long
+    string_regex("[a-z0-9]([a-z0-9-]{0,18}[a-z0-9])?(\\.[a-z0-9]([a-z0-9-]{0,18}[a-z0-9])?){0,3}")
+        .unwrap()
+        .prop_map(|s| AsciiString::from_ascii(s).unwrap())
+}
+
+pub fn ip_address() -> impl Strategy<Value = IpAddr> {
+    prop_oneof![
+        any::<std::net::Ipv4Addr>().prop_map(IpAddr::V4),
+        any::<std::net::Ipv6Addr>().prop_map(IpAddr::V6),
+    ]
+}
+
+pub fn from_u8<T: TryFrom<u8> + fmt::Debug>() -> impl Strategy<Value = T> {
+    // Any of the values that T can be decoded from e.g. the variants of an IE's enum
+    let values: Vec<u8> = (0..=u8::MAX).filter(|v| T::try_from(*v).is_ok()).collect();
+
+    select(values).prop_map(|v| T::try_from(v).ok().unwrap())
+}
+
+pub fn octets(max: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=max)
+}
+
+pub fn pco() -> impl Strategy<Value = crate::pco::ProtocolConfigurationOptions> {
+    (0..=7u8, prop::collection::vec((any::<u16>(), octets(16)), 0..=4)).prop_map(|(configuration_protocol, options)| {
+        let mut pco = crate::pco::ProtocolConfigurationOptions::new();
+
+        pco.configuration_protocol = configuration_protocol;
+
+        for (id, contents) in options {
+            pco.push(id, &contents).unwrap();
+        }
+
+        pco
+    })
+}
+
+pub fn tft_component() -> impl Strategy<Value = crate::tft::Component> {
+    use crate::tft::Component;
+
+    prop_oneof![
+        (any::<std::net::Ipv4Addr>(), any::<std::net::Ipv4Addr>()).prop_map(|(a, m)| Component::Ipv4RemoteAddress(a, m)),
+        (any::<std::net::Ipv4Addr>(), any::<std::net::Ipv4Addr>()).prop_map(|(a, m)| Component::Ipv4LocalAddress(a, m)),
+        (any::<std::net::Ipv6Addr>(), any::<std::net::Ipv6Addr>()).prop_map(|(a, m)| Component::Ipv6RemoteAddress(a, m)),
+        (any::<std::net::Ipv6Addr>(), 0..=128u8).prop_map(|(a, l)| Component::Ipv6RemoteAddressPrefix(a, l)),
+        (any::<std::net::Ipv6Addr>(), 0..=128u8).prop_map(|(a, l)| Component::Ipv6LocalAddressPrefix(a, l)),
+        any::<u8>().prop_map(Component::ProtocolIdentifier),
+        any::<u16>().prop_map(Component::SingleLocalPort),
+        (any::<u16>(), any::<u16>()).prop_map(|(low, high)| Component::LocalPortRange(low, high)),
+        any::<u16>().prop_map(Component::SingleRemotePort),
+        (any::<u16>(), any::<u16>()).prop_map(|(low, high)| Component::RemotePortRange(low, high)),
+        any::<u32>().prop_map(Component::SecurityParameterIndex),
+        (any::<u8>(), any::<u8>()).prop_map(|(tos, mask)| Component::TypeOfService(tos, mask)),
+        (0..=0xFFFFFu32).prop_map(Component::FlowLabel),
+        any::<[u8; 6]>().prop_map(Component::DestinationMacAddress),
+        any::<[u8; 6]>().prop_map(Component::SourceMacAddress),
+        (0..=0xFFFu16).prop_map(Component::CTagVid),
+        (0..=0xFFFu16).prop_map(Component::STagVid),
+        (0..=0xFu8).prop_map(Component::CTagPcpDei),
+        (0..=0xFu8).prop_map(Component::STagPcpDei),
+        any::<u16>().prop_map(Component::Ethertype),
+    ]
+}
+
+pub fn packet_filter() -> impl Strategy<Value = crate::tft::PacketFilter> {
+    use crate::tft::Direction;
+
+    (0..=0xFu8, 0..=3u8, any::<u8>(), prop::collection::vec(tft_component(), 0..=4)).prop_map(|(id, direction, precedence, components)| {
+        crate::tft::PacketFilter::new(id, Direction::from(direction), precedence, components).unwrap()
+    })
+}
+
+pub fn tft() -> impl Strategy<Value = crate::tft::TrafficFlowTemplate> {
+    use crate::tft::{Operation, TrafficFlowTemplate};
+
+    let filters = || prop::collection::vec(packet_filter(), 1..=4);
+
+    (
+        prop_oneof![
+            filters().prop_map(Operation::CreateNew),
+            Just(Operation::DeleteExisting),
+            filters().prop_map(Operation::AddFilters),
+            filters().prop_map(Operation::ReplaceFilters),
+            prop::collection::vec(0..=0xFu8, 1..=4).prop_map(Operation::DeleteFilters),
+            Just(Operation::NoOperation),
+        ],
+        prop::collection::vec((any::<u8>(), octets(16)), 0..=2),
+    ).prop_map(|(operation, parameters)| {
+        let mut tft = TrafficFlowTemplate::new(operation).unwrap();
+
+        for (id, contents) in parameters {
+            tft.push_parameter(id, &contents).unwrap();
+        }
+
+        tft
+    })
+}
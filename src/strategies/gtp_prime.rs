@@ -0,0 +1,42 @@
+pub mod information_elements;
+pub mod messages;
+
+use proptest::prelude::*;
+
+use crate::gtp_prime::packet::Packet;
+
+pub fn packet() -> impl Strategy<Value = Packet> {
+    (messages::message(), 0..=2u8, any::<bool>(), any::<u16>()).prop_map(|(message, version, short_header, sequence_number)| {
+        let mut p = Packet::new(message);
+
+        p.header.set_version(version).unwrap();
+        if short_header {
+            p.header.enable_short_header();
+        }
+        else {
+            p.header.disable_short_header();
+        }
+        p.header.set_sequence_number(sequence_number);
+
+        p
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_packet_round_trip(mut packet in packet()) {
+            prop_assert_eq!(packet.verify_round_trip(), Ok(()));
+
+            let bytes = packet.to_vec();
+
+            let (mut parsed, pos) = Packet::parse(&bytes).unwrap();
+            prop_assert_eq!(pos, bytes.len());
+
+            prop_assert_eq!(parsed.to_vec(), bytes);
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::convert::TryFrom;
+
+use proptest::prelude::*;
+
+use crate::gtp_prime::packet::messages::information_elements::{
+    InformationElement,
+    InformationElementType,
+    cause,
+    packet_transfer_command::{self, PacketTransferCommand},
+    data_record_packet,
+    sequence_numbers,
+};
+
+use crate::strategies;
+
+pub fn cause() -> impl Strategy<Value = cause::InformationElement> {
+    any::<u8>().prop_map(cause::InformationElement::new)
+}
+
+pub fn packet_transfer_command() -> impl Strategy<Value = packet_transfer_command::InformationElement> {
+    (1..=4u8).prop_map(|command| packet_transfer_command::InformationElement::new(PacketTransferCommand::try_from(command).unwrap()))
+}
+
+pub fn sequence_numbers(ie_type: InformationElementType) -> impl Strategy<Value = sequence_numbers::InformationElement> {
+    // ie_type must be one of the lists of Sequence Numbers
+    prop::collection::vec(any::<u16>(), 0..=8)
+        .prop_map(move |sequence_numbers| sequence_numbers::InformationElement::new(ie_type, sequence_numbers).unwrap())
+}
+
+pub fn data_record_packet() -> impl Strategy<Value = data_record_packet::InformationElement> {
+    (any::<u8>(), any::<u16>(), prop::collection::vec(strategies::octets(64), 0..=4)).prop_map(|(format, version, data_records)| {
+        let mut ie = data_record_packet::InformationElement::new(format, version);
+
+        for data_record in data_records {
+            ie.push_data_record(&data_record).unwrap();
+        }
+
+        ie
+    })
+}
+
+pub fn information_element() -> impl Strategy<Value = InformationElement> {
+    prop_oneof![
+        cause().prop_map(InformationElement::Cause),
+        packet_transfer_command().prop_map(InformationElement::PacketTransferCommand),
+        sequence_numbers(InformationElementType::SequenceNumbersOfReleasedPackets).prop_map(InformationElement::SequenceNumbers),
+        sequence_numbers(InformationElementType::SequenceNumbersOfCancelledPackets).prop_map(InformationElement::SequenceNumbers),
+        sequence_numbers(InformationElementType::RequestsResponded).prop_map(InformationElement::SequenceNumbers),
+        data_record_packet().prop_map(InformationElement::DataRecordPacket),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtp_prime::packet::messages::information_elements::InformationElementTraits;
+
+    proptest! {
+        #[test]
+        fn test_information_element_round_trip(ie in information_element()) {
+            let mut buffer = vec![0; ie.length() as usize];
+
+            let pos = ie.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = InformationElement::parse(&buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+
+            let mut regenerated = vec![0; parsed.length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, ie);
+        }
+    }
+}
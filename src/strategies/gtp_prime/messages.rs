@@ -0,0 +1,58 @@
+use proptest::prelude::*;
+
+use crate::gtp_prime::packet::messages::{
+    Message,
+    data_record_transfer_request,
+    data_record_transfer_response,
+};
+
+use super::information_elements as ies;
+
+pub fn data_record_transfer_request() -> impl Strategy<Value = data_record_transfer_request::Message> {
+    // Each Packet Transfer Command carries the IE it is about
+    prop_oneof![
+        (ies::data_record_packet(), any::<bool>())
+            .prop_map(|(data_record_packet, possibly_duplicated)| data_record_transfer_request::Message::send(data_record_packet, possibly_duplicated)),
+        prop::collection::vec(any::<u16>(), 0..=8)
+            .prop_map(|sequence_numbers| data_record_transfer_request::Message::release(sequence_numbers).unwrap()),
+        prop::collection::vec(any::<u16>(), 0..=8)
+            .prop_map(|sequence_numbers| data_record_transfer_request::Message::cancel(sequence_numbers).unwrap()),
+    ]
+}
+
+pub fn data_record_transfer_response() -> impl Strategy<Value = data_record_transfer_response::Message> {
+    (any::<u8>(), prop::collection::vec(any::<u16>(), 0..=8))
+        .prop_map(|(cause, requests_responded)| data_record_transfer_response::Message::new(cause, requests_responded).unwrap())
+}
+
+pub fn message() -> impl Strategy<Value = Message> {
+    prop_oneof![
+        data_record_transfer_request().prop_map(Message::DataRecordTransferRequest),
+        data_record_transfer_response().prop_map(Message::DataRecordTransferResponse),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtp_prime::packet::messages::MessageTraits;
+
+    proptest! {
+        #[test]
+        fn test_message_round_trip(message in message()) {
+            let mut buffer = vec![0; message.length() as usize];
+
+            let pos = message.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = Message::parse(message.message_type(), &buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+
+            let mut regenerated = vec![0; parsed.length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, message);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+pub mod information_elements;
+pub mod messages;
+
+use proptest::prelude::*;
+use proptest::option;
+
+use crate::gtp_v1::packet::Packet;
+
+pub fn packet() -> impl Strategy<Value = Packet> {
+    (messages::message(), any::<u32>(), option::of(any::<u16>())).prop_map(|(message, teid, sequence_number)| {
+        let mut p = Packet::new(message);
+
+        p.header.set_teid(teid);
+        if let Some(sequence_number) = sequence_number {
+            p.header.enable_sequence_number();
+            p.header.set_sequence_number(sequence_number);
+        }
+
+        p
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_packet_round_trip(mut packet in packet()) {
+            prop_assert_eq!(packet.verify_round_trip(), Ok(()));
+
+            let bytes = packet.to_vec();
+
+            let (mut parsed, pos) = Packet::parse(&bytes).unwrap();
+            prop_assert_eq!(pos, bytes.len());
+
+            prop_assert_eq!(parsed.to_vec(), bytes);
+        }
+    }
+}
@@ -0,0 +1,319 @@
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use proptest::prelude::*;
+use proptest::option;
+
+use crate::gtp_v1::packet::messages::information_elements::{
+    InformationElement,
+    cause,
+    imsi,
+    rai,
+    recovery,
+    teid_data_i,
+    teid_control_plane,
+    teid_data_ii,
+    nsapi,
+    ranap_cause,
+    end_user_address,
+    gsn_address,
+    qos_profile,
+    mm_context,
+    target_identification,
+    utran_transparent_container,
+    private_extension,
+};
+
+use crate::strategies;
+use crate::strategies::gtp_v2::information_elements::plmn;
+
+pub fn cause() -> impl Strategy<Value = cause::InformationElement> {
+    any::<u8>().prop_map(|cause| cause::InformationElement::new(cause::CauseCode::from(cause)))
+}
+
+pub fn imsi() -> impl Strategy<Value = imsi::InformationElement> {
+    strategies::digits(15, 15).prop_map(|imsi| imsi::InformationElement::new(&imsi).unwrap())
+}
+
+pub fn rai() -> impl Strategy<Value = rai::InformationElement> {
+    (plmn(), any::<u16>(), any::<u8>()).prop_map(|(plmn, lac, rac)| rai::InformationElement::new(plmn, lac, rac))
+}
+
+pub fn recovery() -> impl Strategy<Value = recovery::InformationElement> {
+    any::<u8>().prop_map(recovery::InformationElement::new)
+}
+
+pub fn teid_data_i() -> impl Strategy<Value = teid_data_i::InformationElement> {
+    any::<u32>().prop_map(teid_data_i::InformationElement::new)
+}
+
+pub fn teid_control_plane() -> impl Strategy<Value = teid_control_plane::InformationElement> {
+    any::<u32>().prop_map(teid_control_plane::InformationElement::new)
+}
+
+pub fn teid_data_ii() -> impl Strategy<Value = teid_data_ii::InformationElement> {
+    (0..=0xFu8, any::<u32>()).prop_map(|(nsapi, teid)| teid_data_ii::InformationElement::new(nsapi, teid).unwrap())
+}
+
+pub fn nsapi() -> impl Strategy<Value = nsapi::InformationElement> {
+    (0..=0xFu8).prop_map(|nsapi| nsapi::InformationElement::new(nsapi).unwrap())
+}
+
+pub fn ranap_cause() -> impl Strategy<Value = ranap_cause::InformationElement> {
+    any::<u8>().prop_map(ranap_cause::InformationElement::new)
+}
+
+pub fn end_user_address() -> impl Strategy<Value = end_user_address::InformationElement> {
+    prop_oneof![
+        any::<Ipv4Addr>().prop_map(|a| end_user_address::InformationElement::new(end_user_address::PdpType::IPv4, Some(a), None).unwrap()),
+        any::<Ipv6Addr>().prop_map(|a| end_user_address::InformationElement::new(end_user_address::PdpType::IPv6, None, Some(a)).unwrap()),
+        (any::<Ipv4Addr>(), any::<Ipv6Addr>()).prop_map(|(a, b)| end_user_address::InformationElement::new(end_user_address::PdpType::IPv4v6, Some(a), Some(b)).unwrap()),
+        Just(end_user_address::InformationElement::dynamic(end_user_address::PdpType::IPv4v6)),
+        Just(end_user_address::InformationElement::dynamic(end_user_address::PdpType::Ppp)),
+    ]
+}
+
+pub fn gsn_address() -> impl Strategy<Value = gsn_address::InformationElement> {
+    strategies::ip_address().prop_map(gsn_address::InformationElement::new)
+}
+
+fn bitrate() -> impl Strategy<Value = Option<u32>> {
+    // Bitrates (kbps) that are exactly one of the steps of TS 24.008 10.5.6.5 so they decode to the same value
+    option::of(prop_oneof![
+        0..=63u32,
+        (0..=63u32).prop_map(|k| 64 + k * 8),
+        (0..=126u32).prop_map(|k| 576 + k * 64),
+        (1..=74u32).prop_map(|k| 8600 + k * 100),
+        (1..=112u32).prop_map(|k| 16_000 + k * 1000),
+        (1..=64u32).prop_map(|k| 128_000 + k * 2000),
+        (1..=61u32).prop_map(|k| 256_000 + k * 4000),
+        (1..=100u32).prop_map(|k| 500_000 + k * 10_000),
+        (1..=85u32).prop_map(|k| 1_500_000 + k * 100_000),
+    ])
+}
+
+fn maximum_sdu_size() -> impl Strategy<Value = u16> {
+    prop_oneof![
+        (0..=150u16).prop_map(|k| k * 10),
+        Just(1502),
+        Just(1510),
+        Just(qos_profile::MAX_SDU_SIZE),
+    ]
+}
+
+fn transfer_delay() -> impl Strategy<Value = u16> {
+    prop_oneof![
+        (0..=15u16).prop_map(|k| k * 10),
+        (0..=15u16).prop_map(|k| 200 + k * 50),
+        (0..=30u16).prop_map(|k| 1000 + k * 100),
+    ]
+}
+
+pub fn r99() -> impl Strategy<Value = qos_profile::R99> {
+    (
+        (
+            strategies::from_u8(),
+            strategies::from_u8(),
+            strategies::from_u8(),
+            maximum_sdu_size(),
+            bitrate(),
+            bitrate(),
+        ),
+        (
+            strategies::from_u8(),
+            strategies::from_u8(),
+            transfer_delay(),
+            strategies::from_u8(),
+            bitrate(),
+            bitrate(),
+        ),
+    ).prop_map(|(
+        (traffic_class, delivery_order, delivery_of_erroneus_sdus, maximum_sdu_size, maximum_uplink_bitrate, maximum_downlink_bitrate),
+        (residual_ber, sdu_error_ratio, transfer_delay, traffic_handling_priority, guaranteed_uplink_bitrate, guaranteed_downlink_bitrate),
+    )| {
+        qos_profile::R99 {
+            traffic_class,
+            delivery_order,
+            delivery_of_erroneus_sdus,
+            maximum_sdu_size,
+            maximum_uplink_bitrate,
+            maximum_downlink_bitrate,
+            residual_ber,
+            sdu_error_ratio,
+            transfer_delay,
+            traffic_handling_priority,
+            guaranteed_uplink_bitrate,
+            guaranteed_downlink_bitrate,
+        }
+    })
+}
+
+pub fn r5() -> impl Strategy<Value = qos_profile::R5> {
+    (
+        prop_oneof![
+            Just(qos_profile::SignallingIndication::NotOptimisedForSignallingTraffic),
+            Just(qos_profile::SignallingIndication::OptimisedForSignallingTraffic),
+        ],
+        strategies::from_u8(),
+    ).prop_map(|(signalling_indication, source_statistics_descriptor)| {
+        qos_profile::R5 {
+            signalling_indication,
+            source_statistics_descriptor,
+        }
+    })
+}
+
+pub fn qos_profile() -> impl Strategy<Value = qos_profile::InformationElement> {
+    // The R5 attributes can only be present with the R99 attributes. They are always present when the
+    // extended bitrate octets are as those octets follow them.
+    (
+        any::<u8>(),
+        strategies::from_u8(),
+        strategies::from_u8(),
+        strategies::from_u8(),
+        strategies::from_u8(),
+        strategies::from_u8(),
+        option::of((r99(), option::of(r5()))),
+    ).prop_map(|(arp, delay_class, reliability_class, peak_throughput, precedence_class, mean_throughput, later_releases)| {
+        let mut ie = qos_profile::InformationElement::new(arp, delay_class, reliability_class, peak_throughput, precedence_class, mean_throughput);
+
+        if let Some((r99, r5)) = later_releases {
+            let extended = [
+                r99.maximum_uplink_bitrate,
+                r99.maximum_downlink_bitrate,
+                r99.guaranteed_uplink_bitrate,
+                r99.guaranteed_downlink_bitrate,
+            ].iter().flatten().any(|kbps| *kbps > qos_profile::MAX_BITRATE_KBPS);
+
+            ie.set_r99(r99).unwrap();
+
+            let r5 = match r5 {
+                None if extended => Some(qos_profile::R5 {
+                    signalling_indication: qos_profile::SignallingIndication::NotOptimisedForSignallingTraffic,
+                    source_statistics_descriptor: qos_profile::SourceStatisticsDescriptor::UnknownOrSpare,
+                }),
+                r5 => r5,
+            };
+
+            if let Some(r5) = r5 {
+                ie.set_r5(r5).unwrap();
+            }
+        }
+
+        ie
+    })
+}
+
+pub fn quintuplet() -> impl Strategy<Value = mm_context::Quintuplet> {
+    (any::<[u8; 16]>(), strategies::octets(16), any::<[u8; 16]>(), any::<[u8; 16]>(), strategies::octets(16))
+        .prop_map(|(rand, xres, ck, ik, autn)| mm_context::Quintuplet { rand, xres, ck, ik, autn })
+}
+
+pub fn security_context() -> impl Strategy<Value = mm_context::SecurityContext> {
+    // Up to 5 vectors with 3 bit CKSN/KSI and Used Cipher values
+    let triplet = (any::<[u8; 16]>(), any::<[u8; 4]>(), any::<[u8; 8]>())
+        .prop_map(|(rand, sres, kc)| mm_context::Triplet { rand, sres, kc });
+
+    prop_oneof![
+        (0..=7u8, 0..=7u8, any::<[u8; 8]>(), prop::collection::vec(triplet, 0..=5))
+            .prop_map(|(cksn, used_cipher, kc, triplets)| mm_context::SecurityContext::GsmKeyAndTriplets { cksn, used_cipher, kc, triplets }),
+        (0..=7u8, 0..=7u8, any::<[u8; 8]>(), prop::collection::vec(quintuplet(), 0..=5))
+            .prop_map(|(cksn, used_cipher, kc, quintuplets)| mm_context::SecurityContext::GsmKeyAndQuintuplets { cksn, used_cipher, kc, quintuplets }),
+        (0..=7u8, any::<[u8; 16]>(), any::<[u8; 16]>(), prop::collection::vec(quintuplet(), 0..=5))
+            .prop_map(|(ksi, ck, ik, quintuplets)| mm_context::SecurityContext::UmtsKeysAndQuintuplets { ksi, ck, ik, quintuplets }),
+        (0..=7u8, 0..=7u8, any::<[u8; 16]>(), any::<[u8; 16]>(), prop::collection::vec(quintuplet(), 0..=5))
+            .prop_map(|(ksi, used_cipher, ck, ik, quintuplets)| mm_context::SecurityContext::UsedCipherUmtsKeysAndQuintuplets { ksi, used_cipher, ck, ik, quintuplets }),
+    ]
+}
+
+pub fn mm_context() -> impl Strategy<Value = mm_context::InformationElement> {
+    (
+        security_context(),
+        any::<u16>(),
+        any::<bool>(),
+        option::of(0..=7u8),
+        strategies::octets(16),
+        strategies::octets(64),
+        option::of(any::<bool>()),
+    ).prop_map(|(security_context, drx_parameter, gupii, used_gprs_integrity_protection_algorithm, ms_network_capability, container, nrsrna)| {
+        // GUPII, UGIPAI and the Used GPRS integrity protection algorithm are spare with GSM keys
+        let gsm_keys = matches!(
+            security_context.security_mode(),
+            mm_context::SecurityMode::GsmKeyAndTriplets | mm_context::SecurityMode::GsmKeyAndQuintuplets
+        );
+
+        let mut ie = mm_context::InformationElement::new(security_context, drx_parameter).unwrap();
+
+        if !gsm_keys {
+            ie.gupii = gupii;
+            ie.used_gprs_integrity_protection_algorithm = used_gprs_integrity_protection_algorithm;
+        }
+        ie.ms_network_capability = ms_network_capability;
+        ie.container = container;
+        ie.nrsrna = nrsrna;
+
+        ie
+    })
+}
+
+pub fn target_identification() -> impl Strategy<Value = target_identification::InformationElement> {
+    (plmn(), any::<u16>(), any::<u8>(), 0..=0xFFFu16, option::of(4096..=u16::MAX)).prop_map(|(plmn, lac, rac, rnc_id, extended_rnc_id)| {
+        target_identification::InformationElement::new(plmn, lac, rac, rnc_id, extended_rnc_id).unwrap()
+    })
+}
+
+pub fn utran_transparent_container() -> impl Strategy<Value = utran_transparent_container::InformationElement> {
+    strategies::octets(64).prop_map(|field| utran_transparent_container::InformationElement::new(&field).unwrap())
+}
+
+pub fn private_extension() -> impl Strategy<Value = private_extension::InformationElement> {
+    (any::<u16>(), strategies::octets(16)).prop_map(|(extension_identifier, extension_value)| {
+        private_extension::InformationElement::new(extension_identifier, &extension_value).unwrap()
+    })
+}
+
+pub fn information_element() -> impl Strategy<Value = InformationElement> {
+    prop_oneof![
+        cause().prop_map(InformationElement::Cause),
+        imsi().prop_map(InformationElement::Imsi),
+        rai().prop_map(InformationElement::Rai),
+        recovery().prop_map(InformationElement::Recovery),
+        teid_data_i().prop_map(InformationElement::TeidDataI),
+        teid_control_plane().prop_map(InformationElement::TeidControlPlane),
+        teid_data_ii().prop_map(InformationElement::TeidDataII),
+        nsapi().prop_map(InformationElement::Nsapi),
+        ranap_cause().prop_map(InformationElement::RanapCause),
+        end_user_address().prop_map(InformationElement::EndUserAddress),
+        mm_context().prop_map(InformationElement::MmContext),
+        gsn_address().prop_map(InformationElement::GsnAddress),
+        qos_profile().prop_map(InformationElement::QoSProfile),
+        target_identification().prop_map(InformationElement::TargetIdentification),
+        utran_transparent_container().prop_map(InformationElement::UtranTransparentContainer),
+        private_extension().prop_map(InformationElement::PrivateExtension),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtp_v1::packet::messages::information_elements::InformationElementTraits;
+
+    proptest! {
+        #[test]
+        fn test_information_element_round_trip(ie in information_element()) {
+            let mut buffer = vec![0; ie.length() as usize];
+
+            let pos = ie.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = InformationElement::parse(&buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+
+            let mut regenerated = vec![0; parsed.length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, ie);
+        }
+    }
+}
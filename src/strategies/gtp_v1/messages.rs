@@ -0,0 +1,160 @@
+use proptest::prelude::*;
+use proptest::option;
+
+use crate::gtp_v1::packet::messages::{
+    Message,
+    MessageTraits,
+    echo_request,
+    echo_response,
+    create_pdp_context_response,
+    error_indication,
+    sgsn_context_request,
+    sgsn_context_response,
+    sgsn_context_acknowledge,
+    forward_relocation_request,
+    forward_relocation_response,
+    forward_relocation_complete,
+    end_marker,
+    g_pdu,
+};
+
+use crate::gtp_v1::packet::messages::information_elements::{InformationElement, InformationElementTraits, cause};
+
+use crate::strategies;
+use super::information_elements as ies;
+
+/*
+    The messages that carry a list of IEs get between 0 and 4 of any of the supported IEs.
+    The list is sorted by IE type as generate() expects it to be.
+
+    The Create PDP Context Request isn't generated as it can't be parsed yet.
+*/
+
+fn information_elements() -> impl Strategy<Value = Vec<InformationElement>> {
+    prop::collection::vec(ies::information_element(), 0..=4).prop_map(|mut information_elements| {
+        information_elements.sort_by_key(|ie| ie.information_element_type() as u8);
+        information_elements
+    })
+}
+
+fn with_information_elements<M: MessageTraits + std::fmt::Debug>(new: fn() -> M) -> impl Strategy<Value = M> {
+    information_elements().prop_map(move |information_elements| {
+        let mut m = new();
+
+        for ie in information_elements {
+            m.push_ie(ie);
+        }
+
+        m
+    })
+}
+
+pub fn echo_request() -> impl Strategy<Value = echo_request::Message> {
+    option::of(ies::private_extension()).prop_map(|private_extension| {
+        let mut m = echo_request::Message::new();
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn echo_response() -> impl Strategy<Value = echo_response::Message> {
+    (ies::recovery(), option::of(ies::private_extension())).prop_map(|(recovery, private_extension)| {
+        let mut m = echo_response::Message::new(recovery);
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn create_pdp_context_response() -> impl Strategy<Value = create_pdp_context_response::Message> {
+    // The Cause has the lowest IE type so the IEs stay sorted
+    (any::<u8>(), information_elements()).prop_map(|(cause, information_elements)| {
+        let mut m = create_pdp_context_response::Message::new(cause::CauseCode::from(cause));
+
+        for ie in information_elements {
+            m.push_ie(ie);
+        }
+
+        m
+    })
+}
+
+pub fn error_indication() -> impl Strategy<Value = error_indication::Message> {
+    (any::<u32>(), strategies::ip_address()).prop_map(|(teid, peer_address)| error_indication::Message::new(teid, peer_address))
+}
+
+pub fn sgsn_context_request() -> impl Strategy<Value = sgsn_context_request::Message> {
+    with_information_elements(sgsn_context_request::Message::new)
+}
+
+pub fn sgsn_context_response() -> impl Strategy<Value = sgsn_context_response::Message> {
+    with_information_elements(sgsn_context_response::Message::new)
+}
+
+pub fn sgsn_context_acknowledge() -> impl Strategy<Value = sgsn_context_acknowledge::Message> {
+    with_information_elements(sgsn_context_acknowledge::Message::new)
+}
+
+pub fn forward_relocation_request() -> impl Strategy<Value = forward_relocation_request::Message> {
+    with_information_elements(forward_relocation_request::Message::new)
+}
+
+pub fn forward_relocation_response() -> impl Strategy<Value = forward_relocation_response::Message> {
+    with_information_elements(forward_relocation_response::Message::new)
+}
+
+pub fn forward_relocation_complete() -> impl Strategy<Value = forward_relocation_complete::Message> {
+    with_information_elements(forward_relocation_complete::Message::new)
+}
+
+pub fn end_marker() -> impl Strategy<Value = end_marker::Message> {
+    option::of(ies::private_extension()).prop_map(|private_extension| {
+        let mut m = end_marker::Message::new();
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn g_pdu() -> impl Strategy<Value = g_pdu::Message> {
+    strategies::octets(128).prop_map(|t_pdu| g_pdu::Message::new(&t_pdu).unwrap())
+}
+
+pub fn message() -> impl Strategy<Value = Message> {
+    prop_oneof![
+        echo_request().prop_map(Message::EchoRequest),
+        echo_response().prop_map(Message::EchoResponse),
+        create_pdp_context_response().prop_map(Message::CreatePDPContextResponse),
+        error_indication().prop_map(Message::ErrorIndication),
+        sgsn_context_request().prop_map(Message::SGSNContextRequest),
+        sgsn_context_response().prop_map(Message::SGSNContextResponse),
+        sgsn_context_acknowledge().prop_map(Message::SGSNContextAcknowledge),
+        forward_relocation_request().prop_map(Message::ForwardRelocationRequest),
+        forward_relocation_response().prop_map(Message::ForwardRelocationResponse),
+        forward_relocation_complete().prop_map(Message::ForwardRelocationComplete),
+        end_marker().prop_map(Message::EndMarker),
+        g_pdu().prop_map(Message::GPDU),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_message_round_trip(message in message()) {
+            let mut buffer = vec![0; message.length() as usize];
+
+            let pos = message.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = Message::parse(message.message_type(), &buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+
+            let mut regenerated = vec![0; parsed.length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, message);
+        }
+    }
+}
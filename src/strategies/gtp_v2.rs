@@ -0,0 +1,39 @@
+pub mod information_elements;
+pub mod messages;
+
+use proptest::prelude::*;
+
+use crate::gtp_v2::packet::Packet;
+
+pub fn packet() -> impl Strategy<Value = Packet> {
+    // The T flag is set from the message type by Packet::new(). Sequence Numbers are 3 octets.
+    (messages::message(), any::<u32>(), 0..=0xFFFFFFu32).prop_map(|(message, teid, sequence_number)| {
+        let mut p = Packet::new(message);
+
+        if p.header.teid_enabled() {
+            p.header.set_teid(teid);
+        }
+        p.header.set_sequence_number(sequence_number).unwrap();
+
+        p
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_packet_round_trip(mut packet in packet()) {
+            prop_assert_eq!(packet.verify_round_trip(), Ok(()));
+
+            let bytes = packet.to_vec().unwrap();
+
+            let (mut parsed, pos) = Packet::parse(&bytes).unwrap();
+            prop_assert_eq!(pos, bytes.len());
+
+            prop_assert_eq!(parsed.to_vec().unwrap(), bytes);
+        }
+    }
+}
@@ -0,0 +1,623 @@
+use std::convert::TryFrom;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use proptest::prelude::*;
+use proptest::option;
+use proptest::sample::select;
+
+use crate::gtp_v2::packet::messages::information_elements::{
+    InformationElement,
+    InformationElementType,
+    ambr,
+    apn,
+    apn_and_relative_capacity,
+    apn_rate_control_status,
+    apn_restriction,
+    arp,
+    bearer_context,
+    bearer_qos,
+    cause,
+    change_reporting_action,
+    change_to_report_flags,
+    charging_characteristics,
+    charging_id,
+    cmi,
+    complete_request_message,
+    csg_id,
+    csg_information_reporting_action,
+    delay_value,
+    ebi,
+    epc_timer,
+    epco,
+    extended_trace_information,
+    f_teid,
+    flow_qos,
+    fq_csid,
+    fqdn,
+    guti,
+    imsi,
+    indication,
+    ip_address,
+    ldn,
+    mei,
+    mm_context,
+    msisdn,
+    node_features,
+    node_identifier,
+    paging_and_service_information,
+    pdn_address_allocation,
+    pdn_connection,
+    pdn_type,
+    port_number,
+    private_extension,
+    pti,
+    rat_type,
+    recovery,
+    remote_ue_context,
+    remote_ue_ip_information,
+    remote_user_id,
+    selection_mode,
+    serving_network,
+    serving_plmn_rate_control,
+    stn_sr,
+    tad,
+    throttling,
+    timer,
+    trace_information,
+    uci,
+    ue_time_zone,
+    user_location_information,
+};
+
+use user_location_information::PLMN;
+
+use crate::strategies;
+
+// The largest bitrate (kbps) that can be encoded in a Bearer QoS or Flow QoS IE
+const MAX_BITRATE: u64 = bearer_qos::MAX_BITRATE_KBPS;
+
+pub fn instance() -> impl Strategy<Value = u8> {
+    0..=0xFu8
+}
+
+pub fn information_element_type() -> impl Strategy<Value = InformationElementType> {
+    strategies::from_u8()
+}
+
+pub fn plmn() -> impl Strategy<Value = PLMN> {
+    prop_oneof![
+        (0..=999u16, 0..=99u16).prop_map(|(mcc, mnc)| PLMN::new(mcc, mnc, false).unwrap()),
+        (0..=999u16, 0..=999u16).prop_map(|(mcc, mnc)| PLMN::new(mcc, mnc, true).unwrap()),
+    ]
+}
+
+pub fn timer() -> impl Strategy<Value = timer::Timer> {
+    (strategies::from_u8(), 0..=timer::MAX_TIMER_VALUE)
+        .prop_map(|(unit, value)| timer::Timer::new(unit, value).unwrap())
+}
+
+pub fn recovery(instance: u8) -> impl Strategy<Value = recovery::InformationElement> {
+    any::<u8>().prop_map(move |restart_counter| recovery::InformationElement::new(restart_counter, instance).unwrap())
+}
+
+pub fn rat_type(instance: u8) -> impl Strategy<Value = rat_type::InformationElement> {
+    strategies::from_u8().prop_map(move |rat_type| rat_type::InformationElement::new(rat_type, instance).unwrap())
+}
+
+pub fn f_teid(instance: u8) -> impl Strategy<Value = f_teid::InformationElement> {
+    (
+        // Interface Type is only 6 bits
+        (0..=0x3Fu8).prop_map(f_teid::InterfaceType::from),
+        any::<u32>(),
+        option::of(any::<Ipv4Addr>()),
+        option::of(any::<Ipv6Addr>()),
+    ).prop_map(move |(interface_type, teid, ipv4_address, ipv6_address)| {
+        f_teid::InformationElement::new(interface_type, teid, ipv4_address, ipv6_address, instance).unwrap()
+    }).boxed()
+}
+
+pub fn apn(instance: u8) -> impl Strategy<Value = apn::InformationElement> {
+    strategies::fqdn().prop_map(move |apn| apn::InformationElement::new(apn, instance).unwrap())
+}
+
+pub fn ebi(instance: u8) -> impl Strategy<Value = ebi::InformationElement> {
+    (0..=0xFu8).prop_map(move |eps_bearer_id| ebi::InformationElement::new(eps_bearer_id, instance).unwrap())
+}
+
+pub fn bearer_qos(instance: u8) -> impl Strategy<Value = bearer_qos::InformationElement> {
+    (
+        (any::<bool>(), 0..=0xFu8, any::<bool>(), any::<u8>()),
+        (0..=MAX_BITRATE, 0..=MAX_BITRATE, 0..=MAX_BITRATE, 0..=MAX_BITRATE),
+    ).prop_map(move |((pci, pl, pvi, qci), (max_ul, max_dl, gbr_ul, gbr_dl))| {
+        let arp = bearer_qos::Arp::new(pci, pl, pvi).unwrap();
+        bearer_qos::InformationElement::new(arp, qci.into(), max_ul, max_dl, gbr_ul, gbr_dl, instance).unwrap()
+    })
+}
+
+pub fn bearer_context(instance: u8) -> impl Strategy<Value = bearer_context::InformationElement> {
+    // Each of the optional F-TEIDs uses the instance that the Bearer Context parses it from (0 = S1-U eNodeB ... 7 = S11-U MME)
+    (
+        ebi(0),
+        bearer_qos(0),
+        (
+            option::of(f_teid(0)),
+            option::of(f_teid(1)),
+            option::of(f_teid(2)),
+            option::of(f_teid(3)),
+        ),
+        (
+            option::of(f_teid(4)),
+            option::of(f_teid(5)),
+            option::of(f_teid(6)),
+            option::of(f_teid(7)),
+        ),
+        option::of(charging_id(0)),
+        option::of(cause(0)),
+    ).prop_map(move |(eps_bearer_id, bearer_level_qos, (s1_u, s4_u, s5_s8_u_sgw, s5_s8_u_pgw), (s12, s2b_u, s2a_u, s11_u), charging_id, cause)| {
+        let mut ie = bearer_context::InformationElement::new(eps_bearer_id, bearer_level_qos, instance).unwrap();
+
+        ie.cause = cause;
+        ie.s1_u_enodeb_f_teid = s1_u;
+        ie.s4_u_sgsn_f_teid = s4_u;
+        ie.s5_s8_u_sgw_f_teid = s5_s8_u_sgw;
+        ie.s5_s8_u_pgw_f_teid = s5_s8_u_pgw;
+        ie.s12_rnc_f_teid = s12;
+        ie.s2b_u_epdg_f_teid = s2b_u;
+        ie.s2a_u_twan_f_teid = s2a_u;
+        ie.s11_u_mme_f_teid = s11_u;
+        ie.charging_id = charging_id;
+
+        ie
+    }).boxed()
+}
+
+pub fn imsi(instance: u8) -> impl Strategy<Value = imsi::InformationElement> {
+    strategies::digits(imsi::MIN_DIGITS, imsi::MAX_DIGITS)
+        .prop_map(move |imsi| imsi::InformationElement::new(&imsi, instance).unwrap())
+}
+
+pub fn pdn_type(instance: u8) -> impl Strategy<Value = pdn_type::InformationElement> {
+    // PDN Type is only 3 bits
+    (0..=0b111u8).prop_map(move |pdn_type| pdn_type::InformationElement::new(pdn_type::PDNType::from(pdn_type), instance).unwrap())
+}
+
+pub fn pdn_address_allocation(instance: u8) -> impl Strategy<Value = pdn_address_allocation::InformationElement> {
+    // Only the addresses required by the PDN Type are present
+    (
+        select(vec![
+            pdn_type::PDNType::IPv4,
+            pdn_type::PDNType::IPv6,
+            pdn_type::PDNType::IPv4v6,
+            pdn_type::PDNType::NonIp,
+        ]),
+        any::<Ipv4Addr>(),
+        (any::<Ipv6Addr>(), 0..=128u8),
+    ).prop_map(move |(pdn_type, ipv4_address, ipv6_address_and_prefix)| {
+        let (ipv4_address, ipv6_address_and_prefix) = match pdn_type {
+            pdn_type::PDNType::IPv4 => (Some(ipv4_address), None),
+            pdn_type::PDNType::IPv6 => (None, Some(ipv6_address_and_prefix)),
+            pdn_type::PDNType::IPv4v6 => (Some(ipv4_address), Some(ipv6_address_and_prefix)),
+            _ => (None, None),
+        };
+
+        pdn_address_allocation::InformationElement::new(pdn_type, ipv4_address, ipv6_address_and_prefix, instance).unwrap()
+    })
+}
+
+pub fn msisdn(instance: u8) -> impl Strategy<Value = msisdn::InformationElement> {
+    strategies::digits(1, msisdn::MAX_DIGITS)
+        .prop_map(move |msisdn| msisdn::InformationElement::new(&msisdn, instance).unwrap())
+}
+
+pub fn user_location_information(instance: u8) -> impl Strategy<Value = user_location_information::InformationElement> {
+    use user_location_information::{CGI, SAI, RAI, TAI, ECGI, LAI, MeNBID, EMeNBID};
+
+    (
+        (
+            option::of((plmn(), any::<u16>(), any::<u16>()).prop_map(|(plmn, lac, ci)| CGI::new(plmn, lac, ci))),
+            option::of((plmn(), any::<u16>(), any::<u16>()).prop_map(|(plmn, lac, sac)| SAI::new(plmn, lac, sac))),
+            option::of((plmn(), any::<u16>(), any::<u16>()).prop_map(|(plmn, lac, rac)| RAI::new(plmn, lac, rac))),
+            option::of((plmn(), any::<u16>()).prop_map(|(plmn, tac)| TAI::new(plmn, tac))),
+        ),
+        (
+            option::of((plmn(), 0..=0xFFFFFFFu32).prop_map(|(plmn, eci)| ECGI::new(plmn, eci).unwrap())),
+            option::of((plmn(), any::<u16>()).prop_map(|(plmn, lac)| LAI::new(plmn, lac))),
+            option::of((plmn(), 0..=0xFFFFFu32).prop_map(|(plmn, menbid)| MeNBID::new(plmn, menbid).unwrap())),
+            option::of((plmn(), 0..=0x1FFFFFu32).prop_map(|(plmn, menbid)| EMeNBID::new(plmn, menbid).unwrap())),
+        ),
+    ).prop_map(move |((cgi, sai, rai, tai), (ecgi, lai, menbid, emenbid))| {
+        user_location_information::InformationElement::new(cgi, sai, rai, tai, ecgi, lai, menbid, emenbid, instance).unwrap()
+    }).boxed()
+}
+
+pub fn mei(instance: u8) -> impl Strategy<Value = mei::InformationElement> {
+    (prop::array::uniform15(0..=9u8), option::of(0..=9u8))
+        .prop_map(move |(imei, sv)| mei::InformationElement::new(imei, sv, instance).unwrap())
+}
+
+pub fn serving_network(instance: u8) -> impl Strategy<Value = serving_network::InformationElement> {
+    plmn().prop_map(move |plmn| serving_network::InformationElement::new(plmn, instance).unwrap())
+}
+
+pub fn selection_mode(instance: u8) -> impl Strategy<Value = selection_mode::InformationElement> {
+    strategies::from_u8()
+        .prop_map(move |selection_mode| selection_mode::InformationElement::new(selection_mode, instance).unwrap())
+}
+
+pub fn apn_restriction(instance: u8) -> impl Strategy<Value = apn_restriction::InformationElement> {
+    strategies::from_u8()
+        .prop_map(move |restriction| apn_restriction::InformationElement::new(restriction, instance).unwrap())
+}
+
+pub fn ambr(instance: u8) -> impl Strategy<Value = ambr::InformationElement> {
+    (any::<u32>(), any::<u32>())
+        .prop_map(move |(uplink, downlink)| ambr::InformationElement::new(uplink, downlink, instance).unwrap())
+}
+
+pub fn ue_time_zone(instance: u8) -> impl Strategy<Value = ue_time_zone::InformationElement> {
+    (any::<u8>(), strategies::from_u8())
+        .prop_map(move |(offset, dst_adjustment)| ue_time_zone::InformationElement::new(offset, dst_adjustment, instance).unwrap())
+}
+
+pub fn charging_characteristics(instance: u8) -> impl Strategy<Value = charging_characteristics::InformationElement> {
+    any::<u16>().prop_map(move |cc| charging_characteristics::InformationElement::new(cc, instance).unwrap())
+}
+
+pub fn cause(instance: u8) -> impl Strategy<Value = cause::InformationElement> {
+    (
+        strategies::from_u8(),
+        strategies::from_u8(),
+        any::<bool>(),
+        any::<bool>(),
+        option::of((information_element_type(), 0..=0xFu8)),
+    ).prop_map(move |(cause_code, cause_source, bce, pce, offending_ie)| {
+        cause::InformationElement::new(cause_code, cause_source, bce, pce, offending_ie, instance).unwrap()
+    }).boxed()
+}
+
+pub fn ldn(instance: u8) -> impl Strategy<Value = ldn::InformationElement> {
+    strategies::fqdn().prop_map(move |ldn| ldn::InformationElement::new(ldn, instance).unwrap())
+}
+
+pub fn fq_csid(instance: u8) -> impl Strategy<Value = fq_csid::InformationElement> {
+    let node_id = prop_oneof![
+        any::<Ipv4Addr>().prop_map(fq_csid::NodeId::IPv4),
+        any::<Ipv6Addr>().prop_map(fq_csid::NodeId::IPv6),
+        (0..=999u16, 0..=999u16, 0..=0xFFFu16).prop_map(|(mcc, mnc, id)| fq_csid::NodeId::new_global(mcc, mnc, id).unwrap()),
+    ];
+
+    (node_id, prop::collection::vec(any::<u16>(), 0..=0xF))
+        .prop_map(move |(node_id, csids)| fq_csid::InformationElement::new(node_id, csids, instance).unwrap())
+}
+
+pub fn charging_id(instance: u8) -> impl Strategy<Value = charging_id::InformationElement> {
+    any::<u32>().prop_map(move |id| charging_id::InformationElement::new(id, instance).unwrap())
+}
+
+pub fn fqdn(instance: u8) -> impl Strategy<Value = fqdn::InformationElement> {
+    strategies::fqdn().prop_map(move |fqdn| fqdn::InformationElement::new(fqdn, instance).unwrap())
+}
+
+pub fn node_identifier(instance: u8) -> impl Strategy<Value = node_identifier::InformationElement> {
+    (strategies::fqdn(), strategies::fqdn())
+        .prop_map(move |(name, realm)| node_identifier::InformationElement::new(name, realm, instance).unwrap())
+}
+
+pub fn arp(instance: u8) -> impl Strategy<Value = arp::InformationElement> {
+    (any::<bool>(), 0..=0xFu8, any::<bool>())
+        .prop_map(move |(pci, pl, pvi)| arp::InformationElement::new(pci, pl, pvi, instance).unwrap())
+}
+
+pub fn guti(instance: u8) -> impl Strategy<Value = guti::InformationElement> {
+    (plmn(), any::<u16>(), any::<u8>(), any::<u32>()).prop_map(move |(plmn, mme_group_id, mme_code, m_tmsi)| {
+        guti::InformationElement::new(plmn, mme_group_id, mme_code, m_tmsi, instance).unwrap()
+    })
+}
+
+pub fn complete_request_message(instance: u8) -> impl Strategy<Value = complete_request_message::InformationElement> {
+    (strategies::from_u8(), strategies::octets(64)).prop_map(move |(message_type, message)| {
+        complete_request_message::InformationElement::new(message_type, &message, instance).unwrap()
+    })
+}
+
+pub fn mm_context(instance: u8) -> impl Strategy<Value = mm_context::InformationElement> {
+    // The MM Context is carried as opaque octets in any of the MM Context IE types
+    let mm_context_types: Vec<InformationElementType> = (0..=u8::MAX)
+        .filter_map(|v| InformationElementType::try_from(v).ok())
+        .filter(|t| mm_context::InformationElement::is_mm_context(*t))
+        .collect();
+
+    (
+        select(mm_context_types),
+        strategies::octets(64),
+    ).prop_map(move |(mm_context_type, mm_context)| {
+        mm_context::InformationElement::new(mm_context_type, &mm_context, instance).unwrap()
+    })
+}
+
+pub fn pdn_connection(instance: u8) -> impl Strategy<Value = pdn_connection::InformationElement> {
+    // All of the IEs in a PDN Connection are instance 0 except the IPv6 Address
+    (
+        (apn(0), ebi(0), f_teid(0), prop::collection::vec(bearer_context(0), 1..=2), ambr(0)),
+        (
+            option::of(apn_restriction(0)),
+            option::of(selection_mode(0)),
+            option::of(any::<Ipv4Addr>().prop_map(|a| ip_address::InformationElement::new(IpAddr::V4(a), 0).unwrap())),
+            option::of(any::<Ipv6Addr>().prop_map(|a| ip_address::InformationElement::new(IpAddr::V6(a), 1).unwrap())),
+            option::of(fqdn(0)),
+            option::of(charging_characteristics(0)),
+        ),
+    ).prop_map(move |((apn, linked_eps_bearer_id, pgw_f_teid, bearer_contexts, apn_ambr), (apn_restriction, selection_mode, ipv4_address, ipv6_address, pgw_node_name, charging_characteristics))| {
+        let mut ie = pdn_connection::InformationElement::new(apn, linked_eps_bearer_id, pgw_f_teid, bearer_contexts, apn_ambr, instance).unwrap();
+
+        ie.apn_restriction = apn_restriction;
+        ie.selection_mode = selection_mode;
+        ie.ipv4_address = ipv4_address;
+        ie.ipv6_address = ipv6_address;
+        ie.pgw_node_name = pgw_node_name;
+        ie.charging_characteristics = charging_characteristics;
+
+        ie
+    }).boxed()
+}
+
+pub fn node_features(instance: u8) -> impl Strategy<Value = node_features::InformationElement> {
+    any::<u8>().prop_map(move |features| node_features::InformationElement::new(features, instance).unwrap())
+}
+
+pub fn change_reporting_action(instance: u8) -> impl Strategy<Value = change_reporting_action::InformationElement> {
+    strategies::from_u8()
+        .prop_map(move |action| change_reporting_action::InformationElement::new(action, instance).unwrap())
+}
+
+pub fn csg_information_reporting_action(instance: u8) -> impl Strategy<Value = csg_information_reporting_action::InformationElement> {
+    any::<u8>().prop_map(move |action| csg_information_reporting_action::InformationElement::new(action, instance).unwrap())
+}
+
+pub fn serving_plmn_rate_control(instance: u8) -> impl Strategy<Value = serving_plmn_rate_control::InformationElement> {
+    (any::<u16>(), any::<u16>()).prop_map(move |(uplink, downlink)| {
+        serving_plmn_rate_control::InformationElement::new(uplink, downlink, instance).unwrap()
+    })
+}
+
+pub fn apn_rate_control_status(instance: u8) -> impl Strategy<Value = apn_rate_control_status::InformationElement> {
+    (any::<u32>(), any::<u32>(), any::<u32>(), any::<u64>()).prop_map(move |(uplink, exception_reports, downlink, validity_time)| {
+        apn_rate_control_status::InformationElement::new(uplink, exception_reports, downlink, validity_time, instance).unwrap()
+    })
+}
+
+pub fn apn_and_relative_capacity(instance: u8) -> impl Strategy<Value = apn_and_relative_capacity::InformationElement> {
+    (apn_and_relative_capacity::MIN_RELATIVE_CAPACITY..=apn_and_relative_capacity::MAX_RELATIVE_CAPACITY, strategies::fqdn())
+        .prop_map(move |(relative_capacity, apn)| apn_and_relative_capacity::InformationElement::new(relative_capacity, apn, instance).unwrap())
+}
+
+pub fn trace_information(instance: u8) -> impl Strategy<Value = trace_information::InformationElement> {
+    (
+        plmn(),
+        0..=trace_information::MAX_TRACE_ID,
+        any::<[u8; 9]>(),
+        any::<[u8; 2]>(),
+        any::<u8>(),
+        any::<[u8; 12]>(),
+        strategies::ip_address(),
+    ).prop_map(move |(plmn, trace_id, triggering_events, ne_types, depth, interfaces, tce)| {
+        trace_information::InformationElement::new(plmn, trace_id, triggering_events, ne_types, depth, interfaces, tce, instance).unwrap()
+    }).boxed()
+}
+
+pub fn extended_trace_information(instance: u8) -> impl Strategy<Value = extended_trace_information::InformationElement> {
+    (
+        plmn(),
+        0..=trace_information::MAX_TRACE_ID,
+        strategies::octets(16),
+        strategies::octets(16),
+        any::<u8>(),
+        strategies::octets(16),
+        strategies::ip_address(),
+    ).prop_map(move |(plmn, trace_id, triggering_events, ne_types, depth, interfaces, tce)| {
+        extended_trace_information::InformationElement::new(plmn, trace_id, triggering_events, ne_types, depth, interfaces, tce, instance).unwrap()
+    }).boxed()
+}
+
+pub fn flow_qos(instance: u8) -> impl Strategy<Value = flow_qos::InformationElement> {
+    (any::<u8>(), 0..=MAX_BITRATE, 0..=MAX_BITRATE, 0..=MAX_BITRATE, 0..=MAX_BITRATE).prop_map(move |(qci, max_ul, max_dl, gbr_ul, gbr_dl)| {
+        flow_qos::InformationElement::new(qci, max_ul, max_dl, gbr_ul, gbr_dl, instance).unwrap()
+    })
+}
+
+pub fn tad(instance: u8) -> impl Strategy<Value = tad::InformationElement> {
+    strategies::tft().prop_map(move |tad| tad::InformationElement::new(tad, instance).unwrap())
+}
+
+pub fn pti(instance: u8) -> impl Strategy<Value = pti::InformationElement> {
+    any::<u8>().prop_map(move |pti| pti::InformationElement::new(pti, instance).unwrap())
+}
+
+pub fn delay_value(instance: u8) -> impl Strategy<Value = delay_value::InformationElement> {
+    any::<u8>().prop_map(move |delay| delay_value::InformationElement::new(delay, instance).unwrap())
+}
+
+pub fn throttling(instance: u8) -> impl Strategy<Value = throttling::InformationElement> {
+    (timer(), 0..=throttling::MAX_THROTTLING_FACTOR)
+        .prop_map(move |(delay, factor)| throttling::InformationElement::new(delay, factor, instance).unwrap())
+}
+
+pub fn epc_timer(instance: u8) -> impl Strategy<Value = epc_timer::InformationElement> {
+    timer().prop_map(move |timer| epc_timer::InformationElement::new(timer, instance).unwrap())
+}
+
+pub fn ip_address(instance: u8) -> impl Strategy<Value = ip_address::InformationElement> {
+    strategies::ip_address().prop_map(move |address| ip_address::InformationElement::new(address, instance).unwrap())
+}
+
+pub fn port_number(instance: u8) -> impl Strategy<Value = port_number::InformationElement> {
+    any::<u16>().prop_map(move |port_number| port_number::InformationElement::new(port_number, instance).unwrap())
+}
+
+pub fn stn_sr(instance: u8) -> impl Strategy<Value = stn_sr::InformationElement> {
+    strategies::digits(1, stn_sr::MAX_DIGITS)
+        .prop_map(move |stn_sr| stn_sr::InformationElement::new(&stn_sr, instance).unwrap())
+}
+
+pub fn csg_id(instance: u8) -> impl Strategy<Value = csg_id::InformationElement> {
+    (0..=csg_id::MAX_CSG_ID).prop_map(move |csg_id| csg_id::InformationElement::new(csg_id, instance).unwrap())
+}
+
+pub fn cmi(instance: u8) -> impl Strategy<Value = cmi::InformationElement> {
+    any::<bool>().prop_map(move |member| cmi::InformationElement::new(member, instance).unwrap())
+}
+
+pub fn indication(instance: u8) -> impl Strategy<Value = indication::InformationElement> {
+    prop::collection::vec(any::<u8>(), 0..8).prop_map(move |flags| {
+        let mut ie = indication::InformationElement::new(instance).unwrap();
+        ie.flags = flags;
+        ie
+    })
+}
+
+pub fn paging_and_service_information(instance: u8) -> impl Strategy<Value = paging_and_service_information::InformationElement> {
+    (0..=0xFu8, option::of(0..=paging_and_service_information::MAX_PAGING_POLICY_INDICATION))
+        .prop_map(move |(eps_bearer_id, ppi)| paging_and_service_information::InformationElement::new(eps_bearer_id, ppi, instance).unwrap())
+}
+
+pub fn change_to_report_flags(instance: u8) -> impl Strategy<Value = change_to_report_flags::InformationElement> {
+    (any::<bool>(), any::<bool>())
+        .prop_map(move |(sncr, tzcr)| change_to_report_flags::InformationElement::new(sncr, tzcr, instance).unwrap())
+}
+
+pub fn private_extension(instance: u8) -> impl Strategy<Value = private_extension::InformationElement> {
+    (any::<u16>(), strategies::octets(16)).prop_map(move |(enterprise_id, proprietary_value)| {
+        private_extension::InformationElement::new(enterprise_id, &proprietary_value, instance).unwrap()
+    })
+}
+
+pub fn uci(instance: u8) -> impl Strategy<Value = uci::InformationElement> {
+    (
+        plmn(),
+        0..=csg_id::MAX_CSG_ID,
+        // Access mode is only 2 bits
+        (0..=0b11u8).prop_map(uci::AccessMode::from),
+        any::<bool>(),
+        any::<bool>(),
+    ).prop_map(move |(plmn, csg_id, access_mode, lcsg, cmi)| {
+        uci::InformationElement::new(plmn, csg_id, access_mode, lcsg, cmi, instance).unwrap()
+    })
+}
+
+pub fn remote_user_id(instance: u8) -> impl Strategy<Value = remote_user_id::InformationElement> {
+    (
+        strategies::digits(imsi::MIN_DIGITS, imsi::MAX_DIGITS),
+        option::of(strategies::digits(1, msisdn::MAX_DIGITS)),
+        option::of(strategies::digits(remote_user_id::MIN_IMEI_DIGITS, remote_user_id::MAX_IMEI_DIGITS)),
+    ).prop_map(move |(imsi, msisdn, imei)| {
+        remote_user_id::InformationElement::new(&imsi, msisdn.as_deref(), imei.as_deref(), instance).unwrap()
+    })
+}
+
+pub fn remote_ue_ip_information(instance: u8) -> impl Strategy<Value = remote_ue_ip_information::InformationElement> {
+    strategies::octets(17).prop_map(move |information| remote_ue_ip_information::InformationElement::new(&information, instance).unwrap())
+}
+
+pub fn epco(instance: u8) -> impl Strategy<Value = epco::InformationElement> {
+    strategies::pco().prop_map(move |pco| epco::InformationElement::new(pco, instance).unwrap())
+}
+
+pub fn remote_ue_context(instance: u8) -> impl Strategy<Value = remote_ue_context::InformationElement> {
+    (remote_user_id(0), option::of(remote_ue_ip_information(0))).prop_map(move |(remote_user_id, remote_ue_ip_information)| {
+        let mut ie = remote_ue_context::InformationElement::new(remote_user_id, instance).unwrap();
+
+        ie.remote_ue_ip_information = remote_ue_ip_information;
+
+        ie
+    })
+}
+
+pub fn information_element() -> impl Strategy<Value = InformationElement> {
+    // Any IE with any instance
+    instance().prop_flat_map(|i| prop_oneof![
+        recovery(i).prop_map(InformationElement::Recovery),
+        rat_type(i).prop_map(InformationElement::RATType),
+        f_teid(i).prop_map(InformationElement::FTEID),
+        apn(i).prop_map(InformationElement::APN),
+        bearer_context(i).prop_map(InformationElement::BearerContext),
+        ebi(i).prop_map(InformationElement::EBI),
+        bearer_qos(i).prop_map(InformationElement::BearerQoS),
+        imsi(i).prop_map(InformationElement::IMSI),
+        pdn_type(i).prop_map(InformationElement::PDNType),
+        pdn_address_allocation(i).prop_map(InformationElement::PDNAddressAllocation),
+        msisdn(i).prop_map(InformationElement::MSISDN),
+        user_location_information(i).prop_map(InformationElement::ULI),
+        mei(i).prop_map(InformationElement::MEI),
+        serving_network(i).prop_map(InformationElement::ServingNetwork),
+        selection_mode(i).prop_map(InformationElement::SelectionMode),
+        apn_restriction(i).prop_map(InformationElement::APNRestriction),
+        ambr(i).prop_map(InformationElement::AMBR),
+        ue_time_zone(i).prop_map(InformationElement::UETimeZone),
+        charging_characteristics(i).prop_map(InformationElement::ChargingCharacteristics),
+        cause(i).prop_map(InformationElement::Cause),
+        ldn(i).prop_map(InformationElement::LDN),
+        fq_csid(i).prop_map(InformationElement::FQCSID),
+        charging_id(i).prop_map(InformationElement::ChargingID),
+        fqdn(i).prop_map(InformationElement::FQDN),
+        arp(i).prop_map(InformationElement::ARP),
+        guti(i).prop_map(InformationElement::GUTI),
+        complete_request_message(i).prop_map(InformationElement::CompleteRequestMessage),
+        mm_context(i).prop_map(InformationElement::MMContext),
+        pdn_connection(i).prop_map(InformationElement::PDNConnection),
+        node_features(i).prop_map(InformationElement::NodeFeatures),
+        change_reporting_action(i).prop_map(InformationElement::ChangeReportingAction),
+        csg_information_reporting_action(i).prop_map(InformationElement::CSGInformationReportingAction),
+        serving_plmn_rate_control(i).prop_map(InformationElement::ServingPLMNRateControl),
+        apn_rate_control_status(i).prop_map(InformationElement::APNRateControlStatus),
+        trace_information(i).prop_map(InformationElement::TraceInformation),
+        flow_qos(i).prop_map(InformationElement::FlowQoS),
+        tad(i).prop_map(InformationElement::TAD),
+        pti(i).prop_map(InformationElement::PTI),
+        delay_value(i).prop_map(InformationElement::DelayValue),
+        throttling(i).prop_map(InformationElement::Throttling),
+        epc_timer(i).prop_map(InformationElement::EPCTimer),
+        ip_address(i).prop_map(InformationElement::IPAddress),
+        stn_sr(i).prop_map(InformationElement::STNSR),
+        csg_id(i).prop_map(InformationElement::CSGID),
+        cmi(i).prop_map(InformationElement::CMI),
+        uci(i).prop_map(InformationElement::UCI),
+        port_number(i).prop_map(InformationElement::PortNumber),
+        remote_user_id(i).prop_map(InformationElement::RemoteUserID),
+        remote_ue_ip_information(i).prop_map(InformationElement::RemoteUEIPInformation),
+        remote_ue_context(i).prop_map(InformationElement::RemoteUEContext),
+        epco(i).prop_map(InformationElement::EPCO),
+        indication(i).prop_map(InformationElement::Indication),
+        change_to_report_flags(i).prop_map(InformationElement::ChangeToReportFlags),
+        private_extension(i).prop_map(InformationElement::PrivateExtension),
+        paging_and_service_information(i).prop_map(InformationElement::PagingAndServiceInformation),
+        apn_and_relative_capacity(i).prop_map(InformationElement::APNAndRelativeCapacity),
+        extended_trace_information(i).prop_map(InformationElement::ExtendedTraceInformation),
+        node_identifier(i).prop_map(InformationElement::NodeIdentifier),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtp_v2::packet::messages::information_elements::InformationElementTraits;
+
+    proptest! {
+        #[test]
+        fn test_information_element_round_trip(ie in information_element()) {
+            let mut buffer = vec![0; ie.wire_length() as usize];
+
+            let pos = ie.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = InformationElement::parse(&buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+            prop_assert_eq!(parsed.instance(), ie.instance());
+
+            let mut regenerated = vec![0; parsed.wire_length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, ie);
+        }
+    }
+}
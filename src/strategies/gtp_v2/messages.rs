@@ -0,0 +1,789 @@
+use proptest::prelude::*;
+use proptest::option;
+
+use crate::gtp_v2::packet::messages::{
+    Message,
+    echo_request,
+    echo_response,
+    create_session_request,
+    create_session_response,
+    modify_bearer_request,
+    modify_bearer_response,
+    delete_session_request,
+    delete_session_response,
+    downlink_data_notification,
+    downlink_data_notification_acknowledge,
+    downlink_data_notification_failure_indication,
+    context_request,
+    context_response,
+    context_acknowledge,
+    modify_bearer_command,
+    modify_bearer_failure_indication,
+    bearer_resource_command,
+    bearer_resource_failure_indication,
+    suspend_notification,
+    suspend_acknowledge,
+    resume_notification,
+    resume_acknowledge,
+    forward_relocation_request,
+    remote_ue_report_notification,
+    remote_ue_report_acknowledge,
+    release_access_bearers_request,
+    release_access_bearers_response,
+    modify_access_bearers_request,
+    modify_access_bearers_response,
+};
+
+#[cfg(feature = "sv-interface")]
+use crate::gtp_v2::packet::messages::{
+    srvcc_ps_to_cs_request,
+    srvcc_ps_to_cs_response,
+    srvcc_ps_to_cs_complete_notification,
+    srvcc_ps_to_cs_complete_acknowledge,
+};
+
+use super::information_elements as ies;
+
+/*
+    Each strategy generates the IEs of a message with the instances that the message's parse() assigns to its fields.
+    IEs that the message doesn't distinguish by instance are instance 0.
+
+    The strategies for the larger messages are boxed. Their nested strategy types otherwise need more stack than
+    the test threads have in debug builds.
+*/
+
+pub fn echo_request() -> impl Strategy<Value = echo_request::Message> {
+    (ies::recovery(0), option::of(ies::node_features(0)), option::of(ies::private_extension(0))).prop_map(|(recovery, sending_node_features, private_extension)| {
+        let mut m = echo_request::Message::new(recovery);
+        m.sending_node_features = sending_node_features;
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn echo_response() -> impl Strategy<Value = echo_response::Message> {
+    (ies::recovery(0), option::of(ies::node_features(0)), option::of(ies::private_extension(0))).prop_map(|(recovery, sending_node_features, private_extension)| {
+        let mut m = echo_response::Message::new(recovery);
+        m.sending_node_features = sending_node_features;
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn create_session_request() -> impl Strategy<Value = create_session_request::Message> {
+    (
+        (ies::rat_type(0), ies::f_teid(0), ies::bearer_context(0), ies::apn(0)),
+        (
+            option::of(ies::imsi(0)),
+            option::of(ies::msisdn(0)),
+            option::of(ies::mei(0)),
+            option::of(ies::user_location_information(0)),
+            option::of(ies::serving_network(0)),
+            option::of(ies::f_teid(1)),
+            option::of(ies::selection_mode(0)),
+            option::of(ies::pdn_type(0)),
+            option::of(ies::pdn_address_allocation(0)),
+            option::of(ies::apn_restriction(0)),
+            option::of(ies::ambr(0)),
+        ),
+        (
+            prop::collection::vec(ies::bearer_context(0), 0..=1),
+            prop::collection::vec(ies::bearer_context(1), 0..=2),
+            option::of(ies::trace_information(0)),
+            option::of(ies::fq_csid(0)),
+            option::of(ies::fq_csid(1)),
+            option::of(ies::fq_csid(2)),
+            option::of(ies::fq_csid(3)),
+            option::of(ies::ue_time_zone(0)),
+            option::of(ies::charging_characteristics(0)),
+        ),
+        (
+            option::of(ies::ldn(0)),
+            option::of(ies::ldn(1)),
+            option::of(ies::ldn(2)),
+            option::of(ies::ldn(3)),
+            option::of(ies::serving_plmn_rate_control(0)),
+            option::of(ies::fqdn(0)),
+            option::of(ies::apn_rate_control_status(0)),
+            option::of(ies::node_features(0)),
+        ),
+        (
+            option::of(ies::uci(0)),
+            option::of(ies::ip_address(0)),
+            option::of(ies::port_number(0)),
+            option::of(ies::ip_address(1)),
+            option::of(ies::port_number(1)),
+            option::of(ies::ip_address(2)),
+            option::of(ies::node_identifier(0)),
+            option::of(ies::epco(0)),
+            option::of(ies::recovery(0)),
+        ),
+    ).prop_map(|(
+        (rat_type, sender_f_teid, bearer_context, apn),
+        (imsi, msisdn, mei, uli, serving_network, pgw_s5_s8, selection_mode, pdn_type, paa, apn_restriction, apn_ambr),
+        (to_be_created, to_be_removed, trace_information, mme_fq_csid, sgw_fq_csid, epdg_fq_csid, twan_fq_csid, ue_time_zone, charging_characteristics),
+        (mme_s4_sgsn_ldn, sgw_ldn, epdg_ldn, twan_ldn, serving_plmn_rate_control, sgw_u_node_name, apn_rate_control_status, sending_node_features),
+        (uci, ue_local_ip_address, ue_udp_port, henb_local_ip_address, henb_udp_port, mme_s4_sgsn_identifier, aaa_server_identifier, epco, recovery),
+    )| {
+        let mut m = create_session_request::Message::new(rat_type, sender_f_teid, bearer_context, apn);
+
+        m.imsi = imsi;
+        m.msisdn = msisdn;
+        m.mei = mei;
+        m.uli = uli;
+        m.serving_network = serving_network;
+        m.pgw_s5_s8_for_control_plane = pgw_s5_s8;
+        m.selection_mode = selection_mode;
+        m.pdn_type = pdn_type;
+        m.pdn_address_allocation = paa;
+        m.maximum_apn_restriction = apn_restriction;
+        m.apn_ambr = apn_ambr;
+        for bc in to_be_created {
+            m.push_bearer_context_to_be_created(bc);
+        }
+        for bc in to_be_removed {
+            m.push_bearer_context_to_be_removed(bc);
+        }
+        m.trace_information = trace_information;
+        m.recovery = recovery;
+        m.mme_fq_csid = mme_fq_csid;
+        m.sgw_fq_csid = sgw_fq_csid;
+        m.epdg_fq_csid = epdg_fq_csid;
+        m.twan_fq_csid = twan_fq_csid;
+        m.ue_time_zone = ue_time_zone;
+        m.uci = uci;
+        m.charging_characteristics = charging_characteristics;
+        m.mme_s4_sgsn_ldn = mme_s4_sgsn_ldn;
+        m.sgw_ldn = sgw_ldn;
+        m.epdg_ldn = epdg_ldn;
+        m.twan_ldn = twan_ldn;
+        m.ue_local_ip_address = ue_local_ip_address;
+        m.ue_udp_port = ue_udp_port;
+        m.henb_local_ip_address = henb_local_ip_address;
+        m.henb_udp_port = henb_udp_port;
+        m.mme_s4_sgsn_identifier = mme_s4_sgsn_identifier;
+        m.aaa_server_identifier = aaa_server_identifier;
+        m.epco = epco;
+        m.serving_plmn_rate_control = serving_plmn_rate_control;
+        m.sgw_u_node_name = sgw_u_node_name;
+        m.apn_rate_control_status = apn_rate_control_status;
+        m.sending_node_features = sending_node_features;
+
+        m
+    }).boxed()
+}
+
+pub fn create_session_response() -> impl Strategy<Value = create_session_response::Message> {
+    (
+        (ies::cause(0), prop::collection::vec(ies::bearer_context(0), 1..=2), prop::collection::vec(ies::bearer_context(1), 0..=2), option::of(ies::recovery(0))),
+        (
+            option::of(ies::change_reporting_action(0)),
+            option::of(ies::csg_information_reporting_action(0)),
+            option::of(ies::f_teid(0)),
+            option::of(ies::f_teid(1)),
+            option::of(ies::pdn_address_allocation(0)),
+            option::of(ies::apn_restriction(0)),
+            option::of(ies::ambr(0)),
+            option::of(ies::fqdn(0)),
+            option::of(ies::ip_address(0)),
+        ),
+        (
+            option::of(ies::fq_csid(0)),
+            option::of(ies::fq_csid(1)),
+            option::of(ies::ldn(0)),
+            option::of(ies::ldn(1)),
+            option::of(ies::epc_timer(0)),
+            option::of(ies::charging_id(0)),
+            option::of(ies::serving_plmn_rate_control(0)),
+            option::of(ies::apn_rate_control_status(0)),
+            option::of(ies::epco(0)),
+        ),
+    ).prop_map(|(
+        (cause, created, marked_for_removal, recovery),
+        (change_reporting_action, csg_information_reporting_action, sender_f_teid, pgw_s5_s8, paa, apn_restriction, apn_ambr, charging_gateway_name, charging_gateway_address),
+        (pgw_fq_csid, sgw_fq_csid, sgw_ldn, pgw_ldn, pgw_back_off_time, pdn_connection_charging_id, serving_plmn_rate_control, apn_rate_control_status, epco),
+    )| {
+        let mut m = create_session_response::Message::new(cause, created);
+
+        for bc in marked_for_removal {
+            m.push_bearer_context_marked_for_removal(bc);
+        }
+        m.recovery = recovery;
+        m.change_reporting_action = change_reporting_action;
+        m.csg_information_reporting_action = csg_information_reporting_action;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.pgw_s5_s8_for_control_plane = pgw_s5_s8;
+        m.pdn_address_allocation = paa;
+        m.apn_restriction = apn_restriction;
+        m.apn_ambr = apn_ambr;
+        m.charging_gateway_name = charging_gateway_name;
+        m.charging_gateway_address = charging_gateway_address;
+        m.pgw_fq_csid = pgw_fq_csid;
+        m.sgw_fq_csid = sgw_fq_csid;
+        m.sgw_ldn = sgw_ldn;
+        m.pgw_ldn = pgw_ldn;
+        m.pgw_back_off_time = pgw_back_off_time;
+        m.pdn_connection_charging_id = pdn_connection_charging_id;
+        m.serving_plmn_rate_control = serving_plmn_rate_control;
+        m.apn_rate_control_status = apn_rate_control_status;
+        m.epco = epco;
+
+        m
+    }).boxed()
+}
+
+pub fn modify_bearer_request() -> impl Strategy<Value = modify_bearer_request::Message> {
+    (
+        (
+            option::of(ies::mei(0)),
+            option::of(ies::user_location_information(0)),
+            option::of(ies::serving_network(0)),
+            option::of(ies::rat_type(0)),
+            option::of(ies::indication(0)),
+            option::of(ies::f_teid(0)),
+            option::of(ies::ambr(0)),
+        ),
+        (
+            option::of(ies::delay_value(0)),
+            option::of(ies::recovery(0)),
+            option::of(ies::ue_time_zone(0)),
+            option::of(ies::fq_csid(0)),
+            option::of(ies::fq_csid(1)),
+            option::of(ies::uci(0)),
+            option::of(ies::change_to_report_flags(0)),
+        ),
+        (
+            prop::collection::vec(ies::bearer_context(0), 0..=2),
+            prop::collection::vec(ies::bearer_context(1), 0..=1),
+            any::<bool>(),
+        ),
+    ).prop_map(|(
+        (mei, uli, serving_network, rat_type, indication, sender_f_teid, apn_ambr),
+        (delay_value, recovery, ue_time_zone, mme_fq_csid, sgw_fq_csid, uci, change_to_report_flags),
+        (bearer_contexts_to_be_modified, bearer_contexts_to_be_removed, without_qos),
+    )| {
+        let mut m = modify_bearer_request::Message::new();
+
+        m.mei = mei;
+        m.user_location_information = uli;
+        m.serving_network = serving_network;
+        m.rat_type = rat_type;
+        m.indication = indication;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.apn_ambr = apn_ambr;
+        m.delay_downlink_packet_notification_request = delay_value;
+        m.recovery = recovery;
+        m.ue_time_zone = ue_time_zone;
+        m.mme_fq_csid = mme_fq_csid;
+        m.sgw_fq_csid = sgw_fq_csid;
+        m.uci = uci;
+        m.change_to_report_flags = change_to_report_flags;
+
+        for mut bc in bearer_contexts_to_be_modified {
+            // The Bearer Level QoS is optional here
+            if without_qos {
+                bc.bearer_level_qos = None;
+            }
+            m.push_bearer_context_to_be_modified(bc);
+        }
+
+        for bc in bearer_contexts_to_be_removed {
+            m.push_bearer_context_to_be_removed(bc);
+        }
+
+        m
+    }).boxed()
+}
+
+pub fn modify_bearer_response() -> impl Strategy<Value = modify_bearer_response::Message> {
+    (
+        ies::cause(0),
+        option::of(ies::ebi(0)),
+        option::of(ies::apn_restriction(0)),
+        option::of(ies::fq_csid(0)),
+        option::of(ies::fq_csid(1)),
+        option::of(ies::recovery(0)),
+        option::of(ies::indication(0)),
+        option::of(ies::private_extension(0)),
+    ).prop_map(|(cause, linked_eps_bearer_id, apn_restriction, pgw_fq_csid, sgw_fq_csid, recovery, indication, private_extension)| {
+        let mut m = modify_bearer_response::Message::new(cause);
+
+        m.linked_eps_bearer_id = linked_eps_bearer_id;
+        m.apn_restriction = apn_restriction;
+        m.pgw_fq_csid = pgw_fq_csid;
+        m.sgw_fq_csid = sgw_fq_csid;
+        m.recovery = recovery;
+        m.indication = indication;
+        m.private_extension = private_extension;
+
+        m
+    })
+}
+
+pub fn delete_session_request() -> impl Strategy<Value = delete_session_request::Message> {
+    (
+        option::of(ies::cause(0)),
+        option::of(ies::ebi(0)),
+        option::of(ies::user_location_information(0)),
+        option::of(ies::indication(0)),
+        option::of(ies::f_teid(0)),
+        option::of(ies::ue_time_zone(0)),
+        option::of(ies::private_extension(0)),
+    ).prop_map(|(cause, linked_eps_bearer_id, uli, indication, sender_f_teid, ue_time_zone, private_extension)| {
+        let mut m = delete_session_request::Message::new();
+
+        m.cause = cause;
+        m.linked_eps_bearer_id = linked_eps_bearer_id;
+        m.user_location_information = uli;
+        m.indication = indication;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.ue_time_zone = ue_time_zone;
+        m.private_extension = private_extension;
+
+        m
+    }).boxed()
+}
+
+pub fn delete_session_response() -> impl Strategy<Value = delete_session_response::Message> {
+    (ies::cause(0), option::of(ies::recovery(0)), option::of(ies::private_extension(0))).prop_map(|(cause, recovery, private_extension)| {
+        let mut m = delete_session_response::Message::new(cause);
+        m.recovery = recovery;
+        m.private_extension = private_extension;
+        m
+    })
+}
+
+pub fn downlink_data_notification() -> impl Strategy<Value = downlink_data_notification::Message> {
+    (
+        option::of(ies::cause(0)),
+        prop::collection::vec(ies::ebi(0), 0..=3),
+        option::of(ies::arp(0)),
+        option::of(ies::imsi(0)),
+        option::of(ies::f_teid(0)),
+        option::of(ies::indication(0)),
+        prop::collection::vec(ies::paging_and_service_information(0), 0..=2),
+    ).prop_map(|(cause, eps_bearer_ids, arp, imsi, sender_f_teid, indication_flags, paging_and_service_informations)| {
+        let mut m = downlink_data_notification::Message::new();
+
+        m.cause = cause;
+        for ebi in eps_bearer_ids {
+            m.push_eps_bearer_id(ebi);
+        }
+        m.arp = arp;
+        m.imsi = imsi;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.indication_flags = indication_flags;
+        for ie in paging_and_service_informations {
+            m.push_paging_and_service_information(ie);
+        }
+
+        m
+    })
+}
+
+pub fn downlink_data_notification_acknowledge() -> impl Strategy<Value = downlink_data_notification_acknowledge::Message> {
+    (ies::cause(0), option::of(ies::recovery(0)), option::of(ies::imsi(0)), option::of(ies::node_identifier(0))).prop_map(|(cause, recovery, imsi, node_identifier)| {
+        let mut m = downlink_data_notification_acknowledge::Message::new(cause);
+        m.recovery = recovery;
+        m.imsi = imsi;
+        m.node_identifier = node_identifier;
+        m
+    })
+}
+
+pub fn downlink_data_notification_failure_indication() -> impl Strategy<Value = downlink_data_notification_failure_indication::Message> {
+    (ies::cause(0), option::of(ies::imsi(0))).prop_map(|(cause, imsi)| {
+        let mut m = downlink_data_notification_failure_indication::Message::new(cause);
+        m.imsi = imsi;
+        m
+    })
+}
+
+pub fn context_request() -> impl Strategy<Value = context_request::Message> {
+    (
+        option::of(ies::imsi(0)),
+        option::of(ies::guti(0)),
+        option::of(ies::complete_request_message(0)),
+        option::of(ies::f_teid(0)),
+        option::of(ies::rat_type(0)),
+        option::of(ies::serving_network(0)),
+    ).prop_map(|(imsi, guti, complete_tau_request_message, sender_f_teid, rat_type, target_plmn_id)| {
+        let mut m = context_request::Message::new();
+
+        m.imsi = imsi;
+        m.guti = guti;
+        m.complete_tau_request_message = complete_tau_request_message;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.rat_type = rat_type;
+        m.target_plmn_id = target_plmn_id;
+
+        m
+    })
+}
+
+pub fn context_response() -> impl Strategy<Value = context_response::Message> {
+    (
+        ies::cause(0),
+        option::of(ies::imsi(0)),
+        option::of(ies::mm_context(0)),
+        prop::collection::vec(ies::pdn_connection(0), 0..=2),
+        option::of(ies::f_teid(0)),
+        option::of(ies::f_teid(1)),
+        option::of(ies::fqdn(0)),
+    ).prop_map(|(cause, imsi, mm_context, pdn_connections, sender_f_teid, sgw_f_teid, sgw_node_name)| {
+        let mut m = context_response::Message::new(cause);
+
+        m.imsi = imsi;
+        m.mm_context = mm_context;
+        for pdn_connection in pdn_connections {
+            m.push_pdn_connection(pdn_connection);
+        }
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.sgw_s11_s4_f_teid_for_control_plane = sgw_f_teid;
+        m.sgw_node_name = sgw_node_name;
+
+        m
+    }).boxed()
+}
+
+pub fn context_acknowledge() -> impl Strategy<Value = context_acknowledge::Message> {
+    (ies::cause(0), option::of(ies::f_teid(0))).prop_map(|(cause, forwarding_f_teid)| {
+        let mut m = context_acknowledge::Message::new(cause);
+        m.forwarding_f_teid = forwarding_f_teid;
+        m
+    })
+}
+
+pub fn modify_bearer_command() -> impl Strategy<Value = modify_bearer_command::Message> {
+    (ies::ambr(0), ies::bearer_context(0), option::of(ies::f_teid(0))).prop_map(|(apn_ambr, bearer_context, sender_f_teid)| {
+        let mut m = modify_bearer_command::Message::new(apn_ambr, bearer_context);
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m
+    })
+}
+
+pub fn modify_bearer_failure_indication() -> impl Strategy<Value = modify_bearer_failure_indication::Message> {
+    (ies::cause(0), option::of(ies::recovery(0))).prop_map(|(cause, recovery)| {
+        let mut m = modify_bearer_failure_indication::Message::new(cause);
+        m.recovery = recovery;
+        m
+    })
+}
+
+pub fn bearer_resource_command() -> impl Strategy<Value = bearer_resource_command::Message> {
+    (
+        (ies::ebi(0), ies::pti(0), ies::tad(0)),
+        (
+            option::of(ies::flow_qos(0)),
+            option::of(ies::rat_type(0)),
+            option::of(ies::serving_network(0)),
+            option::of(ies::user_location_information(0)),
+            option::of(ies::ebi(1)),
+            option::of(ies::f_teid(0)),
+            option::of(ies::f_teid(1)),
+            option::of(ies::epco(0)),
+            option::of(ies::f_teid(2)),
+        ),
+    ).prop_map(|((linked_eps_bearer_id, pti, tad), (flow_qos, rat_type, serving_network, uli, eps_bearer_id, s4_u, s12, epco, sender_f_teid))| {
+        let mut m = bearer_resource_command::Message::new(linked_eps_bearer_id, pti, tad);
+
+        m.flow_qos = flow_qos;
+        m.rat_type = rat_type;
+        m.serving_network = serving_network;
+        m.uli = uli;
+        m.eps_bearer_id = eps_bearer_id;
+        m.s4_u_sgsn_f_teid = s4_u;
+        m.s12_rnc_f_teid = s12;
+        m.epco = epco;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+
+        m
+    }).boxed()
+}
+
+pub fn bearer_resource_failure_indication() -> impl Strategy<Value = bearer_resource_failure_indication::Message> {
+    (ies::cause(0), ies::ebi(0), ies::pti(0), option::of(ies::recovery(0))).prop_map(|(cause, linked_eps_bearer_id, pti, recovery)| {
+        let mut m = bearer_resource_failure_indication::Message::new(cause, linked_eps_bearer_id, pti);
+        m.recovery = recovery;
+        m
+    })
+}
+
+pub fn suspend_notification() -> impl Strategy<Value = suspend_notification::Message> {
+    (option::of(ies::imsi(0)), option::of(ies::ebi(0)), option::of(ies::f_teid(0))).prop_map(|(imsi, linked_eps_bearer_id, sender_f_teid)| {
+        let mut m = suspend_notification::Message::new();
+        m.imsi = imsi;
+        m.linked_eps_bearer_id = linked_eps_bearer_id;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m
+    })
+}
+
+pub fn suspend_acknowledge() -> impl Strategy<Value = suspend_acknowledge::Message> {
+    ies::cause(0).prop_map(suspend_acknowledge::Message::new)
+}
+
+pub fn release_access_bearers_request() -> impl Strategy<Value = release_access_bearers_request::Message> {
+    (
+        prop::collection::vec(ies::ebi(0), 0..=3),
+        option::of(ies::indication(0)),
+    ).prop_map(|(list_of_rabs, indication_flags)| {
+        let mut m = release_access_bearers_request::Message::new();
+
+        for ebi in list_of_rabs {
+            m.push_rab(ebi);
+        }
+        m.indication_flags = indication_flags;
+
+        m
+    })
+}
+
+pub fn release_access_bearers_response() -> impl Strategy<Value = release_access_bearers_response::Message> {
+    (ies::cause(0), option::of(ies::recovery(0))).prop_map(|(cause, recovery)| {
+        let mut m = release_access_bearers_response::Message::new(cause);
+        m.recovery = recovery;
+        m
+    })
+}
+
+pub fn modify_access_bearers_request() -> impl Strategy<Value = modify_access_bearers_request::Message> {
+    (
+        option::of(ies::indication(0)),
+        option::of(ies::f_teid(0)),
+        option::of(ies::delay_value(0)),
+        prop::collection::vec(ies::bearer_context(0), 0..=2),
+        prop::collection::vec(ies::bearer_context(1), 0..=2),
+        option::of(ies::recovery(0)),
+    ).prop_map(|(indication, sender_f_teid, delay_value, to_be_modified, to_be_removed, recovery)| {
+        let mut m = modify_access_bearers_request::Message::new();
+        m.indication = indication;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m.delay_downlink_packet_notification_request = delay_value;
+        m.bearer_contexts_to_be_modified = to_be_modified;
+        m.bearer_contexts_to_be_removed = to_be_removed;
+        m.recovery = recovery;
+        m
+    })
+}
+
+pub fn modify_access_bearers_response() -> impl Strategy<Value = modify_access_bearers_response::Message> {
+    (
+        ies::cause(0),
+        prop::collection::vec(ies::bearer_context(0), 0..=2),
+        prop::collection::vec(ies::bearer_context(1), 0..=2),
+        option::of(ies::recovery(0)),
+        option::of(ies::indication(0)),
+    ).prop_map(|(cause, modified, marked_for_removal, recovery, indication)| {
+        let mut m = modify_access_bearers_response::Message::new(cause);
+        m.bearer_contexts_modified = modified;
+        m.bearer_contexts_marked_for_removal = marked_for_removal;
+        m.recovery = recovery;
+        m.indication = indication;
+        m
+    })
+}
+
+pub fn resume_notification() -> impl Strategy<Value = resume_notification::Message> {
+    (ies::imsi(0), option::of(ies::ebi(0)), option::of(ies::f_teid(0))).prop_map(|(imsi, linked_eps_bearer_id, sender_f_teid)| {
+        let mut m = resume_notification::Message::new(imsi);
+        m.linked_eps_bearer_id = linked_eps_bearer_id;
+        m.sender_f_teid_for_control_plane = sender_f_teid;
+        m
+    })
+}
+
+pub fn resume_acknowledge() -> impl Strategy<Value = resume_acknowledge::Message> {
+    ies::cause(0).prop_map(resume_acknowledge::Message::new)
+}
+
+pub fn forward_relocation_request() -> impl Strategy<Value = forward_relocation_request::Message> {
+    (
+        ies::f_teid(0),
+        ies::mm_context(0),
+        option::of(ies::imsi(0)),
+        prop::collection::vec(ies::pdn_connection(0), 0..=2),
+        option::of(ies::f_teid(1)),
+        option::of(ies::fqdn(0)),
+    ).prop_map(|(sender_f_teid, mm_context, imsi, pdn_connections, sgw_f_teid, sgw_node_name)| {
+        let mut m = forward_relocation_request::Message::new(sender_f_teid, mm_context).unwrap();
+
+        m.imsi = imsi;
+        for pdn_connection in pdn_connections {
+            m.push_pdn_connection(pdn_connection);
+        }
+        m.sgw_s11_s4_f_teid_for_control_plane = sgw_f_teid;
+        m.sgw_node_name = sgw_node_name;
+
+        m
+    }).boxed()
+}
+
+pub fn remote_ue_report_notification() -> impl Strategy<Value = remote_ue_report_notification::Message> {
+    (
+        prop::collection::vec(ies::remote_ue_context(0), 0..=2),
+        prop::collection::vec(ies::remote_ue_context(1), 0..=2),
+    ).prop_map(|(connected, disconnected)| {
+        let mut m = remote_ue_report_notification::Message::new();
+        m.remote_ue_contexts_connected = connected;
+        m.remote_ue_contexts_disconnected = disconnected;
+        m
+    })
+}
+
+pub fn remote_ue_report_acknowledge() -> impl Strategy<Value = remote_ue_report_acknowledge::Message> {
+    ies::cause(0).prop_map(remote_ue_report_acknowledge::Message::new)
+}
+
+#[cfg(feature = "sv-interface")]
+pub fn srvcc_ps_to_cs_request() -> impl Strategy<Value = srvcc_ps_to_cs_request::Message> {
+    (
+        ies::imsi(0),
+        ies::f_teid(0),
+        option::of(ies::mei(0)),
+        option::of(ies::msisdn(0)),
+        option::of(ies::stn_sr(0)),
+    ).prop_map(|(imsi, sender_f_teid, mei, c_msisdn, stn_sr)| {
+        let mut m = srvcc_ps_to_cs_request::Message::new(imsi, sender_f_teid);
+        m.mei = mei;
+        m.c_msisdn = c_msisdn;
+        m.stn_sr = stn_sr;
+        m
+    })
+}
+
+#[cfg(feature = "sv-interface")]
+pub fn srvcc_ps_to_cs_response() -> impl Strategy<Value = srvcc_ps_to_cs_response::Message> {
+    ies::cause(0).prop_map(srvcc_ps_to_cs_response::Message::new)
+}
+
+#[cfg(feature = "sv-interface")]
+pub fn srvcc_ps_to_cs_complete_notification() -> impl Strategy<Value = srvcc_ps_to_cs_complete_notification::Message> {
+    option::of(ies::imsi(0)).prop_map(|imsi| {
+        let mut m = srvcc_ps_to_cs_complete_notification::Message::new();
+        m.imsi = imsi;
+        m
+    })
+}
+
+#[cfg(feature = "sv-interface")]
+pub fn srvcc_ps_to_cs_complete_acknowledge() -> impl Strategy<Value = srvcc_ps_to_cs_complete_acknowledge::Message> {
+    ies::cause(0).prop_map(srvcc_ps_to_cs_complete_acknowledge::Message::new)
+}
+
+#[cfg(not(feature = "sv-interface"))]
+pub fn message() -> impl Strategy<Value = Message> {
+    prop_oneof![
+        echo_request().prop_map(Message::EchoRequest).boxed(),
+        echo_response().prop_map(Message::EchoResponse).boxed(),
+        create_session_request().prop_map(Message::CreateSessionRequest).boxed(),
+        create_session_response().prop_map(Message::CreateSessionResponse).boxed(),
+        modify_bearer_request().prop_map(Message::ModifyBearerRequest).boxed(),
+        modify_bearer_response().prop_map(Message::ModifyBearerResponse).boxed(),
+        delete_session_request().prop_map(Message::DeleteSessionRequest).boxed(),
+        delete_session_response().prop_map(Message::DeleteSessionResponse).boxed(),
+        downlink_data_notification().prop_map(Message::DownlinkDataNotification).boxed(),
+        downlink_data_notification_acknowledge().prop_map(Message::DownlinkDataNotificationAcknowledge).boxed(),
+        downlink_data_notification_failure_indication().prop_map(Message::DownlinkDataNotificationFailureIndication).boxed(),
+        context_request().prop_map(Message::ContextRequest).boxed(),
+        context_response().prop_map(Message::ContextResponse).boxed(),
+        context_acknowledge().prop_map(Message::ContextAcknowledge).boxed(),
+        modify_bearer_command().prop_map(Message::ModifyBearerCommand).boxed(),
+        modify_bearer_failure_indication().prop_map(Message::ModifyBearerFailureIndication).boxed(),
+        bearer_resource_command().prop_map(Message::BearerResourceCommand).boxed(),
+        bearer_resource_failure_indication().prop_map(Message::BearerResourceFailureIndication).boxed(),
+        suspend_notification().prop_map(Message::SuspendNotification).boxed(),
+        suspend_acknowledge().prop_map(Message::SuspendAcknowledge).boxed(),
+        resume_notification().prop_map(Message::ResumeNotification).boxed(),
+        resume_acknowledge().prop_map(Message::ResumeAcknowledge).boxed(),
+        forward_relocation_request().prop_map(Message::ForwardRelocationRequest).boxed(),
+        release_access_bearers_request().prop_map(Message::ReleaseAccessBearersRequest).boxed(),
+        release_access_bearers_response().prop_map(Message::ReleaseAccessBearersResponse).boxed(),
+        modify_access_bearers_request().prop_map(Message::ModifyAccessBearersRequest).boxed(),
+        modify_access_bearers_response().prop_map(Message::ModifyAccessBearersResponse).boxed(),
+        remote_ue_report_notification().prop_map(Message::RemoteUEReportNotification).boxed(),
+        remote_ue_report_acknowledge().prop_map(Message::RemoteUEReportAcknowledge).boxed(),
+    ]
+}
+
+#[cfg(feature = "sv-interface")]
+pub fn message() -> impl Strategy<Value = Message> {
+    prop_oneof![
+        echo_request().prop_map(Message::EchoRequest).boxed(),
+        echo_response().prop_map(Message::EchoResponse).boxed(),
+        create_session_request().prop_map(Message::CreateSessionRequest).boxed(),
+        create_session_response().prop_map(Message::CreateSessionResponse).boxed(),
+        modify_bearer_request().prop_map(Message::ModifyBearerRequest).boxed(),
+        modify_bearer_response().prop_map(Message::ModifyBearerResponse).boxed(),
+        delete_session_request().prop_map(Message::DeleteSessionRequest).boxed(),
+        delete_session_response().prop_map(Message::DeleteSessionResponse).boxed(),
+        downlink_data_notification().prop_map(Message::DownlinkDataNotification).boxed(),
+        downlink_data_notification_acknowledge().prop_map(Message::DownlinkDataNotificationAcknowledge).boxed(),
+        downlink_data_notification_failure_indication().prop_map(Message::DownlinkDataNotificationFailureIndication).boxed(),
+        context_request().prop_map(Message::ContextRequest).boxed(),
+        context_response().prop_map(Message::ContextResponse).boxed(),
+        context_acknowledge().prop_map(Message::ContextAcknowledge).boxed(),
+        modify_bearer_command().prop_map(Message::ModifyBearerCommand).boxed(),
+        modify_bearer_failure_indication().prop_map(Message::ModifyBearerFailureIndication).boxed(),
+        bearer_resource_command().prop_map(Message::BearerResourceCommand).boxed(),
+        bearer_resource_failure_indication().prop_map(Message::BearerResourceFailureIndication).boxed(),
+        suspend_notification().prop_map(Message::SuspendNotification).boxed(),
+        suspend_acknowledge().prop_map(Message::SuspendAcknowledge).boxed(),
+        resume_notification().prop_map(Message::ResumeNotification).boxed(),
+        resume_acknowledge().prop_map(Message::ResumeAcknowledge).boxed(),
+        forward_relocation_request().prop_map(Message::ForwardRelocationRequest).boxed(),
+        release_access_bearers_request().prop_map(Message::ReleaseAccessBearersRequest).boxed(),
+        release_access_bearers_response().prop_map(Message::ReleaseAccessBearersResponse).boxed(),
+        modify_access_bearers_request().prop_map(Message::ModifyAccessBearersRequest).boxed(),
+        modify_access_bearers_response().prop_map(Message::ModifyAccessBearersResponse).boxed(),
+        remote_ue_report_notification().prop_map(Message::RemoteUEReportNotification).boxed(),
+        remote_ue_report_acknowledge().prop_map(Message::RemoteUEReportAcknowledge).boxed(),
+        srvcc_ps_to_cs_request().prop_map(Message::SRVCCPsToCsRequest).boxed(),
+        srvcc_ps_to_cs_response().prop_map(Message::SRVCCPsToCsResponse).boxed(),
+        srvcc_ps_to_cs_complete_notification().prop_map(Message::SRVCCPsToCsCompleteNotification).boxed(),
+        srvcc_ps_to_cs_complete_acknowledge().prop_map(Message::SRVCCPsToCsCompleteAcknowledge).boxed(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtp_v2::packet::messages::MessageTraits;
+
+    proptest! {
+        #[test]
+        fn test_message_round_trip(message in message()) {
+            let mut buffer = vec![0; message.length() as usize];
+
+            let pos = message.generate(&mut buffer);
+            prop_assert_eq!(pos, buffer.len());
+
+            let (parsed, parsed_pos) = Message::parse(message.message_type(), &buffer).unwrap();
+            prop_assert_eq!(parsed_pos, pos);
+
+            let mut regenerated = vec![0; parsed.length() as usize];
+            parsed.generate(&mut regenerated);
+
+            prop_assert_eq!(regenerated, buffer);
+            prop_assert_eq!(parsed, message);
+        }
+
+        #[test]
+        fn test_message_ies(message in message()) {
+            let mut buffer = vec![0; message.length() as usize];
+            message.generate(&mut buffer);
+
+            // Generating the IEs one after the other gives the message
+            let mut ies = vec![0; buffer.len()];
+            let mut pos = 0;
+
+            for ie in message.ies() {
+                prop_assert_eq!(ie.generate(&mut ies[pos..]), ie.wire_length() as usize);
+                pos = pos + ie.wire_length() as usize;
+            }
+
+            prop_assert_eq!(pos, buffer.len());
+            prop_assert_eq!(ies, buffer);
+        }
+    }
+}
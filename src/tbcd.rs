@@ -0,0 +1,153 @@
+/*
+    Telephony Binary Coded Decimal (TBCD) strings as per TS 29.002. These are used for identities such as the
+    IMSI, MSISDN and IMEI.
+
+    Two digits are packed in to each octet with the first digit in bits 1-4. If there is an odd number of digits
+    bits 5-8 of the last octet are filled with 0xF.
+
+                                        Bits
+            |---------------------------------------------------------------|
+    Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+            |---------------------------------------------------------------|
+    1       | Digit 2                       | Digit 1                       |
+    2       | Digit 4                       | Digit 3                       |
+    n       | Digit m or 0xF (filler)       | Digit m-1                     |
+            |---------------------------------------------------------------|
+*/
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub const FILLER: u8 = 0xF;
+
+pub fn parse_digits(digits: &str) -> Result<Vec<u8>, String> {
+    // Converts a string of decimal digits to a digit per octet
+    let mut parsed = Vec::with_capacity(digits.len());
+
+    for c in digits.chars() {
+        match c.to_digit(10) {
+            Some(d) => parsed.push(d as u8),
+            None => return Err(format!("Not a decimal digit ({})", c)),
+        }
+    }
+
+    Ok(parsed)
+}
+
+pub fn to_string(digits: &[u8]) -> String {
+    // The 0xA - 0xE values are used for "*", "#", "a", "b" and "c"
+    digits.iter().map(|d| {
+        match d {
+            0..=9 => (b'0' + d) as char,
+            0xA => '*',
+            0xB => '#',
+            0xC => 'a',
+            0xD => 'b',
+            0xE => 'c',
+            _ => '?',
+        }
+    }).collect()
+}
+
+pub fn length(digits: &[u8]) -> usize {
+    // The number of octets needed to encode digits
+    digits.len() / 2 + digits.len() % 2
+}
+
+pub fn generate(digits: &[u8], buffer: &mut[u8]) -> usize {
+    let mut pos = 0;
+
+    for pair in digits.chunks(2) {
+        let second_digit = if pair.len() == 2 { pair[1] } else { FILLER };
+
+        buffer[pos] = ((second_digit & 0xF) << 4) | (pair[0] & 0xF);
+        pos = pos + 1;
+    }
+
+    pos
+}
+
+pub fn encode(digits: &[u8]) -> Vec<u8> {
+    let mut buffer = vec![0; length(digits)];
+
+    generate(digits, &mut buffer);
+
+    buffer
+}
+
+pub fn decode(buffer: &[u8]) -> Option<Vec<u8>> {
+    // The filler is only valid in bits 5-8 of the last octet
+    let mut digits = Vec::with_capacity(buffer.len() * 2);
+
+    for (i, o) in buffer.iter().enumerate() {
+        let first_digit = o & 0xF;
+        let second_digit = (o >> 4) & 0xF;
+
+        if first_digit == FILLER {
+            return None
+        }
+
+        digits.push(first_digit);
+
+        if second_digit != FILLER {
+            digits.push(second_digit);
+        }
+        else if i != buffer.len() - 1 {
+            return None
+        }
+    }
+
+    Some(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_digits() {
+        assert_eq!(parse_digits("505013485090404").unwrap(), [5, 0, 5, 0, 1, 3, 4, 8, 5, 0, 9, 0, 4, 0, 4]);
+        assert!(parse_digits("5f5").is_err());
+        assert!(parse_digits("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&[6, 1, 1, 2, 3]), "61123");
+        assert_eq!(to_string(&[0xA, 0xB, 1]), "*#1");
+    }
+
+    #[test]
+    fn test_encode() {
+        // Odd number of digits
+        assert_eq!(encode(&[6, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9]), [0x16, 0x21, 0x43, 0x65, 0x87, 0xF9]);
+        // Even number of digits
+        assert_eq!(encode(&[6, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0]), [0x16, 0x21, 0x43, 0x65, 0x87, 0x09]);
+        assert!(encode(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_decode() {
+        if let Some(digits) = decode(&[0x16, 0x21, 0x43, 0x65, 0x87, 0xF9]) {
+            assert_eq!(digits, [6, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        }
+        else {
+            assert!(false);
+        }
+
+        if let Some(digits) = decode(&[0x16, 0x21, 0x43, 0x65, 0x87, 0x09]) {
+            assert_eq!(digits, [6, 1, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0]);
+        }
+        else {
+            assert!(false);
+        }
+
+        // Filler before the last octet
+        assert!(decode(&[0xF6, 0x21]).is_none());
+
+        // Filler in bits 1-4
+        assert!(decode(&[0x16, 0x2F]).is_none());
+    }
+}
@@ -0,0 +1,686 @@
+/*
+    Traffic Flow Template (TFT) as per TS 24.008 10.5.6.12 starting from octet 3. The same coding is carried by the
+    Bearer TFT and the Traffic Aggregate Description (TAD) IEs.
+
+                                        Bits
+            |---------------------------------------------------------------|
+    Octets  |   8   |   7   |   6   |   5   |   4   |   3   |   2   |   1   |
+            |---------------------------------------------------------------|
+    1       | TFT operation code    | E     | Number of packet filters      |
+    2 -> m  | Packet filter list                                            |
+    m+1 ->n | Parameters list (if E is set)                                 |
+            |---------------------------------------------------------------|
+
+    Each packet filter is coded as
+
+            |---------------------------------------------------------------|
+    1       | Spare         | Direction     | Packet filter identifier      |
+    2       | Packet filter evaluation precedence                           |
+    3       | Length of packet filter contents                              |
+    4 -> p  | Packet filter contents (components)                           |
+            |---------------------------------------------------------------|
+
+    except when deleting packet filters where only the octet with the packet filter identifier is present. There are no
+    packet filters when deleting the TFT or when there is no TFT operation.
+*/
+
+use byteorder::{ByteOrder, NetworkEndian};
+
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::address;
+use crate::dissect::{Dissect, Dissector};
+use crate::uint;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const MAX_PACKET_FILTERS: usize = 15;
+
+// Parameter identifiers
+pub const AUTHORIZATION_TOKEN: u8 = 0x01;
+pub const FLOW_IDENTIFIER: u8 = 0x02;
+pub const PACKET_FILTER_IDENTIFIER: u8 = 0x03;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Direction {
+    PreRel7, // TFTs created before Rel-7 didn't have a direction
+    Downlink,
+    Uplink,
+    Bidirectional,
+}
+
+impl From<u8> for Direction {
+    fn from(value: u8) -> Self {
+        match value & 0b11 {
+            0 => Direction::PreRel7,
+            1 => Direction::Downlink,
+            2 => Direction::Uplink,
+            _ => Direction::Bidirectional,
+        }
+    }
+}
+
+impl From<Direction> for u8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::PreRel7 => 0,
+            Direction::Downlink => 1,
+            Direction::Uplink => 2,
+            Direction::Bidirectional => 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Component {
+    Ipv4RemoteAddress(Ipv4Addr, Ipv4Addr), // Address and mask
+    Ipv4LocalAddress(Ipv4Addr, Ipv4Addr),
+    Ipv6RemoteAddress(Ipv6Addr, Ipv6Addr),
+    Ipv6RemoteAddressPrefix(Ipv6Addr, u8), // Address and prefix length
+    Ipv6LocalAddressPrefix(Ipv6Addr, u8),
+    ProtocolIdentifier(u8), // Protocol (IPv4) or Next Header (IPv6)
+    SingleLocalPort(u16),
+    LocalPortRange(u16, u16),
+    SingleRemotePort(u16),
+    RemotePortRange(u16, u16),
+    SecurityParameterIndex(u32),
+    TypeOfService(u8, u8), // Type of Service (IPv4) or Traffic Class (IPv6) and mask
+    FlowLabel(u32), // 20 bits
+    DestinationMacAddress([u8; 6]),
+    SourceMacAddress([u8; 6]),
+    CTagVid(u16), // 12 bits
+    STagVid(u16),
+    CTagPcpDei(u8), // 4 bits
+    STagPcpDei(u8),
+    Ethertype(u16),
+}
+
+impl Component {
+    fn type_identifier(&self) -> u8 {
+        match self {
+            Component::Ipv4RemoteAddress(_, _) => 0x10,
+            Component::Ipv4LocalAddress(_, _) => 0x11,
+            Component::Ipv6RemoteAddress(_, _) => 0x20,
+            Component::Ipv6RemoteAddressPrefix(_, _) => 0x21,
+            Component::Ipv6LocalAddressPrefix(_, _) => 0x23,
+            Component::ProtocolIdentifier(_) => 0x30,
+            Component::SingleLocalPort(_) => 0x40,
+            Component::LocalPortRange(_, _) => 0x41,
+            Component::SingleRemotePort(_) => 0x50,
+            Component::RemotePortRange(_, _) => 0x51,
+            Component::SecurityParameterIndex(_) => 0x60,
+            Component::TypeOfService(_, _) => 0x70,
+            Component::FlowLabel(_) => 0x80,
+            Component::DestinationMacAddress(_) => 0x81,
+            Component::SourceMacAddress(_) => 0x82,
+            Component::CTagVid(_) => 0x83,
+            Component::STagVid(_) => 0x84,
+            Component::CTagPcpDei(_) => 0x85,
+            Component::STagPcpDei(_) => 0x86,
+            Component::Ethertype(_) => 0x87,
+        }
+    }
+
+    fn value_length(type_identifier: u8) -> Option<usize> {
+        match type_identifier {
+            0x10 | 0x11 => Some(8),
+            0x20 => Some(32),
+            0x21 | 0x23 => Some(17),
+            0x30 | 0x85 | 0x86 => Some(1),
+            0x40 | 0x50 | 0x83 | 0x84 | 0x87 => Some(2),
+            0x41 | 0x51 | 0x60 => Some(4),
+            0x70 => Some(2),
+            0x80 => Some(3),
+            0x81 | 0x82 => Some(6),
+            _ => None,
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        // Including the type identifier
+        1 + Component::value_length(self.type_identifier()).unwrap()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        // An unknown component can't be skipped as its length isn't coded
+        let type_identifier = *buffer.first()?;
+        let length = Component::value_length(type_identifier)?;
+
+        let v = buffer.get(1..1+length)?;
+
+        let component = match type_identifier {
+            0x10 => Component::Ipv4RemoteAddress(address::parse_ipv4(v)?, address::parse_ipv4(&v[4..])?),
+            0x11 => Component::Ipv4LocalAddress(address::parse_ipv4(v)?, address::parse_ipv4(&v[4..])?),
+            0x20 => Component::Ipv6RemoteAddress(address::parse_ipv6(v)?, address::parse_ipv6(&v[16..])?),
+            0x21 => Component::Ipv6RemoteAddressPrefix(address::parse_ipv6(v)?, v[16]),
+            0x23 => Component::Ipv6LocalAddressPrefix(address::parse_ipv6(v)?, v[16]),
+            0x30 => Component::ProtocolIdentifier(v[0]),
+            0x40 => Component::SingleLocalPort(NetworkEndian::read_u16(v)),
+            0x41 => Component::LocalPortRange(NetworkEndian::read_u16(v), NetworkEndian::read_u16(&v[2..])),
+            0x50 => Component::SingleRemotePort(NetworkEndian::read_u16(v)),
+            0x51 => Component::RemotePortRange(NetworkEndian::read_u16(v), NetworkEndian::read_u16(&v[2..])),
+            0x60 => Component::SecurityParameterIndex(NetworkEndian::read_u32(v)),
+            0x70 => Component::TypeOfService(v[0], v[1]),
+            0x80 => Component::FlowLabel(uint::parse_u24(v)? & 0xFFFFF),
+            0x81 => Component::DestinationMacAddress([v[0], v[1], v[2], v[3], v[4], v[5]]),
+            0x82 => Component::SourceMacAddress([v[0], v[1], v[2], v[3], v[4], v[5]]),
+            0x83 => Component::CTagVid(NetworkEndian::read_u16(v) & 0xFFF),
+            0x84 => Component::STagVid(NetworkEndian::read_u16(v) & 0xFFF),
+            0x85 => Component::CTagPcpDei(v[0] & 0xF),
+            0x86 => Component::STagPcpDei(v[0] & 0xF),
+            _ => Component::Ethertype(NetworkEndian::read_u16(v)),
+        };
+
+        Some((component, 1 + length))
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        buffer[0] = self.type_identifier();
+
+        let v = &mut buffer[1..];
+
+        match *self {
+            Component::Ipv4RemoteAddress(a, m) | Component::Ipv4LocalAddress(a, m) => {
+                address::generate_ipv4(a, v);
+                address::generate_ipv4(m, &mut v[4..]);
+            },
+            Component::Ipv6RemoteAddress(a, m) => {
+                address::generate_ipv6(a, v);
+                address::generate_ipv6(m, &mut v[16..]);
+            },
+            Component::Ipv6RemoteAddressPrefix(a, prefix_length) | Component::Ipv6LocalAddressPrefix(a, prefix_length) => {
+                address::generate_ipv6(a, v);
+                v[16] = prefix_length;
+            },
+            Component::ProtocolIdentifier(p) => v[0] = p,
+            Component::SingleLocalPort(p) | Component::SingleRemotePort(p) | Component::Ethertype(p) => NetworkEndian::write_u16(v, p),
+            Component::LocalPortRange(low, high) | Component::RemotePortRange(low, high) => {
+                NetworkEndian::write_u16(v, low);
+                NetworkEndian::write_u16(&mut v[2..], high);
+            },
+            Component::SecurityParameterIndex(spi) => NetworkEndian::write_u32(v, spi),
+            Component::TypeOfService(tos, mask) => {
+                v[0] = tos;
+                v[1] = mask;
+            },
+            Component::FlowLabel(label) => { uint::generate_u24_lenient(label & 0xFFFFF, v); },
+            Component::DestinationMacAddress(mac) | Component::SourceMacAddress(mac) => v[..6].copy_from_slice(&mac),
+            Component::CTagVid(vid) | Component::STagVid(vid) => NetworkEndian::write_u16(v, vid & 0xFFF),
+            Component::CTagPcpDei(p) | Component::STagPcpDei(p) => v[0] = p & 0xF,
+        }
+
+        self.length()
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Component::Ipv4RemoteAddress(a, m) => write!(f, "IPv4 remote address {}/{}", a, m),
+            Component::Ipv4LocalAddress(a, m) => write!(f, "IPv4 local address {}/{}", a, m),
+            Component::Ipv6RemoteAddress(a, m) => write!(f, "IPv6 remote address {}/{}", a, m),
+            Component::Ipv6RemoteAddressPrefix(a, l) => write!(f, "IPv6 remote address {}/{}", a, l),
+            Component::Ipv6LocalAddressPrefix(a, l) => write!(f, "IPv6 local address {}/{}", a, l),
+            Component::ProtocolIdentifier(p) => write!(f, "Protocol identifier {}", p),
+            Component::SingleLocalPort(p) => write!(f, "Local port {}", p),
+            Component::LocalPortRange(low, high) => write!(f, "Local ports {}-{}", low, high),
+            Component::SingleRemotePort(p) => write!(f, "Remote port {}", p),
+            Component::RemotePortRange(low, high) => write!(f, "Remote ports {}-{}", low, high),
+            Component::SecurityParameterIndex(spi) => write!(f, "Security parameter index {:#010x}", spi),
+            Component::TypeOfService(tos, mask) => write!(f, "Type of service {:#04x}/{:#04x}", tos, mask),
+            Component::FlowLabel(label) => write!(f, "Flow label {:#07x}", label),
+            Component::DestinationMacAddress(mac) => write!(f, "Destination MAC address {}", crate::dissect::hex_string(mac)),
+            Component::SourceMacAddress(mac) => write!(f, "Source MAC address {}", crate::dissect::hex_string(mac)),
+            Component::CTagVid(vid) => write!(f, "C-TAG VID {}", vid),
+            Component::STagVid(vid) => write!(f, "S-TAG VID {}", vid),
+            Component::CTagPcpDei(p) => write!(f, "C-TAG PCP/DEI {:#03x}", p),
+            Component::STagPcpDei(p) => write!(f, "S-TAG PCP/DEI {:#03x}", p),
+            Component::Ethertype(e) => write!(f, "Ethertype {:#06x}", e),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct PacketFilter {
+    pub identifier: u8, // 4 bits
+    pub direction: Direction,
+    pub precedence: u8, // Filters with a lower value are evaluated first
+    pub components: Vec<Component>,
+}
+
+impl PacketFilter {
+    pub fn new(identifier: u8, direction: Direction, precedence: u8, components: Vec<Component>) -> Result<Self, String> {
+        if identifier > 0xF {
+            return Err(format!("Packet filter identifier is > 0xF {}", identifier));
+        }
+
+        let f = PacketFilter {
+            identifier,
+            direction,
+            precedence,
+            components,
+        };
+
+        if f.contents_length() > 0xFF {
+            return Err(format!("Packet filter contents are too long {}", f.contents_length()));
+        }
+
+        Ok(f)
+    }
+
+    fn contents_length(&self) -> usize {
+        self.components.iter().map(|c| c.length()).sum()
+    }
+
+    pub fn length(&self) -> usize {
+        3 + self.contents_length()
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<(Self, usize)> {
+        if buffer.len() < 3 {
+            return None
+        }
+
+        let identifier = buffer[0] & 0xF;
+        let direction = Direction::from(buffer[0] >> 4);
+        let precedence = buffer[1];
+        let length = buffer[2] as usize;
+
+        let contents = buffer.get(3..3+length)?;
+
+        let mut components = Vec::new();
+        let mut pos = 0;
+
+        while pos < contents.len() {
+            let (c, c_pos) = Component::parse(&contents[pos..])?;
+            components.push(c);
+            pos = pos + c_pos;
+        }
+
+        Some((PacketFilter { identifier, direction, precedence, components }, 3 + length))
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        buffer[0] = (u8::from(self.direction) << 4) | (self.identifier & 0xF);
+        buffer[1] = self.precedence;
+        buffer[2] = self.contents_length() as u8;
+
+        let mut pos = 3;
+
+        for c in &self.components {
+            pos = pos + c.generate(&mut buffer[pos..]);
+        }
+
+        pos
+    }
+}
+
+impl Dissect for PacketFilter {
+    fn dissect(&self, d: &mut Dissector) {
+        d.tree(&format!("Packet Filter {}", self.identifier), |d| {
+            d.field("Direction", format!("{:?}", self.direction));
+            d.field("Evaluation Precedence", self.precedence);
+
+            for c in &self.components {
+                d.field("Component", c);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub enum Operation {
+    CreateNew(Vec<PacketFilter>),
+    DeleteExisting,
+    AddFilters(Vec<PacketFilter>),
+    ReplaceFilters(Vec<PacketFilter>),
+    DeleteFilters(Vec<u8>), // Packet filter identifiers
+    NoOperation, // Only the parameters are used e.g. to carry an Authorization Token
+}
+
+impl Operation {
+    pub fn code(&self) -> u8 {
+        match self {
+            Operation::CreateNew(_) => 1,
+            Operation::DeleteExisting => 2,
+            Operation::AddFilters(_) => 3,
+            Operation::ReplaceFilters(_) => 4,
+            Operation::DeleteFilters(_) => 5,
+            Operation::NoOperation => 6,
+        }
+    }
+
+    fn number_of_packet_filters(&self) -> usize {
+        match self {
+            Operation::CreateNew(f) | Operation::AddFilters(f) | Operation::ReplaceFilters(f) => f.len(),
+            Operation::DeleteFilters(ids) => ids.len(),
+            Operation::DeleteExisting | Operation::NoOperation => 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct Parameter {
+    pub id: u8,
+    pub contents: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct TrafficFlowTemplate {
+    pub operation: Operation,
+    pub parameters: Vec<Parameter>,
+}
+
+impl TrafficFlowTemplate {
+    pub fn new(operation: Operation) -> Result<Self, String> {
+        let n = operation.number_of_packet_filters();
+
+        if n > MAX_PACKET_FILTERS {
+            return Err(format!("Too many packet filters {}", n));
+        }
+
+        match operation {
+            Operation::CreateNew(ref f) | Operation::AddFilters(ref f) | Operation::ReplaceFilters(ref f) if f.is_empty() => {
+                Err(format!("TFT operation {} needs at least one packet filter", operation.code()))
+            },
+            Operation::DeleteFilters(ref ids) if ids.is_empty() || ids.iter().any(|id| *id > 0xF) => {
+                Err(format!("Invalid packet filter identifiers to delete {:?}", ids))
+            },
+            _ => Ok(TrafficFlowTemplate { operation, parameters: Vec::new() }),
+        }
+    }
+
+    pub fn create(filters: Vec<PacketFilter>) -> Result<Self, String> {
+        TrafficFlowTemplate::new(Operation::CreateNew(filters))
+    }
+
+    pub fn add(filters: Vec<PacketFilter>) -> Result<Self, String> {
+        TrafficFlowTemplate::new(Operation::AddFilters(filters))
+    }
+
+    pub fn replace(filters: Vec<PacketFilter>) -> Result<Self, String> {
+        TrafficFlowTemplate::new(Operation::ReplaceFilters(filters))
+    }
+
+    pub fn delete(identifiers: Vec<u8>) -> Result<Self, String> {
+        TrafficFlowTemplate::new(Operation::DeleteFilters(identifiers))
+    }
+
+    pub fn push_parameter(&mut self, id: u8, contents: &[u8]) -> Result<(), String> {
+        if contents.len() > 0xFF {
+            return Err(format!("Contents of parameter {:#04x} are too long {}", id, contents.len()));
+        }
+
+        self.parameters.push(Parameter { id, contents: contents.to_vec() });
+
+        Ok(())
+    }
+
+    pub fn packet_filters(&self) -> &[PacketFilter] {
+        match self.operation {
+            Operation::CreateNew(ref f) | Operation::AddFilters(ref f) | Operation::ReplaceFilters(ref f) => f,
+            _ => &[],
+        }
+    }
+
+    pub fn length(&self) -> usize {
+        let mut length = 1;
+
+        length = length + match self.operation {
+            Operation::DeleteFilters(ref ids) => ids.len(),
+            _ => self.packet_filters().iter().map(|f| f.length()).sum(),
+        };
+
+        for p in &self.parameters {
+            length = length + 2 + p.contents.len();
+        }
+
+        length
+    }
+
+    pub fn parse(buffer: &[u8]) -> Option<Self> {
+        // The TFT takes up the whole of buffer
+        if buffer.is_empty() {
+            return None
+        }
+
+        let code = buffer[0] >> 5;
+        let e = buffer[0] & 0x10 != 0;
+        let n = (buffer[0] & 0xF) as usize;
+        let mut pos = 1;
+
+        let mut filters = Vec::new();
+
+        match code {
+            1 | 3 | 4 => {
+                for _ in 0..n {
+                    let (f, f_pos) = PacketFilter::parse(&buffer[pos..])?;
+                    filters.push(f);
+                    pos = pos + f_pos;
+                }
+            },
+            5 if buffer.len() < pos + n => return None,
+            _ => (),
+        }
+
+        let operation = match code {
+            1 => Operation::CreateNew(filters),
+            2 => Operation::DeleteExisting,
+            3 => Operation::AddFilters(filters),
+            4 => Operation::ReplaceFilters(filters),
+            5 => {
+                let ids = buffer[pos..pos+n].iter().map(|id| id & 0xF).collect();
+                pos = pos + n;
+                Operation::DeleteFilters(ids)
+            },
+            6 => Operation::NoOperation,
+            _ => return None, // Spare and reserved codes are a semantic error in the TFT operation
+        };
+
+        let mut parameters = Vec::new();
+
+        if e {
+            while pos < buffer.len() {
+                if buffer.len() < pos + 2 {
+                    return None
+                }
+
+                let id = buffer[pos];
+                let length = buffer[pos+1] as usize;
+                pos = pos + 2;
+
+                if buffer.len() < pos + length {
+                    return None
+                }
+
+                parameters.push(Parameter { id, contents: buffer[pos..pos+length].to_vec() });
+                pos = pos + length;
+            }
+        }
+
+        Some(TrafficFlowTemplate { operation, parameters })
+    }
+
+    pub fn generate(&self, buffer: &mut[u8]) -> usize {
+        let mut pos = 0;
+
+        let e = if self.parameters.is_empty() { 0 } else { 0x10 };
+        buffer[pos] = (self.operation.code() << 5) | e | (self.operation.number_of_packet_filters() as u8 & 0xF);
+        pos = pos + 1;
+
+        match self.operation {
+            Operation::DeleteFilters(ref ids) => {
+                for id in ids {
+                    buffer[pos] = id & 0xF;
+                    pos = pos + 1;
+                }
+            },
+            _ => {
+                for f in self.packet_filters() {
+                    pos = pos + f.generate(&mut buffer[pos..]);
+                }
+            },
+        }
+
+        for p in &self.parameters {
+            buffer[pos] = p.id;
+            buffer[pos+1] = p.contents.len() as u8;
+            pos = pos + 2;
+
+            buffer[pos..pos+p.contents.len()].copy_from_slice(&p.contents);
+            pos = pos + p.contents.len();
+        }
+
+        pos
+    }
+}
+
+impl Dissect for TrafficFlowTemplate {
+    fn dissect(&self, d: &mut Dissector) {
+        let operation = match self.operation {
+            Operation::CreateNew(_) => "Create new TFT",
+            Operation::DeleteExisting => "Delete existing TFT",
+            Operation::AddFilters(_) => "Add packet filters to existing TFT",
+            Operation::ReplaceFilters(_) => "Replace packet filters in existing TFT",
+            Operation::DeleteFilters(_) => "Delete packet filters from existing TFT",
+            Operation::NoOperation => "No TFT operation",
+        };
+
+        d.field("TFT Operation", operation);
+
+        if let Operation::DeleteFilters(ref ids) = self.operation {
+            d.field("Packet Filter Identifiers", format!("{:?}", ids));
+        }
+
+        for f in self.packet_filters() {
+            f.dissect(d);
+        }
+
+        for p in &self.parameters {
+            d.hex(&format!("Parameter {:#04x}", p.id), &p.contents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::vec;
+
+    // Create new TFT with one packet filter matching UDP (17) to or from 10.0.0.0/8 port 5060
+    const TFT: [u8; 20] = [
+        0x21, // Create new TFT and 1 packet filter
+        0x31, 0x10, 0x0E, // Bidirectional packet filter 1, precedence 16 and 14 octets of contents
+        0x10, 10, 0, 0, 0, 255, 0, 0, 0, // IPv4 remote address
+        0x30, 0x11, // Protocol identifier
+        0x50, 0x13, 0xC4, // Single remote port
+        0x00, 0x00, // This is cut off by the tests where needed
+    ];
+
+    fn packet_filter() -> PacketFilter {
+        PacketFilter::new(1, Direction::Bidirectional, 16, vec![
+            Component::Ipv4RemoteAddress(Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(255, 0, 0, 0)),
+            Component::ProtocolIdentifier(17),
+            Component::SingleRemotePort(5060),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut buffer = [0; 64];
+
+        let tft = TrafficFlowTemplate::create(vec![packet_filter()]).unwrap();
+
+        let pos = tft.generate(&mut buffer);
+
+        assert_eq!(buffer[..pos], TFT[..18]);
+        assert_eq!(tft.length(), 18);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(TrafficFlowTemplate::parse(&TFT[..18]), Some(TrafficFlowTemplate::create(vec![packet_filter()]).unwrap()));
+
+        // Truncated packet filter and component
+        assert!(TrafficFlowTemplate::parse(&TFT[..17]).is_none());
+        assert!(TrafficFlowTemplate::parse(&TFT[..3]).is_none());
+        assert!(TrafficFlowTemplate::parse(&[]).is_none());
+
+        // Reserved operation code
+        assert!(TrafficFlowTemplate::parse(&[0xE0]).is_none());
+    }
+
+    #[test]
+    fn test_operations() {
+        let mut buffer = [0; 256];
+
+        let ipv6 = PacketFilter::new(2, Direction::Uplink, 0, vec![
+            Component::Ipv6RemoteAddressPrefix(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32),
+            Component::LocalPortRange(1000, 2000),
+            Component::TypeOfService(0xB8, 0xFC),
+            Component::FlowLabel(0xABCDE),
+        ]).unwrap();
+
+        let mut no_operation = TrafficFlowTemplate::new(Operation::NoOperation).unwrap();
+        no_operation.push_parameter(AUTHORIZATION_TOKEN, &[1, 2, 3]).unwrap();
+
+        let tfts = [
+            TrafficFlowTemplate::create(vec![packet_filter(), ipv6.clone()]).unwrap(),
+            TrafficFlowTemplate::add(vec![ipv6.clone()]).unwrap(),
+            TrafficFlowTemplate::replace(vec![packet_filter()]).unwrap(),
+            TrafficFlowTemplate::delete(vec![1, 2]).unwrap(),
+            TrafficFlowTemplate::new(Operation::DeleteExisting).unwrap(),
+            no_operation,
+        ];
+
+        for tft in tfts.iter() {
+            let pos = tft.generate(&mut buffer);
+
+            assert_eq!(pos, tft.length());
+            assert_eq!(TrafficFlowTemplate::parse(&buffer[..pos]).as_ref(), Some(tft));
+        }
+
+        assert_eq!(tfts[3].generate(&mut buffer), 3);
+        assert_eq!(buffer[..3], [0xA2, 0x01, 0x02]);
+
+        assert_eq!(tfts[5].generate(&mut buffer), 6);
+        assert_eq!(buffer[..6], [0xD0, AUTHORIZATION_TOKEN, 3, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_new_invalid() {
+        assert!(TrafficFlowTemplate::create(vec![]).is_err());
+        assert!(TrafficFlowTemplate::add(vec![packet_filter(); 16]).is_err());
+        assert!(TrafficFlowTemplate::delete(vec![]).is_err());
+        assert!(TrafficFlowTemplate::delete(vec![0x10]).is_err());
+
+        assert!(PacketFilter::new(0x10, Direction::Uplink, 0, vec![]).is_err());
+        assert!(PacketFilter::new(1, Direction::Uplink, 0, vec![Component::Ipv6RemoteAddress(Ipv6Addr::LOCALHOST, Ipv6Addr::LOCALHOST); 8]).is_err());
+    }
+
+    #[test]
+    fn test_component_unknown() {
+        // An unknown component type can't be skipped
+        assert!(Component::parse(&[0x99, 0x00]).is_none());
+        assert!(PacketFilter::parse(&[0x31, 0x10, 0x02, 0x99, 0x00]).is_none());
+    }
+
+    #[test]
+    fn test_packet_filters() {
+        let tft = TrafficFlowTemplate::create(vec![packet_filter()]).unwrap();
+        assert_eq!(tft.packet_filters(), [packet_filter()]);
+
+        let tft = TrafficFlowTemplate::delete(vec![1]).unwrap();
+        assert!(tft.packet_filters().is_empty());
+    }
+}
@@ -0,0 +1,479 @@
+/* A hierarchical timer wheel for retransmission timers (e.g. T3-RESPONSE, TS 29.274 7.6) and session idle timeouts.
+
+Time is a count of milliseconds from any epoch the caller chooses, e.g. the milliseconds since the node started, so
+the wheel doesn't need std. Timers are inserted with the time they expire and advance() returns the ones that have
+expired by the given time.
+
+There are 6 levels of 64 slots. A slot in level 0 is 1 ms, a slot in level 1 is 64 ms and so on, so the wheel covers
+2^36 ms (about 795 days). Timers further out than that are kept in the last level and moved down as time passes.
+A timer is put in the lowest level whose slots still separate it from the current time, and is moved (cascaded) to a
+lower level when its slot is reached. Each slot is a doubly linked list of timers so that inserting and cancelling a
+timer are O(1).
+
+    let mut wheel = TimerWheel::new(0);
+
+    let t3 = wheel.insert(3000, (peer, sequence_number));
+    ...
+    wheel.cancel(t3); // The response arrived
+
+    for (id, (peer, sequence_number)) in wheel.advance(now) {
+        // Retransmit
+    }
+*/
+
+use alloc::vec::Vec;
+
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 6;
+
+// About how far ahead the wheel covers. Timers with a later deadline are placed in the top level and moved as it wraps
+pub const MAX_DELAY: u64 = (1 << (SLOT_BITS as usize * LEVELS)) - 1;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TimerId {
+    index: usize,
+    generation: u32, // Stops an id from cancelling a later timer that reuses its entry
+}
+
+struct Timer<T> {
+    when: u64,
+    value: T,
+    level: usize,
+    slot: usize,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+enum Entry<T> {
+    Occupied(Timer<T>, u32),
+    Vacant(Option<usize>, u32), // The next vacant entry
+}
+
+#[derive(Copy, Clone, Default)]
+struct Slot {
+    head: Option<usize>,
+    tail: Option<usize>,
+}
+
+#[derive(Copy, Clone)]
+struct Level {
+    occupied: u64, // Bit n is set if slot n has any timers
+    slots: [Slot; SLOTS],
+}
+
+pub struct TimerWheel<T> {
+    elapsed: u64, // The time the wheel has been advanced to
+    levels: [Level; LEVELS],
+    entries: Vec<Entry<T>>,
+    vacant: Option<usize>,
+    len: usize,
+}
+
+impl<T> TimerWheel<T> {
+    pub fn new(now: u64) -> Self {
+        TimerWheel {
+            elapsed: now,
+            levels: [Level { occupied: 0, slots: [Slot::default(); SLOTS] }; LEVELS],
+            entries: Vec::new(),
+            vacant: None,
+            len: 0,
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        // The time the wheel was last advanced to
+        self.elapsed
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, when: u64, value: T) -> TimerId {
+        // A timer that has already expired is returned by the next call to advance()
+        let timer = Timer {
+            when,
+            value,
+            level: 0,
+            slot: 0,
+            prev: None,
+            next: None,
+        };
+
+        let id = match self.vacant {
+            Some(index) => {
+                let generation = match self.entries[index] {
+                    Entry::Vacant(next, generation) => {
+                        self.vacant = next;
+                        generation
+                    },
+                    // The vacant list only holds vacant entries
+                    Entry::Occupied(_, generation) => generation,
+                };
+
+                self.entries[index] = Entry::Occupied(timer, generation);
+
+                TimerId { index, generation }
+            },
+            None => {
+                self.entries.push(Entry::Occupied(timer, 0));
+
+                TimerId { index: self.entries.len() - 1, generation: 0 }
+            }
+        };
+
+        self.len = self.len + 1;
+        self.link(id.index);
+
+        id
+    }
+
+    pub fn insert_after(&mut self, delay: u64, value: T) -> TimerId {
+        let when = self.elapsed.saturating_add(delay);
+        self.insert(when, value)
+    }
+
+    pub fn cancel(&mut self, id: TimerId) -> Option<T> {
+        // Returns the value of the timer if it hadn't expired or already been cancelled
+        self.deadline(id)?;
+
+        self.unlink(id.index);
+        self.len = self.len - 1;
+
+        let entry = core::mem::replace(&mut self.entries[id.index], Entry::Vacant(self.vacant, id.generation.wrapping_add(1)));
+        self.vacant = Some(id.index);
+
+        match entry {
+            Entry::Occupied(timer, _) => Some(timer.value),
+            Entry::Vacant(_, _) => None,
+        }
+    }
+
+    pub fn deadline(&self, id: TimerId) -> Option<u64> {
+        // When the timer expires or None if it has expired or been cancelled
+        match self.entries.get(id.index) {
+            Some(Entry::Occupied(timer, generation)) if *generation == id.generation => Some(timer.when),
+            _ => None,
+        }
+    }
+
+    pub fn next_deadline(&self) -> Option<u64> {
+        /* The time advance() next needs to be called. This is the start of the earliest slot with a timer so it is no
+        later than the earliest deadline. It is only exact for timers less than 64 ms away e.g. for a poll timeout */
+        (0..LEVELS).find_map(|level| self.next_slot(level).map(|(_slot, start)| start))
+    }
+
+    pub fn advance(&mut self, now: u64) -> Vec<(TimerId, T)> {
+        /* Moves the wheel to now and returns the timers that have expired, in the order of their deadlines. Timers with
+        the same deadline are returned in the order they were inserted */
+        let mut expired = Vec::new();
+
+        while let Some((level, slot, start)) = (0..LEVELS).find_map(|level| self.next_slot(level).map(|(slot, start)| (level, slot, start))) {
+            if start > now {
+                break
+            }
+
+            self.elapsed = self.elapsed.max(start);
+
+            // Timers in the slot have either expired or are moved to a lower level
+            let mut next = self.levels[level].slots[slot].head;
+
+            self.levels[level].slots[slot] = Slot::default();
+            self.levels[level].occupied = self.levels[level].occupied & !(1 << slot);
+
+            while let Some(index) = next {
+                let when = match self.entries[index] {
+                    Entry::Occupied(ref mut timer, _) => {
+                        next = timer.next;
+                        timer.prev = None;
+                        timer.next = None;
+                        timer.when
+                    },
+                    Entry::Vacant(_, _) => break,
+                };
+
+                if when <= self.elapsed {
+                    let generation = match self.entries[index] {
+                        Entry::Occupied(_, generation) => generation,
+                        Entry::Vacant(_, generation) => generation,
+                    };
+
+                    let entry = core::mem::replace(&mut self.entries[index], Entry::Vacant(self.vacant, generation.wrapping_add(1)));
+                    self.vacant = Some(index);
+                    self.len = self.len - 1;
+
+                    if let Entry::Occupied(timer, _) = entry {
+                        expired.push((when, TimerId { index, generation }, timer.value));
+                    }
+                }
+                else {
+                    self.link(index);
+                }
+            }
+        }
+
+        self.elapsed = self.elapsed.max(now);
+
+        // Timers inserted after their deadline share the current slot in the order they were inserted. The sort is stable
+        expired.sort_by_key(|(when, _, _)| *when);
+
+        expired.into_iter().map(|(_, id, value)| (id, value)).collect()
+    }
+
+    fn next_slot(&self, level: usize) -> Option<(usize, u64)> {
+        // The first slot of level at or after the current time that has timers, and the time the slot starts
+        let slot_range = 1u64 << (SLOT_BITS as usize * level);
+        let level_range = slot_range << SLOT_BITS;
+
+        let current = ((self.elapsed / slot_range) % SLOTS as u64) as usize;
+        let occupied = self.levels[level].occupied;
+
+        if occupied == 0 {
+            return None
+        }
+
+        // Only the top level wraps around. The lower levels have no timers before the current slot
+        let slot = (current + occupied.rotate_right(current as u32).trailing_zeros() as usize) % SLOTS;
+        let mut start = (self.elapsed - self.elapsed % level_range) + slot as u64 * slot_range;
+
+        if slot < current {
+            start = start + level_range;
+        }
+
+        // The slot holding an expired timer started before the current time
+        Some((slot, start.max(self.elapsed)))
+    }
+
+    fn link(&mut self, index: usize) {
+        // Adds the timer to the end of the slot for its deadline
+        let when = match self.entries[index] {
+            Entry::Occupied(ref timer, _) => timer.when,
+            Entry::Vacant(_, _) => return,
+        };
+
+        /* Timers past the top level are placed in the slot before the current one, which the top level reaches last
+        as it wraps around */
+        let top_slot_range = 1u64 << (SLOT_BITS as usize * (LEVELS - 1));
+        let limit = (self.elapsed - self.elapsed % top_slot_range).saturating_add(MAX_DELAY);
+        let placement = when.max(self.elapsed).min(limit);
+
+        // The level is set by the highest bit that differs between the current time and the deadline
+        let significant = 63 - ((self.elapsed ^ placement) | (SLOTS as u64 - 1)).leading_zeros();
+        let level = ((significant / SLOT_BITS) as usize).min(LEVELS - 1);
+        let slot = ((placement >> (SLOT_BITS as usize * level)) % SLOTS as u64) as usize;
+
+        let tail = self.levels[level].slots[slot].tail;
+
+        if let Entry::Occupied(ref mut timer, _) = self.entries[index] {
+            timer.level = level;
+            timer.slot = slot;
+            timer.prev = tail;
+            timer.next = None;
+        }
+
+        match tail {
+            Some(tail) => {
+                if let Entry::Occupied(ref mut timer, _) = self.entries[tail] {
+                    timer.next = Some(index);
+                }
+            },
+            None => self.levels[level].slots[slot].head = Some(index),
+        }
+
+        self.levels[level].slots[slot].tail = Some(index);
+        self.levels[level].occupied = self.levels[level].occupied | (1 << slot);
+    }
+
+    fn unlink(&mut self, index: usize) {
+        let (level, slot, prev, next) = match self.entries[index] {
+            Entry::Occupied(ref timer, _) => (timer.level, timer.slot, timer.prev, timer.next),
+            Entry::Vacant(_, _) => return,
+        };
+
+        match prev {
+            Some(prev) => {
+                if let Entry::Occupied(ref mut timer, _) = self.entries[prev] {
+                    timer.next = next;
+                }
+            },
+            None => self.levels[level].slots[slot].head = next,
+        }
+
+        match next {
+            Some(next) => {
+                if let Entry::Occupied(ref mut timer, _) = self.entries[next] {
+                    timer.prev = prev;
+                }
+            },
+            None => self.levels[level].slots[slot].tail = prev,
+        }
+
+        if self.levels[level].slots[slot].head.is_none() {
+            self.levels[level].occupied = self.levels[level].occupied & !(1 << slot);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_advance() {
+        let mut wheel = TimerWheel::new(1000);
+
+        let a = wheel.insert(1005, "a");
+        let b = wheel.insert_after(3000, "b");
+
+        assert_eq!(wheel.len(), 2);
+        assert_eq!(wheel.deadline(a), Some(1005));
+        assert_eq!(wheel.deadline(b), Some(4000));
+        assert_eq!(wheel.next_deadline(), Some(1005));
+
+        assert!(wheel.advance(1004).is_empty());
+        assert_eq!(wheel.now(), 1004);
+
+        assert_eq!(wheel.advance(1005), vec![(a, "a")]);
+        assert_eq!(wheel.deadline(a), None);
+
+        // b is in a higher level so the next deadline is the start of its slot
+        assert!(wheel.next_deadline().unwrap() <= 4000);
+
+        assert!(wheel.advance(3999).is_empty());
+        assert_eq!(wheel.next_deadline(), Some(4000));
+        assert_eq!(wheel.advance(10000), vec![(b, "b")]);
+
+        assert!(wheel.is_empty());
+        assert_eq!(wheel.next_deadline(), None);
+        assert_eq!(wheel.now(), 10000);
+    }
+
+    #[test]
+    fn test_expired() {
+        let mut wheel = TimerWheel::new(1000);
+
+        // Timers that have already expired are returned by the next advance
+        let a = wheel.insert(10, "a");
+        let b = wheel.insert(1000, "b");
+
+        assert_eq!(wheel.next_deadline(), Some(1000));
+        assert_eq!(wheel.advance(1000), vec![(a, "a"), (b, "b")]);
+    }
+
+    #[test]
+    fn test_expired_order() {
+        let mut wheel = TimerWheel::new(891644);
+
+        // Inserted after their deadlines have passed and out of order
+        let a = wheel.insert(891615, "a");
+        let b = wheel.insert(891613, "b");
+        let c = wheel.insert(891613, "c");
+        let d = wheel.insert(891650, "d");
+
+        assert_eq!(wheel.advance(891644), vec![(b, "b"), (c, "c"), (a, "a")]);
+        assert_eq!(wheel.advance(891650), vec![(d, "d")]);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut wheel = TimerWheel::new(0);
+
+        let a = wheel.insert(100, 'a');
+        let b = wheel.insert(100, 'b');
+        let c = wheel.insert(100, 'c');
+
+        assert_eq!(wheel.cancel(b), Some('b'));
+        assert_eq!(wheel.cancel(b), None);
+        assert_eq!(wheel.len(), 2);
+
+        // The entry of b is reused but its id doesn't cancel the new timer
+        let d = wheel.insert(200, 'd');
+        assert_eq!(wheel.cancel(b), None);
+        assert_eq!(wheel.deadline(d), Some(200));
+
+        assert_eq!(wheel.advance(100), vec![(a, 'a'), (c, 'c')]);
+
+        assert_eq!(wheel.cancel(a), None);
+        assert_eq!(wheel.cancel(d), Some('d'));
+        assert_eq!(wheel.next_deadline(), None);
+        assert!(wheel.advance(1000).is_empty());
+    }
+
+    #[test]
+    fn test_far_future() {
+        let mut wheel = TimerWheel::new(5);
+
+        let a = wheel.insert(MAX_DELAY * 3, "a");
+        let b = wheel.insert(MAX_DELAY + 10, "b");
+
+        // The timers are moved down as each pass of the top level is reached
+        assert!(wheel.next_deadline().unwrap() > 5);
+        assert!(wheel.advance(MAX_DELAY + 9).is_empty());
+        assert_eq!(wheel.advance(MAX_DELAY + 10), vec![(b, "b")]);
+
+        assert!(wheel.advance(MAX_DELAY * 3 - 1).is_empty());
+        assert_eq!(wheel.deadline(a), Some(MAX_DELAY * 3));
+        assert_eq!(wheel.advance(MAX_DELAY * 3), vec![(a, "a")]);
+    }
+
+    #[test]
+    fn test_many() {
+        // Compares the wheel with sorting the deadlines
+        let mut wheel = TimerWheel::new(0);
+        let mut expected = Vec::new();
+
+        let mut seed: u64 = 0x1234_5678;
+
+        for i in 0..5000u64 {
+            // A simple LCG so the test is deterministic
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+
+            let when = (seed >> 33) % 10_000_000;
+            let id = wheel.insert(when, i);
+
+            if i % 7 == 0 {
+                wheel.cancel(id);
+            }
+            else {
+                expected.push((when, i));
+            }
+        }
+
+        expected.sort();
+
+        let mut actual = Vec::new();
+        let mut now = 0;
+
+        while !wheel.is_empty() {
+            // Irregular steps so that some slots are skipped over and others are reached exactly
+            now = now + 1 + (now % 7919);
+
+            for (id, i) in wheel.advance(now) {
+                assert_eq!(wheel.deadline(id), None);
+                actual.push(i);
+            }
+
+            if let Some(deadline) = wheel.next_deadline() {
+                assert!(deadline > now);
+            }
+        }
+
+        // Timers with the same deadline can be returned in any order
+        let mut actual_sorted: Vec<(u64, u64)> = Vec::new();
+
+        for i in actual {
+            let (when, _) = expected.iter().find(|(_, e)| *e == i).unwrap();
+            actual_sorted.push((*when, i));
+        }
+
+        assert!(actual_sorted.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        actual_sorted.sort();
+        assert_eq!(actual_sorted, expected);
+    }
+}
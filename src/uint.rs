@@ -0,0 +1,108 @@
+/* Encoding of the 3 octet (u24) and 5 octet (u40) unsigned integers carried by headers and IEs
+
+Sequence Numbers, NAS COUNTs, Trace IDs, Macro eNB IDs and Long PDCP PDU Numbers are 3 octets and the bitrates of the
+Bearer and Flow QoS are 5 octets. Rather than a slice and a width that have to agree at every call, these functions
+take the width from their name.
+
+parse_u24/parse_u40 return None when the buffer is too short. generate_u24/generate_u40 return None, and write
+nothing, when the buffer is too short or the value doesn't fit in the width.
+
+generate() of a header or IE can't fail, so it uses generate_u24_lenient/generate_u40_lenient. These mask the value to
+the width and, like the rest of generate(), expect buffer to be long enough. new() and the setters have already
+rejected values that don't fit, so only a field set directly (e.g. by the fuzzer) is masked.
+*/
+
+pub const U24_LENGTH: usize = 3;
+pub const U40_LENGTH: usize = 5;
+
+pub const MAX_U24: u32 = 0xFF_FFFF;
+pub const MAX_U40: u64 = 0xFF_FFFF_FFFF;
+
+pub fn parse_u24(buffer: &[u8]) -> Option<u32> {
+    // Reads the first 3 octets of buffer
+    let b = buffer.get(..U24_LENGTH)?;
+    Some(((b[0] as u32) << 16) | ((b[1] as u32) << 8) | b[2] as u32)
+}
+
+pub fn generate_u24(value: u32, buffer: &mut[u8]) -> Option<usize> {
+    if value > MAX_U24 {
+        return None
+    }
+
+    buffer.get_mut(..U24_LENGTH)?.copy_from_slice(&value.to_be_bytes()[1..]);
+    Some(U24_LENGTH)
+}
+
+pub fn generate_u24_lenient(value: u32, buffer: &mut[u8]) -> usize {
+    // Writes the low 3 octets of value
+    buffer[..U24_LENGTH].copy_from_slice(&(value & MAX_U24).to_be_bytes()[1..]);
+    U24_LENGTH
+}
+
+pub fn parse_u40(buffer: &[u8]) -> Option<u64> {
+    // Reads the first 5 octets of buffer
+    let b = buffer.get(..U40_LENGTH)?;
+    Some(b.iter().fold(0, |value, o| (value << 8) | *o as u64))
+}
+
+pub fn generate_u40(value: u64, buffer: &mut[u8]) -> Option<usize> {
+    if value > MAX_U40 {
+        return None
+    }
+
+    buffer.get_mut(..U40_LENGTH)?.copy_from_slice(&value.to_be_bytes()[3..]);
+    Some(U40_LENGTH)
+}
+
+pub fn generate_u40_lenient(value: u64, buffer: &mut[u8]) -> usize {
+    // Writes the low 5 octets of value
+    buffer[..U40_LENGTH].copy_from_slice(&(value & MAX_U40).to_be_bytes()[3..]);
+    U40_LENGTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u24() {
+        let mut buffer = [0xAA; 4];
+
+        assert_eq!(generate_u24(0x123456, &mut buffer), Some(3));
+        assert_eq!(buffer, [0x12, 0x34, 0x56, 0xAA]);
+        assert_eq!(parse_u24(&buffer), Some(0x123456));
+
+        // Values that don't fit and short buffers are rejected without writing anything
+        assert!(generate_u24(MAX_U24 + 1, &mut buffer).is_none());
+        assert!(generate_u24(1, &mut buffer[..2]).is_none());
+        assert_eq!(buffer, [0x12, 0x34, 0x56, 0xAA]);
+
+        // Only the low 3 octets are written
+        assert_eq!(generate_u24_lenient(0xFF000001, &mut buffer), 3);
+        assert_eq!(buffer, [0x00, 0x00, 0x01, 0xAA]);
+
+        assert_eq!(parse_u24(&[0xFF, 0xFF, 0xFF]), Some(MAX_U24));
+        assert!(parse_u24(&[0x12, 0x34]).is_none());
+    }
+
+    #[test]
+    fn test_u40() {
+        let mut buffer = [0xAA; 6];
+
+        assert_eq!(generate_u40(0x12_3456_789A, &mut buffer), Some(5));
+        assert_eq!(buffer, [0x12, 0x34, 0x56, 0x78, 0x9A, 0xAA]);
+        assert_eq!(parse_u40(&buffer), Some(0x12_3456_789A));
+
+        // Values that don't fit and short buffers are rejected without writing anything
+        assert!(generate_u40(MAX_U40 + 1, &mut buffer).is_none());
+        assert!(generate_u40(1, &mut buffer[..4]).is_none());
+        assert_eq!(buffer, [0x12, 0x34, 0x56, 0x78, 0x9A, 0xAA]);
+
+        // Only the low 5 octets are written
+        assert_eq!(generate_u40_lenient(0xFFFF_FF00_0000_0001, &mut buffer), 5);
+        assert_eq!(buffer, [0x00, 0x00, 0x00, 0x00, 0x01, 0xAA]);
+
+        assert_eq!(parse_u40(&[0xFF; 5]), Some(MAX_U40));
+        assert!(parse_u40(&[0x12, 0x34, 0x56, 0x78]).is_none());
+    }
+}